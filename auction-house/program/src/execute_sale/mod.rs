@@ -1018,7 +1018,6 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
     let ata_clone = ata_program.to_account_info();
     let token_clone = token_program.to_account_info();
     let sys_clone = system_program.to_account_info();
-    let rent_clone = rent.to_account_info();
     let treasury_clone = auction_house_treasury.to_account_info();
     let authority_clone = authority.to_account_info();
     let buyer_receipt_clone = buyer_receipt_token_account.to_account_info();
@@ -1204,7 +1203,6 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &ata_clone,
         &token_clone,
         &sys_clone,
-        &rent_clone,
         &signer_seeds_for_royalties,
         fee_payer_seeds,
         price,
@@ -1401,7 +1399,6 @@ fn execute_sale_logic<'c, 'info>(
     let ata_clone = ata_program.to_account_info();
     let token_clone = token_program.to_account_info();
     let sys_clone = system_program.to_account_info();
-    let rent_clone = rent.to_account_info();
     let treasury_clone = auction_house_treasury.to_account_info();
     let authority_clone = authority.to_account_info();
     let buyer_receipt_clone = buyer_receipt_token_account.to_account_info();
@@ -1586,7 +1583,6 @@ fn execute_sale_logic<'c, 'info>(
         &ata_clone,
         &token_clone,
         &sys_clone,
-        &rent_clone,
         &signer_seeds_for_royalties,
         fee_payer_seeds,
         price,
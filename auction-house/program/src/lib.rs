@@ -617,6 +617,15 @@ pub mod auction_house {
         receipt::print_listing_receipt(ctx, receipt_bump)
     }
 
+    /// Create a listing receipt with a snapshot of the token standard, rule set and royalties
+    /// of the asset by creating (or resizing) a `listing_receipt` account.
+    pub fn print_listing_receipt_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintListingReceiptV2<'info>>,
+        receipt_bump: u8,
+    ) -> Result<()> {
+        receipt::print_listing_receipt_v2(ctx, receipt_bump)
+    }
+
     /// Cancel an active listing receipt by setting the `canceled_at` field to the current time.
     pub fn cancel_listing_receipt<'info>(
         ctx: Context<'_, '_, '_, 'info, CancelListingReceipt<'info>>,
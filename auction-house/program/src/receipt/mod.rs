@@ -6,8 +6,14 @@ use crate::{
     instruction::{Buy, ExecuteSale, Sell},
     utils::*,
 };
-use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
-use solana_program::{sysvar, sysvar::instructions::get_instruction_relative};
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize, Discriminator};
+use mpl_token_metadata::{
+    state::{Metadata, ProgrammableConfig, TokenMetadataAccount},
+    utils::hash_compressed_creators,
+};
+use solana_program::{
+    program::invoke, system_instruction, sysvar, sysvar::instructions::get_instruction_relative,
+};
 
 pub const BID_RECEIPT_SIZE: usize = 8 + //key
 32 + // trade_state
@@ -73,6 +79,37 @@ pub struct ListingReceipt {
     pub canceled_at: Option<i64>,
 }
 
+pub const LISTING_RECEIPT_V2_SIZE: usize = LISTING_RECEIPT_SIZE +
+1 + 1 + // token_standard
+1 + 32 + // rule_set
+2 + // seller_fee_basis_points
+32; // creator_hash
+
+/// Receipt for a listing transaction, including a snapshot of the asset at listing time.
+#[account]
+pub struct ListingReceiptV2 {
+    pub trade_state: Pubkey,
+    pub bookkeeper: Pubkey,
+    pub auction_house: Pubkey,
+    pub seller: Pubkey,
+    pub metadata: Pubkey,
+    pub purchase_receipt: Option<Pubkey>,
+    pub price: u64,
+    pub token_size: u64,
+    pub bump: u8,
+    pub trade_state_bump: u8,
+    pub created_at: i64,
+    pub canceled_at: Option<i64>,
+    /// Token standard of the asset (as its `mpl-token-metadata` discriminant).
+    pub token_standard: Option<u8>,
+    /// Rule set of programmable assets.
+    pub rule_set: Option<Pubkey>,
+    /// Royalties of the asset, including royalties inherited from its collection.
+    pub seller_fee_basis_points: u16,
+    /// Keccak hash of the creators that receive the royalties.
+    pub creator_hash: [u8; 32],
+}
+
 pub const PURCHASE_RECEIPT_SIZE: usize = 8 + //key
 32 + // bookkeeper
 32 + // buyer
@@ -128,11 +165,123 @@ pub fn print_listing_receipt<'info>(
     receipt_bump: u8,
 ) -> Result<()> {
     let receipt_account = &ctx.accounts.receipt;
-    let instruction_account = &ctx.accounts.instruction;
-    let bookkeeper_account = &ctx.accounts.bookkeeper;
 
-    let rent = &ctx.accounts.rent;
-    let system_program = &ctx.accounts.system_program;
+    let receipt = init_listing_receipt(
+        &receipt_account.to_account_info(),
+        &ctx.accounts.instruction,
+        &ctx.accounts.bookkeeper,
+        &ctx.accounts.rent,
+        &ctx.accounts.system_program,
+        receipt_bump,
+        LISTING_RECEIPT_SIZE,
+    )?;
+
+    receipt.try_serialize(&mut *receipt_account.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+/// Accounts for the [`print_listing_receipt_v2` handler](fn.print_listing_receipt_v2.html).
+#[derive(Accounts)]
+#[instruction(receipt_bump: u8)]
+pub struct PrintListingReceiptV2<'info> {
+    /// CHECK: Receipt seeds are checked in print_listing_receipt_v2 handler.
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    /// CHECK: Must match the metadata of the listing; validated in the handler.
+    pub metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Validated by the address constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Create a Listing Receipt V2 account at a PDA with the seeds:
+/// "listing_receipt", <SELLER_TRADE_STATE_PUBKEY>.
+///
+/// In addition to the fields of the listing receipt, it stores a snapshot of the token
+/// standard, rule set, royalties and creators of the asset at listing time. Assets that
+/// inherit the royalties of their collection must pass the collection parent metadata as
+/// the first remaining account. Listing receipts printed by the previous version are
+/// resized and written over.
+pub fn print_listing_receipt_v2<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintListingReceiptV2<'info>>,
+    receipt_bump: u8,
+) -> Result<()> {
+    let receipt_account = &ctx.accounts.receipt;
+
+    let receipt = init_listing_receipt(
+        &receipt_account.to_account_info(),
+        &ctx.accounts.instruction,
+        &ctx.accounts.bookkeeper,
+        &ctx.accounts.rent,
+        &ctx.accounts.system_program,
+        receipt_bump,
+        LISTING_RECEIPT_V2_SIZE,
+    )?;
+
+    assert_keys_equal(ctx.accounts.metadata.key(), receipt.metadata)?;
+
+    let metadata = Metadata::from_account_info(&ctx.accounts.metadata.to_account_info())?;
+
+    let (seller_fee_basis_points, creators) = if metadata.inherit_royalties {
+        let parent_info = next_account_info(&mut ctx.remaining_accounts.iter())?;
+        let parent = Metadata::from_account_info(parent_info)?;
+        metadata.effective_royalties(&parent)?
+    } else {
+        (
+            metadata.data.seller_fee_basis_points,
+            metadata.data.creators.clone(),
+        )
+    };
+
+    let rule_set = match metadata.programmable_config {
+        Some(ProgrammableConfig::V1 { rule_set }) => rule_set,
+        None => None,
+    };
+
+    let receipt = ListingReceiptV2 {
+        trade_state: receipt.trade_state,
+        bookkeeper: receipt.bookkeeper,
+        auction_house: receipt.auction_house,
+        seller: receipt.seller,
+        metadata: receipt.metadata,
+        purchase_receipt: receipt.purchase_receipt,
+        price: receipt.price,
+        token_size: receipt.token_size,
+        bump: receipt.bump,
+        trade_state_bump: receipt.trade_state_bump,
+        created_at: receipt.created_at,
+        canceled_at: receipt.canceled_at,
+        token_standard: metadata.token_standard.map(|token_standard| token_standard as u8),
+        rule_set,
+        seller_fee_basis_points: seller_fee_basis_points.value(),
+        creator_hash: hash_compressed_creators(&creators.unwrap_or_default()),
+    };
+
+    receipt.try_serialize(&mut *receipt_account.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+/// Validates the listing instruction preceding a receipt instruction and allocates the
+/// receipt account, returning the receipt values.
+fn init_listing_receipt<'info>(
+    receipt_info: &AccountInfo<'info>,
+    instruction_account: &UncheckedAccount<'info>,
+    bookkeeper_account: &Signer<'info>,
+    rent: &Sysvar<'info, Rent>,
+    system_program: &Program<'info, System>,
+    receipt_bump: u8,
+    receipt_size: usize,
+) -> Result<ListingReceipt> {
     let clock = Clock::get()?;
 
     let prev_instruction = get_instruction_relative(-1, instruction_account)?;
@@ -150,11 +299,9 @@ pub fn print_listing_receipt<'info>(
 
     assert_keys_equal(prev_instruction.program_id, id())?;
 
-    let receipt_info = receipt_account.to_account_info();
-
     assert_derivation(
         &id(),
-        &receipt_info,
+        receipt_info,
         &[
             LISTING_RECEIPT_PREFIX.as_ref(),
             seller_trade_state.pubkey.as_ref(),
@@ -169,18 +316,40 @@ pub fn print_listing_receipt<'info>(
         ];
 
         create_or_allocate_account_raw(
-            *ctx.program_id,
-            &receipt_info,
+            id(),
+            receipt_info,
             &rent.to_account_info(),
             system_program,
             bookkeeper_account,
-            LISTING_RECEIPT_SIZE,
+            receipt_size,
             &[],
             &receipt_seeds,
         )?;
+    } else if receipt_info.data_len() < receipt_size {
+        // receipts printed by a previous version are smaller
+        let required_lamports = rent
+            .minimum_balance(receipt_size)
+            .saturating_sub(receipt_info.lamports());
+
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    bookkeeper_account.key,
+                    receipt_info.key,
+                    required_lamports,
+                ),
+                &[
+                    bookkeeper_account.to_account_info(),
+                    receipt_info.clone(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        receipt_info.realloc(receipt_size, false)?;
     }
 
-    let receipt = ListingReceipt {
+    Ok(ListingReceipt {
         trade_state: seller_trade_state.pubkey,
         bookkeeper: bookkeeper_account.key(),
         auction_house: auction_house.pubkey,
@@ -193,11 +362,12 @@ pub fn print_listing_receipt<'info>(
         trade_state_bump: sell_data.trade_state_bump,
         created_at: clock.unix_timestamp,
         canceled_at: None,
-    };
-
-    receipt.try_serialize(&mut *receipt_account.try_borrow_mut_data()?)?;
+    })
+}
 
-    Ok(())
+/// Returns whether the account data is a `ListingReceiptV2`.
+fn is_listing_receipt_v2(data: &[u8]) -> bool {
+    data.len() >= 8 && data[..8] == ListingReceiptV2::discriminator()
 }
 
 /// Accounts for the [`cancel_listing_receipt` handler](fn.cancel_listing_receipt.html).
@@ -244,11 +414,15 @@ pub fn cancel_listing_receipt<'info>(
     let mut receipt_data = receipt_info.try_borrow_mut_data()?;
     let mut receipt_data_slice: &[u8] = &receipt_data;
 
-    let mut receipt = ListingReceipt::try_deserialize(&mut receipt_data_slice)?;
-
-    receipt.canceled_at = Some(clock.unix_timestamp);
-
-    receipt.try_serialize(&mut *receipt_data)?;
+    if is_listing_receipt_v2(receipt_data_slice) {
+        let mut receipt = ListingReceiptV2::try_deserialize(&mut receipt_data_slice)?;
+        receipt.canceled_at = Some(clock.unix_timestamp);
+        receipt.try_serialize(&mut *receipt_data)?;
+    } else {
+        let mut receipt = ListingReceipt::try_deserialize(&mut receipt_data_slice)?;
+        receipt.canceled_at = Some(clock.unix_timestamp);
+        receipt.try_serialize(&mut *receipt_data)?;
+    }
 
     Ok(())
 }
@@ -555,11 +729,16 @@ pub fn print_purchase_receipt<'info>(
     let mut listing_receipt_data = listing_receipt_info.try_borrow_mut_data()?;
     let mut listing_receipt_data_slice: &[u8] = &listing_receipt_data;
 
-    let mut listing_receipt = ListingReceipt::try_deserialize(&mut listing_receipt_data_slice)?;
-
-    listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
-
-    listing_receipt.try_serialize(&mut *listing_receipt_data)?;
+    if is_listing_receipt_v2(listing_receipt_data_slice) {
+        let mut listing_receipt =
+            ListingReceiptV2::try_deserialize(&mut listing_receipt_data_slice)?;
+        listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+        listing_receipt.try_serialize(&mut *listing_receipt_data)?;
+    } else {
+        let mut listing_receipt = ListingReceipt::try_deserialize(&mut listing_receipt_data_slice)?;
+        listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+        listing_receipt.try_serialize(&mut *listing_receipt_data)?;
+    }
 
     let mut bid_receipt_data = bid_receipt_account.try_borrow_mut_data()?;
     let mut bid_receipt_slice: &[u8] = &bid_receipt_data;
@@ -15,7 +15,8 @@ use anchor_lang::{
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use mpl_royalty_engine::{distribute, Payment, PaymentAmount, RoyaltyError};
+use mpl_token_metadata::state::BasisPoints;
 use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
 use std::{convert::TryInto, slice::Iter};
 
@@ -274,12 +275,10 @@ pub fn pay_auction_house_fees<'a>(
     size: u64,
     is_native: bool,
 ) -> Result<u64> {
-    let fees = auction_house.seller_fee_basis_points;
-    let total_fee = (fees as u128)
-        .checked_mul(size as u128)
-        .ok_or(AuctionHouseError::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
+    let total_fee = BasisPoints::new(auction_house.seller_fee_basis_points)
+        .ok()
+        .and_then(|fees| fees.apply_to(size))
+        .ok_or(AuctionHouseError::InvalidBasisPoints)?;
     if !is_native {
         invoke_signed(
             &spl_token::instruction::transfer(
@@ -362,6 +361,8 @@ pub fn create_program_token_account_if_not_present<'a>(
     Ok(())
 }
 
+/// Pays the creators of the asset their share of the sale and returns the amount left to
+/// the seller, including any dust from rounding creator fees down.
 #[allow(clippy::too_many_arguments)]
 pub fn pay_creator_fees<'a>(
     remaining_accounts: &mut Iter<AccountInfo<'a>>,
@@ -373,103 +374,58 @@ pub fn pay_creator_fees<'a>(
     ata_program: &AccountInfo<'a>,
     token_program: &AccountInfo<'a>,
     system_program: &AccountInfo<'a>,
-    rent: &AccountInfo<'a>,
     signer_seeds: &[&[u8]],
     fee_payer_seeds: &[&[u8]],
     size: u64,
     is_native: bool,
 ) -> Result<u64> {
-    let metadata = Metadata::from_account_info(metadata_info)?;
-    let fees = metadata.data.seller_fee_basis_points;
-    let total_fee = (fees as u128)
-        .checked_mul(size as u128)
-        .ok_or(AuctionHouseError::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
-    let mut remaining_fee = total_fee;
-    let remaining_size = size
-        .checked_sub(total_fee)
-        .ok_or(AuctionHouseError::NumericalOverflow)?;
-    match metadata.data.creators {
-        Some(creators) => {
-            for creator in creators {
-                let pct = creator.share as u128;
-                let creator_fee =
-                    pct.checked_mul(total_fee as u128)
-                        .ok_or(AuctionHouseError::NumericalOverflow)?
-                        .checked_div(100)
-                        .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
-                remaining_fee = remaining_fee
-                    .checked_sub(creator_fee)
-                    .ok_or(AuctionHouseError::NumericalOverflow)?;
-                let current_creator_info = next_account_info(remaining_accounts)?;
-                assert_keys_equal(creator.address, *current_creator_info.key)?;
-                if !is_native {
-                    let current_creator_token_account_info = next_account_info(remaining_accounts)?;
-                    if current_creator_token_account_info.data_is_empty() {
-                        make_ata(
-                            current_creator_token_account_info.to_account_info(),
-                            current_creator_info.to_account_info(),
-                            treasury_mint.to_account_info(),
-                            fee_payer.to_account_info(),
-                            ata_program.to_account_info(),
-                            token_program.to_account_info(),
-                            system_program.to_account_info(),
-                            rent.to_account_info(),
-                            fee_payer_seeds,
-                        )?;
-                    }
-                    assert_is_ata(
-                        current_creator_token_account_info,
-                        current_creator_info.key,
-                        &treasury_mint.key(),
-                    )?;
-                    if creator_fee > 0 {
-                        invoke_signed(
-                            &spl_token::instruction::transfer(
-                                token_program.key,
-                                escrow_payment_account.key,
-                                current_creator_token_account_info.key,
-                                payment_account_owner.key,
-                                &[],
-                                creator_fee,
-                            )?,
-                            &[
-                                escrow_payment_account.clone(),
-                                current_creator_token_account_info.clone(),
-                                token_program.clone(),
-                                payment_account_owner.clone(),
-                            ],
-                            &[signer_seeds],
-                        )?;
-                    }
-                } else if creator_fee > 0 {
-                    invoke_signed(
-                        &system_instruction::transfer(
-                            escrow_payment_account.key,
-                            current_creator_info.key,
-                            creator_fee,
-                        ),
-                        &[
-                            escrow_payment_account.clone(),
-                            current_creator_info.clone(),
-                            system_program.clone(),
-                        ],
-                        &[signer_seeds],
-                    )?;
-                }
-            }
-        }
-        None => {
-            msg!("No creators found in metadata");
-        }
-    }
+    let as_arr = [fee_payer_seeds];
+
+    let fee_payer_signer_seeds: &[&[&[u8]]] = if !fee_payer_seeds.is_empty() {
+        &as_arr
+    } else {
+        &[]
+    };
+
+    let payment = Payment {
+        amount: PaymentAmount::Sale(size),
+        is_native,
+        source: escrow_payment_account,
+        authority: payment_account_owner,
+        signer_seeds: &[signer_seeds],
+        mint: treasury_mint,
+        fee_payer,
+        fee_payer_seeds: fee_payer_signer_seeds,
+        ata_program,
+        token_program,
+        system_program,
+    };
+
+    // Items inheriting the royalties of their collection must pass the metadata of the
+    // collection parent as the first remaining account, ahead of the creators.
+    let distribution =
+        distribute(&payment, metadata_info, remaining_accounts).map_err(royalty_error)?;
+
     // Any dust is returned to the party posting the NFT
-    Ok(remaining_size
-        .checked_add(remaining_fee)
+    Ok(size
+        .checked_sub(distribution.paid)
         .ok_or(AuctionHouseError::NumericalOverflow)?)
 }
 
+/// Maps the errors of the royalty engine into the error codes of the auction house.
+fn royalty_error(error: RoyaltyError) -> Error {
+    match error {
+        RoyaltyError::NumericalOverflow => AuctionHouseError::NumericalOverflow.into(),
+        RoyaltyError::InvalidBasisPoints => AuctionHouseError::InvalidBasisPoints.into(),
+        RoyaltyError::CreatorMismatch | RoyaltyError::InvalidTokenAccount => {
+            AuctionHouseError::PublicKeyMismatch.into()
+        }
+        RoyaltyError::IncorrectOwner => AuctionHouseError::IncorrectOwner.into(),
+        RoyaltyError::UninitializedAccount => AuctionHouseError::UninitializedAccount.into(),
+        error => ProgramError::from(error).into(),
+    }
+}
+
 /// Cheap method to just grab mint Pubkey from token account, instead of deserializing entire thing
 pub fn get_mint_from_token_account(token_account_info: &AccountInfo) -> Result<Pubkey> {
     // TokeAccount layout:   mint(32), owner(32), ...
@@ -6,7 +6,8 @@ use anchor_lang::AccountDeserialize;
 use common::*;
 use utils::{helpers::default_scopes, setup_functions::*};
 
-use mpl_auction_house::receipt::ListingReceipt;
+use mpl_auction_house::receipt::{ListingReceipt, ListingReceiptV2};
+use mpl_token_metadata::utils::hash_compressed_creators;
 use mpl_testing_utils::{solana::airdrop, utils::Metadata};
 use solana_sdk::{signer::Signer, sysvar::clock::Clock};
 use std::assert_eq;
@@ -79,6 +80,67 @@ async fn sell_success() {
     assert_eq!(listing_receipt.token_size, 1);
 }
 
+#[tokio::test]
+async fn sell_with_receipt_v2_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+    // Payer Wallet
+    let (ah, ahkey, _) = existing_auction_house_test_context(&mut context)
+        .await
+        .unwrap();
+    let test_metadata = Metadata::new();
+    let owner_pubkey = &test_metadata.token.pubkey();
+    airdrop(&mut context, owner_pubkey, TEN_SOL).await.unwrap();
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            1,
+        )
+        .await
+        .unwrap();
+    let ((acc, listing_receipt_acc), sell_tx) =
+        sell_with_receipt_v2(&mut context, &ahkey, &ah, &test_metadata, 1, 1);
+
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    let listing_receipt_account = context
+        .banks_client
+        .get_account(listing_receipt_acc.receipt)
+        .await
+        .expect("getting listing receipt")
+        .expect("empty listing receipt data");
+
+    let listing_receipt =
+        ListingReceiptV2::try_deserialize(&mut listing_receipt_account.data.as_ref()).unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+
+    assert_eq!(listing_receipt.auction_house, acc.auction_house);
+    assert_eq!(listing_receipt.metadata, acc.metadata);
+    assert_eq!(listing_receipt.seller, *owner_pubkey);
+    assert_eq!(listing_receipt.price, 1);
+    assert_eq!(listing_receipt.token_size, 1);
+    assert_eq!(
+        listing_receipt.token_standard,
+        metadata.token_standard.map(|token_standard| token_standard as u8)
+    );
+    assert_eq!(listing_receipt.rule_set, None);
+    assert_eq!(listing_receipt.seller_fee_basis_points, 10);
+    assert_eq!(
+        listing_receipt.creator_hash,
+        hash_compressed_creators(&metadata.data.creators.unwrap_or_default())
+    );
+}
+
 #[tokio::test]
 async fn auctioneer_sell_success() {
     let mut context = auction_house_program_test().start_with_context().await;
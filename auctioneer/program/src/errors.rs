@@ -41,4 +41,16 @@ pub enum AuctioneerError {
     // 6009
     #[msg("The highest bidder is not allowed to cancel")]
     CannotCancelHighestBid,
+
+    // 6010
+    #[msg("The auction end time must be after the start time")]
+    InvalidAuctionTimes,
+
+    // 6011
+    #[msg("A time extension period requires a time extension delta")]
+    InvalidTimeExtension,
+
+    // 6012
+    #[msg("Numerical overflow error")]
+    NumericalOverflow,
 }
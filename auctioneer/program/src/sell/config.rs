@@ -25,7 +25,9 @@ pub struct ListingConfig {
     pub bump: u8,
     pub reserve_price: u64,
     pub min_bid_increment: u64,
+    /// Bids placed within the last `time_ext_period` seconds of the auction extend it.
     pub time_ext_period: u32,
+    /// Number of seconds an auction is extended by a bid within the extension period.
     pub time_ext_delta: u32,
     pub allow_high_bid_cancel: bool,
 }
@@ -1,6 +1,6 @@
 pub mod config;
 
-use crate::{constants::*, errors::*, sell::config::*};
+use crate::{constants::*, errors::*, sell::config::*, utils::*};
 
 use anchor_lang::{prelude::*, AnchorDeserialize, InstructionData};
 use anchor_spl::token::{Token, TokenAccount};
@@ -120,6 +120,13 @@ pub fn auctioneer_sell<'info>(
     time_ext_delta: Option<u32>,
     allow_high_bid_cancel: Option<bool>,
 ) -> Result<()> {
+    assert_valid_auction_times(
+        start_time,
+        end_time,
+        time_ext_period.unwrap_or(0),
+        time_ext_delta.unwrap_or(0),
+    )?;
+
     ctx.accounts.listing_config.version = ListingConfigVersion::V0;
     ctx.accounts.listing_config.highest_bid.version = ListingConfigVersion::V0;
     ctx.accounts.listing_config.start_time = start_time;
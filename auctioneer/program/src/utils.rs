@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use solana_program::clock::UnixTimestamp;
 
 use crate::{errors::*, sell::config::*};
 
@@ -63,12 +64,44 @@ pub fn assert_highest_bidder(
     Ok(())
 }
 
+pub fn assert_valid_auction_times(
+    start_time: UnixTimestamp,
+    end_time: UnixTimestamp,
+    time_ext_period: u32,
+    time_ext_delta: u32,
+) -> Result<()> {
+    if end_time <= start_time {
+        return err!(AuctioneerError::InvalidAuctionTimes);
+    }
+
+    // a bid within the extension period would not extend the auction
+    if time_ext_period > 0 && time_ext_delta == 0 {
+        return err!(AuctioneerError::InvalidTimeExtension);
+    }
+
+    Ok(())
+}
+
+/// Extends the end time of the auction by `time_ext_delta` seconds when a bid is placed
+/// within the last `time_ext_period` seconds, so bids cannot snipe the auction.
 pub fn process_time_extension(listing_config: &mut Account<ListingConfig>) -> Result<()> {
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
 
-    if current_timestamp >= (listing_config.end_time - i64::from(listing_config.time_ext_period)) {
-        listing_config.end_time += i64::from(listing_config.time_ext_delta);
+    if listing_config.time_ext_period == 0 {
+        return Ok(());
+    }
+
+    let extension_start = listing_config
+        .end_time
+        .checked_sub(i64::from(listing_config.time_ext_period))
+        .ok_or(AuctioneerError::NumericalOverflow)?;
+
+    if current_timestamp >= extension_start {
+        listing_config.end_time = listing_config
+            .end_time
+            .checked_add(i64::from(listing_config.time_ext_delta))
+            .ok_or(AuctioneerError::NumericalOverflow)?;
     }
 
     Ok(())
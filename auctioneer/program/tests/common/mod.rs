@@ -38,3 +38,4 @@ pub const NOT_HIGH_BIDDER: u32 = 6006;
 pub const BELOW_RESERVE_PRICE: u32 = 6007;
 pub const BELOW_BID_INCREMENT: u32 = 6008;
 pub const CANNOT_CANCEL_HIGHEST_BID: u32 = 6009;
+pub const INVALID_TIME_EXTENSION: u32 = 6011;
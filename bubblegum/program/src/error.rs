@@ -56,4 +56,8 @@ pub enum BubblegumError {
     LeafAuthorityMustSign,
     #[msg("Collection Not Compatable with Compression, Must be Sized")]
     CollectionMustBeSized,
+    #[msg("Invalid number of leaves in the burn batch")]
+    InvalidBurnBatchSize,
+    #[msg("Proof accounts do not match the leaves of the burn batch")]
+    BurnBatchProofMismatch,
 }
@@ -6,8 +6,8 @@ use crate::{
         leaf_schema::LeafSchema,
         metaplex_adapter::{self, Creator, MetadataArgs, TokenProgramVersion},
         metaplex_anchor::{MasterEdition, MplTokenMetadata, TokenMetadata},
-        TreeConfig, Voucher, ASSET_PREFIX, COLLECTION_CPI_PREFIX, TREE_AUTHORITY_SIZE,
-        VOUCHER_PREFIX, VOUCHER_SIZE,
+        BurnBatchEvent, BurnBatchLeaf, TreeConfig, Voucher, ASSET_PREFIX, COLLECTION_CPI_PREFIX,
+        MAX_BURN_BATCH_SIZE, TREE_AUTHORITY_SIZE, VOUCHER_PREFIX, VOUCHER_SIZE,
     },
     utils::{
         append_leaf, assert_metadata_is_mpl_compatible, assert_pubkey_equal, cmp_bytes,
@@ -18,7 +18,6 @@ use anchor_lang::{
     prelude::*,
     solana_program::{
         account_info::AccountInfo,
-        keccak,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::Pack,
@@ -29,6 +28,7 @@ use anchor_lang::{
 use mpl_token_metadata::{
     assertions::collection::{assert_collection_verify_is_valid, assert_has_collection_authority},
     state::CollectionDetails,
+    utils::{hash_compressed_creators, hash_compressed_metadata_data},
 };
 use spl_account_compression::{
     program::SplAccountCompression, wrap_application_data_v1, Node, Noop,
@@ -425,30 +425,15 @@ pub struct SetTreeDelegate<'info> {
 }
 
 pub fn hash_creators(creators: &[Creator]) -> Result<[u8; 32]> {
-    // Convert creator Vec to bytes Vec.
-    let creator_data = creators
-        .iter()
-        .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
-        .collect::<Vec<_>>();
-    // Calculate new creator hash.
-    Ok(keccak::hashv(
-        creator_data
-            .iter()
-            .map(|c| c.as_slice())
-            .collect::<Vec<&[u8]>>()
-            .as_ref(),
-    )
-    .to_bytes())
+    let creators = creators.iter().map(|c| c.adapt()).collect::<Vec<_>>();
+    Ok(hash_compressed_creators(&creators))
 }
 
 pub fn hash_metadata(metadata: &MetadataArgs) -> Result<[u8; 32]> {
-    let metadata_args_hash = keccak::hashv(&[metadata.try_to_vec()?.as_slice()]);
-    // Calculate new data hash.
-    Ok(keccak::hashv(&[
-        &metadata_args_hash.to_bytes(),
-        &metadata.seller_fee_basis_points.to_le_bytes(),
-    ])
-    .to_bytes())
+    Ok(hash_compressed_metadata_data(
+        metadata.try_to_vec()?.as_slice(),
+        metadata.seller_fee_basis_points,
+    ))
 }
 
 pub enum InstructionName {
@@ -468,6 +453,7 @@ pub enum InstructionName {
     UnverifyCollection,
     SetAndVerifyCollection,
     MintToCollectionV1,
+    BurnBatch,
 }
 
 pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
@@ -492,6 +478,7 @@ pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
         [56, 113, 101, 253, 79, 55, 122, 169] => InstructionName::VerifyCollection,
         [250, 251, 42, 106, 41, 137, 186, 168] => InstructionName::UnverifyCollection,
         [235, 242, 121, 216, 158, 234, 180, 234] => InstructionName::SetAndVerifyCollection,
+        [213, 253, 141, 32, 161, 23, 175, 238] => InstructionName::BurnBatch,
 
         _ => InstructionName::Unknown,
     }
@@ -520,34 +507,18 @@ fn process_mint_v1<'info>(
 
     // @dev: seller_fee_basis points is encoded twice so that it can be passed to marketplace
     // instructions, without passing the entire, un-hashed MetadataArgs struct
-    let metadata_args_hash = keccak::hashv(&[message.try_to_vec()?.as_slice()]);
-    let data_hash = keccak::hashv(&[
-        &metadata_args_hash.to_bytes(),
-        &message.seller_fee_basis_points.to_le_bytes(),
-    ]);
+    let data_hash = hash_metadata(&message)?;
 
     // Use the metadata auth to check whether we can allow `verified` to be set to true in the
     // creator Vec.
-    let creator_data = message
-        .creators
-        .iter()
-        .map(|c| {
-            if c.verified && !metadata_auth.contains(&c.address) {
-                Err(BubblegumError::CreatorDidNotVerify.into())
-            } else {
-                Ok([c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
-            }
-        })
-        .collect::<Result<Vec<_>>>()?;
+    for creator in &message.creators {
+        if creator.verified && !metadata_auth.contains(&creator.address) {
+            return Err(BubblegumError::CreatorDidNotVerify.into());
+        }
+    }
 
     // Calculate creator hash.
-    let creator_hash = keccak::hashv(
-        creator_data
-            .iter()
-            .map(|c| c.as_slice())
-            .collect::<Vec<&[u8]>>()
-            .as_ref(),
-    );
+    let creator_hash = hash_creators(&message.creators)?;
 
     let asset_id = get_asset_id(&merkle_tree.key(), authority.num_minted);
     let leaf = LeafSchema::new_v0(
@@ -555,8 +526,8 @@ fn process_mint_v1<'info>(
         owner,
         delegate,
         authority.num_minted,
-        data_hash.to_bytes(),
-        creator_hash.to_bytes(),
+        data_hash,
+        creator_hash,
     );
 
     wrap_application_data_v1(leaf.to_event().try_to_vec()?, wrapper)?;
@@ -752,7 +723,7 @@ fn process_collection_verification_mpl_only<'info>(
     if let Some(details) = &collection_metadata.collection_details {
         // Increment or decrement existing size.
         let new_size = match details {
-            CollectionDetails::V1 { size } => {
+            CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => {
                 if verify {
                     size.checked_add(1)
                         .ok_or(BubblegumError::NumericalOverflowError)?
@@ -761,6 +732,8 @@ fn process_collection_verification_mpl_only<'info>(
                         .ok_or(BubblegumError::NumericalOverflowError)?
                 }
             }
+            // Uncapped collections do not keep track of their size.
+            CollectionDetails::Uncapped => return Ok(()),
         };
 
         // CPI into to token-metadata program to change the collection size.
@@ -1338,6 +1311,76 @@ pub mod bubblegum {
         )
     }
 
+    /// Burns multiple leaves of the tree owned (or delegated) to the same authority. The
+    /// proofs of the leaves are passed in the remaining accounts, in the order of the leaves,
+    /// and a single event is logged with the ids of the burned assets.
+    pub fn burn_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, Burn<'info>>,
+        leaves: Vec<BurnBatchLeaf>,
+    ) -> Result<()> {
+        let owner = ctx.accounts.leaf_owner.to_account_info();
+        let delegate = ctx.accounts.leaf_delegate.to_account_info();
+
+        // Burn must be initiated by either the leaf owner or leaf delegate.
+        require!(
+            owner.is_signer || delegate.is_signer,
+            BubblegumError::LeafAuthorityMustSign
+        );
+        require!(
+            !leaves.is_empty() && leaves.len() <= MAX_BURN_BATCH_SIZE,
+            BubblegumError::InvalidBurnBatchSize
+        );
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let bump = *ctx.bumps.get("tree_authority").unwrap();
+
+        let mut proofs = ctx.remaining_accounts;
+        let mut asset_ids = Vec::with_capacity(leaves.len());
+
+        for leaf in leaves {
+            let proof_length = usize::from(leaf.proof_length);
+            require!(
+                proofs.len() >= proof_length,
+                BubblegumError::BurnBatchProofMismatch
+            );
+            let (proof, remaining) = proofs.split_at(proof_length);
+            proofs = remaining;
+
+            let asset_id = get_asset_id(&merkle_tree.key(), leaf.nonce);
+
+            let previous_leaf = LeafSchema::new_v0(
+                asset_id,
+                owner.key(),
+                delegate.key(),
+                leaf.nonce,
+                leaf.data_hash,
+                leaf.creator_hash,
+            );
+
+            replace_leaf(
+                &merkle_tree.key(),
+                bump,
+                &ctx.accounts.compression_program.to_account_info(),
+                &ctx.accounts.tree_authority.to_account_info(),
+                &merkle_tree,
+                &ctx.accounts.log_wrapper.to_account_info(),
+                proof,
+                leaf.root,
+                previous_leaf.to_node(),
+                Node::default(),
+                leaf.index,
+            )?;
+
+            asset_ids.push(asset_id);
+        }
+
+        require!(proofs.is_empty(), BubblegumError::BurnBatchProofMismatch);
+
+        wrap_application_data_v1(
+            BurnBatchEvent::new(asset_ids).try_to_vec()?,
+            &ctx.accounts.log_wrapper,
+        )
+    }
+
     pub fn redeem<'info>(
         ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
         root: [u8; 32],
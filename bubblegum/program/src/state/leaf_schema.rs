@@ -1,6 +1,7 @@
 use crate::state::BubblegumEventType;
-use anchor_lang::{prelude::*, solana_program::keccak};
+use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::utils::hash_compressed_leaf;
 use spl_account_compression::Node;
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -122,16 +123,7 @@ impl LeafSchema {
                 nonce,
                 data_hash,
                 creator_hash,
-            } => keccak::hashv(&[
-                &[self.version().to_bytes()],
-                id.as_ref(),
-                owner.as_ref(),
-                delegate.as_ref(),
-                nonce.to_le_bytes().as_ref(),
-                data_hash.as_ref(),
-                creator_hash.as_ref(),
-            ])
-            .to_bytes(),
+            } => hash_compressed_leaf(id, owner, delegate, *nonce, data_hash, creator_hash),
         };
         hashed_leaf
     }
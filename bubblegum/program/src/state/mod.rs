@@ -4,13 +4,14 @@ pub mod metaplex_anchor;
 
 use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSerialize};
-use leaf_schema::LeafSchema;
+use leaf_schema::{LeafSchema, Version};
 
 pub const TREE_AUTHORITY_SIZE: usize = 32 + 32 + 8 + 8 + 1 + 15; // 15 bytes padding
 pub const VOUCHER_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 32 + 32 + 4 + 32;
 pub const VOUCHER_PREFIX: &str = "voucher";
 pub const ASSET_PREFIX: &str = "asset";
 pub const COLLECTION_CPI_PREFIX: &str = "collection_cpi";
+pub const MAX_BURN_BATCH_SIZE: usize = 16;
 
 #[account]
 #[derive(Copy, Debug, PartialEq, Eq)]
@@ -78,4 +79,36 @@ pub enum BubblegumEventType {
     Uninitialized,
     /// Leaf schema event.
     LeafSchemaEvent,
+    /// Burn batch event.
+    BurnBatchEvent,
+}
+
+/// Leaf burned by the `burn_batch` instruction. The proof of the leaf is passed in the
+/// remaining accounts, following the proofs of the previous leaves of the batch.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct BurnBatchLeaf {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub proof_length: u8,
+}
+
+/// Single event logged for all the leaves burned by a `burn_batch` instruction.
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct BurnBatchEvent {
+    pub event_type: BubblegumEventType,
+    pub version: Version,
+    pub asset_ids: Vec<Pubkey>,
+}
+
+impl BurnBatchEvent {
+    pub fn new(asset_ids: Vec<Pubkey>) -> Self {
+        Self {
+            event_type: BubblegumEventType::BurnBatchEvent,
+            version: Version::V1,
+            asset_ids,
+        }
+    }
 }
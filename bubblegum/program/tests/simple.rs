@@ -153,6 +153,18 @@ async fn test_burn_passes() {
     }
 }
 
+#[tokio::test]
+async fn test_burn_batch_passes() {
+    let (_, tree, leaves) = context_tree_and_leaves().await.unwrap();
+
+    // `burn_batch` also validates whether the on-chain tree root always has the expected
+    // value via the inner `TxBuilder::execute` call.
+
+    for batch in leaves.chunks(2) {
+        tree.burn_batch(batch).await.unwrap();
+    }
+}
+
 #[tokio::test]
 async fn test_set_tree_delegate_passes() {
     let (context, tree, _) = context_tree_and_leaves().await.unwrap();
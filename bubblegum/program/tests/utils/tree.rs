@@ -2,7 +2,7 @@ use crate::utils::tx_builder::DecompressV1Builder;
 use anchor_lang::{self, AccountDeserialize};
 use bytemuck::try_from_bytes;
 use mpl_bubblegum::{
-    state::{leaf_schema::LeafSchema, TreeConfig, Voucher, VOUCHER_PREFIX},
+    state::{leaf_schema::LeafSchema, BurnBatchLeaf, TreeConfig, Voucher, VOUCHER_PREFIX},
     utils::get_asset_id,
 };
 use solana_program::{
@@ -32,9 +32,10 @@ use std::{
 use super::{
     clone_keypair, compute_metadata_hashes,
     tx_builder::{
-        BurnBuilder, CancelRedeemBuilder, CreateBuilder, CreatorVerificationInner, DelegateBuilder,
-        DelegateInner, MintV1Builder, RedeemBuilder, SetTreeDelegateBuilder, TransferBuilder,
-        TransferInner, TxBuilder, UnverifyCreatorBuilder, VerifyCreatorBuilder,
+        BurnBatchBuilder, BurnBuilder, CancelRedeemBuilder, CreateBuilder,
+        CreatorVerificationInner, DelegateBuilder, DelegateInner, MintV1Builder, RedeemBuilder,
+        SetTreeDelegateBuilder, TransferBuilder, TransferInner, TxBuilder, UnverifyCreatorBuilder,
+        VerifyCreatorBuilder,
     },
     Error, LeafArgs, Result,
 };
@@ -391,6 +392,62 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         self.burn_tx(args).await?.execute().await
     }
 
+    // All leaves must have the same owner and delegate.
+    pub async fn burn_batch_tx<'a>(
+        &'a self,
+        leaves: &'a [LeafArgs],
+    ) -> Result<BurnBatchBuilder<MAX_DEPTH, MAX_BUFFER_SIZE>> {
+        let root = self.decode_root().await?;
+        let args = &leaves[0];
+
+        let accounts = mpl_bubblegum::accounts::Burn {
+            tree_authority: self.authority(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            system_program: system_program::id(),
+        };
+
+        let mut batch = Vec::with_capacity(leaves.len());
+        let mut proofs = Vec::new();
+
+        for leaf in leaves {
+            let (data_hash, creator_hash) = compute_metadata_hashes(&leaf.metadata)?;
+            let mut proof = self.proof_of_leaf_metas(leaf.index);
+
+            batch.push(BurnBatchLeaf {
+                root,
+                data_hash,
+                creator_hash,
+                nonce: leaf.nonce,
+                index: leaf.index,
+                proof_length: u8::try_from(proof.len()).unwrap(),
+            });
+            proofs.append(&mut proof);
+        }
+
+        let data = mpl_bubblegum::instruction::BurnBatch { leaves: batch };
+
+        let mut tx = self.tx_builder(
+            accounts,
+            data,
+            None,
+            leaves,
+            args.owner.pubkey(),
+            &[&args.owner],
+        );
+        // The proofs of all the leaves are computed before any of them is burned.
+        tx.set_additional_account_metas(&proofs);
+
+        Ok(tx)
+    }
+
+    pub async fn burn_batch(&self, leaves: &[LeafArgs]) -> Result<()> {
+        self.burn_batch_tx(leaves).await?.execute().await
+    }
+
     pub async fn verify_creator_tx<'a>(
         &'a self,
         args: &'a mut LeafArgs,
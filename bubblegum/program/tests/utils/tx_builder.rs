@@ -197,6 +197,26 @@ impl<'a, const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> OnSuccessfulTxExe
     }
 }
 
+pub type BurnBatchBuilder<'a, const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> = TxBuilder<
+    'a,
+    mpl_bubblegum::accounts::Burn,
+    mpl_bubblegum::instruction::BurnBatch,
+    &'a [LeafArgs],
+    MAX_DEPTH,
+    MAX_BUFFER_SIZE,
+>;
+
+impl<'a, const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> OnSuccessfulTxExec
+    for BurnBatchBuilder<'a, MAX_DEPTH, MAX_BUFFER_SIZE>
+{
+    fn on_successful_execute(&mut self) -> Result<()> {
+        for args in self.inner {
+            self.tree.zero_leaf(args.index)?;
+        }
+        Ok(())
+    }
+}
+
 pub struct TransferInner<'a> {
     pub args: &'a mut LeafArgs,
     pub new_owner: Keypair,
@@ -0,0 +1,372 @@
+use std::slice::Iter;
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    program::invoke_signed,
+    program_pack::{IsInitialized, Pack},
+    system_instruction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
+
+use crate::{royalty_fee, split_fee, Royalties, RoyaltyError};
+
+/// Amount of a payment and how it relates to the royalties of the asset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentAmount {
+    /// Price of a sale: the creators receive the seller fee of the asset.
+    Sale(u64),
+    /// Royalty fee paid in full to the creators: the seller fee of the asset is not applied.
+    Royalty(u64),
+}
+
+/// Accounts and signers used to pay the creators of an asset.
+pub struct Payment<'a, 'b> {
+    pub amount: PaymentAmount,
+    /// Whether the payment is made in SOL; otherwise, it is made in tokens of `mint`.
+    pub is_native: bool,
+    /// Account paying the creators: a system account for SOL payments or a token account
+    /// otherwise.
+    pub source: &'b AccountInfo<'a>,
+    /// Authority of the `source` token account (unused for SOL payments).
+    pub authority: &'b AccountInfo<'a>,
+    /// Signer seeds of the `source` (SOL) or the `authority` (tokens) when it is a PDA.
+    pub signer_seeds: &'b [&'b [&'b [u8]]],
+    pub mint: &'b AccountInfo<'a>,
+    /// Pays for the creation of the associated token accounts of creators.
+    pub fee_payer: &'b AccountInfo<'a>,
+    /// Signer seeds of the `fee_payer` when it is a PDA.
+    pub fee_payer_seeds: &'b [&'b [&'b [u8]]],
+    pub ata_program: &'b AccountInfo<'a>,
+    pub token_program: &'b AccountInfo<'a>,
+    pub system_program: &'b AccountInfo<'a>,
+}
+
+/// Outcome of a royalty distribution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Distribution {
+    /// Royalty fee of the payment.
+    pub fee: u64,
+    /// Amount transferred to the creators.
+    pub paid: u64,
+}
+
+impl Distribution {
+    /// Amount of the fee not transferred, either from rounding creator fees down or
+    /// because the asset has no creators.
+    pub fn dust(&self) -> u64 {
+        self.fee.saturating_sub(self.paid)
+    }
+}
+
+/// Pays the creators of an asset their share of a payment.
+///
+/// The royalties are read from the metadata of the asset and the creator accounts are
+/// consumed from `remaining_accounts` (see [`pay_creators`]).
+pub fn distribute<'a>(
+    payment: &Payment<'a, '_>,
+    metadata_info: &AccountInfo<'a>,
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+) -> Result<Distribution, RoyaltyError> {
+    let royalties = Royalties::read(metadata_info, remaining_accounts)?;
+    pay_creators(payment, &royalties, remaining_accounts)
+}
+
+/// Pays the creators their share of a payment.
+///
+/// For each creator, in order, `remaining_accounts` must contain the creator wallet and,
+/// for token payments, its associated token account for the payment mint. All accounts
+/// are validated before any transfer is made; associated token accounts that do not exist
+/// yet are created.
+pub fn pay_creators<'a>(
+    payment: &Payment<'a, '_>,
+    royalties: &Royalties,
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+) -> Result<Distribution, RoyaltyError> {
+    let fee = match payment.amount {
+        PaymentAmount::Sale(amount) => royalty_fee(amount, royalties.seller_fee_basis_points)?,
+        PaymentAmount::Royalty(fee) => fee,
+    };
+
+    if royalties.creators.is_empty() {
+        msg!("No creators found in metadata");
+        return Ok(Distribution { fee, paid: 0 });
+    }
+
+    let (creator_fees, dust) = split_fee(fee, royalties.creators.iter().map(|c| c.share))?;
+    let recipients = creator_accounts(payment, royalties, remaining_accounts)?;
+
+    for ((creator_info, token_account_info), creator_fee) in
+        recipients.into_iter().zip(creator_fees)
+    {
+        match token_account_info {
+            Some(token_account_info) => {
+                if token_account_info.data_is_empty() {
+                    create_associated_token_account(payment, creator_info, token_account_info)?;
+                }
+                assert_token_account(token_account_info)?;
+
+                if creator_fee > 0 {
+                    invoke_signed(
+                        &spl_token::instruction::transfer(
+                            payment.token_program.key,
+                            payment.source.key,
+                            token_account_info.key,
+                            payment.authority.key,
+                            &[],
+                            creator_fee,
+                        )?,
+                        &[
+                            payment.source.clone(),
+                            token_account_info.clone(),
+                            payment.token_program.clone(),
+                            payment.authority.clone(),
+                        ],
+                        payment.signer_seeds,
+                    )?;
+                }
+            }
+            None if creator_fee > 0 => {
+                invoke_signed(
+                    &system_instruction::transfer(
+                        payment.source.key,
+                        creator_info.key,
+                        creator_fee,
+                    ),
+                    &[
+                        payment.source.clone(),
+                        creator_info.clone(),
+                        payment.system_program.clone(),
+                    ],
+                    payment.signer_seeds,
+                )?;
+            }
+            None => (),
+        }
+    }
+
+    Ok(Distribution {
+        fee,
+        paid: fee - dust,
+    })
+}
+
+type CreatorAccounts<'b, 'a> = (&'b AccountInfo<'a>, Option<&'b AccountInfo<'a>>);
+
+/// Takes the accounts of each creator from `remaining_accounts`, checking that they match
+/// the creators of the asset.
+fn creator_accounts<'a, 'b>(
+    payment: &Payment<'a, '_>,
+    royalties: &Royalties,
+    remaining_accounts: &mut Iter<'b, AccountInfo<'a>>,
+) -> Result<Vec<CreatorAccounts<'b, 'a>>, RoyaltyError> {
+    royalties
+        .creators
+        .iter()
+        .map(|creator| {
+            let creator_info = next_account_info(remaining_accounts)
+                .map_err(|_| RoyaltyError::MissingCreatorAccount)?;
+
+            if creator_info.key != &creator.address {
+                return Err(RoyaltyError::CreatorMismatch);
+            }
+
+            if payment.is_native {
+                return Ok((creator_info, None));
+            }
+
+            let token_account_info = next_account_info(remaining_accounts)
+                .map_err(|_| RoyaltyError::MissingCreatorAccount)?;
+
+            if token_account_info.key
+                != &get_associated_token_address(&creator.address, payment.mint.key)
+            {
+                return Err(RoyaltyError::InvalidTokenAccount);
+            }
+
+            Ok((creator_info, Some(token_account_info)))
+        })
+        .collect()
+}
+
+fn create_associated_token_account<'a>(
+    payment: &Payment<'a, '_>,
+    creator_info: &AccountInfo<'a>,
+    token_account_info: &AccountInfo<'a>,
+) -> Result<(), RoyaltyError> {
+    invoke_signed(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payment.fee_payer.key,
+            creator_info.key,
+            payment.mint.key,
+            &spl_token::ID,
+        ),
+        &[
+            token_account_info.clone(),
+            creator_info.clone(),
+            payment.mint.clone(),
+            payment.fee_payer.clone(),
+            payment.ata_program.clone(),
+            payment.system_program.clone(),
+            payment.token_program.clone(),
+        ],
+        payment.fee_payer_seeds,
+    )
+    .map_err(RoyaltyError::from)
+}
+
+fn assert_token_account(token_account_info: &AccountInfo) -> Result<(), RoyaltyError> {
+    if token_account_info.owner != &spl_token::ID {
+        return Err(RoyaltyError::IncorrectOwner);
+    }
+
+    let account = Account::unpack_unchecked(&token_account_info.data.borrow())?;
+
+    if !account.is_initialized() {
+        return Err(RoyaltyError::UninitializedAccount);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mpl_token_metadata::state::Creator;
+    use solana_program::{pubkey::Pubkey, system_program};
+
+    use super::*;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey) -> Self {
+            Self {
+                key,
+                owner: system_program::ID,
+                lamports: 1_000_000,
+                data: vec![],
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn royalties(creators: &[Pubkey]) -> Royalties {
+        Royalties {
+            seller_fee_basis_points: 500,
+            creators: creators
+                .iter()
+                .map(|address| Creator {
+                    address: *address,
+                    verified: true,
+                    share: (100 / creators.len()) as u8,
+                })
+                .collect(),
+        }
+    }
+
+    fn pay_test_creators(
+        is_native: bool,
+        royalties: &Royalties,
+        remaining_keys: &[Pubkey],
+    ) -> Result<Distribution, RoyaltyError> {
+        let mut source = TestAccount::new(Pubkey::new_unique());
+        let mut mint = TestAccount::new(Pubkey::new_unique());
+        let mut program = TestAccount::new(Pubkey::new_unique());
+        let mut remaining = remaining_keys
+            .iter()
+            .map(|key| TestAccount::new(*key))
+            .collect::<Vec<_>>();
+
+        let source_info = source.info();
+        let mint_info = mint.info();
+        let program_info = program.info();
+        let remaining_infos = remaining.iter_mut().map(|a| a.info()).collect::<Vec<_>>();
+
+        let payment = Payment {
+            amount: PaymentAmount::Sale(1_000_000),
+            is_native,
+            source: &source_info,
+            authority: &source_info,
+            signer_seeds: &[],
+            mint: &mint_info,
+            fee_payer: &source_info,
+            fee_payer_seeds: &[],
+            ata_program: &program_info,
+            token_program: &program_info,
+            system_program: &program_info,
+        };
+
+        pay_creators(&payment, royalties, &mut remaining_infos.iter())
+    }
+
+    #[test]
+    fn no_creators_leaves_fee_as_dust() {
+        let distribution = pay_test_creators(true, &Royalties::default(), &[]).unwrap();
+        assert_eq!(distribution, Distribution { fee: 0, paid: 0 });
+
+        let royalties = Royalties {
+            seller_fee_basis_points: 500,
+            creators: vec![],
+        };
+        let distribution = pay_test_creators(true, &royalties, &[]).unwrap();
+        assert_eq!(distribution.fee, 50_000);
+        assert_eq!(distribution.dust(), 50_000);
+    }
+
+    #[test]
+    fn missing_creator_account() {
+        let creators = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let error = pay_test_creators(true, &royalties(&creators), &creators[..1]).unwrap_err();
+        assert_eq!(error, RoyaltyError::MissingCreatorAccount);
+    }
+
+    #[test]
+    fn missing_creator_token_account() {
+        let creators = [Pubkey::new_unique()];
+
+        let error = pay_test_creators(false, &royalties(&creators), &creators).unwrap_err();
+        assert_eq!(error, RoyaltyError::MissingCreatorAccount);
+    }
+
+    #[test]
+    fn creator_account_mismatch() {
+        let creators = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // creator accounts out of order
+        let error = pay_test_creators(true, &royalties(&creators), &[creators[1], creators[0]])
+            .unwrap_err();
+        assert_eq!(error, RoyaltyError::CreatorMismatch);
+    }
+
+    #[test]
+    fn creator_token_account_is_not_associated() {
+        let creators = [Pubkey::new_unique()];
+
+        let error = pay_test_creators(
+            false,
+            &royalties(&creators),
+            &[creators[0], Pubkey::new_unique()],
+        )
+        .unwrap_err();
+        assert_eq!(error, RoyaltyError::InvalidTokenAccount);
+    }
+}
@@ -0,0 +1,53 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+/// Errors that may be returned while distributing royalties.
+///
+/// Programs are expected to map these into their own error codes; the conversion into
+/// `ProgramError` is provided for callers without an error type of their own.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum RoyaltyError {
+    #[error("Numerical overflow")]
+    NumericalOverflow,
+
+    #[error("Invalid basis points")]
+    InvalidBasisPoints,
+
+    #[error("Missing collection parent metadata")]
+    MissingCollectionMetadata,
+
+    #[error("Missing creator account")]
+    MissingCreatorAccount,
+
+    #[error("Creator account does not match the metadata")]
+    CreatorMismatch,
+
+    #[error("Token account is not the associated token account of the creator")]
+    InvalidTokenAccount,
+
+    #[error("Incorrect account owner")]
+    IncorrectOwner,
+
+    #[error("Uninitialized account")]
+    UninitializedAccount,
+
+    #[error(transparent)]
+    Program(#[from] ProgramError),
+}
+
+impl From<RoyaltyError> for ProgramError {
+    fn from(error: RoyaltyError) -> Self {
+        match error {
+            RoyaltyError::NumericalOverflow => ProgramError::ArithmeticOverflow,
+            RoyaltyError::MissingCollectionMetadata | RoyaltyError::MissingCreatorAccount => {
+                ProgramError::NotEnoughAccountKeys
+            }
+            RoyaltyError::InvalidBasisPoints
+            | RoyaltyError::CreatorMismatch
+            | RoyaltyError::InvalidTokenAccount => ProgramError::InvalidArgument,
+            RoyaltyError::IncorrectOwner => ProgramError::IllegalOwner,
+            RoyaltyError::UninitializedAccount => ProgramError::UninitializedAccount,
+            RoyaltyError::Program(error) => error,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Royalty distribution shared by the Metaplex marketplace programs.
+
+pub use distribute::*;
+pub use error::*;
+pub use math::*;
+pub use royalties::*;
+
+mod distribute;
+mod error;
+mod math;
+mod royalties;
@@ -0,0 +1,123 @@
+use crate::RoyaltyError;
+
+/// Basis points representing 100%.
+pub const MAX_BASIS_POINTS: u16 = 10000;
+
+/// Creator share representing 100%.
+pub const MAX_SHARE: u8 = 100;
+
+/// Returns the royalty fee of a sale of `amount` with the given seller fee, rounded down.
+pub fn royalty_fee(amount: u64, seller_fee_basis_points: u16) -> Result<u64, RoyaltyError> {
+    if seller_fee_basis_points > MAX_BASIS_POINTS {
+        return Err(RoyaltyError::InvalidBasisPoints);
+    }
+
+    // the result is at most `amount`, so it always fits in a u64
+    Ok((amount as u128 * seller_fee_basis_points as u128 / MAX_BASIS_POINTS as u128) as u64)
+}
+
+/// Returns the amount of a sale of `amount` left to the seller once the royalty fee is
+/// deducted. Dust from creator splits is not included.
+pub fn seller_proceeds(amount: u64, seller_fee_basis_points: u16) -> Result<u64, RoyaltyError> {
+    amount
+        .checked_sub(royalty_fee(amount, seller_fee_basis_points)?)
+        .ok_or(RoyaltyError::NumericalOverflow)
+}
+
+/// Returns the portion of `fee` owed to a creator with `share` percent of the royalties,
+/// rounded down.
+pub fn creator_fee(fee: u64, share: u8) -> Result<u64, RoyaltyError> {
+    let creator_fee = (fee as u128)
+        .checked_mul(share as u128)
+        .ok_or(RoyaltyError::NumericalOverflow)?
+        / MAX_SHARE as u128;
+
+    u64::try_from(creator_fee).map_err(|_| RoyaltyError::NumericalOverflow)
+}
+
+/// Splits `fee` between creators with the given shares.
+///
+/// Returns the fee of each creator, in the order of the shares, and the dust left over from
+/// rounding every creator fee down. Shares adding up to more than 100% are rejected.
+pub fn split_fee(
+    fee: u64,
+    shares: impl IntoIterator<Item = u8>,
+) -> Result<(Vec<u64>, u64), RoyaltyError> {
+    let mut dust = fee;
+
+    let fees = shares
+        .into_iter()
+        .map(|share| {
+            let creator_fee = creator_fee(fee, share)?;
+            dust = dust
+                .checked_sub(creator_fee)
+                .ok_or(RoyaltyError::NumericalOverflow)?;
+            Ok(creator_fee)
+        })
+        .collect::<Result<Vec<_>, RoyaltyError>>()?;
+
+    Ok((fees, dust))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn royalty_fee_rounds_down() {
+        assert_eq!(royalty_fee(1_000_000, 500).unwrap(), 50_000);
+        assert_eq!(royalty_fee(199, 500).unwrap(), 9);
+        assert_eq!(royalty_fee(u64::MAX, MAX_BASIS_POINTS).unwrap(), u64::MAX);
+        assert_eq!(royalty_fee(1_000_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn royalty_fee_rejects_invalid_basis_points() {
+        assert_eq!(
+            royalty_fee(1_000_000, MAX_BASIS_POINTS + 1),
+            Err(RoyaltyError::InvalidBasisPoints)
+        );
+    }
+
+    #[test]
+    fn seller_proceeds_keep_rounding_remainder() {
+        // 5% of 199 is 9.95, rounded down to 9
+        assert_eq!(seller_proceeds(199, 500).unwrap(), 190);
+    }
+
+    #[test]
+    fn creator_fee_does_not_overflow() {
+        assert_eq!(creator_fee(u64::MAX, MAX_SHARE).unwrap(), u64::MAX);
+        assert_eq!(creator_fee(u64::MAX, 50).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn split_fee_returns_dust() {
+        let (fees, dust) = split_fee(100, [33, 33, 34]).unwrap();
+        assert_eq!(fees, vec![33, 33, 34]);
+        assert_eq!(dust, 0);
+
+        let (fees, dust) = split_fee(10, [33, 33, 34]).unwrap();
+        assert_eq!(fees, vec![3, 3, 3]);
+        assert_eq!(dust, 1);
+
+        let (fees, dust) = split_fee(1, [50, 50]).unwrap();
+        assert_eq!(fees, vec![0, 0]);
+        assert_eq!(dust, 1);
+    }
+
+    #[test]
+    fn split_fee_without_creators_is_all_dust() {
+        let (fees, dust) = split_fee(42, []).unwrap();
+        assert!(fees.is_empty());
+        assert_eq!(dust, 42);
+    }
+
+    #[test]
+    fn split_fee_rejects_shares_over_total() {
+        assert_eq!(
+            split_fee(100, [60, 60]),
+            Err(RoyaltyError::NumericalOverflow)
+        );
+    }
+}
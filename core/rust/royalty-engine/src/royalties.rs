@@ -0,0 +1,49 @@
+use std::slice::Iter;
+
+use mpl_token_metadata::state::{Creator, Metadata, TokenMetadataAccount};
+use solana_program::account_info::{next_account_info, AccountInfo};
+
+use crate::RoyaltyError;
+
+/// Royalties of an asset: the seller fee and the creators sharing it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Royalties {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+impl Royalties {
+    /// Reads the royalties of an asset from its metadata account.
+    ///
+    /// Items inheriting the royalties of their collection must provide the metadata of the
+    /// collection parent as the next remaining account.
+    pub fn read<'a>(
+        metadata_info: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> Result<Self, RoyaltyError> {
+        let metadata = Metadata::from_account_info(metadata_info)?;
+
+        if !metadata.inherit_royalties {
+            return Ok(Self::from(metadata));
+        }
+
+        let parent_info = next_account_info(remaining_accounts)
+            .map_err(|_| RoyaltyError::MissingCollectionMetadata)?;
+        let parent = Metadata::from_account_info(parent_info)?;
+        let (seller_fee_basis_points, creators) = metadata.effective_royalties(&parent)?;
+
+        Ok(Self {
+            seller_fee_basis_points: seller_fee_basis_points.into(),
+            creators: creators.unwrap_or_default(),
+        })
+    }
+}
+
+impl From<Metadata> for Royalties {
+    fn from(metadata: Metadata) -> Self {
+        Self {
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points.into(),
+            creators: metadata.data.creators.unwrap_or_default(),
+        }
+    }
+}
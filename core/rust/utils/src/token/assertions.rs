@@ -1,12 +1,16 @@
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
+/// Token programs supported: SPL Token and SPL Token-2022.
+pub const SPL_TOKEN_PROGRAM_IDS: [Pubkey; 2] = [spl_token::ID, spl_token_2022::ID];
+
 pub fn assert_token_program_matches_package(
     token_program_info: &AccountInfo,
     error: impl Into<ProgramError>,
 ) -> ProgramResult {
-    if *token_program_info.key != spl_token::id() {
+    if !SPL_TOKEN_PROGRAM_IDS.contains(token_program_info.key) {
         return Err(error.into());
     }
 
@@ -2,6 +2,8 @@ use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
 };
 
+use super::get_mint_decimals;
+
 pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
     let TokenBurnParams {
         mint,
@@ -16,7 +18,7 @@ pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::burn(
+        &spl_token_2022::instruction::burn(
             token_program.key,
             source.key,
             mint.key,
@@ -58,7 +60,7 @@ pub fn spl_token_close(params: TokenCloseParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::close_account(
+        &spl_token_2022::instruction::close_account(
             token_program.key,
             account.key,
             destination.key,
@@ -98,7 +100,7 @@ pub fn spl_token_mint_to(params: TokenMintToParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::mint_to(
+        &spl_token_2022::instruction::mint_to(
             token_program.key,
             mint.key,
             destination.key,
@@ -129,7 +131,7 @@ pub struct TokenMintToParams<'a: 'b, 'b> {
 
 pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult {
     let TokenTransferParams {
-        mint: _,
+        mint,
         source,
         destination,
         amount,
@@ -141,18 +143,39 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
     if let Some(seed) = authority_signer_seeds {
         seeds.push(seed);
     }
-    invoke_signed(
-        &spl_token::instruction::transfer(
-            token_program.key,
-            source.key,
-            destination.key,
-            authority.key,
-            &[authority.key],
-            amount,
-        )?,
-        &[source, destination, authority],
-        seeds.as_slice(),
-    )
+    // Token-2022 mints might have extensions that require the mint account (e.g.,
+    // transfer fees), so the "checked" variant is used
+    if *token_program.key == spl_token_2022::id() {
+        let decimals = get_mint_decimals(&mint)?;
+
+        invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program.key,
+                source.key,
+                mint.key,
+                destination.key,
+                authority.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[source, mint, destination, authority],
+            seeds.as_slice(),
+        )
+    } else {
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                source.key,
+                destination.key,
+                authority.key,
+                &[authority.key],
+                amount,
+            )?,
+            &[source, destination, authority],
+            seeds.as_slice(),
+        )
+    }
 }
 
 /// TokenTransferParams
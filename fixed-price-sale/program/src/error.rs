@@ -136,4 +136,16 @@ pub enum ErrorCode {
     // 6043
     #[msg("Wrong gating token")]
     WrongGatingToken,
+    // 6044
+    #[msg("Allowlist signature is missing or invalid")]
+    InvalidAllowlistSignature,
+    // 6045
+    #[msg("Allowlist signature is expired")]
+    AllowlistSignatureExpired,
+    // 6046
+    #[msg("User reach allowlist buy limit")]
+    UserReachAllowlistLimit,
+    // 6047
+    #[msg("Market requires an allowlist signature")]
+    AllowlistSignatureRequired,
 }
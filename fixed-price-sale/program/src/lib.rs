@@ -8,8 +8,8 @@ pub mod utils;
 use crate::{
     error::ErrorCode,
     state::{
-        Creator, GatingConfig, Market, PayoutTicket, PrimaryMetadataCreators, SellingResource,
-        Store, TradeHistory,
+        AllowlistSigner, Creator, GatingConfig, Market, PayoutTicket, PrimaryMetadataCreators,
+        SellingResource, Store, TradeHistory,
     },
     utils::*,
 };
@@ -55,6 +55,29 @@ pub mod fixed_price_sale {
         )
     }
 
+    pub fn buy_with_signature<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyWithSignature<'info>>,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        max_amount: u64,
+        expiry: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            _trade_history_bump,
+            vault_owner_bump,
+            max_amount,
+            expiry,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn set_allowlist_signer<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetAllowlistSigner<'info>>,
+        signer: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(signer)
+    }
+
     pub fn close_market<'info>(ctx: Context<'_, '_, '_, 'info, CloseMarket<'info>>) -> Result<()> {
         ctx.accounts.process()
     }
@@ -264,6 +287,30 @@ pub struct Buy<'info> {
     // metadata_account: UncheckedAccount<'info>
 }
 
+#[derive(Accounts)]
+#[instruction(trade_history:u8, vault_owner_bump: u8, max_amount: u64, expiry: u64)]
+pub struct BuyWithSignature<'info> {
+    buy: Buy<'info>,
+    #[account(seeds=[ALLOWLIST_PREFIX.as_bytes(), buy.market.key().as_ref()], bump)]
+    allowlist_signer: Box<Account<'info, AllowlistSigner>>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::id())]
+    /// CHECK: Instructions SYSVAR
+    instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(signer: Pubkey)]
+pub struct SetAllowlistSigner<'info> {
+    // markets created before the allowlist flag may need one more byte to store it
+    #[account(mut, has_one=owner, realloc=Market::LEN, realloc::payer=owner, realloc::zero=false)]
+    market: Account<'info, Market>,
+    #[account(mut)]
+    owner: Signer<'info>,
+    #[account(init_if_needed, space=AllowlistSigner::LEN, payer=owner, seeds=[ALLOWLIST_PREFIX.as_bytes(), market.key().as_ref()], bump)]
+    allowlist_signer: Box<Account<'info, AllowlistSigner>>,
+    system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(treasury_owner_bump: u8, payout_ticket_bump: u8)]
 pub struct Withdraw<'info> {
@@ -23,6 +23,21 @@ impl<'info> Buy<'info> {
         _trade_history_bump: u8,
         vault_owner_bump: u8,
         remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Check, that `Market` does not require an allowlist signature
+        if self.market.allowlist.enabled {
+            return Err(ErrorCode::AllowlistSignatureRequired.into());
+        }
+
+        self.purchase(_trade_history_bump, vault_owner_bump, remaining_accounts)
+    }
+
+    /// Buy a new edition from the market, once the purchase is authorized.
+    pub(crate) fn purchase(
+        &mut self,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         let market = &mut self.market;
         let selling_resource = &mut self.selling_resource;
@@ -0,0 +1,112 @@
+use crate::{error::ErrorCode, BuyWithSignature};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program, instruction::Instruction, sysvar::instructions::get_instruction_relative,
+};
+
+/// Size of the offsets of a signature in the data of an Ed25519 program instruction.
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// Start of the signature offsets, after the number of signatures and padding bytes.
+const SIGNATURE_OFFSETS_START: usize = 2;
+
+impl<'info> BuyWithSignature<'info> {
+    pub fn process(
+        &mut self,
+        _trade_history_bump: u8,
+        vault_owner_bump: u8,
+        max_amount: u64,
+        expiry: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let clock = &self.buy.clock;
+
+        // Check, that the allowlist signature is not expired
+        let now = u64::try_from(clock.unix_timestamp).map_err(|_| ErrorCode::MathOverflow)?;
+        if now > expiry {
+            return Err(ErrorCode::AllowlistSignatureExpired.into());
+        }
+
+        // Check, that the previous instruction verified the allowlist signature
+        let ed25519_ix = get_instruction_relative(-1, &self.instructions.to_account_info())
+            .map_err(|_| ErrorCode::InvalidAllowlistSignature)?;
+
+        let message = allowlist_message(
+            &self.buy.market.key(),
+            &self.buy.user_wallet.key(),
+            max_amount,
+            expiry,
+        );
+
+        verify_ed25519_instruction(&ed25519_ix, &self.allowlist_signer.signer, &message)?;
+
+        // Check, that user not reach the amount allowed by the signature
+        if self.buy.trade_history.already_bought >= max_amount {
+            return Err(ErrorCode::UserReachAllowlistLimit.into());
+        }
+
+        self.buy
+            .purchase(_trade_history_bump, vault_owner_bump, remaining_accounts)
+    }
+}
+
+/// Return the message signed by the allowlist signer to authorize `wallet` to buy up to
+/// `max_amount` pieces from `market` until `expiry`.
+pub fn allowlist_message(
+    market: &Pubkey,
+    wallet: &Pubkey,
+    max_amount: u64,
+    expiry: u64,
+) -> Vec<u8> {
+    [
+        market.as_ref(),
+        wallet.as_ref(),
+        &max_amount.to_le_bytes(),
+        &expiry.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Check, that `ix` is an Ed25519 program instruction verifying a single signature of
+/// `message` by `signer`, with all data contained in the instruction itself.
+fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    if ix.program_id != ed25519_program::id() || !ix.accounts.is_empty() {
+        return Err(ErrorCode::InvalidAllowlistSignature.into());
+    }
+
+    let data = &ix.data;
+
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN || data[0] != 1 {
+        return Err(ErrorCode::InvalidAllowlistSignature.into());
+    }
+
+    let read_u16 = |index: usize| -> usize {
+        let start = SIGNATURE_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[start], data[start + 1]]) as usize
+    };
+
+    // offsets: signature, signature ix, public key, public key ix, message, message size,
+    // message ix; the instruction indexes must point to the Ed25519 instruction itself
+    let public_key_offset = read_u16(2);
+    let message_offset = read_u16(4);
+    let message_size = read_u16(5);
+
+    if [read_u16(1), read_u16(3), read_u16(6)]
+        .iter()
+        .any(|index| *index != u16::MAX as usize)
+    {
+        return Err(ErrorCode::InvalidAllowlistSignature.into());
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidAllowlistSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAllowlistSignature)?;
+
+    if public_key != signer.as_ref() || signed_message != message {
+        return Err(ErrorCode::InvalidAllowlistSignature.into());
+    }
+
+    Ok(())
+}
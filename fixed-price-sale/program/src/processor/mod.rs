@@ -1,4 +1,5 @@
 pub mod buy;
+pub mod buy_with_signature;
 pub mod change_market;
 pub mod claim_resource;
 pub mod close_market;
@@ -7,5 +8,6 @@ pub mod create_store;
 pub mod init_selling_resource;
 pub mod resume_market;
 pub mod save_primary_metadata_creators;
+pub mod set_allowlist_signer;
 pub mod suspend_market;
 pub mod withdraw;
@@ -0,0 +1,17 @@
+use crate::{state::AllowlistFlag, SetAllowlistSigner};
+use anchor_lang::prelude::*;
+
+impl<'info> SetAllowlistSigner<'info> {
+    pub fn process(&mut self, signer: Pubkey) -> Result<()> {
+        let market = &self.market;
+        let allowlist_signer = &mut self.allowlist_signer;
+
+        allowlist_signer.market = market.key();
+        allowlist_signer.signer = signer;
+
+        // purchases now require a signature of the allowlist signer
+        self.market.allowlist = AllowlistFlag { enabled: true };
+
+        Ok(())
+    }
+}
@@ -115,13 +115,13 @@ impl<'info> Withdraw<'info> {
 
                 let funder_as_creator_share = calculate_secondary_shares_for_creator(
                     market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
-                    funder_creator.share as u64,
+                    metadata.data.seller_fee_basis_points,
+                    funder_creator.share,
                 )?;
 
                 let funder_as_market_owner_share = calculate_secondary_shares_for_market_owner(
                     market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    metadata.data.seller_fee_basis_points,
                 )?;
 
                 funder_as_creator_share
@@ -130,20 +130,17 @@ impl<'info> Withdraw<'info> {
             } else if let Some(funder_creator) = &funder_creator {
                 calculate_secondary_shares_for_creator(
                     market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
-                    funder_creator.share as u64,
+                    metadata.data.seller_fee_basis_points,
+                    funder_creator.share,
                 )?
             } else {
                 calculate_secondary_shares_for_market_owner(
                     market.funds_collected,
-                    metadata.data.seller_fee_basis_points as u64,
+                    metadata.data.seller_fee_basis_points,
                 )?
             }
         } else if let Some(funder_creator) = funder_creator {
-            calculate_primary_shares_for_creator(
-                market.funds_collected,
-                funder_creator.share as u64,
-            )?
+            calculate_primary_shares_for_creator(market.funds_collected, funder_creator.share)?
         } else {
             return Err(ErrorCode::MarketOwnerDoesntHaveShares.into());
         };
@@ -72,6 +72,8 @@ pub struct Market {
     // need this field to calculate royalties at withdraw
     pub funds_collected: u64,
     pub gatekeeper: Option<GatingConfig>,
+    // purchases of allowlisted markets are only possible with `buy_with_signature`
+    pub allowlist: AllowlistFlag,
 }
 
 impl Market {
@@ -94,7 +96,39 @@ impl Market {
         + 1
         + 32
         + 1
-        + 9;
+        + 9
+        + 1;
+}
+
+/// Allowlist flag of a `Market`, stored after its original fields.
+///
+/// Markets created before the flag was added may not have space left for it, so it is only
+/// written once enabled and reads as disabled when missing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllowlistFlag {
+    pub enabled: bool,
+}
+
+impl AnchorSerialize for AllowlistFlag {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.enabled {
+            self.enabled.serialize(writer)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl AnchorDeserialize for AllowlistFlag {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let enabled = if buf.is_empty() {
+            false
+        } else {
+            bool::deserialize(buf)?
+        };
+
+        Ok(Self { enabled })
+    }
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
@@ -137,6 +171,19 @@ impl PrimaryMetadataCreators {
     pub const LEN: usize = 8 + ((32 + 1 + 1) * MAX_PRIMARY_CREATORS_LEN + 1);
 }
 
+/// Signer of the off-chain allowlist of a `Market`, which authorizes purchases through
+/// `buy_with_signature`.
+#[account]
+#[derive(Default)]
+pub struct AllowlistSigner {
+    pub market: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl AllowlistSigner {
+    pub const LEN: usize = 8 + 32 + 32;
+}
+
 #[account]
 #[derive(Default)]
 pub struct PayoutTicket {
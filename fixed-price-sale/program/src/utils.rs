@@ -5,6 +5,7 @@ use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke_signed, system_instruction},
 };
+use mpl_royalty_engine::{creator_fee, royalty_fee, seller_proceeds, RoyaltyError};
 
 pub const NAME_MAX_LEN: usize = 40; // max len of a string buffer in bytes
 pub const NAME_DEFAULT_SIZE: usize = 4 + NAME_MAX_LEN; // max lenght of serialized string (str_len + <buffer>)
@@ -15,6 +16,7 @@ pub const HISTORY_PREFIX: &str = "history";
 pub const VAULT_OWNER_PREFIX: &str = "mt_vault";
 pub const PAYOUT_TICKET_PREFIX: &str = "payout_ticket";
 pub const PRIMARY_METADATA_CREATORS_PREFIX: &str = "primary_creators";
+pub const ALLOWLIST_PREFIX: &str = "allowlist";
 pub const FLAG_ACCOUNT_SIZE: usize = 1; // Size for flag account to indicate something
 pub const MAX_PRIMARY_CREATORS_LEN: usize = 5; // Total allowed creators in `PrimaryMetadataCreators`
 
@@ -85,6 +87,11 @@ pub fn find_primary_metadata_creators(metadata: &Pubkey) -> (Pubkey, u8) {
     )
 }
 
+/// Return `AllowlistSigner` `Pubkey` and bump seed.
+pub fn find_allowlist_signer_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWLIST_PREFIX.as_bytes(), market.as_ref()], &id())
+}
+
 /// Wrapper of `create_account` instruction from `system_program` program
 #[inline(always)]
 pub fn sys_create_account<'a>(
@@ -250,41 +257,36 @@ pub fn assert_keys_equal(key1: Pubkey, key2: Pubkey) -> Result<()> {
     }
 }
 
-pub fn calculate_primary_shares_for_creator(total_amount: u64, shares: u64) -> Result<u64> {
-    Ok(total_amount
-        .checked_mul(shares)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(100)
-        .ok_or(ErrorCode::MathOverflow)?)
+/// Creator share of the funds collected by a primary sale, where all funds go to the creators
+pub fn calculate_primary_shares_for_creator(total_amount: u64, share: u8) -> Result<u64> {
+    creator_fee(total_amount, share).map_err(royalty_error)
 }
 
+/// Creator share of the royalties of the funds collected by a secondary sale
 pub fn calculate_secondary_shares_for_creator(
     total_amount: u64,
-    seller_fee_basis_points: u64,
-    shares: u64,
+    seller_fee_basis_points: u16,
+    share: u8,
 ) -> Result<u64> {
-    Ok((total_amount
-        .checked_mul(seller_fee_basis_points)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::MathOverflow)?)
-    .checked_mul(shares)
-    .ok_or(ErrorCode::MathOverflow)?
-    .checked_div(100)
-    .ok_or(ErrorCode::MathOverflow)?)
+    royalty_fee(total_amount, seller_fee_basis_points)
+        .and_then(|fee| creator_fee(fee, share))
+        .map_err(royalty_error)
 }
 
+/// Market owner share of the funds collected by a secondary sale, once royalties are deducted
 pub fn calculate_secondary_shares_for_market_owner(
     total_amount: u64,
-    seller_fee_basis_points: u64,
+    seller_fee_basis_points: u16,
 ) -> Result<u64> {
-    Ok(total_amount
-        .checked_sub(
-            total_amount
-                .checked_mul(seller_fee_basis_points)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(ErrorCode::MathOverflow)?,
-        )
-        .ok_or(ErrorCode::MathOverflow)?)
+    seller_proceeds(total_amount, seller_fee_basis_points).map_err(royalty_error)
+}
+
+/// Map royalty engine errors into program errors
+fn royalty_error(error: RoyaltyError) -> Error {
+    match error {
+        RoyaltyError::NumericalOverflow | RoyaltyError::InvalidBasisPoints => {
+            ErrorCode::MathOverflow.into()
+        }
+        error => ProgramError::from(error).into(),
+    }
 }
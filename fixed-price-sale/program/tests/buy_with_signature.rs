@@ -0,0 +1,604 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod buy_with_signature {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::{airdrop, create_mint, create_token_account, mint_to},
+            setup_functions::{setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{
+        error::ERROR_CODE_OFFSET, AccountDeserialize, InstructionData, ToAccountMetas,
+    };
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts,
+        error::ErrorCode,
+        instruction as mpl_fixed_price_sale_instruction,
+        processor::buy_with_signature::allowlist_message,
+        state::{SellingResource, TradeHistory},
+        utils::{
+            find_allowlist_signer_address, find_trade_history_address, find_treasury_owner_address,
+            find_vault_owner_address,
+        },
+    };
+    use solana_program::clock::Clock;
+    use solana_program_test::*;
+    use solana_sdk::{
+        commitment_config::CommitmentLevel,
+        ed25519_program,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        system_program, sysvar,
+        transaction::{Transaction, TransactionError},
+    };
+
+    /// Builds an Ed25519 program instruction with the signature of `signer` over `message`,
+    /// keeping the public key, signature and message in the instruction data.
+    fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+        const DATA_START: u16 = 16;
+        const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+        let public_key_offset = DATA_START;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            CURRENT_INSTRUCTION,
+            public_key_offset,
+            CURRENT_INSTRUCTION,
+            message_offset,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.pubkey().as_ref());
+        data.extend_from_slice(signer.sign_message(message).as_ref());
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::id(),
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn assert_custom_error(err: BanksClientError, index: u8, error: ErrorCode) {
+        match err {
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                err_index,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(err_index, index);
+                assert_eq!(err_code, ERROR_CODE_OFFSET + error as u32);
+            }
+            _ => assert!(false),
+        }
+    }
+
+    /// Creates a started market selling editions for native SOL without a limit of pieces
+    /// per wallet, with `signer` as its allowlist signer.
+    async fn setup_allowlisted_market(
+        context: &mut ProgramTestContext,
+        signer: &Pubkey,
+    ) -> (Keypair, Keypair) {
+        let (admin_wallet, store_keypair) = setup_store(context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _vault) =
+            setup_selling_resource(
+                context,
+                &admin_wallet,
+                &store_keypair,
+                100,
+                None,
+                true,
+                false,
+            )
+            .await;
+
+        airdrop(
+            context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = Keypair::new();
+
+        let treasury_mint = system_program::id();
+
+        let (treasury_owner, treasyry_owner_bump) =
+            find_treasury_owner_address(&treasury_mint, &selling_resource_keypair.pubkey());
+
+        let start_date = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+            + 1;
+
+        // CreateMarket
+        let accounts = mpl_fixed_price_sale_accounts::CreateMarket {
+            market: market_keypair.pubkey(),
+            store: store_keypair.pubkey(),
+            selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+            selling_resource: selling_resource_keypair.pubkey(),
+            mint: treasury_mint,
+            treasury_holder: treasury_owner,
+            owner: treasury_owner,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::CreateMarket {
+            _treasury_owner_bump: treasyry_owner_bump,
+            name: "Marktname".to_string(),
+            description: "Marktbeschreibung".to_string(),
+            mutable: true,
+            price: 1_000_000,
+            pieces_in_one_wallet: None,
+            start_date: start_date as u64,
+            end_date: None,
+            gating_config: None,
+        }
+        .data();
+
+        let create_market_ix = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        // SetAllowlistSigner
+        let (allowlist_signer, _) = find_allowlist_signer_address(&market_keypair.pubkey());
+
+        let accounts = mpl_fixed_price_sale_accounts::SetAllowlistSigner {
+            market: market_keypair.pubkey(),
+            owner: selling_resource_owner_keypair.pubkey(),
+            allowlist_signer,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::SetAllowlistSigner { signer: *signer }.data();
+
+        let set_allowlist_signer_ix = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_market_ix, set_allowlist_signer_ix],
+            Some(&context.payer.pubkey()),
+            &[
+                &context.payer,
+                &market_keypair,
+                &selling_resource_owner_keypair,
+            ],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.warp_to_slot(clock.slot + 1500).unwrap();
+
+        (market_keypair, selling_resource_keypair)
+    }
+
+    /// Creates the accounts of `user_wallet` to buy a new edition from the market, returning
+    /// the `Buy` accounts with the trade history and vault owner bumps.
+    async fn setup_buy(
+        context: &mut ProgramTestContext,
+        market: &Pubkey,
+        selling_resource_key: &Pubkey,
+        user_wallet: &Keypair,
+    ) -> (mpl_fixed_price_sale_accounts::Buy, u8, u8) {
+        let selling_resource_data = context
+            .banks_client
+            .get_account(*selling_resource_key)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let selling_resource =
+            SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+        let (trade_history, trade_history_bump) =
+            find_trade_history_address(&user_wallet.pubkey(), market);
+        let (owner, vault_owner_bump) =
+            find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+        let (treasury_owner, _) =
+            find_treasury_owner_address(&system_program::id(), selling_resource_key);
+
+        let new_mint_keypair = Keypair::new();
+        create_mint(context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+        let new_mint_token_account = Keypair::new();
+        create_token_account(
+            context,
+            &new_mint_token_account,
+            &new_mint_keypair.pubkey(),
+            &user_wallet.pubkey(),
+        )
+        .await;
+
+        mint_to(
+            context,
+            &new_mint_keypair.pubkey(),
+            &new_mint_token_account.pubkey(),
+            user_wallet,
+            1,
+        )
+        .await;
+
+        let (master_edition_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (master_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (edition_marker, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+                selling_resource.supply.to_string().as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                new_mint_keypair.pubkey().as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        let accounts = mpl_fixed_price_sale_accounts::Buy {
+            market: *market,
+            selling_resource: *selling_resource_key,
+            user_token_account: user_wallet.pubkey(),
+            user_wallet: user_wallet.pubkey(),
+            trade_history,
+            treasury_holder: treasury_owner,
+            new_metadata,
+            new_edition,
+            master_edition,
+            new_mint: new_mint_keypair.pubkey(),
+            edition_marker,
+            vault: selling_resource.vault,
+            owner,
+            new_token_account: new_mint_token_account.pubkey(),
+            master_edition_metadata,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        };
+
+        (accounts, trade_history_bump, vault_owner_bump)
+    }
+
+    /// Builds the Ed25519 and `buy_with_signature` instructions of a purchase authorized by
+    /// `signer`.
+    async fn buy_with_signature_instructions(
+        context: &mut ProgramTestContext,
+        market: &Pubkey,
+        selling_resource: &Pubkey,
+        user_wallet: &Keypair,
+        signer: &Keypair,
+        max_amount: u64,
+        expiry: u64,
+    ) -> Vec<Instruction> {
+        let (buy, trade_history_bump, vault_owner_bump) =
+            setup_buy(context, market, selling_resource, user_wallet).await;
+
+        let (allowlist_signer, _) = find_allowlist_signer_address(market);
+
+        let accounts = mpl_fixed_price_sale_accounts::BuyWithSignature {
+            buy,
+            allowlist_signer,
+            instructions: sysvar::instructions::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::BuyWithSignature {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+            max_amount,
+            expiry,
+        }
+        .data();
+
+        let message = allowlist_message(market, &user_wallet.pubkey(), max_amount, expiry);
+
+        vec![
+            ed25519_instruction(signer, &message),
+            Instruction {
+                program_id: mpl_fixed_price_sale::id(),
+                data,
+                accounts,
+            },
+        ]
+    }
+
+    async fn now(context: &mut ProgramTestContext) -> u64 {
+        context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp as u64
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+
+        let signer = Keypair::new();
+        let (market_keypair, selling_resource_keypair) =
+            setup_allowlisted_market(&mut context, &signer.pubkey()).await;
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let expiry = now(&mut context).await + 3_600;
+        let instructions = buy_with_signature_instructions(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &user_wallet,
+            &signer,
+            1,
+            expiry,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+
+        let (trade_history, _) =
+            find_trade_history_address(&user_wallet.pubkey(), &market_keypair.pubkey());
+        let trade_history_acc = context
+            .banks_client
+            .get_account(trade_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let trade_history_data =
+            TradeHistory::try_deserialize(&mut trade_history_acc.data.as_ref()).unwrap();
+
+        assert_eq!(trade_history_data.already_bought, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_wrong_signer() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+
+        let signer = Keypair::new();
+        let (market_keypair, selling_resource_keypair) =
+            setup_allowlisted_market(&mut context, &signer.pubkey()).await;
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let expiry = now(&mut context).await + 3_600;
+        let instructions = buy_with_signature_instructions(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &user_wallet,
+            &Keypair::new(),
+            1,
+            expiry,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap_err();
+
+        assert_custom_error(err, 1, ErrorCode::InvalidAllowlistSignature);
+    }
+
+    #[tokio::test]
+    async fn fail_replayed_signature() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+
+        let signer = Keypair::new();
+        let (market_keypair, selling_resource_keypair) =
+            setup_allowlisted_market(&mut context, &signer.pubkey()).await;
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let expiry = now(&mut context).await + 3_600;
+        let instructions = buy_with_signature_instructions(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &user_wallet,
+            &signer,
+            1,
+            expiry,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+
+        // the signature allows a single piece, so it cannot be used again
+
+        let last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap_err();
+
+        assert_custom_error(err, 1, ErrorCode::UserReachAllowlistLimit);
+    }
+
+    #[tokio::test]
+    async fn fail_expired_signature() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+
+        let signer = Keypair::new();
+        let (market_keypair, selling_resource_keypair) =
+            setup_allowlisted_market(&mut context, &signer.pubkey()).await;
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let expiry = now(&mut context).await - 1;
+        let instructions = buy_with_signature_instructions(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &user_wallet,
+            &signer,
+            1,
+            expiry,
+        )
+        .await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap_err();
+
+        assert_custom_error(err, 1, ErrorCode::AllowlistSignatureExpired);
+    }
+
+    #[tokio::test]
+    async fn fail_buy_without_signature() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+
+        let signer = Keypair::new();
+        let (market_keypair, selling_resource_keypair) =
+            setup_allowlisted_market(&mut context, &signer.pubkey()).await;
+
+        let user_wallet = Keypair::new();
+        airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await;
+
+        let (buy, trade_history_bump, vault_owner_bump) = setup_buy(
+            &mut context,
+            &market_keypair.pubkey(),
+            &selling_resource_keypair.pubkey(),
+            &user_wallet,
+        )
+        .await;
+
+        let data = mpl_fixed_price_sale_instruction::Buy {
+            _trade_history_bump: trade_history_bump,
+            vault_owner_bump,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts: buy.to_account_metas(None),
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user_wallet],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap_err();
+
+        assert_custom_error(err, 0, ErrorCode::AllowlistSignatureRequired);
+    }
+}
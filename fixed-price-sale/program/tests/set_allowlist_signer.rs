@@ -0,0 +1,193 @@
+mod utils;
+
+#[cfg(feature = "test-bpf")]
+mod set_allowlist_signer {
+    use crate::{
+        setup_context,
+        utils::{
+            helpers::airdrop,
+            setup_functions::{setup_market, setup_selling_resource, setup_store},
+        },
+    };
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use mpl_fixed_price_sale::{
+        accounts as mpl_fixed_price_sale_accounts, instruction as mpl_fixed_price_sale_instruction,
+        state::{AllowlistSigner, Market},
+        utils::find_allowlist_signer_address,
+    };
+    use solana_program_test::*;
+    use solana_sdk::{
+        commitment_config::CommitmentLevel,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        system_program,
+        transaction::{Transaction, TransactionError},
+    };
+
+    fn set_allowlist_signer_instruction(
+        market: &Pubkey,
+        owner: &Pubkey,
+        signer: Pubkey,
+    ) -> Instruction {
+        let (allowlist_signer, _) = find_allowlist_signer_address(market);
+
+        let accounts = mpl_fixed_price_sale_accounts::SetAllowlistSigner {
+            market: *market,
+            owner: *owner,
+            allowlist_signer,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None);
+
+        let data = mpl_fixed_price_sale_instruction::SetAllowlistSigner { signer }.data();
+
+        Instruction {
+            program_id: mpl_fixed_price_sale::id(),
+            data,
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn success() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let signer = Keypair::new();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_allowlist_signer_instruction(
+                &market_keypair.pubkey(),
+                &selling_resource_owner_keypair.pubkey(),
+                signer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &selling_resource_owner_keypair],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+
+        let (allowlist_signer, _) = find_allowlist_signer_address(&market_keypair.pubkey());
+        let allowlist_signer_acc = context
+            .banks_client
+            .get_account(allowlist_signer)
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let allowlist_signer_data =
+            AllowlistSigner::try_deserialize(&mut allowlist_signer_acc.data.as_ref()).unwrap();
+        assert_eq!(allowlist_signer_data.market, market_keypair.pubkey());
+        assert_eq!(allowlist_signer_data.signer, signer.pubkey());
+
+        let market_acc = context
+            .banks_client
+            .get_account(market_keypair.pubkey())
+            .await
+            .expect("account not found")
+            .expect("account empty");
+
+        let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+        assert!(market_data.allowlist.enabled);
+    }
+
+    #[tokio::test]
+    async fn fail_wrong_owner() {
+        setup_context!(context, mpl_fixed_price_sale, mpl_token_metadata);
+        let (admin_wallet, store_keypair) = setup_store(&mut context).await;
+
+        let (selling_resource_keypair, selling_resource_owner_keypair, _) = setup_selling_resource(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            100,
+            None,
+            true,
+            false,
+        )
+        .await;
+
+        airdrop(
+            &mut context,
+            &selling_resource_owner_keypair.pubkey(),
+            10_000_000_000,
+        )
+        .await;
+
+        let market_keypair = setup_market(
+            &mut context,
+            &admin_wallet,
+            &store_keypair,
+            &selling_resource_keypair,
+            &selling_resource_owner_keypair,
+        )
+        .await;
+
+        let fake_owner = Keypair::new();
+        airdrop(&mut context, &fake_owner.pubkey(), 10_000_000_000).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_allowlist_signer_instruction(
+                &market_keypair.pubkey(),
+                &fake_owner.pubkey(),
+                fake_owner.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &fake_owner],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction_with_commitment(tx, CommitmentLevel::Confirmed)
+            .await
+            .unwrap_err();
+
+        match err {
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(err_code),
+            )) => {
+                assert_eq!(
+                    err_code,
+                    anchor_lang::error::ErrorCode::ConstraintHasOne as u32
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+}
@@ -66,7 +66,8 @@ pub fn set_token_member_stake(ctx: Context<SetTokenMemberStake>, shares: u64) ->
     membership_voucher.membership_key = member.key();
     fanout.total_staked_shares = fanout
         .total_staked_shares
-        .and_then(|ss| ss.checked_add(shares));
+        .map(|ss| ss.checked_add(shares).or_arith_error())
+        .transpose()?;
     fanout.total_shares = membership_mint.supply;
     fanout.total_members = fanout.total_members.checked_add(1).or_arith_error()?;
     membership_voucher.shares = shares;
@@ -62,8 +62,8 @@ pub fn unstake(ctx: Context<UnStakeTokenMember>) -> Result<()> {
     let amount = ctx.accounts.member_stake_account.amount;
     fanout.total_staked_shares = fanout
         .total_staked_shares
-        .to_owned()
-        .map(|tss| tss.checked_sub(amount).or_arith_error().unwrap());
+        .map(|tss| tss.checked_sub(amount).or_arith_error())
+        .transpose()?;
     fanout.total_shares = membership_mint.supply;
     fanout.total_members = fanout.total_members.checked_sub(1).or_arith_error()?;
     let stake_account_info = ctx.accounts.member_stake_account.to_account_info();
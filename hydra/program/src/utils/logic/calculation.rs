@@ -23,6 +23,24 @@ pub fn calculate_dist_amount(
     Ok(dist_amount as u64)
 }
 
+/// Returns the inflow owed to the unstaked supply of the membership mint of a `Token`
+/// membership fanout, which is added to the total inflow so that the inflow is distributed
+/// proportionally to the staked shares only.
+pub fn calculate_unstaked_correction(fanout: &Fanout, inflow_diff: u64) -> Result<u64> {
+    match fanout.total_staked_shares {
+        Some(tss) if tss > 0 => {
+            let shares_diff = fanout.total_shares.checked_sub(tss).or_arith_error()?;
+            let unstaked_correction = (inflow_diff as u128)
+                .checked_mul(shares_diff as u128)
+                .or_arith_error()?
+                .checked_div(tss as u128)
+                .or_arith_error()?;
+            u64::try_from(unstaked_correction).map_err(|_| HydraError::NumericalOverflow.into())
+        }
+        _ => Ok(0),
+    }
+}
+
 pub fn update_fanout_for_add(fanout: &mut Account<Fanout>, shares: u64) -> Result<()> {
     let less_shares = fanout
         .total_available_shares
@@ -54,18 +72,10 @@ pub fn update_inflow_for_mint(
         .total_inflow
         .checked_add(diff)
         .or_arith_error()?;
-    if fanout.total_staked_shares.is_some() && fanout.total_staked_shares.unwrap() > 0 {
-        let tss = fanout.total_staked_shares.unwrap();
-        let shares_diff = (fanout.total_shares as u64)
-            .checked_sub(tss)
-            .or_arith_error()?;
-        let unstaked_correction = (diff as u128)
-            .checked_mul(shares_diff as u128)
-            .or_arith_error()?
-            .checked_div(tss as u128)
-            .or_arith_error()? as u64;
-        fanout_for_mint.total_inflow += unstaked_correction;
-    }
+    fanout_for_mint.total_inflow = fanout_for_mint
+        .total_inflow
+        .checked_add(calculate_unstaked_correction(fanout, diff)?)
+        .or_arith_error()?;
     fanout_for_mint.last_snapshot_amount = current_snapshot;
     Ok(())
 }
@@ -75,18 +85,10 @@ pub fn update_inflow(fanout: &mut Fanout, current_snapshot: u64) -> Result<()> {
         .checked_sub(fanout.last_snapshot_amount)
         .or_arith_error()?;
     fanout.total_inflow = fanout.total_inflow.checked_add(diff).or_arith_error()?;
-    if fanout.total_staked_shares.is_some() && fanout.total_staked_shares.unwrap() > 0 {
-        let tss = fanout.total_staked_shares.unwrap();
-        let shares_diff = (fanout.total_shares as u64)
-            .checked_sub(tss)
-            .or_arith_error()?;
-        let unstaked_correction = (diff as u128)
-            .checked_mul(shares_diff as u128)
-            .or_arith_error()?
-            .checked_div(tss as u128)
-            .or_arith_error()? as u64;
-        fanout.total_inflow += unstaked_correction;
-    }
+    fanout.total_inflow = fanout
+        .total_inflow
+        .checked_add(calculate_unstaked_correction(fanout, diff)?)
+        .or_arith_error()?;
     fanout.last_snapshot_amount = current_snapshot;
     Ok(())
 }
@@ -127,3 +129,59 @@ pub fn current_lamports(
         .checked_sub(subtract_size)
         .ok_or_else(|| HydraError::NumericalOverflow.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_fanout(total_shares: u64, total_staked_shares: Option<u64>) -> Fanout {
+        Fanout {
+            total_shares,
+            total_staked_shares,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unstaked_correction_without_staked_shares() {
+        // fanouts that are not stake-weighted, or without any stake, are not corrected
+        let fanout = token_fanout(100, None);
+        assert_eq!(calculate_unstaked_correction(&fanout, 1_000).unwrap(), 0);
+
+        let fanout = token_fanout(100, Some(0));
+        assert_eq!(calculate_unstaked_correction(&fanout, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_unstaked_correction_with_full_stake() {
+        let fanout = token_fanout(100, Some(100));
+        assert_eq!(calculate_unstaked_correction(&fanout, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_unstaked_correction_after_partial_unstake() {
+        // 25 of the 100 shares were unstaked, so the inflow of 750 is scaled up to 1000
+        // and each of the 75 staked shares receives 10
+        let mut fanout = token_fanout(100, Some(75));
+        assert_eq!(calculate_unstaked_correction(&fanout, 750).unwrap(), 250);
+
+        update_inflow(&mut fanout, 750).unwrap();
+        assert_eq!(fanout.total_inflow, 1_000);
+        assert_eq!(fanout.last_snapshot_amount, 750);
+        assert_eq!(
+            calculate_dist_amount(75, fanout.total_inflow, fanout.total_shares).unwrap(),
+            750
+        );
+    }
+
+    #[test]
+    fn test_unstaked_correction_overflow() {
+        // the correction does not fit in a u64
+        let fanout = token_fanout(u64::MAX, Some(1));
+        assert!(calculate_unstaked_correction(&fanout, 2).is_err());
+
+        // more shares staked than the supply of the membership mint
+        let fanout = token_fanout(10, Some(11));
+        assert!(calculate_unstaked_correction(&fanout, 1).is_err());
+    }
+}
@@ -5,13 +5,23 @@ pub mod utils;
 use crate::utils::*;
 use anchor_lang::{
     prelude::*,
-    solana_program::program::{invoke, invoke_signed},
+    solana_program::{
+        program::{invoke, invoke_signed},
+        program_pack::Pack,
+    },
     AnchorDeserialize, AnchorSerialize,
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
 };
+use mpl_bubblegum::{
+    hash_creators, hash_metadata,
+    program::Bubblegum,
+    state::{leaf_schema::LeafSchema, metaplex_adapter::MetadataArgs, TreeConfig},
+    utils::get_asset_id,
+};
+use spl_account_compression::{program::SplAccountCompression, Noop};
 
 anchor_lang::declare_id!("qntmGodpGkrM42mN68VCZHXnKqDCT8rdY23wFcXCLPd");
 
@@ -19,6 +29,7 @@ const PREFIX: &str = "token_entangler";
 const ESCROW: &str = "escrow";
 const A_NAME: &str = "A";
 const B_NAME: &str = "B";
+const MINT: &str = "mint";
 #[program]
 pub mod token_entangler {
     use spl_token::amount_to_ui_amount;
@@ -301,7 +312,6 @@ pub mod token_entangler {
                 &ata_program.to_account_info(),
                 &token_program.to_account_info(),
                 &system_program.to_account_info(),
-                &rent.to_account_info(),
                 entangled_pair.price,
                 is_native,
             )?;
@@ -309,6 +319,482 @@ pub mod token_entangler {
         entangled_pair.paid = true;
         Ok(())
     }
+
+    /// Creates an entangled pair between a compressed (Bubblegum) asset and its standard form.
+    ///
+    /// The leaf is verified against the tree with the `metadata` of the asset, and the
+    /// standard NFT (B) is minted through Token Metadata with the same metadata into the escrow
+    /// of the pair, while the compressed asset stays with its owner, who can swap it for the
+    /// NFT at any time. Bubblegum decompression burns the leaf and cannot be reversed, so the
+    /// pair keeps whichever form is not held by the user instead. The remaining accounts are
+    /// the proof of the leaf.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_compressed_entangled_pair<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCompressedEntangledPair<'info>>,
+        asset_id: Pubkey,
+        nonce: u64,
+        index: u32,
+        root: [u8; 32],
+        metadata: MetadataArgs,
+        token_b_escrow_bump: u8,
+        price: u64,
+        pays_every_time: bool,
+    ) -> Result<()> {
+        let treasury_mint = &ctx.accounts.treasury_mint;
+        let payer = &ctx.accounts.payer;
+        let authority = &ctx.accounts.authority;
+        let tree_authority = &ctx.accounts.tree_authority;
+        let merkle_tree = &ctx.accounts.merkle_tree;
+        let mint_b = &ctx.accounts.mint_b;
+        let metadata_b = &ctx.accounts.metadata_b;
+        let edition_b = &ctx.accounts.edition_b;
+        let token_b_escrow = &ctx.accounts.token_b_escrow;
+        let entangled_pair = &mut ctx.accounts.entangled_pair;
+        let token_metadata_program = &ctx.accounts.token_metadata_program;
+        let token_program = &ctx.accounts.token_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent = &ctx.accounts.rent;
+
+        if get_asset_id(&merkle_tree.key(), nonce) != asset_id {
+            return Err(ErrorCode::InvalidAsset.into());
+        }
+
+        // only the creator or the delegate of the tree entangles its assets
+        if authority.key() != tree_authority.tree_creator
+            && authority.key() != tree_authority.tree_delegate
+        {
+            return Err(ErrorCode::InvalidTreeAuthority.into());
+        }
+
+        // the leaf must be in the tree with the given metadata
+        let leaf = LeafSchema::new_v0(
+            asset_id,
+            ctx.accounts.leaf_owner.key(),
+            ctx.accounts.leaf_delegate.key(),
+            nonce,
+            hash_metadata(&metadata)?,
+            hash_creators(&metadata.creators)?,
+        );
+
+        spl_account_compression::cpi::verify_leaf(
+            CpiContext::new(
+                ctx.accounts.compression_program.to_account_info(),
+                spl_account_compression::cpi::accounts::VerifyLeaf {
+                    merkle_tree: merkle_tree.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            root,
+            leaf.to_node(),
+            index,
+        )?;
+
+        entangled_pair.bump = *ctx
+            .bumps
+            .get("entangled_pair")
+            .ok_or(ErrorCode::BumpSeedNotInHashMap)?;
+        entangled_pair.token_b_escrow_bump = token_b_escrow_bump;
+        entangled_pair.price = price;
+        entangled_pair.pays_every_time = pays_every_time;
+        entangled_pair.authority = authority.key();
+        entangled_pair.asset_id = asset_id;
+        entangled_pair.merkle_tree = merkle_tree.key();
+        entangled_pair.nonce = nonce;
+        entangled_pair.mint_b = mint_b.key();
+        entangled_pair.token_b_escrow = token_b_escrow.key();
+        entangled_pair.treasury_mint = treasury_mint.key();
+
+        let mint_b_key = mint_b.key();
+        let mint_b_seeds = [
+            PREFIX.as_bytes(),
+            asset_id.as_ref(),
+            MINT.as_bytes(),
+            &[*ctx
+                .bumps
+                .get("mint_b")
+                .ok_or(ErrorCode::BumpSeedNotInHashMap)?],
+        ];
+        let token_b_escrow_seeds = [
+            PREFIX.as_bytes(),
+            asset_id.as_ref(),
+            mint_b_key.as_ref(),
+            ESCROW.as_bytes(),
+            B_NAME.as_bytes(),
+            &[token_b_escrow_bump],
+        ];
+        let entangled_pair_seeds = [
+            PREFIX.as_bytes(),
+            asset_id.as_ref(),
+            mint_b_key.as_ref(),
+            &[entangled_pair.bump],
+        ];
+
+        // mints the standard NFT into the escrow of the pair
+
+        create_or_allocate_account_raw(
+            *token_program.key,
+            &mint_b.to_account_info(),
+            &rent.to_account_info(),
+            system_program,
+            payer,
+            spl_token::state::Mint::LEN,
+            &[],
+            &mint_b_seeds,
+        )?;
+
+        invoke(
+            &spl_token::instruction::initialize_mint2(
+                token_program.key,
+                &mint_b_key,
+                &entangled_pair.key(),
+                Some(&entangled_pair.key()),
+                0,
+            )?,
+            &[mint_b.to_account_info(), token_program.to_account_info()],
+        )?;
+
+        create_program_token_account_if_not_present(
+            token_b_escrow,
+            system_program,
+            payer,
+            token_program,
+            &mint_b.to_account_info(),
+            &entangled_pair.to_account_info(),
+            rent,
+            &token_b_escrow_seeds,
+            &[],
+        )?;
+
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                token_program.key,
+                &mint_b_key,
+                &token_b_escrow.key(),
+                &entangled_pair.key(),
+                &[],
+                1,
+            )?,
+            &[
+                mint_b.to_account_info(),
+                token_b_escrow.to_account_info(),
+                entangled_pair.to_account_info(),
+                token_program.to_account_info(),
+            ],
+            &[&entangled_pair_seeds],
+        )?;
+
+        // creators and collection cannot be verified on behalf of their owners
+        let creators = metadata
+            .creators
+            .iter()
+            .map(|creator| mpl_token_metadata::state::Creator {
+                address: creator.address,
+                verified: false,
+                share: creator.share,
+            })
+            .collect::<Vec<_>>();
+
+        invoke_signed(
+            &mpl_token_metadata::instruction::create_metadata_accounts_v3(
+                token_metadata_program.key(),
+                metadata_b.key(),
+                mint_b_key,
+                entangled_pair.key(),
+                payer.key(),
+                authority.key(),
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+                if creators.is_empty() {
+                    None
+                } else {
+                    Some(creators)
+                },
+                metadata.seller_fee_basis_points,
+                true,
+                metadata.is_mutable,
+                metadata
+                    .collection
+                    .map(|collection| mpl_token_metadata::state::Collection {
+                        verified: false,
+                        key: collection.key,
+                    }),
+                None,
+                None,
+            ),
+            &[
+                metadata_b.to_account_info(),
+                mint_b.to_account_info(),
+                entangled_pair.to_account_info(),
+                payer.to_account_info(),
+                authority.to_account_info(),
+                token_metadata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+            ],
+            &[&entangled_pair_seeds],
+        )?;
+
+        invoke_signed(
+            &mpl_token_metadata::instruction::create_master_edition_v3(
+                token_metadata_program.key(),
+                edition_b.key(),
+                mint_b_key,
+                authority.key(),
+                entangled_pair.key(),
+                metadata_b.key(),
+                payer.key(),
+                Some(0),
+            ),
+            &[
+                edition_b.to_account_info(),
+                mint_b.to_account_info(),
+                authority.to_account_info(),
+                entangled_pair.to_account_info(),
+                payer.to_account_info(),
+                metadata_b.to_account_info(),
+                token_metadata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+            ],
+            &[&entangled_pair_seeds],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_compressed_entangled_pair<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateCompressedEntangledPair<'info>>,
+        price: u64,
+        pays_every_time: bool,
+    ) -> Result<()> {
+        let new_authority = &ctx.accounts.new_authority;
+        let entangled_pair = &mut ctx.accounts.entangled_pair;
+
+        entangled_pair.authority = new_authority.key();
+        entangled_pair.pays_every_time = pays_every_time;
+        entangled_pair.price = price;
+        Ok(())
+    }
+
+    /// Swaps the compressed asset of the pair for the escrowed standard NFT.
+    ///
+    /// The leaf is transferred to the entangled pair, so the first `proof_length` remaining
+    /// accounts are the proof of the leaf; the rest are the creator accounts for the fees.
+    pub fn swap_compressed_for_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapCompressed<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        index: u32,
+        proof_length: u8,
+    ) -> Result<()> {
+        let payer = &ctx.accounts.payer;
+        let leaf_delegate = &ctx.accounts.leaf_delegate;
+        let token = &ctx.accounts.token;
+        let mint_b = &ctx.accounts.mint_b;
+        let token_b_escrow = &ctx.accounts.token_b_escrow;
+        let entangled_pair = &ctx.accounts.entangled_pair;
+        let token_program = &ctx.accounts.token_program;
+        let system_program = &ctx.accounts.system_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let rent = &ctx.accounts.rent;
+
+        let (proof, creator_accounts) = split_proof(ctx.remaining_accounts, proof_length)?;
+
+        if token.data_is_empty() {
+            make_ata(
+                token.to_account_info(),
+                payer.to_account_info(),
+                mint_b.to_account_info(),
+                payer.to_account_info(),
+                ata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+
+        assert_is_ata(&token.to_account_info(), &payer.key(), &mint_b.key())?;
+
+        // the leaf owner signs the transfer of the leaf to the entangled pair
+        mpl_bubblegum::cpi::transfer(
+            CpiContext::new(
+                ctx.accounts.bubblegum_program.to_account_info(),
+                mpl_bubblegum::cpi::accounts::Transfer {
+                    tree_authority: ctx.accounts.tree_authority.to_account_info(),
+                    leaf_owner: payer.to_account_info(),
+                    leaf_delegate: leaf_delegate.to_account_info(),
+                    new_leaf_owner: entangled_pair.to_account_info(),
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+                    compression_program: ctx.accounts.compression_program.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(proof.to_vec()),
+            root,
+            data_hash,
+            creator_hash,
+            entangled_pair.nonce,
+            index,
+        )?;
+
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            entangled_pair.asset_id.as_ref(),
+            entangled_pair.mint_b.as_ref(),
+            &[entangled_pair.bump],
+        ];
+
+        let (mint_b_supply, _) = get_mint_details(&mint_b.to_account_info())?;
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &token_b_escrow.key(),
+                &token.key(),
+                &entangled_pair.key(),
+                &[],
+                mint_b_supply,
+            )?,
+            &[
+                token_b_escrow.to_account_info(),
+                token.to_account_info(),
+                token_program.to_account_info(),
+                entangled_pair.to_account_info(),
+            ],
+            &[&signer_seeds],
+        )?;
+
+        pay_compressed_pair_fees(ctx.accounts, creator_accounts)
+    }
+
+    /// Swaps the standard NFT of the pair for the escrowed compressed asset.
+    ///
+    /// The entangled pair signs the transfer of the leaf, so the first `proof_length`
+    /// remaining accounts are the proof of the leaf; the rest are the creator accounts
+    /// for the fees.
+    pub fn swap_token_for_compressed<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapCompressed<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        index: u32,
+        proof_length: u8,
+    ) -> Result<()> {
+        let payer = &ctx.accounts.payer;
+        let token = &ctx.accounts.token;
+        let mint_b = &ctx.accounts.mint_b;
+        let transfer_authority = &ctx.accounts.transfer_authority;
+        let token_b_escrow = &ctx.accounts.token_b_escrow;
+        let entangled_pair = &ctx.accounts.entangled_pair;
+        let token_program = &ctx.accounts.token_program;
+        let system_program = &ctx.accounts.system_program;
+
+        let (proof, creator_accounts) = split_proof(ctx.remaining_accounts, proof_length)?;
+
+        let token_account = assert_is_ata(&token.to_account_info(), &payer.key(), &mint_b.key())?;
+        let (mint_b_supply, _) = get_mint_details(&mint_b.to_account_info())?;
+        if token_account.amount != mint_b_supply {
+            return Err(ErrorCode::InvalidTokenAmount.into());
+        }
+
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &token.key(),
+                &token_b_escrow.key(),
+                &transfer_authority.key(),
+                &[],
+                mint_b_supply,
+            )?,
+            &[
+                token.to_account_info(),
+                token_b_escrow.to_account_info(),
+                token_program.to_account_info(),
+                transfer_authority.to_account_info(),
+            ],
+        )?;
+
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            entangled_pair.asset_id.as_ref(),
+            entangled_pair.mint_b.as_ref(),
+            &[entangled_pair.bump],
+        ];
+
+        // the entangled pair is both the owner and the delegate of the escrowed leaf
+        mpl_bubblegum::cpi::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.bubblegum_program.to_account_info(),
+                mpl_bubblegum::cpi::accounts::Transfer {
+                    tree_authority: ctx.accounts.tree_authority.to_account_info(),
+                    leaf_owner: entangled_pair.to_account_info(),
+                    leaf_delegate: entangled_pair.to_account_info(),
+                    new_leaf_owner: payer.to_account_info(),
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+                    compression_program: ctx.accounts.compression_program.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                },
+                &[&signer_seeds],
+            )
+            .with_remaining_accounts(proof.to_vec()),
+            root,
+            data_hash,
+            creator_hash,
+            entangled_pair.nonce,
+            index,
+        )?;
+
+        pay_compressed_pair_fees(ctx.accounts, creator_accounts)
+    }
+}
+
+/// Splits the remaining accounts of a compressed swap into the leaf proof and the
+/// creator accounts.
+fn split_proof<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    proof_length: u8,
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let proof_length = proof_length as usize;
+
+    if remaining_accounts.len() < proof_length {
+        return Err(ErrorCode::InvalidProofLength.into());
+    }
+
+    Ok(remaining_accounts.split_at(proof_length))
+}
+
+/// Pays the creators of the standard NFT of a compressed pair, either on the first swap
+/// or on every swap.
+fn pay_compressed_pair_fees<'info>(
+    accounts: &mut SwapCompressed<'info>,
+    creator_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let entangled_pair = &mut accounts.entangled_pair;
+
+    if !entangled_pair.paid || entangled_pair.pays_every_time {
+        let is_native = accounts.treasury_mint.key() == spl_token::native_mint::id();
+
+        pay_creator_fees(
+            &mut creator_accounts.iter(),
+            &accounts.metadata_b,
+            &accounts.payment_account,
+            &accounts.payment_transfer_authority,
+            &accounts.payer,
+            &accounts.treasury_mint.to_account_info(),
+            &accounts.ata_program.to_account_info(),
+            &accounts.token_program.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            entangled_pair.price,
+            is_native,
+        )?;
+    }
+    entangled_pair.paid = true;
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -390,6 +876,92 @@ pub struct Swap<'info> {
     rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey, nonce: u64, index: u32, root: [u8; 32], metadata: MetadataArgs, token_b_escrow_bump: u8)]
+pub struct CreateCompressedEntangledPair<'info> {
+    treasury_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    authority: Signer<'info>,
+    #[account(seeds=[merkle_tree.key().as_ref()], seeds::program=mpl_bubblegum::id(), bump)]
+    tree_authority: Box<Account<'info, TreeConfig>>,
+    /// CHECK: Checked against the asset id
+    merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX.as_bytes(), asset_id.as_ref(), MINT.as_bytes()], bump)]
+    mint_b: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    #[account(mut)]
+    metadata_b: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    #[account(mut)]
+    edition_b: UncheckedAccount<'info>,
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut,seeds=[PREFIX.as_bytes(), asset_id.as_ref(), mint_b.key().as_ref(), ESCROW.as_bytes(), B_NAME.as_bytes()], bump=token_b_escrow_bump)]
+    token_b_escrow: UncheckedAccount<'info>,
+    #[account(init, seeds=[PREFIX.as_bytes(), asset_id.as_ref(), mint_b.key().as_ref()], bump, space=COMPRESSED_ENTANGLED_PAIR_SIZE, payer=payer)]
+    entangled_pair: Box<Account<'info, CompressedEntangledPair>>,
+    compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK: Checked in constraint
+    #[account(address=mpl_token_metadata::id())]
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCompressedEntangledPair<'info> {
+    authority: Signer<'info>,
+    /// CHECK: Verified through CPI
+    new_authority: UncheckedAccount<'info>,
+    #[account(mut, seeds=[PREFIX.as_bytes(), entangled_pair.asset_id.as_ref(), entangled_pair.mint_b.as_ref()], bump=entangled_pair.bump, has_one=authority)]
+    entangled_pair: Account<'info, CompressedEntangledPair>,
+}
+
+#[derive(Accounts)]
+pub struct SwapCompressed<'info> {
+    treasury_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: Verified through CPI
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    payment_transfer_authority: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    #[account(mut)]
+    token: UncheckedAccount<'info>,
+    mint_b: Box<Account<'info, Mint>>,
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[mpl_token_metadata::state::PREFIX.as_bytes(), mpl_token_metadata::id().as_ref(), mint_b.key().as_ref()], seeds::program=mpl_token_metadata::id(), bump)]
+    metadata_b: UncheckedAccount<'info>,
+    transfer_authority: Signer<'info>,
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut,seeds=[PREFIX.as_bytes(), entangled_pair.asset_id.as_ref(), entangled_pair.mint_b.as_ref(), ESCROW.as_bytes(), B_NAME.as_bytes()], bump=entangled_pair.token_b_escrow_bump)]
+    token_b_escrow: UncheckedAccount<'info>,
+    #[account(mut, seeds=[PREFIX.as_bytes(), entangled_pair.asset_id.as_ref(), entangled_pair.mint_b.as_ref()], bump=entangled_pair.bump, has_one=treasury_mint, has_one=merkle_tree, has_one=mint_b)]
+    entangled_pair: Account<'info, CompressedEntangledPair>,
+    /// CHECK: Verified through CPI
+    tree_authority: UncheckedAccount<'info>,
+    /// CHECK: Verified through CPI
+    #[account(mut)]
+    merkle_tree: UncheckedAccount<'info>,
+    log_wrapper: Program<'info, Noop>,
+    compression_program: Program<'info, SplAccountCompression>,
+    bubblegum_program: Program<'info, Bubblegum>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    ata_program: Program<'info, AssociatedToken>,
+    rent: Sysvar<'info, Rent>,
+}
+
 pub const ENTANGLED_PAIR_SIZE: usize = 8 +// key 
 32 + // treasury mint
 32 + // mint a
@@ -420,6 +992,42 @@ pub struct EntangledPair {
     pub pays_every_time: bool,
 }
 
+pub const COMPRESSED_ENTANGLED_PAIR_SIZE: usize = 8 +// key
+32 + // treasury mint
+32 + // asset id
+32 + // merkle tree
+8 + // nonce
+32 + // mint b
+32 + // token b
+32 + // authority
+1 + // bump
+1 + // token b bump
+8 + // price
+1 + // paid
+1 + // pays every time
+200; // padding
+
+/// Entangled pair between a compressed asset (A) and its standard form (B), minted by the
+/// pair with the metadata of the asset.
+///
+/// The pair escrows whichever side is not held by a user: the NFT in the `token_b_escrow`
+/// account, or the leaf owned by the pair itself.
+#[account]
+pub struct CompressedEntangledPair {
+    pub treasury_mint: Pubkey,
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub nonce: u64,
+    pub mint_b: Pubkey,
+    pub token_b_escrow: Pubkey,
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub token_b_escrow_bump: u8,
+    pub price: u64,
+    pub paid: bool,
+    pub pays_every_time: bool,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("PublicKeyMismatch")]
@@ -454,4 +1062,10 @@ pub enum ErrorCode {
     MustHaveSupplyOne,
     #[msg("Bump seed not in hash map")]
     BumpSeedNotInHashMap,
+    #[msg("This asset is not the compressed asset of this entangled pair")]
+    InvalidAsset,
+    #[msg("Not enough remaining accounts for the proof")]
+    InvalidProofLength,
+    #[msg("Only the creator or the delegate of the tree can entangle its assets")]
+    InvalidTreeAuthority,
 }
@@ -2,7 +2,7 @@ use crate::ErrorCode;
 use anchor_lang::{
     prelude::*,
     solana_program::{
-        program::{invoke, invoke_signed},
+        program::invoke_signed,
         program_memory::sol_memcmp,
         program_pack::{IsInitialized, Pack},
         pubkey::PUBKEY_BYTES,
@@ -11,7 +11,7 @@ use anchor_lang::{
 };
 use anchor_spl::token::Token;
 use arrayref::array_ref;
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use mpl_royalty_engine::{distribute, Payment, PaymentAmount, RoyaltyError};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::{instruction::initialize_account2, state::Account};
 use std::{convert::TryInto, slice::Iter};
@@ -176,6 +176,7 @@ pub fn create_program_token_account_if_not_present<'a>(
     Ok(())
 }
 
+/// Pays the creators of the asset their share of the entangled pair price.
 #[allow(clippy::too_many_arguments)]
 pub fn pay_creator_fees<'a>(
     remaining_accounts: &mut Iter<AccountInfo<'a>>,
@@ -187,82 +188,39 @@ pub fn pay_creator_fees<'a>(
     ata_program: &AccountInfo<'a>,
     token_program: &AccountInfo<'a>,
     system_program: &AccountInfo<'a>,
-    rent: &AccountInfo<'a>,
     size: u64,
     is_native: bool,
 ) -> Result<()> {
-    let metadata = Metadata::from_account_info(metadata_info)?;
-    let total_fee = size as u128;
-    match metadata.data.creators {
-        Some(creators) => {
-            for creator in creators {
-                let pct = creator.share as u128;
-                let creator_fee = pct
-                    .checked_mul(total_fee)
-                    .ok_or(ErrorCode::NumericalOverflow)?
-                    .checked_div(100)
-                    .ok_or(ErrorCode::NumericalOverflow)? as u64;
-                let current_creator_info = next_account_info(remaining_accounts)?;
-                assert_keys_equal(creator.address, *current_creator_info.key)?;
-                if !is_native {
-                    let current_creator_token_account_info = next_account_info(remaining_accounts)?;
-                    if current_creator_token_account_info.data_is_empty() {
-                        make_ata(
-                            current_creator_token_account_info.to_account_info(),
-                            current_creator_info.to_account_info(),
-                            treasury_mint.to_account_info(),
-                            fee_payer.to_account_info(),
-                            ata_program.to_account_info(),
-                            token_program.to_account_info(),
-                            system_program.to_account_info(),
-                            rent.to_account_info(),
-                            &[],
-                        )?;
-                    }
-                    assert_is_ata(
-                        current_creator_token_account_info,
-                        current_creator_info.key,
-                        &treasury_mint.key(),
-                    )?;
-                    if creator_fee > 0 {
-                        invoke(
-                            &spl_token::instruction::transfer(
-                                token_program.key,
-                                payment_account.key,
-                                current_creator_token_account_info.key,
-                                payment_account_owner.key,
-                                &[],
-                                creator_fee,
-                            )?,
-                            &[
-                                payment_account.clone(),
-                                current_creator_token_account_info.clone(),
-                                token_program.clone(),
-                                payment_account_owner.clone(),
-                            ],
-                        )?;
-                    }
-                } else if creator_fee > 0 {
-                    invoke(
-                        &system_instruction::transfer(
-                            payment_account.key,
-                            current_creator_info.key,
-                            creator_fee,
-                        ),
-                        &[
-                            payment_account.clone(),
-                            current_creator_info.clone(),
-                            system_program.clone(),
-                        ],
-                    )?;
-                }
-            }
-        }
-        None => {
-            msg!("No creators found in metadata");
+    let payment = Payment {
+        amount: PaymentAmount::Royalty(size),
+        is_native,
+        source: payment_account,
+        authority: payment_account_owner,
+        signer_seeds: &[],
+        mint: treasury_mint,
+        fee_payer,
+        fee_payer_seeds: &[],
+        ata_program,
+        token_program,
+        system_program,
+    };
+
+    distribute(&payment, metadata_info, remaining_accounts).map_err(royalty_error)?;
+
+    Ok(())
+}
+
+/// Maps the errors of the royalty engine into the error codes of the entangler.
+fn royalty_error(error: RoyaltyError) -> Error {
+    match error {
+        RoyaltyError::NumericalOverflow => ErrorCode::NumericalOverflow.into(),
+        RoyaltyError::CreatorMismatch | RoyaltyError::InvalidTokenAccount => {
+            ErrorCode::PublicKeyMismatch.into()
         }
+        RoyaltyError::IncorrectOwner => ErrorCode::IncorrectOwner.into(),
+        RoyaltyError::UninitializedAccount => ErrorCode::UninitializedAccount.into(),
+        error => ProgramError::from(error).into(),
     }
-    Ok(())
 }
 
 /// Create account almost from scratch, lifted from
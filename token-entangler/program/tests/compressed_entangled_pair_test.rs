@@ -0,0 +1,582 @@
+#![cfg(feature = "test-bpf")]
+use anchor_lang::{
+    error::ERROR_CODE_OFFSET, prelude::Pubkey, AccountDeserialize, AnchorDeserialize,
+    InstructionData, ToAccountMetas,
+};
+use mpl_bubblegum::{
+    hash_creators, hash_metadata,
+    state::{
+        leaf_schema::LeafSchema,
+        metaplex_adapter::{Creator, MetadataArgs, TokenProgramVersion},
+    },
+    utils::get_asset_id,
+};
+use mpl_token_entangler::{CompressedEntangledPair, ErrorCode};
+use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+use solana_program_test::{tokio, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use spl_account_compression::state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1;
+use spl_associated_token_account::get_associated_token_address;
+use spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree;
+use spl_merkle_tree_reference::{MerkleTree, Node};
+use std::mem::size_of;
+
+const TREASURY_MINT: &str = "So11111111111111111111111111111111111111112";
+const MAX_DEPTH: usize = 5;
+const MAX_BUFFER_SIZE: usize = 8;
+
+/// A tree with a single compressed asset and a reference copy of it to build the proofs.
+struct CompressedAsset {
+    tree_creator: Keypair,
+    merkle_tree: Keypair,
+    proof_tree: MerkleTree,
+    metadata: MetadataArgs,
+    owner: Pubkey,
+    nonce: u64,
+    index: u32,
+}
+
+impl CompressedAsset {
+    fn tree_authority(&self) -> Pubkey {
+        Pubkey::find_program_address(&[self.merkle_tree.pubkey().as_ref()], &mpl_bubblegum::id()).0
+    }
+
+    fn asset_id(&self) -> Pubkey {
+        get_asset_id(&self.merkle_tree.pubkey(), self.nonce)
+    }
+
+    fn hashes(&self) -> ([u8; 32], [u8; 32]) {
+        (
+            hash_metadata(&self.metadata).unwrap(),
+            hash_creators(&self.metadata.creators).unwrap(),
+        )
+    }
+
+    fn leaf(&self, owner: &Pubkey) -> Node {
+        let (data_hash, creator_hash) = self.hashes();
+        LeafSchema::new_v0(
+            self.asset_id(),
+            *owner,
+            *owner,
+            self.nonce,
+            data_hash,
+            creator_hash,
+        )
+        .to_node()
+    }
+
+    /// Records a new owner (and delegate) of the leaf on the reference tree.
+    fn set_owner(&mut self, owner: Pubkey) {
+        self.owner = owner;
+        let leaf = self.leaf(&owner);
+        self.proof_tree.add_leaf(leaf, self.index as usize);
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.proof_tree.get_root()
+    }
+
+    fn proof_metas(&self) -> Vec<AccountMeta> {
+        self.proof_tree
+            .get_proof_of_leaf(self.index as usize)
+            .into_iter()
+            .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(node), false))
+            .collect()
+    }
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    program_test.add_program("mpl_token_entangler", mpl_token_entangler::id(), None);
+    program_test.add_program("mpl_bubblegum", mpl_bubblegum::id(), None);
+    program_test.add_program(
+        "spl_account_compression",
+        spl_account_compression::id(),
+        None,
+    );
+    program_test.add_program("spl_noop", spl_noop::id(), None);
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::id(), None);
+    program_test
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let payer = context.payer.pubkey();
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer),
+        &all_signers,
+        last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Creates a tree and mints a compressed asset owned by the payer of the context.
+async fn mint_compressed_asset(context: &mut ProgramTestContext) -> CompressedAsset {
+    let tree_creator = Keypair::new();
+    let merkle_tree = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let tree_size = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1
+        + size_of::<ConcurrentMerkleTree<MAX_DEPTH, MAX_BUFFER_SIZE>>();
+
+    let mut asset = CompressedAsset {
+        metadata: MetadataArgs {
+            name: "Entangled".to_string(),
+            symbol: "ENT".to_string(),
+            uri: "https://example.com/entangled.json".to_string(),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![Creator {
+                address: tree_creator.pubkey(),
+                verified: false,
+                share: 100,
+            }],
+        },
+        tree_creator,
+        merkle_tree,
+        proof_tree: MerkleTree::new(vec![Node::default(); 1 << MAX_DEPTH].as_slice()),
+        owner: context.payer.pubkey(),
+        nonce: 0,
+        index: 0,
+    };
+
+    let create_tree = Instruction {
+        program_id: mpl_bubblegum::id(),
+        accounts: mpl_bubblegum::accounts::CreateTree {
+            tree_authority: asset.tree_authority(),
+            merkle_tree: asset.merkle_tree.pubkey(),
+            payer: context.payer.pubkey(),
+            tree_creator: asset.tree_creator.pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+        data: mpl_bubblegum::instruction::CreateTree {
+            max_depth: MAX_DEPTH as u32,
+            max_buffer_size: MAX_BUFFER_SIZE as u32,
+            public: Some(false),
+        }
+        .data(),
+    };
+
+    let mint = Instruction {
+        program_id: mpl_bubblegum::id(),
+        accounts: mpl_bubblegum::accounts::MintV1 {
+            tree_authority: asset.tree_authority(),
+            leaf_owner: asset.owner,
+            leaf_delegate: asset.owner,
+            merkle_tree: asset.merkle_tree.pubkey(),
+            payer: context.payer.pubkey(),
+            tree_delegate: asset.tree_creator.pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+        data: mpl_bubblegum::instruction::MintV1 {
+            message: asset.metadata.clone(),
+        }
+        .data(),
+    };
+
+    process(
+        context,
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &asset.merkle_tree.pubkey(),
+                rent.minimum_balance(tree_size),
+                tree_size as u64,
+                &spl_account_compression::id(),
+            ),
+            system_instruction::transfer(
+                &context.payer.pubkey(),
+                &asset.tree_creator.pubkey(),
+                1_000_000_000,
+            ),
+            create_tree,
+            mint,
+        ],
+        &[&asset.merkle_tree, &asset.tree_creator],
+    )
+    .await
+    .unwrap();
+
+    let owner = asset.owner;
+    asset.set_owner(owner);
+
+    asset
+}
+
+fn find_mint_b(asset_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"token_entangler", asset_id.as_ref(), b"mint"],
+        &mpl_token_entangler::id(),
+    )
+    .0
+}
+
+fn find_escrow_b(asset_id: &Pubkey, mint_b: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"token_entangler",
+            asset_id.as_ref(),
+            mint_b.as_ref(),
+            b"escrow",
+            b"B",
+        ],
+        &mpl_token_entangler::id(),
+    )
+}
+
+fn find_entangled_pair(asset_id: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"token_entangler", asset_id.as_ref(), mint_b.as_ref()],
+        &mpl_token_entangler::id(),
+    )
+    .0
+}
+
+fn create_instruction(
+    context: &ProgramTestContext,
+    asset: &CompressedAsset,
+    asset_id: Pubkey,
+    proof: Vec<AccountMeta>,
+) -> Instruction {
+    let mint_b = find_mint_b(&asset_id);
+    let (token_b_escrow, token_b_escrow_bump) = find_escrow_b(&asset_id, &mint_b);
+
+    let mut accounts = mpl_token_entangler::accounts::CreateCompressedEntangledPair {
+        treasury_mint: TREASURY_MINT.parse().unwrap(),
+        payer: context.payer.pubkey(),
+        authority: asset.tree_creator.pubkey(),
+        tree_authority: asset.tree_authority(),
+        merkle_tree: asset.merkle_tree.pubkey(),
+        leaf_owner: asset.owner,
+        leaf_delegate: asset.owner,
+        mint_b,
+        metadata_b: find_metadata_account(&mint_b).0,
+        edition_b: find_master_edition_account(&mint_b).0,
+        token_b_escrow,
+        entangled_pair: find_entangled_pair(&asset_id, &mint_b),
+        compression_program: spl_account_compression::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(proof);
+
+    Instruction {
+        program_id: mpl_token_entangler::id(),
+        accounts,
+        data: mpl_token_entangler::instruction::CreateCompressedEntangledPair {
+            asset_id,
+            nonce: asset.nonce,
+            index: asset.index,
+            root: asset.root(),
+            metadata: asset.metadata.clone(),
+            token_b_escrow_bump,
+            price: 1,
+            pays_every_time: true,
+        }
+        .data(),
+    }
+}
+
+fn swap_accounts(
+    context: &ProgramTestContext,
+    asset: &CompressedAsset,
+    proof: Vec<AccountMeta>,
+) -> Vec<AccountMeta> {
+    let asset_id = asset.asset_id();
+    let mint_b = find_mint_b(&asset_id);
+    let payer = context.payer.pubkey();
+
+    let mut accounts = mpl_token_entangler::accounts::SwapCompressed {
+        treasury_mint: TREASURY_MINT.parse().unwrap(),
+        payer,
+        payment_account: payer,
+        payment_transfer_authority: payer,
+        leaf_delegate: payer,
+        token: get_associated_token_address(&payer, &mint_b),
+        mint_b,
+        metadata_b: find_metadata_account(&mint_b).0,
+        transfer_authority: payer,
+        token_b_escrow: find_escrow_b(&asset_id, &mint_b).0,
+        entangled_pair: find_entangled_pair(&asset_id, &mint_b),
+        tree_authority: asset.tree_authority(),
+        merkle_tree: asset.merkle_tree.pubkey(),
+        log_wrapper: spl_noop::id(),
+        compression_program: spl_account_compression::id(),
+        bubblegum_program: mpl_bubblegum::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        ata_program: spl_associated_token_account::id(),
+        rent: sysvar::rent::id(),
+    }
+    .to_account_metas(None);
+
+    accounts.extend(proof);
+    // the creator of the asset receives the fees
+    accounts.push(AccountMeta::new(asset.tree_creator.pubkey(), false));
+
+    accounts
+}
+
+async fn swap_compressed_for_token(
+    context: &mut ProgramTestContext,
+    asset: &CompressedAsset,
+    proof: Vec<AccountMeta>,
+) -> Result<(), BanksClientError> {
+    let (data_hash, creator_hash) = asset.hashes();
+
+    let instruction = Instruction {
+        program_id: mpl_token_entangler::id(),
+        accounts: swap_accounts(context, asset, proof),
+        data: mpl_token_entangler::instruction::SwapCompressedForToken {
+            root: asset.root(),
+            data_hash,
+            creator_hash,
+            index: asset.index,
+            proof_length: MAX_DEPTH as u8,
+        }
+        .data(),
+    };
+
+    process(context, &[instruction], &[]).await
+}
+
+async fn swap_token_for_compressed(
+    context: &mut ProgramTestContext,
+    asset: &CompressedAsset,
+    proof: Vec<AccountMeta>,
+) -> Result<(), BanksClientError> {
+    let (data_hash, creator_hash) = asset.hashes();
+
+    let instruction = Instruction {
+        program_id: mpl_token_entangler::id(),
+        accounts: swap_accounts(context, asset, proof),
+        data: mpl_token_entangler::instruction::SwapTokenForCompressed {
+            root: asset.root(),
+            data_hash,
+            creator_hash,
+            index: asset.index,
+            proof_length: MAX_DEPTH as u8,
+        }
+        .data(),
+    };
+
+    process(context, &[instruction], &[]).await
+}
+
+async fn token_amount(context: &mut ProgramTestContext, token: Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(token)
+        .await
+        .unwrap()
+        .unwrap();
+
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+/// Checks that the root of the tree matches the reference tree.
+async fn assert_root(context: &mut ProgramTestContext, asset: &CompressedAsset) {
+    let mut account = context
+        .banks_client
+        .get_account(asset.merkle_tree.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (_, tree_bytes) = account
+        .data
+        .as_mut_slice()
+        .split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+    let tree =
+        bytemuck::try_from_bytes::<ConcurrentMerkleTree<MAX_DEPTH, MAX_BUFFER_SIZE>>(tree_bytes)
+            .unwrap();
+
+    assert_eq!(
+        tree.change_logs[tree.active_index as usize].root,
+        asset.root()
+    );
+}
+
+fn assert_custom_error(err: BanksClientError, index: u8, error: ErrorCode) {
+    match err {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            err_index,
+            InstructionError::Custom(code),
+        )) => {
+            assert_eq!(err_index, index);
+            assert_eq!(code, ERROR_CODE_OFFSET + error as u32);
+        }
+        err => panic!("unexpected error {:?}", err),
+    }
+}
+
+#[tokio::test]
+async fn create_compressed_entangled_pair() {
+    let mut context = program_test().start_with_context().await;
+    let asset = mint_compressed_asset(&mut context).await;
+
+    let asset_id = asset.asset_id();
+    let instruction = create_instruction(&context, &asset, asset_id, asset.proof_metas());
+    process(&mut context, &[instruction], &[&asset.tree_creator])
+        .await
+        .unwrap();
+
+    let mint_b = find_mint_b(&asset_id);
+    let (token_b_escrow, _) = find_escrow_b(&asset_id, &mint_b);
+
+    // the standard form is minted into the escrow with the metadata of the asset
+    assert_eq!(token_amount(&mut context, token_b_escrow).await, 1);
+
+    let metadata_account = context
+        .banks_client
+        .get_account(find_metadata_account(&mint_b).0)
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata =
+        mpl_token_metadata::state::Metadata::deserialize(&mut metadata_account.data.as_slice())
+            .unwrap();
+    assert!(metadata.data.name.starts_with(&asset.metadata.name));
+    assert_eq!(metadata.data.seller_fee_basis_points, 500);
+    assert_eq!(metadata.update_authority, asset.tree_creator.pubkey());
+
+    let pair_account = context
+        .banks_client
+        .get_account(find_entangled_pair(&asset_id, &mint_b))
+        .await
+        .unwrap()
+        .unwrap();
+    let pair = CompressedEntangledPair::try_deserialize(&mut pair_account.data.as_slice()).unwrap();
+    assert_eq!(pair.asset_id, asset_id);
+    assert_eq!(pair.mint_b, mint_b);
+    assert_eq!(pair.merkle_tree, asset.merkle_tree.pubkey());
+}
+
+#[tokio::test]
+async fn swap_compressed_and_token() {
+    let mut context = program_test().start_with_context().await;
+    let mut asset = mint_compressed_asset(&mut context).await;
+
+    let asset_id = asset.asset_id();
+    let instruction = create_instruction(&context, &asset, asset_id, asset.proof_metas());
+    process(&mut context, &[instruction], &[&asset.tree_creator])
+        .await
+        .unwrap();
+
+    let mint_b = find_mint_b(&asset_id);
+    let (token_b_escrow, _) = find_escrow_b(&asset_id, &mint_b);
+    let token = get_associated_token_address(&context.payer.pubkey(), &mint_b);
+    let entangled_pair = find_entangled_pair(&asset_id, &mint_b);
+
+    // compressed -> token: the leaf goes to the pair and the NFT to the owner
+
+    swap_compressed_for_token(&mut context, &asset, asset.proof_metas())
+        .await
+        .unwrap();
+    asset.set_owner(entangled_pair);
+
+    assert_root(&mut context, &asset).await;
+    assert_eq!(token_amount(&mut context, token).await, 1);
+    assert_eq!(token_amount(&mut context, token_b_escrow).await, 0);
+
+    // token -> compressed: the NFT goes back to the escrow and the leaf to the owner
+
+    swap_token_for_compressed(&mut context, &asset, asset.proof_metas())
+        .await
+        .unwrap();
+    asset.set_owner(context.payer.pubkey());
+
+    assert_root(&mut context, &asset).await;
+    assert_eq!(token_amount(&mut context, token).await, 0);
+    assert_eq!(token_amount(&mut context, token_b_escrow).await, 1);
+}
+
+#[tokio::test]
+async fn fail_create_with_wrong_asset_id() {
+    let mut context = program_test().start_with_context().await;
+    let asset = mint_compressed_asset(&mut context).await;
+
+    let wrong_asset_id = get_asset_id(&asset.merkle_tree.pubkey(), asset.nonce + 1);
+    let instruction = create_instruction(&context, &asset, wrong_asset_id, asset.proof_metas());
+
+    let err = process(&mut context, &[instruction], &[&asset.tree_creator])
+        .await
+        .unwrap_err();
+    assert_custom_error(err, 0, ErrorCode::InvalidAsset);
+}
+
+#[tokio::test]
+async fn fail_create_with_wrong_proof() {
+    let mut context = program_test().start_with_context().await;
+    let asset = mint_compressed_asset(&mut context).await;
+
+    let mut proof = asset.proof_metas();
+    proof[0] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+    let instruction = create_instruction(&context, &asset, asset.asset_id(), proof);
+
+    let err = process(&mut context, &[instruction], &[&asset.tree_creator])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BanksClientError::TransactionError(TransactionError::InstructionError(0, _))
+    ));
+}
+
+#[tokio::test]
+async fn fail_swap_with_wrong_proof() {
+    let mut context = program_test().start_with_context().await;
+    let asset = mint_compressed_asset(&mut context).await;
+
+    let asset_id = asset.asset_id();
+    let instruction = create_instruction(&context, &asset, asset_id, asset.proof_metas());
+    process(&mut context, &[instruction], &[&asset.tree_creator])
+        .await
+        .unwrap();
+
+    let mut proof = asset.proof_metas();
+    proof[0] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+    let err = swap_compressed_for_token(&mut context, &asset, proof)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BanksClientError::TransactionError(TransactionError::InstructionError(0, _))
+    ));
+}
@@ -11,8 +11,9 @@ use {
             update_metadata_accounts,
         },
         state::{
-            get_reservation_list, Data, Edition, Key, MasterEditionV1, MasterEditionV2, Metadata,
-            EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+            get_reservation_list, BasisPoints, Data, Edition, Key, MasterEditionV1,
+            MasterEditionV2, Metadata, EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+            PREFIX,
         },
     },
     solana_clap_utils::{
@@ -472,7 +473,7 @@ fn update_metadata_account_call(
         name: name.unwrap_or(metadata.data.name),
         symbol: metadata.data.symbol,
         uri: uri.unwrap_or(metadata.data.uri),
-        seller_fee_basis_points: 0,
+        seller_fee_basis_points: BasisPoints::default(),
         creators: metadata.data.creators,
     };
 
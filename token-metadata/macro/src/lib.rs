@@ -19,6 +19,8 @@ struct Variant {
 struct Account {
     pub name: String,
     pub optional: bool,
+    pub signer: bool,
+    pub writable: bool,
 }
 
 // Helper account attribute (reusing from shank annotation).
@@ -29,6 +31,10 @@ const ARGS_ATTRIBUTE: &str = "args";
 const NAME_PROPERTY: &str = "name";
 // Optional property in the account attribute.
 const OPTIONAL_PROPERTY: &str = "optional";
+// Signer property in the account attribute.
+const SIGNER_PROPERTY: &str = "signer";
+// Writable property in the account attribute.
+const WRITABLE_PROPERTY: &str = "writable";
 
 #[proc_macro_derive(AccountContext, attributes(account, args))]
 pub fn account_context_derive(input: TokenStream) -> TokenStream {
@@ -89,8 +95,9 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
                             panic!("#[account] requires attributes account name");
                         };
 
-                        // (name, optional)
-                        let mut property: (Option<String>, Option<String>) = (None, None);
+                        // (name, optional, signer, writable)
+                        let mut property: (Option<String>, bool, bool, bool) =
+                            (None, false, false, false);
 
                         for element in nested_meta {
                             match element {
@@ -114,12 +121,16 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
                                         }
                                     }
                                 }
-                                // optional
+                                // optional, signer and writable
                                 NestedMeta::Meta(Meta::Path(path)) => {
                                     let name = path.get_ident().map(|x| x.to_string());
                                     if let Some(name) = name {
                                         if name == OPTIONAL_PROPERTY {
-                                            property.1 = Some(name);
+                                            property.1 = true;
+                                        } else if name == SIGNER_PROPERTY {
+                                            property.2 = true;
+                                        } else if name == WRITABLE_PROPERTY {
+                                            property.3 = true;
                                         }
                                     }
                                 }
@@ -128,7 +139,9 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
                         }
                         variant.accounts.push(Account {
                             name: property.0.unwrap(),
-                            optional: property.1.is_some(),
+                            optional: property.1,
+                            signer: property.2,
+                            writable: property.3,
                         });
                     } else if attribute == ARGS_ATTRIBUTE {
                         let args_tokens: syn::ExprType = a.parse_args().unwrap();
@@ -181,6 +194,13 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
         panic!("No enum variants found");
     };
 
+    // variants without accounts have no context to validate, so no struct
+    // or builder is generated for them
+    let variants = variants
+        .into_iter()
+        .filter(|variant| !variant.accounts.is_empty())
+        .collect::<Vec<Variant>>();
+
     let mut account_structs = generate_accounts(&variants);
     account_structs.extend(generate_builders(&variants));
 
@@ -194,6 +214,9 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
 /// optional accounts, which would generate an account field with an
 /// `Option<AccountInfo<'a>>` type.
 ///
+/// Accounts annotated as `signer` or `writable` are validated after they are
+/// initialized; optional accounts are only validated when they are present.
+///
 /// ```ignore
 /// pub struct MyAccount<'a> {
 ///     my_first_account: solana_program::account_info::AccountInfo<'a>,
@@ -210,6 +233,10 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
 ///
 ///         ..
 ///
+///         crate::assertions::assert_writable(my_first_account)?;
+///
+///         ..
+///
 ///     }
 /// }
 /// ```
@@ -249,6 +276,38 @@ fn generate_accounts(variants: &[Variant]) -> TokenStream {
             }
         });
 
+        // signer and writable validation for the impl block
+        let impl_validations = variant.accounts.iter().map(|account| {
+            let account_name = syn::parse_str::<syn::Ident>(format!("{}_info", &account.name).as_str()).unwrap();
+            let mut validations = Vec::new();
+
+            if account.signer {
+                validations.push(quote! {
+                    mpl_utils::assert_signer(account_info)?;
+                });
+            }
+            if account.writable {
+                validations.push(quote! {
+                    crate::assertions::assert_writable(account_info)?;
+                });
+            }
+
+            if validations.is_empty() {
+                quote! {}
+            } else if account.optional {
+                quote! {
+                    if let Some(account_info) = #account_name {
+                        #(#validations)*
+                    }
+                }
+            } else {
+                quote! {
+                    let account_info = #account_name;
+                    #(#validations)*
+                }
+            }
+        });
+
         quote! {
             pub struct #name<'a> {
                 #(#struct_fields,)*
@@ -259,6 +318,8 @@ fn generate_accounts(variants: &[Variant]) -> TokenStream {
 
                     #(#impl_fields)*
 
+                    #(#impl_validations)*
+
                     let accounts = Self {
                         #(#fields,)*
                     };
@@ -281,23 +342,27 @@ fn generate_builders(variants: &[Variant]) -> TokenStream {
     let mut default_pubkeys = HashMap::new();
     default_pubkeys.insert(
         "system_program".to_string(),
-        syn::parse_str::<syn::ExprPath>("solana_program::system_program::ID").unwrap(),
+        syn::parse_str::<syn::ExprPath>("crate::programs::SYSTEM_PROGRAM").unwrap(),
     );
     default_pubkeys.insert(
         "spl_token_program".to_string(),
-        syn::parse_str::<syn::ExprPath>("spl_token::ID").unwrap(),
-    );
-    default_pubkeys.insert(
-        "spl_ata_program".to_string(),
-        syn::parse_str::<syn::ExprPath>("spl_associated_token_account::ID").unwrap(),
+        syn::parse_str::<syn::ExprPath>("crate::programs::SPL_TOKEN_PROGRAM").unwrap(),
     );
     default_pubkeys.insert(
         "sysvar_instructions".to_string(),
-        syn::parse_str::<syn::ExprPath>("solana_program::sysvar::instructions::ID").unwrap(),
+        syn::parse_str::<syn::ExprPath>("crate::programs::SYSVAR_INSTRUCTIONS").unwrap(),
     );
-    default_pubkeys.insert(
+
+    // program ids of optional dependencies: the default value is only available
+    // when the corresponding feature is enabled on the program crate
+    let mut feature_pubkeys = HashMap::new();
+    feature_pubkeys.insert(
+        "spl_ata_program".to_string(),
+        syn::parse_str::<syn::ExprPath>("crate::instruction::DEFAULT_SPL_ATA_PROGRAM").unwrap(),
+    );
+    feature_pubkeys.insert(
         "authorization_rules_program".to_string(),
-        syn::parse_str::<syn::ExprPath>("mpl_token_auth_rules::ID").unwrap(),
+        syn::parse_str::<syn::ExprPath>("crate::instruction::DEFAULT_AUTH_RULES_PROGRAM").unwrap(),
     );
 
     // build the trait implementation
@@ -427,6 +492,12 @@ fn generate_builders(variants: &[Variant]) -> TokenStream {
                         #account_name: self.#account_name.unwrap_or(#pubkey)
                     }
                 }
+                else if let Some(pubkey) = feature_pubkeys.get(&account.name) {
+                    // the default key is used as a fallback if available
+                    quote! {
+                        #account_name: self.#account_name.or(#pubkey).ok_or(concat!(stringify!(#account_name), " is not set"))?
+                    }
+                }
                 else {
                     // if not a default pubkey, we will need to have it set
                     quote! {
@@ -467,6 +538,26 @@ fn generate_builders(variants: &[Variant]) -> TokenStream {
             quote! { }
         };
 
+        // account specs, in the order expected by the processor
+        let account_specs = variant.accounts.iter().map(|account| {
+            let account_name = &account.name;
+            let signer = account.signer;
+            let writable = account.writable;
+            quote! {
+                (#account_name, #signer, #writable)
+            }
+        });
+
+        // test account setters: each account is set to a pubkey derived from its
+        // position so the order of the instruction accounts can be checked
+        let builder_test_accounts = variant.accounts.iter().enumerate().map(|(index, account)| {
+            let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+            let seed = index as u8 + 1;
+            quote! {
+                self.#account_name = Some(solana_program::pubkey::Pubkey::new_from_array([#seed; 32]));
+            }
+        });
+
         // builder name
         let builder_name = syn::parse_str::<syn::Ident>(&format!("{}Builder", name)).unwrap();
 
@@ -475,11 +566,26 @@ fn generate_builders(variants: &[Variant]) -> TokenStream {
                 #(#struct_accounts,)*
                 #(#struct_args,)*
                 #instruction_args
+                /// Program id of the instruction, only set through the builder (see
+                /// `with_program_id`) and defaulting to `crate::ID`.
+                pub(crate) program_id: solana_program::pubkey::Pubkey,
+            }
+
+            impl #name {
+                /// Accounts of the instruction in the order expected by the processor, as
+                /// `(name, signer, writable)` tuples.
+                pub const ACCOUNTS: &'static [(&'static str, bool, bool)] = &[#(#account_specs,)*];
+
+                /// Returns the program id of the instruction.
+                pub fn program_id(&self) -> solana_program::pubkey::Pubkey {
+                    self.program_id
+                }
             }
 
             pub struct #builder_name {
                 #(#builder_accounts,)*
                 #(#builder_args,)*
+                program_id: Option<solana_program::pubkey::Pubkey>,
             }
 
             impl #builder_name {
@@ -487,17 +593,34 @@ fn generate_builders(variants: &[Variant]) -> TokenStream {
                     Box::new(#builder_name {
                         #(#builder_initialize_accounts,)*
                         #(#builder_initialize_args,)*
+                        program_id: None,
                     })
                 }
 
+                /// Sets the program id of the instruction, which defaults to `crate::ID`. This
+                /// is used to target deployments of the program under a different program id;
+                /// optional accounts not set are still represented by `crate::ID`.
+                pub fn with_program_id(&mut self, program_id: solana_program::pubkey::Pubkey) -> &mut Self {
+                    self.program_id = Some(program_id);
+                    self
+                }
+
                 #(#builder_accounts_methods)*
                 #(#builder_args_methods)*
 
+                /// Sets every account to a pubkey derived from its position.
+                #[cfg(test)]
+                pub fn with_test_accounts(&mut self) -> &mut Self {
+                    #(#builder_test_accounts)*
+                    self
+                }
+
                 pub fn build(#args) -> Result<Box<#name>, Box<dyn std::error::Error>> {
                     Ok(Box::new(#name {
                         #(#required_accounts,)*
                         #(#required_args,)*
                         #required_instruction_args
+                        program_id: self.program_id.unwrap_or(crate::ID),
                     }))
                 }
             }
@@ -1,12 +1,15 @@
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
 
 use crate::{
-    assertions::assert_derivation,
+    assertions::{assert_derivation, assert_keys_equal, assert_owned_by},
     error::MetadataError,
-    pda::find_collection_authority_account,
+    pda::{find_collection_authority_account, ProgramContext},
     state::{
-        Collection, CollectionAuthorityRecord, MasterEditionV2, Metadata, TokenMetadataAccount,
-        TokenStandard, EDITION, PREFIX,
+        Collection, CollectionAuthorityRecord, CollectionPause, Denylisted, MasterEditionV2,
+        Metadata, TokenMetadataAccount, TokenStandard, EDITION, PREFIX,
     },
 };
 
@@ -259,3 +262,98 @@ pub mod tests {
         .unwrap();
     }
 }
+
+/// Checks that the collection of a verified collection item is not paused.
+///
+/// The pause is only checked when the collection pause account is specified, so clients
+/// unaware of collection pauses keep working; assets that are not verified members of a
+/// collection are never paused.
+pub fn assert_collection_not_paused(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    collection_pause_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    let (collection_mint, collection_pause_info) =
+        match (&metadata.collection, collection_pause_info) {
+            (
+                Some(Collection {
+                    key,
+                    verified: true,
+                }),
+                Some(collection_pause_info),
+            ) => (key, collection_pause_info),
+            _ => return Ok(()),
+        };
+
+    let (pda_key, _) =
+        ProgramContext::new(*program_id).find_collection_pause_account(collection_mint);
+    assert_keys_equal(&pda_key, collection_pause_info.key)?;
+
+    // collections that were never paused do not have a pause account
+    if collection_pause_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(collection_pause_info, program_id)?;
+    let pause = CollectionPause::from_account_info(collection_pause_info)?;
+
+    if pause.is_paused(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::CollectionPaused.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that a verified collection item is not on the stolen asset denylist when its
+/// collection opted in to the denylist.
+///
+/// The denylist is only consulted when the collection metadata is specified, so clients
+/// unaware of the denylist keep working; the `Denylisted` account of the asset is then
+/// required when the collection enforces the denylist.
+pub fn assert_not_denylisted(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    collection_metadata_info: Option<&AccountInfo>,
+    denylisted_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    let (collection_mint, collection_metadata_info) =
+        match (&metadata.collection, collection_metadata_info) {
+            (
+                Some(Collection {
+                    key,
+                    verified: true,
+                }),
+                Some(collection_metadata_info),
+            ) => (key, collection_metadata_info),
+            _ => return Ok(()),
+        };
+
+    let program_context = ProgramContext::new(*program_id);
+
+    let (pda_key, _) = program_context.find_metadata_account(collection_mint);
+    assert_keys_equal(&pda_key, collection_metadata_info.key)?;
+    assert_owned_by(collection_metadata_info, program_id)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    let enforces_denylist = collection_metadata
+        .collection_details
+        .map_or(false, |details| details.enforces_denylist());
+
+    if !enforces_denylist {
+        return Ok(());
+    }
+
+    let denylisted_info = denylisted_info.ok_or(MetadataError::MissingDenylistAccount)?;
+    let (pda_key, _) = program_context.find_denylisted_account(&metadata.mint);
+    assert_keys_equal(&pda_key, denylisted_info.key)?;
+
+    // assets that are not on the denylist do not have a denylisted account
+    if denylisted_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(denylisted_info, program_id)?;
+    Denylisted::from_account_info(denylisted_info)?;
+
+    Err(MetadataError::StolenAsset.into())
+}
@@ -1,13 +1,17 @@
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption,
-    program_pack::Pack, pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, program_option::COption,
+    program_pack::Pack, pubkey::Pubkey, sysvar::Sysvar,
 };
 use spl_token::state::Mint;
 
 use crate::{
+    assertions::{assert_derivation, assert_owned_by},
     error::MetadataError,
-    pda::find_master_edition_account,
-    state::{TokenStandard, EDITION, PREFIX, TOKEN_STANDARD_INDEX},
+    pda::{find_master_edition_account, EXTENSION},
+    state::{
+        MasterEditionExtension, TokenMetadataAccount, TokenStandard, EDITION, PREFIX,
+        TOKEN_STANDARD_INDEX,
+    },
 };
 
 pub fn assert_edition_is_not_mint_authority(mint_account_info: &AccountInfo) -> ProgramResult {
@@ -22,12 +26,13 @@ pub fn assert_edition_is_not_mint_authority(mint_account_info: &AccountInfo) ->
     Ok(())
 }
 
-/// Checks that the `master_edition` is not a pNFT master edition.
+/// Checks that the `master_edition` is not a pNFT (or non-transferable) master edition.
 pub fn assert_edition_is_not_programmable(master_edition_info: &AccountInfo) -> ProgramResult {
     let edition_data = master_edition_info.data.borrow();
 
     if edition_data.len() > TOKEN_STANDARD_INDEX
-        && edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::ProgrammableNonFungible as u8
+        && (edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::ProgrammableNonFungible as u8
+            || edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::NonTransferable as u8)
     {
         return Err(MetadataError::InvalidTokenStandard.into());
     }
@@ -54,3 +59,38 @@ pub fn assert_edition_valid(
 
     Ok(())
 }
+
+/// Checks that editions of the master edition can be printed at the current time.
+///
+/// The `extension_info` must be the master edition extension PDA of the `mint`. Master
+/// editions without an extension account have no print window restriction.
+pub fn assert_print_window_open(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    extension_info: &AccountInfo,
+) -> ProgramResult {
+    assert_derivation(
+        program_id,
+        extension_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            EXTENSION.as_bytes(),
+        ],
+    )?;
+
+    if extension_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(extension_info, program_id)?;
+    let extension = MasterEditionExtension::from_account_info(extension_info)?;
+
+    if !extension.is_print_window_open(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::PrintWindowClosed.into());
+    }
+
+    Ok(())
+}
@@ -4,15 +4,16 @@ use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_token::state::Account;
 
 use crate::{
-    assertions::{assert_initialized, assert_owned_by},
+    assertions::{
+        assert_initialized_token_account, assert_owned_by, assert_owned_by_token_program,
+    },
     error::MetadataError,
     pda::PREFIX,
     state::{
         Creator, Data, Metadata, TokenRecord, TokenState, MAX_CREATOR_LIMIT, MAX_NAME_LENGTH,
-        MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+        MAX_OVERFLOW_CREATOR_LIMIT, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
     },
 };
 
@@ -35,7 +36,7 @@ pub fn assert_data_valid(
         return Err(MetadataError::UriTooLong.into());
     }
 
-    if data.seller_fee_basis_points > 10000 {
+    if !data.seller_fee_basis_points.is_valid() {
         return Err(MetadataError::InvalidBasisPoints.into());
     }
 
@@ -45,6 +46,11 @@ pub fn assert_data_valid(
     let creators = match data.creators {
         Some(ref creators) => creators,
         None => {
+            // the creators cannot be removed while the asset has overflow creators
+            if existing_metadata.overflow_creators_share.is_some() {
+                return Err(MetadataError::CreatorsMustBeAtleastOne.into());
+            }
+
             if let Some(ref existing_creators) = existing_metadata.data.creators {
                 if existing_creators.iter().any(|c| c.verified) {
                     return Err(MetadataError::CannotRemoveVerifiedCreator.into());
@@ -125,7 +131,12 @@ pub fn assert_data_valid(
         }
     }
 
-    // Ensure share total is 100%.
+    // Ensure share total is 100%, including the share of the overflow creators (stored
+    // on the `CreatorsOverflow` account).
+    let share_total = share_total
+        .checked_add(existing_metadata.overflow_creators_share.unwrap_or(0))
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
     if share_total != 100 {
         return Err(MetadataError::ShareTotalMustBe100.into());
     }
@@ -150,6 +161,78 @@ pub fn assert_data_valid(
     Ok(())
 }
 
+/// Validates the creators of an asset split between the metadata (`creators`) and the
+/// creators overflow account (`overflow_creators`).
+///
+/// The verification of the creators must match the `existing_creators` of both lists,
+/// except for the (signing) update authority, which can set or clear its own verification.
+pub fn assert_creators_overflow_valid(
+    creators: &[Creator],
+    overflow_creators: &[Creator],
+    existing_creators: &[Creator],
+    update_authority: &Pubkey,
+) -> ProgramResult {
+    if creators.is_empty() {
+        return Err(MetadataError::CreatorsMustBeAtleastOne.into());
+    }
+
+    if creators.len() > MAX_CREATOR_LIMIT || overflow_creators.len() > MAX_OVERFLOW_CREATOR_LIMIT {
+        return Err(MetadataError::CreatorsTooLong.into());
+    }
+
+    let new_creators_map: HashMap<&Pubkey, &Creator> = creators
+        .iter()
+        .chain(overflow_creators)
+        .map(|c| (&c.address, c))
+        .collect();
+
+    // Do not allow duplicate entries across both lists.
+    if new_creators_map.len() != creators.len() + overflow_creators.len() {
+        return Err(MetadataError::DuplicateCreatorAddress.into());
+    }
+
+    let existing_creators_map: HashMap<&Pubkey, &Creator> =
+        existing_creators.iter().map(|c| (&c.address, c)).collect();
+
+    let mut share_total: u8 = 0;
+    for (address, creator) in &new_creators_map {
+        share_total = share_total
+            .checked_add(creator.share)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+
+        if *address == update_authority {
+            continue;
+        }
+
+        let existing_verified = existing_creators_map
+            .get(address)
+            .map(|c| c.verified)
+            .unwrap_or(false);
+
+        if creator.verified && !existing_verified {
+            return Err(MetadataError::CannotVerifyAnotherCreator.into());
+        } else if !creator.verified && existing_verified {
+            return Err(MetadataError::CannotUnverifyAnotherCreator.into());
+        }
+    }
+
+    if share_total != 100 {
+        return Err(MetadataError::ShareTotalMustBe100.into());
+    }
+
+    // Verified creators cannot be removed by the update authority.
+    for (address, existing_creator) in &existing_creators_map {
+        if *address != update_authority
+            && existing_creator.verified
+            && !new_creators_map.contains_key(address)
+        {
+            return Err(MetadataError::CannotUnverifyAnotherCreator.into());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn assert_update_authority_is_correct(
     metadata: &Metadata,
     update_authority_info: &AccountInfo,
@@ -212,11 +295,12 @@ pub fn assert_holding_amount(
     amount: u64,
 ) -> ProgramResult {
     assert_owned_by(metadata_info, program_id)?;
-    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by_token_program(mint_info)?;
 
-    let token_account: Account = assert_initialized(token_account_info)?;
+    let token_account = assert_initialized_token_account(token_account_info)?;
 
-    assert_owned_by(token_account_info, &spl_token::id())?;
+    // the token account must belong to the token program of the mint
+    assert_owned_by(token_account_info, mint_info.owner)?;
 
     if token_account.owner != *owner_info.key {
         return Err(MetadataError::InvalidOwner.into());
@@ -1,4 +1,4 @@
-use mpl_utils::cmp_pubkeys;
+use mpl_utils::{cmp_pubkeys, token::SPL_TOKEN_PROGRAM_IDS};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -8,7 +8,8 @@ use solana_program::{
     pubkey::Pubkey,
     rent::Rent,
 };
-use spl_token::state::Account;
+use spl_token::state::{Account, Mint};
+use spl_token_2022::extension::{BaseState, StateWithExtensions};
 
 use crate::{
     error::MetadataError,
@@ -35,6 +36,15 @@ pub fn assert_keys_equal_with_error(
     }
 }
 
+/// Asserts that the account is writable.
+pub fn assert_writable(account_info: &AccountInfo) -> ProgramResult {
+    if !account_info.is_writable {
+        Err(MetadataError::ExpectedWritableAccount.into())
+    } else {
+        Ok(())
+    }
+}
+
 /// assert initialized account
 pub fn assert_initialized<T: Pack + IsInitialized>(
     account_info: &AccountInfo,
@@ -42,6 +52,37 @@ pub fn assert_initialized<T: Pack + IsInitialized>(
     mpl_utils::assert_initialized(account_info, MetadataError::Uninitialized)
 }
 
+/// Asserts that the mint account is initialized, returning its base state.
+///
+/// The mint can be owned by either token program; Token-2022 extensions are
+/// validated but not returned.
+pub fn assert_initialized_mint(mint_info: &AccountInfo) -> Result<Mint, ProgramError> {
+    unpack_initialized::<Mint, spl_token_2022::state::Mint>(mint_info)
+}
+
+/// Asserts that the token account is initialized, returning its base state.
+///
+/// The token account can be owned by either token program; Token-2022 extensions
+/// are validated but not returned.
+pub fn assert_initialized_token_account(token_info: &AccountInfo) -> Result<Account, ProgramError> {
+    unpack_initialized::<Account, spl_token_2022::state::Account>(token_info)
+}
+
+/// Unpacks the base state `T` of an account using the layout (with extensions) `S`
+/// of Token-2022, which is compatible with SPL Token accounts.
+fn unpack_initialized<T: Pack + IsInitialized, S: BaseState>(
+    account_info: &AccountInfo,
+) -> Result<T, ProgramError> {
+    let data = account_info.try_borrow_data()?;
+
+    StateWithExtensions::<S>::unpack(&data).map_err(|error| match error {
+        ProgramError::UninitializedAccount => MetadataError::Uninitialized.into(),
+        error => error,
+    })?;
+
+    T::unpack_from_slice(&data[..T::LEN])
+}
+
 pub fn assert_mint_authority_matches_mint(
     mint_authority: &COption<Pubkey>,
     mint_authority_info: &AccountInfo,
@@ -121,6 +162,16 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
     mpl_utils::assert_owned_by(account, owner, MetadataError::IncorrectOwner)
 }
 
+/// Asserts that the account is owned by one of the supported token programs
+/// (spl-token or Token-2022).
+pub fn assert_owned_by_token_program(account: &AccountInfo) -> ProgramResult {
+    if !SPL_TOKEN_PROGRAM_IDS.contains(account.owner) {
+        return Err(MetadataError::IncorrectOwner.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_token_program_matches_package(token_program_info: &AccountInfo) -> ProgramResult {
     mpl_utils::token::assert_token_program_matches_package(
         token_program_info,
@@ -156,7 +207,7 @@ pub fn assert_token_matches_owner_and_mint(
     owner: &Pubkey,
     mint: &Pubkey,
 ) -> ProgramResult {
-    let token_account: Account = assert_initialized(token_info)?;
+    let token_account = assert_initialized_token_account(token_info)?;
 
     if token_account.owner != *owner {
         return Err(MetadataError::InvalidOwner.into());
@@ -1,7 +1,18 @@
 use mpl_utils::cmp_pubkeys;
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    sysvar::{instructions::get_instruction_relative, Sysvar},
+};
 
-use crate::{error::MetadataError, state::ProgrammableConfig};
+use crate::{
+    error::MetadataError,
+    state::{
+        MetadataDelegateRecord, ProgrammableConfig, TokenDelegateRole, TokenMetadataAccount,
+        TokenRecord,
+    },
+};
 
 /// If a programmable rule set is present, then we need:
 ///   1. authorization rules and data
@@ -29,3 +40,83 @@ pub(crate) fn assert_valid_authorization<'info>(
 
     Err(MetadataError::InvalidAuthorizationRules.into())
 }
+
+/// Checks that the expiry of a new delegate (if any) is in the future.
+pub(crate) fn assert_valid_delegate_expiry(expiry: Option<i64>) -> ProgramResult {
+    match expiry {
+        Some(expiry) if expiry <= Clock::get()?.unix_timestamp => {
+            Err(MetadataError::InvalidDelegateExpiry.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the token delegate of a token record has not expired.
+pub(crate) fn assert_token_delegate_not_expired(token_record: &TokenRecord) -> ProgramResult {
+    if token_record.is_delegate_expired(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::DelegateExpired.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the token is not locked by its holder.
+pub(crate) fn assert_not_holder_locked(token_record: &TokenRecord) -> ProgramResult {
+    if token_record.holder_locked {
+        return Err(MetadataError::HolderLocked.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the token is not frozen by the `CollectionFreeze` delegate of its collection.
+pub(crate) fn assert_not_collection_frozen(token_record: &TokenRecord) -> ProgramResult {
+    if token_record.collection_frozen {
+        return Err(MetadataError::CollectionFrozen.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the token does not have an active `Rental` delegate.
+pub(crate) fn assert_not_rented(token_record: &TokenRecord) -> ProgramResult {
+    if token_record.is_rented(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::RentalActive.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the metadata delegate of a delegate record has not expired.
+pub(crate) fn assert_metadata_delegate_not_expired(
+    delegate_record_info: &AccountInfo,
+) -> ProgramResult {
+    let delegate_record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+    if delegate_record.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::DelegateExpired.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a staked asset is being unlocked by its staking program.
+///
+/// `Staking` delegates record the program that is allowed to unlock the asset, so the
+/// top-level instruction must be from that program (the unlock is a CPI from it).
+pub(crate) fn assert_staking_program_unlock(
+    token_record: &TokenRecord,
+    sysvar_instructions_info: &AccountInfo,
+) -> ProgramResult {
+    if let (Some(TokenDelegateRole::Staking), Some(staking_program)) =
+        (&token_record.delegate_role, &token_record.staking_program)
+    {
+        let current_ix = get_instruction_relative(0, sysvar_instructions_info)?;
+
+        if !cmp_pubkeys(&current_ix.program_id, staking_program) {
+            return Err(MetadataError::InvalidStakingProgram.into());
+        }
+    }
+
+    Ok(())
+}
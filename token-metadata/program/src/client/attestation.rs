@@ -0,0 +1,212 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use spl_token::state::Account;
+
+use crate::{
+    error::MetadataError,
+    state::{
+        Collection, Metadata, Resizable, TokenMetadataAccount, TokenRecord, TokenStandard,
+        TokenState,
+    },
+};
+
+/// Ownership claim over an asset, built from the state of its accounts at a given slot.
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct OwnershipClaim {
+    /// Slot at which the accounts were read.
+    pub slot: u64,
+    /// Address of the mint.
+    pub mint: Pubkey,
+    /// Address of the token account.
+    pub token: Pubkey,
+    /// Owner of the token account.
+    pub owner: Pubkey,
+    /// Amount of tokens held by the token account.
+    pub amount: u64,
+    /// Token standard of the asset.
+    pub token_standard: Option<TokenStandard>,
+    /// Collection of the asset.
+    pub collection: Option<Collection>,
+    /// State of the token (only present for programmable assets).
+    pub token_state: Option<TokenState>,
+}
+
+impl OwnershipClaim {
+    /// Creates a claim from the data of the metadata, token and token record accounts read
+    /// at `slot`. The token record data is required for programmable assets.
+    pub fn new(
+        slot: u64,
+        token: Pubkey,
+        metadata_data: &[u8],
+        token_data: &[u8],
+        token_record_data: Option<&[u8]>,
+    ) -> Result<Self, ProgramError> {
+        let metadata = Metadata::safe_deserialize(metadata_data)
+            .map_err(|_| MetadataError::DataTypeMismatch)?;
+        let token_account = Account::unpack(token_data)?;
+
+        if token_account.mint != metadata.mint {
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        let token_state = if matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        ) {
+            let token_record = TokenRecord::from_bytes(
+                token_record_data.ok_or(MetadataError::MissingTokenRecord)?,
+            )?;
+            Some(token_record.state)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            slot,
+            mint: metadata.mint,
+            token,
+            owner: token_account.owner,
+            amount: token_account.amount,
+            token_standard: metadata.token_standard,
+            collection: metadata.collection,
+            token_state,
+        })
+    }
+}
+
+/// Ownership claim signed by an attester.
+///
+/// Services trusting the attester can verify the claim presented by a user without
+/// fetching the asset accounts at verification time.
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct OwnershipAttestation {
+    /// The attested claim.
+    pub claim: OwnershipClaim,
+    /// Address of the attester.
+    pub attester: Pubkey,
+    /// Signature of the attester over the serialized claim.
+    pub signature: [u8; 64],
+}
+
+impl OwnershipAttestation {
+    /// Signs the `claim` with the `attester` keypair.
+    pub fn sign(claim: OwnershipClaim, attester: &Keypair) -> Result<Self, ProgramError> {
+        let signature = attester.sign_message(&claim.try_to_vec()?);
+
+        Ok(Self {
+            claim,
+            attester: attester.pubkey(),
+            signature: signature.into(),
+        })
+    }
+
+    /// Verifies that the attestation was signed by the `attester` and returns the
+    /// attested claim.
+    pub fn verify(&self, attester: &Pubkey) -> Result<&OwnershipClaim, ProgramError> {
+        if self.attester != *attester
+            || !Signature::from(self.signature).verify(attester.as_ref(), &self.claim.try_to_vec()?)
+        {
+            return Err(MetadataError::InvalidAttestation.into());
+        }
+
+        Ok(&self.claim)
+    }
+}
+
+/// Deserializes an attestation and verifies that it was signed by the `attester`.
+pub fn verify_attestation(data: &[u8], attester: &Pubkey) -> Result<OwnershipClaim, ProgramError> {
+    let attestation = OwnershipAttestation::try_from_slice(data)
+        .map_err(|_| MetadataError::InvalidAttestation)?;
+    attestation.verify(attester).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::program_option::COption;
+    use spl_token::state::AccountState;
+
+    use super::*;
+
+    fn asset_data(token_standard: TokenStandard) -> (Vec<u8>, Vec<u8>) {
+        let owner = Pubkey::new_unique();
+
+        let metadata = Metadata {
+            mint: Pubkey::new_unique(),
+            token_standard: Some(token_standard),
+            ..Default::default()
+        };
+
+        let mut metadata_data = metadata.try_to_vec().unwrap();
+        Metadata::pad_length(&mut metadata_data).unwrap();
+
+        let token_account = Account {
+            mint: metadata.mint,
+            owner,
+            amount: 1,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+
+        let mut token_data = vec![0u8; Account::LEN];
+        Account::pack(token_account, &mut token_data).unwrap();
+
+        (metadata_data, token_data)
+    }
+
+    #[test]
+    fn attest_and_verify_ownership() {
+        let (metadata_data, token_data) = asset_data(TokenStandard::NonFungible);
+        let token = Pubkey::new_unique();
+
+        let claim = OwnershipClaim::new(10, token, &metadata_data, &token_data, None).unwrap();
+        assert_eq!(claim.amount, 1);
+        assert_eq!(claim.token_state, None);
+
+        let attester = Keypair::new();
+        let attestation = OwnershipAttestation::sign(claim.clone(), &attester).unwrap();
+        let data = attestation.try_to_vec().unwrap();
+
+        let verified = verify_attestation(&data, &attester.pubkey()).unwrap();
+        assert_eq!(verified, claim);
+
+        // a different attester
+        let err = verify_attestation(&data, &Pubkey::new_unique()).unwrap_err();
+        assert_eq!(err, MetadataError::InvalidAttestation.into());
+
+        // a tampered claim
+        let mut tampered = attestation;
+        tampered.claim.owner = Pubkey::new_unique();
+        let err = tampered.verify(&attester.pubkey()).unwrap_err();
+        assert_eq!(err, MetadataError::InvalidAttestation.into());
+    }
+
+    #[test]
+    fn programmable_claim_requires_token_record() {
+        let (metadata_data, token_data) = asset_data(TokenStandard::ProgrammableNonFungible);
+        let token = Pubkey::new_unique();
+
+        let err = OwnershipClaim::new(10, token, &metadata_data, &token_data, None).unwrap_err();
+        assert_eq!(err, MetadataError::MissingTokenRecord.into());
+
+        let token_record = TokenRecord {
+            state: TokenState::Locked,
+            ..Default::default()
+        };
+        let mut token_record_data = token_record.try_to_vec().unwrap();
+        TokenRecord::pad_length(&mut token_record_data).unwrap();
+
+        let claim = OwnershipClaim::new(
+            10,
+            token,
+            &metadata_data,
+            &token_data,
+            Some(&token_record_data),
+        )
+        .unwrap();
+        assert_eq!(claim.token_state, Some(TokenState::Locked));
+    }
+}
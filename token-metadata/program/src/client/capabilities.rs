@@ -0,0 +1,64 @@
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::transaction_context::TransactionReturnData;
+
+use super::decode_transaction_return_data;
+use crate::instruction::ProgramCapabilities;
+
+/// Returns the capabilities of a deployment of the program.
+///
+/// The `return_data` is the return data of a simulated `GetProgramCapabilities` instruction
+/// (see `instruction::get_program_capabilities`). Deployments that predate the instruction
+/// fail the simulation without return data; in this case, or when the return data was not
+/// set by `program_id`, no capabilities are reported and clients should only rely on the
+/// instructions available on every deployment.
+pub fn program_capabilities(
+    program_id: &Pubkey,
+    return_data: Option<&TransactionReturnData>,
+) -> ProgramCapabilities {
+    match decode_transaction_return_data(program_id, return_data) {
+        Ok(Some(capabilities)) => capabilities,
+        Ok(None) => ProgramCapabilities::default(),
+        // deployments that predate the return data envelope set the capabilities only
+        Err(_) => return_data
+            .and_then(|return_data| ProgramCapabilities::try_from_slice(&return_data.data).ok())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+
+    use super::*;
+    use crate::{instruction::ProgramCapability, return_data::encode_return_data};
+
+    #[test]
+    fn decodes_envelope_and_legacy_return_data() {
+        let capabilities = ProgramCapabilities::from_capabilities(&[ProgramCapability::CreateV1]);
+
+        let return_data = TransactionReturnData {
+            program_id: crate::ID,
+            data: encode_return_data(&capabilities).unwrap(),
+        };
+        assert_eq!(
+            program_capabilities(&crate::ID, Some(&return_data)),
+            capabilities
+        );
+
+        let legacy = TransactionReturnData {
+            program_id: crate::ID,
+            data: capabilities.try_to_vec().unwrap(),
+        };
+        assert_eq!(
+            program_capabilities(&crate::ID, Some(&legacy)),
+            capabilities
+        );
+
+        // return data set by another program is ignored
+        assert_eq!(
+            program_capabilities(&Pubkey::new_unique(), Some(&return_data)),
+            ProgramCapabilities::default()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use borsh::BorshDeserialize;
+use solana_program::instruction::Instruction;
+
+use crate::{
+    error::MetadataError,
+    instruction::{
+        create_collection_instructions, set_compute_unit_limit, CollectionAddresses,
+        CreateCollectionParams, MetadataInstruction, CREATE_COLLECTION_SETUP_LEN,
+        MAX_COMPUTE_UNIT_LIMIT,
+    },
+};
+
+/// Returns the transactions to launch a collection parent, together with the addresses of
+/// its accounts (see `instruction::create_collection_instructions`).
+///
+/// The first transaction creates and mints the collection parent, so it must be signed by
+/// the mint, the authority and the payer. The second transaction, only present when the
+/// collection parent uses the default rule set or has a collection delegate, must be signed
+/// by the authority and the payer. Each transaction starts with a compute unit limit that
+/// covers the compute budget hints of its instructions.
+pub fn create_collection(
+    params: CreateCollectionParams,
+) -> Result<(CollectionAddresses, Vec<Vec<Instruction>>), MetadataError> {
+    let token_standard = params.asset_data.token_standard;
+    let has_rule_set = params.asset_data.rule_set.is_some() || params.use_default_rule_set;
+
+    let (addresses, mut setup) = create_collection_instructions(params)?;
+    let configure = setup.split_off(CREATE_COLLECTION_SETUP_LEN);
+
+    let transactions = [setup, configure]
+        .into_iter()
+        .filter(|instructions| !instructions.is_empty())
+        .map(|instructions| {
+            let units = instructions
+                .iter()
+                .filter_map(|instruction| {
+                    MetadataInstruction::try_from_slice(&instruction.data).ok()
+                })
+                .fold(0u32, |units, instruction| {
+                    units.saturating_add(
+                        instruction.compute_budget_hint(token_standard, has_rule_set),
+                    )
+                })
+                .min(MAX_COMPUTE_UNIT_LIMIT);
+
+            let mut transaction = Vec::with_capacity(instructions.len() + 1);
+            transaction.push(set_compute_unit_limit(units));
+            transaction.extend(instructions);
+            transaction
+        })
+        .collect();
+
+    Ok((addresses, transactions))
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::{
+        pda::find_metadata_account,
+        programs,
+        state::{AssetData, TokenStandard},
+    };
+
+    fn params(token_standard: TokenStandard) -> CreateCollectionParams {
+        let authority = Pubkey::new_unique();
+
+        CreateCollectionParams {
+            mint: Pubkey::new_unique(),
+            authority,
+            payer: authority,
+            asset_data: AssetData::new(
+                token_standard,
+                "Collection".to_string(),
+                "COL".to_string(),
+                "uri".to_string(),
+            ),
+            collection_delegate: None,
+            use_default_rule_set: false,
+        }
+    }
+
+    #[test]
+    fn create_and_mint_in_single_transaction() {
+        let params = params(TokenStandard::NonFungible);
+        let mint = params.mint;
+
+        let (addresses, transactions) = create_collection(params).unwrap();
+
+        assert_eq!(addresses.metadata, find_metadata_account(&mint).0);
+        assert_eq!(addresses.token_record, None);
+        assert_eq!(addresses.delegate_record, None);
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].len(), 3);
+        assert_eq!(
+            transactions[0][0].program_id,
+            programs::COMPUTE_BUDGET_PROGRAM
+        );
+        assert!(matches!(
+            MetadataInstruction::try_from_slice(&transactions[0][1].data).unwrap(),
+            MetadataInstruction::Create(_)
+        ));
+        assert!(matches!(
+            MetadataInstruction::try_from_slice(&transactions[0][2].data).unwrap(),
+            MetadataInstruction::Mint(_)
+        ));
+    }
+
+    #[test]
+    fn programmable_collection_with_delegate() {
+        let mut params = params(TokenStandard::ProgrammableNonFungible);
+        params.collection_delegate = Some(Pubkey::new_unique());
+        params.use_default_rule_set = true;
+
+        let (addresses, transactions) = create_collection(params).unwrap();
+
+        assert!(addresses.token_record.is_some());
+        assert!(addresses.delegate_record.is_some());
+        assert!(addresses.delegate_registry.is_some());
+
+        assert_eq!(transactions.len(), 2);
+        assert!(matches!(
+            MetadataInstruction::try_from_slice(&transactions[1][1].data).unwrap(),
+            MetadataInstruction::Update(_)
+        ));
+        assert!(matches!(
+            MetadataInstruction::try_from_slice(&transactions[1][2].data).unwrap(),
+            MetadataInstruction::Delegate(_)
+        ));
+    }
+
+    #[test]
+    fn default_rule_set_requires_programmable_collection() {
+        let mut params = params(TokenStandard::NonFungible);
+        params.use_default_rule_set = true;
+
+        assert_eq!(
+            create_collection(params).unwrap_err(),
+            MetadataError::InvalidTokenStandard
+        );
+    }
+}
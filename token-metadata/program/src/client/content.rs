@@ -0,0 +1,35 @@
+use solana_program::hash::hash;
+
+use crate::state::Metadata;
+
+/// Returns the hash of the off-chain JSON of an asset, as attested by `VerifyContent`.
+///
+/// The hash is computed over the raw bytes served at the URI of the asset, so the content
+/// must not be re-serialized (e.g., parsed and formatted) before hashing.
+pub fn content_hash(content: &[u8]) -> [u8; 32] {
+    hash(content).to_bytes()
+}
+
+/// Returns whether the off-chain JSON matches the hash attested by a creator of the asset.
+///
+/// Assets without an attested hash never match.
+pub fn matches_content(metadata: &Metadata, content: &[u8]) -> bool {
+    metadata.uri_hash == Some(content_hash(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_attested_content() {
+        let content = br#"{"name":"Digital Asset"}"#;
+
+        let mut metadata = Metadata::default();
+        assert!(!matches_content(&metadata, content));
+
+        metadata.uri_hash = Some(content_hash(content));
+        assert!(matches_content(&metadata, content));
+        assert!(!matches_content(&metadata, br#"{"name":"Swapped"}"#));
+    }
+}
@@ -0,0 +1,15 @@
+use solana_program::program_error::ProgramError;
+
+use crate::state::{FlagReason, Flagged};
+
+/// Returns the active flags of an asset.
+///
+/// The `data` is the content of the `Flagged` account of the asset (derived using
+/// `pda::find_flagged_account`); when the account does not exist, `None` should be
+/// passed and an empty list is returned.
+pub fn flags(data: Option<&[u8]>) -> Result<Vec<FlagReason>, ProgramError> {
+    match data {
+        Some(data) if !data.is_empty() => Ok(Flagged::from_bytes(data)?.reasons()),
+        _ => Ok(Vec::new()),
+    }
+}
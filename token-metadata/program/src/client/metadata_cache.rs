@@ -0,0 +1,272 @@
+use std::{collections::HashMap, thread};
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_sdk::account::Account;
+
+use crate::{
+    error::MetadataError,
+    pda::find_metadata_account,
+    state::{Key, Metadata},
+    utils::meta_deser_unchecked,
+};
+
+/// Maximum number of accounts of a single `getMultipleAccounts` call.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Minimum number of accounts of a batch to deserialize them in parallel.
+const PARALLEL_DESERIALIZE_THRESHOLD: usize = 16;
+
+/// Response of a `getMultipleAccounts` call.
+pub struct MultipleAccounts {
+    /// Slot of the response context.
+    pub slot: u64,
+    /// Accounts in the order of the requested addresses (`None` if the account does not exist).
+    pub accounts: Vec<Option<Account>>,
+}
+
+/// Cached metadata of a mint.
+struct CacheEntry {
+    slot: u64,
+    metadata: Option<Metadata>,
+}
+
+/// Cache of `Metadata` accounts indexed by mint.
+///
+/// The accounts are fetched in batches of up to `MAX_MULTIPLE_ACCOUNTS` using the `fetch`
+/// function, which is expected to perform a `getMultipleAccounts` call for the addresses.
+/// Each entry stores the slot it was fetched at; entries fetched before the slot set by
+/// `invalidate_before` are fetched again.
+pub struct MetadataCache<F>
+where
+    F: FnMut(&[Pubkey]) -> Result<MultipleAccounts, ProgramError>,
+{
+    fetch: F,
+    entries: HashMap<Pubkey, CacheEntry>,
+    min_slot: u64,
+}
+
+impl<F> MetadataCache<F>
+where
+    F: FnMut(&[Pubkey]) -> Result<MultipleAccounts, ProgramError>,
+{
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            entries: HashMap::new(),
+            min_slot: 0,
+        }
+    }
+
+    /// Returns the metadata of the mint, fetching it if it is not cached. Returns `None`
+    /// when the mint does not have a metadata account.
+    pub fn get_or_fetch(&mut self, mint: &Pubkey) -> Result<Option<&Metadata>, ProgramError> {
+        if !self.is_cached(mint) {
+            self.fetch_all(&[*mint])?;
+        }
+
+        Ok(self
+            .entries
+            .get(mint)
+            .and_then(|entry| entry.metadata.as_ref()))
+    }
+
+    /// Fetches the metadata of the mints that are not cached.
+    pub fn fetch_all(&mut self, mints: &[Pubkey]) -> Result<(), ProgramError> {
+        let mut missing: Vec<Pubkey> = mints
+            .iter()
+            .filter(|mint| !self.is_cached(mint))
+            .copied()
+            .collect();
+        missing.sort();
+        missing.dedup();
+
+        for chunk in missing.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses: Vec<Pubkey> = chunk
+                .iter()
+                .map(|mint| find_metadata_account(mint).0)
+                .collect();
+
+            let MultipleAccounts { slot, accounts } = (self.fetch)(&addresses)?;
+
+            if accounts.len() != addresses.len() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+
+            for (mint, metadata) in chunk.iter().zip(deserialize_metadata_accounts(&accounts)?) {
+                self.entries.insert(*mint, CacheEntry { slot, metadata });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the slot at which the metadata of the mint was fetched (if cached).
+    pub fn slot(&self, mint: &Pubkey) -> Option<u64> {
+        self.entries.get(mint).map(|entry| entry.slot)
+    }
+
+    /// Invalidates the entries fetched before `slot`.
+    pub fn invalidate_before(&mut self, slot: u64) {
+        self.min_slot = slot;
+        self.entries.retain(|_, entry| entry.slot >= slot);
+    }
+
+    fn is_cached(&self, mint: &Pubkey) -> bool {
+        self.entries
+            .get(mint)
+            .map(|entry| entry.slot >= self.min_slot)
+            .unwrap_or(false)
+    }
+}
+
+/// Deserializes the data of a `Metadata` account.
+///
+/// Accounts created by older versions of the program or resized with `Resize` are shorter
+/// than the current `MAX_METADATA_LEN`; fields added after the account was created take
+/// their default values.
+pub fn deserialize_metadata(mut data: &[u8]) -> Result<Metadata, ProgramError> {
+    if Key::of(data) != Some(Key::MetadataV1) {
+        return Err(MetadataError::DataTypeMismatch.into());
+    }
+
+    meta_deser_unchecked(&mut data).map_err(|_| MetadataError::DataTypeMismatch.into())
+}
+
+/// Deserializes the `Metadata` accounts of a `getMultipleAccounts` response, splitting
+/// large batches across threads.
+fn deserialize_metadata_accounts(
+    accounts: &[Option<Account>],
+) -> Result<Vec<Option<Metadata>>, ProgramError> {
+    let deserialize = |accounts: &[Option<Account>]| {
+        accounts
+            .iter()
+            .map(|account| {
+                account
+                    .as_ref()
+                    .filter(|account| account.owner == crate::ID && !account.data.is_empty())
+                    .map(|account| deserialize_metadata(&account.data))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, ProgramError>>()
+    };
+
+    if accounts.len() < PARALLEL_DESERIALIZE_THRESHOLD {
+        return deserialize(accounts);
+    }
+
+    let threads = thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1);
+    let chunk_size = (accounts.len() + threads - 1) / threads;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = accounts
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || deserialize(chunk)))
+            .collect();
+
+        let mut metadata = Vec::with_capacity(accounts.len());
+
+        for handle in handles {
+            metadata.extend(
+                handle
+                    .join()
+                    .map_err(|_| ProgramError::from(MetadataError::DataTypeMismatch))??,
+            );
+        }
+
+        Ok(metadata)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    fn account(metadata: &Metadata) -> Account {
+        Account {
+            lamports: 1,
+            data: metadata.try_to_vec().unwrap(),
+            owner: crate::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn fetch_in_batches() {
+        let mints: Vec<Pubkey> = (0..150).map(|_| Pubkey::new_unique()).collect();
+        let accounts: HashMap<Pubkey, Account> = mints
+            .iter()
+            .take(149)
+            .map(|mint| {
+                let metadata = Metadata {
+                    mint: *mint,
+                    ..Default::default()
+                };
+                (find_metadata_account(mint).0, account(&metadata))
+            })
+            .collect();
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let slot = Rc::new(RefCell::new(10));
+
+        let mut cache = MetadataCache::new({
+            let calls = calls.clone();
+            let slot = slot.clone();
+            move |addresses: &[Pubkey]| {
+                calls.borrow_mut().push(addresses.len());
+                Ok(MultipleAccounts {
+                    slot: *slot.borrow(),
+                    accounts: addresses
+                        .iter()
+                        .map(|address| accounts.get(address).cloned())
+                        .collect(),
+                })
+            }
+        });
+
+        cache.fetch_all(&mints).unwrap();
+        assert_eq!(*calls.borrow(), vec![MAX_MULTIPLE_ACCOUNTS, 50]);
+
+        // cached entries do not trigger a new fetch
+        assert_eq!(
+            cache.get_or_fetch(&mints[0]).unwrap().unwrap().mint,
+            mints[0]
+        );
+        assert!(cache.get_or_fetch(&mints[149]).unwrap().is_none());
+        assert_eq!(calls.borrow().len(), 2);
+        assert_eq!(cache.slot(&mints[0]), Some(10));
+
+        // invalidated entries are fetched again
+        *slot.borrow_mut() = 20;
+        cache.invalidate_before(15);
+        assert_eq!(
+            cache.get_or_fetch(&mints[0]).unwrap().unwrap().mint,
+            mints[0]
+        );
+        assert_eq!(*calls.borrow(), vec![MAX_MULTIPLE_ACCOUNTS, 50, 1]);
+        assert_eq!(cache.slot(&mints[0]), Some(20));
+        assert_eq!(cache.slot(&mints[1]), None);
+    }
+
+    #[test]
+    fn deserialize_legacy_metadata() {
+        let metadata = Metadata {
+            mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        // accounts created before the latest fields were added are shorter
+        let mut data = metadata.try_to_vec().unwrap();
+        data.truncate(data.len() - 3);
+        assert_eq!(deserialize_metadata(&data).unwrap(), metadata);
+
+        let err = deserialize_metadata(&[Key::EditionV1 as u8]).unwrap_err();
+        assert_eq!(err, MetadataError::DataTypeMismatch.into());
+    }
+}
@@ -0,0 +1,22 @@
+//! Client-side helpers to read the state of Token Metadata accounts and compose its
+//! instructions.
+
+pub(crate) mod attestation;
+pub(crate) mod capabilities;
+pub(crate) mod collection;
+pub(crate) mod content;
+pub(crate) mod flag;
+pub(crate) mod metadata_cache;
+#[cfg(feature = "ata")]
+pub(crate) mod pnft;
+pub(crate) mod return_data;
+
+pub use attestation::*;
+pub use capabilities::*;
+pub use collection::*;
+pub use content::*;
+pub use flag::*;
+pub use metadata_cache::*;
+#[cfg(feature = "ata")]
+pub use pnft::*;
+pub use return_data::*;
@@ -0,0 +1,257 @@
+use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::account::Account;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use crate::{
+    error::MetadataError,
+    pda::{
+        find_delegate_registry_account, find_master_edition_account, find_metadata_account,
+        find_token_record_account,
+    },
+    state::{
+        DelegateRegistry, MasterEditionV2, Metadata, ProgrammableConfig, Resizable,
+        TokenMetadataAccount, TokenRecord,
+    },
+};
+
+/// Index of the mint account on the account set.
+pub const PNFT_MINT_INDEX: usize = 0;
+/// Index of the metadata account on the account set.
+pub const PNFT_METADATA_INDEX: usize = 1;
+/// Index of the master edition account on the account set.
+pub const PNFT_MASTER_EDITION_INDEX: usize = 2;
+/// Index of the token account on the account set.
+pub const PNFT_TOKEN_INDEX: usize = 3;
+/// Index of the token record account on the account set.
+pub const PNFT_TOKEN_RECORD_INDEX: usize = 4;
+/// Index of the delegate registry account on the account set.
+pub const PNFT_DELEGATE_REGISTRY_INDEX: usize = 5;
+
+/// Number of accounts on the account set.
+pub const PNFT_ACCOUNT_SET_LEN: usize = 6;
+
+/// Returns the addresses of the accounts needed to resolve the state of a programmable
+/// asset held by `owner`, ordered to be fetched with a single `getMultipleAccounts` call.
+///
+/// The token account is the associated token account of the `owner`. The rule set is
+/// stored on the metadata, so its address is only known once the accounts are fetched
+/// (see `PnftState::rule_set`).
+pub fn pnft_account_set(mint: &Pubkey, owner: &Pubkey) -> Vec<Pubkey> {
+    let token = get_associated_token_address(owner, mint);
+
+    vec![
+        *mint,
+        find_metadata_account(mint).0,
+        find_master_edition_account(mint).0,
+        token,
+        find_token_record_account(mint, &token).0,
+        find_delegate_registry_account(mint).0,
+    ]
+}
+
+/// State of a programmable asset, parsed from the accounts of `pnft_account_set`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PnftState {
+    pub mint: Mint,
+    pub metadata: Metadata,
+    pub master_edition: Option<MasterEditionV2>,
+    /// Token account of the owner (`None` if the account does not exist).
+    pub token: Option<TokenAccount>,
+    /// Token record of the token account (`None` if the account does not exist).
+    pub token_record: Option<TokenRecord>,
+    /// Metadata delegates of the mint (`None` if the registry does not exist).
+    pub delegate_registry: Option<DelegateRegistry>,
+}
+
+impl PnftState {
+    /// Parses the response of a `getMultipleAccounts` call for the addresses returned by
+    /// `pnft_account_set`. The mint and metadata accounts are required.
+    pub fn from_accounts(accounts: &[Option<Account>]) -> Result<Self, ProgramError> {
+        if accounts.len() != PNFT_ACCOUNT_SET_LEN {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let mint = match &accounts[PNFT_MINT_INDEX] {
+            Some(account) => Mint::unpack(&account.data)?,
+            None => return Err(ProgramError::UninitializedAccount),
+        };
+
+        let metadata = match &accounts[PNFT_METADATA_INDEX] {
+            Some(account) => Metadata::safe_deserialize(&account.data)
+                .map_err(|_| MetadataError::DataTypeMismatch)?,
+            None => return Err(ProgramError::UninitializedAccount),
+        };
+
+        let master_edition = accounts[PNFT_MASTER_EDITION_INDEX]
+            .as_ref()
+            .map(|account| {
+                MasterEditionV2::safe_deserialize(&account.data)
+                    .map_err(|_| MetadataError::DataTypeMismatch)
+            })
+            .transpose()?;
+
+        let token = accounts[PNFT_TOKEN_INDEX]
+            .as_ref()
+            .map(|account| TokenAccount::unpack(&account.data))
+            .transpose()?;
+
+        let token_record = accounts[PNFT_TOKEN_RECORD_INDEX]
+            .as_ref()
+            .map(|account| TokenRecord::from_bytes(&account.data))
+            .transpose()?;
+
+        let delegate_registry = accounts[PNFT_DELEGATE_REGISTRY_INDEX]
+            .as_ref()
+            .map(|account| DelegateRegistry::from_bytes(&account.data))
+            .transpose()?;
+
+        Ok(Self {
+            mint,
+            metadata,
+            master_edition,
+            token,
+            token_record,
+            delegate_registry,
+        })
+    }
+
+    /// Returns the address of the rule set of the asset (if any), which needs to be
+    /// fetched separately.
+    pub fn rule_set(&self) -> Option<Pubkey> {
+        match self.metadata.programmable_config {
+            Some(ProgrammableConfig::V1 { rule_set }) => rule_set,
+            None => None,
+        }
+    }
+
+    /// Returns the permanent delegate of the asset (if any).
+    ///
+    /// The permanent delegate can transfer the asset without the consent of the holder,
+    /// so wallets should warn holders of assets that have one.
+    pub fn permanent_delegate(&self) -> Option<Pubkey> {
+        self.metadata.permanent_delegate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_program::program_option::COption;
+    use spl_token::state::AccountState;
+
+    use super::*;
+    use crate::state::{TokenStandard, TokenState};
+
+    fn account(data: Vec<u8>) -> Option<Account> {
+        Some(Account {
+            lamports: 1,
+            data,
+            owner: crate::ID,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+
+    #[test]
+    fn parse_pnft_account_set() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let rule_set = Pubkey::new_unique();
+        let permanent_delegate = Pubkey::new_unique();
+
+        let addresses = pnft_account_set(&mint, &owner);
+        assert_eq!(addresses.len(), PNFT_ACCOUNT_SET_LEN);
+        assert_eq!(addresses[PNFT_MINT_INDEX], mint);
+        assert_eq!(
+            addresses[PNFT_METADATA_INDEX],
+            find_metadata_account(&mint).0
+        );
+        assert_eq!(
+            addresses[PNFT_TOKEN_RECORD_INDEX],
+            find_token_record_account(&mint, &addresses[PNFT_TOKEN_INDEX]).0
+        );
+
+        let mut mint_data = vec![0u8; Mint::LEN];
+        Mint::pack(
+            Mint {
+                supply: 1,
+                decimals: 0,
+                is_initialized: true,
+                ..Default::default()
+            },
+            &mut mint_data,
+        )
+        .unwrap();
+
+        let metadata = Metadata {
+            mint,
+            token_standard: Some(TokenStandard::ProgrammableNonFungible),
+            programmable_config: Some(ProgrammableConfig::V1 {
+                rule_set: Some(rule_set),
+            }),
+            permanent_delegate: Some(permanent_delegate),
+            ..Default::default()
+        };
+        let mut metadata_data = metadata.try_to_vec().unwrap();
+        Metadata::pad_length(&mut metadata_data).unwrap();
+
+        let mut token_data = vec![0u8; TokenAccount::LEN];
+        TokenAccount::pack(
+            TokenAccount {
+                mint,
+                owner,
+                amount: 1,
+                delegate: COption::None,
+                state: AccountState::Frozen,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            },
+            &mut token_data,
+        )
+        .unwrap();
+
+        let token_record = TokenRecord {
+            state: TokenState::Locked,
+            ..Default::default()
+        };
+        let mut token_record_data = token_record.try_to_vec().unwrap();
+        TokenRecord::pad_length(&mut token_record_data).unwrap();
+
+        let state = PnftState::from_accounts(&[
+            account(mint_data.clone()),
+            account(metadata_data.clone()),
+            None,
+            account(token_data),
+            account(token_record_data),
+            None,
+        ])
+        .unwrap();
+
+        assert_eq!(state.metadata.mint, mint);
+        assert_eq!(state.rule_set(), Some(rule_set));
+        assert_eq!(state.permanent_delegate(), Some(permanent_delegate));
+        assert_eq!(state.master_edition, None);
+        assert_eq!(state.token.unwrap().owner, owner);
+        assert_eq!(state.token_record.unwrap().state, TokenState::Locked);
+
+        // the owner does not hold the asset
+        let state = PnftState::from_accounts(&[
+            account(mint_data.clone()),
+            account(metadata_data),
+            None,
+            None,
+            None,
+            None,
+        ])
+        .unwrap();
+        assert_eq!(state.token, None);
+        assert_eq!(state.token_record, None);
+
+        // the metadata is required
+        let err = PnftState::from_accounts(&[account(mint_data), None, None, None, None, None])
+            .unwrap_err();
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
+}
@@ -0,0 +1,22 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_sdk::transaction_context::TransactionReturnData;
+
+use crate::return_data::{decode_return_data, ReturnData};
+
+/// Decodes the return data of a (simulated) transaction that executed an instruction
+/// of the program.
+///
+/// Returns `None` when the transaction has no return data or when the return data was
+/// not set by `program_id`, and an error when the return data does not use the current
+/// envelope or carries a different kind of value.
+pub fn decode_transaction_return_data<T: ReturnData>(
+    program_id: &Pubkey,
+    return_data: Option<&TransactionReturnData>,
+) -> Result<Option<T>, ProgramError> {
+    match return_data {
+        Some(return_data) if return_data.program_id == *program_id => {
+            decode_return_data(&return_data.data).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
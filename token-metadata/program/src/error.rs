@@ -687,16 +687,452 @@ pub enum MetadataError {
     /// 173
     #[error("Cannot update the rule set of a programmable asset that has a delegate")]
     CannotUpdateAssetWithDelegate,
+
+    /// 174
     #[error("Invalid Associated Token Account Program")]
     InvalidAssociatedTokenAccountProgram,
 
-    /// 174
+    /// 175
     #[error("Invalid InstructionsSysvar")]
     InvalidInstructionsSysvar,
 
-    /// 175
+    /// 176
     #[error("Authority cannot apply all update args")]
     InvalidUpdateArgs,
+
+    /// 177
+    #[error("Authority is not allowed to flag assets")]
+    InvalidFlagAuthority,
+
+    /// 178
+    #[error("Flag is not set on the asset")]
+    FlagNotSet,
+
+    /// 179
+    #[error("Program was built without the feature required by the instruction")]
+    FeatureNotEnabled,
+
+    /// 180
+    #[error("Governance update authority can only be used in a proposal execution")]
+    InvalidGovernanceExecution,
+
+    /// 181
+    #[error("Ownership attestation is invalid")]
+    InvalidAttestation,
+
+    /// 182
+    #[error("Account is expected to be writable")]
+    ExpectedWritableAccount,
+
+    /// 183
+    #[error("Print window start must not be after its end")]
+    InvalidPrintWindow,
+
+    /// 184
+    #[error("Print window is closed")]
+    PrintWindowClosed,
+
+    /// 185
+    #[error("Invalid operation name")]
+    InvalidOperationName,
+
+    /// 186
+    #[error("Metadata data does not match the archived hash")]
+    ArchivedDataMismatch,
+
+    /// 187
+    #[error("Invalid compressed asset proof")]
+    InvalidCompressionProof,
+
+    /// 188
+    #[error("Number of recipients does not match the number of amounts")]
+    InvalidBatchRecipients,
+
+    /// 189
+    #[error("Mint supply would exceed the maximum supply of the asset")]
+    MintSupplyExceeded,
+
+    /// 190
+    #[error("Delegate has expired")]
+    DelegateExpired,
+
+    /// 191
+    #[error("Delegate expiry must be in the future")]
+    InvalidDelegateExpiry,
+
+    /// 192
+    #[error("Staked assets can only be unlocked by the staking program")]
+    InvalidStakingProgram,
+
+    /// 193
+    #[error("Instruction is deprecated, use its replacement instead")]
+    DeprecatedInstruction,
+
+    /// 194
+    #[error("Authority is not allowed to set default rule sets")]
+    InvalidDefaultRuleSetsAuthority,
+
+    /// 195
+    #[error("Missing default rule sets account")]
+    MissingDefaultRuleSets,
+
+    /// 196
+    #[error("Delegate has locked items of the collection")]
+    CollectionItemsLocked,
+
+    /// 197
+    #[error("Missing edition marker account")]
+    MissingEditionMarkerAccount,
+
+    /// 198
+    #[error("Asset has not been burned")]
+    AssetNotBurned,
+
+    /// 199
+    #[error("Invalid rent destination")]
+    InvalidRentDestination,
+
+    /// 200
+    #[error("Token is locked by its holder")]
+    HolderLocked,
+
+    /// 201
+    #[error("Holder lock cool-down has not elapsed")]
+    HolderLockCooldown,
+
+    /// 202
+    #[error("Missing collection mint account")]
+    MissingCollectionMint,
+
+    /// 203
+    #[error("Missing collection master edition account")]
+    MissingCollectionMasterEdition,
+
+    /// 204
+    #[error("An asset cannot be deposited into its own escrow")]
+    CannotDepositIntoOwnEscrow,
+
+    /// 205
+    #[error("Missing content rating account")]
+    MissingContentRatingAccount,
+
+    /// 206
+    #[error("Rule set changes must be proposed and committed after a delay")]
+    RuleSetChangeRequiresProposal,
+
+    /// 207
+    #[error("Rule set delay cannot be decreased")]
+    InvalidRuleSetDelay,
+
+    /// 208
+    #[error("Rule set proposal cannot be committed yet")]
+    RuleSetProposalNotCommittable,
+
+    /// 209
+    #[error("Collection is paused")]
+    CollectionPaused,
+
+    /// 210
+    #[error("Invalid collection pause duration")]
+    InvalidPauseDuration,
+
+    /// 211
+    #[error("Missing collection pause account")]
+    MissingCollectionPause,
+
+    /// 212
+    #[error("Invalid authority config")]
+    InvalidAuthorityConfig,
+
+    /// 213
+    #[error("Authority is not a signer of the authority config")]
+    NotAuthorityConfigSigner,
+
+    /// 214
+    #[error("Invalid return data")]
+    InvalidReturnData,
+
+    /// 215
+    #[error("Invalid fee authority")]
+    InvalidFeeAuthority,
+
+    /// 216
+    #[error("Delegate has no remaining uses")]
+    NotEnoughDelegateUses,
+
+    /// 217
+    #[error("Missing creators overflow account")]
+    MissingCreatorsOverflow,
+
+    /// 218
+    #[error("Creators overflow account does not match the metadata")]
+    InvalidCreatorsOverflow,
+
+    /// 219
+    #[error("Asset does not have verified creators")]
+    NoVerifiedCreators,
+
+    /// 220
+    #[error("Royalty recipient does not match the creator")]
+    InvalidRoyaltyRecipient,
+
+    /// 221
+    #[error("Primary sale already happened")]
+    PrimarySaleAlreadyHappened,
+
+    /// 222
+    #[error("Missing collection membership account")]
+    MissingCollectionMembership,
+
+    /// 223
+    #[error("Collection membership expiry must be in the future")]
+    InvalidCollectionMembershipExpiry,
+
+    /// 224
+    #[error("Collection membership has not expired")]
+    CollectionMembershipNotExpired,
+
+    /// 225
+    #[error("Holder has not opted in to collection freezes")]
+    CollectionFreezeNotOptedIn,
+
+    /// 226
+    #[error("Token is frozen by its collection")]
+    CollectionFrozen,
+
+    /// 227
+    #[error("Account cannot be closed into itself")]
+    InvalidCloseDestination,
+
+    /// 228
+    #[error("Mint authority signature is missing or invalid")]
+    InvalidMintAuthoritySignature,
+
+    /// 229
+    #[error("Programmable config extension account is missing or invalid")]
+    InvalidProgrammableConfigExtension,
+
+    /// 230
+    #[error("Operation not supported while the asset uses the legacy layout")]
+    LegacyLayoutAsset,
+
+    /// 231
+    #[error("Token record is still in use")]
+    TokenRecordInUse,
+
+    /// 232
+    #[error("Migration grace period must be at least seven days")]
+    InvalidMigrationGracePeriod,
+
+    /// 233
+    #[error("Migration grace period has not elapsed")]
+    MigrationGracePeriodNotElapsed,
+
+    /// 234
+    #[error("Migration grace period has elapsed")]
+    MigrationGracePeriodElapsed,
+
+    /// 235
+    #[error("Authority is not allowed to burn the asset")]
+    UnauthorizedBurn,
+
+    /// 236
+    #[error("Permanent delegate must also be the delegate of the token account")]
+    PermanentDelegateNotTokenDelegate,
+
+    /// 237
+    #[error("Only the delegate or the holder that approved it can revoke the delegate")]
+    UnauthorizedRevoke,
+
+    /// 238
+    #[error("Authority is not allowed to update the asset")]
+    UnauthorizedUpdate,
+
+    /// 239
+    #[error("Delegate role is not allowed to update the asset")]
+    InvalidUpdateDelegateRole,
+
+    /// 240
+    #[error("Asset is on the stolen asset denylist")]
+    StolenAsset,
+
+    /// 241
+    #[error("Collection metadata and denylisted accounts are required to check the denylist")]
+    MissingDenylistAccount,
+
+    /// 242
+    #[error("Uses cool-down has not elapsed")]
+    UsesCooldown,
+
+    /// 243
+    #[error("Missing uses v2 account")]
+    MissingUsesV2Account,
+
+    /// 244
+    #[error("Uses of the asset are rate limited and must be consumed through Use")]
+    RateLimitedUses,
+
+    /// 245
+    #[error("Missing rent destination of the edition tombstone")]
+    MissingTombstoneRentDestination,
+
+    /// 246
+    #[error("Token is rented until the rental delegate expires")]
+    RentalActive,
+
+    /// 247
+    #[error("Transfer session does not match the transfer")]
+    InvalidTransferSession,
+
+    /// 248
+    #[error("Transfer session has expired")]
+    TransferSessionExpired,
+
+    /// 249
+    #[error("Invalid duration of the transfer session")]
+    InvalidTransferSessionDuration,
+
+    /// 250
+    #[error("Non-transferable assets cannot be transferred")]
+    NonTransferableAsset,
+
+    /// 251
+    #[error("Permanent delegate requires a new Token-2022 mint")]
+    InvalidPermanentDelegateMint,
+
+    /// 252
+    #[error("Collection cannot be paused again before the pause cooldown elapses")]
+    CollectionPauseCooldown,
+
+    /// 253
+    #[error("Mint authority signature has expired")]
+    MintAuthoritySignatureExpired,
+}
+
+/// Broad category of a [`MetadataError`].
+///
+/// Error codes are part of the program interface and are never renumbered, so the category
+/// is not encoded in the code itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCategory {
+    /// The accounts or arguments of the instruction are malformed or do not match.
+    Validation,
+    /// The signer is not allowed to perform the operation.
+    Permission,
+    /// The operation conflicts with the current state of the asset.
+    State,
+}
+
+impl MetadataError {
+    /// Returns the category of the error.
+    pub fn category(&self) -> ErrorCategory {
+        use MetadataError::*;
+
+        match self {
+            UpdateAuthorityIncorrect
+            | UpdateAuthorityIsNotSigner
+            | NotMintAuthority
+            | InvalidMintAuthority
+            | UpdateAuthorityMustBeEqualToMetadataAuthorityAndSigner
+            | MustBeOneOfCreators
+            | CannotVerifyAnotherCreator
+            | CannotUnverifyAnotherCreator
+            | IncorrectOwner
+            | InvalidOwner
+            | InvalidCollectionUpdateAuthority
+            | InvalidUseAuthorityRecord
+            | InvalidCollectionAuthorityRecord
+            | InvalidFreezeAuthority
+            | InvalidDelegate
+            | InvalidUser
+            | RevokeCollectionAuthoritySignerIncorrect
+            | MustBeEscrowAuthority
+            | InvalidBubblegumSigner
+            | MintIsNotSigner
+            | OnlySaleDelegateCanTransfer
+            | InvalidDelegateRoleForTransfer
+            | InvalidTransferAuthority
+            | InvalidAuthorityType
+            | InvalidDelegateRole
+            | CannotUpdateAssetWithDelegate
+            | InvalidFlagAuthority
+            | InvalidDefaultRuleSetsAuthority
+            | NotAuthorityConfigSigner
+            | InvalidFeeAuthority
+            | InvalidMintAuthoritySignature
+            | UnauthorizedBurn
+            | PermanentDelegateNotTokenDelegate
+            | UnauthorizedRevoke
+            | UnauthorizedUpdate
+            | InvalidUpdateDelegateRole => ErrorCategory::Permission,
+
+            AlreadyInitialized
+            | Uninitialized
+            | MaxEditionsMintedAlready
+            | EditionAlreadyMinted
+            | Disabled
+            | PrimarySaleCanOnlyBeFlippedToTrue
+            | PrintingWouldBreachMaximumSupply
+            | DataIsImmutable
+            | IsMutableCanOnlyBeFlippedToFalse
+            | MustBeBurned
+            | CannotChangeUseMethodAfterFirstUse
+            | CannotChangeUsesAfterFirstUse
+            | UseAuthorityRecordAlreadyExists
+            | UseAuthorityRecordAlreadyRevoked
+            | Unusable
+            | NotEnoughUses
+            | CollectionAuthorityRecordAlreadyExists
+            | CollectionAuthorityDoesNotExist
+            | CannotAdjustVerifiedCreator
+            | CannotRemoveVerifiedCreator
+            | CannotWipeVerifiedCreators
+            | UnsizedCollection
+            | SizedCollection
+            | MasterEditionHasPrints
+            | CannotUpdateVerifiedCollection
+            | AlreadyVerified
+            | AlreadyUnverified
+            | MustUnverify
+            | EscrowParentHasDelegate
+            | DelegateAlreadyExists
+            | DelegateNotFound
+            | LockedToken
+            | UnlockedToken
+            | IncorrectTokenState
+            | MintSupplyMustBeZero
+            | FlagNotSet
+            | FeatureNotEnabled
+            | PrintWindowClosed
+            | MintSupplyExceeded
+            | DelegateExpired
+            | CollectionItemsLocked
+            | AssetNotBurned
+            | HolderLocked
+            | HolderLockCooldown
+            | RuleSetProposalNotCommittable
+            | CollectionPaused
+            | CollectionPauseCooldown
+            | NotEnoughDelegateUses
+            | PrimarySaleAlreadyHappened
+            | CollectionMembershipNotExpired
+            | CollectionFreezeNotOptedIn
+            | CollectionFrozen
+            | LegacyLayoutAsset
+            | TokenRecordInUse
+            | MigrationGracePeriodNotElapsed
+            | MigrationGracePeriodElapsed
+            | StolenAsset
+            | UsesCooldown
+            | RateLimitedUses
+            | RentalActive
+            | TransferSessionExpired
+            | MintAuthoritySignatureExpired
+            | NonTransferableAsset => ErrorCategory::State,
+
+            _ => ErrorCategory::Validation,
+        }
+    }
 }
 
 impl PrintProgramError for MetadataError {
@@ -716,3 +1152,31 @@ impl<T> DecodeError<T> for MetadataError {
         "Metadata Error"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_are_stable() {
+        assert_eq!(MetadataError::InvalidAuthorityType as u32, 158);
+        assert_eq!(
+            MetadataError::InvalidAssociatedTokenAccountProgram as u32,
+            174
+        );
+        assert_eq!(MetadataError::UnauthorizedBurn as u32, 235);
+    }
+
+    #[test]
+    fn error_category() {
+        assert_eq!(
+            MetadataError::InvalidMetadataKey.category(),
+            ErrorCategory::Validation
+        );
+        assert_eq!(
+            MetadataError::UnauthorizedBurn.category(),
+            ErrorCategory::Permission
+        );
+        assert_eq!(MetadataError::LockedToken.category(), ErrorCategory::State);
+    }
+}
@@ -0,0 +1,113 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use super::{InstructionBuilder, MetadataInstruction};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ArchiveArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum RestoreArgs {
+    V1 {
+        /// Serialized metadata at the time it was archived.
+        data: Vec<u8>,
+    },
+}
+
+/// Archives a metadata account, keeping only a hash of its data on a `Tombstone` account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Tombstone account
+///   1. `[writable]` Metadata account
+///   2. `[]` Mint account
+///   3. `[]` Token account
+///   4. `[signer, writable]` Token owner
+///   5. `[signer]` Update authority
+///   6. `[signer, writable]` Payer
+///   7. `[]` System Program
+impl InstructionBuilder for super::builders::Archive {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.tombstone, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new(self.token_owner, true),
+            AccountMeta::new_readonly(self.update_authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Archive(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Restores an archived metadata account from its original data.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Tombstone account
+///   1. `[writable]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer, writable]` Payer
+///   4. `[]` System Program
+impl InstructionBuilder for super::builders::Restore {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.tombstone, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Restore(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{Archive, ArchiveBuilder, Restore, RestoreBuilder},
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = ArchiveBuilder::new()
+            .with_test_accounts()
+            .build(ArchiveArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Archive::ACCOUNTS);
+
+        let instruction = RestoreBuilder::new()
+            .with_test_accounts()
+            .build(RestoreArgs::V1 { data: vec![] })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Restore::ACCOUNTS);
+    }
+}
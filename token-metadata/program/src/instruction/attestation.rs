@@ -0,0 +1,116 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use super::{InstructionBuilder, MetadataInstruction};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ApproveAttestationArgs {
+    V1 {
+        /// Hash of the topic of the attestation (e.g., the name and version of a license).
+        topic_hash: [u8; 32],
+        /// Hash of the attested message.
+        message_hash: [u8; 32],
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum RevokeAttestationArgs {
+    V1,
+}
+
+/// Records an attestation signed by the holder of an asset. The `Attestation` account is
+/// created if it does not exist, otherwise its message hash is replaced.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attestation account
+///   1. `[signer]` Holder of the asset
+///   2. `[]` Token account of the holder
+///   3. `[]` Mint account
+///   4. `[]` Metadata account
+///   5. `[signer, writable]` Payer
+///   6. `[]` System Program
+impl InstructionBuilder for super::builders::ApproveAttestation {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.attestation, false),
+            AccountMeta::new_readonly(self.holder, true),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::ApproveAttestation(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Revokes an attestation, closing the `Attestation` account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attestation account
+///   1. `[signer]` Holder that signed the attestation
+///   2. `[writable]` Destination account for the rent
+impl InstructionBuilder for super::builders::RevokeAttestation {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.attestation, false),
+            AccountMeta::new_readonly(self.holder, true),
+            AccountMeta::new(self.destination, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::RevokeAttestation(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            ApproveAttestation, ApproveAttestationBuilder, RevokeAttestation,
+            RevokeAttestationBuilder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = ApproveAttestationBuilder::new()
+            .with_test_accounts()
+            .build(ApproveAttestationArgs::V1 {
+                topic_hash: [1; 32],
+                message_hash: [2; 32],
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, ApproveAttestation::ACCOUNTS);
+
+        let instruction = RevokeAttestationBuilder::new()
+            .with_test_accounts()
+            .build(RevokeAttestationArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, RevokeAttestation::ACCOUNTS);
+    }
+}
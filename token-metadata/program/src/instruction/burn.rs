@@ -6,6 +6,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+use super::InstructionBuilder;
 use crate::{instruction::MetadataInstruction, processor::AuthorizationData};
 
 ///# Burn Edition NFT
@@ -106,4 +107,329 @@ pub enum BurnArgs {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
     },
+    V2 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Policy for burning a master edition that still has prints.
+        orphan_prints: OrphanPrintsPolicy,
+    },
+}
+
+/// Policy for burning a master edition that still has prints.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum OrphanPrintsPolicy {
+    /// The master edition cannot be burned while it has prints.
+    Deny,
+    /// The master edition is burned and replaced by an `EditionTombstone`, which keeps
+    /// track of the prints until they are burned.
+    AllowAndMark,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CloseAccountsArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CloseTokenRecordArgs {
+    V1,
+}
+
+/// Burns an asset, closing associated accounts.
+///
+/// Burning a print edition decrements the supply of the master edition and clears
+/// the edition from the edition marker. Burning the last print of a burned master edition
+/// closes its tombstone, which requires the tombstone rent destination.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer, writable]` Asset owner or permanent delegate
+///   2. `[writable]` Mint account
+///   3. `[writable]` Token account
+///   4. `[writable]` Edition account (master edition or print edition)
+///   5. `[]` SPL Token program
+///   6. `[optional, writable]` Collection metadata account
+///   7. `[optional]` Token Authorization Rules account
+///   8. `[optional]` Token Authorization Rules Program
+///   9. `[optional]` Mint of the master edition (print editions)
+///   10. `[optional, writable]` Master edition account (print editions)
+///   11. `[optional, writable]` Edition marker account (print editions)
+///   12. `[optional, writable]` Token record account
+///   13. `[optional, writable]` Rent destination account (token owner)
+///   14. `[optional, writable]` Rent destination of the edition tombstone (print editions)
+///   15. `[optional]` Collection pause account (verified collection items)
+impl InstructionBuilder for super::builders::Burn {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new(self.authority, true),
+            AccountMeta::new(self.mint, false),
+            AccountMeta::new(self.token_account, false),
+            AccountMeta::new(self.edition, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            if let Some(collection_metadata) = self.collection_metadata {
+                AccountMeta::new(collection_metadata, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.master_edition_mint.unwrap_or(crate::ID), false),
+            if let Some(master_edition) = self.master_edition {
+                AccountMeta::new(master_edition, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(edition_marker) = self.edition_marker {
+                AccountMeta::new(edition_marker, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(token_record) = self.token_record {
+                AccountMeta::new(token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(rent_destination) = self.rent_destination {
+                AccountMeta::new(rent_destination, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(tombstone_rent_destination) = self.tombstone_rent_destination {
+                AccountMeta::new(tombstone_rent_destination, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.collection_pause.unwrap_or(crate::ID), false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Burn(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Closes the metadata accounts of an asset whose tokens were burned.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[optional, writable]` Edition account (master edition or print edition)
+///   2. `[]` Mint account
+///   3. `[optional]` Token account of the token record
+///   4. `[optional, writable]` Token record account
+///   5. `[signer]` Update authority
+///   6. `[writable]` Destination account
+impl InstructionBuilder for super::builders::CloseAccounts {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            if let Some(edition) = self.edition {
+                AccountMeta::new(edition, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token.unwrap_or(crate::ID), false),
+            if let Some(token_record) = self.token_record {
+                AccountMeta::new(token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.destination, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CloseAccounts(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Closes a stale token record, refunding the rent to its payer.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Token record account
+///   1. `[]` Mint account
+///   2. `[]` Token account of the token record
+///   3. `[writable]` Payer of the token record or destination account
+///   4. `[optional]` Metadata account
+///   5. `[optional, signer]` Update authority
+impl InstructionBuilder for super::builders::CloseTokenRecord {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.token_record, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.metadata.unwrap_or(crate::ID), false),
+            if let Some(authority) = self.authority {
+                AccountMeta::new_readonly(authority, true)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CloseTokenRecord(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            Burn, BurnBuilder, CloseAccounts, CloseAccountsBuilder, CloseTokenRecord,
+            CloseTokenRecordBuilder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = BurnBuilder::new()
+            .with_test_accounts()
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Burn::ACCOUNTS);
+
+        let instruction = CloseAccountsBuilder::new()
+            .with_test_accounts()
+            .build(CloseAccountsArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CloseAccounts::ACCOUNTS);
+
+        let instruction = CloseTokenRecordBuilder::new()
+            .with_test_accounts()
+            .build(CloseTokenRecordArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CloseTokenRecord::ACCOUNTS);
+    }
+
+    /// Returns the accounts of a `CloseTokenRecord` instruction, where `authority` is the
+    /// key of its (optional) authority account.
+    fn close_token_record_accounts<'a>(
+        keys: &'a mut [Pubkey; 6],
+        lamports: &'a mut [u64; 6],
+        data: &'a mut [Vec<u8>; 6],
+        authority: Pubkey,
+        signer: bool,
+        writable: bool,
+    ) -> Vec<solana_program::account_info::AccountInfo<'a>> {
+        keys[5] = authority;
+
+        keys.iter()
+            .zip(lamports.iter_mut().zip(data.iter_mut()))
+            .enumerate()
+            .map(|(index, (key, (lamports, data)))| {
+                solana_program::account_info::AccountInfo::new(
+                    key,
+                    index == 5 && signer,
+                    // token record and destination
+                    (index == 0 || index == 3) && writable,
+                    lamports,
+                    data,
+                    &crate::ID,
+                    false,
+                    0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn context_skips_absent_optional_signer() {
+        let mut keys = [(); 6].map(|_| Pubkey::new_unique());
+        let mut lamports = [0; 6];
+        let mut data: [Vec<u8>; 6] = Default::default();
+
+        // the authority is not present (set to the program id) and does not sign
+        let accounts = close_token_record_accounts(
+            &mut keys,
+            &mut lamports,
+            &mut data,
+            crate::ID,
+            false,
+            true,
+        );
+
+        let context = crate::instruction::CloseTokenRecord::to_context(&accounts).unwrap();
+        assert!(context.accounts.authority_info.is_none());
+    }
+
+    #[test]
+    fn fail_context_with_unsigned_optional_signer() {
+        let mut keys = [(); 6].map(|_| Pubkey::new_unique());
+        let mut lamports = [0; 6];
+        let mut data: [Vec<u8>; 6] = Default::default();
+
+        // the authority is present, so it must sign
+        let accounts = close_token_record_accounts(
+            &mut keys,
+            &mut lamports,
+            &mut data,
+            Pubkey::new_unique(),
+            false,
+            true,
+        );
+
+        let error = crate::instruction::CloseTokenRecord::to_context(&accounts)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            solana_program::program_error::ProgramError::MissingRequiredSignature
+        );
+    }
+
+    #[test]
+    fn fail_context_with_readonly_writable_account() {
+        let mut keys = [(); 6].map(|_| Pubkey::new_unique());
+        let mut lamports = [0; 6];
+        let mut data: [Vec<u8>; 6] = Default::default();
+
+        let accounts = close_token_record_accounts(
+            &mut keys,
+            &mut lamports,
+            &mut data,
+            Pubkey::new_unique(),
+            true,
+            false,
+        );
+
+        let error = crate::instruction::CloseTokenRecord::to_context(&accounts)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            crate::error::MetadataError::ExpectedWritableAccount.into()
+        );
+    }
 }
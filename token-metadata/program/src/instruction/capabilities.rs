@@ -0,0 +1,215 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+use super::MetadataInstruction;
+use crate::return_data::{ReturnData, ReturnDataKind};
+
+/// Instruction and argument versions that a deployment of the program can support.
+///
+/// The discriminant of each capability is the index of its bit on the capabilities
+/// bitmask, so new capabilities must always be appended. Delegate capabilities cover
+/// the `Delegate`, `Revoke`, `DelegateAndLock` and `UnlockAndRevoke` instructions of
+/// the corresponding role.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgramCapability {
+    CreateV1 = 0,
+    MintV1 = 1,
+    MintBatchV1 = 2,
+    TransferV1 = 3,
+    UpdateV1 = 4,
+    BatchUpdateV1 = 5,
+    BurnV1 = 6,
+    VerifyV1 = 7,
+    LockV1 = 8,
+    UnlockV1 = 9,
+    MigrateV1 = 10,
+    FlagV1 = 11,
+    SetPrintWindowV1 = 12,
+    ArchiveV1 = 13,
+    DelegateCollectionV1 = 14,
+    DelegateUpdateV1 = 15,
+    DelegateProgrammableConfigV1 = 16,
+    DelegateSaleV1 = 17,
+    DelegateTransferV1 = 18,
+    DelegateLockedTransferV1 = 19,
+    DelegateUtilityV1 = 20,
+    DelegateStakingV1 = 21,
+    DelegateStandardV1 = 22,
+    DelegateEscrowV1 = 23,
+    DelegateDataV1 = 24,
+    DelegateUsesV1 = 25,
+    RevokeAllV1 = 26,
+    SetDefaultRuleSetV1 = 27,
+    DelegateCollectionItemsV1 = 28,
+    PrintV1 = 29,
+    CreateV2 = 30,
+    CloseAccountsV1 = 31,
+    ResizeV1 = 32,
+    UseV1 = 33,
+    SelfLockV1 = 34,
+    SelfUnlockV1 = 35,
+    UnverifyV1 = 36,
+    MigrateCollectionV1 = 37,
+    EscrowTransferOutV1 = 38,
+    EscrowDepositV1 = 39,
+    ProposeRuleSetV1 = 40,
+    CommitRuleSetV1 = 41,
+    PauseCollectionV1 = 42,
+    UnpauseCollectionV1 = 43,
+    CreateAuthorityConfigV1 = 44,
+    CollectFeesV1 = 45,
+    DelegateUseV1 = 46,
+    SetCreatorsOverflowV1 = 47,
+    PayRoyaltiesV1 = 48,
+    SettlePrimarySaleV1 = 49,
+    CollectionMembershipV1 = 50,
+    CollectionFreezeV1 = 51,
+    AttestationV1 = 52,
+    MintV2 = 53,
+    CreateExistingMintMetadataV1 = 54,
+    LegacyLayoutV1 = 55,
+    CloseTokenRecordV1 = 56,
+    MigrationOptOutV1 = 57,
+    BatchSetTokenStandardV1 = 58,
+    VerifyContentV1 = 59,
+    TransferDenylistV1 = 60,
+    UsesV2 = 61,
+    BurnOrphanPrintsV1 = 62,
+    DelegateRentalV1 = 63,
+}
+
+/// Capabilities supported by this version of the program.
+pub const SUPPORTED_CAPABILITIES: &[ProgramCapability] = &[
+    ProgramCapability::CreateV1,
+    ProgramCapability::MintV1,
+    ProgramCapability::MintBatchV1,
+    ProgramCapability::TransferV1,
+    ProgramCapability::UpdateV1,
+    ProgramCapability::BatchUpdateV1,
+    ProgramCapability::BurnV1,
+    ProgramCapability::VerifyV1,
+    ProgramCapability::LockV1,
+    ProgramCapability::UnlockV1,
+    ProgramCapability::MigrateV1,
+    ProgramCapability::FlagV1,
+    ProgramCapability::SetPrintWindowV1,
+    ProgramCapability::ArchiveV1,
+    ProgramCapability::DelegateCollectionV1,
+    ProgramCapability::DelegateUpdateV1,
+    ProgramCapability::DelegateProgrammableConfigV1,
+    ProgramCapability::DelegateSaleV1,
+    ProgramCapability::DelegateTransferV1,
+    ProgramCapability::DelegateLockedTransferV1,
+    ProgramCapability::DelegateUtilityV1,
+    ProgramCapability::DelegateStakingV1,
+    ProgramCapability::DelegateStandardV1,
+    ProgramCapability::DelegateEscrowV1,
+    ProgramCapability::DelegateDataV1,
+    ProgramCapability::DelegateUsesV1,
+    ProgramCapability::RevokeAllV1,
+    ProgramCapability::SetDefaultRuleSetV1,
+    ProgramCapability::DelegateCollectionItemsV1,
+    ProgramCapability::PrintV1,
+    ProgramCapability::CreateV2,
+    ProgramCapability::CloseAccountsV1,
+    ProgramCapability::ResizeV1,
+    ProgramCapability::UseV1,
+    ProgramCapability::SelfLockV1,
+    ProgramCapability::SelfUnlockV1,
+    ProgramCapability::UnverifyV1,
+    ProgramCapability::MigrateCollectionV1,
+    ProgramCapability::EscrowTransferOutV1,
+    ProgramCapability::EscrowDepositV1,
+    ProgramCapability::ProposeRuleSetV1,
+    ProgramCapability::CommitRuleSetV1,
+    ProgramCapability::PauseCollectionV1,
+    ProgramCapability::UnpauseCollectionV1,
+    ProgramCapability::CreateAuthorityConfigV1,
+    ProgramCapability::CollectFeesV1,
+    ProgramCapability::DelegateUseV1,
+    ProgramCapability::SetCreatorsOverflowV1,
+    ProgramCapability::PayRoyaltiesV1,
+    ProgramCapability::SettlePrimarySaleV1,
+    ProgramCapability::CollectionMembershipV1,
+    ProgramCapability::CollectionFreezeV1,
+    ProgramCapability::AttestationV1,
+    ProgramCapability::MintV2,
+    ProgramCapability::CreateExistingMintMetadataV1,
+    ProgramCapability::LegacyLayoutV1,
+    ProgramCapability::CloseTokenRecordV1,
+    ProgramCapability::MigrationOptOutV1,
+    ProgramCapability::BatchSetTokenStandardV1,
+    ProgramCapability::VerifyContentV1,
+    ProgramCapability::TransferDenylistV1,
+    ProgramCapability::UsesV2,
+    ProgramCapability::BurnOrphanPrintsV1,
+    ProgramCapability::DelegateRentalV1,
+];
+
+/// Bitmask of capabilities, returned by the `GetProgramCapabilities` instruction.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, Default, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ProgramCapabilities {
+    pub bits: u64,
+}
+
+impl ProgramCapabilities {
+    /// Returns the capabilities supported by this version of the program.
+    pub fn supported() -> Self {
+        Self::from_capabilities(SUPPORTED_CAPABILITIES)
+    }
+
+    pub fn from_capabilities(capabilities: &[ProgramCapability]) -> Self {
+        let bits = capabilities
+            .iter()
+            .fold(0u64, |bits, capability| bits | (1 << *capability as u8));
+
+        Self { bits }
+    }
+
+    pub fn contains(&self, capability: ProgramCapability) -> bool {
+        self.bits & (1 << capability as u8) != 0
+    }
+}
+
+impl ReturnData for ProgramCapabilities {
+    const KIND: ReturnDataKind = ReturnDataKind::ProgramCapabilities;
+}
+
+/// Builds the instruction to read the capabilities of the program.
+///
+/// The instruction does not require any account and sets the `ProgramCapabilities`
+/// of the program as its return data (see `return_data`), so it is meant to be
+/// simulated.
+pub fn get_program_capabilities(program_id: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![],
+        data: MetadataInstruction::GetProgramCapabilities
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_capabilities() {
+        let capabilities = ProgramCapabilities::supported();
+
+        for capability in SUPPORTED_CAPABILITIES {
+            assert!(capabilities.contains(*capability));
+        }
+        assert_eq!(
+            capabilities.bits.count_ones() as usize,
+            SUPPORTED_CAPABILITIES.len()
+        );
+        assert!(!ProgramCapabilities::default().contains(ProgramCapability::CreateV1));
+    }
+}
@@ -2,11 +2,28 @@ use borsh::{BorshDeserialize, BorshSerialize};
 #[cfg(feature = "serde-feature")]
 use serde::{Deserialize, Serialize};
 use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
     pubkey::Pubkey,
 };
 
-use crate::{instruction::MetadataInstruction, processor::AuthorizationData};
+use super::{
+    builders::{CreateBuilder, DelegateBuilder, MintBuilder, UpdateBuilder},
+    CreateArgs, DelegateArgs, InstructionBuilder, MetadataDelegateRole, MetadataInstruction,
+    MintArgs, UpdateArgs,
+};
+use crate::{
+    error::MetadataError,
+    pda::{
+        find_delegate_registry_account, find_master_edition_account, find_metadata_account,
+        find_metadata_delegate_record_account, find_token_record_account,
+    },
+    processor::AuthorizationData,
+    programs,
+    state::{AssetData, CollectionDetails, PrintSupply, TokenStandard},
+};
 
 ///# Approve Collection Authority
 ///
@@ -41,7 +58,7 @@ pub fn approve_collection_authority(
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(metadata, false),
             AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
         ],
         data: MetadataInstruction::ApproveCollectionAuthority
             .try_to_vec()
@@ -416,3 +433,604 @@ pub enum VerifyArgs {
         authorization_data: Option<AuthorizationData>,
     },
 }
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum VerificationArgs {
+    /// Verifies the authority as a creator of the asset.
+    CreatorV1,
+    /// Verifies the asset as a member of its collection.
+    CollectionV1,
+    /// Verifies the asset as a member of its collection until the membership expires.
+    ///
+    /// Without an expiry, the existing membership of the asset (if any) is closed.
+    CollectionV2 { membership_expires_at: Option<i64> },
+}
+
+/// Verifies a creator or the collection of an asset.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Creator to verify, collection update authority, delegate or authority config signer
+///   1. `[optional]` Delegate record PDA
+///   2. `[writable]` Metadata account
+///   3. `[optional]` Mint of the Collection
+///   4. `[optional, writable]` Metadata Account of the Collection
+///   5. `[optional]` Master Edition Account of the Collection Token
+///   6. `[]` System program
+///   7. `[]` Instructions sysvar account
+///   8. `[optional, writable]` Authority config account
+///   9. `[optional, writable]` Creators overflow account
+///   10. `[optional, writable]` Collection membership account
+///   11. `[optional, signer, writable]` Payer of the collection membership account
+impl InstructionBuilder for super::builders::VerifyV2 {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.collection_mint.unwrap_or(crate::ID), false),
+            if let Some(collection_metadata) = self.collection_metadata {
+                AccountMeta::new(collection_metadata, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.collection_master_edition.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            if let Some(authority_config) = self.authority_config {
+                AccountMeta::new(authority_config, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(creators_overflow) = self.creators_overflow {
+                AccountMeta::new(creators_overflow, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(collection_membership) = self.collection_membership {
+                AccountMeta::new(collection_membership, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(payer) = self.payer {
+                AccountMeta::new(payer, true)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::VerifyV2(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Unverifies a creator or the collection of an asset.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Creator to unverify, collection update authority or delegate
+///   1. `[optional]` Delegate record PDA
+///   2. `[writable]` Metadata account
+///   3. `[optional]` Mint of the Collection
+///   4. `[optional, writable]` Metadata Account of the Collection
+///   5. `[]` System program
+///   6. `[]` Instructions sysvar account
+///   7. `[optional, writable]` Creators overflow account
+impl InstructionBuilder for super::builders::Unverify {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.collection_mint.unwrap_or(crate::ID), false),
+            if let Some(collection_metadata) = self.collection_metadata {
+                AccountMeta::new(collection_metadata, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            if let Some(creators_overflow) = self.creators_overflow {
+                AccountMeta::new(creators_overflow, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Unverify(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum PauseCollectionArgs {
+    V1 {
+        /// Duration (in seconds) of the pause.
+        duration: i64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UnpauseCollectionArgs {
+    V1,
+}
+
+/// Pauses the verified items of a collection. The `CollectionPause` account is created
+/// if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Collection pause account
+///   1. `[]` Mint of the Collection
+///   2. `[]` Metadata Account of the Collection
+///   3. `[signer]` Update authority of the Collection
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::PauseCollection {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.collection_pause, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::PauseCollection(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Lifts the pause of a collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Collection pause account
+///   1. `[]` Mint of the Collection
+///   2. `[]` Metadata Account of the Collection
+///   3. `[signer]` Update authority of the Collection
+impl InstructionBuilder for super::builders::UnpauseCollection {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.collection_pause, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::UnpauseCollection(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetLegacyLayoutArgs {
+    V1 {
+        /// Indicates whether the items of the collection use the legacy layout.
+        enabled: bool,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SyncLayoutArgs {
+    V1,
+}
+
+/// Sets whether the verified items of a collection use the legacy layout. The
+/// `LegacyLayout` account is created if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Legacy layout account
+///   1. `[]` Mint of the Collection
+///   2. `[]` Metadata Account of the Collection
+///   3. `[signer]` Update authority of the Collection
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SetLegacyLayout {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.legacy_layout, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetLegacyLayout(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Moves the programmable config of an asset between its metadata and its
+/// `ProgrammableConfigExtension` account, following the layout of its collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[writable]` Programmable config extension account
+///   2. `[]` Legacy layout account of the collection
+///   3. `[signer, writable]` Payer
+///   4. `[]` System Program
+impl InstructionBuilder for super::builders::SyncLayout {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new(self.programmable_config_extension, false),
+            AccountMeta::new_readonly(self.legacy_layout, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SyncLayout(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ExpireCollectionMembershipArgs {
+    V1,
+}
+
+/// Closes an expired collection membership, unverifying the collection of the asset.
+///
+/// # Accounts:
+///
+///   0. `[signer, writable]` Payer
+///   1. `[writable]` Metadata account
+///   2. `[writable]` Collection membership account
+///   3. `[writable]` Metadata account of the collection
+impl InstructionBuilder for super::builders::ExpireCollectionMembership {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new(self.collection_membership, false),
+            AccountMeta::new(self.collection_metadata, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::ExpireCollectionMembership(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Parameters of a collection parent created by `create_collection_instructions`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateCollectionParams {
+    /// Mint of the collection parent. The mint is initialized by the `Create` instruction,
+    /// so it must sign the transaction.
+    pub mint: Pubkey,
+    /// Update authority, mint authority and holder of the collection parent.
+    pub authority: Pubkey,
+    /// Payer of the accounts.
+    pub payer: Pubkey,
+    /// Data of the collection parent, which must be a `NonFungible` or
+    /// `ProgrammableNonFungible` asset. Collection parents without collection details are
+    /// created as sized collections.
+    pub asset_data: AssetData,
+    /// Collection delegate approved to verify the items of the collection, e.g. the
+    /// launchpad minting them.
+    pub collection_delegate: Option<Pubkey>,
+    /// Opts a programmable collection parent in to the default rule set of its token
+    /// standard (see `DefaultRuleSets`), used when it does not have a rule set.
+    pub use_default_rule_set: bool,
+}
+
+/// Addresses of the accounts of a collection parent created by
+/// `create_collection_instructions`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollectionAddresses {
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub master_edition: Pubkey,
+    /// Associated token account of the authority holding the collection parent.
+    pub token: Pubkey,
+    /// Token record of the token account (programmable collection parents only).
+    pub token_record: Option<Pubkey>,
+    /// Delegate record of the collection delegate (if any).
+    pub delegate_record: Option<Pubkey>,
+    /// Delegate registry of the mint (only when there is a collection delegate).
+    pub delegate_registry: Option<Pubkey>,
+}
+
+/// Number of instructions of `create_collection_instructions` that create and mint the
+/// collection parent; the remaining instructions configure it.
+pub const CREATE_COLLECTION_SETUP_LEN: usize = 2;
+
+/// Returns the instructions to launch a collection parent, in the order in which they must
+/// be executed, together with the addresses of its accounts:
+///
+///   1. `Create` the collection parent (sized, unless other collection details are set).
+///   2. `Mint` the collection parent to the associated token account of the authority.
+///   3. `Update` the collection parent to use the default rule set (if requested).
+///   4. `Delegate` the `Collection` role to the collection delegate (if any).
+///
+/// Clients can use `client::create_collection` to group the instructions in transactions,
+/// while programs can invoke them with `invoke_create_collection`.
+pub fn create_collection_instructions(
+    params: CreateCollectionParams,
+) -> Result<(CollectionAddresses, Vec<Instruction>), MetadataError> {
+    let CreateCollectionParams {
+        mint,
+        authority,
+        payer,
+        mut asset_data,
+        collection_delegate,
+        use_default_rule_set,
+    } = params;
+
+    let programmable = match asset_data.token_standard {
+        TokenStandard::NonFungible => false,
+        TokenStandard::ProgrammableNonFungible => true,
+        _ => return Err(MetadataError::InvalidTokenStandard),
+    };
+
+    // the default rule set only applies to programmable assets
+    if use_default_rule_set && !programmable {
+        return Err(MetadataError::InvalidTokenStandard);
+    }
+
+    if asset_data.collection_details.is_none() {
+        asset_data.collection_details = Some(CollectionDetails::V1 { size: 0 });
+    }
+
+    let rule_set = asset_data.rule_set;
+
+    let metadata = find_metadata_account(&mint).0;
+    let master_edition = find_master_edition_account(&mint).0;
+    let token = Pubkey::find_program_address(
+        &[
+            authority.as_ref(),
+            programs::SPL_TOKEN_PROGRAM.as_ref(),
+            mint.as_ref(),
+        ],
+        &programs::SPL_ATA_PROGRAM,
+    )
+    .0;
+    let token_record = programmable.then(|| find_token_record_account(&mint, &token).0);
+
+    let mut instructions = Vec::with_capacity(4);
+
+    let create = CreateBuilder::new()
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .mint(mint)
+        .authority(authority)
+        .payer(payer)
+        .update_authority(authority)
+        .initialize_mint(true)
+        .update_authority_as_signer(true)
+        .build(CreateArgs::V1 {
+            asset_data,
+            decimals: Some(0),
+            print_supply: Some(PrintSupply::Zero),
+        })
+        .map_err(|_| MetadataError::MissingAccountInBuilder)?;
+    instructions.push(create.instruction());
+
+    let mut builder = MintBuilder::new();
+    builder
+        .token(token)
+        .token_owner(authority)
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .mint(mint)
+        .authority(authority)
+        .payer(payer);
+
+    if let Some(token_record) = token_record {
+        builder.token_record(token_record);
+    }
+
+    if let Some(rule_set) = rule_set {
+        builder
+            .authorization_rules(rule_set)
+            .authorization_rules_program(programs::AUTH_RULES_PROGRAM);
+    }
+
+    let mint_to = builder
+        .build(MintArgs::V1 {
+            amount: 1,
+            authorization_data: None,
+        })
+        .map_err(|_| MetadataError::MissingAccountInBuilder)?;
+    instructions.push(mint_to.instruction());
+
+    if use_default_rule_set {
+        let mut args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            use_default_rule_set,
+            ..
+        } = &mut args;
+        *use_default_rule_set = Some(true);
+
+        let update = UpdateBuilder::new()
+            .authority(authority)
+            .token(token)
+            .mint(mint)
+            .metadata(metadata)
+            .edition(master_edition)
+            .payer(payer)
+            .build(args)
+            .map_err(|_| MetadataError::MissingAccountInBuilder)?;
+        instructions.push(update.instruction());
+    }
+
+    let (delegate_record, delegate_registry) = match collection_delegate {
+        Some(delegate) => {
+            let delegate_record = find_metadata_delegate_record_account(
+                &mint,
+                MetadataDelegateRole::Collection,
+                &authority,
+                &delegate,
+            )
+            .0;
+            let delegate_registry = find_delegate_registry_account(&mint).0;
+
+            let approve = DelegateBuilder::new()
+                .delegate_record(delegate_record)
+                .delegate(delegate)
+                .metadata(metadata)
+                .master_edition(master_edition)
+                .mint(mint)
+                .authority(authority)
+                .payer(payer)
+                .spl_token_program(programs::SPL_TOKEN_PROGRAM)
+                .delegate_registry(delegate_registry)
+                .build(DelegateArgs::CollectionV1 {
+                    authorization_data: None,
+                    expiry: None,
+                })
+                .map_err(|_| MetadataError::MissingAccountInBuilder)?;
+            instructions.push(approve.instruction());
+
+            (Some(delegate_record), Some(delegate_registry))
+        }
+        None => (None, None),
+    };
+
+    Ok((
+        CollectionAddresses {
+            mint,
+            metadata,
+            master_edition,
+            token,
+            token_record,
+            delegate_record,
+            delegate_registry,
+        },
+        instructions,
+    ))
+}
+
+/// Invokes the instructions returned by `create_collection_instructions` from a program.
+///
+/// The `account_infos` must include the accounts of every instruction, including programs
+/// and sysvars. The `signers_seeds` apply to all instructions, so PDAs of the calling program
+/// can be used as the mint, authority or payer.
+pub fn invoke_create_collection(
+    instructions: &[Instruction],
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    for instruction in instructions {
+        invoke_signed(instruction, account_infos, signers_seeds)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            ExpireCollectionMembership, ExpireCollectionMembershipBuilder, PauseCollection,
+            PauseCollectionBuilder, SetLegacyLayout, SetLegacyLayoutBuilder, SyncLayout,
+            SyncLayoutBuilder, UnpauseCollection, UnpauseCollectionBuilder, Unverify,
+            UnverifyBuilder, VerifyV2, VerifyV2Builder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = PauseCollectionBuilder::new()
+            .with_test_accounts()
+            .build(PauseCollectionArgs::V1 { duration: 3_600 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, PauseCollection::ACCOUNTS);
+
+        let instruction = UnpauseCollectionBuilder::new()
+            .with_test_accounts()
+            .build(UnpauseCollectionArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, UnpauseCollection::ACCOUNTS);
+
+        let instruction = SetLegacyLayoutBuilder::new()
+            .with_test_accounts()
+            .build(SetLegacyLayoutArgs::V1 { enabled: true })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetLegacyLayout::ACCOUNTS);
+
+        let instruction = SyncLayoutBuilder::new()
+            .with_test_accounts()
+            .build(SyncLayoutArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SyncLayout::ACCOUNTS);
+
+        let instruction = ExpireCollectionMembershipBuilder::new()
+            .with_test_accounts()
+            .build(ExpireCollectionMembershipArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, ExpireCollectionMembership::ACCOUNTS);
+
+        let instruction = VerifyV2Builder::new()
+            .with_test_accounts()
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, VerifyV2::ACCOUNTS);
+
+        let instruction = UnverifyBuilder::new()
+            .with_test_accounts()
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Unverify::ACCOUNTS);
+    }
+}
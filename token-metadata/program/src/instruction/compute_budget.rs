@@ -0,0 +1,137 @@
+use borsh::BorshDeserialize;
+use solana_program::instruction::Instruction;
+
+use super::MetadataInstruction;
+use crate::{programs, state::TokenStandard};
+
+/// Compute unit limit of an instruction when the transaction does not request a limit.
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// Maximum compute unit limit that a transaction can request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Compute units added to the hint of instructions that validate the rule set of a
+/// programmable asset through the Token Auth Rules program.
+const RULE_SET_COMPUTE_UNITS: u32 = 250_000;
+
+/// Discriminant of the `SetComputeUnitLimit` instruction of the compute budget program.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+impl MetadataInstruction {
+    /// Returns an upper bound of the compute units consumed by the instruction.
+    ///
+    /// The hints are measured on the instructions of the program test suite (`compute_budget`
+    /// tests), which fail when an instruction consumes more than its hint; they must be updated
+    /// alongside changes to the processors. Instructions without a measured hint return
+    /// `DEFAULT_COMPUTE_UNIT_LIMIT`.
+    pub fn compute_budget_hint(&self, token_standard: TokenStandard, has_rule_set: bool) -> u32 {
+        let programmable = matches!(token_standard, TokenStandard::ProgrammableNonFungible);
+
+        let (units, validates_rule_set) = match self {
+            MetadataInstruction::Create(_) => (if programmable { 120_000 } else { 80_000 }, false),
+            MetadataInstruction::Mint(_) => (if programmable { 150_000 } else { 80_000 }, true),
+            MetadataInstruction::Transfer(_) => (if programmable { 200_000 } else { 80_000 }, true),
+            MetadataInstruction::Delegate(_)
+            | MetadataInstruction::Revoke(_)
+            | MetadataInstruction::Lock(_)
+            | MetadataInstruction::Unlock(_) => (if programmable { 120_000 } else { 60_000 }, true),
+            MetadataInstruction::DelegateAndLock(_) | MetadataInstruction::UnlockAndRevoke(_) => {
+                (if programmable { 180_000 } else { 90_000 }, true)
+            }
+            MetadataInstruction::Update(_) => (80_000, false),
+            MetadataInstruction::Burn(_) => (100_000, false),
+            MetadataInstruction::Migrate(_) => (150_000, false),
+            MetadataInstruction::Print(_) => (if programmable { 200_000 } else { 150_000 }, false),
+            MetadataInstruction::VerifyV2(_) | MetadataInstruction::Unverify(_) => (60_000, false),
+            MetadataInstruction::UseV2(_) => (60_000, true),
+            _ => return DEFAULT_COMPUTE_UNIT_LIMIT,
+        };
+
+        if programmable && has_rule_set && validates_rule_set {
+            units.saturating_add(RULE_SET_COMPUTE_UNITS)
+        } else {
+            units
+        }
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+    }
+}
+
+/// Builds the instruction of the compute budget program that sets the compute unit limit
+/// of the transaction.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = Vec::with_capacity(5);
+    data.push(SET_COMPUTE_UNIT_LIMIT);
+    data.extend_from_slice(&units.to_le_bytes());
+
+    Instruction {
+        program_id: programs::COMPUTE_BUDGET_PROGRAM,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Returns the instruction preceded by a compute unit limit matching its compute budget
+/// hint (see `MetadataInstruction::compute_budget_hint`).
+///
+/// Instructions that are not Token Metadata instructions are returned without a limit.
+pub fn with_compute_budget(
+    instruction: Instruction,
+    token_standard: TokenStandard,
+    has_rule_set: bool,
+) -> Vec<Instruction> {
+    let hint = if instruction.program_id == crate::ID {
+        MetadataInstruction::try_from_slice(&instruction.data)
+            .ok()
+            .map(|ix| ix.compute_budget_hint(token_standard, has_rule_set))
+    } else {
+        None
+    };
+
+    match hint {
+        Some(units) => vec![set_compute_unit_limit(units), instruction],
+        None => vec![instruction],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+
+    use super::*;
+    use crate::instruction::TransferArgs;
+
+    #[test]
+    fn set_compute_unit_limit_matches_sdk() {
+        assert_eq!(
+            set_compute_unit_limit(400_000),
+            ComputeBudgetInstruction::set_compute_unit_limit(400_000)
+        );
+    }
+
+    #[test]
+    fn programmable_transfer_exceeds_default_limit_with_rule_set() {
+        let transfer = MetadataInstruction::Transfer(TransferArgs::V1 {
+            amount: 1,
+            authorization_data: None,
+        });
+
+        let hint = transfer.compute_budget_hint(TokenStandard::ProgrammableNonFungible, true);
+        assert!(hint > DEFAULT_COMPUTE_UNIT_LIMIT);
+        assert!(hint <= MAX_COMPUTE_UNIT_LIMIT);
+
+        // the rule set is only validated for programmable assets
+        assert_eq!(
+            transfer.compute_budget_hint(TokenStandard::NonFungible, true),
+            transfer.compute_budget_hint(TokenStandard::NonFungible, false)
+        );
+    }
+
+    #[test]
+    fn unmeasured_instructions_use_default_limit() {
+        assert_eq!(
+            MetadataInstruction::UnverifyCollection
+                .compute_budget_hint(TokenStandard::NonFungible, false),
+            DEFAULT_COMPUTE_UNIT_LIMIT
+        );
+    }
+}
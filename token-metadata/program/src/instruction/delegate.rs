@@ -18,30 +18,44 @@ pub enum DelegateArgs {
     CollectionV1 {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     SaleV1 {
         amount: u64,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     TransferV1 {
         amount: u64,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     UpdateV1 {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     UtilityV1 {
         amount: u64,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     StakingV1 {
         amount: u64,
+        /// Staking program allowed to unlock the asset.
+        staking_program: Pubkey,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     StandardV1 {
         amount: u64,
@@ -52,10 +66,68 @@ pub enum DelegateArgs {
         locked_address: Pubkey,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
     },
     ProgrammableConfigV1 {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    EscrowV1 {
+        amount: u64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    DataV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    UsesV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    /// Approves a delegate to lock and unlock any item of the collection held by the
+    /// authority (token owner).
+    CollectionItemsV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
+    /// Approves a delegate to use the asset up to a number of uses, tracked in the
+    /// delegate record independently of other delegates. It replaces the legacy use
+    /// authority records and also supports programmable assets.
+    UseV1 {
+        /// Number of uses allowed to the delegate.
+        allowed_uses: u64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    /// Approves a delegate to freeze and thaw the items of the collection whose holders
+    /// opted in to collection freezes.
+    CollectionFreezeV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Optional unix timestamp at which the delegate expires.
+        expiry: Option<i64>,
+    },
+    /// Rents a programmable asset to the delegate until the expiry. The asset cannot be
+    /// transferred or burned while the rental is active, and the delegate can be revoked
+    /// by anyone once the rental expires.
+    RentalV1 {
+        amount: u64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Unix timestamp at which the rental expires.
+        expiry: i64,
     },
 }
 
@@ -73,6 +145,31 @@ pub enum RevokeArgs {
     LockedTransferV1,
     ProgrammableConfigV1,
     MigrationV1,
+    EscrowV1,
+    DataV1,
+    UsesV1,
+    /// Revokes the token delegate (if any) and closes the metadata delegate records
+    /// passed as remaining accounts.
+    AllV1,
+    /// Revokes a collection items delegate.
+    CollectionItemsV1,
+    UseV1,
+    CollectionFreezeV1,
+    /// Revokes an expired `Rental` delegate; any signer can be the authority.
+    RentalV1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum RotateAuthorityArgs {
+    V1 {
+        /// Address of the new update authority.
+        new_update_authority: Pubkey,
+        /// Indicates whether verified creators other than the new update authority
+        /// are unverified.
+        unverify_creators: bool,
+    },
 }
 
 #[repr(C)]
@@ -84,6 +181,14 @@ pub enum MetadataDelegateRole {
     Use,
     Update,
     ProgrammableConfig,
+    /// Can only update the `Data` (name, symbol, uri, royalties and creators) of the asset.
+    Data,
+    /// Can only update the `Uses` configuration (e.g., to replenish the remaining uses)
+    /// of the asset.
+    Uses,
+    /// Can freeze and thaw the items of the collection whose holders opted in to
+    /// collection freezes.
+    CollectionFreeze,
 }
 
 impl fmt::Display for MetadataDelegateRole {
@@ -94,6 +199,9 @@ impl fmt::Display for MetadataDelegateRole {
             Self::Use => "use_delegate".to_string(),
             Self::Update => "update_delegate".to_string(),
             Self::ProgrammableConfig => "programmable_config_delegate".to_string(),
+            Self::Data => "data_delegate".to_string(),
+            Self::Uses => "uses_delegate".to_string(),
+            Self::CollectionFreeze => "collection_freeze_delegate".to_string(),
         };
 
         write!(f, "{message}")
@@ -118,6 +226,8 @@ impl fmt::Display for MetadataDelegateRole {
 ///   11. `[optional]` SPL Token Program
 ///   12. `[optional]` Token Authorization Rules program
 ///   13. `[optional]` Token Authorization Rules account
+///   14. `[optional, writable]` Delegate registry account
+///   15. `[optional]` Collection pause account
 impl InstructionBuilder for super::builders::Delegate {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let accounts = vec![
@@ -147,10 +257,16 @@ impl InstructionBuilder for super::builders::Delegate {
             AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            if let Some(delegate_registry) = self.delegate_registry {
+                AccountMeta::new(delegate_registry, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.collection_pause.unwrap_or(crate::ID), false),
         ];
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Delegate(self.args.clone())
                 .try_to_vec()
@@ -159,6 +275,73 @@ impl InstructionBuilder for super::builders::Delegate {
     }
 }
 
+/// Creates a token delegate and locks the asset.
+///
+/// # Accounts:
+///
+///   0. `[optional, writable]` Delegate record account
+///   1. `[]` Delegated owner
+///   2. `[writable]` Metadata account
+///   3. `[optional]` Master Edition account
+///   4. `[optional, writable]` Token record account
+///   5. `[]` Mint account
+///   6. `[optional, writable]` Token account
+///   7. `[signer]` Token owner
+///   8. `[signer, writable]` Payer
+///   9. `[]` System Program
+///   10. `[]` Instructions sysvar account
+///   11. `[optional]` SPL Token Program
+///   12. `[optional]` Token Authorization Rules program
+///   13. `[optional]` Token Authorization Rules account
+///   14. `[optional, writable]` Delegate registry account
+///   15. `[optional]` Collection pause account
+impl InstructionBuilder for super::builders::DelegateAndLock {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.delegate, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.master_edition.unwrap_or(crate::ID), false),
+            if let Some(token_record) = self.token_record {
+                AccountMeta::new(token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.mint, false),
+            if let Some(token) = self.token {
+                AccountMeta::new(token, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            if let Some(delegate_registry) = self.delegate_registry {
+                AccountMeta::new(delegate_registry, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.collection_pause.unwrap_or(crate::ID), false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::DelegateAndLock(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
 /// Revokes a delegate.
 ///
 /// # Accounts:
@@ -177,6 +360,7 @@ impl InstructionBuilder for super::builders::Delegate {
 ///   11. `[optional]` SPL Token Program
 ///   12. `[optional]` Token Authorization Rules program
 ///   13. `[optional]` Token Authorization Rules account
+///   14. `[optional, writable]` Delegate registry account
 impl InstructionBuilder for super::builders::Revoke {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let accounts = vec![
@@ -206,10 +390,15 @@ impl InstructionBuilder for super::builders::Revoke {
             AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            if let Some(delegate_registry) = self.delegate_registry {
+                AccountMeta::new(delegate_registry, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
         ];
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Revoke(self.args.clone())
                 .try_to_vec()
@@ -217,3 +406,157 @@ impl InstructionBuilder for super::builders::Revoke {
         }
     }
 }
+
+/// Unlocks the asset and revokes the token delegate.
+///
+/// # Accounts:
+///
+///   0. `[optional, writable]` Delegate record account
+///   1. `[signer]` Delegated owner
+///   2. `[writable]` Metadata account
+///   3. `[optional]` Master Edition account
+///   4. `[optional, writable]` Token record account
+///   5. `[]` Mint account
+///   6. `[optional, writable]` Token account
+///   7. `[signer]` Token owner
+///   8. `[signer, writable]` Payer
+///   9. `[]` System Program
+///   10. `[]` Instructions sysvar account
+///   11. `[optional]` SPL Token Program
+///   12. `[optional]` Token Authorization Rules program
+///   13. `[optional]` Token Authorization Rules account
+///   14. `[optional, writable]` Delegate registry account
+impl InstructionBuilder for super::builders::UnlockAndRevoke {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.delegate, true),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.master_edition.unwrap_or(crate::ID), false),
+            if let Some(token_record) = self.token_record {
+                AccountMeta::new(token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.mint, false),
+            if let Some(token) = self.token {
+                AccountMeta::new(token, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            if let Some(delegate_registry) = self.delegate_registry {
+                AccountMeta::new(delegate_registry, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::UnlockAndRevoke(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Rotates the update authority of an asset, revoking every metadata delegate listed on
+/// the delegate registry of the mint.
+///
+/// The delegate records of the registry entries must be appended to the instruction as
+/// writable remaining accounts.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[]` Mint account
+///   2. `[signer]` Current update authority
+///   3. `[writable]` Delegate registry account
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::RotateAuthorityWithPolicy {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.delegate_registry, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::RotateAuthorityWithPolicy(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            Delegate, DelegateAndLock, DelegateAndLockBuilder, DelegateBuilder, Revoke,
+            RevokeBuilder, RotateAuthorityWithPolicy, RotateAuthorityWithPolicyBuilder,
+            UnlockAndRevoke, UnlockAndRevokeBuilder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = DelegateBuilder::new()
+            .with_test_accounts()
+            .build(DelegateArgs::StandardV1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Delegate::ACCOUNTS);
+
+        let instruction = DelegateAndLockBuilder::new()
+            .with_test_accounts()
+            .build(DelegateArgs::StandardV1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, DelegateAndLock::ACCOUNTS);
+
+        let instruction = RevokeBuilder::new()
+            .with_test_accounts()
+            .build(RevokeArgs::StandardV1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Revoke::ACCOUNTS);
+
+        let instruction = UnlockAndRevokeBuilder::new()
+            .with_test_accounts()
+            .build(RevokeArgs::StandardV1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, UnlockAndRevoke::ACCOUNTS);
+
+        let instruction = RotateAuthorityWithPolicyBuilder::new()
+            .with_test_accounts()
+            .build(RotateAuthorityArgs::V1 {
+                new_update_authority: Pubkey::new_unique(),
+                unverify_creators: true,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, RotateAuthorityWithPolicy::ACCOUNTS);
+    }
+}
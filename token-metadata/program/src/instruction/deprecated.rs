@@ -2,7 +2,6 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    sysvar,
 };
 #[cfg(feature = "serde-feature")]
 use {
@@ -14,7 +13,8 @@ use crate::{
     instruction::{
         CreateMasterEditionArgs, MetadataInstruction, MintNewEditionFromMasterEditionViaTokenArgs,
     },
-    state::{Collection, Creator, Data, DataV2, Reservation, Uses},
+    programs,
+    state::{BasisPoints, Collection, Creator, Data, DataV2, Reservation, Uses},
 };
 
 #[repr(C)]
@@ -59,14 +59,14 @@ pub fn create_metadata_accounts_v2(
             AccountMeta::new_readonly(mint_authority, true),
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(update_authority, update_authority_is_signer),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
         ],
         data: MetadataInstruction::CreateMetadataAccountV2(CreateMetadataAccountArgsV2 {
             data: DataV2 {
                 name,
                 symbol,
                 uri,
-                seller_fee_basis_points,
+                seller_fee_basis_points: BasisPoints::new_unchecked(seller_fee_basis_points),
                 creators,
                 collection,
                 uses,
@@ -101,9 +101,9 @@ pub fn create_master_edition(
         AccountMeta::new_readonly(mint_authority, true),
         AccountMeta::new(payer, true),
         AccountMeta::new_readonly(metadata, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSVAR_RENT, false),
     ];
 
     Instruction {
@@ -155,15 +155,15 @@ pub fn create_metadata_accounts(
             AccountMeta::new_readonly(mint_authority, true),
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(update_authority, update_authority_is_signer),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
+            AccountMeta::new_readonly(programs::SYSVAR_RENT, false),
         ],
         data: MetadataInstruction::CreateMetadataAccount(CreateMetadataAccountArgs {
             data: Data {
                 name,
                 symbol,
                 uri,
-                seller_fee_basis_points,
+                seller_fee_basis_points: BasisPoints::new_unchecked(seller_fee_basis_points),
                 creators,
             },
             is_mutable,
@@ -254,7 +254,7 @@ pub fn mint_edition_from_master_edition_via_vault_proxy(
         AccountMeta::new_readonly(metadata, false),
         AccountMeta::new_readonly(token_program, false),
         AccountMeta::new_readonly(token_vault_program_info, false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
     ];
 
     Instruction {
@@ -6,8 +6,10 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+use super::InstructionBuilder;
 use crate::{
     instruction::MetadataInstruction,
+    programs,
     state::{EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
 };
 
@@ -59,8 +61,8 @@ pub fn create_master_edition_v3(
         AccountMeta::new_readonly(mint_authority, true),
         AccountMeta::new(payer, true),
         AccountMeta::new(metadata, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
     ];
 
     Instruction {
@@ -121,8 +123,8 @@ pub fn mint_new_edition_from_master_edition_via_token(
         AccountMeta::new_readonly(token_account, false),
         AccountMeta::new_readonly(new_metadata_update_authority, false),
         AccountMeta::new_readonly(metadata, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
     ];
 
     Instruction {
@@ -135,3 +137,145 @@ pub fn mint_new_edition_from_master_edition_via_token(
         .unwrap(),
     }
 }
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetPrintWindowArgs {
+    V1 {
+        /// Unix timestamps (start and end, both inclusive) during which editions can be
+        /// printed. `None` removes the restriction.
+        print_window: Option<(i64, i64)>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum PrintArgs {
+    V1 {
+        /// Number of the edition to print.
+        edition: u64,
+    },
+}
+
+/// Sets the print window of a master edition. The master edition extension account is
+/// created if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Master edition extension account
+///   1. `[]` Master edition account
+///   2. `[]` Mint account
+///   3. `[]` Metadata account
+///   4. `[signer]` Update authority
+///   5. `[signer, writable]` Payer
+///   6. `[]` System Program
+impl InstructionBuilder for super::builders::SetPrintWindow {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.master_edition_extension, false),
+            AccountMeta::new_readonly(self.master_edition, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetPrintWindow(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Prints a new edition from a master edition.
+///
+/// # Accounts:
+///
+///   0. `[writable]` New metadata account
+///   1. `[writable]` New edition account
+///   2. `[writable]` Mint of the new edition
+///   3. `[]` Owner of the token account of the new edition
+///   4. `[writable]` Token account of the new edition
+///   5. `[signer]` Mint authority of the new edition
+///   6. `[optional, writable]` Token record account of the new edition
+///   7. `[writable]` Master edition account
+///   8. `[writable]` Edition marker account
+///   9. `[signer, writable]` Payer
+///   10. `[signer]` Owner of the token account of the master edition
+///   11. `[]` Token account of the master edition
+///   12. `[]` Metadata account of the master edition
+///   13. `[]` Update authority of the master edition
+///   14. `[]` Master edition extension account
+///   15. `[]` SPL Token program
+///   16. `[]` SPL Associated Token Account program
+///   17. `[]` Instructions sysvar account
+///   18. `[]` System Program
+impl InstructionBuilder for super::builders::Print {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.edition_metadata, false),
+            AccountMeta::new(self.edition, false),
+            AccountMeta::new(self.edition_mint, false),
+            AccountMeta::new_readonly(self.edition_token_account_owner, false),
+            AccountMeta::new(self.edition_token_account, false),
+            AccountMeta::new_readonly(self.edition_mint_authority, true),
+            if let Some(edition_token_record) = self.edition_token_record {
+                AccountMeta::new(edition_token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new(self.master_edition, false),
+            AccountMeta::new(self.edition_marker_pda, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.master_token_account_owner, true),
+            AccountMeta::new_readonly(self.master_token_account, false),
+            AccountMeta::new_readonly(self.master_metadata, false),
+            AccountMeta::new_readonly(self.update_authority, false),
+            AccountMeta::new_readonly(self.master_edition_extension, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            AccountMeta::new_readonly(self.spl_ata_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Print(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{Print, PrintBuilder, SetPrintWindow, SetPrintWindowBuilder},
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = SetPrintWindowBuilder::new()
+            .with_test_accounts()
+            .build(SetPrintWindowArgs::V1 { print_window: None })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetPrintWindow::ACCOUNTS);
+
+        let instruction = PrintBuilder::new()
+            .with_test_accounts()
+            .build(PrintArgs::V1 { edition: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Print::ACCOUNTS);
+    }
+}
@@ -4,10 +4,10 @@ use serde::{Deserialize, Serialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    system_program, sysvar,
 };
 
-use crate::instruction::MetadataInstruction;
+use super::{InstructionBuilder, MetadataInstruction};
+use crate::programs;
 
 pub fn close_escrow_account(
     program_id: Pubkey,
@@ -25,8 +25,8 @@ pub fn close_escrow_account(
         AccountMeta::new_readonly(token_account, false),
         AccountMeta::new_readonly(edition_account, false),
         AccountMeta::new(payer_account, true),
-        AccountMeta::new_readonly(system_program::id(), false),
-        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSVAR_INSTRUCTIONS, false),
     ];
     let data = MetadataInstruction::CloseEscrowAccount
         .try_to_vec()
@@ -56,8 +56,8 @@ pub fn create_escrow_account(
         AccountMeta::new_readonly(token_account, false),
         AccountMeta::new_readonly(edition_account, false),
         AccountMeta::new(payer_account, true),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSVAR_INSTRUCTIONS, false),
     ];
 
     if let Some(authority) = authority {
@@ -82,6 +82,27 @@ pub struct TransferOutOfEscrowArgs {
     pub amount: u64,
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum EscrowTransferOutArgs {
+    V1 {
+        /// Amount of tokens to transfer.
+        amount: u64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum EscrowDepositArgs {
+    V1 {
+        /// Amount of tokens to deposit.
+        amount: u64,
+    },
+}
+
+#[cfg(feature = "ata")]
 pub fn transfer_out_of_escrow(
     program_id: Pubkey,
     escrow: Pubkey,
@@ -104,10 +125,10 @@ pub fn transfer_out_of_escrow(
         AccountMeta::new(attribute_dst, false),
         AccountMeta::new_readonly(escrow_mint, false),
         AccountMeta::new_readonly(escrow_account, false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SPL_ATA_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSVAR_INSTRUCTIONS, false),
     ];
 
     if let Some(authority) = authority {
@@ -124,3 +145,196 @@ pub fn transfer_out_of_escrow(
         data,
     }
 }
+
+/// Transfers tokens out of the token-owned escrow of an asset as an `Escrow` delegate
+/// of the holder, validated by the token record of the asset.
+#[cfg(feature = "ata")]
+pub fn delegated_transfer_out_of_escrow(
+    program_id: Pubkey,
+    escrow: Pubkey,
+    metadata: Pubkey,
+    payer: Pubkey,
+    attribute_mint: Pubkey,
+    attribute_src: Pubkey,
+    attribute_dst: Pubkey,
+    escrow_mint: Pubkey,
+    escrow_account: Pubkey,
+    delegate: Pubkey,
+    token_record: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut instruction = transfer_out_of_escrow(
+        program_id,
+        escrow,
+        metadata,
+        payer,
+        attribute_mint,
+        attribute_src,
+        attribute_dst,
+        escrow_mint,
+        escrow_account,
+        Some(delegate),
+        amount,
+    );
+    // the token record follows the authority
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(token_record, false));
+
+    instruction
+}
+
+/// Transfers tokens out of the token-owned escrow of an asset.
+///
+/// # Accounts:
+///
+///   0. `[]` Token-owned escrow account
+///   1. `[]` Metadata account of the asset the escrow is attached to
+///   2. `[]` Mint account of the asset the escrow is attached to
+///   3. `[]` Token account holding the asset the escrow is attached to
+///   4. `[optional]` Token record account of the escrow token
+///   5. `[]` Mint account of the tokens being transferred
+///   6. `[writable]` Token account of the escrow holding the tokens
+///   7. `[writable]` Destination token account
+///   8. `[signer]` Escrow authority (holder, creator or delegate)
+///   9. `[signer, writable]` Payer
+///   10. `[]` System program
+///   11. `[]` Instructions sysvar account
+///   12. `[]` SPL Token Program
+///   13. `[]` SPL Associated Token Account program
+///   14. `[optional, writable]` Ledger account of the escrow
+impl InstructionBuilder for super::builders::EscrowTransferOut {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.escrow_mint, false),
+            AccountMeta::new_readonly(self.escrow_token, false),
+            AccountMeta::new_readonly(self.escrow_token_record.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.attribute_mint, false),
+            AccountMeta::new(self.attribute_src, false),
+            AccountMeta::new(self.attribute_dst, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            AccountMeta::new_readonly(self.spl_ata_program, false),
+            if let Some(escrow_ledger) = self.escrow_ledger {
+                AccountMeta::new(escrow_ledger, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::EscrowTransferOut(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Deposits tokens into the token-owned escrow of an asset.
+///
+/// # Accounts:
+///
+///   0. `[]` Token-owned escrow account
+///   1. `[]` Metadata account of the asset the escrow is attached to
+///   2. `[]` Mint account of the asset the escrow is attached to
+///   3. `[writable]` Ledger account of the escrow
+///   4. `[]` Mint account of the tokens being deposited
+///   5. `[writable]` Source token account
+///   6. `[writable]` Token account of the escrow for the tokens being deposited
+///   7. `[signer]` Owner or delegate of the source token account
+///   8. `[signer, writable]` Payer
+///   9. `[]` System program
+///   10. `[]` Instructions sysvar account
+///   11. `[]` SPL Token Program
+///   12. `[]` SPL Associated Token Account program
+impl InstructionBuilder for super::builders::EscrowDeposit {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.escrow_mint, false),
+            AccountMeta::new(self.escrow_ledger, false),
+            AccountMeta::new_readonly(self.attribute_mint, false),
+            AccountMeta::new(self.attribute_src, false),
+            AccountMeta::new(self.attribute_dst, false),
+            AccountMeta::new_readonly(self.depositor, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            AccountMeta::new_readonly(self.spl_ata_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::EscrowDeposit(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            EscrowDeposit, EscrowDepositBuilder, EscrowTransferOut, EscrowTransferOutBuilder,
+        },
+    };
+
+    #[test]
+    fn deposit_builder_accounts() {
+        let instruction = EscrowDepositBuilder::new()
+            .with_test_accounts()
+            .build(EscrowDepositArgs::V1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, EscrowDeposit::ACCOUNTS);
+    }
+
+    #[test]
+    #[cfg(not(feature = "ata"))]
+    fn deposit_builder_requires_ata_program_without_ata_feature() {
+        let mut builder = EscrowDepositBuilder::new();
+        builder
+            .escrow(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .escrow_mint(Pubkey::new_unique())
+            .escrow_ledger(Pubkey::new_unique())
+            .attribute_mint(Pubkey::new_unique())
+            .attribute_src(Pubkey::new_unique())
+            .attribute_dst(Pubkey::new_unique())
+            .depositor(Pubkey::new_unique())
+            .payer(Pubkey::new_unique());
+
+        // there is no default program id without the `ata` feature
+        assert!(builder.build(EscrowDepositArgs::V1 { amount: 1 }).is_err());
+
+        let instruction = builder
+            .spl_ata_program(programs::SPL_ATA_PROGRAM)
+            .build(EscrowDepositArgs::V1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_eq!(instruction.accounts[12].pubkey, programs::SPL_ATA_PROGRAM);
+    }
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = EscrowTransferOutBuilder::new()
+            .with_test_accounts()
+            .build(EscrowTransferOutArgs::V1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, EscrowTransferOut::ACCOUNTS);
+    }
+}
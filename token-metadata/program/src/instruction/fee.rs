@@ -0,0 +1,59 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use super::{InstructionBuilder, MetadataInstruction};
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CollectFeesArgs {
+    V1,
+}
+
+/// Transfers the fees accumulated in the fee vault to a recipient.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Protocol authority
+///   1. `[writable]` Fee vault account
+///   2. `[writable]` Recipient account
+///   3. `[]` System Program
+impl InstructionBuilder for super::builders::CollectFees {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.fee_vault, false),
+            AccountMeta::new(self.recipient, false),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CollectFees(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{CollectFees, CollectFeesBuilder},
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = CollectFeesBuilder::new()
+            .with_test_accounts()
+            .build(CollectFeesArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CollectFees::ACCOUNTS);
+    }
+}
@@ -0,0 +1,236 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use super::{InstructionBuilder, MetadataInstruction};
+use crate::state::FlagReason;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum FlagArgs {
+    V1 {
+        /// Reason for flagging the asset.
+        reason: FlagReason,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UnflagArgs {
+    V1 {
+        /// Reason to be cleared from the asset.
+        reason: FlagReason,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum DenylistArgs {
+    V1,
+}
+
+/// Flags an asset. The `Flagged` account is created if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Flagged account
+///   1. `[]` Mint account
+///   2. `[]` Metadata account
+///   3. `[signer]` Protocol authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::Flag {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.flagged, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Flag(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Clears a flag from an asset. The `Flagged` account is closed when no flags remain set,
+/// returning the rent to the payer of the account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Flagged account
+///   1. `[]` Mint account
+///   2. `[]` Metadata account
+///   3. `[signer]` Protocol authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+///   6. `[writable]` Rent destination (payer of the flagged account)
+impl InstructionBuilder for super::builders::Unflag {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.flagged, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new(self.rent_destination, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Unflag(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Adds an asset to the stolen asset denylist, creating its `Denylisted` account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Denylisted account
+///   1. `[]` Mint account
+///   2. `[]` Metadata account
+///   3. `[signer]` Protocol authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::AddToDenylist {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.denylisted, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::AddToDenylist(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Removes an asset from the stolen asset denylist, closing its `Denylisted` account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Denylisted account
+///   1. `[]` Mint account
+///   2. `[]` Metadata account
+///   3. `[signer]` Protocol authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+///   6. `[writable]` Rent destination (payer of the denylisted account)
+impl InstructionBuilder for super::builders::RemoveFromDenylist {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.denylisted, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new(self.rent_destination, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::RemoveFromDenylist(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            AddToDenylist, AddToDenylistBuilder, Flag, FlagBuilder, RemoveFromDenylist,
+            RemoveFromDenylistBuilder, Unflag, UnflagBuilder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = FlagBuilder::new()
+            .with_test_accounts()
+            .build(FlagArgs::V1 {
+                reason: FlagReason::Scam,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Flag::ACCOUNTS);
+
+        let instruction = UnflagBuilder::new()
+            .with_test_accounts()
+            .build(UnflagArgs::V1 {
+                reason: FlagReason::Scam,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Unflag::ACCOUNTS);
+
+        let instruction = AddToDenylistBuilder::new()
+            .with_test_accounts()
+            .build(DenylistArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, AddToDenylist::ACCOUNTS);
+
+        let instruction = RemoveFromDenylistBuilder::new()
+            .with_test_accounts()
+            .build(DenylistArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, RemoveFromDenylist::ACCOUNTS);
+    }
+
+    #[test]
+    fn builder_with_program_id() {
+        let program_id = Pubkey::new_unique();
+
+        let instruction = FlagBuilder::new()
+            .with_test_accounts()
+            .build(FlagArgs::V1 {
+                reason: FlagReason::Scam,
+            })
+            .unwrap()
+            .instruction();
+        assert_eq!(instruction.program_id, crate::ID);
+
+        let instruction = FlagBuilder::new()
+            .with_test_accounts()
+            .with_program_id(program_id)
+            .build(FlagArgs::V1 {
+                reason: FlagReason::Scam,
+            })
+            .unwrap()
+            .instruction();
+        assert_eq!(instruction.program_id, program_id);
+    }
+}
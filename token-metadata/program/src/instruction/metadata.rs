@@ -1,5 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    hash::hash,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
@@ -9,13 +10,14 @@ use {
     serde_with::{As, DisplayFromStr},
 };
 
-use super::InstructionBuilder;
+use super::{InstructionBuilder, DEFAULT_AUTH_RULES_PROGRAM};
 use crate::{
     instruction::MetadataInstruction,
     processor::AuthorizationData,
+    programs,
     state::{
-        AssetData, Collection, CollectionDetails, Creator, Data, DataV2, MigrationType,
-        PrintSupply, Uses,
+        AssetData, BasisPoints, Collection, CollectionDetails, ContentRating, Creator, Data,
+        DataV2, MigrationType, PrintSupply, TokenStandard, Uses,
     },
 };
 
@@ -45,6 +47,15 @@ pub enum CreateArgs {
         decimals: Option<u8>,
         print_supply: Option<PrintSupply>,
     },
+    V2 {
+        asset_data: AssetData,
+        decimals: Option<u8>,
+        print_supply: Option<PrintSupply>,
+        /// Permanent delegate of the asset, which retains the right to transfer the asset
+        /// regardless of the holder. It cannot be changed after the asset is created and
+        /// requires the mint to be initialized by the instruction with Token-2022.
+        permanent_delegate: Option<Pubkey>,
+    },
 }
 
 #[repr(C)]
@@ -56,6 +67,20 @@ pub enum MintArgs {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
     },
+    /// Mints a fungible asset to multiple token accounts.
+    BatchV1 {
+        /// Amount to mint to each token account, in the order the accounts are passed.
+        amounts: Vec<u64>,
+    },
+    /// Mints to the associated token account of an explicit recipient; the token account
+    /// must be the associated token account of the `token_owner`.
+    V2 {
+        amount: u64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+        /// Owner of the token account receiving the tokens.
+        token_owner: Pubkey,
+    },
 }
 
 #[repr(C)]
@@ -69,6 +94,170 @@ pub enum TransferArgs {
     },
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum BeginTransferSessionArgs {
+    V1 {
+        /// Amount of the validated transfer.
+        amount: u64,
+        /// Number of slots the session is open for (up to `MAX_TRANSFER_SESSION_SLOTS`).
+        duration_slots: u64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum EndTransferSessionArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetDefaultRuleSetArgs {
+    V1 {
+        /// Token standard of the assets.
+        token_standard: TokenStandard,
+        /// Default rule set of the token standard (`None` removes the default).
+        rule_set: Option<Pubkey>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ProposeRuleSetArgs {
+    V1 {
+        /// Proposed rule set.
+        rule_set: Pubkey,
+        /// New delay (in seconds) before proposals can be committed; the delay can only
+        /// be increased (`None` keeps the current delay).
+        delay: Option<i64>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CommitRuleSetArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CreateAuthorityConfigArgs {
+    V1 {
+        /// Number of signers required to approve an action.
+        threshold: u8,
+        /// Signers of the authority config.
+        signers: Vec<Pubkey>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetCreatorsOverflowArgs {
+    V1 {
+        /// Creators stored on the metadata.
+        creators: Vec<Creator>,
+        /// Creators stored on the creators overflow account.
+        overflow_creators: Vec<Creator>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum PayRoyaltiesArgs {
+    V1 {
+        /// Amount (in lamports or tokens of the payment mint) to split between the creators.
+        amount: u64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SettlePrimarySaleArgs {
+    V1 {
+        /// Sale proceeds (in lamports or tokens of the payment mint) to split between the
+        /// creators.
+        amount: u64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ResizeArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum BatchSetTokenStandardArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum VerifyContentArgs {
+    V1 {
+        /// SHA-256 hash of the off-chain JSON at the URI of the asset.
+        uri_hash: [u8; 32],
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CreateExistingMintMetadataArgs {
+    V1 {
+        data: DataV2,
+        /// Whether the metadata can be updated in the future.
+        is_mutable: bool,
+        /// Last slot in which the signed message of the mint authority is accepted; required
+        /// when the creation is not approved by the freeze authority.
+        expiry_slot: Option<u64>,
+    },
+}
+
+/// Prefix of the message signed by the mint authority to approve the creation of
+/// the metadata of an existing mint.
+pub const CREATE_EXISTING_MINT_METADATA_MESSAGE_PREFIX: &[u8] =
+    b"token-metadata:create-existing-mint-metadata";
+
+/// Returns the message that the mint authority (or the signers of its multisig) signs
+/// to approve the creation of the metadata of an existing mint with the given arguments.
+///
+/// The message is the prefix followed by the metadata account, the mint, the update authority
+/// and the hash of the instruction arguments, which include the expiry slot of the message.
+pub fn create_existing_mint_metadata_message(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    args: &CreateExistingMintMetadataArgs,
+) -> Vec<u8> {
+    let args_hash = hash(&args.try_to_vec().unwrap());
+
+    [
+        CREATE_EXISTING_MINT_METADATA_MESSAGE_PREFIX,
+        metadata.as_ref(),
+        mint.as_ref(),
+        update_authority.as_ref(),
+        args_hash.as_ref(),
+    ]
+    .concat()
+}
+
 /// Struct representing the values to be updated for an `update` instructions.
 ///
 /// Values that are set to 'None' are not changed; any value set to `Some(_)` will
@@ -95,10 +284,22 @@ pub enum UpdateArgs {
         collection_details: CollectionDetailsToggle,
         /// Uses information.
         uses: UsesToggle,
-        // Programmable rule set configuration (only applicable to `Programmable` asset types).
+        // Programmable rule set configuration (only applicable to `Programmable` asset types);
+        /// new rule sets are set through `ProposeRuleSet`, so only clearing is supported.
         rule_set: RuleSetToggle,
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
+        /// Indicates whether the royalties are inherited from the collection parent.
+        inherit_royalties: Option<bool>,
+        /// Indicates whether the default rule set applies when the asset does not have
+        /// a rule set (only applicable to `Programmable` asset types).
+        use_default_rule_set: Option<bool>,
+        /// Indicates whether the verified creators are unverified when the update
+        /// authority changes.
+        unverify_creators_on_authority_change: Option<bool>,
+        /// Content rating of the asset (only settable by the update authority; requires
+        /// the content rating record as the first remaining account).
+        content_rating: Option<ContentRating>,
     },
 }
 
@@ -114,6 +315,10 @@ impl Default for UpdateArgs {
             uses: UsesToggle::None,
             collection_details: CollectionDetailsToggle::None,
             rule_set: RuleSetToggle::None,
+            inherit_royalties: None,
+            use_default_rule_set: None,
+            unverify_creators_on_authority_change: None,
+            content_rating: None,
         }
     }
 }
@@ -273,6 +478,28 @@ pub enum MigrateArgs {
         migration_type: MigrationType,
         rule_set: Option<Pubkey>,
     },
+    /// Migrates multiple assets of a collection to `ProgrammableNonFungible` using the
+    /// collection update authority (or a collection delegate), once the grace period of the
+    /// migration window of the collection has elapsed. Assets opted out by their holders
+    /// are skipped.
+    CollectionV1 { rule_set: Option<Pubkey> },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum OpenMigrationWindowArgs {
+    V1 {
+        /// Grace period (in seconds) before the items of the collection can be migrated.
+        grace_period: i64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum OptOutMigrationArgs {
+    V1,
 }
 
 #[repr(C)]
@@ -336,14 +563,14 @@ pub fn create_metadata_accounts_v3(
             AccountMeta::new_readonly(mint_authority, true),
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(update_authority, update_authority_is_signer),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
         ],
         data: MetadataInstruction::CreateMetadataAccountV3(CreateMetadataAccountArgsV3 {
             data: DataV2 {
                 name,
                 symbol,
                 uri,
-                seller_fee_basis_points,
+                seller_fee_basis_points: BasisPoints::new_unchecked(seller_fee_basis_points),
                 creators,
                 collection,
                 uses,
@@ -409,6 +636,35 @@ pub fn set_token_standard(
     }
 }
 
+/// Sets the token standard of multiple assets, inferred from their mint and edition accounts.
+///
+/// Each asset is a `(metadata, mint, edition)` triplet, where `edition` is the edition PDA
+/// of the mint (which may not exist). The instruction is permissionless and only sets the
+/// token standard of assets without one.
+pub fn batch_set_token_standard(
+    program_id: Pubkey,
+    assets: &[(Pubkey, Pubkey, Pubkey)],
+) -> Instruction {
+    let accounts = assets
+        .iter()
+        .flat_map(|(metadata, mint, edition)| {
+            [
+                AccountMeta::new(*metadata, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(*edition, false),
+            ]
+        })
+        .collect();
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::BatchSetTokenStandard(BatchSetTokenStandardArgs::V1)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 /// Sign Metadata
 #[allow(clippy::too_many_arguments)]
 pub fn sign_metadata(program_id: Pubkey, metadata: Pubkey, creator: Pubkey) -> Instruction {
@@ -485,6 +741,7 @@ pub fn update_primary_sale_happened_via_token(
 ///   6. `[]` System program
 ///   7. `[]` Instructions sysvar account
 ///   8. `[]` SPL Token program
+///   9. `[optional, writable]` Fee vault account
 impl InstructionBuilder for super::builders::Create {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let accounts = vec![
@@ -502,10 +759,15 @@ impl InstructionBuilder for super::builders::Create {
             AccountMeta::new_readonly(self.system_program, false),
             AccountMeta::new_readonly(self.sysvar_instructions, false),
             AccountMeta::new_readonly(self.spl_token_program, false),
+            if let Some(fee_vault) = self.fee_vault {
+                AccountMeta::new(fee_vault, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
         ];
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Create(self.args.clone())
                 .try_to_vec()
@@ -516,6 +778,10 @@ impl InstructionBuilder for super::builders::Create {
 
 /// Migrates an asset to a ProgrammableAsset type.
 ///
+/// For `CollectionV1` migrations, the migration window of the collection, the opt-out
+/// account of the first asset and the accounts of additional assets must be appended to
+/// the accounts of the instruction.
+///
 /// # Accounts:
 ///
 ///
@@ -554,7 +820,12 @@ impl InstructionBuilder for super::builders::Migrate {
 
         // Optional authorization rules accounts
         if let Some(rules) = &self.authorization_rules {
-            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ));
             accounts.push(AccountMeta::new_readonly(*rules, false));
         } else {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
@@ -562,7 +833,7 @@ impl InstructionBuilder for super::builders::Migrate {
         }
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Migrate(self.args.clone())
                 .try_to_vec()
@@ -571,6 +842,72 @@ impl InstructionBuilder for super::builders::Migrate {
     }
 }
 
+/// Opens the migration window of a collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Migration window account
+///   1. `[]` Collection mint account
+///   2. `[]` Collection metadata account
+///   3. `[signer]` Update authority of the collection
+///   4. `[signer, writable]` Payer
+///   5. `[]` System program
+impl InstructionBuilder for super::builders::OpenMigrationWindow {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.migration_window, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::OpenMigrationWindow(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Opts an asset out of the migration of its collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Migration opt-out account
+///   1. `[]` Migration window account
+///   2. `[]` Metadata account
+///   3. `[]` Mint account
+///   4. `[]` Token account
+///   5. `[signer]` Token owner
+///   6. `[signer, writable]` Payer
+///   7. `[]` System program
+impl InstructionBuilder for super::builders::OptOutMigration {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.migration_opt_out, false),
+            AccountMeta::new_readonly(self.migration_window, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.token_owner, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::OptOutMigration(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
 /// Builds the instruction to mint a token.
 ///
 /// # Accounts:
@@ -592,9 +929,15 @@ impl InstructionBuilder for super::builders::Migrate {
 ///   14. `[optional]` Token Authorization Rules account
 impl InstructionBuilder for super::builders::Mint {
     fn instruction(&self) -> solana_program::instruction::Instruction {
+        // the owner of the token account defaults to the recipient of V2 args
+        let token_owner = match &self.args {
+            MintArgs::V2 { token_owner, .. } => self.token_owner.or(Some(*token_owner)),
+            _ => self.token_owner,
+        };
+
         let mut accounts = vec![
             AccountMeta::new(self.token, false),
-            AccountMeta::new_readonly(self.token_owner.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(token_owner.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.metadata, false),
             if let Some(master_edition) = self.master_edition {
                 AccountMeta::new(master_edition, false)
@@ -617,7 +960,12 @@ impl InstructionBuilder for super::builders::Mint {
         ];
         // Optional authorization rules accounts
         if let Some(rules) = &self.authorization_rules {
-            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ));
             accounts.push(AccountMeta::new_readonly(*rules, false));
         } else {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
@@ -625,7 +973,7 @@ impl InstructionBuilder for super::builders::Mint {
         }
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Mint(self.args.clone())
                 .try_to_vec()
@@ -655,6 +1003,10 @@ impl InstructionBuilder for super::builders::Mint {
 ///   14. `[]` SPL Associated Token Account program
 ///   15. `[optional]` Token Authorization Rules Program
 ///   16. `[optional]` Token Authorization Rules account
+///   17. `[optional]` Default rule sets account
+///   18. `[optional]` Collection pause account
+///   19. `[optional]` Collection metadata account
+///   20. `[optional]` Denylisted account
 impl InstructionBuilder for super::builders::Transfer {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let mut accounts = vec![
@@ -693,9 +1045,29 @@ impl InstructionBuilder for super::builders::Transfer {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
         }
+        accounts.push(AccountMeta::new_readonly(
+            self.default_rule_sets.unwrap_or(crate::ID),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            self.collection_pause.unwrap_or(crate::ID),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            self.collection_metadata.unwrap_or(crate::ID),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            self.denylisted.unwrap_or(crate::ID),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            self.transfer_session.unwrap_or(crate::ID),
+            false,
+        ));
 
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Transfer(self.args.clone())
                 .try_to_vec()
@@ -704,11 +1076,306 @@ impl InstructionBuilder for super::builders::Transfer {
     }
 }
 
+/// Sets the default rule set of a token standard. The `DefaultRuleSets` account is
+/// created if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Default rule sets account
+///   1. `[signer]` Protocol authority
+///   2. `[signer, writable]` Payer
+///   3. `[]` System Program
+impl InstructionBuilder for super::builders::SetDefaultRuleSet {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.default_rule_sets, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetDefaultRuleSet(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Proposes a new rule set for a programmable asset. The `RuleSetProposal` account is
+/// created if it does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Rule set proposal account
+///   1. `[]` Mint account
+///   2. `[]` Metadata account
+///   3. `[signer]` Update authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::ProposeRuleSet {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.rule_set_proposal, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::ProposeRuleSet(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Commits the proposed rule set of a programmable asset once its delay has elapsed.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Rule set proposal account
+///   1. `[]` Mint account
+///   2. `[writable]` Metadata account
+///   3. `[]` Token account
+///   4. `[signer]` Update authority
+impl InstructionBuilder for super::builders::CommitRuleSet {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.rule_set_proposal, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.authority, true),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CommitRuleSet(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Creates a threshold (M-of-N) authority config.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Authority config account
+///   1. `[signer]` Base address of the authority config
+///   2. `[signer, writable]` Payer
+///   3. `[]` System Program
+impl InstructionBuilder for super::builders::CreateAuthorityConfig {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.authority_config, false),
+            AccountMeta::new_readonly(self.base, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CreateAuthorityConfig(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Sets the creators of an asset, including the creators stored on the creators
+/// overflow account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[]` Mint account
+///   2. `[writable]` Creators overflow account
+///   3. `[signer]` Update authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SetCreatorsOverflow {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.creators_overflow, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetCreatorsOverflow(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Pays the royalties of an asset to its verified creators.
+///
+/// # Accounts:
+///
+///   0. `[signer, writable]` Payer of the royalties
+///   1. `[]` Metadata account
+///   2. `[optional]` Creators overflow account
+///   3. `[optional]` Metadata account of the collection
+///   4. `[]` System Program
+///   5. `[optional, writable]` Token account of the payer
+///   6. `[optional]` Mint of the payment
+///   7. `[optional]` SPL Token program
+impl InstructionBuilder for super::builders::PayRoyalties {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.creators_overflow.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.collection_metadata.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.system_program, false),
+            if let Some(payer_token) = self.payer_token {
+                AccountMeta::new(payer_token, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.payment_mint.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::PayRoyalties(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Settles the primary sale of an asset, paying the proceeds to its verified creators.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Holder or Sale delegate of the asset
+///   1. `[signer, writable]` Buyer paying the sale proceeds
+///   2. `[writable]` Metadata account
+///   3. `[]` Mint account
+///   4. `[]` Token account of the holder
+///   5. `[optional]` Token record account
+///   6. `[optional]` Creators overflow account
+///   7. `[]` System Program
+///   8. `[optional, writable]` Token account of the payer
+///   9. `[optional]` Mint of the payment
+///   10. `[optional]` SPL Token program
+impl InstructionBuilder for super::builders::SettlePrimarySale {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.token_record.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.creators_overflow.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.system_program, false),
+            if let Some(payer_token) = self.payer_token {
+                AccountMeta::new(payer_token, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.payment_mint.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SettlePrimarySale(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Builds the instruction to create the metadata of an existing mint.
+///
+/// The authority signs the transaction when it is the freeze authority of the mint;
+/// otherwise, the instruction must be preceded by an Ed25519 program instruction with
+/// the signature of the mint authority over `create_existing_mint_metadata_message`.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[]` Mint account
+///   2. `[optional signer]` Mint authority or freeze authority
+///   3. `[signer, writable]` Payer
+///   4. `[]` Update authority
+///   5. `[]` System program
+///   6. `[]` Instructions sysvar account
+impl InstructionBuilder for super::builders::CreateExistingMintMetadata {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.authority, self.authority_as_signer),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.update_authority, false),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::CreateExistingMintMetadata(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Resizes a metadata account to the size of its current contents, sending the
+/// excess rent to the destination account.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[writable]` Destination account
+impl InstructionBuilder for super::builders::Resize {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.destination, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::Resize(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
 /// Updates the metadata of an asset.
 ///
 /// # Accounts:
 ///
-///   0. `[signer]` Update authority or delegate
+///   0. `[signer]` Update authority, delegate or authority config signer
 ///   1. `[optional]` Delegate record PDA
 ///   2. `[optional]` Token account
 ///   3. `[]` Mint account
@@ -719,6 +1386,7 @@ impl InstructionBuilder for super::builders::Transfer {
 ///   8. `[]` System program
 ///   9. `[optional]` Token Authorization Rules Program
 ///   10. `[optional]` Token Authorization Rules account
+///   11. `[optional, writable]` Authority config account
 impl InstructionBuilder for super::builders::Update {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let mut accounts = vec![
@@ -747,15 +1415,26 @@ impl InstructionBuilder for super::builders::Update {
 
         // Optional authorization rules accounts
         if let Some(rules) = &self.authorization_rules {
-            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ));
             accounts.push(AccountMeta::new_readonly(*rules, false));
         } else {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
         }
 
+        accounts.push(if let Some(authority_config) = self.authority_config {
+            AccountMeta::new(authority_config, false)
+        } else {
+            AccountMeta::new_readonly(crate::ID, false)
+        });
+
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Update(self.args.clone())
                 .try_to_vec()
@@ -763,3 +1442,345 @@ impl InstructionBuilder for super::builders::Update {
         }
     }
 }
+
+/// Updates the metadata of multiple assets of a collection.
+///
+/// The metadata accounts of the assets must be appended to the accounts of the instruction
+/// as writable accounts.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Update authority or collection delegate
+///   1. `[optional]` Collection delegate record PDA
+///   2. `[]` Mint account of the collection
+///   3. `[]` Metadata account of the collection
+impl InstructionBuilder for super::builders::BatchUpdate {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::BatchUpdate(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Attests the hash of the off-chain JSON of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Creator of the asset
+impl InstructionBuilder for super::builders::VerifyContent {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.creator, true),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::VerifyContent(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Opens a transfer session, validating the rule set of an asset once for a burst of
+/// holder transfers of the assets of its collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Transfer session account
+///   1. `[signer]` Holder opening the session
+///   2. `[]` Destination owner of the validated transfer
+///   3. `[]` Mint account
+///   4. `[]` Metadata account
+///   5. `[signer, writable]` Payer
+///   6. `[]` System Program
+///   7. `[]` Token Authorization Rules Program
+///   8. `[]` Token Authorization Rules account
+impl InstructionBuilder for super::builders::BeginTransferSession {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.transfer_session, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.destination_owner, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ),
+            AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::BeginTransferSession(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Closes a transfer session.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Transfer session account
+///   1. `[signer, writable]` Holder that opened the session
+impl InstructionBuilder for super::builders::EndTransferSession {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.transfer_session, false),
+            AccountMeta::new(self.authority, true),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::EndTransferSession(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruction::{
+            assert_builder_accounts,
+            builders::{
+                BatchUpdate, BatchUpdateBuilder, BeginTransferSession, BeginTransferSessionBuilder,
+                CommitRuleSet, CommitRuleSetBuilder, Create, CreateAuthorityConfig,
+                CreateAuthorityConfigBuilder, CreateBuilder, CreateExistingMintMetadata,
+                CreateExistingMintMetadataBuilder, EndTransferSession, EndTransferSessionBuilder,
+                Migrate, MigrateBuilder, Mint, MintBuilder, OpenMigrationWindow,
+                OpenMigrationWindowBuilder, OptOutMigration, OptOutMigrationBuilder, PayRoyalties,
+                PayRoyaltiesBuilder, ProposeRuleSet, ProposeRuleSetBuilder, Resize, ResizeBuilder,
+                SetCreatorsOverflow, SetCreatorsOverflowBuilder, SetDefaultRuleSet,
+                SetDefaultRuleSetBuilder, SettlePrimarySale, SettlePrimarySaleBuilder, Transfer,
+                TransferBuilder, Update, UpdateBuilder, VerifyContent, VerifyContentBuilder,
+            },
+        },
+        state::AssetDataConfig,
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = CreateBuilder::new()
+            .with_test_accounts()
+            .initialize_mint(true)
+            .update_authority_as_signer(true)
+            .build(CreateArgs::V1 {
+                asset_data: AssetData::from_config(AssetDataConfig::default()),
+                decimals: None,
+                print_supply: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Create::ACCOUNTS);
+
+        let instruction = MigrateBuilder::new()
+            .with_test_accounts()
+            .build(MigrateArgs::V1 {
+                migration_type: MigrationType::ProgrammableV1,
+                rule_set: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Migrate::ACCOUNTS);
+
+        let instruction = OpenMigrationWindowBuilder::new()
+            .with_test_accounts()
+            .build(OpenMigrationWindowArgs::V1 {
+                grace_period: 604_800,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, OpenMigrationWindow::ACCOUNTS);
+
+        let instruction = OptOutMigrationBuilder::new()
+            .with_test_accounts()
+            .build(OptOutMigrationArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, OptOutMigration::ACCOUNTS);
+
+        let instruction = MintBuilder::new()
+            .with_test_accounts()
+            .build(MintArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Mint::ACCOUNTS);
+
+        let instruction = MintBuilder::new()
+            .with_test_accounts()
+            .build(MintArgs::V2 {
+                amount: 1,
+                authorization_data: None,
+                token_owner: Pubkey::new_unique(),
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Mint::ACCOUNTS);
+
+        let instruction = TransferBuilder::new()
+            .with_test_accounts()
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Transfer::ACCOUNTS);
+
+        let instruction = UpdateBuilder::new()
+            .with_test_accounts()
+            .build(UpdateArgs::default())
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Update::ACCOUNTS);
+
+        let instruction = VerifyContentBuilder::new()
+            .with_test_accounts()
+            .build(VerifyContentArgs::V1 { uri_hash: [1; 32] })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, VerifyContent::ACCOUNTS);
+
+        let instruction = BatchUpdateBuilder::new()
+            .with_test_accounts()
+            .build(UpdateArgs::default())
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, BatchUpdate::ACCOUNTS);
+
+        let instruction = SetDefaultRuleSetBuilder::new()
+            .with_test_accounts()
+            .build(SetDefaultRuleSetArgs::V1 {
+                token_standard: TokenStandard::ProgrammableNonFungible,
+                rule_set: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetDefaultRuleSet::ACCOUNTS);
+
+        let instruction = ResizeBuilder::new()
+            .with_test_accounts()
+            .build(ResizeArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Resize::ACCOUNTS);
+
+        let instruction = CreateExistingMintMetadataBuilder::new()
+            .with_test_accounts()
+            .authority_as_signer(true)
+            .build(CreateExistingMintMetadataArgs::V1 {
+                data: DataV2 {
+                    name: String::from("Token"),
+                    symbol: String::from("TKN"),
+                    uri: String::new(),
+                    seller_fee_basis_points: BasisPoints::new_unchecked(0),
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+                is_mutable: true,
+                expiry_slot: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CreateExistingMintMetadata::ACCOUNTS);
+
+        let instruction = ProposeRuleSetBuilder::new()
+            .with_test_accounts()
+            .build(ProposeRuleSetArgs::V1 {
+                rule_set: Pubkey::new_unique(),
+                delay: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, ProposeRuleSet::ACCOUNTS);
+
+        let instruction = CommitRuleSetBuilder::new()
+            .with_test_accounts()
+            .build(CommitRuleSetArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CommitRuleSet::ACCOUNTS);
+
+        let instruction = CreateAuthorityConfigBuilder::new()
+            .with_test_accounts()
+            .build(CreateAuthorityConfigArgs::V1 {
+                threshold: 1,
+                signers: vec![Pubkey::new_unique()],
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, CreateAuthorityConfig::ACCOUNTS);
+
+        let instruction = SetCreatorsOverflowBuilder::new()
+            .with_test_accounts()
+            .build(SetCreatorsOverflowArgs::V1 {
+                creators: vec![],
+                overflow_creators: vec![],
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetCreatorsOverflow::ACCOUNTS);
+
+        let instruction = PayRoyaltiesBuilder::new()
+            .with_test_accounts()
+            .build(PayRoyaltiesArgs::V1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, PayRoyalties::ACCOUNTS);
+
+        let instruction = SettlePrimarySaleBuilder::new()
+            .with_test_accounts()
+            .build(SettlePrimarySaleArgs::V1 { amount: 1 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SettlePrimarySale::ACCOUNTS);
+
+        let instruction = BeginTransferSessionBuilder::new()
+            .with_test_accounts()
+            .build(BeginTransferSessionArgs::V1 {
+                amount: 1,
+                duration_slots: 100,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, BeginTransferSession::ACCOUNTS);
+
+        let instruction = EndTransferSessionBuilder::new()
+            .with_test_accounts()
+            .build(EndTransferSessionArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, EndTransferSession::ACCOUNTS);
+    }
+}
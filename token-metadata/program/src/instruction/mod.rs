@@ -1,29 +1,41 @@
+mod archive;
+mod attestation;
 mod bubblegum;
 mod burn;
+mod capabilities;
 mod collection;
+mod compute_budget;
 mod delegate;
 pub(crate) mod deprecated;
 mod edition;
 pub(crate) mod escrow;
+mod fee;
+mod flag;
 mod freeze;
 mod metadata;
 mod state;
 mod uses;
 
+pub use archive::*;
+pub use attestation::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 pub use bubblegum::*;
 pub use burn::*;
+pub use capabilities::*;
 pub use collection::*;
+pub use compute_budget::*;
 pub use delegate::*;
 pub use edition::*;
 pub use escrow::*;
+pub use fee::*;
+pub use flag::*;
 pub use freeze::*;
 pub use metadata::*;
 use mpl_token_metadata_context_derive::AccountContext;
 #[cfg(feature = "serde-feature")]
 use serde::{Deserialize, Serialize};
 use shank::ShankInstruction;
-use solana_program::account_info::AccountInfo;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 pub use state::*;
 pub use uses::*;
 
@@ -33,7 +45,10 @@ pub use crate::deprecated_instruction::{
     mint_edition_from_master_edition_via_vault_proxy, update_metadata_accounts,
     CreateMetadataAccountArgs, CreateMetadataAccountArgsV2, UpdateMetadataAccountArgs,
 };
-use crate::deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs};
+use crate::{
+    deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs},
+    state::TokenStandard,
+};
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -265,6 +280,8 @@ pub enum MetadataInstruction {
 
     /// Utilize or Use an NFT , burns the NFT and returns the lamports to the update authority if the use method is burn and its out of uses.
     /// Use Authority can be the Holder of the NFT, or a Delegated Use Authority.
+    ///
+    /// Deprecated: use `Use`, which also supports programmable assets.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, writable, name="token_account", desc="Token Account Of NFT")]
     #[account(2, writable, name="mint", desc="Mint of the Metadata")]
@@ -281,6 +298,8 @@ pub enum MetadataInstruction {
     Utilize(UtilizeArgs),
 
     /// Approve another account to call [utilize] on this NFT.
+    ///
+    /// Deprecated: approve a `Use` delegate with `Delegate` (`DelegateArgs::UseV1`) instead.
     #[account(0, writable, name="use_authority_record", desc="Use Authority Record PDA")]
     #[account(1, signer, writable, name="owner", desc="Owner")]
     #[account(2, signer, writable, name="payer", desc="Payer")]
@@ -295,6 +314,8 @@ pub enum MetadataInstruction {
     ApproveUseAuthority(ApproveUseAuthorityArgs),
 
     /// Revoke account to call [utilize] on this NFT.
+    ///
+    /// Deprecated: revoke a `Use` delegate with `Revoke` (`RevokeArgs::UseV1`) instead.
     #[account(0, writable, name="use_authority_record", desc="Use Authority Record PDA")]
     #[account(1, signer, writable, name="owner", desc="Owner")]
     #[account(2, name="user", desc="A Use Authority")]
@@ -420,6 +441,7 @@ pub enum MetadataInstruction {
     #[account(4, name="update_authority", desc="update authority info")]
     #[account(5, name="system_program", desc="System program")]
     #[account(6, optional, name="rent", desc="Rent info")]
+    #[account(7, optional, writable, name="fee_vault", desc="Fee vault (pda of ['metadata', program id, 'fee_vault']); the create fee is charged when present")]
     CreateMetadataAccountV3(CreateMetadataAccountArgsV3),
 
     /// Set size of an existing collection.
@@ -495,6 +517,7 @@ pub enum MetadataInstruction {
     #[account(10, name="token_program", desc="Token program")]
     #[account(11, name="sysvar_instructions", desc="Instructions sysvar account")]
     #[account(12, optional, signer, name="authority", desc="Authority/creator of the escrow account")]
+    #[account(13, optional, name="token_record", desc="Token record account of the escrow account (required when the authority is an escrow delegate)")]
     TransferOutOfEscrow(TransferOutOfEscrowArgs),
 
     //---- New API
@@ -503,15 +526,36 @@ pub enum MetadataInstruction {
     /// 
     /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
     /// it may require additional accounts to validate the rules.
+    ///
+    /// Burning a print edition decrements the supply of its master edition and clears the
+    /// edition from the edition marker, closing the marker when it no longer has editions.
+    ///
+    /// The reclaimed rent is sent to the `rent_destination` account when present, which must
+    /// be the owner of the token account; otherwise it is sent to the authority.
+    ///
+    /// A master edition with prints can only be burned with the `AllowAndMark` orphan prints
+    /// policy, in which case the master edition account is replaced by an `EditionTombstone`.
+    /// Burning the last print of a burned master edition closes the tombstone, sending its rent
+    /// to the `tombstone_rent_destination` recorded on the tombstone.
+    ///
+    /// Verified items of a paused collection cannot be burned when the `collection_pause`
+    /// account is specified.
     #[account(0, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
-    #[account(1, signer, writable, name="owner", desc="Asset owner")]
+    #[account(1, signer, writable, name="authority", desc="Asset owner or permanent delegate")]
     #[account(2, writable, name="mint", desc="Mint of token asset")]
     #[account(3, writable, name="token_account", desc="Token account to close")]
-    #[account(4, writable, name="master_edition_account", desc="MasterEdition of the asset")]
+    #[account(4, writable, name="edition", desc="Edition of the asset (master edition or print edition)")]
     #[account(5, name="spl_token_program", desc="SPL Token Program")]
     #[account(6, optional, writable, name="collection_metadata", desc="Metadata of the Collection")]
     #[account(7, optional, name="authorization_rules", desc="Token Authorization Rules account")]
     #[account(8, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(9, optional, name="master_edition_mint", desc="Mint of the master edition (required for print editions)")]
+    #[account(10, optional, writable, name="master_edition", desc="Master edition of the print edition")]
+    #[account(11, optional, writable, name="edition_marker", desc="Edition marker of the print edition")]
+    #[account(12, optional, writable, name="token_record", desc="Token record account (required for programmable assets)")]
+    #[account(13, optional, writable, name="rent_destination", desc="Destination of the reclaimed rent (token owner; defaults to the authority)")]
+    #[account(14, optional, writable, name="tombstone_rent_destination", desc="Rent destination recorded on the edition tombstone (required to burn the last print of a burned master edition)")]
+    #[account(15, optional, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause']); the pause of verified collection items is checked when present")]
     #[default_optional_accounts]
     Burn(BurnArgs),
 
@@ -521,6 +565,14 @@ pub enum MetadataInstruction {
     /// the mint key is a signer on the transaction.
     ///
     /// When creating a non-fungible assert, the `master_edition` needs to be specified.
+    ///
+    /// `CreateArgs::V2` can also set a permanent delegate for the asset, which cannot be
+    /// changed afterwards.
+    ///
+    /// `NonTransferable` assets are created without prints; their token is frozen when minted
+    /// and can only be burned by the holder.
+    ///
+    /// When the `fee_vault` is specified, the protocol create fee is charged to the payer.
     #[account(0, writable, name="metadata", desc="Unallocated metadata account with address as pda of ['metadata', program id, mint id]")]
     #[account(1, optional, writable, name="master_edition", desc="Unallocated edition account with address as pda of ['metadata', program id, mint, 'edition']")]
     #[account(2, writable, name="mint", desc="Mint of token asset")]
@@ -530,6 +582,7 @@ pub enum MetadataInstruction {
     #[account(6, name="system_program", desc="System program")]
     #[account(7, name="sysvar_instructions", desc="Instructions sysvar account")]
     #[account(8, name="spl_token_program", desc="SPL Token program")]
+    #[account(9, optional, writable, name="fee_vault", desc="Fee vault (pda of ['metadata', program id, 'fee_vault'])")]
     #[args(initialize_mint: bool)]
     #[args(update_authority_as_signer: bool)]
     #[default_optional_accounts]
@@ -541,6 +594,12 @@ pub enum MetadataInstruction {
     /// this case the `token_owner` will be required. When minting `*NonFungible` assets, the `authority`
     /// must be the update authority; in all other cases, it must be the mint authority from the mint
     /// account.
+    /// 
+    /// Fungible assets can be minted to multiple token accounts with `MintArgs::BatchV1`, passing
+    /// the additional `(token, token_owner)` pairs as remaining accounts.
+    /// 
+    /// With `MintArgs::V2`, the `token` account must be the associated token account of the
+    /// `token_owner` specified in the args.
     #[account(0, writable, name="token", desc="Token or Associated Token account")]
     #[account(1, optional, name="token_owner", desc="Owner of the token account")]
     #[account(2, name="metadata", desc="Metadata account (pda of ['metadata', program id, mint id])")]
@@ -567,6 +626,9 @@ pub enum MetadataInstruction {
     ///      `Utility` actions (pda of ["metadata", program id, mint id, "persistent_delegate", token owner id])
     ///   2. Multiple delegates: for `Authority`, `Collection`, `Update` and `Uses` actions (pda of ["metadata",
     ///      program id, mint id, role, update authority id, delegate owner id])
+    /// 
+    /// When the delegate registry account is provided, metadata delegates are added to the registry
+    /// of the mint (the registry is created if it does not exist).
     #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
     #[account(1, name="delegate", desc="Owner of the delegated account")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
@@ -581,12 +643,19 @@ pub enum MetadataInstruction {
     #[account(11, optional, name="spl_token_program", desc="SPL Token Program")]
     #[account(12, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(13, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(14, optional, writable, name="delegate_registry", desc="Delegate registry account (pda of ['metadata', program id, mint id, 'delegate_registry'])")]
+    #[account(15, optional, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause']); the pause of verified collection items is checked when present")]
     #[default_optional_accounts]
     Delegate(DelegateArgs),
 
     /// Revokes a delegate.
     /// 
-    /// A delegate can revoke itself by signing the transaction as the 'approver'.
+    /// A delegate can revoke itself by signing the transaction as the 'approver'. When the
+    /// delegate registry account is provided, metadata delegates are removed from the registry.
+    /// 
+    /// `RevokeArgs::AllV1` revokes every delegate of the token owner in a single call: it clears
+    /// the token delegate and closes the metadata delegate records (approved by the authority)
+    /// passed as remaining accounts. In this case, the `delegate` account is not used.
     #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
     #[account(1, name="delegate", desc="Owner of the delegated account")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
@@ -601,11 +670,15 @@ pub enum MetadataInstruction {
     #[account(11, optional, name="spl_token_program", desc="SPL Token Program")]
     #[account(12, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(13, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(14, optional, writable, name="delegate_registry", desc="Delegate registry account (pda of ['metadata', program id, mint id, 'delegate_registry'])")]
     #[default_optional_accounts]
     Revoke(RevokeArgs),
 
     /// Locks an asset. For non-programmable assets, this will also freeze the token account.
     /// 
+    /// When the `collection_delegate_record` is provided, the authority is validated as a
+    /// collection items delegate of the token owner instead of a token delegate.
+    /// 
     /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
     /// it may require additional accounts to validate the rules.
     #[account(0, signer, name="authority", desc="Delegate or freeze authority")]
@@ -621,13 +694,21 @@ pub enum MetadataInstruction {
     #[account(10, optional, name="spl_token_program", desc="SPL Token Program")]
     #[account(11, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(12, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(13, optional, writable, name="collection_delegate_record", desc="Collection items delegate record account (pda of ['metadata', program id, collection mint id, 'collection_items_delegate', holder id, delegate id])")]
     #[default_optional_accounts]
     Lock(LockArgs),
 
     /// Unlocks an asset. For non-programmable assets, this will also thaw the token account.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
-    /// it may require additional accounts to validate the rules.
+    /// When the `collection_delegate_record` is provided, the authority is validated as a
+    /// collection items delegate of the token owner instead of a token delegate.
+    /// 
+    /// Assets locked by a `Staking` delegate can only be unlocked through a CPI from the
+    /// staking program recorded on the token record.
+    /// 
+    /// Unlocks from a `Utility` delegate of a `ProgrammableNonFungible` asset are validated
+    /// against the `authorization_rules` (operation `Unlock:UtilityDelegate`) using the
+    /// `authorization_data` of the args; the rules may require additional accounts.
     #[account(0, signer, name="authority", desc="Delegate or freeze authority")]
     #[account(1, optional, name="token_owner", desc="Token owner account")]
     #[account(2, writable, name="token", desc="Token account")]
@@ -641,10 +722,21 @@ pub enum MetadataInstruction {
     #[account(10, optional, name="spl_token_program", desc="SPL Token Program")]
     #[account(11, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(12, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(13, optional, writable, name="collection_delegate_record", desc="Collection items delegate record account (pda of ['metadata', program id, collection mint id, 'collection_items_delegate', holder id, delegate id])")]
     #[default_optional_accounts]
     Unlock(UnlockArgs),
 
     /// Migrates an asset to a ProgrammableAsset type.
+    /// 
+    /// The `CollectionV1` migration is performed by the collection update authority (or a
+    /// `Collection` delegate, whose record is passed as the delegate record account) without
+    /// the participation of the holders, and migrates multiple assets of the collection in a
+    /// single instruction. It requires the grace period of the migration window of the
+    /// collection (see `OpenMigrationWindow`) to have elapsed; assets whose holders opted out
+    /// are skipped. The migration window and the opt-out account of the first asset are the
+    /// first remaining accounts, followed by additional assets in groups of `[metadata
+    /// (writable), edition (writable), mint, token (writable), token_record (writable),
+    /// migration_opt_out]` accounts.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, writable, name="edition", desc="Edition account")]
     #[account(2, writable, name="token", desc="Token account")]
@@ -666,7 +758,22 @@ pub enum MetadataInstruction {
     /// Transfer an asset.
     /// 
     /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
-    /// it may require additional accounts to validate the rules.
+    /// it may require additional accounts to validate the rules. Assets without a rule set that
+    /// opted in to the default rule set (`use_default_rule_set`) are validated against the rule
+    /// set of their token standard on the `default_rule_sets` account.
+    /// 
+    /// A programmable asset with a `Sale` delegate is `Listed` and can only be transferred by
+    /// the delegate; the transfer closes the listing, returning the token state to `Unlocked`
+    /// and clearing the delegate.
+    /// 
+    /// Holder transfers can include a transfer session opened by `BeginTransferSession` to skip
+    /// the rule set validation; transfers not covered by the session are validated in full.
+    /// 
+    /// `NonTransferable` assets cannot be transferred.
+    /// 
+    /// Verified collection items cannot be transferred while their collection is paused, or
+    /// while on the denylist of a collection that enforces it; these checks apply when the
+    /// `collection_pause` and `collection_metadata` accounts are specified.
     #[account(0, writable, name="token", desc="Token account")]
     #[account(1, name="token_owner", desc="Token account owner")]
     #[account(2, writable, name="destination", desc="Destination token account")]
@@ -684,14 +791,36 @@ pub enum MetadataInstruction {
     #[account(14, name="spl_ata_program", desc="SPL Associated Token Account program")]
     #[account(15, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(16, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(17, optional, name="default_rule_sets", desc="Default rule sets account (pda of ['metadata', program id, 'default_rule_sets'])")]
+    #[account(18, optional, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause']); the pause of verified collection items is checked when present")]
+    #[account(19, optional, name="collection_metadata", desc="Collection metadata account (pda of ['metadata', program id, collection mint id]); the denylist of verified collection items is consulted when present")]
+    #[account(20, optional, name="denylisted", desc="Denylisted account (pda of ['metadata', program id, mint id, 'denylisted']), required with the collection metadata when the collection enforces the denylist")]
+    #[account(21, optional, name="transfer_session", desc="Transfer session account (pda of ['metadata', program id, authority, 'transfer_session']), skips the rule set validation of holder transfers")]
     #[default_optional_accounts]
     Transfer(TransferArgs),
 
     /// Updates the metadata of an asset.
     /// 
     /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
-    /// it may require additional accounts to validate the rules.
-    #[account(0, signer, name="authority", desc="Update authority or delegate")]
+    /// it may require additional accounts to validate the rules. The rule set can be cleared, but
+    /// a new rule set must be proposed with `ProposeRuleSet`.
+    /// 
+    /// When the update authority is also the authority of the collection (or a collection
+    /// delegate), setting a verified collection verifies the asset in the same instruction;
+    /// the collection mint, metadata (writable), master edition and (optional) delegate record
+    /// accounts are expected as remaining accounts.
+    /// 
+    /// Setting the content rating of the asset (update authority only) requires the content
+    /// rating record (writable, pda of ['metadata', program id, mint id, 'content_rating']) as
+    /// the first remaining account, ahead of the collection accounts.
+    /// 
+    /// When the update authority is an authority config, the authority is one of its signers:
+    /// the update is only applied once enough signers submitted the same update.
+    /// 
+    /// When the update authority is an SPL Governance account, the update must be executed
+    /// by a proposal transaction of the governance, which is expected as the first remaining
+    /// account, ahead of the content rating and collection accounts.
+    #[account(0, signer, name="authority", desc="Update authority, delegate or authority config signer")]
     #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, optional, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
@@ -702,6 +831,7 @@ pub enum MetadataInstruction {
     #[account(8, name="sysvar_instructions", desc="System program")]
     #[account(9, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(10, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(11, optional, writable, name="authority_config", desc="Authority config account, when the update authority is an authority config")]
     #[default_optional_accounts]
     Update(UpdateArgs),
 
@@ -711,6 +841,8 @@ pub enum MetadataInstruction {
     /// 
     /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
     /// it may require additional accounts to validate the rules.
+    /// 
+    /// This instruction is not supported: assets are used with `UseV2`.
     #[account(0, signer, name="authority", desc="Token owner or delegate")]
     #[account(1, writable, optional, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, writable, optional, name="token", desc="Token account")]
@@ -733,6 +865,8 @@ pub enum MetadataInstruction {
     /// 
     /// Depending on the type of verification (e.g., creator or collection), additional accounts
     /// are required.
+    /// 
+    /// This instruction is not supported: creators and collections are verified with `VerifyV2`.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, writable, name="collection_authority", desc="Collection Update authority")]
     #[account(2, signer, writable, name="payer", desc="payer")]
@@ -740,6 +874,716 @@ pub enum MetadataInstruction {
     #[account(4, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[default_optional_accounts]
     Verify(VerifyArgs),
+
+    /// Flags an asset on the content registry.
+    /// 
+    /// Flags are set by the protocol authority and do not restrict any operation on the
+    /// asset; they are meant to be read by wallets and marketplaces.
+    #[account(0, writable, name="flagged", desc="Flagged account (pda of ['metadata', program id, mint id, 'flagged'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Protocol authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    Flag(FlagArgs),
+
+    /// Clears a flag of an asset from the content registry.
+    /// 
+    /// The `Flagged` account is closed when no flags remain set, returning its rent to the
+    /// payer that created it.
+    #[account(0, writable, name="flagged", desc="Flagged account (pda of ['metadata', program id, mint id, 'flagged'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Protocol authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, writable, name="rent_destination", desc="Payer of the flagged account, receiving its rent when the last flag is cleared")]
+    Unflag(UnflagArgs),
+
+    /// Creates a token delegate and locks the asset in a single instruction.
+    /// 
+    /// This is equivalent to a `Delegate` followed by a `Lock` from the new delegate, which
+    /// avoids the window where the asset is delegated but not locked. Only delegate roles that
+    /// can unlock the asset are supported (`Utility`, `Staking` and `LockedTransfer` for
+    /// programmable assets, and `Standard` for non-fungibles).
+    #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
+    #[account(1, name="delegate", desc="Owner of the delegated account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, optional, name="master_edition", desc="Master Edition account")]
+    #[account(4, optional, writable, name="token_record", desc="Token record account")]
+    #[account(5, name="mint", desc="Mint of metadata")]
+    #[account(6, optional, writable, name="token", desc="Token account of mint")]
+    #[account(7, signer, name="authority", desc="Token owner")]
+    #[account(8, signer, writable, name="payer", desc="Payer")]
+    #[account(9, name="system_program", desc="System Program")]
+    #[account(10, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(11, optional, name="spl_token_program", desc="SPL Token Program")]
+    #[account(12, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(14, optional, writable, name="delegate_registry", desc="Delegate registry account (pda of ['metadata', program id, mint id, 'delegate_registry'])")]
+    #[account(15, optional, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause']); the pause of verified collection items is checked when present")]
+    #[default_optional_accounts]
+    DelegateAndLock(DelegateArgs),
+
+    /// Unlocks the asset and revokes the token delegate in a single instruction.
+    /// 
+    /// This is equivalent to an `Unlock` from the delegate followed by a `Revoke` from the
+    /// token owner, therefore both the delegate and the token owner must sign the transaction.
+    #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
+    #[account(1, signer, name="delegate", desc="Owner of the delegated account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, optional, name="master_edition", desc="Master Edition account")]
+    #[account(4, optional, writable, name="token_record", desc="Token record account")]
+    #[account(5, name="mint", desc="Mint of metadata")]
+    #[account(6, optional, writable, name="token", desc="Token account of mint")]
+    #[account(7, signer, name="authority", desc="Token owner")]
+    #[account(8, signer, writable, name="payer", desc="Payer")]
+    #[account(9, name="system_program", desc="System Program")]
+    #[account(10, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(11, optional, name="spl_token_program", desc="SPL Token Program")]
+    #[account(12, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(14, optional, writable, name="delegate_registry", desc="Delegate registry account (pda of ['metadata', program id, mint id, 'delegate_registry'])")]
+    #[default_optional_accounts]
+    UnlockAndRevoke(RevokeArgs),
+
+    /// Sets the window of time during which editions of a master edition can be printed.
+    /// 
+    /// The window is stored on the master edition extension account and enforced when
+    /// printing editions through the `Print` instruction.
+    #[account(0, writable, name="master_edition_extension", desc="Master edition extension account (pda of ['metadata', program id, mint id, 'edition', 'extension'])")]
+    #[account(1, name="master_edition", desc="Master edition account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, name="metadata", desc="Metadata account")]
+    #[account(4, signer, name="authority", desc="Update authority")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    SetPrintWindow(SetPrintWindowArgs),
+
+    /// Archives the metadata account of an asset.
+    /// 
+    /// The metadata account is closed and its rent returned to the token owner; a hash of the
+    /// serialized metadata is kept on a tombstone account so the metadata can be restored
+    /// later. Requires the consent of both the token owner and the update authority.
+    #[account(0, writable, name="tombstone", desc="Tombstone account (pda of ['metadata', program id, mint id, 'tombstone'])")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, name="token", desc="Token account")]
+    #[account(4, signer, writable, name="token_owner", desc="Token owner")]
+    #[account(5, signer, name="update_authority", desc="Update authority")]
+    #[account(6, signer, writable, name="payer", desc="Payer")]
+    #[account(7, name="system_program", desc="System program")]
+    Archive(ArchiveArgs),
+
+    /// Restores an archived metadata account.
+    /// 
+    /// The serialized metadata must match the hash stored on the tombstone account, which
+    /// is closed and its rent returned to the payer.
+    #[account(0, writable, name="tombstone", desc="Tombstone account (pda of ['metadata', program id, mint id, 'tombstone'])")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    Restore(RestoreArgs),
+
+    /// Updates the metadata of multiple assets of a collection.
+    /// 
+    /// The same update is applied to every metadata account passed as a remaining account
+    /// (writable); each asset must be a verified member of the collection. The authority must
+    /// be the update authority of the collection or a collection delegate, which can only
+    /// update the data, primary sale, uses and royalty inheritance of the assets.
+    #[account(0, signer, name="authority", desc="Update authority or collection delegate")]
+    #[account(1, optional, name="delegate_record", desc="Collection delegate record PDA")]
+    #[account(2, name="collection_mint", desc="Mint account of the collection")]
+    #[account(3, name="collection_metadata", desc="Metadata account of the collection")]
+    BatchUpdate(UpdateArgs),
+
+    /// Returns the instruction and argument versions supported by the program.
+    /// 
+    /// The borsh-serialized `ProgramCapabilities` bitmask is set as the return data of the
+    /// instruction, which does not require any account.
+    GetProgramCapabilities,
+
+    /// Sets the default rule set of a token standard.
+    /// 
+    /// Default rule sets are set by the protocol authority and apply to assets that do not have
+    /// a rule set and opted in to the default rule set of their token standard.
+    #[account(0, writable, name="default_rule_sets", desc="Default rule sets account (pda of ['metadata', program id, 'default_rule_sets'])")]
+    #[account(1, signer, name="authority", desc="Protocol authority")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="system_program", desc="System program")]
+    SetDefaultRuleSet(SetDefaultRuleSetArgs),
+
+    /// Prints a new edition from a master edition.
+    /// 
+    /// The token of the new edition is minted to the `edition_token_account` (initialized as an
+    /// associated token account if it does not exist) and the mint and freeze authorities are
+    /// transferred to the new edition. Editions of `ProgrammableNonFungible` master editions are
+    /// also programmable, in which case the `edition_token_record` is required.
+    /// 
+    /// The print window of the master edition (if any) is enforced.
+    #[account(0, writable, name="edition_metadata", desc="New metadata account (pda of ['metadata', program id, mint id])")]
+    #[account(1, writable, name="edition", desc="New edition account (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, writable, name="edition_mint", desc="Mint of the new edition")]
+    #[account(3, name="edition_token_account_owner", desc="Owner of the token account of the new edition")]
+    #[account(4, writable, name="edition_token_account", desc="Token account of the new edition")]
+    #[account(5, signer, name="edition_mint_authority", desc="Mint authority of the new edition")]
+    #[account(6, optional, writable, name="edition_token_record", desc="Token record account of the new edition")]
+    #[account(7, writable, name="master_edition", desc="Master edition account (pda of ['metadata', program id, master mint id, 'edition'])")]
+    #[account(8, writable, name="edition_marker_pda", desc="Edition marker account (pda of ['metadata', program id, master mint id, 'edition', edition_number]) where edition_number is floor(edition/EDITION_MARKER_BIT_SIZE)")]
+    #[account(9, signer, writable, name="payer", desc="Payer")]
+    #[account(10, signer, name="master_token_account_owner", desc="Owner of the token account of the master edition")]
+    #[account(11, name="master_token_account", desc="Token account of the master edition")]
+    #[account(12, name="master_metadata", desc="Metadata account of the master edition")]
+    #[account(13, name="update_authority", desc="Update authority of the master edition")]
+    #[account(14, name="master_edition_extension", desc="Master edition extension account (pda of ['metadata', program id, master mint id, 'edition', 'extension'])")]
+    #[account(15, name="spl_token_program", desc="SPL Token program")]
+    #[account(16, name="spl_ata_program", desc="SPL Associated Token Account program")]
+    #[account(17, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(18, name="system_program", desc="System program")]
+    #[default_optional_accounts]
+    Print(PrintArgs),
+
+    /// Closes the metadata accounts of an asset whose tokens were burned.
+    /// 
+    /// The mint must have a supply of zero and no active mint authority (other than its
+    /// edition account). The metadata, edition and token record accounts are closed and
+    /// their rent is sent to the destination account. Requires the update authority.
+    ///
+    /// Closing a print edition does not update the supply of its master edition.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, optional, writable, name="edition", desc="Edition account (master edition or print edition)")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, optional, name="token", desc="Token account of the token record")]
+    #[account(4, optional, writable, name="token_record", desc="Token record account")]
+    #[account(5, signer, name="authority", desc="Update authority")]
+    #[account(6, writable, name="destination", desc="Account receiving the rent of the closed accounts")]
+    #[default_optional_accounts]
+    CloseAccounts(CloseAccountsArgs),
+
+    /// Resizes a metadata account to the size of its current contents.
+    ///
+    /// Metadata accounts are created with the maximum size of their variable length
+    /// fields; resizing the account removes the unused space and sends the excess rent
+    /// to the destination account. Requires the update authority.
+    ///
+    /// A resized account grows again when an update requires more space, in which case
+    /// the account must be funded to remain rent exempt.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="authority", desc="Update authority")]
+    #[account(2, writable, name="destination", desc="Account receiving the excess rent")]
+    Resize(ResizeArgs),
+
+    /// Locks a `ProgrammableNonFungible` asset on behalf of its holder.
+    ///
+    /// A holder lock blocks `Transfer`, `Delegate` and `Burn` until it is removed by the
+    /// holder through `SelfUnlock`, which is only possible after the cool-down set when
+    /// locking has elapsed.
+    #[account(0, signer, name="authority", desc="Token owner")]
+    #[account(1, name="token", desc="Token account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, writable, name="token_record", desc="Token record account")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SelfLock(SelfLockArgs),
+
+    /// Removes the holder lock of a `ProgrammableNonFungible` asset once its cool-down
+    /// has elapsed.
+    #[account(0, signer, name="authority", desc="Token owner")]
+    #[account(1, name="token", desc="Token account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, writable, name="token_record", desc="Token record account")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SelfUnlock(SelfUnlockArgs),
+
+    /// Unverifies a creator or the collection of an asset.
+    /// 
+    /// The creator is unverified by the creator itself. The collection is unverified by the update
+    /// authority of the collection or a `Collection` delegate, in which case the delegate record
+    /// is required; the size of sized collections is decremented. When the collection parent is
+    /// burned, the update authority of the asset can unverify it.
+    #[account(0, signer, name="authority", desc="Creator to unverify, collection update authority or delegate")]
+    #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, optional, name="collection_mint", desc="Mint of the Collection")]
+    #[account(4, optional, writable, name="collection_metadata", desc="Metadata Account of the Collection")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(7, optional, writable, name="creators_overflow", desc="Creators overflow account, when the creator is stored on the creators overflow")]
+    #[default_optional_accounts]
+    Unverify(VerificationArgs),
+
+    /// Transfers tokens out of the token-owned escrow of an asset.
+    /// 
+    /// Escrows with a `TokenOwner` authority are operated by the holder of the asset or by an
+    /// `Escrow`, `Utility` or `Transfer` delegate of the asset, in which case the token record
+    /// is required. Escrows with a `Creator` authority are operated by the creator. When the
+    /// escrow has a ledger, it must be provided so the amount withdrawn is recorded.
+    #[account(0, name="escrow", desc="Token-owned escrow account")]
+    #[account(1, name="metadata", desc="Metadata account of the asset the escrow is attached to")]
+    #[account(2, name="escrow_mint", desc="Mint account of the asset the escrow is attached to")]
+    #[account(3, name="escrow_token", desc="Token account holding the asset the escrow is attached to")]
+    #[account(4, optional, name="escrow_token_record", desc="Token record account of the escrow token (required when the authority is a delegate)")]
+    #[account(5, name="attribute_mint", desc="Mint account of the tokens being transferred")]
+    #[account(6, writable, name="attribute_src", desc="Token account of the escrow holding the tokens")]
+    #[account(7, writable, name="attribute_dst", desc="Destination token account")]
+    #[account(8, signer, name="authority", desc="Escrow authority (holder, creator or delegate)")]
+    #[account(9, signer, writable, name="payer", desc="Payer")]
+    #[account(10, name="system_program", desc="System program")]
+    #[account(11, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(12, name="spl_token_program", desc="SPL Token Program")]
+    #[account(13, name="spl_ata_program", desc="SPL Associated Token Account program")]
+    #[account(14, optional, writable, name="escrow_ledger", desc="Ledger account of the escrow (required when the escrow has a ledger)")]
+    #[default_optional_accounts]
+    EscrowTransferOut(EscrowTransferOutArgs),
+
+    /// Deposits tokens into the token-owned escrow of an asset, recording the amount held by
+    /// the escrow in its ledger.
+    ///
+    /// The escrow token account of the mint is created if it does not exist, as is the ledger
+    /// on the first deposit. Deposits are made from a token account of the depositor, who must
+    /// be its owner or delegate.
+    #[account(0, name="escrow", desc="Token-owned escrow account")]
+    #[account(1, name="metadata", desc="Metadata account of the asset the escrow is attached to")]
+    #[account(2, name="escrow_mint", desc="Mint account of the asset the escrow is attached to")]
+    #[account(3, writable, name="escrow_ledger", desc="Ledger account of the escrow")]
+    #[account(4, name="attribute_mint", desc="Mint account of the tokens being deposited")]
+    #[account(5, writable, name="attribute_src", desc="Source token account")]
+    #[account(6, writable, name="attribute_dst", desc="Token account of the escrow for the tokens being deposited")]
+    #[account(7, signer, name="depositor", desc="Owner or delegate of the source token account")]
+    #[account(8, signer, writable, name="payer", desc="Payer")]
+    #[account(9, name="system_program", desc="System program")]
+    #[account(10, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(11, name="spl_token_program", desc="SPL Token Program")]
+    #[account(12, name="spl_ata_program", desc="SPL Associated Token Account program")]
+    #[default_optional_accounts]
+    EscrowDeposit(EscrowDepositArgs),
+
+    /// Proposes a new rule set for a programmable asset.
+    /// 
+    /// The rule set of an existing asset cannot be set through `Update`: it is proposed by the
+    /// update authority and can only be committed once the delay stored on the proposal account
+    /// has elapsed. The delay can be increased (but never decreased) by a proposal.
+    #[account(0, writable, name="rule_set_proposal", desc="Rule set proposal account (pda of ['metadata', program id, mint id, 'rule_set_proposal'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    ProposeRuleSet(ProposeRuleSetArgs),
+
+    /// Commits the proposed rule set of a programmable asset once its delay has elapsed.
+    #[account(0, writable, name="rule_set_proposal", desc="Rule set proposal account (pda of ['metadata', program id, mint id, 'rule_set_proposal'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, name="token", desc="Token account")]
+    #[account(4, signer, name="authority", desc="Update authority")]
+    CommitRuleSet(CommitRuleSetArgs),
+
+    /// Pauses transfers, token delegations and burns of the verified items of a collection.
+    /// 
+    /// The pause is set by the update authority of the collection parent for a limited duration
+    /// (capped by the program); it can be lifted earlier with `UnpauseCollection`. A pause cannot
+    /// be extended, and the collection can only be paused again once a cooldown has elapsed
+    /// since the end of the previous pause.
+    ///
+    /// The pause applies to instructions that include the collection pause account.
+    #[account(0, writable, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause'])")]
+    #[account(1, name="collection_mint", desc="Mint of the collection")]
+    #[account(2, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(3, signer, name="authority", desc="Update authority of the collection")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    PauseCollection(PauseCollectionArgs),
+
+    /// Lifts the pause of a collection.
+    #[account(0, writable, name="collection_pause", desc="Collection pause account (pda of ['metadata', program id, collection mint id, 'collection_pause'])")]
+    #[account(1, name="collection_mint", desc="Mint of the collection")]
+    #[account(2, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(3, signer, name="authority", desc="Update authority of the collection")]
+    UnpauseCollection(UnpauseCollectionArgs),
+
+    /// Creates a threshold (M-of-N) authority config.
+    /// 
+    /// The authority config can be set as the update authority (or a creator) of an asset, in
+    /// which case `Update` and `VerifyV2` require the approval of `threshold` of its signers.
+    #[account(0, writable, name="authority_config", desc="Authority config account (pda of ['metadata', program id, base, 'authority_config'])")]
+    #[account(1, signer, name="base", desc="Base address of the authority config")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="system_program", desc="System program")]
+    CreateAuthorityConfig(CreateAuthorityConfigArgs),
+
+    /// Transfers the fees accumulated in the fee vault to a recipient.
+    /// 
+    /// Fees can only be collected by the protocol authority.
+    #[account(0, signer, name="authority", desc="Protocol authority")]
+    #[account(1, writable, name="fee_vault", desc="Fee vault account (pda of ['metadata', program id, 'fee_vault'])")]
+    #[account(2, writable, name="recipient", desc="Recipient of the fees")]
+    #[account(3, name="system_program", desc="System program")]
+    CollectFees(CollectFeesArgs),
+
+    /// Sets the creators of an asset, storing the creators that do not fit in the metadata
+    /// on a `CreatorsOverflow` account.
+    /// 
+    /// The shares of the creators of both lists must add up to 100. Setting an empty list of
+    /// overflow creators closes the `CreatorsOverflow` account. Prints cannot be created from
+    /// a master edition with overflow creators.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, writable, name="creators_overflow", desc="Creators overflow account (pda of ['metadata', program id, mint id, 'creators_overflow'])")]
+    #[account(3, signer, name="authority", desc="Update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetCreatorsOverflow(SetCreatorsOverflowArgs),
+
+    /// Pays the royalties of an asset, splitting the amount between its verified creators
+    /// proportionally to their shares.
+    /// 
+    /// The amount is paid in lamports or, when the payment mint is provided, in SPL tokens. The
+    /// recipients are passed as remaining accounts, one per verified creator in the order of the
+    /// creators: the creator wallet for lamport payments or its token account of the payment mint.
+    #[account(0, signer, writable, name="payer", desc="Payer of the royalties")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, optional, name="creators_overflow", desc="Creators overflow account of the metadata the royalties are read from")]
+    #[account(3, optional, name="collection_metadata", desc="Metadata account of the collection, when the asset inherits its royalties")]
+    #[account(4, name="system_program", desc="System program")]
+    #[account(5, optional, writable, name="payer_token", desc="Token account of the payer, when paying in SPL tokens")]
+    #[account(6, optional, name="payment_mint", desc="Mint of the payment, when paying in SPL tokens")]
+    #[account(7, optional, name="spl_token_program", desc="SPL Token program, when paying in SPL tokens")]
+    #[default_optional_accounts]
+    PayRoyalties(PayRoyaltiesArgs),
+
+    /// Settles the primary sale of an asset: the proceeds are split between its verified
+    /// creators proportionally to their shares and `primary_sale_happened` is set.
+    /// 
+    /// The settlement is signed by the holder or a `Sale` delegate. The proceeds are paid in
+    /// lamports or, when the payment mint is provided, in SPL tokens to the recipients passed as
+    /// remaining accounts, one per verified creator in the order of the creators.
+    #[account(0, signer, name="authority", desc="Holder or Sale delegate of the asset")]
+    #[account(1, signer, writable, name="payer", desc="Buyer paying the sale proceeds")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, name="token", desc="Token account of the holder")]
+    #[account(5, optional, name="token_record", desc="Token record account")]
+    #[account(6, optional, name="creators_overflow", desc="Creators overflow account")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, optional, writable, name="payer_token", desc="Token account of the payer, when paying in SPL tokens")]
+    #[account(9, optional, name="payment_mint", desc="Mint of the payment, when paying in SPL tokens")]
+    #[account(10, optional, name="spl_token_program", desc="SPL Token program, when paying in SPL tokens")]
+    #[default_optional_accounts]
+    SettlePrimarySale(SettlePrimarySaleArgs),
+
+    /// Closes an expired collection membership, unverifying the collection of the asset and
+    /// decrementing the size of sized collections.
+    /// 
+    /// The instruction is permissionless: the rent of the membership account is transferred to
+    /// the payer as an incentive to clean up expired memberships.
+    #[account(0, signer, writable, name="payer", desc="Payer, receives the rent of the membership account")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, writable, name="collection_membership", desc="Collection membership account (pda of ['metadata', program id, mint id, 'collection_membership'])")]
+    #[account(3, writable, name="collection_metadata", desc="Metadata account of the collection")]
+    ExpireCollectionMembership(ExpireCollectionMembershipArgs),
+
+    /// Sets whether the holder of a `ProgrammableNonFungible` asset allows the `CollectionFreeze`
+    /// delegate of its collection to freeze the token. The holder cannot opt out while the
+    /// token is frozen.
+    #[account(0, signer, name="authority", desc="Token owner")]
+    #[account(1, name="token", desc="Token account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, writable, name="token_record", desc="Token record account")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetCollectionFreezeOptIn(SetCollectionFreezeOptInArgs),
+
+    /// Freezes a verified item of a collection on behalf of the `CollectionFreeze` delegate of
+    /// the collection, which is approved by the collection update authority.
+    /// 
+    /// Only `ProgrammableNonFungible` items whose holder opted in can be frozen; a frozen item
+    /// cannot be transferred, delegated or burned until it is thawed.
+    #[account(0, signer, name="authority", desc="Collection freeze delegate")]
+    #[account(1, name="delegate_record", desc="Delegate record account (pda of ['metadata', program id, collection mint id, 'collection_freeze_delegate', collection update authority id, delegate id])")]
+    #[account(2, name="collection_mint", desc="Mint of the collection")]
+    #[account(3, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, name="mint", desc="Mint account")]
+    #[account(6, name="token", desc="Token account")]
+    #[account(7, writable, name="token_record", desc="Token record account")]
+    FreezeCollectionItem(FreezeCollectionItemArgs),
+
+    /// Thaws an item of a collection frozen by the `CollectionFreeze` delegate of the collection.
+    #[account(0, signer, name="authority", desc="Collection freeze delegate")]
+    #[account(1, name="delegate_record", desc="Delegate record account (pda of ['metadata', program id, collection mint id, 'collection_freeze_delegate', collection update authority id, delegate id])")]
+    #[account(2, name="collection_mint", desc="Mint of the collection")]
+    #[account(3, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, name="mint", desc="Mint account")]
+    #[account(6, name="token", desc="Token account")]
+    #[account(7, writable, name="token_record", desc="Token record account")]
+    ThawCollectionItem(ThawCollectionItemArgs),
+
+    /// Records a statement signed by the holder of an asset (e.g., the acceptance of a license).
+    ///
+    /// The attestation stores the hash of the message and the slot at which it was signed;
+    /// approving an existing attestation replaces its message hash.
+    #[account(0, writable, name="attestation", desc="Attestation account (pda of ['metadata', program id, mint id, 'attest', holder id, topic hash])")]
+    #[account(1, signer, name="holder", desc="Holder of the asset")]
+    #[account(2, name="token", desc="Token account of the holder")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    ApproveAttestation(ApproveAttestationArgs),
+
+    /// Revokes an attestation signed by the holder, closing the attestation account.
+    #[account(0, writable, name="attestation", desc="Attestation account")]
+    #[account(1, signer, name="holder", desc="Holder that signed the attestation")]
+    #[account(2, writable, name="destination", desc="Destination account for the rent")]
+    RevokeAttestation(RevokeAttestationArgs),
+
+    /// Creates the metadata account of an existing mint without the signature of the mint
+    /// authority on the transaction.
+    ///
+    /// The creation is authorized by either:
+    ///   1. the freeze authority of the mint signing the transaction; or
+    ///   2. an Ed25519 program instruction, immediately before this instruction, with the
+    ///      signature of the mint authority over `create_existing_mint_metadata_message`. When
+    ///      the mint authority is an SPL Token multisig, the message must be signed by the
+    ///      required number of its signers. The message is bound to the metadata account and
+    ///      is only accepted until its expiry slot.
+    ///
+    /// The token standard is set from the decimals of the mint.
+    #[account(0, writable, name="metadata", desc="Unallocated metadata account with address as pda of ['metadata', program id, mint id]")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="authority", desc="Mint authority (signed message) or freeze authority (signer)")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="update_authority", desc="Update authority for the metadata account")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[args(authority_as_signer: bool)]
+    CreateExistingMintMetadata(CreateExistingMintMetadataArgs),
+
+    /// Sets whether the verified items of a collection use the legacy layout, which stores
+    /// their programmable config on a `ProgrammableConfigExtension` account so parsers unaware
+    /// of the programmable config can read their metadata.
+    ///
+    /// The layout of each item is switched by `SyncLayout`.
+    #[account(0, writable, name="legacy_layout", desc="Legacy layout account (pda of ['metadata', program id, collection mint id, 'legacy_layout'])")]
+    #[account(1, name="collection_mint", desc="Mint of the collection")]
+    #[account(2, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(3, signer, name="authority", desc="Update authority of the collection")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetLegacyLayout(SetLegacyLayoutArgs),
+
+    /// Moves the programmable config of an asset to its `ProgrammableConfigExtension` account
+    /// when its verified collection uses the legacy layout, or back to the metadata (closing
+    /// the extension) otherwise. Anyone can sync the layout of an asset.
+    ///
+    /// While an asset uses the legacy layout, instructions that read its programmable config
+    /// expect the extension as the first remaining account, and its rule set cannot change.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, writable, name="programmable_config_extension", desc="Programmable config extension account (pda of ['metadata', program id, mint id, 'programmable_config'])")]
+    #[account(2, name="legacy_layout", desc="Legacy layout account of the collection of the asset")]
+    #[account(3, signer, writable, name="payer", desc="Payer (receives the rent of the extension when it is closed)")]
+    #[account(4, name="system_program", desc="System program")]
+    SyncLayout(SyncLayoutArgs),
+
+    /// Closes a stale token record: the record of a token account that was closed or no
+    /// longer holds the asset. Anyone can close a stale token record.
+    ///
+    /// The rent is refunded to the payer of the token record. Token records created before
+    /// the payer was recorded require the update authority, which sets the destination.
+    #[account(0, writable, name="token_record", desc="Token record account")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="token", desc="Token account of the token record (can be closed)")]
+    #[account(3, writable, name="destination", desc="Payer of the token record or, when no payer is recorded, the account receiving the rent")]
+    #[account(4, optional, name="metadata", desc="Metadata account (token records without a payer)")]
+    #[account(5, optional, signer, name="authority", desc="Update authority (token records without a payer)")]
+    #[default_optional_accounts]
+    CloseTokenRecord(CloseTokenRecordArgs),
+
+    /// Opens the migration window of a collection, starting the grace period (at least seven
+    /// days) after which the collection authority can migrate the items of the collection
+    /// (`Migrate` with `CollectionV1`). An open window cannot be shortened.
+    #[account(0, writable, name="migration_window", desc="Migration window account (pda of ['metadata', program id, collection mint id, 'migration_window'])")]
+    #[account(1, name="collection_mint", desc="Mint of the collection")]
+    #[account(2, name="collection_metadata", desc="Metadata account of the collection")]
+    #[account(3, signer, name="authority", desc="Update authority of the collection")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    OpenMigrationWindow(OpenMigrationWindowArgs),
+
+    /// Opts an asset out of the migration of its collection by the collection authority. The
+    /// holder can opt out until the grace period of the migration window has elapsed.
+    #[account(0, writable, name="migration_opt_out", desc="Migration opt-out account (pda of ['metadata', program id, mint id, 'migration_opt_out'])")]
+    #[account(1, name="migration_window", desc="Migration window account of the collection of the asset")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, name="token", desc="Token account holding the asset")]
+    #[account(5, signer, name="token_owner", desc="Owner of the token account")]
+    #[account(6, signer, writable, name="payer", desc="Payer")]
+    #[account(7, name="system_program", desc="System program")]
+    OptOutMigration(OptOutMigrationArgs),
+
+    /// Sets the token standard of assets created without one, inferring it from their mint
+    /// and edition accounts. Anyone can set the token standard of an asset.
+    ///
+    /// The assets are passed as remaining accounts in `(metadata, mint, edition)` triplets,
+    /// where the metadata is writable and the edition is the edition PDA of the mint (which
+    /// may not exist). Assets that already have a token standard are skipped.
+    BatchSetTokenStandard(BatchSetTokenStandardArgs),
+
+    /// Attests the hash of the off-chain JSON at the URI of an asset, so buyers can detect
+    /// content swapped after the attestation.
+    ///
+    /// The hash is attested by one of the creators of the asset and is cleared when the URI
+    /// changes. The hash of an immutable asset cannot be replaced once attested.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="creator", desc="Creator of the asset")]
+    VerifyContent(VerifyContentArgs),
+
+    /// Sets the rate limit of the uses of an asset: a cooldown between uses and a periodic
+    /// refill of the remaining uses, both measured in slots.
+    ///
+    /// The rate limit is set by the update authority and is enforced by `Use`; the legacy
+    /// `Utilize` instruction cannot be used on rate limited assets. Setting the rate limit
+    /// restarts the refill period.
+    #[account(0, writable, name="uses_v2", desc="Uses v2 account (pda of ['metadata', program id, mint id, 'uses_v2'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetUsesV2(SetUsesV2Args),
+
+    /// Rotates the update authority of an asset in a single instruction.
+    /// 
+    /// The new update authority is set and every metadata delegate listed on the delegate
+    /// registry of the mint is revoked, so delegates approved by the previous authority do not
+    /// survive the rotation. The delegate records of all registry entries must be passed as
+    /// remaining accounts (the instruction fails if one is missing) and are closed to the payer.
+    /// Optionally, verified creators other than the new update authority are unverified.
+    /// 
+    /// A single log line describing the rotation is emitted.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, signer, name="authority", desc="Current update authority")]
+    #[account(3, writable, name="delegate_registry", desc="Delegate registry account (pda of ['metadata', program id, mint id, 'delegate_registry'])")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    RotateAuthorityWithPolicy(RotateAuthorityArgs),
+
+    /// Opens a transfer session, validating the rule set of an asset once for a burst of
+    /// holder transfers of the assets of its collection.
+    /// 
+    /// The asset must be a verified item of a collection. Its rule set is validated for a
+    /// transfer of `amount` from the authority to the destination owner, and the result is
+    /// cached on the transfer session account for at most `MAX_TRANSFER_SESSION_SLOTS` slots.
+    /// Holder transfers from the authority that include the session skip the Token Auth Rules
+    /// CPI when the asset is a verified item of the same collection with the same rule set (and
+    /// revision); other transfers are validated in full. Opening a session replaces any open
+    /// session of the authority.
+    #[account(0, writable, name="transfer_session", desc="Transfer session account (pda of ['metadata', program id, authority, 'transfer_session'])")]
+    #[account(1, signer, name="authority", desc="Holder opening the session")]
+    #[account(2, name="destination_owner", desc="Destination owner of the validated transfer")]
+    #[account(3, name="mint", desc="Mint of the validated asset")]
+    #[account(4, name="metadata", desc="Metadata account of the asset")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    #[account(7, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(8, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[default_optional_accounts]
+    BeginTransferSession(BeginTransferSessionArgs),
+
+    /// Closes a transfer session, refunding the rent to the authority.
+    #[account(0, writable, name="transfer_session", desc="Transfer session account (pda of ['metadata', program id, authority, 'transfer_session'])")]
+    #[account(1, signer, writable, name="authority", desc="Holder that opened the session")]
+    EndTransferSession(EndTransferSessionArgs),
+
+    /// Adds an asset to the stolen asset denylist.
+    /// 
+    /// Assets on the denylist are set by the protocol authority; transfers of verified items
+    /// of collections that enforce the denylist fail while the asset is on it.
+    #[account(0, writable, name="denylisted", desc="Denylisted account (pda of ['metadata', program id, mint id, 'denylisted'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Protocol authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    AddToDenylist(DenylistArgs),
+
+    /// Removes an asset from the stolen asset denylist, returning the rent of the denylisted
+    /// account to the payer that created it.
+    #[account(0, writable, name="denylisted", desc="Denylisted account (pda of ['metadata', program id, mint id, 'denylisted'])")]
+    #[account(1, name="mint", desc="Mint account")]
+    #[account(2, name="metadata", desc="Metadata account")]
+    #[account(3, signer, name="authority", desc="Protocol authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    #[account(6, writable, name="rent_destination", desc="Payer of the denylisted account")]
+    RemoveFromDenylist(DenylistArgs),
+
+    /// Uses an asset, decrementing its remaining uses. This replaces the `Use` instruction,
+    /// whose accounts cannot identify the token record of programmable assets.
+    /// 
+    /// The authority can be the owner of the asset or, for `ProgrammableNonFungible` assets,
+    /// a `Utility` token delegate. A `Use` delegate can also use the asset up to its own
+    /// allowance, which is decremented in its delegate record. When the use method is `Burn` and the asset runs out of
+    /// uses, the token is burned; programmable assets are thawed for the burn and the token
+    /// account is frozen again afterwards.
+    /// 
+    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// it may require additional accounts to validate the rules.
+    /// 
+    /// The `uses_v2` account is required when the uses of the asset are rate limited (see
+    /// `SetUsesV2`).
+    #[account(0, signer, name="authority", desc="Token owner, utility delegate or use delegate")]
+    #[account(1, writable, optional, name="token_record", desc="Token record account (required for programmable assets)")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, writable, name="mint", desc="Mint account")]
+    #[account(4, writable, name="metadata", desc="Metadata account")]
+    #[account(5, optional, name="edition", desc="Edition account")]
+    #[account(6, signer, writable, name="payer", desc="Payer")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="sysvar_instructions", desc="System program")]
+    #[account(9, optional, name="spl_token_program", desc="SPL Token Program")]
+    #[account(10, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(11, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(12, optional, writable, name="delegate_record", desc="Use delegate record account")]
+    #[account(13, optional, writable, name="uses_v2", desc="Uses v2 account (pda of ['metadata', program id, mint id, 'uses_v2'])")]
+    #[default_optional_accounts]
+    UseV2(UseArgs),
+
+    /// Verifies a creator or the collection of an asset. This replaces the `Verify` instruction,
+    /// whose accounts cannot identify the collection of the asset.
+    /// 
+    /// The creator is verified by the creator itself. The collection is verified by the update
+    /// authority of the collection or a `Collection` delegate, in which case the delegate record
+    /// is required; the size of sized collections is incremented.
+    /// 
+    /// Depending on the type of verification (e.g., creator or collection), additional accounts
+    /// are required.
+    /// 
+    /// When the creator or the collection update authority is an authority config, the authority
+    /// is one of its signers: the verification is only applied once enough signers submitted it.
+    /// 
+    /// The collection membership can expire (`CollectionV2`): the expiry is stored on the
+    /// collection membership account, which is created by the payer.
+    #[account(0, signer, name="authority", desc="Creator to verify, collection update authority, delegate or authority config signer")]
+    #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, optional, name="collection_mint", desc="Mint of the Collection")]
+    #[account(4, optional, writable, name="collection_metadata", desc="Metadata Account of the Collection")]
+    #[account(5, optional, name="collection_master_edition", desc="Master Edition Account of the Collection Token")]
+    #[account(6, name="system_program", desc="System program")]
+    #[account(7, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(8, optional, writable, name="authority_config", desc="Authority config account, when the creator or collection update authority is an authority config")]
+    #[account(9, optional, writable, name="creators_overflow", desc="Creators overflow account, when the creator is stored on the creators overflow")]
+    #[account(10, optional, writable, name="collection_membership", desc="Collection membership account (pda of ['metadata', program id, mint id, 'collection_membership']), when the membership expires")]
+    #[account(11, optional, signer, writable, name="payer", desc="Payer of the collection membership account")]
+    #[default_optional_accounts]
+    VerifyV2(VerificationArgs),
 }
 
 pub struct Context<'a, T> {
@@ -749,4 +1593,63 @@ pub struct Context<'a, T> {
 
 pub trait InstructionBuilder {
     fn instruction(&self) -> solana_program::instruction::Instruction;
+
+    /// Returns the instruction preceded by a compute unit limit matching its compute
+    /// budget hint (see `MetadataInstruction::compute_budget_hint`).
+    fn instructions_with_compute_budget(
+        &self,
+        token_standard: TokenStandard,
+        has_rule_set: bool,
+    ) -> Vec<solana_program::instruction::Instruction> {
+        with_compute_budget(self.instruction(), token_standard, has_rule_set)
+    }
+}
+
+/// Asserts that the accounts of an instruction created by a builder match the accounts
+/// expected by the processor (`ACCOUNTS` of the builder) in number, order and signer
+/// and writable flags.
+///
+/// The builder must have all its accounts set by `with_test_accounts`.
+#[cfg(test)]
+pub(crate) fn assert_builder_accounts(
+    instruction: &solana_program::instruction::Instruction,
+    expected: &[(&str, bool, bool)],
+) {
+    assert_eq!(
+        instruction.accounts.len(),
+        expected.len(),
+        "number of accounts does not match the processor"
+    );
+
+    for (index, (account, (name, signer, writable))) in
+        instruction.accounts.iter().zip(expected).enumerate()
+    {
+        assert_eq!(
+            account.pubkey,
+            Pubkey::new_from_array([index as u8 + 1; 32]),
+            "account {name} is not at position {index}"
+        );
+        assert!(
+            !signer || account.is_signer,
+            "account {name} must be a signer"
+        );
+        assert!(
+            !writable || account.is_writable,
+            "account {name} must be writable"
+        );
+    }
 }
+
+/// Default Token Auth Rules program id used by the builders. When the `auth-rules`
+/// feature is disabled, the program id must be provided to the builder.
+#[cfg(feature = "auth-rules")]
+pub const DEFAULT_AUTH_RULES_PROGRAM: Option<Pubkey> = Some(crate::programs::AUTH_RULES_PROGRAM);
+#[cfg(not(feature = "auth-rules"))]
+pub const DEFAULT_AUTH_RULES_PROGRAM: Option<Pubkey> = None;
+
+/// Default SPL Associated Token Account program id used by the builders. When the `ata`
+/// feature is disabled, the program id must be provided to the builder.
+#[cfg(feature = "ata")]
+pub const DEFAULT_SPL_ATA_PROGRAM: Option<Pubkey> = Some(crate::programs::SPL_ATA_PROGRAM);
+#[cfg(not(feature = "ata"))]
+pub const DEFAULT_SPL_ATA_PROGRAM: Option<Pubkey> = None;
@@ -3,7 +3,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use solana_program::instruction::{AccountMeta, Instruction};
 
-use super::{InstructionBuilder, MetadataInstruction};
+use super::{InstructionBuilder, MetadataInstruction, DEFAULT_AUTH_RULES_PROGRAM};
 use crate::processor::AuthorizationData;
 
 #[repr(C)]
@@ -26,6 +26,47 @@ pub enum UnlockArgs {
     },
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SelfLockArgs {
+    V1 {
+        /// Number of seconds that must elapse before the holder can remove the lock.
+        cooldown: u64,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SelfUnlockArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetCollectionFreezeOptInArgs {
+    V1 {
+        /// Whether the `CollectionFreeze` delegate of the collection can freeze the token.
+        opt_in: bool,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum FreezeCollectionItemArgs {
+    V1,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ThawCollectionItemArgs {
+    V1,
+}
+
 /// Locks an asset. For non-programmable assets, this will also freeze the token account.
 ///
 /// # Accounts:
@@ -43,6 +84,7 @@ pub enum UnlockArgs {
 ///   10. `[optional]` SPL Token Program
 ///   11. `[optional]` Token Authorization Rules program
 ///   12. `[optional]` Token Authorization Rules account
+///   13. `[optional, writable]` Collection items delegate record account
 impl InstructionBuilder for super::builders::Lock {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let mut accounts = vec![
@@ -65,15 +107,26 @@ impl InstructionBuilder for super::builders::Lock {
 
         // Optional authorization rules accounts
         if let Some(rules) = &self.authorization_rules {
-            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ));
             accounts.push(AccountMeta::new_readonly(*rules, false));
         } else {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
         }
 
+        if let Some(collection_delegate_record) = self.collection_delegate_record {
+            accounts.push(AccountMeta::new(collection_delegate_record, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Lock(self.args.clone())
                 .try_to_vec()
@@ -99,6 +152,7 @@ impl InstructionBuilder for super::builders::Lock {
 ///   10. `[optional]` SPL Token Program
 ///   11. `[optional]` Token Authorization Rules program
 ///   12. `[optional]` Token Authorization Rules account
+///   13. `[optional, writable]` Collection items delegate record account
 impl InstructionBuilder for super::builders::Unlock {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let mut accounts = vec![
@@ -121,15 +175,26 @@ impl InstructionBuilder for super::builders::Unlock {
 
         // Optional authorization rules accounts
         if let Some(rules) = &self.authorization_rules {
-            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program
+                    .or(DEFAULT_AUTH_RULES_PROGRAM)
+                    .unwrap_or(crate::ID),
+                false,
+            ));
             accounts.push(AccountMeta::new_readonly(*rules, false));
         } else {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
         }
 
+        if let Some(collection_delegate_record) = self.collection_delegate_record {
+            accounts.push(AccountMeta::new(collection_delegate_record, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
         Instruction {
-            program_id: crate::ID,
+            program_id: self.program_id,
             accounts,
             data: MetadataInstruction::Unlock(self.args.clone())
                 .try_to_vec()
@@ -137,3 +202,236 @@ impl InstructionBuilder for super::builders::Unlock {
         }
     }
 }
+
+/// Locks a programmable asset on behalf of its holder.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Token owner
+///   1. `[]` Token account
+///   2. `[]` Mint account
+///   3. `[writable]` Token record account
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SelfLock {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.token_record, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SelfLock(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Removes the holder lock of a programmable asset.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Token owner
+///   1. `[]` Token account
+///   2. `[]` Mint account
+///   3. `[writable]` Token record account
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SelfUnlock {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.token_record, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SelfUnlock(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Sets whether the holder of a programmable asset allows collection freezes.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Token owner
+///   1. `[]` Token account
+///   2. `[]` Mint account
+///   3. `[writable]` Token record account
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SetCollectionFreezeOptIn {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.token_record, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetCollectionFreezeOptIn(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Freezes an item of a collection on behalf of the `CollectionFreeze` delegate.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Collection freeze delegate
+///   1. `[]` Delegate record account
+///   2. `[]` Mint of the collection
+///   3. `[]` Metadata account of the collection
+///   4. `[]` Metadata account
+///   5. `[]` Mint account
+///   6. `[]` Token account
+///   7. `[writable]` Token record account
+impl InstructionBuilder for super::builders::FreezeCollectionItem {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.delegate_record, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new(self.token_record, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::FreezeCollectionItem(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Thaws an item of a collection on behalf of the `CollectionFreeze` delegate.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Collection freeze delegate
+///   1. `[]` Delegate record account
+///   2. `[]` Mint of the collection
+///   3. `[]` Metadata account of the collection
+///   4. `[]` Metadata account
+///   5. `[]` Mint account
+///   6. `[]` Token account
+///   7. `[writable]` Token record account
+impl InstructionBuilder for super::builders::ThawCollectionItem {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.delegate_record, false),
+            AccountMeta::new_readonly(self.collection_mint, false),
+            AccountMeta::new_readonly(self.collection_metadata, false),
+            AccountMeta::new_readonly(self.metadata, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(self.token, false),
+            AccountMeta::new(self.token_record, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::ThawCollectionItem(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{
+            FreezeCollectionItem, FreezeCollectionItemBuilder, Lock, LockBuilder, SelfLock,
+            SelfLockBuilder, SelfUnlock, SelfUnlockBuilder, SetCollectionFreezeOptIn,
+            SetCollectionFreezeOptInBuilder, ThawCollectionItem, ThawCollectionItemBuilder, Unlock,
+            UnlockBuilder,
+        },
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = LockBuilder::new()
+            .with_test_accounts()
+            .build(LockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Lock::ACCOUNTS);
+
+        let instruction = UnlockBuilder::new()
+            .with_test_accounts()
+            .build(UnlockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, Unlock::ACCOUNTS);
+
+        let instruction = SelfLockBuilder::new()
+            .with_test_accounts()
+            .build(SelfLockArgs::V1 { cooldown: 0 })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SelfLock::ACCOUNTS);
+
+        let instruction = SelfUnlockBuilder::new()
+            .with_test_accounts()
+            .build(SelfUnlockArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SelfUnlock::ACCOUNTS);
+
+        let instruction = SetCollectionFreezeOptInBuilder::new()
+            .with_test_accounts()
+            .build(SetCollectionFreezeOptInArgs::V1 { opt_in: true })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetCollectionFreezeOptIn::ACCOUNTS);
+
+        let instruction = FreezeCollectionItemBuilder::new()
+            .with_test_accounts()
+            .build(FreezeCollectionItemArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, FreezeCollectionItem::ACCOUNTS);
+
+        let instruction = ThawCollectionItemBuilder::new()
+            .with_test_accounts()
+            .build(ThawCollectionItemArgs::V1)
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, ThawCollectionItem::ACCOUNTS);
+    }
+}
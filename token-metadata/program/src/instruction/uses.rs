@@ -6,7 +6,8 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-use crate::{instruction::MetadataInstruction, processor::AuthorizationData};
+use super::InstructionBuilder;
+use crate::{instruction::MetadataInstruction, processor::AuthorizationData, programs};
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -32,6 +33,20 @@ pub enum UseArgs {
     },
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetUsesV2Args {
+    V1 {
+        /// Minimum number of slots between two uses (`0` for no cooldown).
+        cooldown_slots: u64,
+        /// Number of slots between two refills (`0` for no refill).
+        refill_period_slots: u64,
+        /// Number of uses added back at each refill.
+        refill_amount: u64,
+    },
+}
+
 ///# Approve Use Authority
 ///
 ///Approve another account to call [utilize] on this NFT
@@ -77,8 +92,8 @@ pub fn approve_use_authority(
             AccountMeta::new_readonly(metadata, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(burner, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
         ],
         data: MetadataInstruction::ApproveUseAuthority(ApproveUseAuthorityArgs { number_of_uses })
             .try_to_vec()
@@ -121,8 +136,8 @@ pub fn revoke_use_authority(
             AccountMeta::new(owner_token_account, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(metadata, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+            AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
         ],
         data: MetadataInstruction::RevokeUseAuthority
             .try_to_vec()
@@ -148,6 +163,7 @@ pub fn revoke_use_authority(
 ///   7. `[]` System program
 ///   8. Optional `[]` Rent info
 ///   9. Optional `[writable]` Use Authority Record PDA If present the program Assumes a delegated use authority
+#[cfg(feature = "ata")]
 #[allow(clippy::too_many_arguments)]
 pub fn utilize(
     program_id: Pubkey,
@@ -166,9 +182,9 @@ pub fn utilize(
         AccountMeta::new(mint, false),
         AccountMeta::new(use_authority, true),
         AccountMeta::new_readonly(owner, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(programs::SPL_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SPL_ATA_PROGRAM, false),
+        AccountMeta::new_readonly(programs::SYSTEM_PROGRAM, false),
     ];
     if let Some(use_authority_record_pda) = use_authority_record_pda {
         accounts.push(AccountMeta::new(use_authority_record_pda, false));
@@ -186,3 +202,126 @@ pub fn utilize(
             .unwrap(),
     }
 }
+
+/// Uses an asset, decrementing its remaining uses.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Token owner, utility delegate or use delegate
+///   1. `[optional, writable]` Token record account
+///   2. `[writable]` Token account
+///   3. `[writable]` Mint account
+///   4. `[writable]` Metadata account
+///   5. `[optional]` Edition account
+///   6. `[signer, writable]` Payer
+///   7. `[]` System Program
+///   8. `[]` Instructions sysvar account
+///   9. `[optional]` SPL Token Program
+///   10. `[optional]` Token Authorization Rules program
+///   11. `[optional]` Token Authorization Rules account
+///   12. `[optional, writable]` Use delegate record account
+///   13. `[optional, writable]` Uses v2 account
+impl InstructionBuilder for super::builders::UseV2 {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            if let Some(token_record) = self.token_record {
+                AccountMeta::new(token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new(self.token, false),
+            AccountMeta::new(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.edition.unwrap_or(crate::ID), false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules_program.unwrap_or(crate::ID), false),
+            AccountMeta::new_readonly(self.authorization_rules.unwrap_or(crate::ID), false),
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(uses_v2) = self.uses_v2 {
+                AccountMeta::new(uses_v2, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::UseV2(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Sets the rate limit of the uses of an asset. The `UsesV2` account is created if it
+/// does not exist.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Uses v2 account
+///   1. `[]` Mint account
+///   2. `[writable]` Metadata account
+///   3. `[signer]` Update authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System Program
+impl InstructionBuilder for super::builders::SetUsesV2 {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.uses_v2, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: MetadataInstruction::SetUsesV2(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{
+        assert_builder_accounts,
+        builders::{SetUsesV2, SetUsesV2Builder, UseV2, UseV2Builder},
+    };
+
+    #[test]
+    fn builder_accounts() {
+        let instruction = UseV2Builder::new()
+            .with_test_accounts()
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, UseV2::ACCOUNTS);
+
+        let instruction = SetUsesV2Builder::new()
+            .with_test_accounts()
+            .build(SetUsesV2Args::V1 {
+                cooldown_slots: 10,
+                refill_period_slots: 100,
+                refill_amount: 1,
+            })
+            .unwrap()
+            .instruction();
+        assert_builder_accounts(&instruction, SetUsesV2::ACCOUNTS);
+    }
+}
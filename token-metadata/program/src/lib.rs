@@ -3,6 +3,8 @@
 //! The program attach additional data to Fungible or Non-Fungible Tokens on Solana.
 
 pub mod assertions;
+#[cfg(feature = "client")]
+pub mod client;
 
 // (Re-)Declare modules to maintain API compatibility.
 
@@ -21,6 +23,8 @@ pub mod error;
 pub mod instruction;
 pub mod pda;
 pub mod processor;
+pub mod programs;
+pub mod return_data;
 pub mod state;
 pub mod utils;
 
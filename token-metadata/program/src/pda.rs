@@ -15,69 +15,461 @@ pub const BURN: &str = "burn";
 
 pub const COLLECTION_AUTHORITY: &str = "collection_authority";
 
+pub const FLAGGED: &str = "flagged";
+
+pub const DENYLISTED: &str = "denylisted";
+
+pub const EXTENSION: &str = "extension";
+
+pub const TOMBSTONE: &str = "tombstone";
+
+pub const DELEGATE_REGISTRY: &str = "delegate_registry";
+
+pub const DEFAULT_RULE_SETS: &str = "default_rule_sets";
+
+pub const COLLECTION_ITEMS_DELEGATE: &str = "collection_items_delegate";
+
+pub const CONTENT_RATING: &str = "content_rating";
+
+pub const RULE_SET_PROPOSAL: &str = "rule_set_proposal";
+
+pub const COLLECTION_PAUSE: &str = "collection_pause";
+
+pub const AUTHORITY_CONFIG: &str = "authority_config";
+
+pub const FEE_VAULT: &str = "fee_vault";
+
+pub const CREATORS_OVERFLOW: &str = "creators_overflow";
+
+pub const COLLECTION_MEMBERSHIP: &str = "collection_membership";
+
+pub const ATTESTATION: &str = "attest";
+
+pub const LEGACY_LAYOUT: &str = "legacy_layout";
+
+pub const PROGRAMMABLE_CONFIG: &str = "programmable_config";
+
+pub const MIGRATION_WINDOW: &str = "migration_window";
+
+pub const MIGRATION_OPT_OUT: &str = "migration_opt_out";
+
+pub const USES_V2: &str = "uses_v2";
+
+pub const TRANSFER_SESSION: &str = "transfer_session";
+
+/// Program the PDAs are derived for.
+///
+/// The free `find_*` functions derive the PDAs of the token metadata program (`crate::ID`);
+/// a `ProgramContext` allows deriving the same PDAs for a deployment of the program under
+/// a different program id (e.g., forks, localnet or private clusters).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramContext {
+    pub program_id: Pubkey,
+}
+
+impl Default for ProgramContext {
+    fn default() -> Self {
+        Self {
+            program_id: crate::ID,
+        }
+    }
+}
+
+impl ProgramContext {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    pub fn find_edition_account(&self, mint: &Pubkey, edition_number: String) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                EDITION.as_bytes(),
+                edition_number.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_master_edition_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_metadata_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), self.program_id.as_ref(), mint.as_ref()],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_use_authority_account(&self, mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                USER.as_bytes(),
+                authority.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_collection_authority_account(
+        &self,
+        mint: &Pubkey,
+        authority: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                COLLECTION_AUTHORITY.as_bytes(),
+                authority.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_program_as_burner_account(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), self.program_id.as_ref(), BURN.as_bytes()],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_metadata_delegate_record_account(
+        &self,
+        mint: &Pubkey,
+        role: MetadataDelegateRole,
+        update_authority: &Pubkey,
+        delegate: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                role.to_string().as_bytes(),
+                update_authority.as_ref(),
+                delegate.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_token_record_account(&self, mint: &Pubkey, token: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                TOKEN_RECORD_SEED.as_bytes(),
+                token.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_flagged_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                FLAGGED.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_denylisted_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                DENYLISTED.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_master_edition_extension_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                EDITION.as_bytes(),
+                EXTENSION.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_tombstone_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                TOMBSTONE.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_delegate_registry_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                DELEGATE_REGISTRY.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_default_rule_sets_account(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                DEFAULT_RULE_SETS.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_collection_items_delegate_record_account(
+        &self,
+        collection_mint: &Pubkey,
+        holder: &Pubkey,
+        delegate: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                collection_mint.as_ref(),
+                COLLECTION_ITEMS_DELEGATE.as_bytes(),
+                holder.as_ref(),
+                delegate.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_content_rating_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                CONTENT_RATING.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_rule_set_proposal_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                RULE_SET_PROPOSAL.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_collection_pause_account(&self, collection_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                collection_mint.as_ref(),
+                COLLECTION_PAUSE.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_authority_config_account(&self, base: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                base.as_ref(),
+                AUTHORITY_CONFIG.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_fee_vault_account(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                FEE_VAULT.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_creators_overflow_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                CREATORS_OVERFLOW.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_collection_membership_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                COLLECTION_MEMBERSHIP.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_attestation_account(
+        &self,
+        mint: &Pubkey,
+        holder: &Pubkey,
+        topic_hash: &[u8; 32],
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                ATTESTATION.as_bytes(),
+                holder.as_ref(),
+                topic_hash,
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_legacy_layout_account(&self, collection_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                collection_mint.as_ref(),
+                LEGACY_LAYOUT.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_programmable_config_extension_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                PROGRAMMABLE_CONFIG.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_migration_window_account(&self, collection_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                collection_mint.as_ref(),
+                MIGRATION_WINDOW.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_migration_opt_out_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                MIGRATION_OPT_OUT.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_uses_v2_account(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                mint.as_ref(),
+                USES_V2.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn find_transfer_session_account(&self, authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                self.program_id.as_ref(),
+                authority.as_ref(),
+                TRANSFER_SESSION.as_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+}
+
 pub fn find_edition_account(mint: &Pubkey, edition_number: String) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            EDITION.as_bytes(),
-            edition_number.as_bytes(),
-        ],
-        &crate::id(),
-    )
+    ProgramContext::default().find_edition_account(mint, edition_number)
 }
 
 pub fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            EDITION.as_bytes(),
-        ],
-        &crate::id(),
-    )
+    ProgramContext::default().find_master_edition_account(mint)
 }
 
 pub fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[PREFIX.as_bytes(), crate::id().as_ref(), mint.as_ref()],
-        &crate::id(),
-    )
+    ProgramContext::default().find_metadata_account(mint)
 }
 
 pub fn find_use_authority_account(mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            USER.as_bytes(),
-            authority.as_ref(),
-        ],
-        &crate::id(),
-    )
+    ProgramContext::default().find_use_authority_account(mint, authority)
 }
 
 pub fn find_collection_authority_account(mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            COLLECTION_AUTHORITY.as_bytes(),
-            authority.as_ref(),
-        ],
-        &crate::id(),
-    )
+    ProgramContext::default().find_collection_authority_account(mint, authority)
 }
 
 pub fn find_program_as_burner_account() -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[PREFIX.as_bytes(), crate::id().as_ref(), BURN.as_bytes()],
-        &crate::id(),
-    )
+    ProgramContext::default().find_program_as_burner_account()
 }
 
 pub fn find_metadata_delegate_record_account(
@@ -86,28 +478,133 @@ pub fn find_metadata_delegate_record_account(
     update_authority: &Pubkey,
     delegate: &Pubkey,
 ) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            role.to_string().as_bytes(),
-            update_authority.as_ref(),
-            delegate.as_ref(),
-        ],
-        &crate::id(),
+    ProgramContext::default().find_metadata_delegate_record_account(
+        mint,
+        role,
+        update_authority,
+        delegate,
     )
 }
 
 pub fn find_token_record_account(mint: &Pubkey, token: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            PREFIX.as_bytes(),
-            crate::id().as_ref(),
-            mint.as_ref(),
-            TOKEN_RECORD_SEED.as_bytes(),
-            token.as_ref(),
-        ],
-        &crate::id(),
+    ProgramContext::default().find_token_record_account(mint, token)
+}
+
+pub fn find_flagged_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_flagged_account(mint)
+}
+
+pub fn find_denylisted_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_denylisted_account(mint)
+}
+
+pub fn find_master_edition_extension_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_master_edition_extension_account(mint)
+}
+
+pub fn find_tombstone_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_tombstone_account(mint)
+}
+
+pub fn find_delegate_registry_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_delegate_registry_account(mint)
+}
+
+pub fn find_default_rule_sets_account() -> (Pubkey, u8) {
+    ProgramContext::default().find_default_rule_sets_account()
+}
+
+pub fn find_collection_items_delegate_record_account(
+    collection_mint: &Pubkey,
+    holder: &Pubkey,
+    delegate: &Pubkey,
+) -> (Pubkey, u8) {
+    ProgramContext::default().find_collection_items_delegate_record_account(
+        collection_mint,
+        holder,
+        delegate,
     )
 }
+
+pub fn find_content_rating_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_content_rating_account(mint)
+}
+
+pub fn find_rule_set_proposal_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_rule_set_proposal_account(mint)
+}
+
+pub fn find_collection_pause_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_collection_pause_account(collection_mint)
+}
+
+pub fn find_authority_config_account(base: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_authority_config_account(base)
+}
+
+pub fn find_fee_vault_account() -> (Pubkey, u8) {
+    ProgramContext::default().find_fee_vault_account()
+}
+
+pub fn find_creators_overflow_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_creators_overflow_account(mint)
+}
+
+pub fn find_collection_membership_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_collection_membership_account(mint)
+}
+
+pub fn find_attestation_account(
+    mint: &Pubkey,
+    holder: &Pubkey,
+    topic_hash: &[u8; 32],
+) -> (Pubkey, u8) {
+    ProgramContext::default().find_attestation_account(mint, holder, topic_hash)
+}
+
+pub fn find_legacy_layout_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_legacy_layout_account(collection_mint)
+}
+
+pub fn find_programmable_config_extension_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_programmable_config_extension_account(mint)
+}
+
+pub fn find_migration_window_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_migration_window_account(collection_mint)
+}
+
+pub fn find_migration_opt_out_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_migration_opt_out_account(mint)
+}
+
+pub fn find_uses_v2_account(mint: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_uses_v2_account(mint)
+}
+
+pub fn find_transfer_session_account(authority: &Pubkey) -> (Pubkey, u8) {
+    ProgramContext::default().find_transfer_session_account(authority)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_with_program_id() {
+        let mint = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            ProgramContext::default().find_metadata_account(&mint),
+            find_metadata_account(&mint)
+        );
+        assert_eq!(
+            ProgramContext::new(program_id).find_metadata_account(&mint),
+            Pubkey::find_program_address(
+                &[PREFIX.as_bytes(), program_id.as_ref(), mint.as_ref()],
+                &program_id,
+            )
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_pack::Pack, pubkey::Pubkey,
+    system_program,
+};
+use spl_token::state::Mint;
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by,
+        metadata::{
+            assert_holding_amount, assert_metadata_derivation, assert_update_authority_is_correct,
+        },
+    },
+    error::MetadataError,
+    instruction::{Archive, ArchiveArgs, Context},
+    pda::{PREFIX, TOMBSTONE},
+    state::{Key, Metadata, TokenMetadataAccount, TokenStandard, Tombstone},
+    utils::close_account_checked,
+};
+
+pub fn archive<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ArchiveArgs,
+) -> ProgramResult {
+    let context = Archive::to_context(accounts)?;
+
+    match args {
+        ArchiveArgs::V1 => archive_v1(program_id, context),
+    }
+}
+
+fn archive_v1(program_id: &Pubkey, ctx: Context<Archive>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.token_owner_info)?;
+    assert_signer(ctx.accounts.update_authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.update_authority_info)?;
+
+    // only non-fungible assets held by a single owner can be archived; programmable
+    // assets are excluded since their token record depends on the metadata
+    let mint = Mint::unpack(&ctx.accounts.mint_info.try_borrow_data()?)?;
+
+    if mint.supply != 1
+        || matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        )
+    {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    assert_holding_amount(
+        program_id,
+        ctx.accounts.token_owner_info,
+        ctx.accounts.metadata_info,
+        &metadata,
+        ctx.accounts.mint_info,
+        ctx.accounts.token_info,
+        1,
+    )?;
+
+    // creates the tombstone
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        TOMBSTONE.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.tombstone_info,
+        &signer_seeds,
+    )?];
+
+    if !ctx.accounts.tombstone_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    signer_seeds.push(bump);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.tombstone_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        Tombstone::size(),
+        &signer_seeds,
+    )?;
+
+    let tombstone = Tombstone {
+        bump: bump[0],
+        mint: *ctx.accounts.mint_info.key,
+        hash: Tombstone::hash_metadata(&metadata.try_to_vec()?),
+        ..Default::default()
+    };
+    tombstone.serialize(&mut *ctx.accounts.tombstone_info.try_borrow_mut_data()?)?;
+
+    // closes the metadata and returns the rent to the token owner
+    close_account_checked(
+        ctx.accounts.metadata_info,
+        ctx.accounts.token_owner_info,
+        Key::MetadataV1,
+    )
+}
@@ -0,0 +1,6 @@
+#![allow(clippy::module_inception)]
+mod archive;
+mod restore;
+
+pub use archive::*;
+pub use restore::*;
@@ -0,0 +1,101 @@
+use borsh::BorshDeserialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, Restore, RestoreArgs},
+    pda::{PREFIX, TOMBSTONE},
+    state::{Key, Metadata, TokenMetadataAccount, Tombstone, MAX_METADATA_LEN},
+    utils::close_account_checked,
+};
+
+pub fn restore<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RestoreArgs,
+) -> ProgramResult {
+    let context = Restore::to_context(accounts)?;
+
+    match args {
+        RestoreArgs::V1 { data } => restore_v1(program_id, context, data),
+    }
+}
+
+fn restore_v1(program_id: &Pubkey, ctx: Context<Restore>, data: Vec<u8>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.tombstone_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.tombstone_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            TOMBSTONE.as_bytes(),
+        ],
+    )?;
+    let metadata_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+    ];
+    let bump = assert_derivation(program_id, ctx.accounts.metadata_info, metadata_seeds)?;
+
+    // account relationships
+
+    let tombstone = Tombstone::from_account_info(ctx.accounts.tombstone_info)?;
+
+    if !tombstone.matches(&data) {
+        return Err(MetadataError::ArchivedDataMismatch.into());
+    }
+
+    let metadata =
+        Metadata::deserialize(&mut data.as_slice()).map_err(|_| MetadataError::DataTypeMismatch)?;
+
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if !ctx.accounts.metadata_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    // recreates the metadata with the archived data
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        MAX_METADATA_LEN.max(data.len()),
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    ctx.accounts.metadata_info.try_borrow_mut_data()?[..data.len()].copy_from_slice(&data);
+
+    // closes the tombstone and returns the rent to the payer
+    close_account_checked(
+        ctx.accounts.tombstone_info,
+        ctx.accounts.payer_info,
+        Key::Tombstone,
+    )
+}
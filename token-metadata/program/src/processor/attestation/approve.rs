@@ -0,0 +1,121 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_owned_by_token_program, metadata::assert_metadata_derivation,
+    },
+    error::MetadataError,
+    instruction::{ApproveAttestation, ApproveAttestationArgs, Context},
+    pda::{ATTESTATION, PREFIX},
+    state::{Attestation, TokenMetadataAccount},
+};
+
+/// Records an attestation signed by the holder of an asset.
+pub fn approve_attestation<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ApproveAttestationArgs,
+) -> ProgramResult {
+    let context = ApproveAttestation::to_context(accounts)?;
+
+    match args {
+        ApproveAttestationArgs::V1 {
+            topic_hash,
+            message_hash,
+        } => approve_attestation_v1(program_id, context, topic_hash, message_hash),
+    }
+}
+
+fn approve_attestation_v1(
+    program_id: &Pubkey,
+    ctx: Context<ApproveAttestation>,
+    topic_hash: [u8; 32],
+    message_hash: [u8; 32],
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.holder_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by(ctx.accounts.token_info, ctx.accounts.mint_info.owner)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
+
+    if !cmp_pubkeys(&token.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // only the current holder of the asset can attest
+    if !cmp_pubkeys(&token.owner, ctx.accounts.holder_info.key) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    if token.amount == 0 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        ATTESTATION.as_bytes(),
+        ctx.accounts.holder_info.key.as_ref(),
+        &topic_hash,
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.attestation_info,
+        &signer_seeds,
+    )?];
+
+    let mut attestation = if ctx.accounts.attestation_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.attestation_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            Attestation::size(),
+            &signer_seeds,
+        )?;
+
+        Attestation {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            holder: *ctx.accounts.holder_info.key,
+            topic_hash,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.attestation_info, program_id)?;
+        Attestation::from_account_info(ctx.accounts.attestation_info)?
+    };
+
+    attestation.message_hash = message_hash;
+    attestation.slot = Clock::get()?.slot;
+    attestation.serialize(&mut *ctx.accounts.attestation_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod approve;
+mod revoke;
+
+pub use approve::*;
+pub use revoke::*;
@@ -0,0 +1,49 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::assert_owned_by,
+    error::MetadataError,
+    instruction::{Context, RevokeAttestation, RevokeAttestationArgs},
+    state::{Attestation, Key, TokenMetadataAccount},
+    utils::close_account_checked,
+};
+
+/// Revokes an attestation, closing the attestation account.
+pub fn revoke_attestation<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RevokeAttestationArgs,
+) -> ProgramResult {
+    let context = RevokeAttestation::to_context(accounts)?;
+
+    match args {
+        RevokeAttestationArgs::V1 => revoke_attestation_v1(program_id, context),
+    }
+}
+
+fn revoke_attestation_v1(program_id: &Pubkey, ctx: Context<RevokeAttestation>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.holder_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.attestation_info, program_id)?;
+
+    // account relationships
+
+    let attestation = Attestation::from_account_info(ctx.accounts.attestation_info)?;
+
+    // the attestation can be revoked by the holder that signed it, even if it no
+    // longer holds the asset
+    if !cmp_pubkeys(&attestation.holder, ctx.accounts.holder_info.key) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    close_account_checked(
+        ctx.accounts.attestation_info,
+        ctx.accounts.destination_info,
+        Key::Attestation,
+    )
+}
@@ -0,0 +1,144 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "auth-rules")]
+use mpl_token_auth_rules::payload::{Payload, PayloadType, SeedsVec};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+#[cfg(not(feature = "auth-rules"))]
+use crate::state::token_auth_payload::{Payload, PayloadType, SeedsVec};
+use crate::state::PayloadKey;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct AuthorizationData {
+    pub payload: Payload,
+}
+
+impl AuthorizationData {
+    pub fn new(payload: Payload) -> Self {
+        Self { payload }
+    }
+
+    pub fn new_empty() -> Self {
+        Self {
+            payload: Payload::new(),
+        }
+    }
+
+    /// Creates the authorization data of a transfer, using the same payload keys
+    /// as the `Transfer` processor.
+    pub fn for_transfer(
+        amount: u64,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+    ) -> Self {
+        Self::new_empty()
+            .with_amount(amount)
+            .with_pubkey(PayloadKey::Source, source)
+            .with_pubkey(PayloadKey::Destination, destination)
+            .with_pubkey(PayloadKey::Authority, authority)
+    }
+
+    /// Creates the authorization data of a delegate, using the same payload keys
+    /// as the `Delegate` processor.
+    pub fn for_delegate(amount: u64, delegate: &Pubkey) -> Self {
+        Self::new_empty()
+            .with_amount(amount)
+            .with_pubkey(PayloadKey::Delegate, delegate)
+    }
+
+    /// Creates the authorization data of an unlock, using the same payload keys
+    /// as the `Unlock` processor.
+    pub fn for_unlock(authority: &Pubkey) -> Self {
+        Self::new_empty().with_pubkey(PayloadKey::Authority, authority)
+    }
+
+    /// Adds the derivation seeds of an account to the payload. This is required
+    /// by rules that check whether an account is a PDA of a program (e.g., the
+    /// `DestinationSeeds` of a transfer to a program-owned account).
+    pub fn with_seeds(mut self, key: PayloadKey, seeds: Vec<Vec<u8>>) -> Self {
+        self.payload
+            .insert(key.to_string(), PayloadType::Seeds(SeedsVec { seeds }));
+        self
+    }
+
+    fn with_amount(mut self, amount: u64) -> Self {
+        self.payload
+            .insert(PayloadKey::Amount.to_string(), PayloadType::Number(amount));
+        self
+    }
+
+    fn with_pubkey(mut self, key: PayloadKey, pubkey: &Pubkey) -> Self {
+        self.payload
+            .insert(key.to_string(), PayloadType::Pubkey(*pubkey));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_payload_keys() {
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let auth_data = AuthorizationData::for_transfer(1, &source, &destination, &authority)
+            .with_seeds(PayloadKey::DestinationSeeds, vec![b"seed".to_vec()]);
+        let payload = &auth_data.payload;
+
+        assert_eq!(
+            payload.get(&PayloadKey::Amount.to_string()),
+            Some(&PayloadType::Number(1))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::Source.to_string()),
+            Some(&PayloadType::Pubkey(source))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::Destination.to_string()),
+            Some(&PayloadType::Pubkey(destination))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::Authority.to_string()),
+            Some(&PayloadType::Pubkey(authority))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::DestinationSeeds.to_string()),
+            Some(&PayloadType::Seeds(SeedsVec {
+                seeds: vec![b"seed".to_vec()]
+            }))
+        );
+    }
+
+    #[test]
+    fn delegate_payload_keys() {
+        let delegate = Pubkey::new_unique();
+        let payload = AuthorizationData::for_delegate(1, &delegate).payload;
+
+        assert_eq!(
+            payload.get(&PayloadKey::Amount.to_string()),
+            Some(&PayloadType::Number(1))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::Delegate.to_string()),
+            Some(&PayloadType::Pubkey(delegate))
+        );
+    }
+
+    #[test]
+    fn unlock_payload_keys() {
+        let authority = Pubkey::new_unique();
+        let payload = AuthorizationData::for_unlock(&authority).payload;
+
+        assert_eq!(
+            payload.get(&PayloadKey::Authority.to_string()),
+            Some(&PayloadType::Pubkey(authority))
+        );
+    }
+}
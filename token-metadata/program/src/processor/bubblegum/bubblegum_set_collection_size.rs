@@ -66,14 +66,18 @@ pub fn bubblegum_set_collection_size(
     )?;
 
     // Ensure new size is + or - 1 of the current size.
-    let current_size = if let Some(details) = metadata.collection_details {
-        match details {
-            CollectionDetails::V1 { size } => size,
-        }
+    let details = if let Some(details) = metadata.collection_details {
+        details
     } else {
         return Err(MetadataError::NotACollectionParent.into());
     };
 
+    let current_size = match details {
+        CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => size,
+        // uncapped collections do not keep track of their size
+        CollectionDetails::Uncapped => return Err(MetadataError::UnsizedCollection.into()),
+    };
+
     let diff = cmp::max(current_size, size)
         .checked_sub(cmp::min(current_size, size))
         .ok_or(MetadataError::InvalidCollectionSizeChange)?;
@@ -83,7 +87,7 @@ pub fn bubblegum_set_collection_size(
     }
 
     // The Bubblegum program has authority to manage the collection details.
-    metadata.collection_details = Some(CollectionDetails::V1 { size });
+    metadata.collection_details = Some(details.with_size(size));
 
     clean_write_metadata(&mut metadata, parent_nft_metadata_account_info)?;
     Ok(())
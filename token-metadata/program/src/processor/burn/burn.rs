@@ -1,11 +1,542 @@
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use borsh::BorshSerialize;
+use mpl_utils::{
+    assert_signer, cmp_pubkeys,
+    token::{
+        get_mint_decimals, get_mint_supply, spl_token_burn, spl_token_close, TokenBurnParams,
+        TokenCloseParams,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey,
+};
 
-use crate::{error::MetadataError, instruction::BurnArgs};
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_token_program_matches_package,
+        collection::assert_collection_not_paused,
+        metadata::assert_verified_member_of_collection,
+        programmable::{assert_not_collection_frozen, assert_not_holder_locked, assert_not_rented},
+    },
+    error::MetadataError,
+    instruction::{Burn, BurnArgs, Context, OrphanPrintsPolicy},
+    pda::{find_edition_account, find_metadata_account, find_token_record_account},
+    state::{
+        AuthorityRequest, AuthorityResponse, AuthorityType, Edition, EditionMarker,
+        EditionTombstone, Key, MasterEditionV2, Metadata, TokenMetadataAccount, TokenRecord,
+        TokenStandard, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX,
+    },
+    utils::{
+        clean_write_metadata, close_account_checked, is_master_edition, is_print_edition, thaw,
+    },
+};
 
+/// Burns an asset, closing the associated accounts.
+///
+/// Only non-fungible assets are currently supported. Burning a print edition decrements the
+/// supply of its master edition (or the orphaned prints of its tombstone) and clears the
+/// edition from the edition marker.
 pub fn burn<'a>(
-    _program_id: &Pubkey,
-    _accounts: &'a [AccountInfo<'a>],
-    _args: BurnArgs,
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BurnArgs,
 ) -> ProgramResult {
-    Err(MetadataError::FeatureNotSupported.into())
+    let context = Burn::to_context(accounts)?;
+
+    match args {
+        BurnArgs::V1 { .. } => burn_v1(program_id, context, OrphanPrintsPolicy::Deny),
+        BurnArgs::V2 { orphan_prints, .. } => burn_v1(program_id, context, orphan_prints),
+    }
+}
+
+fn burn_v1(
+    program_id: &Pubkey,
+    ctx: Context<Burn>,
+    orphan_prints: OrphanPrintsPolicy,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.edition_info, program_id)?;
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    assert_owned_by(
+        ctx.accounts.token_account_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+
+    // key match
+
+    assert_token_program_matches_package(ctx.accounts.spl_token_program_info)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if !cmp_pubkeys(&metadata.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    assert_collection_not_paused(program_id, &metadata, ctx.accounts.collection_pause_info)?;
+
+    let token = assert_initialized_token_account(ctx.accounts.token_account_info)?;
+
+    if !cmp_pubkeys(&token.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            precedence: &[AuthorityType::Holder, AuthorityType::PermanentDelegate],
+            authority: ctx.accounts.authority_info.key,
+            mint: ctx.accounts.mint_info.key,
+            token: Some(ctx.accounts.token_account_info.key),
+            token_account: Some(&token),
+            permanent_delegate: metadata.permanent_delegate.as_ref(),
+            ..Default::default()
+        })?;
+
+    match authority_type {
+        AuthorityType::Holder => (),
+        // non-transferable assets can only be burned by their holder
+        AuthorityType::PermanentDelegate
+            if matches!(
+                metadata.token_standard,
+                Some(TokenStandard::NonTransferable)
+            ) =>
+        {
+            return Err(MetadataError::UnauthorizedBurn.into());
+        }
+        AuthorityType::PermanentDelegate => {
+            // the token program only allows the owner or the delegate of the token account
+            // to burn tokens, so the permanent delegate must also be the delegate of the
+            // token account
+            if COption::from(*ctx.accounts.authority_info.key) != token.delegate
+                || token.delegated_amount < 1
+            {
+                return Err(MetadataError::PermanentDelegateNotTokenDelegate.into());
+            }
+        }
+        _ => return Err(MetadataError::UnauthorizedBurn.into()),
+    }
+
+    // the reclaimed rent goes to the authority unless a rent destination is specified,
+    // which must be the owner of the token account
+    let rent_destination_info =
+        if let Some(rent_destination_info) = ctx.accounts.rent_destination_info {
+            if !cmp_pubkeys(rent_destination_info.key, &token.owner) {
+                return Err(MetadataError::InvalidRentDestination.into());
+            }
+            rent_destination_info
+        } else {
+            ctx.accounts.authority_info
+        };
+
+    let decimals = get_mint_decimals(ctx.accounts.mint_info)?;
+    let supply = get_mint_supply(ctx.accounts.mint_info)?;
+
+    if is_print_edition(ctx.accounts.edition_info, decimals, supply) {
+        burn_print_edition(
+            program_id,
+            &ctx,
+            &metadata,
+            authority_type,
+            rent_destination_info,
+        )
+    } else if is_master_edition(ctx.accounts.edition_info, decimals, supply) {
+        burn_master_edition(
+            program_id,
+            &ctx,
+            &metadata,
+            authority_type,
+            rent_destination_info,
+            orphan_prints,
+        )
+    } else {
+        Err(MetadataError::FeatureNotSupported.into())
+    }
+}
+
+fn burn_master_edition<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<Burn<'a>>,
+    metadata: &Metadata,
+    authority_type: AuthorityType,
+    rent_destination_info: &AccountInfo<'a>,
+    orphan_prints: OrphanPrintsPolicy,
+) -> ProgramResult {
+    assert_derivation(
+        program_id,
+        ctx.accounts.edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+
+    let master_edition = MasterEditionV2::from_account_info(ctx.accounts.edition_info)?;
+
+    if master_edition.supply > 0 && orphan_prints == OrphanPrintsPolicy::Deny {
+        return Err(MetadataError::MasterEditionHasPrints.into());
+    }
+
+    decrement_collection_size(program_id, metadata, ctx.accounts.collection_metadata_info)?;
+
+    // programmable assets are kept frozen and have a token record
+
+    let token_record_info = if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        let token_record_info = ctx
+            .accounts
+            .token_record_info
+            .ok_or(MetadataError::MissingTokenRecord)?;
+
+        let (pda_key, _) = find_token_record_account(
+            ctx.accounts.mint_info.key,
+            ctx.accounts.token_account_info.key,
+        );
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+        assert_owned_by(token_record_info, program_id)?;
+
+        let token_record = TokenRecord::from_account_info(token_record_info)?;
+        assert_not_holder_locked(&token_record)?;
+        assert_not_collection_frozen(&token_record)?;
+        assert_not_rented(&token_record)?;
+
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_account_info.clone(),
+            ctx.accounts.edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+
+        Some(token_record_info)
+    } else {
+        // non-transferable assets are also kept frozen, without a token record
+        if matches!(
+            metadata.token_standard,
+            Some(TokenStandard::NonTransferable)
+        ) {
+            thaw(
+                ctx.accounts.mint_info.clone(),
+                ctx.accounts.token_account_info.clone(),
+                ctx.accounts.edition_info.clone(),
+                ctx.accounts.spl_token_program_info.clone(),
+            )?;
+        }
+
+        None
+    };
+
+    // burns the token
+
+    spl_token_burn(TokenBurnParams {
+        mint: ctx.accounts.mint_info.clone(),
+        source: ctx.accounts.token_account_info.clone(),
+        authority: ctx.accounts.authority_info.clone(),
+        token_program: ctx.accounts.spl_token_program_info.clone(),
+        amount: 1,
+        authority_signer_seeds: None,
+    })?;
+
+    // only the owner can close the token account
+    if matches!(authority_type, AuthorityType::Holder) {
+        spl_token_close(TokenCloseParams {
+            token_program: ctx.accounts.spl_token_program_info.clone(),
+            account: ctx.accounts.token_account_info.clone(),
+            destination: rent_destination_info.clone(),
+            owner: ctx.accounts.authority_info.clone(),
+            authority_signer_seeds: None,
+        })?;
+    }
+
+    close_account_checked(
+        ctx.accounts.metadata_info,
+        rent_destination_info,
+        Key::MetadataV1,
+    )?;
+
+    if let Some(token_record_info) = token_record_info {
+        close_account_checked(token_record_info, rent_destination_info, Key::TokenRecord)?;
+    }
+
+    if master_edition.supply > 0 {
+        // the master edition account is kept as the tombstone of the prints, so they
+        // can still be burned
+        let tombstone = EditionTombstone {
+            master_mint: *ctx.accounts.mint_info.key,
+            rent_destination: *rent_destination_info.key,
+            orphaned_prints: master_edition.supply,
+            ..Default::default()
+        };
+
+        let mut edition_data = ctx.accounts.edition_info.try_borrow_mut_data()?;
+        edition_data.fill(0);
+        tombstone.serialize(&mut *edition_data)?;
+    } else {
+        close_account_checked(
+            ctx.accounts.edition_info,
+            rent_destination_info,
+            Key::MasterEditionV2,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Decrements the size of the collection of a verified collection item being burned.
+fn decrement_collection_size(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    collection_metadata_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    let collection = match &metadata.collection {
+        Some(collection) if collection.verified => collection,
+        _ => return Ok(()),
+    };
+
+    let collection_metadata_info =
+        collection_metadata_info.ok_or(MetadataError::MissingCollectionMetadata)?;
+
+    // the collection parent might have been burned already
+    if collection_metadata_info.data_is_empty() {
+        let (collection_metadata_key, _) = find_metadata_account(&collection.key);
+
+        if !cmp_pubkeys(&collection_metadata_key, collection_metadata_info.key) {
+            return Err(MetadataError::NotAMemberOfCollection.into());
+        }
+
+        return Ok(());
+    }
+
+    assert_owned_by(collection_metadata_info, program_id)?;
+
+    let mut collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    assert_verified_member_of_collection(metadata, &collection_metadata)?;
+
+    if let Some(details) = collection_metadata.collection_details.clone() {
+        if let Some(size) = details.size() {
+            collection_metadata.collection_details = Some(
+                details.with_size(
+                    size.checked_sub(1)
+                        .ok_or(MetadataError::NumericalOverflowError)?,
+                ),
+            );
+            clean_write_metadata(&mut collection_metadata, collection_metadata_info)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn burn_print_edition<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<Burn<'a>>,
+    metadata: &Metadata,
+    authority_type: AuthorityType,
+    rent_destination_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let master_edition_mint_info = ctx
+        .accounts
+        .master_edition_mint_info
+        .ok_or(MetadataError::MissingMasterEditionAccount)?;
+    let master_edition_info = ctx
+        .accounts
+        .master_edition_info
+        .ok_or(MetadataError::MissingMasterEditionAccount)?;
+    let edition_marker_info = ctx
+        .accounts
+        .edition_marker_info
+        .ok_or(MetadataError::MissingEditionMarkerAccount)?;
+
+    assert_owned_by(master_edition_info, program_id)?;
+    assert_owned_by(edition_marker_info, program_id)?;
+
+    // master and print editions are valid PDAs for their mints
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidPrintEdition)?;
+
+    assert_derivation(
+        program_id,
+        master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_edition_mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+
+    let print_edition = Edition::from_account_info(ctx.accounts.edition_info)?;
+
+    if !cmp_pubkeys(&print_edition.parent, master_edition_info.key) {
+        return Err(MetadataError::PrintEditionDoesNotMatchMasterEdition.into());
+    }
+
+    let edition_marker_number = print_edition
+        .edition
+        .checked_div(EDITION_MARKER_BIT_SIZE)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    let (edition_marker_key, _) = find_edition_account(
+        master_edition_mint_info.key,
+        edition_marker_number.to_string(),
+    );
+
+    if !cmp_pubkeys(&edition_marker_key, edition_marker_info.key) {
+        return Err(MetadataError::InvalidEditionMarker.into());
+    }
+
+    // programmable editions are kept frozen and have a token record
+
+    let token_record_info = if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        let token_record_info = ctx
+            .accounts
+            .token_record_info
+            .ok_or(MetadataError::MissingTokenRecord)?;
+
+        let (pda_key, _) = find_token_record_account(
+            ctx.accounts.mint_info.key,
+            ctx.accounts.token_account_info.key,
+        );
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+        assert_owned_by(token_record_info, program_id)?;
+
+        let token_record = TokenRecord::from_account_info(token_record_info)?;
+        assert_not_holder_locked(&token_record)?;
+        assert_not_collection_frozen(&token_record)?;
+        assert_not_rented(&token_record)?;
+
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_account_info.clone(),
+            ctx.accounts.edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+
+        Some(token_record_info)
+    } else {
+        None
+    };
+
+    // burns the token
+
+    spl_token_burn(TokenBurnParams {
+        mint: ctx.accounts.mint_info.clone(),
+        source: ctx.accounts.token_account_info.clone(),
+        authority: ctx.accounts.authority_info.clone(),
+        token_program: ctx.accounts.spl_token_program_info.clone(),
+        amount: 1,
+        authority_signer_seeds: None,
+    })?;
+
+    // only the owner can close the token account
+    if matches!(authority_type, AuthorityType::Holder) {
+        spl_token_close(TokenCloseParams {
+            token_program: ctx.accounts.spl_token_program_info.clone(),
+            account: ctx.accounts.token_account_info.clone(),
+            destination: rent_destination_info.clone(),
+            owner: ctx.accounts.authority_info.clone(),
+            authority_signer_seeds: None,
+        })?;
+    }
+
+    close_account_checked(
+        ctx.accounts.metadata_info,
+        rent_destination_info,
+        Key::MetadataV1,
+    )?;
+    close_account_checked(
+        ctx.accounts.edition_info,
+        rent_destination_info,
+        Key::EditionV1,
+    )?;
+
+    if let Some(token_record_info) = token_record_info {
+        close_account_checked(token_record_info, rent_destination_info, Key::TokenRecord)?;
+    }
+
+    // clears the edition from the marker, closing the marker when it no longer has
+    // editions
+
+    let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+    edition_marker.remove_edition(print_edition.edition)?;
+
+    if edition_marker.is_empty() {
+        close_account_checked(
+            edition_marker_info,
+            rent_destination_info,
+            Key::EditionMarker,
+        )?;
+    } else {
+        let mut edition_marker_data = edition_marker_info.try_borrow_mut_data()?;
+        edition_marker_data[0..].fill(0);
+        edition_marker.serialize(&mut *edition_marker_data)?;
+    }
+
+    // decrements the supply of the master edition or, when the master edition was
+    // burned, the orphaned prints of its tombstone
+
+    if Key::of(&master_edition_info.try_borrow_data()?) == Some(Key::EditionTombstone) {
+        let mut tombstone = EditionTombstone::from_account_info(master_edition_info)?;
+
+        if tombstone.release_print()? {
+            let tombstone_rent_destination_info = ctx
+                .accounts
+                .tombstone_rent_destination_info
+                .ok_or(MetadataError::MissingTombstoneRentDestination)?;
+
+            if !cmp_pubkeys(
+                tombstone_rent_destination_info.key,
+                &tombstone.rent_destination,
+            ) {
+                return Err(MetadataError::InvalidRentDestination.into());
+            }
+
+            close_account_checked(
+                master_edition_info,
+                tombstone_rent_destination_info,
+                Key::EditionTombstone,
+            )?;
+        } else {
+            tombstone.serialize(&mut *master_edition_info.try_borrow_mut_data()?)?;
+        }
+    } else {
+        let mut master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+        master_edition.supply = master_edition
+            .supply
+            .checked_sub(1)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        master_edition.serialize(&mut *master_edition_info.try_borrow_mut_data()?)?;
+    }
+
+    Ok(())
 }
@@ -21,7 +21,7 @@ use crate::{
     error::MetadataError,
     state::{
         Edition, EditionMarker, MasterEditionV2, Metadata, TokenMetadataAccount, EDITION,
-        EDITION_MARKER_BIT_SIZE, MAX_METADATA_LEN, PREFIX,
+        EDITION_MARKER_BIT_SIZE, PREFIX,
     },
     utils::{is_master_edition, is_print_edition},
 };
@@ -193,10 +193,11 @@ pub fn process_burn_edition_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -
 
     let metadata_data = &mut metadata_info.try_borrow_mut_data()?;
     let edition_data = &mut print_edition_info.try_borrow_mut_data()?;
+    let metadata_data_len = metadata_data.len();
     let edition_data_len = edition_data.len();
 
-    // Use MAX_METADATA_LEN because it has unused padding, making it longer than current metadata len.
-    sol_memset(metadata_data, 0, MAX_METADATA_LEN);
+    // Use the account length since resized metadata accounts are shorter than MAX_METADATA_LEN.
+    sol_memset(metadata_data, 0, metadata_data_len);
     sol_memset(edition_data, 0, edition_data_len);
 
     //       **EDITION HOUSEKEEPING**
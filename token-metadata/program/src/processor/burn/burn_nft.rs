@@ -17,10 +17,7 @@ use crate::{
     },
     error::MetadataError,
     pda::find_metadata_account,
-    state::{
-        Collection, CollectionDetails, Key, Metadata, TokenMetadataAccount, EDITION,
-        MAX_METADATA_LEN, PREFIX,
-    },
+    state::{Collection, CollectionDetails, Key, Metadata, TokenMetadataAccount, EDITION, PREFIX},
     utils::clean_write_metadata,
 };
 
@@ -144,10 +141,11 @@ pub fn process_burn_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
 
     let metadata_data = &mut metadata_info.try_borrow_mut_data()?;
     let edition_data = &mut edition_info.try_borrow_mut_data()?;
+    let metadata_data_len = metadata_data.len();
     let edition_data_len = edition_data.len();
 
-    // Use MAX_METADATA_LEN because it has unused padding, making it longer than current metadata len.
-    sol_memset(metadata_data, 0, MAX_METADATA_LEN);
+    // Use the account length since resized metadata accounts are shorter than MAX_METADATA_LEN.
+    sol_memset(metadata_data, 0, metadata_data_len);
     sol_memset(edition_data, 0, edition_data_len);
 
     if collection_nft_provided {
@@ -179,14 +177,16 @@ pub fn process_burn_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
             // Update collection size if it's sized.
             if let Some(ref details) = collection_metadata.collection_details {
                 match details {
-                    CollectionDetails::V1 { size } => {
-                        collection_metadata.collection_details = Some(CollectionDetails::V1 {
-                            size: size
-                                .checked_sub(1)
-                                .ok_or(MetadataError::NumericalOverflowError)?,
-                        });
+                    CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => {
+                        collection_metadata.collection_details = Some(
+                            details.with_size(
+                                size.checked_sub(1)
+                                    .ok_or(MetadataError::NumericalOverflowError)?,
+                            ),
+                        );
                         clean_write_metadata(&mut collection_metadata, collection_metadata_info)?;
                     }
+                    CollectionDetails::Uncapped => (),
                 }
             }
         }
@@ -0,0 +1,133 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::state::Mint;
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{CloseAccounts, CloseAccountsArgs, Context},
+    pda::find_token_record_account,
+    state::{Edition, Key, Metadata, TokenMetadataAccount, EDITION, PREFIX},
+    utils::close_account_checked,
+};
+
+/// Closes the metadata accounts of an asset whose tokens were burned.
+pub fn close_accounts<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CloseAccountsArgs,
+) -> ProgramResult {
+    let context = CloseAccounts::to_context(accounts)?;
+
+    match args {
+        CloseAccountsArgs::V1 => close_accounts_v1(program_id, context),
+    }
+}
+
+fn close_accounts_v1(program_id: &Pubkey, ctx: Context<CloseAccounts>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    if let Some(edition_info) = ctx.accounts.edition_info {
+        assert_owned_by(edition_info, program_id)?;
+        assert_derivation(
+            program_id,
+            edition_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                ctx.accounts.mint_info.key.as_ref(),
+                EDITION.as_bytes(),
+            ],
+        )?;
+    }
+
+    let token_record_info = if let Some(token_record_info) = ctx.accounts.token_record_info {
+        let token_info = ctx
+            .accounts
+            .token_info
+            .ok_or(MetadataError::MissingTokenAccount)?;
+
+        assert_owned_by(token_record_info, program_id)?;
+
+        let (pda_key, _) = find_token_record_account(ctx.accounts.mint_info.key, token_info.key);
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+
+        Some(token_record_info)
+    } else {
+        None
+    };
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // the asset is considered burned when there are no tokens and no new tokens
+    // can be minted; the edition account is the mint authority of non-fungibles,
+    // so it must be closed together with the metadata
+    let mint = Mint::unpack(&ctx.accounts.mint_info.try_borrow_data()?)?;
+
+    if mint.supply > 0 {
+        return Err(MetadataError::MintSupplyMustBeZero.into());
+    }
+
+    if let COption::Some(mint_authority) = mint.mint_authority {
+        match ctx.accounts.edition_info {
+            Some(edition_info) if cmp_pubkeys(&mint_authority, edition_info.key) => (),
+            _ => return Err(MetadataError::AssetNotBurned.into()),
+        }
+    }
+
+    // closes the accounts and sends the rent to the destination
+
+    close_account_checked(
+        ctx.accounts.metadata_info,
+        ctx.accounts.destination_info,
+        Key::MetadataV1,
+    )?;
+
+    if let Some(edition_info) = ctx.accounts.edition_info {
+        // the edition is either the master edition or a print edition of the asset
+        let edition_key_type = if Edition::from_account_info(edition_info).is_ok() {
+            Key::EditionV1
+        } else {
+            Key::MasterEditionV2
+        };
+        close_account_checked(
+            edition_info,
+            ctx.accounts.destination_info,
+            edition_key_type,
+        )?;
+    }
+
+    if let Some(token_record_info) = token_record_info {
+        close_account_checked(
+            token_record_info,
+            ctx.accounts.destination_info,
+            Key::TokenRecord,
+        )?;
+    }
+
+    Ok(())
+}
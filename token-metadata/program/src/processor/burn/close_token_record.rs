@@ -0,0 +1,90 @@
+use mpl_utils::{assert_signer, cmp_pubkeys, token::SPL_TOKEN_PROGRAM_IDS};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::{
+        assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{CloseTokenRecord, CloseTokenRecordArgs, Context},
+    pda::find_token_record_account,
+    state::{Key, Metadata, TokenMetadataAccount, TokenRecord},
+    utils::close_account_checked,
+};
+
+/// Closes a stale token record, refunding the rent to its payer.
+pub fn close_token_record<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CloseTokenRecordArgs,
+) -> ProgramResult {
+    let context = CloseTokenRecord::to_context(accounts)?;
+
+    match args {
+        CloseTokenRecordArgs::V1 => close_token_record_v1(program_id, context),
+    }
+}
+
+fn close_token_record_v1(program_id: &Pubkey, ctx: Context<CloseTokenRecord>) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.token_record_info, program_id)?;
+
+    // key match
+
+    let (pda_key, _) =
+        find_token_record_account(ctx.accounts.mint_info.key, ctx.accounts.token_info.key);
+    assert_keys_equal(&pda_key, ctx.accounts.token_record_info.key)?;
+
+    // account relationships
+
+    if !is_stale(ctx.accounts.token_info, ctx.accounts.mint_info.key)? {
+        return Err(MetadataError::TokenRecordInUse.into());
+    }
+
+    let token_record = TokenRecord::from_account_info(ctx.accounts.token_record_info)?;
+
+    match token_record.payer {
+        Some(payer) => {
+            if !cmp_pubkeys(&payer, ctx.accounts.destination_info.key) {
+                return Err(MetadataError::InvalidRentDestination.into());
+            }
+        }
+        None => {
+            // records created before the payer was recorded are closed by the
+            // update authority, which sets the destination
+            let (metadata_info, authority_info) =
+                match (ctx.accounts.metadata_info, ctx.accounts.authority_info) {
+                    (Some(metadata_info), Some(authority_info)) => (metadata_info, authority_info),
+                    _ => return Err(MetadataError::UpdateAuthorityIsNotSigner.into()),
+                };
+
+            assert_signer(authority_info)?;
+            assert_owned_by(metadata_info, program_id)?;
+            assert_metadata_derivation(program_id, metadata_info, ctx.accounts.mint_info)?;
+
+            let metadata = Metadata::from_account_info(metadata_info)?;
+            assert_update_authority_is_correct(&metadata, authority_info)?;
+        }
+    }
+
+    close_account_checked(
+        ctx.accounts.token_record_info,
+        ctx.accounts.destination_info,
+        Key::TokenRecord,
+    )
+}
+
+/// Indicates whether the token account no longer holds the asset: it was closed, it is
+/// an account of another mint or it has no tokens.
+fn is_stale(token_info: &AccountInfo, mint: &Pubkey) -> Result<bool, MetadataError> {
+    if token_info.data_is_empty() || !SPL_TOKEN_PROGRAM_IDS.contains(token_info.owner) {
+        return Ok(true);
+    }
+
+    let token =
+        assert_initialized_token_account(token_info).map_err(|_| MetadataError::Uninitialized)?;
+
+    Ok(token.mint != *mint || token.amount == 0)
+}
@@ -2,7 +2,11 @@
 mod burn;
 mod burn_edition_nft;
 mod burn_nft;
+mod close_accounts;
+mod close_token_record;
 
 pub use burn::*;
 pub use burn_edition_nft::*;
 pub use burn_nft::*;
+pub use close_accounts::*;
+pub use close_token_record::*;
@@ -0,0 +1,8 @@
+use solana_program::entrypoint::ProgramResult;
+
+use crate::{instruction::ProgramCapabilities, return_data::set_return_data};
+
+/// Sets the capabilities supported by the program as the return data of the instruction.
+pub fn get_program_capabilities() -> ProgramResult {
+    set_return_data(&ProgramCapabilities::supported())
+}
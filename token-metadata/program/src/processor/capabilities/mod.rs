@@ -0,0 +1,3 @@
+mod get_program_capabilities;
+
+pub use get_program_capabilities::*;
@@ -0,0 +1,114 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, ExpireCollectionMembership, ExpireCollectionMembershipArgs},
+    pda::{COLLECTION_MEMBERSHIP, PREFIX},
+    state::{Collection, CollectionMembership, Key, Metadata, TokenMetadataAccount},
+    utils::{clean_write_metadata, close_account_checked, decrement_collection_size},
+};
+
+/// Closes an expired collection membership, unverifying the collection of the asset and
+/// decrementing the size of sized collections.
+pub fn expire_collection_membership<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ExpireCollectionMembershipArgs,
+) -> ProgramResult {
+    let context = ExpireCollectionMembership::to_context(accounts)?;
+
+    match args {
+        ExpireCollectionMembershipArgs::V1 => expire_collection_membership_v1(program_id, context),
+    }
+}
+
+fn expire_collection_membership_v1(
+    program_id: &Pubkey,
+    ctx: Context<ExpireCollectionMembership>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.collection_membership_info, program_id)?;
+
+    // key match
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.collection_membership_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            COLLECTION_MEMBERSHIP.as_bytes(),
+        ],
+    )?;
+
+    let membership =
+        CollectionMembership::from_account_info(ctx.accounts.collection_membership_info)?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            membership.collection_mint.as_ref(),
+        ],
+    )?;
+
+    // account relationships
+
+    if !membership.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::CollectionMembershipNotExpired.into());
+    }
+
+    // the asset might have been unverified (or moved to another collection) since the
+    // membership was set, in which case the membership is only closed
+    let is_verified_member = matches!(
+        &metadata.collection,
+        Some(Collection { key, verified: true }) if cmp_pubkeys(key, &membership.collection_mint)
+    );
+
+    if is_verified_member {
+        // when the collection parent is burned, there is no size to update
+        if !ctx.accounts.collection_metadata_info.data_is_empty() {
+            assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+
+            let mut collection_metadata =
+                Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+
+            // only sized collections keep track of the number of verified items
+            if collection_metadata.collection_details.is_some() {
+                decrement_collection_size(
+                    &mut collection_metadata,
+                    ctx.accounts.collection_metadata_info,
+                )?;
+            }
+        }
+
+        if let Some(collection) = metadata.collection.as_mut() {
+            collection.verified = false;
+        }
+
+        msg!("Collection membership expired");
+        clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+    }
+
+    close_account_checked(
+        ctx.accounts.collection_membership_info,
+        ctx.accounts.payer_info,
+        Key::CollectionMembership,
+    )
+}
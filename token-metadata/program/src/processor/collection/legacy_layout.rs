@@ -0,0 +1,256 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{Context, SetLegacyLayout, SetLegacyLayoutArgs, SyncLayout, SyncLayoutArgs},
+    pda::{LEGACY_LAYOUT, PREFIX, PROGRAMMABLE_CONFIG},
+    state::{
+        Collection, Key, LegacyLayout, Metadata, ProgrammableConfigExtension, TokenMetadataAccount,
+        TokenStandard,
+    },
+    utils::{clean_write_metadata, clean_write_metadata_with_payer, close_account_checked},
+};
+
+/// Sets whether the verified items of a collection use the legacy layout.
+pub fn set_legacy_layout<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetLegacyLayoutArgs,
+) -> ProgramResult {
+    let context = SetLegacyLayout::to_context(accounts)?;
+
+    match args {
+        SetLegacyLayoutArgs::V1 { enabled } => set_legacy_layout_v1(program_id, context, enabled),
+    }
+}
+
+/// Moves the programmable config of an asset following the layout of its collection.
+pub fn sync_layout<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SyncLayoutArgs,
+) -> ProgramResult {
+    let context = SyncLayout::to_context(accounts)?;
+
+    match args {
+        SyncLayoutArgs::V1 => sync_layout_v1(program_id, context),
+    }
+}
+
+fn set_legacy_layout_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetLegacyLayout>,
+    enabled: bool,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.collection_mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.collection_mint_info,
+    )?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+    assert_update_authority_is_correct(&collection_metadata, ctx.accounts.authority_info)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.collection_mint_info.key.as_ref(),
+        LEGACY_LAYOUT.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.legacy_layout_info,
+        &signer_seeds,
+    )?];
+
+    let mut layout = if ctx.accounts.legacy_layout_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.legacy_layout_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            LegacyLayout::size(),
+            &signer_seeds,
+        )?;
+
+        LegacyLayout {
+            bump: bump[0],
+            collection_mint: *ctx.accounts.collection_mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.legacy_layout_info, program_id)?;
+        LegacyLayout::from_account_info(ctx.accounts.legacy_layout_info)?
+    };
+
+    layout.enabled = enabled;
+    layout.serialize(&mut *ctx.accounts.legacy_layout_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Collection {} legacy layout: {}",
+        ctx.accounts.collection_mint_info.key,
+        enabled
+    );
+
+    Ok(())
+}
+
+fn sync_layout_v1(program_id: &Pubkey, ctx: Context<SyncLayout>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        PROGRAMMABLE_CONFIG.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.programmable_config_extension_info,
+        &signer_seeds,
+    )?];
+
+    let legacy_layout =
+        legacy_layout_enabled(program_id, &metadata, ctx.accounts.legacy_layout_info)?;
+
+    match (legacy_layout, metadata.programmable_config_in_extension) {
+        (true, false) => {
+            // only programmable assets have a programmable config
+            if !matches!(
+                metadata.token_standard,
+                Some(TokenStandard::ProgrammableNonFungible)
+            ) {
+                return Err(MetadataError::InvalidTokenStandard.into());
+            }
+
+            let extension_info = ctx.accounts.programmable_config_extension_info;
+
+            if extension_info.data_is_empty() {
+                signer_seeds.push(bump);
+
+                create_or_allocate_account_raw(
+                    *program_id,
+                    extension_info,
+                    ctx.accounts.system_program_info,
+                    ctx.accounts.payer_info,
+                    ProgrammableConfigExtension::size(),
+                    &signer_seeds,
+                )?;
+            } else {
+                assert_owned_by(extension_info, program_id)?;
+            }
+
+            let extension = ProgrammableConfigExtension {
+                bump: bump[0],
+                mint: metadata.mint,
+                programmable_config: metadata.programmable_config.take(),
+                ..Default::default()
+            };
+            extension.serialize(&mut *extension_info.try_borrow_mut_data()?)?;
+
+            metadata.programmable_config_in_extension = true;
+            clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+            msg!("Asset {} uses the legacy layout", metadata.mint);
+        }
+        (false, true) => {
+            metadata
+                .load_programmable_config(Some(ctx.accounts.programmable_config_extension_info))?;
+            metadata.programmable_config_in_extension = false;
+            clean_write_metadata_with_payer(
+                &mut metadata,
+                ctx.accounts.metadata_info,
+                ctx.accounts.payer_info,
+                ctx.accounts.system_program_info,
+            )?;
+
+            close_account_checked(
+                ctx.accounts.programmable_config_extension_info,
+                ctx.accounts.payer_info,
+                Key::ProgrammableConfigExtension,
+            )?;
+
+            msg!("Asset {} uses the current layout", metadata.mint);
+        }
+        _ => msg!("Layout of asset {} is up to date", metadata.mint),
+    }
+
+    Ok(())
+}
+
+/// Indicates whether the verified collection of the asset uses the legacy layout.
+///
+/// Assets without a verified collection always use the current layout, in which case the
+/// legacy layout account is not validated.
+fn legacy_layout_enabled(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    legacy_layout_info: &AccountInfo,
+) -> Result<bool, MetadataError> {
+    let collection_mint = match &metadata.collection {
+        Some(Collection {
+            key,
+            verified: true,
+        }) => key,
+        _ => return Ok(false),
+    };
+
+    assert_derivation(
+        program_id,
+        legacy_layout_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_mint.as_ref(),
+            LEGACY_LAYOUT.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::DerivedKeyInvalid)?;
+
+    if legacy_layout_info.data_is_empty() {
+        return Ok(false);
+    }
+
+    let layout = LegacyLayout::from_account_info(legacy_layout_info)
+        .map_err(|_| MetadataError::DataTypeMismatch)?;
+
+    Ok(layout.enabled)
+}
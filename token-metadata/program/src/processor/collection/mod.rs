@@ -1,8 +1,12 @@
 mod approve_collection_authority;
+mod expire_membership;
+mod legacy_layout;
+mod pause;
 mod revoke_collection_authority;
 mod set_and_verify_collection;
 mod set_and_verify_sized_collection_item;
 mod set_collection_size;
+mod unverify;
 mod unverify_collection;
 mod unverify_sized_collection_item;
 mod verify;
@@ -10,10 +14,14 @@ mod verify_collection;
 mod verify_sized_collection_item;
 
 pub use approve_collection_authority::*;
+pub use expire_membership::*;
+pub use legacy_layout::*;
+pub use pause::*;
 pub use revoke_collection_authority::*;
 pub use set_and_verify_collection::*;
 pub use set_and_verify_sized_collection_item::*;
 pub use set_collection_size::*;
+pub use unverify::*;
 pub use unverify_collection::*;
 pub use unverify_sized_collection_item::*;
 pub use verify::*;
@@ -0,0 +1,162 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    instruction::{
+        Context, PauseCollection, PauseCollectionArgs, UnpauseCollection, UnpauseCollectionArgs,
+    },
+    pda::{COLLECTION_PAUSE, PREFIX},
+    state::{CollectionPause, Metadata, TokenMetadataAccount},
+};
+
+/// Pauses transfers and token delegations of the verified items of a collection.
+pub fn pause_collection<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: PauseCollectionArgs,
+) -> ProgramResult {
+    let context = PauseCollection::to_context(accounts)?;
+
+    match args {
+        PauseCollectionArgs::V1 { duration } => pause_collection_v1(program_id, context, duration),
+    }
+}
+
+/// Lifts the pause of a collection.
+pub fn unpause_collection<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UnpauseCollectionArgs,
+) -> ProgramResult {
+    let context = UnpauseCollection::to_context(accounts)?;
+
+    match args {
+        UnpauseCollectionArgs::V1 => unpause_collection_v1(program_id, context),
+    }
+}
+
+fn pause_collection_v1(
+    program_id: &Pubkey,
+    ctx: Context<PauseCollection>,
+    duration: i64,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.collection_mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.collection_mint_info,
+    )?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+    assert_update_authority_is_correct(&collection_metadata, ctx.accounts.authority_info)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.collection_mint_info.key.as_ref(),
+        COLLECTION_PAUSE.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.collection_pause_info,
+        &signer_seeds,
+    )?];
+
+    let mut pause = if ctx.accounts.collection_pause_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.collection_pause_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            CollectionPause::size(),
+            &signer_seeds,
+        )?;
+
+        CollectionPause {
+            bump: bump[0],
+            collection_mint: *ctx.accounts.collection_mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.collection_pause_info, program_id)?;
+        CollectionPause::from_account_info(ctx.accounts.collection_pause_info)?
+    };
+
+    pause.pause(duration, Clock::get()?.unix_timestamp)?;
+    pause.serialize(&mut *ctx.accounts.collection_pause_info.try_borrow_mut_data()?)?;
+
+    if let Some(paused_until) = pause.paused_until {
+        msg!(
+            "Collection {} paused until {}",
+            ctx.accounts.collection_mint_info.key,
+            paused_until
+        );
+    }
+
+    Ok(())
+}
+
+fn unpause_collection_v1(program_id: &Pubkey, ctx: Context<UnpauseCollection>) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_pause_info, program_id)?;
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.collection_mint_info)?;
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.collection_mint_info,
+    )?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.collection_pause_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.collection_mint_info.key.as_ref(),
+            COLLECTION_PAUSE.as_bytes(),
+        ],
+    )?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+    assert_update_authority_is_correct(&collection_metadata, ctx.accounts.authority_info)?;
+
+    let mut pause = CollectionPause::from_account_info(ctx.accounts.collection_pause_info)?;
+    pause.unpause(Clock::get()?.unix_timestamp);
+    pause.serialize(&mut *ctx.accounts.collection_pause_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Collection {} unpaused",
+        ctx.accounts.collection_mint_info.key
+    );
+
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use borsh::BorshSerialize;
 use mpl_utils::assert_signer;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -13,6 +12,7 @@ use crate::{
     },
     error::MetadataError,
     state::{Collection, Metadata, TokenMetadataAccount},
+    utils::clean_write_metadata,
 };
 
 pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -82,6 +82,6 @@ pub fn set_and_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo])
         return Err(MetadataError::SizedCollection.into());
     }
 
-    metadata.serialize(&mut *metadata_info.try_borrow_mut_data()?)?;
+    clean_write_metadata(&mut metadata, metadata_info)?;
     Ok(())
 }
@@ -0,0 +1,178 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program, sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::assert_metadata_derivation, programmable::assert_metadata_delegate_not_expired,
+    },
+    error::MetadataError,
+    instruction::{Context, MetadataDelegateRole, Unverify, VerificationArgs},
+    processor::set_overflow_creator_verification,
+    state::{AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, TokenMetadataAccount},
+    utils::{clean_write_metadata, decrement_collection_size},
+};
+
+/// Unverifies either a creator or the collection of an asset.
+///
+/// The collection can be unverified by its update authority or a `Collection` delegate;
+/// the collection size is decremented for sized collections.
+pub fn unverify<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: VerificationArgs,
+) -> ProgramResult {
+    let context = Unverify::to_context(accounts)?;
+
+    match args {
+        VerificationArgs::CreatorV1 => unverify_creator_v1(program_id, context),
+        // the membership expiry only applies to verifications
+        VerificationArgs::CollectionV1 | VerificationArgs::CollectionV2 { .. } => {
+            unverify_collection_v1(program_id, context)
+        }
+    }
+}
+
+fn unverify_creator_v1(program_id: &Pubkey, ctx: Context<Unverify>) -> ProgramResult {
+    let mut metadata = unverified_metadata(program_id, &ctx)?;
+
+    // account relationships
+
+    let creator = metadata
+        .data
+        .creators
+        .as_mut()
+        .ok_or(MetadataError::NoCreatorsPresentOnMetadata)?
+        .iter_mut()
+        .find(|creator| cmp_pubkeys(&creator.address, ctx.accounts.authority_info.key));
+
+    // creators not found on the metadata are unverified on the creators overflow account
+    let creator = match creator {
+        Some(creator) => creator,
+        None => {
+            return set_overflow_creator_verification(
+                program_id,
+                &metadata,
+                ctx.accounts.creators_overflow_info,
+                ctx.accounts.authority_info.key,
+                false,
+            )
+        }
+    };
+
+    if !creator.verified {
+        return Err(MetadataError::AlreadyUnverified.into());
+    }
+
+    creator.verified = false;
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)
+}
+
+fn unverify_collection_v1(program_id: &Pubkey, ctx: Context<Unverify>) -> ProgramResult {
+    let mut metadata = unverified_metadata(program_id, &ctx)?;
+
+    let collection_mint_info = ctx
+        .accounts
+        .collection_mint_info
+        .ok_or(MetadataError::MissingCollectionMint)?;
+    let collection_metadata_info = ctx
+        .accounts
+        .collection_metadata_info
+        .ok_or(MetadataError::MissingCollectionMetadata)?;
+
+    // ownership
+
+    assert_owned_by_token_program(collection_mint_info)?;
+
+    if let Some(delegate_record_info) = ctx.accounts.delegate_record_info {
+        assert_owned_by(delegate_record_info, program_id)?;
+    }
+
+    // key match
+
+    assert_metadata_derivation(program_id, collection_metadata_info, collection_mint_info)?;
+
+    // account relationships
+
+    match &metadata.collection {
+        Some(collection) if !collection.verified => {
+            return Err(MetadataError::AlreadyUnverified.into())
+        }
+        Some(collection) if cmp_pubkeys(&collection.key, collection_mint_info.key) => (),
+        Some(_) => return Err(MetadataError::NotAMemberOfCollection.into()),
+        None => return Err(MetadataError::CollectionNotFound.into()),
+    }
+
+    // when the collection parent is burned, there is no collection update authority
+    // or size to update; delegates of a burned parent cannot be revoked, so only the
+    // update authority of the asset can unverify it
+    if collection_metadata_info.data_is_empty() {
+        if !cmp_pubkeys(&metadata.update_authority, ctx.accounts.authority_info.key) {
+            return Err(MetadataError::UpdateAuthorityIncorrect.into());
+        }
+    } else {
+        assert_owned_by(collection_metadata_info, program_id)?;
+
+        let mut collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+
+        let AuthorityResponse { authority_type, .. } =
+            AuthorityType::get_authority_type(AuthorityRequest {
+                authority: ctx.accounts.authority_info.key,
+                update_authority: &collection_metadata.update_authority,
+                mint: collection_mint_info.key,
+                metadata_delegate_record_info: ctx.accounts.delegate_record_info,
+                metadata_delegate_roles: vec![MetadataDelegateRole::Collection],
+                precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+                ..Default::default()
+            })?;
+
+        match (authority_type, ctx.accounts.delegate_record_info) {
+            (AuthorityType::Metadata, _) => (),
+            // expired delegates can no longer unverify assets
+            (AuthorityType::MetadataDelegate, Some(delegate_record_info)) => {
+                assert_metadata_delegate_not_expired(delegate_record_info)?;
+            }
+            _ => return Err(MetadataError::InvalidAuthorityType.into()),
+        }
+
+        // only sized collections keep track of the number of verified items
+        if collection_metadata.collection_details.is_some() {
+            decrement_collection_size(&mut collection_metadata, collection_metadata_info)?;
+        }
+    }
+
+    if let Some(collection) = metadata.collection.as_mut() {
+        collection.verified = false;
+    }
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)
+}
+
+/// Validates the accounts shared by all unverification types and returns the metadata
+/// of the asset.
+fn unverified_metadata(
+    program_id: &Pubkey,
+    ctx: &Context<Unverify>,
+) -> Result<Metadata, ProgramError> {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    Metadata::from_account_info(ctx.accounts.metadata_info)
+}
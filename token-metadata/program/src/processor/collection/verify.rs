@@ -1,11 +1,394 @@
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, Sysvar},
+};
 
-use crate::{error::MetadataError, instruction::VerifyArgs};
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        collection::assert_collection_verify_is_valid, metadata::assert_metadata_derivation,
+        programmable::assert_metadata_delegate_not_expired,
+    },
+    error::MetadataError,
+    instruction::{Context, MetadataDelegateRole, MetadataInstruction, VerifyV2, VerificationArgs},
+    pda::{COLLECTION_MEMBERSHIP, PREFIX},
+    processor::{approve_authority_action, set_overflow_creator_verification},
+    state::{
+        AuthorityRequest, AuthorityResponse, AuthorityType, Collection, CollectionMembership, Key,
+        Metadata, TokenMetadataAccount,
+    },
+    utils::{clean_write_metadata, close_account_checked, increment_collection_size},
+};
 
+/// Accounts of the collection of an asset being verified.
+pub(crate) struct CollectionVerifyAccounts<'a> {
+    pub collection_mint_info: &'a AccountInfo<'a>,
+    pub collection_metadata_info: &'a AccountInfo<'a>,
+    pub collection_master_edition_info: &'a AccountInfo<'a>,
+    pub delegate_record_info: Option<&'a AccountInfo<'a>>,
+}
+
+/// Verifies either a creator or the collection of an asset.
+///
+/// This replaces the `SignMetadata`, `VerifyCollection` and `VerifySizedCollectionItem`
+/// instructions: the collection is verified by its update authority or a `Collection`
+/// delegate, and the collection size is incremented for sized collections.
 pub fn verify<'a>(
-    _program_id: &Pubkey,
-    _accounts: &'a [AccountInfo<'a>],
-    _args: VerifyArgs,
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: VerificationArgs,
+) -> ProgramResult {
+    let context = VerifyV2::to_context(accounts)?;
+
+    match args {
+        VerificationArgs::CreatorV1 => verify_creator_v1(program_id, context, args),
+        VerificationArgs::CollectionV1 => verify_collection_v1(program_id, context, args),
+        VerificationArgs::CollectionV2 {
+            membership_expires_at,
+        } => verify_collection_v2(program_id, context, args, membership_expires_at),
+    }
+}
+
+fn verify_creator_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, VerifyV2<'a>>,
+    args: VerificationArgs,
+) -> ProgramResult {
+    let mut metadata = verified_metadata(program_id, &ctx)?;
+
+    let authority_info = match verification_authority(program_id, &ctx, args)? {
+        Some(authority_info) => authority_info,
+        None => return Ok(()),
+    };
+
+    // account relationships
+
+    let creator = metadata
+        .data
+        .creators
+        .as_mut()
+        .ok_or(MetadataError::NoCreatorsPresentOnMetadata)?
+        .iter_mut()
+        .find(|creator| cmp_pubkeys(&creator.address, authority_info.key));
+
+    // creators not found on the metadata are verified on the creators overflow account
+    let creator = match creator {
+        Some(creator) => creator,
+        None => {
+            return set_overflow_creator_verification(
+                program_id,
+                &metadata,
+                ctx.accounts.creators_overflow_info,
+                authority_info.key,
+                true,
+            )
+        }
+    };
+
+    if creator.verified {
+        return Err(MetadataError::AlreadyVerified.into());
+    }
+
+    creator.verified = true;
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)
+}
+
+fn verify_collection_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, VerifyV2<'a>>,
+    args: VerificationArgs,
+) -> ProgramResult {
+    verify_collection(program_id, &ctx, args).map(|_| ())
+}
+
+fn verify_collection_v2<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, VerifyV2<'a>>,
+    args: VerificationArgs,
+    membership_expires_at: Option<i64>,
 ) -> ProgramResult {
-    Err(MetadataError::FeatureNotSupported.into())
+    if let Some(expires_at) = membership_expires_at {
+        if expires_at <= Clock::get()?.unix_timestamp {
+            return Err(MetadataError::InvalidCollectionMembershipExpiry.into());
+        }
+    }
+
+    let metadata = match verify_collection(program_id, &ctx, args)? {
+        Some(metadata) => metadata,
+        None => return Ok(()),
+    };
+
+    set_collection_membership(program_id, &ctx, &metadata, membership_expires_at)
+}
+
+/// Verifies the collection of an asset, returning its metadata once verified; `None` is
+/// returned while the approvals of an authority config are pending.
+fn verify_collection<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, VerifyV2<'a>>,
+    args: VerificationArgs,
+) -> Result<Option<Metadata>, ProgramError> {
+    let mut metadata = verified_metadata(program_id, ctx)?;
+
+    let authority_info = match verification_authority(program_id, ctx, args)? {
+        Some(authority_info) => authority_info,
+        None => return Ok(None),
+    };
+
+    let accounts = CollectionVerifyAccounts {
+        collection_mint_info: ctx
+            .accounts
+            .collection_mint_info
+            .ok_or(MetadataError::MissingCollectionMint)?,
+        collection_metadata_info: ctx
+            .accounts
+            .collection_metadata_info
+            .ok_or(MetadataError::MissingCollectionMetadata)?,
+        collection_master_edition_info: ctx
+            .accounts
+            .collection_master_edition_info
+            .ok_or(MetadataError::MissingCollectionMasterEdition)?,
+        delegate_record_info: ctx.accounts.delegate_record_info,
+    };
+
+    // account relationships
+
+    let collection_mint = match &metadata.collection {
+        Some(Collection { verified: true, .. }) => {
+            return Err(MetadataError::AlreadyVerified.into())
+        }
+        Some(Collection { key, .. }) => *key,
+        None => return Err(MetadataError::CollectionNotFound.into()),
+    };
+
+    verify_collection_item(
+        program_id,
+        authority_info,
+        &accounts,
+        &mut metadata,
+        &collection_mint,
+    )?;
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    Ok(Some(metadata))
+}
+
+/// Sets the expiry of the collection membership of a verified asset, creating the membership
+/// account if needed; without an expiry, the existing membership is closed.
+fn set_collection_membership<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, VerifyV2<'a>>,
+    metadata: &Metadata,
+    membership_expires_at: Option<i64>,
+) -> ProgramResult {
+    let collection_membership_info = ctx
+        .accounts
+        .collection_membership_info
+        .ok_or(MetadataError::MissingCollectionMembership)?;
+    let payer_info = ctx
+        .accounts
+        .payer_info
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // signers
+
+    assert_signer(payer_info)?;
+
+    // ownership
+
+    let exists = !collection_membership_info.data_is_empty();
+
+    if exists {
+        assert_owned_by(collection_membership_info, program_id)?;
+    }
+
+    // key match
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        COLLECTION_MEMBERSHIP.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        collection_membership_info,
+        &signer_seeds,
+    )?];
+
+    let collection_mint = metadata
+        .collection
+        .as_ref()
+        .map(|collection| collection.key)
+        .ok_or(MetadataError::CollectionNotFound)?;
+
+    match membership_expires_at {
+        Some(expires_at) => {
+            if !exists {
+                signer_seeds.push(bump);
+
+                create_or_allocate_account_raw(
+                    *program_id,
+                    collection_membership_info,
+                    ctx.accounts.system_program_info,
+                    payer_info,
+                    CollectionMembership::size(),
+                    &signer_seeds,
+                )?;
+            }
+
+            let membership = CollectionMembership {
+                bump: bump[0],
+                mint: metadata.mint,
+                collection_mint,
+                expires_at,
+                ..Default::default()
+            };
+            membership.serialize(&mut *collection_membership_info.try_borrow_mut_data()?)?;
+        }
+        None if exists => close_account_checked(
+            collection_membership_info,
+            payer_info,
+            Key::CollectionMembership,
+        )?,
+        None => (),
+    }
+
+    Ok(())
+}
+
+/// Validates the accounts shared by all verification types and returns the metadata
+/// of the asset.
+fn verified_metadata(program_id: &Pubkey, ctx: &Context<VerifyV2>) -> Result<Metadata, ProgramError> {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    Metadata::from_account_info(ctx.accounts.metadata_info)
+}
+
+/// Returns the authority of the verification.
+///
+/// When an authority config is present, the signer approves the verification and the
+/// authority config is returned once its threshold is reached; `None` is returned while
+/// approvals are pending.
+fn verification_authority<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, VerifyV2<'a>>,
+    args: VerificationArgs,
+) -> Result<Option<&'a AccountInfo<'a>>, ProgramError> {
+    match ctx.accounts.authority_config_info {
+        Some(authority_config_info) => {
+            let approved = approve_authority_action(
+                program_id,
+                authority_config_info,
+                ctx.accounts.authority_info,
+                ctx.accounts.metadata_info.key,
+                &MetadataInstruction::VerifyV2(args),
+            )?;
+
+            Ok(approved.then_some(authority_config_info))
+        }
+        None => Ok(Some(ctx.accounts.authority_info)),
+    }
+}
+
+/// Verifies the asset as a member of the collection, incrementing the size of sized
+/// collections. The authority must be the update authority of the collection or a
+/// (non-expired) `Collection` delegate.
+pub(crate) fn verify_collection_item<'a>(
+    program_id: &Pubkey,
+    authority_info: &'a AccountInfo<'a>,
+    accounts: &CollectionVerifyAccounts<'a>,
+    metadata: &mut Metadata,
+    collection_mint: &Pubkey,
+) -> ProgramResult {
+    // ownership
+
+    assert_owned_by_token_program(accounts.collection_mint_info)?;
+    assert_owned_by(accounts.collection_metadata_info, program_id)?;
+    assert_owned_by(accounts.collection_master_edition_info, program_id)?;
+
+    if let Some(delegate_record_info) = accounts.delegate_record_info {
+        assert_owned_by(delegate_record_info, program_id)?;
+    }
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        accounts.collection_metadata_info,
+        accounts.collection_mint_info,
+    )?;
+
+    // an asset cannot be a member of its own collection
+    if collection_mint == &metadata.mint {
+        return Err(MetadataError::InvalidUpdateArgs.into());
+    }
+
+    // account relationships
+
+    let mut collection_metadata = Metadata::from_account_info(accounts.collection_metadata_info)?;
+
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            authority: authority_info.key,
+            update_authority: &collection_metadata.update_authority,
+            mint: accounts.collection_mint_info.key,
+            metadata_delegate_record_info: accounts.delegate_record_info,
+            metadata_delegate_roles: vec![MetadataDelegateRole::Collection],
+            precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+            ..Default::default()
+        })?;
+
+    match (authority_type, accounts.delegate_record_info) {
+        (AuthorityType::Metadata, _) => (),
+        // expired delegates can no longer verify assets
+        (AuthorityType::MetadataDelegate, Some(delegate_record_info)) => {
+            assert_metadata_delegate_not_expired(delegate_record_info)?;
+        }
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    }
+
+    let collection = Some(Collection {
+        key: *collection_mint,
+        verified: true,
+    });
+
+    assert_collection_verify_is_valid(
+        &collection,
+        &collection_metadata,
+        accounts.collection_mint_info,
+        accounts.collection_master_edition_info,
+    )?;
+
+    // only sized collections keep track of the number of verified items
+    if collection_metadata.collection_details.is_some() {
+        increment_collection_size(&mut collection_metadata, accounts.collection_metadata_info)?;
+    }
+
+    metadata.collection = collection;
+
+    Ok(())
 }
@@ -1,28 +1,36 @@
 use std::fmt::Display;
 
 use borsh::BorshSerialize;
-use mpl_token_auth_rules::utils::get_latest_revision;
 use mpl_utils::{assert_signer, create_or_allocate_account_raw};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, program_pack::Pack,
-    pubkey::Pubkey, system_program, sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, pubkey::Pubkey,
+    system_program, sysvar,
 };
-use spl_token::state::Account;
 
+use super::registry::{register_delegate, RegistryAccounts};
 use crate::{
     assertions::{
-        assert_derivation, assert_keys_equal, assert_owned_by,
+        assert_derivation, assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_owned_by_token_program, assert_token_program_matches_package,
+        collection::assert_collection_not_paused,
         metadata::assert_update_authority_is_correct,
+        programmable::{
+            assert_not_collection_frozen, assert_not_holder_locked, assert_valid_delegate_expiry,
+        },
     },
     error::MetadataError,
     instruction::{Context, Delegate, DelegateArgs, MetadataDelegateRole},
-    pda::{find_token_record_account, PREFIX},
+    pda::{find_token_record_account, COLLECTION_ITEMS_DELEGATE, PREFIX},
     processor::AuthorizationData,
     state::{
-        Metadata, MetadataDelegateRecord, Operation, ProgrammableConfig, Resizable,
-        TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard, TokenState,
+        CollectionItemsDelegateRecord, DelegateRegistryEntry, Metadata, MetadataDelegateRecord,
+        Operation, ProgrammableConfig, Resizable, TokenDelegateRole, TokenMetadataAccount,
+        TokenRecord, TokenStandard, TokenState,
+    },
+    utils::{
+        auth_rules_validate, freeze, get_latest_revision, is_auth_rules_program, thaw,
+        AuthRulesValidateParams,
     },
-    utils::{auth_rules_validate, freeze, thaw, AuthRulesValidateParams},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,6 +48,9 @@ impl Display for DelegateScenario {
                 MetadataDelegateRole::Use => "Use".to_string(),
                 MetadataDelegateRole::Update => "Update".to_string(),
                 MetadataDelegateRole::ProgrammableConfig => "ProgrammableConfig".to_string(),
+                MetadataDelegateRole::Data => "Data".to_string(),
+                MetadataDelegateRole::Uses => "Uses".to_string(),
+                MetadataDelegateRole::CollectionFreeze => "CollectionFreeze".to_string(),
             },
             Self::Token(role) => match role {
                 TokenDelegateRole::Sale => "Sale".to_string(),
@@ -47,6 +58,8 @@ impl Display for DelegateScenario {
                 TokenDelegateRole::LockedTransfer => "LockedTransfer".to_string(),
                 TokenDelegateRole::Utility => "Utility".to_string(),
                 TokenDelegateRole::Staking => "Staking".to_string(),
+                TokenDelegateRole::Escrow => "Escrow".to_string(),
+                TokenDelegateRole::Rental => "Rental".to_string(),
                 _ => panic!("Invalid delegate role"),
             },
         };
@@ -64,76 +77,204 @@ pub fn delegate<'a>(
     let context = Delegate::to_context(accounts)?;
 
     // checks if it is a TokenDelegate creation
+    if let Some((role, amount, authorization_data, expiry)) = get_token_delegate_args(&args) {
+        // proceed with the delegate creation if we have a match
+        return create_persistent_delegate_v1(
+            program_id,
+            context,
+            &args,
+            role,
+            amount,
+            authorization_data,
+            expiry,
+            false,
+        );
+    }
+
+    // checks if it is a MetadataDelegate creation
     let delegate_args = match &args {
+        DelegateArgs::CollectionV1 {
+            authorization_data,
+            expiry,
+        } => Some((
+            MetadataDelegateRole::Collection,
+            authorization_data,
+            *expiry,
+        )),
+        DelegateArgs::UpdateV1 {
+            authorization_data,
+            expiry,
+        } => Some((MetadataDelegateRole::Update, authorization_data, *expiry)),
+        DelegateArgs::ProgrammableConfigV1 {
+            authorization_data,
+            expiry,
+        } => Some((
+            MetadataDelegateRole::ProgrammableConfig,
+            authorization_data,
+            *expiry,
+        )),
+        DelegateArgs::DataV1 {
+            authorization_data,
+            expiry,
+        } => Some((MetadataDelegateRole::Data, authorization_data, *expiry)),
+        DelegateArgs::UsesV1 {
+            authorization_data,
+            expiry,
+        } => Some((MetadataDelegateRole::Uses, authorization_data, *expiry)),
+        DelegateArgs::UseV1 {
+            authorization_data,
+            expiry,
+            ..
+        } => Some((MetadataDelegateRole::Use, authorization_data, *expiry)),
+        DelegateArgs::CollectionFreezeV1 {
+            authorization_data,
+            expiry,
+        } => Some((
+            MetadataDelegateRole::CollectionFreeze,
+            authorization_data,
+            *expiry,
+        )),
+        // we don't need to fail if did not find a match at this point
+        _ => None,
+    };
+
+    if let Some((role, _authorization_data, expiry)) = delegate_args {
+        return create_delegate_v1(program_id, context, args, role, expiry);
+    }
+
+    // checks if it is a collection items delegate creation
+    if matches!(args, DelegateArgs::CollectionItemsV1 { .. }) {
+        return create_collection_items_delegate_v1(program_id, context);
+    }
+
+    // this only happens if we did not find a match
+    Err(MetadataError::InvalidDelegateArgs.into())
+}
+
+/// Delegates an action over an asset to a specific account and locks the asset.
+///
+/// Only token delegates that are able to unlock the asset can be created.
+pub fn delegate_and_lock<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: DelegateArgs,
+) -> ProgramResult {
+    // the instruction has the same accounts as the `Delegate` instruction
+    let context = Delegate::to_context(accounts)?;
+
+    if let Some((role, amount, authorization_data, expiry)) = get_token_delegate_args(&args) {
+        return create_persistent_delegate_v1(
+            program_id,
+            context,
+            &args,
+            role,
+            amount,
+            authorization_data,
+            expiry,
+            true,
+        );
+    }
+
+    // this only happens if we did not find a match
+    Err(MetadataError::InvalidDelegateArgs.into())
+}
+
+/// Returns the role, amount, authorization data and expiry of a token delegate.
+fn get_token_delegate_args(
+    args: &DelegateArgs,
+) -> Option<(
+    TokenDelegateRole,
+    u64,
+    &Option<AuthorizationData>,
+    Option<i64>,
+)> {
+    match args {
         // Sale
         DelegateArgs::SaleV1 {
             amount,
             authorization_data,
-        } => Some((TokenDelegateRole::Sale, amount, authorization_data)),
+            expiry,
+        } => Some((
+            TokenDelegateRole::Sale,
+            *amount,
+            authorization_data,
+            *expiry,
+        )),
         // Transfer
         DelegateArgs::TransferV1 {
             amount,
             authorization_data,
-        } => Some((TokenDelegateRole::Transfer, amount, authorization_data)),
+            expiry,
+        } => Some((
+            TokenDelegateRole::Transfer,
+            *amount,
+            authorization_data,
+            *expiry,
+        )),
         // LockedTransfer
         DelegateArgs::LockedTransferV1 {
             amount,
             authorization_data,
+            expiry,
             ..
         } => Some((
             TokenDelegateRole::LockedTransfer,
-            amount,
+            *amount,
             authorization_data,
+            *expiry,
         )),
         // Utility
         DelegateArgs::UtilityV1 {
             amount,
             authorization_data,
-        } => Some((TokenDelegateRole::Utility, amount, authorization_data)),
+            expiry,
+        } => Some((
+            TokenDelegateRole::Utility,
+            *amount,
+            authorization_data,
+            *expiry,
+        )),
         // Staking
         DelegateArgs::StakingV1 {
             amount,
             authorization_data,
-        } => Some((TokenDelegateRole::Staking, amount, authorization_data)),
-        // Standard
-        DelegateArgs::StandardV1 { amount } => Some((TokenDelegateRole::Standard, amount, &None)),
-        // we don't need to fail if did not find a match at this point
-        _ => None,
-    };
-
-    if let Some((role, amount, authorization_data)) = delegate_args {
-        // proceed with the delegate creation if we have a match
-        return create_persistent_delegate_v1(
-            program_id,
-            context,
-            &args,
-            role,
+            expiry,
+            ..
+        } => Some((
+            TokenDelegateRole::Staking,
             *amount,
             authorization_data,
-        );
-    }
-
-    // checks if it is a MetadataDelegate creation
-    let delegate_args = match &args {
-        DelegateArgs::CollectionV1 { authorization_data } => {
-            Some((MetadataDelegateRole::Collection, authorization_data))
-        }
-        DelegateArgs::UpdateV1 { authorization_data } => {
-            Some((MetadataDelegateRole::Update, authorization_data))
-        }
-        DelegateArgs::ProgrammableConfigV1 { authorization_data } => {
-            Some((MetadataDelegateRole::ProgrammableConfig, authorization_data))
+            *expiry,
+        )),
+        // Escrow
+        DelegateArgs::EscrowV1 {
+            amount,
+            authorization_data,
+            expiry,
+        } => Some((
+            TokenDelegateRole::Escrow,
+            *amount,
+            authorization_data,
+            *expiry,
+        )),
+        // Rental
+        DelegateArgs::RentalV1 {
+            amount,
+            authorization_data,
+            expiry,
+        } => Some((
+            TokenDelegateRole::Rental,
+            *amount,
+            authorization_data,
+            Some(*expiry),
+        )),
+        // Standard
+        DelegateArgs::StandardV1 { amount } => {
+            Some((TokenDelegateRole::Standard, *amount, &None, None))
         }
-        // we don't need to fail if did not find a match at this point
+        // not a token delegate
         _ => None,
-    };
-
-    if let Some((role, _authorization_data)) = delegate_args {
-        return create_delegate_v1(program_id, context, args, role);
     }
-
-    // this only happens if we did not find a match
-    Err(MetadataError::InvalidDelegateArgs.into())
 }
 
 /// Creates a `DelegateRole::Collection` delegate.
@@ -142,8 +283,9 @@ pub fn delegate<'a>(
 fn create_delegate_v1(
     program_id: &Pubkey,
     ctx: Context<Delegate>,
-    _args: DelegateArgs,
+    args: DelegateArgs,
     role: MetadataDelegateRole,
+    expiry: Option<i64>,
 ) -> ProgramResult {
     // signers
 
@@ -153,7 +295,7 @@ fn create_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
 
     // key match
 
@@ -173,6 +315,8 @@ fn create_delegate_v1(
         return Err(MetadataError::MintMismatch.into());
     }
 
+    assert_valid_delegate_expiry(expiry)?;
+
     let delegate_record_info = match ctx.accounts.delegate_record_info {
         Some(delegate_record_info) => delegate_record_info,
         None => {
@@ -185,6 +329,22 @@ fn create_delegate_v1(
 
     let delegate_role = role.to_string();
 
+    // use delegates have their own allowance of uses, which cannot exceed the
+    // remaining uses of the asset
+    let remaining_uses = if let DelegateArgs::UseV1 { allowed_uses, .. } = args {
+        let uses = metadata.uses.as_ref().ok_or(MetadataError::Unusable)?;
+
+        if allowed_uses == 0 {
+            return Err(MetadataError::NotEnoughDelegateUses.into());
+        }
+        if allowed_uses > uses.remaining {
+            return Err(MetadataError::NotEnoughUses.into());
+        }
+        Some(allowed_uses)
+    } else {
+        None
+    };
+
     create_pda_account(
         program_id,
         delegate_record_info,
@@ -194,13 +354,120 @@ fn create_delegate_v1(
         ctx.accounts.payer_info,
         ctx.accounts.system_program_info,
         &delegate_role,
-    )
+        expiry,
+        remaining_uses,
+    )?;
+
+    // lists the delegate on the registry of the mint
+
+    if let Some(delegate_registry_info) = ctx.accounts.delegate_registry_info {
+        register_delegate(
+            program_id,
+            RegistryAccounts {
+                delegate_registry_info,
+                mint_info: ctx.accounts.mint_info,
+                payer_info: ctx.accounts.payer_info,
+                system_program_info: ctx.accounts.system_program_info,
+            },
+            DelegateRegistryEntry {
+                role,
+                delegate: *ctx.accounts.delegate_info.key,
+                approver: *ctx.accounts.authority_info.key,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates a collection items delegate.
+///
+/// The delegate is approved by the holder (authority) of the items and is able to lock
+/// and unlock any item of the collection held by the authority.
+fn create_collection_items_delegate_v1(
+    program_id: &Pubkey,
+    ctx: Context<Delegate>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let delegate_record_info = match ctx.accounts.delegate_record_info {
+        Some(delegate_record_info) => delegate_record_info,
+        None => {
+            return Err(MetadataError::MissingTokenAccount.into());
+        }
+    };
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        COLLECTION_ITEMS_DELEGATE.as_bytes(),
+        ctx.accounts.authority_info.key.as_ref(),
+        ctx.accounts.delegate_info.key.as_ref(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        delegate_record_info,
+        &signer_seeds,
+    )?];
+
+    if !delegate_record_info.data_is_empty() {
+        return Err(MetadataError::DelegateAlreadyExists.into());
+    }
+
+    signer_seeds.push(bump);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        delegate_record_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        CollectionItemsDelegateRecord::size(),
+        &signer_seeds,
+    )?;
+
+    let delegate_record = CollectionItemsDelegateRecord {
+        bump: bump[0],
+        collection_mint: *ctx.accounts.mint_info.key,
+        holder: *ctx.accounts.authority_info.key,
+        delegate: *ctx.accounts.delegate_info.key,
+        ..Default::default()
+    };
+    delegate_record.serialize(&mut *delegate_record_info.try_borrow_mut_data()?)?;
+
+    Ok(())
 }
 
 /// Creates a presistent delegate. For non-programmable assets, this is just a wrapper over
 /// spl-token 'approve' delegate.
 ///
-/// Note that `DelegateRole::Sale` is only available for programmable assets.
+/// Note that `DelegateRole::Sale` is only available for programmable assets. When `lock` is
+/// set, the asset is locked as part of the delegation.
+#[allow(clippy::too_many_arguments)]
 fn create_persistent_delegate_v1(
     program_id: &Pubkey,
     ctx: Context<Delegate>,
@@ -208,6 +475,8 @@ fn create_persistent_delegate_v1(
     role: TokenDelegateRole,
     amount: u64,
     authorization_data: &Option<AuthorizationData>,
+    expiry: Option<i64>,
+    lock: bool,
 ) -> ProgramResult {
     // retrieving required optional accounts
 
@@ -233,8 +502,8 @@ fn create_persistent_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
-    assert_owned_by(token_info, &spl_token::id())?;
+    assert_owned_by(ctx.accounts.mint_info, spl_token_program_info.key)?;
+    assert_owned_by(token_info, spl_token_program_info.key)?;
 
     // key match
 
@@ -243,22 +512,37 @@ fn create_persistent_delegate_v1(
         ctx.accounts.sysvar_instructions_info.key,
         &sysvar::instructions::ID,
     )?;
-    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+    assert_token_program_matches_package(spl_token_program_info)?;
 
     // account relationships
 
-    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
     if metadata.mint != *ctx.accounts.mint_info.key {
         return Err(MetadataError::MintMismatch.into());
     }
+    // assets using the legacy layout pass the programmable config extension as the first
+    // remaining account
+    metadata.load_programmable_config(ctx.remaining_accounts.first().copied())?;
+
+    // verified collection items cannot be delegated while their collection is paused
+    assert_collection_not_paused(program_id, &metadata, ctx.accounts.collection_pause_info)?;
 
     // authority must be the owner of the token account: spl-token required the
     // token owner to set a delegate
-    let token = Account::unpack(&token_info.try_borrow_data()?).unwrap();
+    let token = assert_initialized_token_account(token_info)?;
     if token.owner != *ctx.accounts.authority_info.key {
         return Err(MetadataError::IncorrectOwner.into());
     }
 
+    // non-transferable assets cannot have token delegates, since a delegate could thaw
+    // (and move) the token
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::NonTransferable)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
     // process the delegation
 
     // programmables assets can have delegates from any role apart from `Standard`
@@ -268,6 +552,18 @@ fn create_persistent_delegate_v1(
                 return Err(MetadataError::InvalidDelegateRole.into());
             }
 
+            // only delegates that can unlock the asset can be created locked
+            if lock
+                && !matches!(
+                    role,
+                    TokenDelegateRole::Utility
+                        | TokenDelegateRole::Staking
+                        | TokenDelegateRole::LockedTransfer
+                )
+            {
+                return Err(MetadataError::InvalidDelegateRole.into());
+            }
+
             let (mut token_record, token_record_info) = match ctx.accounts.token_record_info {
                 Some(token_record_info) => {
                     let (pda_key, _) =
@@ -292,6 +588,11 @@ fn create_persistent_delegate_v1(
                 return Err(MetadataError::DelegateAlreadyExists.into());
             }
 
+            assert_not_holder_locked(&token_record)?;
+            assert_not_collection_frozen(&token_record)?;
+
+            assert_valid_delegate_expiry(expiry)?;
+
             // if we have a rule set, we need to store its revision; at this point,
             // we will validate that we have the correct auth rules PDA
             if let Some(ProgrammableConfig::V1 {
@@ -304,17 +605,18 @@ fn create_persistent_delegate_v1(
                     .authorization_rules_info
                     .ok_or(MetadataError::MissingAuthorizationRules)?;
                 assert_keys_equal(authorization_rules_info.key, &rule_set)?;
-                assert_owned_by(authorization_rules_info, &mpl_token_auth_rules::ID)?;
+                if !is_auth_rules_program(authorization_rules_info.owner) {
+                    return Err(MetadataError::IncorrectOwner.into());
+                }
 
                 // validates auth rules program
                 let authorization_rules_program_info = ctx
                     .accounts
                     .authorization_rules_program_info
                     .ok_or(MetadataError::MissingAuthorizationRulesProgram)?;
-                assert_keys_equal(
-                    authorization_rules_program_info.key,
-                    &mpl_token_auth_rules::ID,
-                )?;
+                if !is_auth_rules_program(authorization_rules_program_info.key) {
+                    return Err(MetadataError::KeyMismatch.into());
+                }
 
                 let auth_rules_validate_params = AuthRulesValidateParams {
                     mint_info: ctx.accounts.mint_info,
@@ -342,7 +644,9 @@ fn create_persistent_delegate_v1(
                     get_latest_revision(authorization_rules_info)?.map(|revision| revision as u64);
             }
 
-            token_record.state = if matches!(role, TokenDelegateRole::Sale) {
+            token_record.state = if lock {
+                TokenState::Locked
+            } else if matches!(role, TokenDelegateRole::Sale) {
                 // when a 'Sale' delegate is set, the token state is 'Listed'
                 // to restrict holder transfers
                 TokenState::Listed
@@ -360,8 +664,22 @@ fn create_persistent_delegate_v1(
                 None
             };
 
+            token_record.staking_program = if matches!(role, TokenDelegateRole::Staking) {
+                if let DelegateArgs::StakingV1 {
+                    staking_program, ..
+                } = args
+                {
+                    Some(*staking_program)
+                } else {
+                    return Err(MetadataError::InvalidDelegateArgs.into());
+                }
+            } else {
+                None
+            };
+
             token_record.delegate = Some(*ctx.accounts.delegate_info.key);
             token_record.delegate_role = Some(role);
+            token_record.delegate_expiry = expiry;
             token_record.save(
                 token_record_info,
                 ctx.accounts.payer_info,
@@ -385,12 +703,17 @@ fn create_persistent_delegate_v1(
             if !matches!(role, TokenDelegateRole::Standard) {
                 return Err(MetadataError::InvalidDelegateRole.into());
             }
+            // the (master) edition is the freeze authority of non-fungibles, which
+            // is required to lock the asset
+            if lock && ctx.accounts.master_edition_info.is_none() {
+                return Err(MetadataError::MissingEditionAccount.into());
+            }
         }
     }
 
     // creates the spl-token delegate
     invoke(
-        &spl_token::instruction::approve(
+        &spl_token_2022::instruction::approve(
             spl_token_program_info.key,
             token_info.key,
             ctx.accounts.delegate_info.key,
@@ -405,10 +728,14 @@ fn create_persistent_delegate_v1(
         ],
     )?;
 
-    if matches!(
-        metadata.token_standard,
-        Some(TokenStandard::ProgrammableNonFungible)
-    ) {
+    // programmable assets are always frozen, while other assets are only frozen
+    // when they are locked
+    if lock
+        || matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        )
+    {
         if let Some(master_edition_info) = ctx.accounts.master_edition_info {
             freeze(
                 ctx.accounts.mint_info.clone(),
@@ -435,6 +762,8 @@ fn create_pda_account<'a>(
     payer_info: &'a AccountInfo<'a>,
     system_program_info: &'a AccountInfo<'a>,
     delegate_role: &str,
+    expiry: Option<i64>,
+    remaining_uses: Option<u64>,
 ) -> ProgramResult {
     // validates the delegate derivation
 
@@ -473,6 +802,8 @@ fn create_pda_account<'a>(
         mint: *mint_info.key,
         delegate: *delegate_info.key,
         update_authority: *authority_info.key,
+        expiry,
+        remaining_uses,
         ..Default::default()
     };
     pda.serialize(&mut *delegate_record_info.try_borrow_mut_data()?)?;
@@ -1,6 +1,9 @@
 #![allow(clippy::module_inception)]
 mod delegate;
+mod registry;
 mod revoke;
+mod rotate_authority;
 
 pub use delegate::*;
 pub use revoke::*;
+pub use rotate_authority::*;
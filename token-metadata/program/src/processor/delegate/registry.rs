@@ -0,0 +1,186 @@
+use mpl_utils::{cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    pda::{find_metadata_delegate_record_account, DELEGATE_REGISTRY, PREFIX},
+    state::{
+        DelegateRegistry, DelegateRegistryEntry, MetadataDelegateRecord, TokenMetadataAccount,
+    },
+};
+
+/// Accounts required to update the delegate registry of a mint.
+pub(crate) struct RegistryAccounts<'a> {
+    pub delegate_registry_info: &'a AccountInfo<'a>,
+    pub mint_info: &'a AccountInfo<'a>,
+    pub payer_info: &'a AccountInfo<'a>,
+    pub system_program_info: &'a AccountInfo<'a>,
+}
+
+/// Adds a metadata delegate to the registry of the mint, creating the registry
+/// account if it does not exist.
+pub(crate) fn register_delegate(
+    program_id: &Pubkey,
+    accounts: RegistryAccounts,
+    entry: DelegateRegistryEntry,
+) -> ProgramResult {
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.mint_info.key.as_ref(),
+        DELEGATE_REGISTRY.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        accounts.delegate_registry_info,
+        &signer_seeds,
+    )?];
+
+    let is_new = accounts.delegate_registry_info.data_is_empty();
+
+    let mut registry = if is_new {
+        DelegateRegistry {
+            bump: bump[0],
+            mint: *accounts.mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(accounts.delegate_registry_info, program_id)?;
+        DelegateRegistry::from_account_info(accounts.delegate_registry_info)?
+    };
+
+    registry.add(entry);
+
+    if is_new {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.delegate_registry_info,
+            accounts.system_program_info,
+            accounts.payer_info,
+            registry.serialized_size(),
+            &signer_seeds,
+        )?;
+    }
+
+    registry.save(
+        accounts.delegate_registry_info,
+        accounts.payer_info,
+        accounts.system_program_info,
+    )
+}
+
+/// Removes a metadata delegate from the registry of the mint.
+///
+/// Delegates created without the registry are not listed, so a missing entry is
+/// not an error.
+pub(crate) fn unregister_delegate(
+    program_id: &Pubkey,
+    accounts: RegistryAccounts,
+    entry: &DelegateRegistryEntry,
+) -> ProgramResult {
+    if let Some(mut registry) = load_registry(program_id, &accounts)? {
+        if registry.remove(entry) {
+            registry.save(
+                accounts.delegate_registry_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes the delegates of the specified (address, record) pairs from the registry of
+/// the mint.
+///
+/// Delegate records do not store their role, so the entries are matched by deriving the
+/// record address from each entry.
+pub(crate) fn unregister_delegate_records(
+    program_id: &Pubkey,
+    accounts: RegistryAccounts,
+    records: &[(Pubkey, MetadataDelegateRecord)],
+) -> ProgramResult {
+    if let Some(mut registry) = load_registry(program_id, &accounts)? {
+        let length = registry.delegates.len();
+
+        registry.delegates.retain(|entry| {
+            !records.iter().any(|(address, record)| {
+                cmp_pubkeys(&entry.delegate, &record.delegate)
+                    && cmp_pubkeys(&entry.approver, &record.update_authority)
+                    && cmp_pubkeys(
+                        &find_metadata_delegate_record_account(
+                            &record.mint,
+                            entry.role,
+                            &entry.approver,
+                            &entry.delegate,
+                        )
+                        .0,
+                        address,
+                    )
+            })
+        });
+
+        if registry.delegates.len() != length {
+            registry.save(
+                accounts.delegate_registry_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes every delegate from the registry of the mint, returning the removed entries.
+pub(crate) fn unregister_all_delegates(
+    program_id: &Pubkey,
+    accounts: RegistryAccounts,
+) -> Result<Vec<DelegateRegistryEntry>, ProgramError> {
+    match load_registry(program_id, &accounts)? {
+        Some(mut registry) if !registry.delegates.is_empty() => {
+            let entries = std::mem::take(&mut registry.delegates);
+            registry.save(
+                accounts.delegate_registry_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )?;
+
+            Ok(entries)
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Loads the registry of the mint, returning `None` if the registry was never created.
+fn load_registry(
+    program_id: &Pubkey,
+    accounts: &RegistryAccounts,
+) -> Result<Option<DelegateRegistry>, ProgramError> {
+    assert_derivation(
+        program_id,
+        accounts.delegate_registry_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.mint_info.key.as_ref(),
+            DELEGATE_REGISTRY.as_bytes(),
+        ],
+    )?;
+
+    if accounts.delegate_registry_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    assert_owned_by(accounts.delegate_registry_info, program_id)?;
+
+    Ok(Some(DelegateRegistry::from_account_info(
+        accounts.delegate_registry_info,
+    )?))
+}
@@ -1,22 +1,30 @@
-use mpl_utils::{assert_signer, close_account_raw, cmp_pubkeys};
+use mpl_utils::{assert_signer, cmp_pubkeys};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, program_option::COption,
     program_pack::Pack, pubkey::Pubkey, system_program, sysvar,
 };
 use spl_token::state::Account;
 
+use super::registry::{unregister_delegate, unregister_delegate_records, RegistryAccounts};
 use crate::{
     assertions::{
-        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+        assert_keys_equal, assert_owned_by,
+        metadata::{assert_state, assert_update_authority_is_correct},
+        programmable::{assert_not_rented, assert_staking_program_unlock},
     },
     error::MetadataError,
     instruction::{Context, MetadataDelegateRole, Revoke, RevokeArgs},
-    pda::{find_metadata_delegate_record_account, find_token_record_account},
+    pda::{
+        find_collection_items_delegate_record_account, find_metadata_delegate_record_account,
+        find_token_record_account,
+    },
+    processor::state::validate_unlock,
     state::{
-        Metadata, MetadataDelegateRecord, Resizable, TokenDelegateRole, TokenMetadataAccount,
-        TokenRecord, TokenStandard,
+        CollectionItemsDelegateRecord, DelegateRegistryEntry, Key, Metadata,
+        MetadataDelegateRecord, Resizable, TokenDelegateRole, TokenMetadataAccount, TokenRecord,
+        TokenStandard, TokenState,
     },
-    utils::{freeze, thaw},
+    utils::{close_account_checked, freeze, thaw},
 };
 
 /// Revoke a delegation of the token.
@@ -27,29 +35,25 @@ pub fn revoke<'a>(
 ) -> ProgramResult {
     let context = Revoke::to_context(accounts)?;
 
-    // checks if it is a TokenDelegate creation
-    let token_delegate = match &args {
-        // Sale
-        RevokeArgs::SaleV1 => Some(TokenDelegateRole::Sale),
-        // Transfer
-        RevokeArgs::TransferV1 => Some(TokenDelegateRole::Transfer),
-        // LockedTransfer
-        RevokeArgs::LockedTransferV1 => Some(TokenDelegateRole::LockedTransfer),
-        // Utility
-        RevokeArgs::UtilityV1 => Some(TokenDelegateRole::Utility),
-        // Staking
-        RevokeArgs::StakingV1 => Some(TokenDelegateRole::Staking),
-        // Migration
-        RevokeArgs::MigrationV1 => Some(TokenDelegateRole::Migration),
-        // Standard
-        RevokeArgs::StandardV1 => Some(TokenDelegateRole::Standard),
-        // we don't need to fail if did not find a match at this point
-        _ => None,
-    };
+    // revokes all delegates of the token owner
+    if matches!(args, RevokeArgs::AllV1) {
+        return revoke_all_v1(program_id, context);
+    }
+
+    // revokes a collection items delegate
+    if matches!(args, RevokeArgs::CollectionItemsV1) {
+        return revoke_collection_items_delegate_v1(program_id, context);
+    }
+
+    // revokes an expired rental delegate
+    if matches!(args, RevokeArgs::RentalV1) {
+        return revoke_rental_delegate_v1(program_id, context);
+    }
 
-    if let Some(role) = token_delegate {
+    // checks if it is a TokenDelegate creation
+    if let Some(role) = get_token_delegate_role(&args) {
         // proceed with the delegate creation if we have a match
-        return revoke_persistent_delegate_v1(program_id, context, role);
+        return revoke_persistent_delegate_v1(program_id, context, role, false);
     }
 
     // checks if it is a MetadataDelegate creation
@@ -57,6 +61,10 @@ pub fn revoke<'a>(
         RevokeArgs::CollectionV1 => Some(MetadataDelegateRole::Collection),
         RevokeArgs::UpdateV1 => Some(MetadataDelegateRole::Update),
         RevokeArgs::ProgrammableConfigV1 => Some(MetadataDelegateRole::ProgrammableConfig),
+        RevokeArgs::DataV1 => Some(MetadataDelegateRole::Data),
+        RevokeArgs::UsesV1 => Some(MetadataDelegateRole::Uses),
+        RevokeArgs::UseV1 => Some(MetadataDelegateRole::Use),
+        RevokeArgs::CollectionFreezeV1 => Some(MetadataDelegateRole::CollectionFreeze),
         // we don't need to fail if did not find a match at this point
         _ => None,
     };
@@ -69,6 +77,50 @@ pub fn revoke<'a>(
     Err(MetadataError::InvalidDelegateArgs.into())
 }
 
+/// Unlocks the asset and revokes the token delegate.
+///
+/// The delegate must sign the transaction to unlock the asset, while the token owner
+/// must sign to revoke the delegate.
+pub fn unlock_and_revoke<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RevokeArgs,
+) -> ProgramResult {
+    // the instruction has the same accounts as the `Revoke` instruction
+    let context = Revoke::to_context(accounts)?;
+
+    if let Some(role) = get_token_delegate_role(&args) {
+        return revoke_persistent_delegate_v1(program_id, context, role, true);
+    }
+
+    // this only happens if we did not find a match
+    Err(MetadataError::InvalidDelegateArgs.into())
+}
+
+/// Returns the role of a token delegate.
+fn get_token_delegate_role(args: &RevokeArgs) -> Option<TokenDelegateRole> {
+    match args {
+        // Sale
+        RevokeArgs::SaleV1 => Some(TokenDelegateRole::Sale),
+        // Transfer
+        RevokeArgs::TransferV1 => Some(TokenDelegateRole::Transfer),
+        // LockedTransfer
+        RevokeArgs::LockedTransferV1 => Some(TokenDelegateRole::LockedTransfer),
+        // Utility
+        RevokeArgs::UtilityV1 => Some(TokenDelegateRole::Utility),
+        // Staking
+        RevokeArgs::StakingV1 => Some(TokenDelegateRole::Staking),
+        // Escrow
+        RevokeArgs::EscrowV1 => Some(TokenDelegateRole::Escrow),
+        // Migration
+        RevokeArgs::MigrationV1 => Some(TokenDelegateRole::Migration),
+        // Standard
+        RevokeArgs::StandardV1 => Some(TokenDelegateRole::Standard),
+        // not a token delegate
+        _ => None,
+    }
+}
+
 fn revoke_delegate_v1(
     program_id: &Pubkey,
     ctx: Context<Revoke>,
@@ -139,13 +191,112 @@ fn revoke_delegate_v1(
         ctx.accounts.mint_info.key,
         &approver,
         ctx.accounts.payer_info,
+    )?;
+
+    // removes the delegate from the registry of the mint
+
+    if let Some(delegate_registry_info) = ctx.accounts.delegate_registry_info {
+        unregister_delegate(
+            program_id,
+            RegistryAccounts {
+                delegate_registry_info,
+                mint_info: ctx.accounts.mint_info,
+                payer_info: ctx.accounts.payer_info,
+                system_program_info: ctx.accounts.system_program_info,
+            },
+            &DelegateRegistryEntry {
+                role,
+                delegate: *ctx.accounts.delegate_info.key,
+                approver,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Revokes a collection items delegate.
+///
+/// The delegate can only be revoked once all the items it locked are unlocked, otherwise
+/// the items would remain locked without an authority able to unlock them.
+fn revoke_collection_items_delegate_v1(program_id: &Pubkey, ctx: Context<Revoke>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    // account relationships
+
+    let delegate_record_info = match ctx.accounts.delegate_record_info {
+        Some(delegate_record_info) => delegate_record_info,
+        None => {
+            return Err(MetadataError::MissingTokenAccount.into());
+        }
+    };
+
+    if delegate_record_info.data_is_empty() {
+        return Err(MetadataError::DelegateNotFound.into());
+    }
+
+    assert_owned_by(delegate_record_info, program_id)?;
+    let delegate_record = CollectionItemsDelegateRecord::from_account_info(delegate_record_info)?;
+
+    // there are two scenarios here:
+    //   1. authority is equal to delegate: delegate as a signer is self-revoking
+    //   2. otherwise the authority must be the holder that approved the delegate
+    if !cmp_pubkeys(&delegate_record.delegate, ctx.accounts.delegate_info.key) {
+        return Err(MetadataError::InvalidDelegate.into());
+    }
+
+    if !cmp_pubkeys(ctx.accounts.authority_info.key, &delegate_record.delegate)
+        && !cmp_pubkeys(ctx.accounts.authority_info.key, &delegate_record.holder)
+    {
+        return Err(MetadataError::UnauthorizedRevoke.into());
+    }
+
+    if !cmp_pubkeys(&delegate_record.collection_mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let (pda_key, _) = find_collection_items_delegate_record_account(
+        ctx.accounts.mint_info.key,
+        &delegate_record.holder,
+        ctx.accounts.delegate_info.key,
+    );
+    assert_keys_equal(&pda_key, delegate_record_info.key)?;
+
+    if delegate_record.locked_items > 0 {
+        return Err(MetadataError::CollectionItemsLocked.into());
+    }
+
+    // closes the delegate record
+
+    close_account_checked(
+        delegate_record_info,
+        ctx.accounts.payer_info,
+        Key::CollectionItemsDelegate,
     )
 }
 
+/// Revokes a persistent delegate. When `unlock` is set, the asset is unlocked as part
+/// of the revoke; in this case, the delegate must be a signer.
 fn revoke_persistent_delegate_v1(
     program_id: &Pubkey,
     ctx: Context<Revoke>,
     role: TokenDelegateRole,
+    unlock: bool,
 ) -> ProgramResult {
     // retrieving required optional accounts
 
@@ -167,6 +318,10 @@ fn revoke_persistent_delegate_v1(
 
     assert_signer(ctx.accounts.payer_info)?;
     assert_signer(ctx.accounts.authority_info)?;
+    if unlock {
+        // only the delegate can unlock the asset
+        assert_signer(ctx.accounts.delegate_info)?;
+    }
 
     // ownership
 
@@ -185,10 +340,13 @@ fn revoke_persistent_delegate_v1(
 
     // account relationships
 
-    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
     if metadata.mint != *ctx.accounts.mint_info.key {
         return Err(MetadataError::MintMismatch.into());
     }
+    // assets using the legacy layout pass the programmable config extension as the first
+    // remaining account
+    metadata.load_programmable_config(ctx.remaining_accounts.first().copied())?;
 
     // authority must be the owner of the token account: spl-token required the
     // token owner to revoke a delegate
@@ -233,6 +391,37 @@ fn revoke_persistent_delegate_v1(
                 }
             };
 
+            if unlock {
+                // only delegates that can lock the asset can unlock it
+                if !matches!(
+                    role,
+                    TokenDelegateRole::Utility
+                        | TokenDelegateRole::Staking
+                        | TokenDelegateRole::LockedTransfer
+                        | TokenDelegateRole::Migration
+                ) {
+                    return Err(MetadataError::InvalidDelegateRole.into());
+                }
+                assert_state(&token_record, TokenState::Locked)?;
+                assert_staking_program_unlock(
+                    &token_record,
+                    ctx.accounts.sysvar_instructions_info,
+                )?;
+                // the rule set cannot receive an authorization payload in this case,
+                // so rule sets requiring one only allow an 'Unlock' from the delegate
+                if matches!(role, TokenDelegateRole::Utility) {
+                    validate_unlock(
+                        ctx.accounts.mint_info,
+                        ctx.accounts.delegate_info,
+                        ctx.accounts.authorization_rules_info,
+                        ctx.accounts.authorization_rules_program_info,
+                        &metadata,
+                        &token_record,
+                        None,
+                    )?;
+                }
+            }
+
             if let Some(delegate) = token_record.delegate {
                 assert_keys_equal(&delegate, ctx.accounts.delegate_info.key)?;
 
@@ -266,6 +455,24 @@ fn revoke_persistent_delegate_v1(
             if !matches!(role, TokenDelegateRole::Standard) {
                 return Err(MetadataError::InvalidDelegateRole.into());
             }
+
+            if unlock {
+                if !token.is_frozen() {
+                    return Err(MetadataError::UnlockedToken.into());
+                }
+                // the (master) edition is the freeze authority of non-fungibles
+                if let Some(master_edition_info) = ctx.accounts.master_edition_info {
+                    // derivation is checked on the thaw function
+                    thaw(
+                        ctx.accounts.mint_info.clone(),
+                        token_info.clone(),
+                        master_edition_info.clone(),
+                        spl_token_program_info.clone(),
+                    )?;
+                } else {
+                    return Err(MetadataError::MissingEditionAccount.into());
+                }
+            }
         }
     }
 
@@ -305,6 +512,315 @@ fn revoke_persistent_delegate_v1(
     Ok(())
 }
 
+/// Revokes an expired `Rental` delegate.
+///
+/// Any signer can revoke the delegate once the rental expires. Since only the token owner
+/// can revoke the spl-token delegate, it is kept when the authority is not the owner: the
+/// token account remains frozen and the delegate is no longer on the token record, so it
+/// has no authority over the asset.
+fn revoke_rental_delegate_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Revoke<'a>>,
+) -> ProgramResult {
+    // retrieving required optional accounts
+
+    let token_info = ctx
+        .accounts
+        .token_info
+        .ok_or(MetadataError::MissingTokenAccount)?;
+
+    let spl_token_program_info = ctx
+        .accounts
+        .spl_token_program_info
+        .ok_or(MetadataError::MissingSplTokenProgram)?;
+
+    let token_record_info = ctx
+        .accounts
+        .token_record_info
+        .ok_or(MetadataError::MissingTokenRecord)?;
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
+    assert_owned_by(token_info, &spl_token::id())?;
+    assert_owned_by(token_record_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+
+    let (pda_key, _) = find_token_record_account(ctx.accounts.mint_info.key, token_info.key);
+    assert_keys_equal(&pda_key, token_record_info.key)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let token = Account::unpack(&token_info.try_borrow_data()?)?;
+    if token.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let mut token_record = TokenRecord::from_account_info(token_record_info)?;
+
+    if token_record.delegate_role != Some(TokenDelegateRole::Rental) {
+        return Err(MetadataError::InvalidDelegateRole.into());
+    }
+
+    match token_record.delegate {
+        Some(delegate) => assert_keys_equal(&delegate, ctx.accounts.delegate_info.key)?,
+        None => return Err(MetadataError::DelegateNotFound.into()),
+    }
+
+    assert_not_rented(&token_record)?;
+
+    // resets the token record (state, rule_set_revision and delegate info)
+    token_record.reset();
+    token_record.save(
+        token_record_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )?;
+
+    if cmp_pubkeys(&token.owner, ctx.accounts.authority_info.key) && token.delegate.is_some() {
+        let master_edition_info = ctx
+            .accounts
+            .master_edition_info
+            .ok_or(MetadataError::MissingEditionAccount)?;
+        assert_owned_by(master_edition_info, &crate::ID)?;
+
+        // derivation is checked on the thaw function
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            token_info.clone(),
+            master_edition_info.clone(),
+            spl_token_program_info.clone(),
+        )?;
+        revoke_token_delegate(
+            spl_token_program_info,
+            token_info,
+            ctx.accounts.authority_info,
+        )?;
+        freeze(
+            ctx.accounts.mint_info.clone(),
+            token_info.clone(),
+            master_edition_info.clone(),
+            spl_token_program_info.clone(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Revokes the token delegate (if any) and closes the metadata delegate records passed
+/// as remaining accounts.
+///
+/// The authority must be the owner of the token account and the approver of the metadata
+/// delegate records. Locked assets must be unlocked by their delegate first.
+fn revoke_all_v1<'a>(program_id: &Pubkey, ctx: Context<'a, Revoke<'a>>) -> ProgramResult {
+    // retrieving required optional accounts
+
+    let token_info = match ctx.accounts.token_info {
+        Some(token_info) => token_info,
+        None => {
+            return Err(MetadataError::MissingTokenAccount.into());
+        }
+    };
+
+    let spl_token_program_info = match ctx.accounts.spl_token_program_info {
+        Some(spl_token_program_info) => spl_token_program_info,
+        None => {
+            return Err(MetadataError::MissingSplTokenProgram.into());
+        }
+    };
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
+    assert_owned_by(token_info, &spl_token::id())?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let token = Account::unpack(&token_info.try_borrow_data()?)?;
+    if token.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if token.owner != *ctx.accounts.authority_info.key {
+        return Err(MetadataError::IncorrectOwner.into());
+    }
+
+    // revokes the token delegate
+
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        let token_record_info = match ctx.accounts.token_record_info {
+            Some(token_record_info) => token_record_info,
+            None => {
+                return Err(MetadataError::MissingTokenRecord.into());
+            }
+        };
+
+        let (pda_key, _) = find_token_record_account(ctx.accounts.mint_info.key, token_info.key);
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+        assert_owned_by(token_record_info, &crate::ID)?;
+
+        let mut token_record = TokenRecord::from_account_info(token_record_info)?;
+
+        if token_record.delegate.is_some() {
+            if token_record.is_locked() {
+                return Err(MetadataError::LockedToken.into());
+            }
+            assert_not_rented(&token_record)?;
+            // resets the token record (state, rule_set_revision and delegate info)
+            token_record.reset();
+            token_record.save(
+                token_record_info,
+                ctx.accounts.payer_info,
+                ctx.accounts.system_program_info,
+            )?;
+        }
+
+        if token.delegate.is_some() {
+            let master_edition_info = match ctx.accounts.master_edition_info {
+                Some(master_edition_info) => master_edition_info,
+                None => {
+                    return Err(MetadataError::MissingEditionAccount.into());
+                }
+            };
+            assert_owned_by(master_edition_info, &crate::ID)?;
+
+            // the token account of programmable assets is always frozen, so it needs
+            // to be thawed to revoke the spl-token delegate (derivation is checked on
+            // the thaw function)
+            thaw(
+                ctx.accounts.mint_info.clone(),
+                token_info.clone(),
+                master_edition_info.clone(),
+                spl_token_program_info.clone(),
+            )?;
+            revoke_token_delegate(
+                spl_token_program_info,
+                token_info,
+                ctx.accounts.authority_info,
+            )?;
+            freeze(
+                ctx.accounts.mint_info.clone(),
+                token_info.clone(),
+                master_edition_info.clone(),
+                spl_token_program_info.clone(),
+            )?;
+        }
+    } else if token.delegate.is_some() {
+        // a frozen token account was locked by its delegate
+        if token.is_frozen() {
+            return Err(MetadataError::LockedToken.into());
+        }
+
+        revoke_token_delegate(
+            spl_token_program_info,
+            token_info,
+            ctx.accounts.authority_info,
+        )?;
+    }
+
+    // closes the metadata delegate records
+
+    let mut records = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    for delegate_record_info in ctx.remaining_accounts {
+        assert_owned_by(delegate_record_info, program_id)?;
+        let delegate_record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+        if delegate_record.mint != *ctx.accounts.mint_info.key {
+            return Err(MetadataError::MintMismatch.into());
+        }
+        // only delegates approved by the authority can be revoked
+        if !cmp_pubkeys(
+            &delegate_record.update_authority,
+            ctx.accounts.authority_info.key,
+        ) {
+            return Err(MetadataError::InvalidDelegate.into());
+        }
+
+        close_account_checked(
+            delegate_record_info,
+            ctx.accounts.payer_info,
+            Key::MetadataDelegate,
+        )?;
+        records.push((*delegate_record_info.key, delegate_record));
+    }
+
+    // removes the delegates from the registry of the mint
+
+    if let Some(delegate_registry_info) = ctx.accounts.delegate_registry_info {
+        unregister_delegate_records(
+            program_id,
+            RegistryAccounts {
+                delegate_registry_info,
+                mint_info: ctx.accounts.mint_info,
+                payer_info: ctx.accounts.payer_info,
+                system_program_info: ctx.accounts.system_program_info,
+            },
+            &records,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Revokes the spl-token delegate of a token account.
+fn revoke_token_delegate<'a>(
+    spl_token_program_info: &'a AccountInfo<'a>,
+    token_info: &'a AccountInfo<'a>,
+    owner_info: &'a AccountInfo<'a>,
+) -> ProgramResult {
+    invoke(
+        &spl_token::instruction::revoke(
+            spl_token_program_info.key,
+            token_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[token_info.clone(), owner_info.clone()],
+    )
+}
+
 /// Closes a delegate PDA.
 ///
 /// It checks that the derivation is correct before closing
@@ -327,6 +843,6 @@ fn close_delegate_record<'a>(
         Err(MetadataError::DerivedKeyInvalid.into())
     } else {
         // closes the delegate account
-        close_account_raw(payer_info, delegate_record_info)
+        close_account_checked(delegate_record_info, payer_info, Key::MetadataDelegate)
     }
 }
@@ -0,0 +1,137 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+use super::registry::{unregister_all_delegates, RegistryAccounts};
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{Context, RotateAuthorityArgs, RotateAuthorityWithPolicy},
+    pda::find_metadata_delegate_record_account,
+    state::{Key, Metadata, TokenMetadataAccount},
+    utils::{clean_write_metadata, close_account_checked},
+};
+
+/// Rotates the update authority of an asset, revoking its metadata delegates.
+pub fn rotate_authority_with_policy<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RotateAuthorityArgs,
+) -> ProgramResult {
+    let context = RotateAuthorityWithPolicy::to_context(accounts)?;
+
+    match args {
+        RotateAuthorityArgs::V1 {
+            new_update_authority,
+            unverify_creators,
+        } => rotate_authority_v1(program_id, context, new_update_authority, unverify_creators),
+    }
+}
+
+fn rotate_authority_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, RotateAuthorityWithPolicy<'a>>,
+    new_update_authority: Pubkey,
+    unverify_creators: bool,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // revokes the metadata delegates listed on the registry (derivation is checked when
+    // loading the registry)
+
+    let entries = unregister_all_delegates(
+        program_id,
+        RegistryAccounts {
+            delegate_registry_info: ctx.accounts.delegate_registry_info,
+            mint_info: ctx.accounts.mint_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    for entry in &entries {
+        let (record_key, _) = find_metadata_delegate_record_account(
+            ctx.accounts.mint_info.key,
+            entry.role,
+            &entry.approver,
+            &entry.delegate,
+        );
+
+        // every listed delegate must be revoked, otherwise the rotation is incomplete
+        let delegate_record_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|info| cmp_pubkeys(info.key, &record_key))
+            .ok_or(MetadataError::DelegateNotFound)?;
+
+        close_account_checked(
+            delegate_record_info,
+            ctx.accounts.payer_info,
+            Key::MetadataDelegate,
+        )?;
+    }
+
+    // unverifies the creators that are not the new authority
+
+    let verified_creators = |metadata: &Metadata| {
+        metadata
+            .data
+            .creators
+            .as_ref()
+            .map_or(0, |creators| creators.iter().filter(|c| c.verified).count())
+    };
+    let verified_before = verified_creators(&metadata);
+
+    if unverify_creators {
+        if let Some(creators) = metadata.data.creators.as_mut() {
+            for creator in creators
+                .iter_mut()
+                .filter(|creator| creator.address != new_update_authority)
+            {
+                creator.verified = false;
+            }
+        }
+    }
+
+    let previous_authority = metadata.update_authority;
+    metadata.set_update_authority(new_update_authority);
+
+    let unverified = verified_before - verified_creators(&metadata);
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    msg!(
+        "Update authority of {} rotated from {} to {} ({} delegates revoked, {} creators unverified)",
+        ctx.accounts.mint_info.key,
+        previous_authority,
+        new_update_authority,
+        entries.len(),
+        unverified
+    );
+
+    Ok(())
+}
@@ -32,6 +32,8 @@ pub fn process_create_metadata_accounts_v2<'a>(
             payer_account_info,
             update_authority_info,
             system_account_info,
+            fee_vault_info: None,
+            mint_authority_approved: false,
         },
         data,
         false,
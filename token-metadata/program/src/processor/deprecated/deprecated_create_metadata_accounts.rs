@@ -32,6 +32,8 @@ pub fn process_deprecated_create_metadata_accounts<'a>(
             payer_account_info,
             update_authority_info,
             system_account_info,
+            fee_vault_info: None,
+            mint_authority_approved: false,
         },
         DataV2 {
             name: data.name,
@@ -12,7 +12,7 @@ use crate::{
     },
     error::MetadataError,
     state::{Data, Metadata, TokenMetadataAccount},
-    utils::puff_out_data_fields,
+    utils::{clean_write_metadata, puff_out_data_fields},
 };
 
 /// Update existing account instruction
@@ -41,14 +41,14 @@ pub fn process_deprecated_update_metadata_accounts(
                 false,
                 update_authority_info.is_signer,
             )?;
-            metadata.data = data;
+            metadata.set_data(data);
         } else {
             return Err(MetadataError::DataIsImmutable.into());
         }
     }
 
     if let Some(val) = update_authority {
-        metadata.update_authority = val;
+        metadata.set_update_authority(val);
     }
 
     if let Some(val) = primary_sale_happened {
@@ -61,6 +61,6 @@ pub fn process_deprecated_update_metadata_accounts(
 
     puff_out_data_fields(&mut metadata);
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    clean_write_metadata(&mut metadata, metadata_account_info)?;
     Ok(())
 }
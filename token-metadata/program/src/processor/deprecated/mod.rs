@@ -5,3 +5,41 @@ mod deprecated_update_metadata_accounts;
 pub use create_medatata_accounts_v2::*;
 pub use deprecated_create_metadata_accounts::*;
 pub use deprecated_update_metadata_accounts::*;
+use solana_program::{entrypoint::ProgramResult, msg};
+
+use crate::error::MetadataError;
+
+/// Logs the use of a deprecated instruction together with the instruction that
+/// replaces it, so the migration to the new API can be tracked from transaction logs.
+///
+/// When the `strict-deprecation` feature is enabled (e.g., on test clusters), the
+/// deprecated instruction fails instead.
+pub(crate) fn log_deprecated(instruction: &str, replacement: &str) -> ProgramResult {
+    msg!(
+        "Deprecated: instruction={} replacement={}",
+        instruction,
+        replacement
+    );
+
+    if cfg!(feature = "strict-deprecation") {
+        return Err(MetadataError::DeprecatedInstruction.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecated_instruction() {
+        let result = log_deprecated("CreateMetadataAccountV3", "Create");
+
+        if cfg!(feature = "strict-deprecation") {
+            assert_eq!(result, Err(MetadataError::DeprecatedInstruction.into()));
+        } else {
+            assert_eq!(result, Ok(()));
+        }
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
         Key, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard, EDITION,
         MAX_MASTER_EDITION_LEN, PREFIX,
     },
-    utils::transfer_mint_authority,
+    utils::{clean_write_metadata, transfer_mint_authority},
 };
 
 /// Create master edition
@@ -96,7 +96,7 @@ pub fn process_create_master_edition(
     if metadata_account_info.is_writable {
         let mut metadata_mut = Metadata::from_account_info(metadata_account_info)?;
         metadata_mut.token_standard = Some(TokenStandard::NonFungible);
-        metadata_mut.serialize(&mut *metadata_account_info.try_borrow_mut_data()?)?;
+        clean_write_metadata(&mut metadata_mut, metadata_account_info)?;
     }
 
     // While you can't mint any more of your master record, you can
@@ -1,7 +1,11 @@
 mod convert_master_edition_v1_to_v2;
 mod create_master_edition_v3;
 mod mint_new_edition_from_master_edition_via_token;
+mod print;
+mod set_print_window;
 
 pub use convert_master_edition_v1_to_v2::*;
 pub use create_master_edition_v3::*;
 pub use mint_new_edition_from_master_edition_via_token::*;
+pub use print::*;
+pub use set_print_window::*;
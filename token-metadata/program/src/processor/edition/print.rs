@@ -0,0 +1,212 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke, pubkey::Pubkey,
+    system_program, sysvar,
+};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{
+        assert_initialized, assert_keys_equal, assert_owned_by, edition::assert_print_window_open,
+    },
+    error::MetadataError,
+    instruction::{Context, Print, PrintArgs},
+    pda::find_token_record_account,
+    state::{Metadata, TokenMetadataAccount, TokenStandard},
+    utils::{
+        assert_associated_token_account, create_associated_token_account,
+        create_token_record_account, freeze,
+        process_mint_new_edition_from_master_edition_via_token_logic,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs,
+    },
+};
+
+/// Prints a new edition from a master edition.
+pub fn print<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: PrintArgs,
+) -> ProgramResult {
+    let context = Print::to_context(accounts)?;
+
+    match args {
+        PrintArgs::V1 { edition } => print_v1(program_id, context, edition),
+    }
+}
+
+fn print_v1<'a>(
+    program_id: &'a Pubkey,
+    ctx: Context<'a, Print<'a>>,
+    edition: u64,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+    assert_signer(ctx.accounts.edition_mint_authority_info)?;
+    assert_signer(ctx.accounts.master_token_account_owner_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.master_metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_edition_info, program_id)?;
+    assert_owned_by(ctx.accounts.edition_mint_info, &spl_token::id())?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(ctx.accounts.spl_token_program_info.key, &spl_token::ID)?;
+
+    // account relationships
+
+    let mut master_metadata = Metadata::from_account_info(ctx.accounts.master_metadata_info)?;
+    // masters using the legacy layout pass the programmable config extension as the first
+    // remaining account
+    master_metadata.load_programmable_config(ctx.remaining_accounts.first().copied())?;
+
+    // only non-fungible assets have (master) editions, and prints of non-transferable
+    // assets would be transferable
+    if matches!(
+        master_metadata.token_standard,
+        Some(TokenStandard::Fungible)
+            | Some(TokenStandard::FungibleAsset)
+            | Some(TokenStandard::NonFungibleEdition)
+            | Some(TokenStandard::NonTransferable)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    assert_print_window_open(
+        program_id,
+        &master_metadata.mint,
+        ctx.accounts.master_edition_extension_info,
+    )?;
+
+    let is_programmable = matches!(
+        master_metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    );
+
+    // validates the token record before any account is created
+    let edition_token_record_info = if is_programmable {
+        let edition_token_record_info = ctx
+            .accounts
+            .edition_token_record_info
+            .ok_or(MetadataError::MissingTokenRecord)?;
+
+        let (pda_key, _) = find_token_record_account(
+            ctx.accounts.edition_mint_info.key,
+            ctx.accounts.edition_token_account_info.key,
+        );
+        assert_keys_equal(&pda_key, edition_token_record_info.key)?;
+
+        Some(edition_token_record_info)
+    } else {
+        None
+    };
+
+    // token account of the new edition
+
+    if ctx.accounts.edition_token_account_info.data_is_empty() {
+        assert_associated_token_account(
+            ctx.accounts.edition_token_account_info,
+            ctx.accounts.edition_token_account_owner_info.key,
+            ctx.accounts.edition_mint_info.key,
+            &spl_token::ID,
+        )?;
+
+        msg!("Init ATA");
+
+        create_associated_token_account(
+            ctx.accounts.payer_info.key,
+            ctx.accounts.edition_token_account_owner_info.key,
+            ctx.accounts.edition_mint_info.key,
+            &spl_token::ID,
+            &[
+                ctx.accounts.payer_info.clone(),
+                ctx.accounts.edition_token_account_owner_info.clone(),
+                ctx.accounts.edition_mint_info.clone(),
+                ctx.accounts.edition_token_account_info.clone(),
+            ],
+        )?;
+    } else {
+        assert_owned_by(ctx.accounts.edition_token_account_info, &spl_token::id())?;
+        let token: Account = assert_initialized(ctx.accounts.edition_token_account_info)?;
+
+        if token.mint != *ctx.accounts.edition_mint_info.key {
+            return Err(MetadataError::MintMismatch.into());
+        }
+    }
+
+    // mints the token of the new edition; the mint authority is transferred to the
+    // edition account when the edition is printed
+    invoke(
+        &spl_token::instruction::mint_to(
+            ctx.accounts.spl_token_program_info.key,
+            ctx.accounts.edition_mint_info.key,
+            ctx.accounts.edition_token_account_info.key,
+            ctx.accounts.edition_mint_authority_info.key,
+            &[],
+            1,
+        )?,
+        &[
+            ctx.accounts.edition_mint_info.clone(),
+            ctx.accounts.edition_token_account_info.clone(),
+            ctx.accounts.edition_mint_authority_info.clone(),
+        ],
+    )?;
+
+    process_mint_new_edition_from_master_edition_via_token_logic(
+        program_id,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs {
+            new_metadata_account_info: ctx.accounts.edition_metadata_info,
+            new_edition_account_info: ctx.accounts.edition_info,
+            master_edition_account_info: ctx.accounts.master_edition_info,
+            mint_info: ctx.accounts.edition_mint_info,
+            edition_marker_info: ctx.accounts.edition_marker_pda_info,
+            mint_authority_info: ctx.accounts.edition_mint_authority_info,
+            payer_account_info: ctx.accounts.payer_info,
+            owner_account_info: ctx.accounts.master_token_account_owner_info,
+            token_account_info: ctx.accounts.master_token_account_info,
+            update_authority_info: ctx.accounts.update_authority_info,
+            master_metadata_account_info: ctx.accounts.master_metadata_info,
+            token_program_account_info: ctx.accounts.spl_token_program_info,
+            system_account_info: ctx.accounts.system_program_info,
+        },
+        edition,
+        false,
+    )?;
+
+    // editions of programmable master editions are also programmable: they share
+    // the rule set of the master edition and are kept in a frozen state
+
+    if let Some(edition_token_record_info) = edition_token_record_info {
+        let mut edition_metadata = Metadata::from_account_info(ctx.accounts.edition_metadata_info)?;
+        edition_metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+        edition_metadata.programmable_config = master_metadata.programmable_config;
+        edition_metadata.use_default_rule_set = master_metadata.use_default_rule_set;
+        edition_metadata.save(&mut ctx.accounts.edition_metadata_info.try_borrow_mut_data()?)?;
+
+        create_token_record_account(
+            program_id,
+            edition_token_record_info,
+            ctx.accounts.edition_mint_info,
+            ctx.accounts.edition_token_account_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+        )?;
+
+        // the edition account is now the freeze authority of the mint
+        freeze(
+            ctx.accounts.edition_mint_info.clone(),
+            ctx.accounts.edition_token_account_info.clone(),
+            ctx.accounts.edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    Ok(())
+}
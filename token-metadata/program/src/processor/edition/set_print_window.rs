@@ -0,0 +1,117 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by,
+        edition::assert_edition_valid,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{Context, SetPrintWindow, SetPrintWindowArgs},
+    pda::{EXTENSION, PREFIX},
+    state::{get_master_edition, MasterEditionExtension, Metadata, TokenMetadataAccount, EDITION},
+};
+
+pub fn set_print_window<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetPrintWindowArgs,
+) -> ProgramResult {
+    let context = SetPrintWindow::to_context(accounts)?;
+
+    match args {
+        SetPrintWindowArgs::V1 { print_window } => {
+            set_print_window_v1(program_id, context, print_window)
+        }
+    }
+}
+
+fn set_print_window_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetPrintWindow>,
+    print_window: Option<(i64, i64)>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_edition_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+    assert_edition_valid(
+        program_id,
+        ctx.accounts.mint_info.key,
+        ctx.accounts.master_edition_info,
+    )?;
+
+    // account relationships
+
+    get_master_edition(ctx.accounts.master_edition_info)
+        .map_err(|_| MetadataError::NotAMasterEdition)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    MasterEditionExtension::validate_print_window(print_window)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        EXTENSION.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.master_edition_extension_info,
+        &signer_seeds,
+    )?];
+
+    let mut extension = if ctx.accounts.master_edition_extension_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.master_edition_extension_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            MasterEditionExtension::size(),
+            &signer_seeds,
+        )?;
+
+        MasterEditionExtension {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.master_edition_extension_info, program_id)?;
+        MasterEditionExtension::from_account_info(ctx.accounts.master_edition_extension_info)?
+    };
+
+    extension.print_window = print_window;
+    extension.serialize(
+        &mut *ctx
+            .accounts
+            .master_edition_extension_info
+            .try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, program_pack::Pack,
+    pubkey::Pubkey, system_program, sysvar,
+};
+
+use super::{find_escrow_seeds, record_escrow_amount, LedgerAccounts};
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, metadata::assert_metadata_derivation,
+    },
+    error::MetadataError,
+    instruction::{Context, EscrowDeposit, EscrowDepositArgs},
+    state::{TokenMetadataAccount, TokenOwnedEscrow},
+    utils::{assert_associated_token_account, create_associated_token_account, is_spl_ata_program},
+};
+
+/// Deposits tokens into the token-owned escrow of an asset.
+///
+/// The tokens are held by the associated token account of the escrow for their mint and
+/// the amount held is recorded in the ledger of the escrow.
+pub fn escrow_deposit<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: EscrowDepositArgs,
+) -> ProgramResult {
+    let context = EscrowDeposit::to_context(accounts)?;
+
+    match args {
+        EscrowDepositArgs::V1 { amount } => escrow_deposit_v1(program_id, context, amount),
+    }
+}
+
+fn escrow_deposit_v1(
+    program_id: &Pubkey,
+    ctx: Context<EscrowDeposit>,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Err(MetadataError::AmountMustBeGreaterThanZero.into());
+    }
+
+    // signers
+
+    assert_signer(ctx.accounts.depositor_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.escrow_info, program_id)?;
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.escrow_mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.attribute_mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.attribute_src_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(ctx.accounts.spl_token_program_info.key, &spl_token::ID)?;
+
+    if !is_spl_ata_program(ctx.accounts.spl_ata_program_info.key) {
+        return Err(MetadataError::InvalidAssociatedTokenAccountProgram.into());
+    }
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.escrow_mint_info,
+    )?;
+
+    let toe = TokenOwnedEscrow::from_account_info(ctx.accounts.escrow_info)?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.escrow_info,
+        &find_escrow_seeds(ctx.accounts.escrow_mint_info.key, &toe.authority),
+    )?;
+
+    assert_associated_token_account(
+        ctx.accounts.attribute_dst_info,
+        ctx.accounts.escrow_info.key,
+        ctx.accounts.attribute_mint_info.key,
+        &spl_token::ID,
+    )?;
+
+    // account relationships
+
+    if ctx.accounts.attribute_mint_info.key == ctx.accounts.escrow_mint_info.key {
+        return Err(MetadataError::CannotDepositIntoOwnEscrow.into());
+    }
+
+    let attribute_src =
+        spl_token::state::Account::unpack(&ctx.accounts.attribute_src_info.data.borrow())?;
+    if attribute_src.mint != *ctx.accounts.attribute_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if attribute_src.amount < amount {
+        return Err(MetadataError::InsufficientTokens.into());
+    }
+
+    // allocate the escrow token account if it doesn't exist
+    if ctx.accounts.attribute_dst_info.data_is_empty() {
+        create_associated_token_account(
+            ctx.accounts.payer_info.key,
+            ctx.accounts.escrow_info.key,
+            ctx.accounts.attribute_mint_info.key,
+            &spl_token::ID,
+            &[
+                ctx.accounts.payer_info.clone(),
+                ctx.accounts.attribute_dst_info.clone(),
+                ctx.accounts.escrow_info.clone(),
+                ctx.accounts.attribute_mint_info.clone(),
+                ctx.accounts.system_program_info.clone(),
+                ctx.accounts.spl_token_program_info.clone(),
+                ctx.accounts.spl_ata_program_info.clone(),
+            ],
+        )?;
+    }
+
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            ctx.accounts.attribute_src_info.key,
+            ctx.accounts.attribute_dst_info.key,
+            ctx.accounts.depositor_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            ctx.accounts.attribute_src_info.clone(),
+            ctx.accounts.attribute_dst_info.clone(),
+            ctx.accounts.depositor_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        ],
+    )?;
+
+    // the ledger records the balance of the escrow, which includes tokens transferred
+    // to the escrow token account outside of this instruction
+    let attribute_dst =
+        spl_token::state::Account::unpack(&ctx.accounts.attribute_dst_info.data.borrow())?;
+
+    record_escrow_amount(
+        program_id,
+        LedgerAccounts {
+            escrow_ledger_info: ctx.accounts.escrow_ledger_info,
+            escrow_info: ctx.accounts.escrow_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+        ctx.accounts.attribute_mint_info.key,
+        attribute_dst.amount,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::program_error::ProgramError;
+
+    use super::*;
+    use crate::programs;
+
+    /// Executes a deposit with accounts that pass the ownership and program checks, returning
+    /// the error raised when the associated token account program is validated.
+    fn deposit_with_ata_program() -> ProgramError {
+        let program_id = crate::ID;
+
+        let mut keys = [(); 13].map(|_| Pubkey::new_unique());
+        keys[9] = system_program::ID;
+        keys[10] = sysvar::instructions::ID;
+        keys[11] = spl_token::ID;
+        keys[12] = programs::SPL_ATA_PROGRAM;
+
+        // escrow, metadata, escrow mint, escrow ledger, attribute mint and source
+        let owners = [
+            program_id,
+            program_id,
+            spl_token::ID,
+            program_id,
+            spl_token::ID,
+            spl_token::ID,
+        ];
+
+        let mut lamports = [0; 13];
+        let mut data: [Vec<u8>; 13] = Default::default();
+
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .enumerate()
+            .map(|(index, ((key, lamports), data))| {
+                AccountInfo::new(
+                    key,
+                    // depositor and payer
+                    index == 7 || index == 8,
+                    false,
+                    lamports,
+                    data,
+                    owners.get(index).unwrap_or(&system_program::ID),
+                    false,
+                    0,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        escrow_deposit(&program_id, &accounts, EscrowDepositArgs::V1 { amount: 1 }).unwrap_err()
+    }
+
+    #[test]
+    #[cfg(not(feature = "ata"))]
+    fn fail_deposit_without_ata_feature() {
+        // the associated token account program is not recognized without the `ata` feature
+        assert_eq!(
+            deposit_with_ata_program(),
+            MetadataError::InvalidAssociatedTokenAccountProgram.into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ata")]
+    fn deposit_accepts_ata_program_with_ata_feature() {
+        // the deposit fails later, when validating the derivation of the metadata account
+        assert_ne!(
+            deposit_with_ata_program(),
+            MetadataError::InvalidAssociatedTokenAccountProgram.into()
+        );
+    }
+}
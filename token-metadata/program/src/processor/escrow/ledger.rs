@@ -0,0 +1,121 @@
+use mpl_utils::create_or_allocate_account_raw;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    state::{EscrowLedger, TokenMetadataAccount, ESCROW_LEDGER_POSTFIX, PREFIX},
+};
+
+/// Accounts required to update the ledger of an escrow.
+pub(crate) struct LedgerAccounts<'a> {
+    pub escrow_ledger_info: &'a AccountInfo<'a>,
+    pub escrow_info: &'a AccountInfo<'a>,
+    pub payer_info: &'a AccountInfo<'a>,
+    pub system_program_info: &'a AccountInfo<'a>,
+}
+
+/// Records the amount of tokens of the mint held by the escrow, creating the ledger
+/// account if it does not exist.
+pub(crate) fn record_escrow_amount(
+    program_id: &Pubkey,
+    accounts: LedgerAccounts,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.escrow_info.key.as_ref(),
+        ESCROW_LEDGER_POSTFIX.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        accounts.escrow_ledger_info,
+        &signer_seeds,
+    )?];
+
+    let is_new = accounts.escrow_ledger_info.data_is_empty();
+
+    let mut ledger = if is_new {
+        EscrowLedger {
+            bump: bump[0],
+            escrow: *accounts.escrow_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(accounts.escrow_ledger_info, program_id)?;
+        EscrowLedger::from_account_info(accounts.escrow_ledger_info)?
+    };
+
+    ledger.set_amount(mint, amount);
+
+    if is_new {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.escrow_ledger_info,
+            accounts.system_program_info,
+            accounts.payer_info,
+            ledger.serialized_size(),
+            &signer_seeds,
+        )?;
+    }
+
+    ledger.save(
+        accounts.escrow_ledger_info,
+        accounts.payer_info,
+        accounts.system_program_info,
+    )
+}
+
+/// Updates the amount of tokens of the mint held by the escrow.
+///
+/// Escrows without a ledger do not track their tokens, so a missing ledger is not an error.
+pub(crate) fn update_escrow_amount(
+    program_id: &Pubkey,
+    accounts: LedgerAccounts,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    if let Some(mut ledger) = load_ledger(program_id, &accounts)? {
+        ledger.set_amount(mint, amount);
+        ledger.save(
+            accounts.escrow_ledger_info,
+            accounts.payer_info,
+            accounts.system_program_info,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Loads the ledger of the escrow, returning `None` if the ledger was never created.
+fn load_ledger(
+    program_id: &Pubkey,
+    accounts: &LedgerAccounts,
+) -> Result<Option<EscrowLedger>, ProgramError> {
+    assert_derivation(
+        program_id,
+        accounts.escrow_ledger_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.escrow_info.key.as_ref(),
+            ESCROW_LEDGER_POSTFIX.as_bytes(),
+        ],
+    )?;
+
+    if accounts.escrow_ledger_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    assert_owned_by(accounts.escrow_ledger_info, program_id)?;
+
+    Ok(Some(EscrowLedger::from_account_info(
+        accounts.escrow_ledger_info,
+    )?))
+}
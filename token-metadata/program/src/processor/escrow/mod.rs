@@ -1,9 +1,13 @@
 mod close_escrow_account;
 mod create_escrow_account;
+mod deposit;
+mod ledger;
 mod pda;
 mod transfer_out;
 
 pub use close_escrow_account::*;
 pub use create_escrow_account::*;
+pub use deposit::*;
+pub(crate) use ledger::*;
 pub use pda::*;
 pub use transfer_out::*;
@@ -1,6 +1,6 @@
 use solana_program::pubkey::Pubkey;
 
-use crate::state::{EscrowAuthority, ESCROW_POSTFIX, PREFIX};
+use crate::state::{EscrowAuthority, ESCROW_LEDGER_POSTFIX, ESCROW_POSTFIX, PREFIX};
 
 pub fn find_escrow_seeds<'a>(mint: &'a Pubkey, authority: &'a EscrowAuthority) -> Vec<&'a [u8]> {
     let mut seeds = vec![PREFIX.as_bytes(), crate::ID.as_ref(), mint.as_ref()];
@@ -18,3 +18,15 @@ pub fn find_escrow_account(mint: &Pubkey, authority: &EscrowAuthority) -> (Pubke
     let seeds = find_escrow_seeds(mint, authority);
     Pubkey::find_program_address(&seeds, &crate::id())
 }
+
+pub fn find_escrow_ledger_account(escrow: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            escrow.as_ref(),
+            ESCROW_LEDGER_POSTFIX.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
@@ -2,18 +2,28 @@ use mpl_utils::assert_signer;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    program::{invoke, invoke_signed},
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_option::COption,
     program_pack::Pack,
     pubkey::Pubkey,
+    system_program, sysvar,
 };
 use spl_token::state::is_initialized_account;
 
-use super::find_escrow_seeds;
+use super::{find_escrow_seeds, update_escrow_amount, LedgerAccounts};
 use crate::{
-    assertions::{assert_derivation, assert_owned_by},
+    assertions::{
+        assert_delegate, assert_derivation, assert_keys_equal, assert_owned_by,
+        metadata::assert_metadata_derivation, programmable::assert_token_delegate_not_expired,
+    },
     error::MetadataError,
-    instruction::TransferOutOfEscrowArgs,
-    state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow},
+    instruction::{Context, EscrowTransferOut, EscrowTransferOutArgs, TransferOutOfEscrowArgs},
+    pda::find_token_record_account,
+    state::{
+        EscrowAuthority, TokenDelegateRole, TokenMetadataAccount, TokenOwnedEscrow, TokenRecord,
+    },
+    utils::{create_associated_token_account, is_spl_ata_program},
 };
 
 pub fn process_transfer_out_of_escrow(
@@ -55,7 +65,7 @@ pub fn process_transfer_out_of_escrow(
     }
 
     let ata_program_info = next_account_info(account_info_iter)?;
-    if ata_program_info.key != &spl_associated_token_account::ID {
+    if !is_spl_ata_program(ata_program_info.key) {
         return Err(MetadataError::InvalidAssociatedTokenAccountProgram.into());
     }
 
@@ -69,8 +79,9 @@ pub fn process_transfer_out_of_escrow(
         return Err(MetadataError::InvalidInstructionsSysvar.into());
     }
 
-    // Allow the option to set a different authority than the payer.
-    let is_using_authority = account_info_iter.len() == 1;
+    // Allow the option to set a different authority than the payer, optionally
+    // followed by the token record of an escrow delegate.
+    let is_using_authority = matches!(account_info_iter.len(), 1 | 2);
     let maybe_authority_info: Option<&AccountInfo> = if is_using_authority {
         let auth = next_account_info(account_info_iter)?;
         assert_signer(auth)?;
@@ -80,6 +91,172 @@ pub fn process_transfer_out_of_escrow(
     };
     let authority = maybe_authority_info.unwrap_or(payer_info);
 
+    // The token record is only required when the authority is an escrow delegate.
+    let maybe_token_record_info: Option<&AccountInfo> = if account_info_iter.len() == 1 {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
+
+    transfer_out(
+        &EscrowTransferAccounts {
+            escrow_info,
+            payer_info,
+            authority_info: authority,
+            attribute_mint_info,
+            attribute_src_info,
+            attribute_dst_info,
+            escrow_mint_info,
+            escrow_account_info,
+            token_record_info: maybe_token_record_info,
+            system_program_info,
+            ata_program_info,
+            token_program_info,
+        },
+        args.amount,
+        &[TokenDelegateRole::Escrow],
+    )?;
+
+    Ok(())
+}
+
+/// Transfers tokens out of the token-owned escrow of an asset.
+///
+/// Escrows owned by the holder of the asset can also be operated by an `Escrow`, `Utility`
+/// or `Transfer` delegate of the asset, in which case the token record is required. The
+/// remaining amount of tokens is recorded in the ledger of the escrow, when provided.
+pub fn escrow_transfer_out<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: EscrowTransferOutArgs,
+) -> ProgramResult {
+    let context = EscrowTransferOut::to_context(accounts)?;
+
+    match args {
+        EscrowTransferOutArgs::V1 { amount } => escrow_transfer_out_v1(program_id, context, amount),
+    }
+}
+
+fn escrow_transfer_out_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, EscrowTransferOut<'a>>,
+    amount: u64,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.escrow_info, program_id)?;
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.escrow_mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.escrow_token_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.attribute_mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.attribute_src_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(ctx.accounts.spl_token_program_info.key, &spl_token::ID)?;
+
+    if !is_spl_ata_program(ctx.accounts.spl_ata_program_info.key) {
+        return Err(MetadataError::InvalidAssociatedTokenAccountProgram.into());
+    }
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.escrow_mint_info,
+    )?;
+
+    let remaining_amount = transfer_out(
+        &EscrowTransferAccounts {
+            escrow_info: ctx.accounts.escrow_info,
+            payer_info: ctx.accounts.payer_info,
+            authority_info: ctx.accounts.authority_info,
+            attribute_mint_info: ctx.accounts.attribute_mint_info,
+            attribute_src_info: ctx.accounts.attribute_src_info,
+            attribute_dst_info: ctx.accounts.attribute_dst_info,
+            escrow_mint_info: ctx.accounts.escrow_mint_info,
+            escrow_account_info: ctx.accounts.escrow_token_info,
+            token_record_info: ctx.accounts.escrow_token_record_info,
+            system_program_info: ctx.accounts.system_program_info,
+            ata_program_info: ctx.accounts.spl_ata_program_info,
+            token_program_info: ctx.accounts.spl_token_program_info,
+        },
+        amount,
+        &[
+            TokenDelegateRole::Escrow,
+            TokenDelegateRole::Utility,
+            TokenDelegateRole::Transfer,
+        ],
+    )?;
+
+    if let Some(escrow_ledger_info) = ctx.accounts.escrow_ledger_info {
+        update_escrow_amount(
+            program_id,
+            LedgerAccounts {
+                escrow_ledger_info,
+                escrow_info: ctx.accounts.escrow_info,
+                payer_info: ctx.accounts.payer_info,
+                system_program_info: ctx.accounts.system_program_info,
+            },
+            ctx.accounts.attribute_mint_info.key,
+            remaining_amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Accounts used to transfer tokens out of a token-owned escrow.
+#[derive(Clone, Copy)]
+struct EscrowTransferAccounts<'a, 'b> {
+    escrow_info: &'a AccountInfo<'b>,
+    payer_info: &'a AccountInfo<'b>,
+    authority_info: &'a AccountInfo<'b>,
+    attribute_mint_info: &'a AccountInfo<'b>,
+    attribute_src_info: &'a AccountInfo<'b>,
+    attribute_dst_info: &'a AccountInfo<'b>,
+    escrow_mint_info: &'a AccountInfo<'b>,
+    escrow_account_info: &'a AccountInfo<'b>,
+    token_record_info: Option<&'a AccountInfo<'b>>,
+    system_program_info: &'a AccountInfo<'b>,
+    ata_program_info: &'a AccountInfo<'b>,
+    token_program_info: &'a AccountInfo<'b>,
+}
+
+/// Transfers `amount` tokens from the escrow to the destination token account, which is
+/// created if needed. Delegates of the holder of the asset can operate the escrow when their
+/// role is one of `delegate_roles`.
+///
+/// Returns the amount of tokens left in the escrow token account.
+fn transfer_out(
+    accounts: &EscrowTransferAccounts,
+    amount: u64,
+    delegate_roles: &[TokenDelegateRole],
+) -> Result<u64, ProgramError> {
+    let EscrowTransferAccounts {
+        escrow_info,
+        payer_info,
+        authority_info: authority,
+        attribute_mint_info,
+        attribute_src_info,
+        attribute_dst_info,
+        escrow_mint_info,
+        escrow_account_info,
+        token_record_info: maybe_token_record_info,
+        system_program_info,
+        ata_program_info,
+        token_program_info,
+    } = *accounts;
+
     let toe = TokenOwnedEscrow::from_account_info(escrow_info)?;
 
     // Derive the seeds for PDA signing.
@@ -90,17 +267,11 @@ pub fn process_transfer_out_of_escrow(
 
     // Allocate the target ATA if it doesn't exist.
     if !is_initialized_account(&attribute_dst_info.data.borrow()) {
-        #[allow(deprecated)]
-        let create_escrow_ata_ix =
-            spl_associated_token_account::instruction::create_associated_token_account(
-                payer_info.key,
-                payer_info.key,
-                attribute_mint_info.key,
-                &spl_token::ID,
-            );
-
-        invoke(
-            &create_escrow_ata_ix,
+        create_associated_token_account(
+            payer_info.key,
+            payer_info.key,
+            attribute_mint_info.key,
+            &spl_token::ID,
             &[
                 payer_info.clone(),
                 attribute_dst_info.clone(),
@@ -117,7 +288,7 @@ pub fn process_transfer_out_of_escrow(
     if attribute_src.mint != *attribute_mint_info.key {
         return Err(MetadataError::MintMismatch.into());
     }
-    if attribute_src.amount < args.amount {
+    if attribute_src.amount < amount {
         return Err(MetadataError::InsufficientTokens.into());
     }
     if attribute_src.delegated_amount != 0 {
@@ -136,7 +307,17 @@ pub fn process_transfer_out_of_escrow(
     match toe.authority {
         EscrowAuthority::TokenOwner => {
             if escrow_account.owner != *authority.key {
-                return Err(MetadataError::MustBeEscrowAuthority.into());
+                // the holder can delegate the management of the escrow
+                let token_record_info =
+                    maybe_token_record_info.ok_or(MetadataError::MustBeEscrowAuthority)?;
+                assert_escrow_delegate(
+                    authority,
+                    escrow_mint_info,
+                    escrow_account_info,
+                    &escrow_account,
+                    token_record_info,
+                    delegate_roles,
+                )?;
             }
         }
         EscrowAuthority::Creator(creator) => {
@@ -158,7 +339,7 @@ pub fn process_transfer_out_of_escrow(
         attribute_dst_info.key,
         escrow_info.key,
         &[escrow_info.key],
-        args.amount,
+        amount,
     )?;
 
     invoke_signed(
@@ -196,5 +377,36 @@ pub fn process_transfer_out_of_escrow(
         )?;
     }
 
+    Ok(attribute_src.amount)
+}
+
+/// Checks that the authority is a token delegate, with one of the specified roles, of the
+/// token account holding the asset the escrow is attached to.
+fn assert_escrow_delegate(
+    authority_info: &AccountInfo,
+    escrow_mint_info: &AccountInfo,
+    escrow_account_info: &AccountInfo,
+    escrow_account: &spl_token::state::Account,
+    token_record_info: &AccountInfo,
+    delegate_roles: &[TokenDelegateRole],
+) -> ProgramResult {
+    let (pda_key, _) = find_token_record_account(escrow_mint_info.key, escrow_account_info.key);
+    assert_keys_equal(&pda_key, token_record_info.key)?;
+    assert_owned_by(token_record_info, &crate::ID)?;
+
+    let token_record = TokenRecord::from_account_info(token_record_info)?;
+    if !delegate_roles
+        .iter()
+        .any(|role| assert_delegate(authority_info.key, *role, &token_record).is_ok())
+    {
+        return Err(MetadataError::MustBeEscrowAuthority.into());
+    }
+    assert_token_delegate_not_expired(&token_record)?;
+
+    // the token record delegate always matches the spl-token delegate
+    if escrow_account.delegate != COption::Some(*authority_info.key) {
+        return Err(MetadataError::MustBeEscrowAuthority.into());
+    }
+
     Ok(())
 }
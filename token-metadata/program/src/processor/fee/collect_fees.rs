@@ -0,0 +1,72 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
+    pubkey::Pubkey, system_instruction, system_program,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_keys_equal},
+    error::MetadataError,
+    instruction::{CollectFees, CollectFeesArgs, Context},
+    pda::{FEE_VAULT, PREFIX},
+    utils::SEED_AUTHORITY,
+};
+
+pub fn collect_fees<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CollectFeesArgs,
+) -> ProgramResult {
+    let context = CollectFees::to_context(accounts)?;
+
+    match args {
+        CollectFeesArgs::V1 => collect_fees_v1(program_id, context),
+    }
+}
+
+fn collect_fees_v1(program_id: &Pubkey, ctx: Context<CollectFees>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    if *ctx.accounts.authority_info.key != SEED_AUTHORITY {
+        return Err(MetadataError::InvalidFeeAuthority.into());
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        ctx.accounts.fee_vault_info,
+        &[PREFIX.as_bytes(), program_id.as_ref(), FEE_VAULT.as_bytes()],
+    )?;
+
+    let amount = ctx.accounts.fee_vault_info.lamports();
+
+    if amount > 0 {
+        invoke_signed(
+            &system_instruction::transfer(
+                ctx.accounts.fee_vault_info.key,
+                ctx.accounts.recipient_info.key,
+                amount,
+            ),
+            &[
+                ctx.accounts.fee_vault_info.clone(),
+                ctx.accounts.recipient_info.clone(),
+                ctx.accounts.system_program_info.clone(),
+            ],
+            &[&[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                FEE_VAULT.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+    }
+
+    msg!("Collected {} lamports of fees", amount);
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod collect_fees;
+
+pub use collect_fees::*;
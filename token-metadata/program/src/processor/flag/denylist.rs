@@ -0,0 +1,137 @@
+use borsh::BorshSerialize;
+use mpl_utils::{cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
+
+use super::{assert_flag_accounts, FlagAccounts};
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    error::MetadataError,
+    instruction::{AddToDenylist, Context, DenylistArgs, RemoveFromDenylist},
+    pda::{DENYLISTED, PREFIX},
+    state::{Denylisted, Key, TokenMetadataAccount},
+    utils::close_account_checked,
+};
+
+/// Adds an asset to the stolen asset denylist.
+pub fn add_to_denylist<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: DenylistArgs,
+) -> ProgramResult {
+    let context = AddToDenylist::to_context(accounts)?;
+
+    match args {
+        DenylistArgs::V1 => add_to_denylist_v1(program_id, context),
+    }
+}
+
+/// Removes an asset from the stolen asset denylist.
+pub fn remove_from_denylist<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: DenylistArgs,
+) -> ProgramResult {
+    let context = RemoveFromDenylist::to_context(accounts)?;
+
+    match args {
+        DenylistArgs::V1 => remove_from_denylist_v1(program_id, context),
+    }
+}
+
+fn add_to_denylist_v1(program_id: &Pubkey, ctx: Context<AddToDenylist>) -> ProgramResult {
+    assert_flag_accounts(
+        program_id,
+        &FlagAccounts {
+            mint_info: ctx.accounts.mint_info,
+            metadata_info: ctx.accounts.metadata_info,
+            authority_info: ctx.accounts.authority_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        DENYLISTED.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.denylisted_info,
+        &signer_seeds,
+    )?];
+
+    if !ctx.accounts.denylisted_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    signer_seeds.push(bump);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.denylisted_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        Denylisted::size(),
+        &signer_seeds,
+    )?;
+
+    let denylisted = Denylisted {
+        bump: bump[0],
+        mint: *ctx.accounts.mint_info.key,
+        payer: *ctx.accounts.payer_info.key,
+        ..Default::default()
+    };
+    denylisted.serialize(&mut *ctx.accounts.denylisted_info.try_borrow_mut_data()?)?;
+
+    msg!("{} added to the denylist", ctx.accounts.mint_info.key);
+
+    Ok(())
+}
+
+fn remove_from_denylist_v1(program_id: &Pubkey, ctx: Context<RemoveFromDenylist>) -> ProgramResult {
+    assert_flag_accounts(
+        program_id,
+        &FlagAccounts {
+            mint_info: ctx.accounts.mint_info,
+            metadata_info: ctx.accounts.metadata_info,
+            authority_info: ctx.accounts.authority_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.denylisted_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            DENYLISTED.as_bytes(),
+        ],
+    )?;
+
+    if ctx.accounts.denylisted_info.data_is_empty() {
+        return Err(MetadataError::Uninitialized.into());
+    }
+
+    assert_owned_by(ctx.accounts.denylisted_info, program_id)?;
+    let denylisted = Denylisted::from_account_info(ctx.accounts.denylisted_info)?;
+
+    // the rent returns to the payer of the account
+    if !cmp_pubkeys(&denylisted.payer, ctx.accounts.rent_destination_info.key) {
+        return Err(MetadataError::InvalidRentDestination.into());
+    }
+
+    close_account_checked(
+        ctx.accounts.denylisted_info,
+        ctx.accounts.rent_destination_info,
+        Key::Denylisted,
+    )?;
+
+    msg!("{} removed from the denylist", ctx.accounts.mint_info.key);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use borsh::BorshSerialize;
+use mpl_utils::create_or_allocate_account_raw;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{assert_flag_accounts, FlagAccounts};
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    instruction::{Context, Flag, FlagArgs},
+    pda::{FLAGGED, PREFIX},
+    state::{FlagReason, Flagged, TokenMetadataAccount},
+};
+
+pub fn flag<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: FlagArgs,
+) -> ProgramResult {
+    let context = Flag::to_context(accounts)?;
+
+    match args {
+        FlagArgs::V1 { reason } => flag_v1(program_id, context, reason),
+    }
+}
+
+fn flag_v1(program_id: &Pubkey, ctx: Context<Flag>, reason: FlagReason) -> ProgramResult {
+    assert_flag_accounts(
+        program_id,
+        &FlagAccounts {
+            mint_info: ctx.accounts.mint_info,
+            metadata_info: ctx.accounts.metadata_info,
+            authority_info: ctx.accounts.authority_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        FLAGGED.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.flagged_info,
+        &signer_seeds,
+    )?];
+
+    let mut flagged = if ctx.accounts.flagged_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.flagged_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            Flagged::size(),
+            &signer_seeds,
+        )?;
+
+        Flagged {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            payer: *ctx.accounts.payer_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.flagged_info, program_id)?;
+        Flagged::from_account_info(ctx.accounts.flagged_info)?
+    };
+
+    flagged.set(reason);
+    flagged.serialize(&mut *ctx.accounts.flagged_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+#![allow(clippy::module_inception)]
+mod denylist;
+mod flag;
+mod unflag;
+
+pub use denylist::*;
+pub use flag::*;
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+pub use unflag::*;
+
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by, metadata::assert_metadata_derivation},
+    error::MetadataError,
+    state::{Metadata, TokenMetadataAccount},
+    utils::SEED_AUTHORITY,
+};
+
+pub(crate) struct FlagAccounts<'a> {
+    mint_info: &'a AccountInfo<'a>,
+    metadata_info: &'a AccountInfo<'a>,
+    authority_info: &'a AccountInfo<'a>,
+    payer_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+}
+
+/// Validates the accounts shared by the flag instructions.
+///
+/// Flags can only be set or cleared by the protocol authority, which is the same
+/// authority allowed to create metadata for SPL tokens (`SEED_AUTHORITY`).
+pub(crate) fn assert_flag_accounts(program_id: &Pubkey, accounts: &FlagAccounts) -> ProgramResult {
+    // signers
+
+    assert_signer(accounts.authority_info)?;
+    assert_signer(accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(accounts.metadata_info, program_id)?;
+    assert_owned_by(accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(program_id, accounts.metadata_info, accounts.mint_info)?;
+
+    if *accounts.authority_info.key != SEED_AUTHORITY {
+        return Err(MetadataError::InvalidFlagAuthority.into());
+    }
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(accounts.metadata_info)?;
+    if metadata.mint != *accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    Ok(())
+}
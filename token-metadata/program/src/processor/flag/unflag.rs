@@ -0,0 +1,224 @@
+use borsh::BorshSerialize;
+use mpl_utils::cmp_pubkeys;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{assert_flag_accounts, FlagAccounts};
+use crate::{
+    assertions::{assert_derivation, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, Unflag, UnflagArgs},
+    pda::{FLAGGED, PREFIX},
+    state::{FlagReason, Flagged, Key, TokenMetadataAccount},
+    utils::close_account_checked,
+};
+
+pub fn unflag<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UnflagArgs,
+) -> ProgramResult {
+    let context = Unflag::to_context(accounts)?;
+
+    match args {
+        UnflagArgs::V1 { reason } => unflag_v1(program_id, context, reason),
+    }
+}
+
+fn unflag_v1(program_id: &Pubkey, ctx: Context<Unflag>, reason: FlagReason) -> ProgramResult {
+    assert_flag_accounts(
+        program_id,
+        &FlagAccounts {
+            mint_info: ctx.accounts.mint_info,
+            metadata_info: ctx.accounts.metadata_info,
+            authority_info: ctx.accounts.authority_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.flagged_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            FLAGGED.as_bytes(),
+        ],
+    )?;
+
+    if ctx.accounts.flagged_info.data_is_empty() {
+        return Err(MetadataError::Uninitialized.into());
+    }
+
+    assert_owned_by(ctx.accounts.flagged_info, program_id)?;
+    let mut flagged = Flagged::from_account_info(ctx.accounts.flagged_info)?;
+
+    if !flagged.is_set(reason) {
+        return Err(MetadataError::FlagNotSet.into());
+    }
+
+    flagged.clear(reason);
+
+    if flagged.flags == 0 {
+        // no flags left: closes the account and returns the rent to the payer of the account
+        if !cmp_pubkeys(&flagged.payer, ctx.accounts.rent_destination_info.key) {
+            return Err(MetadataError::InvalidRentDestination.into());
+        }
+
+        close_account_checked(
+            ctx.accounts.flagged_info,
+            ctx.accounts.rent_destination_info,
+            Key::Flagged,
+        )
+    } else {
+        flagged
+            .serialize(&mut *ctx.accounts.flagged_info.try_borrow_mut_data()?)
+            .map_err(|_| MetadataError::BorshSerializationError.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_program::{account_info::AccountInfo, program_error::ProgramError, system_program};
+
+    use super::*;
+    use crate::{
+        pda::{find_flagged_account, find_metadata_account},
+        state::{Metadata, MAX_METADATA_LEN},
+        utils::SEED_AUTHORITY,
+        ID,
+    };
+
+    #[test]
+    fn fail_unflag_with_rent_destination_other_than_payer() {
+        let mint = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let (flagged_key, bump) = find_flagged_account(&mint);
+        let mut flagged = Flagged {
+            bump,
+            mint,
+            payer,
+            ..Default::default()
+        };
+        flagged.set(FlagReason::Spam);
+        let mut flagged_data = flagged.try_to_vec().unwrap();
+        let mut flagged_lamports = 1_000_000;
+
+        let (metadata_key, _) = find_metadata_account(&mint);
+        let mut metadata_data = Metadata {
+            mint,
+            ..Default::default()
+        }
+        .try_to_vec()
+        .unwrap();
+        metadata_data.resize(MAX_METADATA_LEN, 0);
+        let mut metadata_lamports = 1_000_000;
+
+        let mut mint_data = vec![];
+        let mut mint_lamports = 1_000_000;
+        let mut authority_data = vec![];
+        let mut authority_lamports = 0;
+        let mut payer_data = vec![];
+        let mut payer_lamports = 1_000_000;
+        let mut system_program_data = vec![];
+        let mut system_program_lamports = 1;
+
+        // the rent destination is not the payer of the flagged account
+        let rent_destination = Pubkey::new_unique();
+        let mut rent_destination_data = vec![];
+        let mut rent_destination_lamports = 0;
+
+        let accounts = [
+            AccountInfo::new(
+                &flagged_key,
+                false,
+                true,
+                &mut flagged_lamports,
+                &mut flagged_data,
+                &ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &mint,
+                false,
+                false,
+                &mut mint_lamports,
+                &mut mint_data,
+                &spl_token::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &metadata_key,
+                false,
+                false,
+                &mut metadata_lamports,
+                &mut metadata_data,
+                &ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &SEED_AUTHORITY,
+                true,
+                false,
+                &mut authority_lamports,
+                &mut authority_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &payer,
+                true,
+                true,
+                &mut payer_lamports,
+                &mut payer_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &system_program::ID,
+                false,
+                false,
+                &mut system_program_lamports,
+                &mut system_program_data,
+                &system_program::ID,
+                true,
+                0,
+            ),
+            AccountInfo::new(
+                &rent_destination,
+                false,
+                true,
+                &mut rent_destination_lamports,
+                &mut rent_destination_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+        ];
+
+        let error = unflag(
+            &ID,
+            &accounts,
+            UnflagArgs::V1 {
+                reason: FlagReason::Spam,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            ProgramError::from(MetadataError::InvalidRentDestination)
+        );
+
+        // the flagged account is left untouched
+        assert_eq!(accounts[0].lamports(), 1_000_000);
+        assert_eq!(accounts[6].lamports(), 0);
+    }
+}
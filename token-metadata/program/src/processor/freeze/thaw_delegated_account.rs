@@ -35,6 +35,10 @@ pub fn process_thaw_delegated_account(
     assert_owned_by(edition_info, program_id)?;
     assert_freeze_authority_matches_mint(&mint.freeze_authority, edition_info)?;
 
+    // check that we are not dealing with a pNFT (or non-transferable) master edition,
+    // whatever the delegate of the token account
+    assert_edition_is_not_programmable(edition_info)?;
+
     // assert delegate is signer and delegated tokens
     assert_signer(delegate_info)?;
     assert_delegated_tokens(delegate_info, mint_info, token_account_info)?;
@@ -51,9 +55,6 @@ pub fn process_thaw_delegated_account(
         &edition_info_path,
     )?];
 
-    // check that we are not dealing with a pNFT master edition
-    assert_edition_is_not_programmable(edition_info)?;
-
     let mut edition_info_seeds = edition_info_path.clone();
     edition_info_seeds.push(edition_info_path_bump_seed);
     invoke_signed(
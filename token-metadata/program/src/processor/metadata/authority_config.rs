@@ -0,0 +1,109 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, CreateAuthorityConfig, CreateAuthorityConfigArgs, MetadataInstruction},
+    pda::{AUTHORITY_CONFIG, PREFIX},
+    state::{authority_action_digest, AuthorityConfig, TokenMetadataAccount},
+};
+
+pub fn create_authority_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CreateAuthorityConfigArgs,
+) -> ProgramResult {
+    let context = CreateAuthorityConfig::to_context(accounts)?;
+
+    match args {
+        CreateAuthorityConfigArgs::V1 { threshold, signers } => {
+            create_authority_config_v1(program_id, context, threshold, signers)
+        }
+    }
+}
+
+fn create_authority_config_v1(
+    program_id: &Pubkey,
+    ctx: Context<CreateAuthorityConfig>,
+    threshold: u8,
+    signers: Vec<Pubkey>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.base_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.base_info.key.as_ref(),
+        AUTHORITY_CONFIG.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.authority_config_info,
+        &signer_seeds,
+    )?];
+
+    if !ctx.accounts.authority_config_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    let mut config = AuthorityConfig {
+        bump: bump[0],
+        base: *ctx.accounts.base_info.key,
+        ..Default::default()
+    };
+    config.set_signers(threshold, signers)?;
+
+    signer_seeds.push(bump);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.authority_config_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        AuthorityConfig::size(),
+        &signer_seeds,
+    )?;
+
+    config.serialize(&mut *ctx.accounts.authority_config_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+/// Records the approval of `signer_info` for `instruction` on the `target` account.
+///
+/// Returns `true` once the threshold of the authority config is reached, in which case
+/// the instruction should be executed with the authority config as its authority.
+pub(crate) fn approve_authority_action(
+    program_id: &Pubkey,
+    authority_config_info: &AccountInfo,
+    signer_info: &AccountInfo,
+    target: &Pubkey,
+    instruction: &MetadataInstruction,
+) -> Result<bool, ProgramError> {
+    assert_signer(signer_info)?;
+    assert_owned_by(authority_config_info, program_id)?;
+
+    let mut config = AuthorityConfig::from_account_info(authority_config_info)?;
+    let digest = authority_action_digest(instruction)?;
+
+    let approved = config.approve(signer_info.key, target, digest)?;
+    config.serialize(&mut *authority_config_info.try_borrow_mut_data()?)?;
+
+    if !approved {
+        msg!("Approval recorded for {}", signer_info.key);
+    }
+
+    Ok(approved)
+}
@@ -0,0 +1,80 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_owned_by, assert_owned_by_token_program},
+    error::MetadataError,
+    instruction::BatchSetTokenStandardArgs,
+    state::{Metadata, TokenMetadataAccount, EDITION, PREFIX},
+    utils::{check_token_standard, clean_write_metadata},
+};
+
+/// Sets the token standard of assets created without one.
+///
+/// The assets are passed in `(metadata, mint, edition)` triplets. The edition account is
+/// always the edition PDA of the mint, so the token standard cannot be influenced by the
+/// caller: when the edition account does not exist, the asset is fungible.
+pub fn batch_set_token_standard<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BatchSetTokenStandardArgs,
+) -> ProgramResult {
+    match args {
+        BatchSetTokenStandardArgs::V1 => batch_set_token_standard_v1(program_id, accounts),
+    }
+}
+
+fn batch_set_token_standard_v1(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    if accounts.is_empty() || accounts.len() % 3 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut updated = 0;
+
+    for asset in accounts.chunks(3) {
+        let (metadata_info, mint_info, edition_info) = (&asset[0], &asset[1], &asset[2]);
+
+        assert_owned_by(metadata_info, program_id)?;
+        let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+        // assets with a token standard are skipped so the same backfill can be submitted
+        // more than once
+        if metadata.token_standard.is_some() {
+            continue;
+        }
+
+        if &metadata.mint != mint_info.key {
+            return Err(MetadataError::MintMismatch.into());
+        }
+        assert_owned_by_token_program(mint_info)?;
+
+        assert_derivation(
+            program_id,
+            edition_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                EDITION.as_bytes(),
+            ],
+        )?;
+
+        let token_standard = if edition_info.data_is_empty() {
+            check_token_standard(mint_info, None)?
+        } else {
+            assert_owned_by(edition_info, program_id)?;
+            check_token_standard(mint_info, Some(edition_info))?
+        };
+
+        metadata.token_standard = Some(token_standard);
+        clean_write_metadata(&mut metadata, metadata_info)?;
+
+        updated += 1;
+    }
+
+    msg!("Set the token standard of {} assets", updated);
+
+    Ok(())
+}
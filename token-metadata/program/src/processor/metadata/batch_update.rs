@@ -0,0 +1,165 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
+
+use crate::{
+    assertions::{
+        assert_owned_by, assert_writable, metadata::assert_metadata_derivation,
+        programmable::assert_metadata_delegate_not_expired,
+    },
+    error::MetadataError,
+    instruction::{BatchUpdate, Context, MetadataDelegateRole, UpdateArgs},
+    state::{AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, TokenMetadataAccount},
+    utils::clean_write_metadata,
+};
+
+/// Applies the same update to the metadata of multiple assets of a collection.
+///
+/// The metadata accounts are passed as remaining accounts. Assets without a token standard
+/// set are not supported, since the token standard cannot be determined without their mint
+/// and edition accounts.
+pub fn batch_update<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateArgs,
+) -> ProgramResult {
+    let context = BatchUpdate::to_context(accounts)?;
+
+    match args {
+        UpdateArgs::V1 { .. } => batch_update_v1(program_id, context, args),
+    }
+}
+
+fn batch_update_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, BatchUpdate<'a>>,
+    args: UpdateArgs,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.collection_mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.collection_mint_info,
+    )?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+
+    // the authority must be either the update authority of the collection or a
+    // collection delegate
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            authority: ctx.accounts.authority_info.key,
+            update_authority: &collection_metadata.update_authority,
+            mint: ctx.accounts.collection_mint_info.key,
+            metadata_delegate_record_info: ctx.accounts.delegate_record_info,
+            metadata_delegate_roles: vec![MetadataDelegateRole::Collection],
+            precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+            ..Default::default()
+        })?;
+
+    // expired delegates can no longer update the assets
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) =
+        (&authority_type, ctx.accounts.delegate_record_info)
+    {
+        assert_metadata_delegate_not_expired(delegate_record_info)?;
+    }
+
+    validate_batch_update(&args, &authority_type)?;
+
+    msg!("Updating {} assets", ctx.remaining_accounts.len());
+
+    for metadata_info in ctx.remaining_accounts {
+        assert_owned_by(metadata_info, program_id)?;
+        assert_writable(metadata_info)?;
+
+        let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+        match &metadata.collection {
+            Some(collection)
+                if collection.verified
+                    && cmp_pubkeys(&collection.key, ctx.accounts.collection_mint_info.key) => {}
+            _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+        }
+
+        // collection delegates are derived from the update authority of the collection, so
+        // they can only act on assets sharing the same update authority
+        if !cmp_pubkeys(
+            &metadata.update_authority,
+            &collection_metadata.update_authority,
+        ) {
+            return Err(MetadataError::UpdateAuthorityIncorrect.into());
+        }
+
+        // the update is applied on behalf of the update authority of the asset, after the
+        // arguments were restricted to the fields the authority is allowed to update
+        metadata.update_v1(
+            args.clone(),
+            ctx.accounts.authority_info,
+            None,
+            None,
+            AuthorityType::Metadata,
+            None,
+        )?;
+        clean_write_metadata(&mut metadata, metadata_info)?;
+    }
+
+    Ok(())
+}
+
+/// Validates that the authority is only updating metadata fields that
+/// can be updated in batch.
+fn validate_batch_update(args: &UpdateArgs, authority_type: &AuthorityType) -> ProgramResult {
+    let UpdateArgs::V1 {
+        new_update_authority,
+        is_mutable,
+        collection,
+        collection_details,
+        rule_set,
+        use_default_rule_set,
+        unverify_creators_on_authority_change,
+        content_rating,
+        ..
+    } = args;
+
+    // rule sets require the token account of each asset and content ratings the
+    // record of each asset
+    if rule_set.is_some() || use_default_rule_set.is_some() || content_rating.is_some() {
+        return Err(MetadataError::InvalidUpdateArgs.into());
+    }
+
+    match authority_type {
+        AuthorityType::Metadata => {
+            msg!("Auth type: Metadata");
+        }
+        AuthorityType::MetadataDelegate => {
+            msg!("Auth type: Delegate");
+
+            // collection delegates can only update the data, primary sale, uses and
+            // royalty inheritance of the assets
+            if new_update_authority.is_some()
+                || is_mutable.is_some()
+                || collection.is_some()
+                || collection_details.is_some()
+                || unverify_creators_on_authority_change.is_some()
+            {
+                return Err(MetadataError::InvalidUpdateArgs.into());
+            }
+        }
+        _ => {
+            return Err(MetadataError::InvalidAuthorityType.into());
+        }
+    }
+
+    Ok(())
+}
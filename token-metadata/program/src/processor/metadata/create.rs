@@ -1,16 +1,17 @@
-use mpl_utils::assert_initialized;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke, program_pack::Pack,
     pubkey::Pubkey, rent::Rent, system_instruction, sysvar::Sysvar,
 };
-use spl_token::{native_mint::DECIMALS, state::Mint};
+use spl_token::native_mint::DECIMALS;
+use spl_token_2022::{extension::ExtensionType, state::Mint};
 
 use crate::{
+    assertions::{assert_initialized_mint, assert_owned_by, assert_token_program_matches_package},
     error::MetadataError,
     instruction::{Context, Create, CreateArgs},
     state::{
-        Metadata, ProgrammableConfig, TokenMetadataAccount, TokenStandard, MAX_MASTER_EDITION_LEN,
-        TOKEN_STANDARD_INDEX,
+        AssetData, Metadata, PrintSupply, ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+        MAX_MASTER_EDITION_LEN, TOKEN_STANDARD_INDEX,
     },
     utils::{
         create_master_edition, process_create_metadata_accounts_logic,
@@ -30,24 +31,63 @@ pub fn create<'a>(
     let context = Create::to_context(accounts)?;
 
     match args {
-        CreateArgs::V1 { .. } => create_v1(program_id, context, args),
+        CreateArgs::V1 {
+            asset_data,
+            decimals,
+            print_supply,
+        } => create_v1(
+            program_id,
+            context,
+            asset_data,
+            decimals,
+            print_supply,
+            None,
+        ),
+        CreateArgs::V2 {
+            asset_data,
+            decimals,
+            print_supply,
+            permanent_delegate,
+        } => create_v1(
+            program_id,
+            context,
+            asset_data,
+            decimals,
+            print_supply,
+            permanent_delegate,
+        ),
     }
 }
 
 /// V1 implementation of the create instruction.
-fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> ProgramResult {
-    // get the args for the instruction
-    let CreateArgs::V1 {
-        ref asset_data,
-        decimals,
-        print_supply,
-    } = args;
-
+///
+/// `V2` only adds the (optional) permanent delegate, so both versions share the
+/// same implementation.
+fn create_v1(
+    program_id: &Pubkey,
+    ctx: Context<Create>,
+    asset_data: AssetData,
+    decimals: Option<u8>,
+    print_supply: Option<PrintSupply>,
+    permanent_delegate: Option<Pubkey>,
+) -> ProgramResult {
     // cannot create non-fungible editions on this instruction
     if matches!(asset_data.token_standard, TokenStandard::NonFungibleEdition) {
         return Err(MetadataError::InvalidTokenStandard.into());
     }
 
+    // the mint can be owned by either spl-token or Token-2022
+    assert_token_program_matches_package(ctx.accounts.spl_token_program_info)?;
+
+    // the permanent delegate transfers through the `PermanentDelegate` extension of
+    // Token-2022, which can only be added when the mint is initialized
+    if permanent_delegate.is_some()
+        && (!ctx.accounts.mint_info.data_is_empty()
+            || ctx.accounts.spl_token_program_info.key != &spl_token_2022::ID)
+    {
+        return Err(MetadataError::InvalidPermanentDelegateMint.into());
+    }
+
     // if the account does not exist, we will allocate a new mint
 
     if ctx.accounts.mint_info.data_is_empty() {
@@ -58,13 +98,19 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
 
         msg!("Init mint");
 
+        let mint_len = if permanent_delegate.is_some() {
+            ExtensionType::get_account_len::<Mint>(&[ExtensionType::PermanentDelegate])
+        } else {
+            Mint::LEN
+        };
+
         invoke(
             &system_instruction::create_account(
                 ctx.accounts.payer_info.key,
                 ctx.accounts.mint_info.key,
-                Rent::get()?.minimum_balance(spl_token::state::Mint::LEN),
-                spl_token::state::Mint::LEN as u64,
-                &spl_token::id(),
+                Rent::get()?.minimum_balance(mint_len),
+                mint_len as u64,
+                ctx.accounts.spl_token_program_info.key,
             ),
             &[
                 ctx.accounts.payer_info.clone(),
@@ -75,7 +121,9 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
         let decimals = match asset_data.token_standard {
             // for NonFungible variants, we ignore the argument and
             // always use 0 decimals
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible => 0,
+            TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::NonTransferable => 0,
             // for Fungile variants, we either use the specified decimals or the default
             // DECIMALS from spl-token
             TokenStandard::FungibleAsset | TokenStandard::Fungible => match decimals {
@@ -88,9 +136,22 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
             }
         };
 
+        if permanent_delegate.is_some() {
+            // the metadata account is the permanent delegate of the mint, so the program
+            // can sign transfers of the permanent delegate of the asset
+            invoke(
+                &spl_token_2022::instruction::initialize_permanent_delegate(
+                    ctx.accounts.spl_token_program_info.key,
+                    ctx.accounts.mint_info.key,
+                    ctx.accounts.metadata_info.key,
+                )?,
+                &[ctx.accounts.mint_info.clone()],
+            )?;
+        }
+
         // initializing the mint account
         invoke(
-            &spl_token::instruction::initialize_mint2(
+            &spl_token_2022::instruction::initialize_mint2(
                 ctx.accounts.spl_token_program_info.key,
                 ctx.accounts.mint_info.key,
                 ctx.accounts.authority_info.key,
@@ -105,19 +166,26 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
     } else {
         // validates the existing mint account
 
-        let mint: Mint = assert_initialized(ctx.accounts.mint_info, MetadataError::Uninitialized)?;
+        assert_owned_by(
+            ctx.accounts.mint_info,
+            ctx.accounts.spl_token_program_info.key,
+        )?;
+        let mint = assert_initialized_mint(ctx.accounts.mint_info)?;
         // NonFungible assets must have decimals == 0 and supply no greater than 1
         if matches!(
             asset_data.token_standard,
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible
+            TokenStandard::NonFungible
+                | TokenStandard::ProgrammableNonFungible
+                | TokenStandard::NonTransferable
         ) && (mint.decimals > 0 || mint.supply > 1)
         {
             return Err(MetadataError::InvalidMintForTokenStandard.into());
         }
-        // Programmable assets must have supply == 0
+        // Programmable and non-transferable assets must have supply == 0, since their
+        // token is frozen when minted
         if matches!(
             asset_data.token_standard,
-            TokenStandard::ProgrammableNonFungible
+            TokenStandard::ProgrammableNonFungible | TokenStandard::NonTransferable
         ) && (mint.supply > 0)
         {
             return Err(MetadataError::MintSupplyMustBeZero.into());
@@ -135,6 +203,8 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
             payer_account_info: ctx.accounts.payer_info,
             update_authority_info: ctx.accounts.update_authority_info,
             system_account_info: ctx.accounts.system_program_info,
+            fee_vault_info: ctx.accounts.fee_vault_info,
+            mint_authority_approved: false,
         },
         asset_data.as_data_v2(),
         false,
@@ -148,10 +218,19 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
 
     if matches!(
         asset_data.token_standard,
-        TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible
+        TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::NonTransferable
     ) {
         let print_supply = print_supply.ok_or(MetadataError::MissingPrintSupply)?;
 
+        // prints of a non-transferable asset would be transferable
+        if matches!(asset_data.token_standard, TokenStandard::NonTransferable)
+            && print_supply != PrintSupply::Zero
+        {
+            return Err(MetadataError::InvalidTokenStandard.into());
+        }
+
         if let Some(master_edition) = ctx.accounts.master_edition_info {
             create_master_edition(
                 program_id,
@@ -166,11 +245,11 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
                 print_supply.to_option(),
             )?;
 
-            // for pNFTs, we store the token standard value at the end of the
-            // master edition account
+            // for pNFTs and non-transferable assets, we store the token standard value
+            // at the end of the master edition account
             if matches!(
                 asset_data.token_standard,
-                TokenStandard::ProgrammableNonFungible
+                TokenStandard::ProgrammableNonFungible | TokenStandard::NonTransferable
             ) {
                 let mut data = master_edition.data.borrow_mut();
 
@@ -178,7 +257,7 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
                     return Err(MetadataError::InvalidMasterEditionAccountLength.into());
                 }
 
-                data[TOKEN_STANDARD_INDEX] = TokenStandard::ProgrammableNonFungible as u8;
+                data[TOKEN_STANDARD_INDEX] = asset_data.token_standard as u8;
             }
         } else {
             return Err(MetadataError::MissingMasterEditionAccount.into());
@@ -189,6 +268,8 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
 
     let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
     metadata.token_standard = Some(asset_data.token_standard);
+    // the permanent delegate can only be set on creation
+    metadata.permanent_delegate = permanent_delegate;
 
     // sets the programmable config for programmable assets
 
@@ -0,0 +1,168 @@
+use mpl_utils::{assert_signer, cmp_pubkeys, token::SPL_TOKEN_PROGRAM_IDS};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, Sysvar},
+};
+use spl_token::state::Multisig;
+
+use crate::{
+    assertions::{
+        assert_freeze_authority_matches_mint, assert_initialized_mint, assert_keys_equal,
+        assert_owned_by_token_program,
+    },
+    error::MetadataError,
+    instruction::{
+        create_existing_mint_metadata_message, Context, CreateExistingMintMetadata,
+        CreateExistingMintMetadataArgs,
+    },
+    state::DataV2,
+    utils::{
+        ed25519_signers, process_create_metadata_accounts_logic, CreateMetadataAccountsLogicArgs,
+    },
+};
+
+/// Creates the metadata of an existing mint approved by the freeze authority or by a
+/// message signed by the mint authority.
+pub fn create_existing_mint_metadata<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CreateExistingMintMetadataArgs,
+) -> ProgramResult {
+    let context = CreateExistingMintMetadata::to_context(accounts)?;
+
+    // the signed message covers the metadata account and the serialized arguments
+    let message = create_existing_mint_metadata_message(
+        context.accounts.metadata_info.key,
+        context.accounts.mint_info.key,
+        context.accounts.update_authority_info.key,
+        &args,
+    );
+
+    match args {
+        CreateExistingMintMetadataArgs::V1 {
+            data,
+            is_mutable,
+            expiry_slot,
+        } => create_existing_mint_metadata_v1(
+            program_id,
+            context,
+            data,
+            is_mutable,
+            expiry_slot,
+            &message,
+        ),
+    }
+}
+
+fn create_existing_mint_metadata_v1(
+    program_id: &Pubkey,
+    ctx: Context<CreateExistingMintMetadata>,
+    data: DataV2,
+    is_mutable: bool,
+    expiry_slot: Option<u64>,
+    message: &[u8],
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    // account relationships
+
+    let mint = assert_initialized_mint(ctx.accounts.mint_info)?;
+    let authority_info = ctx.accounts.authority_info;
+
+    let is_freeze_authority = authority_info.is_signer
+        && assert_freeze_authority_matches_mint(&mint.freeze_authority, authority_info).is_ok();
+
+    if !is_freeze_authority {
+        match mint.mint_authority {
+            COption::Some(key) if cmp_pubkeys(&key, authority_info.key) => (),
+            _ => return Err(MetadataError::InvalidMintAuthority.into()),
+        }
+
+        // signed messages must expire, so they cannot be replayed indefinitely
+        let expiry_slot = expiry_slot.ok_or(MetadataError::InvalidMintAuthoritySignature)?;
+
+        if Clock::get()?.slot > expiry_slot {
+            return Err(MetadataError::MintAuthoritySignatureExpired.into());
+        }
+
+        let signers = ed25519_signers(ctx.accounts.sysvar_instructions_info, message)?;
+        assert_mint_authority_signed(authority_info, &signers)?;
+    }
+
+    process_create_metadata_accounts_logic(
+        program_id,
+        CreateMetadataAccountsLogicArgs {
+            metadata_account_info: ctx.accounts.metadata_info,
+            mint_info: ctx.accounts.mint_info,
+            mint_authority_info: authority_info,
+            payer_account_info: ctx.accounts.payer_info,
+            update_authority_info: ctx.accounts.update_authority_info,
+            system_account_info: ctx.accounts.system_program_info,
+            fee_vault_info: None,
+            mint_authority_approved: true,
+        },
+        data,
+        false,
+        is_mutable,
+        false,
+        true,
+        None,
+    )
+}
+
+/// Asserts that the mint authority signed the message: either the authority is one of the
+/// `signers` or, for an SPL Token multisig authority, at least the required number of the
+/// multisig signers are.
+fn assert_mint_authority_signed(authority_info: &AccountInfo, signers: &[Pubkey]) -> ProgramResult {
+    if signers
+        .iter()
+        .any(|signer| cmp_pubkeys(signer, authority_info.key))
+    {
+        return Ok(());
+    }
+
+    if !SPL_TOKEN_PROGRAM_IDS.contains(authority_info.owner)
+        || authority_info.data_len() != Multisig::LEN
+    {
+        return Err(MetadataError::InvalidMintAuthoritySignature.into());
+    }
+
+    let multisig = Multisig::unpack_from_slice(&authority_info.try_borrow_data()?)?;
+
+    if !multisig.is_initialized() {
+        return Err(MetadataError::InvalidMintAuthoritySignature.into());
+    }
+
+    let approvals = multisig
+        .signers
+        .iter()
+        .take(multisig.n as usize)
+        .filter(|key| signers.iter().any(|signer| cmp_pubkeys(signer, key)))
+        .count();
+
+    if approvals < multisig.m as usize {
+        return Err(MetadataError::InvalidMintAuthoritySignature.into());
+    }
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use solana_program::{
 };
 
 use crate::{
+    processor::try_get_optional_account_info,
     state::{CollectionDetails, DataV2},
     utils::{process_create_metadata_accounts_logic, CreateMetadataAccountsLogicArgs},
 };
@@ -23,6 +24,13 @@ pub fn process_create_metadata_accounts_v3<'a>(
     let payer_account_info = next_account_info(account_info_iter)?;
     let update_authority_info = next_account_info(account_info_iter)?;
     let system_account_info = next_account_info(account_info_iter)?;
+    // the fee vault follows the (optional) rent account and it is only
+    // present on instructions from clients aware of the create fee
+    let fee_vault_info = if accounts.len() > 7 {
+        try_get_optional_account_info(accounts, 7)?
+    } else {
+        None
+    };
 
     process_create_metadata_accounts_logic(
         program_id,
@@ -33,6 +41,8 @@ pub fn process_create_metadata_accounts_v3<'a>(
             payer_account_info,
             update_authority_info,
             system_account_info,
+            fee_vault_info,
+            mint_authority_approved: false,
         },
         data,
         false,
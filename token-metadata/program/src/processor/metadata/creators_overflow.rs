@@ -0,0 +1,195 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::{
+            assert_creators_overflow_valid, assert_metadata_derivation,
+            assert_update_authority_is_correct,
+        },
+    },
+    error::MetadataError,
+    instruction::{Context, SetCreatorsOverflow, SetCreatorsOverflowArgs},
+    pda::{CREATORS_OVERFLOW, PREFIX},
+    state::{Creator, CreatorsOverflow, Key, Metadata, TokenMetadataAccount},
+    utils::{clean_write_metadata_with_payer, close_account_checked},
+};
+
+/// Sets the creators of an asset, storing the creators that do not fit in the metadata
+/// on the `CreatorsOverflow` account.
+pub fn set_creators_overflow<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetCreatorsOverflowArgs,
+) -> ProgramResult {
+    let context = SetCreatorsOverflow::to_context(accounts)?;
+
+    match args {
+        SetCreatorsOverflowArgs::V1 {
+            creators,
+            overflow_creators,
+        } => set_creators_overflow_v1(program_id, context, creators, overflow_creators),
+    }
+}
+
+fn set_creators_overflow_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetCreatorsOverflow>,
+    creators: Vec<Creator>,
+    overflow_creators: Vec<Creator>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        CREATORS_OVERFLOW.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.creators_overflow_info,
+        &signer_seeds,
+    )?];
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let existing_overflow = if ctx.accounts.creators_overflow_info.data_is_empty() {
+        None
+    } else {
+        Some(CreatorsOverflow::from_account_info(
+            ctx.accounts.creators_overflow_info,
+        )?)
+    };
+
+    let existing_creators = metadata
+        .all_creators(existing_overflow.as_ref())?
+        .unwrap_or_default();
+
+    assert_creators_overflow_valid(
+        &creators,
+        &overflow_creators,
+        &existing_creators,
+        &metadata.update_authority,
+    )?;
+
+    metadata.data.creators = Some(creators);
+
+    if overflow_creators.is_empty() {
+        metadata.overflow_creators_share = None;
+
+        if existing_overflow.is_some() {
+            close_account_checked(
+                ctx.accounts.creators_overflow_info,
+                ctx.accounts.payer_info,
+                Key::CreatorsOverflow,
+            )?;
+        }
+    } else {
+        let overflow = CreatorsOverflow {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            creators: overflow_creators,
+            ..Default::default()
+        };
+        metadata.overflow_creators_share = Some(overflow.share()?);
+
+        if existing_overflow.is_none() {
+            signer_seeds.push(bump);
+
+            create_or_allocate_account_raw(
+                *program_id,
+                ctx.accounts.creators_overflow_info,
+                ctx.accounts.system_program_info,
+                ctx.accounts.payer_info,
+                CreatorsOverflow::size(),
+                &signer_seeds,
+            )?;
+        }
+
+        overflow.serialize(&mut *ctx.accounts.creators_overflow_info.try_borrow_mut_data()?)?;
+    }
+
+    clean_write_metadata_with_payer(
+        &mut metadata,
+        ctx.accounts.metadata_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
+
+/// Sets the verification of a creator stored on the creators overflow account of an asset.
+///
+/// This is used by `VerifyV2` and `Unverify` when the creator is not found on the metadata.
+pub(crate) fn set_overflow_creator_verification(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    creators_overflow_info: Option<&AccountInfo>,
+    creator: &Pubkey,
+    verified: bool,
+) -> ProgramResult {
+    if metadata.overflow_creators_share.is_none() {
+        return Err(MetadataError::CreatorNotFound.into());
+    }
+
+    let creators_overflow_info =
+        creators_overflow_info.ok_or(MetadataError::MissingCreatorsOverflow)?;
+
+    // ownership
+
+    assert_owned_by(creators_overflow_info, program_id)?;
+
+    // account relationships
+
+    let mut overflow = CreatorsOverflow::from_account_info(creators_overflow_info)?;
+
+    if overflow.mint != metadata.mint {
+        return Err(MetadataError::InvalidCreatorsOverflow.into());
+    }
+
+    let overflow_creator = overflow
+        .creators
+        .iter_mut()
+        .find(|overflow_creator| overflow_creator.address == *creator)
+        .ok_or(MetadataError::CreatorNotFound)?;
+
+    if overflow_creator.verified == verified {
+        return Err(if verified {
+            MetadataError::AlreadyVerified.into()
+        } else {
+            MetadataError::AlreadyUnverified.into()
+        });
+    }
+
+    overflow_creator.verified = verified;
+    overflow.serialize(&mut *creators_overflow_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -1,25 +1,35 @@
-use mpl_token_auth_rules::utils::assert_owned_by;
-use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use mpl_utils::{assert_signer, cmp_pubkeys, create_or_allocate_account_raw};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    program_option::COption, pubkey, pubkey::Pubkey, system_program, sysvar,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, Sysvar},
 };
 use spl_token::state::{Account, Mint};
 
 use crate::{
     assertions::{
-        collection::assert_is_collection_delegated_authority, metadata::assert_metadata_valid,
+        assert_owned_by, collection::assert_is_collection_delegated_authority,
+        metadata::assert_metadata_valid, programmable::assert_metadata_delegate_not_expired,
     },
     error::MetadataError,
-    instruction::{Context, Migrate, MigrateArgs},
-    pda::PREFIX,
+    instruction::{Context, MetadataDelegateRole, Migrate, MigrateArgs},
+    pda::{MIGRATION_OPT_OUT, MIGRATION_WINDOW, PREFIX},
     state::{
-        CollectionAuthorityRecord, Metadata, MigrationType, ProgrammableConfig, Resizable,
-        TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard, TokenState,
-        TOKEN_RECORD_SEED, TOKEN_STANDARD_INDEX,
+        AuthorityRequest, AuthorityResponse, AuthorityType, CollectionAuthorityRecord, Metadata,
+        MigrationType, MigrationWindow, ProgrammableConfig, Resizable, TokenDelegateRole,
+        TokenMetadataAccount, TokenRecord, TokenStandard, TokenState, TOKEN_RECORD_SEED,
+        TOKEN_STANDARD_INDEX,
     },
     utils::{
-        assert_derivation, assert_edition_valid, assert_initialized, clean_write_metadata, freeze,
+        assert_derivation, assert_edition_valid, assert_initialized,
+        clean_write_metadata_with_payer, freeze, is_auth_rules_program,
     },
 };
 
@@ -31,17 +41,22 @@ pub fn migrate<'a>(
     let context = Migrate::to_context(accounts)?;
 
     match args {
-        MigrateArgs::V1 { .. } => migrate_v1(program_id, context, args),
+        MigrateArgs::V1 {
+            migration_type,
+            rule_set,
+        } => migrate_v1(program_id, context, migration_type, rule_set),
+        MigrateArgs::CollectionV1 { rule_set } => {
+            migrate_collection_v1(program_id, context, rule_set)
+        }
     }
 }
 
-pub fn migrate_v1(program_id: &Pubkey, ctx: Context<Migrate>, args: MigrateArgs) -> ProgramResult {
-    let MigrateArgs::V1 {
-        migration_type,
-        rule_set,
-    } = args;
-
-    let payer_info = ctx.accounts.payer_info;
+pub fn migrate_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Migrate<'a>>,
+    migration_type: MigrationType,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
     let authority_info = ctx.accounts.authority_info;
     let metadata_info = ctx.accounts.metadata_info;
     let edition_info = ctx.accounts.edition_info;
@@ -49,46 +64,21 @@ pub fn migrate_v1(program_id: &Pubkey, ctx: Context<Migrate>, args: MigrateArgs)
     let collection_metadata_info = ctx.accounts.collection_metadata_info;
     let token_info = ctx.accounts.token_info;
     let token_record_info = ctx.accounts.token_record_info;
-    let system_program_info = ctx.accounts.system_program_info;
-    let sysvar_instructions_info = ctx.accounts.sysvar_instructions_info;
-    let spl_token_program_info = ctx.accounts.spl_token_program_info;
 
     // Validate Accounts
+    assert_migration_programs(&ctx)?;
 
-    // Check signers
-    assert_signer(authority_info)?;
-    assert_signer(payer_info)?;
-
-    // Assert program ownership
-    assert_owned_by(metadata_info, program_id)?;
-    assert_owned_by(edition_info, program_id)?;
-    assert_owned_by(mint_info, &spl_token::ID)?;
-    assert_owned_by(token_info, &spl_token::ID)?;
-
-    // Check program IDs.
-    if spl_token_program_info.key != &spl_token::ID {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    if system_program_info.key != &system_program::ID {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    if sysvar_instructions_info.key != &sysvar::instructions::ID {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    if let Some(auth_rules_program) = ctx.accounts.authorization_rules_program_info {
-        if auth_rules_program.key != &mpl_token_auth_rules::ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-    }
-
-    // Check derivations.
-    assert_edition_valid(program_id, mint_info.key, edition_info)?;
-    assert_metadata_valid(program_id, mint_info.key, metadata_info)?;
+    let asset = MigrationAccounts {
+        metadata_info,
+        edition_info,
+        mint_info,
+        token_info,
+        token_record_info,
+    };
+    assert_migration_asset(program_id, &asset)?;
 
     // Deserialize metadata.
-    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
     let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
 
     match migration_type {
@@ -118,101 +108,357 @@ pub fn migrate_v1(program_id: &Pubkey, ctx: Context<Migrate>, args: MigrateArgs)
                 return Err(MetadataError::UpdateAuthorityIncorrect.into());
             }
 
-            let token: Account = assert_initialized(token_info)?;
-            let mint: Mint = assert_initialized(mint_info)?;
+            migrate_to_programmable(program_id, &ctx, &asset, metadata, rule_set)?;
+        }
+    }
+
+    Ok(())
+}
 
-            if token.mint != *mint_info.key {
-                return Err(MetadataError::MintMismatch.into());
-            }
+/// Migrates multiple assets of a collection to `ProgrammableNonFungible`, applying the
+/// same rule set to all of them.
+///
+/// The authority must be the update authority of the collection or a `Collection` delegate,
+/// and the grace period of the migration window of the collection must have elapsed. The
+/// first remaining accounts are the migration window and the opt-out account of the first
+/// asset, which is specified by the accounts of the instruction; additional assets follow
+/// in groups of `[metadata, edition, mint, token, token_record, migration_opt_out]` accounts.
+/// Assets opted out by their holders are skipped.
+fn migrate_collection_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Migrate<'a>>,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    let collection_metadata_info = ctx.accounts.collection_metadata_info;
 
-            if mint.freeze_authority.is_none() {
-                return Err(MetadataError::NoFreezeAuthoritySet.into());
-            }
+    // the delegate record is only required when a collection delegate is migrating
+    // the assets
+    let delegate_record_info = if cmp_pubkeys(ctx.accounts.delegate_record_info.key, program_id) {
+        None
+    } else {
+        Some(ctx.accounts.delegate_record_info)
+    };
 
-            if mint.freeze_authority.unwrap() != *edition_info.key {
-                return Err(MetadataError::InvalidFreezeAuthority.into());
-            }
+    // Validate Accounts
+    assert_migration_programs(&ctx)?;
 
-            // NFT --> PNFT migration must maintain the current level of functionality
-            // that the token has, but all pNFTs must be frozen. To accomplish this,
-            // we assign Migration delegate to any pNFTs that have a SPL token delegate
-            // set. This allows the delegate to freeze the token via the Token Metadata
-            // Lock abstraction, as well as to transfer it as a normal SPL delegate is
-            // able to.
-            //
-            // Unfrozen tokens are frozen. Already frozen tokens will have the Lock
-            // flag set to match the current state in the new abstraction.
+    assert_owned_by(collection_metadata_info, program_id)?;
 
-            // We create the token record if it does not exist,
-            // but we only serialize once at the end to save on compute.
+    if let Some(delegate_record_info) = delegate_record_info {
+        assert_owned_by(delegate_record_info, program_id)?;
+    }
 
-            let mut token_record = TokenRecord::default();
+    let (migration_window_info, first_opt_out_info, remaining) =
+        match ctx.remaining_accounts.as_slice() {
+            [migration_window_info, first_opt_out_info, remaining @ ..] => (
+                *migration_window_info,
+                *first_opt_out_info,
+                remaining.chunks_exact(MIGRATION_ACCOUNTS_LEN),
+            ),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+    if !remaining.remainder().is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
 
-            // We check the derivation regardless of whether the account exists.
-            let mut signer_seeds = Vec::from([
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    assert_metadata_valid(
+        program_id,
+        &collection_metadata.mint,
+        collection_metadata_info,
+    )?;
+
+    // Check the authority.
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            authority: ctx.accounts.authority_info.key,
+            update_authority: &collection_metadata.update_authority,
+            mint: &collection_metadata.mint,
+            metadata_delegate_record_info: delegate_record_info,
+            metadata_delegate_roles: vec![MetadataDelegateRole::Collection],
+            precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+            ..Default::default()
+        })?;
+
+    match (authority_type, delegate_record_info) {
+        (AuthorityType::Metadata, _) => (),
+        // expired delegates can no longer migrate assets
+        (AuthorityType::MetadataDelegate, Some(delegate_record_info)) => {
+            assert_metadata_delegate_not_expired(delegate_record_info)?;
+        }
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    }
+
+    // holders are given a grace period to opt out of the migration
+    assert_derivation(
+        program_id,
+        migration_window_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_metadata.mint.as_ref(),
+            MIGRATION_WINDOW.as_bytes(),
+        ],
+    )?;
+
+    if migration_window_info.data_is_empty() {
+        return Err(MetadataError::MigrationGracePeriodNotElapsed.into());
+    }
+
+    assert_owned_by(migration_window_info, program_id)?;
+    let window = MigrationWindow::from_account_info(migration_window_info)?;
+
+    if !window.is_elapsed(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::MigrationGracePeriodNotElapsed.into());
+    }
+
+    let first = MigrationAccounts {
+        metadata_info: ctx.accounts.metadata_info,
+        edition_info: ctx.accounts.edition_info,
+        mint_info: ctx.accounts.mint_info,
+        token_info: ctx.accounts.token_info,
+        token_record_info: ctx.accounts.token_record_info,
+    };
+
+    msg!("Migrating {} assets", 1 + remaining.len());
+
+    let remaining = remaining.map(|accounts| {
+        (
+            MigrationAccounts {
+                metadata_info: accounts[0],
+                edition_info: accounts[1],
+                mint_info: accounts[2],
+                token_info: accounts[3],
+                token_record_info: accounts[4],
+            },
+            accounts[5],
+        )
+    });
+
+    for (asset, opt_out_info) in std::iter::once((first, first_opt_out_info)).chain(remaining) {
+        assert_migration_asset(program_id, &asset)?;
+
+        assert_derivation(
+            program_id,
+            opt_out_info,
+            &[
                 PREFIX.as_bytes(),
-                crate::ID.as_ref(),
-                mint_info.key.as_ref(),
-                TOKEN_RECORD_SEED.as_bytes(),
-                token_info.key.as_ref(),
-            ]);
-
-            let bump = &[assert_derivation(
-                program_id,
-                token_record_info,
-                &signer_seeds,
-            )?];
-            signer_seeds.push(bump);
-
-            if ctx.accounts.token_record_info.data.borrow().is_empty() {
-                // allocate the delegate account
-                create_or_allocate_account_raw(
-                    *program_id,
-                    token_record_info,
-                    system_program_info,
-                    payer_info,
-                    TokenRecord::size(),
-                    &signer_seeds,
-                )?;
-
-                token_record.bump = bump[0];
-            }
+                program_id.as_ref(),
+                asset.mint_info.key.as_ref(),
+                MIGRATION_OPT_OUT.as_bytes(),
+            ],
+        )?;
+
+        if !opt_out_info.data_is_empty() {
+            msg!(
+                "Skipping asset {} opted out by its holder",
+                asset.mint_info.key
+            );
+            continue;
+        }
 
-            // Only freeze if the token is not already frozen, otherwise the call will fail.
-            // If the token is frozen already AND it has a SPL delegate set, then we
-            // set the state to Locked.
-            if !token.is_frozen() {
-                freeze(
-                    mint_info.clone(),
-                    token_info.clone(),
-                    edition_info.clone(),
-                    spl_token_program_info.clone(),
-                )?;
-            } else if token.delegate.is_some() {
-                token_record.state = TokenState::Locked;
-            }
+        let metadata = Metadata::from_account_info(asset.metadata_info)?;
 
-            // Set Migration delegate if SPL delegate is set.
-            if let COption::Some(current_delegate) = token.delegate {
-                token_record.delegate = Some(current_delegate);
-                token_record.delegate_role = Some(TokenDelegateRole::Migration);
-            }
+        match &metadata.collection {
+            Some(collection)
+                if collection.verified
+                    && cmp_pubkeys(&collection.key, &collection_metadata.mint) => {}
+            _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+        }
 
-            token_record.save(
-                ctx.accounts.token_record_info,
-                payer_info,
-                system_program_info,
-            )?;
+        // collection delegates are derived from the update authority of the collection, so
+        // they can only act on assets sharing the same update authority
+        if !cmp_pubkeys(
+            &metadata.update_authority,
+            &collection_metadata.update_authority,
+        ) {
+            return Err(MetadataError::UpdateAuthorityIncorrect.into());
+        }
+
+        // only non-fungible assets can be migrated
+        if !matches!(
+            metadata.token_standard,
+            None | Some(TokenStandard::NonFungible)
+        ) {
+            return Err(MetadataError::InvalidTokenStandard.into());
+        }
+
+        migrate_to_programmable(program_id, &ctx, &asset, metadata, rule_set)?;
+    }
+
+    Ok(())
+}
 
-            // Migrate the token.
-            metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
-            metadata.programmable_config = Some(ProgrammableConfig::V1 { rule_set });
-            edition_info.data.borrow_mut()[TOKEN_STANDARD_INDEX] =
-                TokenStandard::ProgrammableNonFungible as u8;
+/// Number of accounts of each additional asset of a collection migration.
+const MIGRATION_ACCOUNTS_LEN: usize = 6;
+
+/// Accounts of an asset being migrated.
+#[derive(Clone, Copy)]
+struct MigrationAccounts<'a> {
+    metadata_info: &'a AccountInfo<'a>,
+    edition_info: &'a AccountInfo<'a>,
+    mint_info: &'a AccountInfo<'a>,
+    token_info: &'a AccountInfo<'a>,
+    token_record_info: &'a AccountInfo<'a>,
+}
 
-            clean_write_metadata(&mut metadata, metadata_info)?;
+/// Validates the signers and programs shared by all migration types.
+fn assert_migration_programs(ctx: &Context<Migrate>) -> ProgramResult {
+    // Check signers
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // Check program IDs.
+    if ctx.accounts.spl_token_program_info.key != &spl_token::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if ctx.accounts.system_program_info.key != &system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if ctx.accounts.sysvar_instructions_info.key != &sysvar::instructions::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if let Some(auth_rules_program) = ctx.accounts.authorization_rules_program_info {
+        if !is_auth_rules_program(auth_rules_program.key) {
+            return Err(ProgramError::IncorrectProgramId);
         }
     }
 
     Ok(())
 }
+
+/// Validates the ownership and derivation of the accounts of an asset being migrated.
+fn assert_migration_asset(program_id: &Pubkey, asset: &MigrationAccounts) -> ProgramResult {
+    // Assert program ownership
+    assert_owned_by(asset.metadata_info, program_id)?;
+    assert_owned_by(asset.edition_info, program_id)?;
+    assert_owned_by(asset.mint_info, &spl_token::ID)?;
+    assert_owned_by(asset.token_info, &spl_token::ID)?;
+
+    // Check derivations.
+    assert_edition_valid(program_id, asset.mint_info.key, asset.edition_info)?;
+    assert_metadata_valid(program_id, asset.mint_info.key, asset.metadata_info)
+}
+
+/// Migrates a non-fungible asset to `ProgrammableNonFungible`, creating its token record
+/// and freezing its token.
+fn migrate_to_programmable<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, Migrate<'a>>,
+    asset: &MigrationAccounts<'a>,
+    mut metadata: Metadata,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    let payer_info = ctx.accounts.payer_info;
+    let system_program_info = ctx.accounts.system_program_info;
+    let spl_token_program_info = ctx.accounts.spl_token_program_info;
+
+    let MigrationAccounts {
+        metadata_info,
+        edition_info,
+        mint_info,
+        token_info,
+        token_record_info,
+    } = *asset;
+
+    let token: Account = assert_initialized(token_info)?;
+    let mint: Mint = assert_initialized(mint_info)?;
+
+    if token.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if mint.freeze_authority.is_none() {
+        return Err(MetadataError::NoFreezeAuthoritySet.into());
+    }
+
+    if mint.freeze_authority.unwrap() != *edition_info.key {
+        return Err(MetadataError::InvalidFreezeAuthority.into());
+    }
+
+    // NFT --> PNFT migration must maintain the current level of functionality
+    // that the token has, but all pNFTs must be frozen. To accomplish this,
+    // we assign Migration delegate to any pNFTs that have a SPL token delegate
+    // set. This allows the delegate to freeze the token via the Token Metadata
+    // Lock abstraction, as well as to transfer it as a normal SPL delegate is
+    // able to.
+    //
+    // Unfrozen tokens are frozen. Already frozen tokens will have the Lock
+    // flag set to match the current state in the new abstraction.
+
+    // We create the token record if it does not exist,
+    // but we only serialize once at the end to save on compute.
+
+    let mut token_record = TokenRecord::default();
+
+    // We check the derivation regardless of whether the account exists.
+    let mut signer_seeds = Vec::from([
+        PREFIX.as_bytes(),
+        crate::ID.as_ref(),
+        mint_info.key.as_ref(),
+        TOKEN_RECORD_SEED.as_bytes(),
+        token_info.key.as_ref(),
+    ]);
+
+    let bump = &[assert_derivation(
+        program_id,
+        token_record_info,
+        &signer_seeds,
+    )?];
+    signer_seeds.push(bump);
+
+    if token_record_info.data.borrow().is_empty() {
+        // allocate the delegate account
+        create_or_allocate_account_raw(
+            *program_id,
+            token_record_info,
+            system_program_info,
+            payer_info,
+            TokenRecord::size(),
+            &signer_seeds,
+        )?;
+
+        token_record.bump = bump[0];
+        token_record.payer = Some(*payer_info.key);
+    }
+
+    // Only freeze if the token is not already frozen, otherwise the call will fail.
+    // If the token is frozen already AND it has a SPL delegate set, then we
+    // set the state to Locked.
+    if !token.is_frozen() {
+        freeze(
+            mint_info.clone(),
+            token_info.clone(),
+            edition_info.clone(),
+            spl_token_program_info.clone(),
+        )?;
+    } else if token.delegate.is_some() {
+        token_record.state = TokenState::Locked;
+    }
+
+    // Set Migration delegate if SPL delegate is set.
+    if let COption::Some(current_delegate) = token.delegate {
+        token_record.delegate = Some(current_delegate);
+        token_record.delegate_role = Some(TokenDelegateRole::Migration);
+    }
+
+    token_record.save(token_record_info, payer_info, system_program_info)?;
+
+    // Migrate the token.
+    metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+    metadata.programmable_config = Some(ProgrammableConfig::V1 { rule_set });
+    edition_info.data.borrow_mut()[TOKEN_STANDARD_INDEX] =
+        TokenStandard::ProgrammableNonFungible as u8;
+
+    clean_write_metadata_with_payer(
+        &mut metadata,
+        metadata_info,
+        payer_info,
+        system_program_info,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{
+        Context, OpenMigrationWindow, OpenMigrationWindowArgs, OptOutMigration, OptOutMigrationArgs,
+    },
+    pda::{MIGRATION_OPT_OUT, MIGRATION_WINDOW, PREFIX},
+    state::{Collection, Metadata, MigrationOptOut, MigrationWindow, TokenMetadataAccount},
+};
+
+/// Opens the migration window of a collection.
+pub fn open_migration_window<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: OpenMigrationWindowArgs,
+) -> ProgramResult {
+    let context = OpenMigrationWindow::to_context(accounts)?;
+
+    match args {
+        OpenMigrationWindowArgs::V1 { grace_period } => {
+            open_migration_window_v1(program_id, context, grace_period)
+        }
+    }
+}
+
+/// Opts an asset out of the migration of its collection.
+pub fn opt_out_migration<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: OptOutMigrationArgs,
+) -> ProgramResult {
+    let context = OptOutMigration::to_context(accounts)?;
+
+    match args {
+        OptOutMigrationArgs::V1 => opt_out_migration_v1(program_id, context),
+    }
+}
+
+fn open_migration_window_v1(
+    program_id: &Pubkey,
+    ctx: Context<OpenMigrationWindow>,
+    grace_period: i64,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.collection_mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.collection_mint_info,
+    )?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+    assert_update_authority_is_correct(&collection_metadata, ctx.accounts.authority_info)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.collection_mint_info.key.as_ref(),
+        MIGRATION_WINDOW.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.migration_window_info,
+        &signer_seeds,
+    )?];
+
+    let mut window = if ctx.accounts.migration_window_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.migration_window_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            MigrationWindow::size(),
+            &signer_seeds,
+        )?;
+
+        MigrationWindow {
+            bump: bump[0],
+            collection_mint: *ctx.accounts.collection_mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.migration_window_info, program_id)?;
+        MigrationWindow::from_account_info(ctx.accounts.migration_window_info)?
+    };
+
+    window.open(grace_period, Clock::get()?.unix_timestamp)?;
+    window.serialize(&mut *ctx.accounts.migration_window_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Collection {} can be migrated after {}",
+        ctx.accounts.collection_mint_info.key,
+        window.migrate_after
+    );
+
+    Ok(())
+}
+
+fn opt_out_migration_v1(program_id: &Pubkey, ctx: Context<OptOutMigration>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.token_owner_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by_token_program(ctx.accounts.token_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
+
+    if token.owner != *ctx.accounts.token_owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount == 0 {
+        return Err(MetadataError::InsufficientTokens.into());
+    }
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    let collection_mint = match &metadata.collection {
+        Some(Collection {
+            key,
+            verified: true,
+        }) => key,
+        _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+    };
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.migration_window_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_mint.as_ref(),
+            MIGRATION_WINDOW.as_bytes(),
+        ],
+    )?;
+
+    // holders can opt out before the window is opened or during its grace period
+    if !ctx.accounts.migration_window_info.data_is_empty() {
+        assert_owned_by(ctx.accounts.migration_window_info, program_id)?;
+        let window = MigrationWindow::from_account_info(ctx.accounts.migration_window_info)?;
+
+        if window.is_elapsed(Clock::get()?.unix_timestamp) {
+            return Err(MetadataError::MigrationGracePeriodElapsed.into());
+        }
+    }
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        MIGRATION_OPT_OUT.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.migration_opt_out_info,
+        &signer_seeds,
+    )?];
+
+    if !ctx.accounts.migration_opt_out_info.data_is_empty() {
+        msg!("Asset {} already opted out", metadata.mint);
+        return Ok(());
+    }
+
+    signer_seeds.push(bump);
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.migration_opt_out_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        MigrationOptOut::size(),
+        &signer_seeds,
+    )?;
+
+    let opt_out = MigrationOptOut {
+        bump: bump[0],
+        mint: *ctx.accounts.mint_info.key,
+        ..Default::default()
+    };
+    opt_out.serialize(&mut *ctx.accounts.migration_opt_out_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Asset {} opted out of the collection migration",
+        metadata.mint
+    );
+
+    Ok(())
+}
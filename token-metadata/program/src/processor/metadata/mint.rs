@@ -1,4 +1,4 @@
-use mpl_utils::{assert_signer, cmp_pubkeys};
+use mpl_utils::{assert_signer, cmp_pubkeys, token::SPL_TOKEN_PROGRAM_IDS};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -7,18 +7,20 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_token::state::{Account, Mint as MintAccount};
 
 use crate::{
     assertions::{
-        assert_derivation, assert_initialized, assert_keys_equal,
-        assert_mint_authority_matches_mint, assert_owned_by,
+        assert_derivation, assert_initialized_mint, assert_initialized_token_account,
+        assert_keys_equal, assert_mint_authority_matches_mint, assert_owned_by,
     },
     error::MetadataError,
     instruction::{Context, Mint, MintArgs},
     pda::{find_token_record_account, EDITION, PREFIX},
-    state::{Metadata, TokenMetadataAccount, TokenStandard},
-    utils::{create_token_record_account, freeze, thaw},
+    state::{get_master_edition, Metadata, TokenMetadataAccount, TokenStandard},
+    utils::{
+        assert_associated_token_account, create_associated_token_account,
+        create_token_record_account, freeze, thaw,
+    },
 };
 
 /// Mints tokens from a mint account.
@@ -27,6 +29,9 @@ use crate::{
 /// this case the `token_owner` will be required. When minting `*NonFungible` assets, the `authority`
 /// must be the update authority; in all other cases, it must be the mint authority from the mint
 /// account.
+///
+/// With `MintArgs::V2`, the token account must be the associated token account of the
+/// recipient `token_owner`, so clients do not need to pass an account matching the payer.
 pub fn mint<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -34,13 +39,22 @@ pub fn mint<'a>(
 ) -> ProgramResult {
     let context = Mint::to_context(accounts)?;
     match args {
-        MintArgs::V1 { .. } => mint_v1(program_id, context, args),
+        MintArgs::V1 { .. } | MintArgs::V2 { .. } => mint_v1(program_id, context, args),
+        MintArgs::BatchV1 { ref amounts } => mint_batch_v1(program_id, context, amounts),
     }
 }
 
 pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> ProgramResult {
     // get the args for the instruction
-    let MintArgs::V1 { amount, .. } = args;
+    let (amount, recipient) = match args {
+        MintArgs::V1 { amount, .. } => (amount, None),
+        MintArgs::V2 {
+            amount,
+            token_owner,
+            ..
+        } => (amount, Some(token_owner)),
+        _ => return Err(MetadataError::InstructionNotSupported.into()),
+    };
 
     if amount == 0 {
         return Err(MetadataError::AmountMustBeGreaterThanZero.into());
@@ -68,39 +82,61 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
         return Err(MetadataError::MintMismatch.into());
     }
 
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::id())?;
-    let mint: MintAccount = assert_initialized(ctx.accounts.mint_info)?;
-
-    if !cmp_pubkeys(ctx.accounts.spl_token_program_info.key, &spl_token::id()) {
+    // the mint can be owned by either spl-token or Token-2022
+    if !SPL_TOKEN_PROGRAM_IDS.contains(ctx.accounts.spl_token_program_info.key) {
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    let mint = assert_initialized_mint(ctx.accounts.mint_info)?;
+
     // validates the authority:
     // - NonFungible must have a "valid" master edition
     // - Fungible must have the authority as the mint_authority
 
     match metadata.token_standard {
-        Some(TokenStandard::ProgrammableNonFungible) | Some(TokenStandard::NonFungible) => {
+        Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::NonFungible)
+        | Some(TokenStandard::NonTransferable) => {
             // for NonFungible assets, the mint authority is the master edition
-            if let Some(master_edition_info) = ctx.accounts.master_edition_info {
-                assert_derivation(
-                    program_id,
-                    master_edition_info,
-                    &[
-                        PREFIX.as_bytes(),
-                        program_id.as_ref(),
-                        ctx.accounts.mint_info.key.as_ref(),
-                        EDITION.as_bytes(),
-                    ],
-                )?;
-            } else {
-                return Err(MetadataError::MissingMasterEditionAccount.into());
-            }
+            let master_edition_info = ctx
+                .accounts
+                .master_edition_info
+                .ok_or(MetadataError::MissingMasterEditionAccount)?;
+
+            assert_derivation(
+                program_id,
+                master_edition_info,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    ctx.accounts.mint_info.key.as_ref(),
+                    EDITION.as_bytes(),
+                ],
+            )?;
+            assert_owned_by(master_edition_info, program_id)?;
+            // the master edition must be initialized, otherwise the mint authority
+            // might not have been transferred to it yet
+            get_master_edition(master_edition_info)
+                .map_err(|_| MetadataError::NotAMasterEdition)?;
 
-            if mint.supply > 0 || amount > 1 {
+            if amount > 1 {
                 return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
             }
 
+            // NonFungible assets have a maximum supply of exactly one token
+            let supply = mint
+                .supply
+                .checked_add(amount)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+
+            if supply > 1 {
+                return Err(MetadataError::MintSupplyExceeded.into());
+            }
+
             // authority must be the update_authority of the metadata account
             if !cmp_pubkeys(&metadata.update_authority, ctx.accounts.authority_info.key) {
                 return Err(MetadataError::UpdateAuthorityIncorrect.into());
@@ -113,51 +149,38 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
 
     // validates the token account
 
-    if ctx.accounts.token_info.data_is_empty() {
-        // if we are initializing a new account, we need the token_owner
-        let token_owner_info = if let Some(token_owner_info) = ctx.accounts.token_owner_info {
-            token_owner_info
-        } else {
-            return Err(MetadataError::MissingTokenOwnerAccount.into());
-        };
+    if let Some(recipient) = &recipient {
+        assert_recipient_token_account(&ctx.accounts, recipient)?;
+    }
 
-        // if the token account is empty, we will initialize a new one but it must
-        // be an ATA account
-        assert_derivation(
-            &spl_associated_token_account::id(),
-            ctx.accounts.token_info,
-            &[
-                token_owner_info.key.as_ref(),
-                spl_token::id().as_ref(),
-                ctx.accounts.mint_info.key.as_ref(),
-            ],
-        )?;
+    init_token_account(
+        &ctx.accounts,
+        ctx.accounts.token_info,
+        ctx.accounts.token_owner_info,
+    )?;
 
-        msg!("Init ATA");
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
 
-        // creating the associated token account
-        invoke(
-            &spl_associated_token_account::instruction::create_associated_token_account(
-                ctx.accounts.payer_info.key,
-                token_owner_info.key,
-                ctx.accounts.mint_info.key,
-                &spl_token::id(),
-            ),
-            &[
-                ctx.accounts.payer_info.clone(),
-                token_owner_info.clone(),
-                ctx.accounts.mint_info.clone(),
-                ctx.accounts.token_info.clone(),
-            ],
-        )?;
-    } else {
-        assert_owned_by(ctx.accounts.token_info, &spl_token::id())?;
+    if let Some(recipient) = &recipient {
+        if !cmp_pubkeys(&token.owner, recipient) {
+            return Err(MetadataError::InvalidOwner.into());
+        }
     }
 
-    let token: Account = assert_initialized(ctx.accounts.token_info)?;
+    // a delegate approved before the token is minted could thaw a non-transferable
+    // asset through the delegate instructions
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::NonTransferable)
+    ) && token.delegate.is_some()
+    {
+        return Err(MetadataError::InvalidDelegate.into());
+    }
 
     match metadata.token_standard {
-        Some(TokenStandard::NonFungible) | Some(TokenStandard::ProgrammableNonFungible) => {
+        Some(TokenStandard::NonFungible)
+        | Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::NonTransferable) => {
             // for pNFTs, we require the token record account
             if matches!(
                 metadata.token_standard,
@@ -213,11 +236,11 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
                 return Err(MetadataError::InvalidMasterEdition.into());
             }
 
-            // thaw the token account for programmable assets; the account
-            // is not frozen if we just initialized it
+            // thaw the token account for programmable (and non-transferable) assets;
+            // the account is not frozen if we just initialized it
             if matches!(
                 metadata.token_standard,
-                Some(TokenStandard::ProgrammableNonFungible)
+                Some(TokenStandard::ProgrammableNonFungible) | Some(TokenStandard::NonTransferable)
             ) && token.is_frozen()
             {
                 thaw(
@@ -229,7 +252,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
             }
 
             invoke_signed(
-                &spl_token::instruction::mint_to(
+                &spl_token_2022::instruction::mint_to(
                     ctx.accounts.spl_token_program_info.key,
                     ctx.accounts.mint_info.key,
                     ctx.accounts.token_info.key,
@@ -245,10 +268,10 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
                 &[&signer_seeds],
             )?;
 
-            // programmable assets are always in a frozen state
+            // programmable and non-transferable assets are always in a frozen state
             if matches!(
                 metadata.token_standard,
-                Some(TokenStandard::ProgrammableNonFungible)
+                Some(TokenStandard::ProgrammableNonFungible) | Some(TokenStandard::NonTransferable)
             ) {
                 freeze(
                     ctx.accounts.mint_info.clone(),
@@ -260,7 +283,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
         }
         _ => {
             invoke(
-                &spl_token::instruction::mint_to(
+                &spl_token_2022::instruction::mint_to(
                     ctx.accounts.spl_token_program_info.key,
                     ctx.accounts.mint_info.key,
                     ctx.accounts.token_info.key,
@@ -279,3 +302,161 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
 
     Ok(())
 }
+
+/// Mints tokens of a fungible asset to multiple token accounts.
+///
+/// The first recipient is the `token` account of the instruction; additional recipients are
+/// passed as `(token, token_owner)` pairs in the remaining accounts, in the same order as the
+/// `amounts`. Token accounts that do not exist are initialized as associated token accounts.
+pub fn mint_batch_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Mint<'a>>,
+    amounts: &[u64],
+) -> ProgramResult {
+    if amounts.contains(&0) {
+        return Err(MetadataError::AmountMustBeGreaterThanZero.into());
+    }
+
+    // each additional recipient is a (token, token_owner) pair
+    if amounts.is_empty() || ctx.remaining_accounts.len() != (amounts.len() - 1) * 2 {
+        return Err(MetadataError::InvalidBatchRecipients.into());
+    }
+
+    // checks that we have the required signers
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // validates the accounts
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+        ],
+    )?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // the mint can be owned by either spl-token or Token-2022
+    if !SPL_TOKEN_PROGRAM_IDS.contains(ctx.accounts.spl_token_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    let mint = assert_initialized_mint(ctx.accounts.mint_info)?;
+
+    // non-fungible assets have a supply of one, so they can only be minted to a
+    // single token account
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::NonFungible)
+            | Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::NonTransferable)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    assert_mint_authority_matches_mint(&mint.mint_authority, ctx.accounts.authority_info)?;
+
+    let mut recipients = vec![(ctx.accounts.token_info, ctx.accounts.token_owner_info)];
+    recipients.extend(
+        ctx.remaining_accounts
+            .chunks(2)
+            .map(|pair| (pair[0], Some(pair[1]))),
+    );
+
+    for ((token_info, token_owner_info), amount) in recipients.into_iter().zip(amounts) {
+        init_token_account(&ctx.accounts, token_info, token_owner_info)?;
+
+        invoke(
+            &spl_token_2022::instruction::mint_to(
+                ctx.accounts.spl_token_program_info.key,
+                ctx.accounts.mint_info.key,
+                token_info.key,
+                ctx.accounts.authority_info.key,
+                &[],
+                *amount,
+            )?,
+            &[
+                ctx.accounts.mint_info.clone(),
+                token_info.clone(),
+                ctx.accounts.authority_info.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Asserts that the token account is the associated token account of the recipient and
+/// that the recipient account (if any) matches.
+fn assert_recipient_token_account(accounts: &Mint, recipient: &Pubkey) -> ProgramResult {
+    if let Some(token_owner_info) = accounts.token_owner_info {
+        if !cmp_pubkeys(token_owner_info.key, recipient) {
+            return Err(MetadataError::InvalidOwner.into());
+        }
+    }
+
+    assert_associated_token_account(
+        accounts.token_info,
+        recipient,
+        accounts.mint_info.key,
+        accounts.spl_token_program_info.key,
+    )
+}
+
+/// Initializes the token account as an associated token account if it does not exist,
+/// otherwise checks that it is owned by the token program of the mint.
+fn init_token_account<'a>(
+    accounts: &Mint<'a>,
+    token_info: &'a AccountInfo<'a>,
+    token_owner_info: Option<&'a AccountInfo<'a>>,
+) -> ProgramResult {
+    if token_info.data_is_empty() {
+        // if we are initializing a new account, we need the token_owner
+        let token_owner_info = if let Some(token_owner_info) = token_owner_info {
+            token_owner_info
+        } else {
+            return Err(MetadataError::MissingTokenOwnerAccount.into());
+        };
+
+        // if the token account is empty, we will initialize a new one but it must
+        // be an ATA account
+        assert_associated_token_account(
+            token_info,
+            token_owner_info.key,
+            accounts.mint_info.key,
+            accounts.spl_token_program_info.key,
+        )?;
+
+        msg!("Init ATA");
+
+        // creating the associated token account
+        create_associated_token_account(
+            accounts.payer_info.key,
+            token_owner_info.key,
+            accounts.mint_info.key,
+            accounts.spl_token_program_info.key,
+            &[
+                accounts.payer_info.clone(),
+                token_owner_info.clone(),
+                accounts.mint_info.clone(),
+                token_info.clone(),
+            ],
+        )?;
+    } else {
+        assert_owned_by(token_info, accounts.spl_token_program_info.key)?;
+    }
+
+    Ok(())
+}
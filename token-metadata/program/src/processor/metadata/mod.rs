@@ -1,25 +1,51 @@
+mod authority_config;
+mod batch_set_token_standard;
+mod batch_update;
 mod create;
+mod create_existing_mint_metadata;
 mod create_medatata_accounts_v3;
+mod creators_overflow;
 mod migrate;
+mod migration_window;
 mod mint;
+mod pay_royalties;
 mod puff_metadata;
 mod remove_creator_verification;
+mod resize;
+mod rule_set_proposal;
+mod set_default_rule_set;
 mod set_token_standard;
+mod settle_primary_sale;
 mod sign_metadata;
 mod transfer;
+mod transfer_session;
 mod update;
 mod update_metadata_account_v2;
 mod update_primary_sale_happened_via_token;
+mod verify_content;
 
+pub use authority_config::*;
+pub use batch_set_token_standard::*;
+pub use batch_update::*;
 pub use create::*;
+pub use create_existing_mint_metadata::*;
 pub use create_medatata_accounts_v3::*;
+pub use creators_overflow::*;
 pub use migrate::*;
+pub use migration_window::*;
 pub use mint::*;
+pub use pay_royalties::*;
 pub use puff_metadata::*;
 pub use remove_creator_verification::*;
+pub use resize::*;
+pub use rule_set_proposal::*;
+pub use set_default_rule_set::*;
 pub use set_token_standard::*;
+pub use settle_primary_sale::*;
 pub use sign_metadata::*;
 pub use transfer::*;
+pub use transfer_session::*;
 pub use update::*;
 pub use update_metadata_account_v2::*;
 pub use update_primary_sale_happened_via_token::*;
+pub use verify_content::*;
@@ -0,0 +1,98 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, PayRoyalties, PayRoyaltiesArgs},
+    state::{split_royalties, CreatorsOverflow, Metadata, TokenMetadataAccount},
+    utils::{pay_creators, TokenPaymentAccounts},
+};
+
+/// Pays the royalties of an asset, splitting the amount between its verified creators.
+pub fn pay_royalties<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: PayRoyaltiesArgs,
+) -> ProgramResult {
+    let context = PayRoyalties::to_context(accounts)?;
+
+    match args {
+        PayRoyaltiesArgs::V1 { amount } => pay_royalties_v1(program_id, context, amount),
+    }
+}
+
+fn pay_royalties_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, PayRoyalties<'a>>,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Err(MetadataError::AmountMustBeGreaterThanZero.into());
+    }
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    if let Some(collection_metadata_info) = ctx.accounts.collection_metadata_info {
+        assert_owned_by(collection_metadata_info, program_id)?;
+    }
+
+    if let Some(creators_overflow_info) = ctx.accounts.creators_overflow_info {
+        assert_owned_by(creators_overflow_info, program_id)?;
+    }
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    // the collection parent is only required when the asset inherits its royalties
+    let parent = if metadata.inherit_royalties {
+        Metadata::from_account_info(
+            ctx.accounts
+                .collection_metadata_info
+                .ok_or(MetadataError::MissingCollectionMetadata)?,
+        )?
+    } else {
+        metadata.clone()
+    };
+    let overflow = ctx
+        .accounts
+        .creators_overflow_info
+        .map(CreatorsOverflow::from_account_info)
+        .transpose()?;
+
+    let (_, creators) = metadata.effective_royalties(&parent, overflow.as_ref())?;
+    let payments = split_royalties(
+        &creators.ok_or(MetadataError::NoCreatorsPresentOnMetadata)?,
+        amount,
+    )?;
+
+    let token_accounts = TokenPaymentAccounts::from_optional(
+        ctx.accounts.payer_token_info,
+        ctx.accounts.payment_mint_info,
+        ctx.accounts.spl_token_program_info,
+    )?;
+
+    pay_creators(
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+        token_accounts.as_ref(),
+        &payments,
+        &ctx.remaining_accounts,
+    )?;
+
+    msg!("Royalties paid to {} creators", payments.len());
+
+    Ok(())
+}
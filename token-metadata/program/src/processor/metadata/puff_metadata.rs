@@ -1,4 +1,3 @@
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -8,7 +7,7 @@ use solana_program::{
 use crate::{
     assertions::assert_owned_by,
     state::{Metadata, TokenMetadataAccount, EDITION, PREFIX},
-    utils::puff_out_data_fields,
+    utils::{clean_write_metadata, puff_out_data_fields},
 };
 
 /// Puff out the variable length fields to a fixed length on a metadata
@@ -35,6 +34,6 @@ pub fn process_puff_metadata_account(
     let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
     metadata.edition_nonce = Some(edition_bump_seed);
 
-    metadata.serialize(&mut *metadata_account_info.try_borrow_mut_data()?)?;
+    clean_write_metadata(&mut metadata, metadata_account_info)?;
     Ok(())
 }
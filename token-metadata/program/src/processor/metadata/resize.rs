@@ -0,0 +1,69 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{assert_owned_by, metadata::assert_update_authority_is_correct},
+    error::MetadataError,
+    instruction::{Context, Resize, ResizeArgs},
+    state::{Metadata, TokenMetadataAccount},
+};
+
+/// Resizes a metadata account to the size of its current contents.
+pub fn resize<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ResizeArgs,
+) -> ProgramResult {
+    let context = Resize::to_context(accounts)?;
+
+    match args {
+        ResizeArgs::V1 => resize_v1(program_id, context),
+    }
+}
+
+fn resize_v1(program_id: &Pubkey, ctx: Context<Resize>) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // the serialized metadata does not include the padding of the variable length
+    // fields, so it is the minimum size of the account
+    let data = metadata.try_to_vec()?;
+
+    if data.len() < ctx.accounts.metadata_info.data_len() {
+        {
+            let mut metadata_data = ctx.accounts.metadata_info.try_borrow_mut_data()?;
+            metadata_data.fill(0);
+            metadata_data[..data.len()].copy_from_slice(&data);
+        }
+        ctx.accounts.metadata_info.realloc(data.len(), false)?;
+    }
+
+    // sends the rent that is no longer required to the destination
+
+    let required_lamports = Rent::get()?.minimum_balance(data.len());
+    let excess_lamports = ctx
+        .accounts
+        .metadata_info
+        .lamports()
+        .saturating_sub(required_lamports);
+
+    if excess_lamports > 0 {
+        **ctx.accounts.metadata_info.lamports.borrow_mut() = required_lamports;
+
+        let destination_lamports = ctx.accounts.destination_info.lamports();
+        **ctx.accounts.destination_info.lamports.borrow_mut() = destination_lamports
+            .checked_add(excess_lamports)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{CommitRuleSet, CommitRuleSetArgs, Context, ProposeRuleSet, ProposeRuleSetArgs},
+    pda::{PREFIX, RULE_SET_PROPOSAL},
+    state::{Metadata, ProgrammableConfig, RuleSetProposal, TokenMetadataAccount, TokenStandard},
+    utils::clean_write_metadata_with_payer,
+};
+
+pub fn propose_rule_set<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ProposeRuleSetArgs,
+) -> ProgramResult {
+    let context = ProposeRuleSet::to_context(accounts)?;
+
+    match args {
+        ProposeRuleSetArgs::V1 { rule_set, delay } => {
+            propose_rule_set_v1(program_id, context, rule_set, delay)
+        }
+    }
+}
+
+pub fn commit_rule_set<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CommitRuleSetArgs,
+) -> ProgramResult {
+    let context = CommitRuleSet::to_context(accounts)?;
+
+    match args {
+        CommitRuleSetArgs::V1 => commit_rule_set_v1(program_id, context),
+    }
+}
+
+fn propose_rule_set_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, ProposeRuleSet<'a>>,
+    rule_set: Pubkey,
+    delay: Option<i64>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+    assert_programmable(&metadata)?;
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        RULE_SET_PROPOSAL.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.rule_set_proposal_info,
+        &signer_seeds,
+    )?];
+
+    let mut proposal = if ctx.accounts.rule_set_proposal_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.rule_set_proposal_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            RuleSetProposal::size(),
+            &signer_seeds,
+        )?;
+
+        RuleSetProposal {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.rule_set_proposal_info, program_id)?;
+        RuleSetProposal::from_account_info(ctx.accounts.rule_set_proposal_info)?
+    };
+
+    // holders rely on the current delay, so it can only be increased
+    if let Some(delay) = delay {
+        if delay < proposal.delay {
+            return Err(MetadataError::InvalidRuleSetDelay.into());
+        }
+        proposal.delay = delay;
+    }
+
+    proposal.propose(rule_set, Clock::get()?.unix_timestamp)?;
+    proposal.serialize(&mut *ctx.accounts.rule_set_proposal_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+fn commit_rule_set_v1(program_id: &Pubkey, ctx: Context<CommitRuleSet>) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.rule_set_proposal_info, program_id)?;
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by_token_program(ctx.accounts.token_info)?;
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.rule_set_proposal_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            RULE_SET_PROPOSAL.as_bytes(),
+        ],
+    )?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+    assert_programmable(&metadata)?;
+    metadata.assert_current_layout()?;
+
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
+
+    if !cmp_pubkeys(&token.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    // the rule set cannot be changed while the token has a delegate
+    if token.delegate.is_some() {
+        return Err(MetadataError::CannotUpdateAssetWithDelegate.into());
+    }
+
+    let mut proposal = RuleSetProposal::from_account_info(ctx.accounts.rule_set_proposal_info)?;
+
+    if !proposal.is_committable(Clock::get()?.unix_timestamp) {
+        return Err(MetadataError::RuleSetProposalNotCommittable.into());
+    }
+
+    metadata.programmable_config = Some(ProgrammableConfig::V1 {
+        rule_set: proposal.rule_set.take(),
+    });
+    proposal.committable_at = 0;
+
+    proposal.serialize(&mut *ctx.accounts.rule_set_proposal_info.try_borrow_mut_data()?)?;
+    clean_write_metadata_with_payer(
+        &mut metadata,
+        ctx.accounts.metadata_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
+
+/// Rule sets are only enforced on programmable assets.
+fn assert_programmable(metadata: &Metadata) -> Result<(), ProgramError> {
+    if !matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    Ok(())
+}
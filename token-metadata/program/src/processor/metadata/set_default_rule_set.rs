@@ -0,0 +1,94 @@
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{assert_derivation, assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, SetDefaultRuleSet, SetDefaultRuleSetArgs},
+    pda::{DEFAULT_RULE_SETS, PREFIX},
+    state::{DefaultRuleSets, TokenMetadataAccount, TokenStandard},
+    utils::SEED_AUTHORITY,
+};
+
+pub fn set_default_rule_set<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetDefaultRuleSetArgs,
+) -> ProgramResult {
+    let context = SetDefaultRuleSet::to_context(accounts)?;
+
+    match args {
+        SetDefaultRuleSetArgs::V1 {
+            token_standard,
+            rule_set,
+        } => set_default_rule_set_v1(program_id, context, token_standard, rule_set),
+    }
+}
+
+fn set_default_rule_set_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, SetDefaultRuleSet<'a>>,
+    token_standard: TokenStandard,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    // default rule sets are a protocol-level setting, so they can only be changed
+    // by the protocol authority
+    if *ctx.accounts.authority_info.key != SEED_AUTHORITY {
+        return Err(MetadataError::InvalidDefaultRuleSetsAuthority.into());
+    }
+
+    // rule sets are only enforced on programmable assets
+    if !matches!(token_standard, TokenStandard::ProgrammableNonFungible) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        DEFAULT_RULE_SETS.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.default_rule_sets_info,
+        &signer_seeds,
+    )?];
+
+    let mut default_rule_sets = if ctx.accounts.default_rule_sets_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.default_rule_sets_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            DefaultRuleSets::size(),
+            &signer_seeds,
+        )?;
+
+        DefaultRuleSets {
+            bump: bump[0],
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.default_rule_sets_info, program_id)?;
+        DefaultRuleSets::from_account_info(ctx.accounts.default_rule_sets_info)?
+    };
+
+    default_rule_sets.set(token_standard, rule_set);
+    default_rule_sets.save(
+        ctx.accounts.default_rule_sets_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
@@ -0,0 +1,135 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_initialized_token_account, assert_keys_equal, assert_owned_by,
+        assert_owned_by_token_program, metadata::assert_metadata_derivation,
+    },
+    error::MetadataError,
+    instruction::{Context, SettlePrimarySale, SettlePrimarySaleArgs},
+    state::{
+        split_royalties, AuthorityRequest, AuthorityResponse, AuthorityType, CreatorsOverflow,
+        Metadata, TokenDelegateRole, TokenMetadataAccount,
+    },
+    utils::{clean_write_metadata, pay_creators, TokenPaymentAccounts},
+};
+
+/// Settles the primary sale of an asset, paying the proceeds to its verified creators and
+/// setting `primary_sale_happened`.
+pub fn settle_primary_sale<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SettlePrimarySaleArgs,
+) -> ProgramResult {
+    let context = SettlePrimarySale::to_context(accounts)?;
+
+    match args {
+        SettlePrimarySaleArgs::V1 { amount } => settle_primary_sale_v1(program_id, context, amount),
+    }
+}
+
+fn settle_primary_sale_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, SettlePrimarySale<'a>>,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Err(MetadataError::AmountMustBeGreaterThanZero.into());
+    }
+
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by(ctx.accounts.token_info, ctx.accounts.mint_info.owner)?;
+
+    if let Some(creators_overflow_info) = ctx.accounts.creators_overflow_info {
+        assert_owned_by(creators_overflow_info, program_id)?;
+    }
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if metadata.primary_sale_happened {
+        return Err(MetadataError::PrimarySaleAlreadyHappened.into());
+    }
+
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
+
+    if token.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount == 0 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    // the settlement is signed by the holder or a sale delegate
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            precedence: &[AuthorityType::TokenDelegate, AuthorityType::Holder],
+            authority: ctx.accounts.authority_info.key,
+            update_authority: &metadata.update_authority,
+            mint: ctx.accounts.mint_info.key,
+            token: Some(ctx.accounts.token_info.key),
+            token_account: Some(&token),
+            token_record_info: ctx.accounts.token_record_info,
+            token_delegate_roles: vec![TokenDelegateRole::Sale],
+            ..Default::default()
+        })?;
+
+    if !matches!(
+        authority_type,
+        AuthorityType::TokenDelegate | AuthorityType::Holder
+    ) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    let overflow = ctx
+        .accounts
+        .creators_overflow_info
+        .map(CreatorsOverflow::from_account_info)
+        .transpose()?;
+
+    let creators = metadata
+        .all_creators(overflow.as_ref())?
+        .ok_or(MetadataError::NoCreatorsPresentOnMetadata)?;
+    let payments = split_royalties(&creators, amount)?;
+
+    let token_accounts = TokenPaymentAccounts::from_optional(
+        ctx.accounts.payer_token_info,
+        ctx.accounts.payment_mint_info,
+        ctx.accounts.spl_token_program_info,
+    )?;
+
+    pay_creators(
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+        token_accounts.as_ref(),
+        &payments,
+        &ctx.remaining_accounts,
+    )?;
+
+    msg!("Primary sale proceeds paid to {} creators", payments.len());
+
+    metadata.primary_sale_happened = true;
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)
+}
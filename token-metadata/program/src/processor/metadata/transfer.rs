@@ -1,36 +1,45 @@
 use std::fmt::Display;
 
-use mpl_token_auth_rules::processor::cmp_pubkeys;
-use mpl_utils::{assert_signer, token::TokenTransferParams};
+use mpl_utils::{
+    assert_signer, cmp_pubkeys,
+    token::{TokenTransferParams, SPL_TOKEN_PROGRAM_IDS},
+};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
     msg,
-    program::invoke,
+    program::invoke_signed,
     program_error::ProgramError,
     program_option::COption,
-    program_pack::Pack,
     pubkey::Pubkey,
     system_program,
     sysvar::{self, instructions::get_instruction_relative},
 };
-use spl_token::state::Account;
 
+use super::transfer_session::is_covered_by_transfer_session;
 use crate::{
     assertions::{
-        assert_keys_equal, assert_owned_by, assert_token_matches_owner_and_mint,
+        assert_initialized_mint, assert_initialized_token_account, assert_keys_equal,
+        assert_owned_by, assert_token_matches_owner_and_mint,
+        collection::{assert_collection_not_paused, assert_not_denylisted},
         metadata::assert_holding_amount,
+        programmable::{
+            assert_not_collection_frozen, assert_not_holder_locked, assert_not_rented,
+            assert_token_delegate_not_expired,
+        },
     },
     error::MetadataError,
     instruction::{Context, Transfer, TransferArgs},
-    pda::find_token_record_account,
+    pda::{find_default_rule_sets_account, find_metadata_account, find_token_record_account},
     state::{
-        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, Operation, Resizable,
-        TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard,
+        AuthorityRequest, AuthorityResponse, AuthorityType, DefaultRuleSets, Metadata, Operation,
+        ProgrammableConfig, Resizable, TokenDelegateRole, TokenMetadataAccount, TokenRecord,
+        TokenStandard, PREFIX,
     },
     utils::{
-        assert_derivation, auth_rules_validate, create_token_record_account, frozen_transfer,
-        AuthRulesValidateParams,
+        assert_associated_token_account, auth_rules_validate, create_associated_token_account,
+        create_token_record_account, freeze, frozen_transfer, is_auth_rules_program,
+        is_spl_ata_program, thaw, AuthRulesValidateParams,
     },
 };
 
@@ -40,6 +49,7 @@ pub enum TransferScenario {
     TransferDelegate,
     SaleDelegate,
     MigrationDelegate,
+    PermanentDelegate,
 }
 
 impl Display for TransferScenario {
@@ -49,6 +59,7 @@ impl Display for TransferScenario {
             Self::TransferDelegate => write!(f, "TransferDelegate"),
             Self::SaleDelegate => write!(f, "SaleDelegate"),
             Self::MigrationDelegate => write!(f, "MigrationDelegate"),
+            Self::PermanentDelegate => write!(f, "PermanentDelegate"),
         }
     }
 }
@@ -102,8 +113,14 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
 
     // Assert program ownership.
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    assert_owned_by(
+        ctx.accounts.token_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
     if let Some(owner_token_record_info) = ctx.accounts.owner_token_record_info {
         assert_owned_by(owner_token_record_info, program_id)?;
     }
@@ -111,31 +128,28 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
         assert_owned_by(master_edition, program_id)?;
     }
     if let Some(authorization_rules) = ctx.accounts.authorization_rules_info {
-        assert_owned_by(authorization_rules, &mpl_token_auth_rules::ID)?;
+        if !is_auth_rules_program(authorization_rules.owner) {
+            return Err(MetadataError::IncorrectOwner.into());
+        }
     }
 
     // Check if the destination exists.
     if ctx.accounts.destination_info.data_is_empty() {
         // if the token account is empty, we will initialize a new one but it must
         // be a ATA account
-        assert_derivation(
-            &spl_associated_token_account::id(),
+        assert_associated_token_account(
             ctx.accounts.destination_info,
-            &[
-                ctx.accounts.destination_owner_info.key.as_ref(),
-                spl_token::id().as_ref(),
-                ctx.accounts.mint_info.key.as_ref(),
-            ],
+            ctx.accounts.destination_owner_info.key,
+            ctx.accounts.mint_info.key,
+            ctx.accounts.spl_token_program_info.key,
         )?;
 
         // creating the associated token account
-        invoke(
-            &spl_associated_token_account::instruction::create_associated_token_account(
-                ctx.accounts.payer_info.key,
-                ctx.accounts.destination_owner_info.key,
-                ctx.accounts.mint_info.key,
-                &spl_token::id(),
-            ),
+        create_associated_token_account(
+            ctx.accounts.payer_info.key,
+            ctx.accounts.destination_owner_info.key,
+            ctx.accounts.mint_info.key,
+            ctx.accounts.spl_token_program_info.key,
             &[
                 ctx.accounts.payer_info.clone(),
                 ctx.accounts.destination_owner_info.clone(),
@@ -144,7 +158,10 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
             ],
         )?;
     } else {
-        assert_owned_by(ctx.accounts.destination_info, &spl_token::id())?;
+        assert_owned_by(
+            ctx.accounts.destination_info,
+            ctx.accounts.spl_token_program_info.key,
+        )?;
         assert_token_matches_owner_and_mint(
             ctx.accounts.destination_info,
             ctx.accounts.destination_owner_info.key,
@@ -154,11 +171,11 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
 
     // Check program IDs.
 
-    if ctx.accounts.spl_token_program_info.key != &spl_token::ID {
+    if !SPL_TOKEN_PROGRAM_IDS.contains(ctx.accounts.spl_token_program_info.key) {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    if ctx.accounts.spl_ata_program_info.key != &spl_associated_token_account::ID {
+    if !is_spl_ata_program(ctx.accounts.spl_ata_program_info.key) {
         return Err(ProgramError::IncorrectProgramId);
     }
 
@@ -171,7 +188,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
     }
 
     if let Some(auth_rules_program) = ctx.accounts.authorization_rules_program_info {
-        if auth_rules_program.key != &mpl_token_auth_rules::ID {
+        if !is_auth_rules_program(auth_rules_program.key) {
             return Err(ProgramError::IncorrectProgramId);
         }
     }
@@ -179,7 +196,31 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
     let mut is_wallet_to_wallet = false;
 
     // Deserialize metadata.
-    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    // assets using the legacy layout pass the programmable config extension as the first
+    // remaining account
+    metadata.load_programmable_config(ctx.remaining_accounts.first().copied())?;
+
+    // Non-transferable assets never move, regardless of the authority.
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::NonTransferable)
+    ) {
+        return Err(MetadataError::NonTransferableAsset.into());
+    }
+
+    // Verified collection items cannot move while their collection is paused (checked when
+    // the collection pause account is specified).
+    assert_collection_not_paused(program_id, &metadata, ctx.accounts.collection_pause_info)?;
+
+    // Items of collections that opted in to the denylist cannot move while on the denylist
+    // (checked when the collection metadata is specified).
+    assert_not_denylisted(
+        program_id,
+        &metadata,
+        ctx.accounts.collection_metadata_info,
+        ctx.accounts.denylisted_info,
+    )?;
 
     // Must be the actual current owner of the token where
     // mint, token, owner and metadata accounts all match up.
@@ -204,11 +245,16 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
     };
 
     let token_standard = metadata.token_standard;
-    let token = Account::unpack(&ctx.accounts.token_info.try_borrow_data()?)?;
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
 
     msg!("getting authority type");
     let AuthorityResponse { authority_type, .. } =
         AuthorityType::get_authority_type(AuthorityRequest {
+            precedence: &[
+                AuthorityType::TokenDelegate,
+                AuthorityType::Holder,
+                AuthorityType::PermanentDelegate,
+            ],
             authority: ctx.accounts.authority_info.key,
             update_authority: &metadata.update_authority,
             mint: ctx.accounts.mint_info.key,
@@ -221,6 +267,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 TokenDelegateRole::LockedTransfer,
                 TokenDelegateRole::Migration,
             ],
+            permanent_delegate: metadata.permanent_delegate.as_ref(),
             ..Default::default()
         })?;
 
@@ -265,6 +312,13 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 return Err(MetadataError::NotEnoughTokens.into());
             }
         }
+        AuthorityType::PermanentDelegate => {
+            // the program transfers on behalf of the permanent delegate, which does not
+            // need to be the delegate of the token account
+            if token.amount < amount {
+                return Err(MetadataError::NotEnoughTokens.into());
+            }
+        }
         _ => {
             if matches!(token_standard, Some(TokenStandard::ProgrammableNonFungible)) {
                 return Err(MetadataError::InvalidAuthorityType.into());
@@ -330,10 +384,35 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 .map(|role| role == TokenDelegateRole::LockedTransfer)
                 .unwrap_or(false);
 
+            // a listed token can only be transferred by its 'Sale' delegate; token records
+            // created before the 'Listed' state was introduced are identified by the role
+            let is_listed = owner_token_record.is_listed() || is_sale_delegate;
+
+            // a locked token can only be transferred by a 'LockedTransfer' delegate, since
+            // the destination was fixed when the delegate was approved
+            if owner_token_record.is_locked()
+                && !(is_locked_transfer_delegate
+                    && matches!(authority_type, AuthorityType::TokenDelegate))
+            {
+                return Err(MetadataError::LockedToken.into());
+            }
+
+            // the holder lock, collection freezes and active rentals block any transfer,
+            // including from delegates
+            assert_not_holder_locked(&owner_token_record)?;
+            assert_not_collection_frozen(&owner_token_record)?;
+            assert_not_rented(&owner_token_record)?;
+
+            if !destination_token_record_info.data_is_empty()
+                && TokenRecord::from_account_info(destination_token_record_info)?.is_locked()
+            {
+                return Err(MetadataError::LockedToken.into());
+            }
+
             msg!("determining scenario");
             let scenario = match authority_type {
                 AuthorityType::Holder => {
-                    if is_sale_delegate {
+                    if is_listed {
                         return Err(MetadataError::OnlySaleDelegateCanTransfer.into());
                     }
                     TransferScenario::Holder
@@ -342,6 +421,13 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                     if owner_token_record.delegate_role.is_none() {
                         return Err(MetadataError::MissingDelegateRole.into());
                     }
+                    // the 'Listed' state is only valid with a 'Sale' delegate
+                    if is_listed && !is_sale_delegate {
+                        return Err(MetadataError::IncorrectTokenState.into());
+                    }
+
+                    // expired delegates can no longer transfer the asset
+                    assert_token_delegate_not_expired(&owner_token_record)?;
 
                     // need to validate whether the destination key matches the locked
                     // transfer address
@@ -360,9 +446,31 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                         owner_token_record.delegate_role.unwrap().into()
                     }
                 }
+                AuthorityType::PermanentDelegate => TransferScenario::PermanentDelegate,
                 _ => return Err(MetadataError::InvalidTransferAuthority.into()),
             };
 
+            // assets without a rule set can opt in to the default rule set of their
+            // token standard
+            let default_rule_sets = match metadata.programmable_config {
+                Some(ProgrammableConfig::V1 { rule_set: Some(_) }) => None,
+                _ if metadata.use_default_rule_set => {
+                    load_default_rule_sets(program_id, ctx.accounts.default_rule_sets_info)?
+                }
+                _ => None,
+            };
+
+            // an open transfer session replaces the rule set validation of the transfers
+            // it covers
+            let covered_by_session = ctx.accounts.transfer_session_info.is_some()
+                && is_covered_by_transfer_session(
+                    program_id,
+                    &ctx,
+                    &metadata,
+                    &owner_token_record,
+                    &authority_type,
+                )?;
+
             // Build our auth rules params.
             let auth_rules_validate_params = AuthRulesValidateParams {
                 mint_info: ctx.accounts.mint_info,
@@ -370,7 +478,8 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 authority_info: Some(ctx.accounts.authority_info),
                 source_info: Some(ctx.accounts.token_owner_info),
                 destination_info: Some(ctx.accounts.destination_owner_info),
-                programmable_config: metadata.programmable_config,
+                programmable_config: metadata
+                    .effective_programmable_config(default_rule_sets.as_ref()),
                 amount,
                 auth_data,
                 auth_rules_info: ctx.accounts.authorization_rules_info,
@@ -381,9 +490,24 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                     .map(|revision| revision as usize),
             };
 
-            auth_rules_validate(auth_rules_validate_params)?;
-            frozen_transfer(token_transfer_params, ctx.accounts.edition_info)?;
+            if !covered_by_session {
+                auth_rules_validate(auth_rules_validate_params)?;
+            }
 
+            if matches!(authority_type, AuthorityType::PermanentDelegate) {
+                permanent_delegate_transfer(&ctx, amount, true)?;
+            } else {
+                frozen_transfer(token_transfer_params, ctx.accounts.edition_info)?;
+            }
+
+            // a completed transfer ends any listing: the state returns to 'Unlocked'
+            // and the token delegate is cleared
+            if is_listed {
+                msg!(
+                    "Listing of {} closed by transfer",
+                    ctx.accounts.mint_info.key
+                );
+            }
             owner_token_record.reset();
             owner_token_record.save(
                 owner_token_record_info,
@@ -406,8 +530,110 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 )?;
             }
         }
-        _ => mpl_utils::token::spl_token_transfer(token_transfer_params).unwrap(),
+        _ => {
+            if matches!(authority_type, AuthorityType::PermanentDelegate) {
+                permanent_delegate_transfer(&ctx, amount, false)?;
+            } else {
+                mpl_utils::token::spl_token_transfer(token_transfer_params).unwrap();
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Transfers tokens on behalf of the permanent delegate of the asset.
+///
+/// The metadata account is the permanent delegate of the mint (`PermanentDelegate` extension
+/// of Token-2022), so the transfer is signed by the program. A frozen token account is thawed
+/// by the master edition first, and the destination is frozen when `freeze_destination` is set.
+fn permanent_delegate_transfer<'a>(
+    ctx: &Context<'a, Transfer<'a>>,
+    amount: u64,
+    freeze_destination: bool,
+) -> ProgramResult {
+    let token = assert_initialized_token_account(ctx.accounts.token_info)?;
+
+    if token.is_frozen() {
+        let edition_info = ctx
+            .accounts
+            .edition_info
+            .ok_or(MetadataError::MissingEditionAccount)?;
+
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    let mint = assert_initialized_mint(ctx.accounts.mint_info)?;
+
+    let (metadata_key, bump) = find_metadata_account(ctx.accounts.mint_info.key);
+    assert_keys_equal(&metadata_key, ctx.accounts.metadata_info.key)?;
+    let signer_seeds = &[
+        PREFIX.as_bytes(),
+        crate::ID.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        &[bump],
+    ];
+
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            ctx.accounts.spl_token_program_info.key,
+            ctx.accounts.token_info.key,
+            ctx.accounts.mint_info.key,
+            ctx.accounts.destination_info.key,
+            ctx.accounts.metadata_info.key,
+            &[],
+            amount,
+            mint.decimals,
+        )?,
+        &[
+            ctx.accounts.token_info.clone(),
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.destination_info.clone(),
+            ctx.accounts.metadata_info.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    if freeze_destination {
+        let edition_info = ctx
+            .accounts
+            .edition_info
+            .ok_or(MetadataError::MissingEditionAccount)?;
+
+        freeze(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.destination_info.clone(),
+            edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Loads the default rule sets account, returning `None` if the account was never created.
+fn load_default_rule_sets(
+    program_id: &Pubkey,
+    default_rule_sets_info: Option<&AccountInfo>,
+) -> Result<Option<DefaultRuleSets>, ProgramError> {
+    let default_rule_sets_info =
+        default_rule_sets_info.ok_or(MetadataError::MissingDefaultRuleSets)?;
+
+    let (pda_key, _) = find_default_rule_sets_account();
+    assert_keys_equal(&pda_key, default_rule_sets_info.key)?;
+
+    if default_rule_sets_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    assert_owned_by(default_rule_sets_info, program_id)?;
+
+    Ok(Some(DefaultRuleSets::from_account_info(
+        default_rule_sets_info,
+    )?))
+}
@@ -0,0 +1,307 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, cmp_pubkeys, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
+};
+
+use super::TransferScenario;
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::assert_metadata_derivation,
+    },
+    error::MetadataError,
+    instruction::{
+        BeginTransferSession, BeginTransferSessionArgs, Context, EndTransferSession,
+        EndTransferSessionArgs, Transfer,
+    },
+    pda::{PREFIX, TRANSFER_SESSION},
+    processor::AuthorizationData,
+    state::{
+        AuthorityType, Key, Metadata, Operation, ProgrammableConfig, TokenMetadataAccount,
+        TokenRecord, TokenStandard, TransferSession, MAX_TRANSFER_SESSION_SLOTS,
+        TRANSFER_SESSION_SIZE,
+    },
+    utils::{
+        auth_rules_validate, close_account_checked, get_latest_revision, is_auth_rules_program,
+        AuthRulesValidateParams,
+    },
+};
+
+/// Opens a transfer session, validating the rule set of an asset of a collection once.
+pub fn begin_transfer_session<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BeginTransferSessionArgs,
+) -> ProgramResult {
+    let context = BeginTransferSession::to_context(accounts)?;
+
+    match args {
+        BeginTransferSessionArgs::V1 {
+            amount,
+            duration_slots,
+            authorization_data,
+        } => begin_transfer_session_v1(
+            program_id,
+            context,
+            amount,
+            duration_slots,
+            authorization_data,
+        ),
+    }
+}
+
+/// Closes a transfer session.
+pub fn end_transfer_session<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: EndTransferSessionArgs,
+) -> ProgramResult {
+    let context = EndTransferSession::to_context(accounts)?;
+
+    match args {
+        EndTransferSessionArgs::V1 => end_transfer_session_v1(program_id, context),
+    }
+}
+
+fn begin_transfer_session_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, BeginTransferSession<'a>>,
+    amount: u64,
+    duration_slots: u64,
+    authorization_data: Option<AuthorizationData>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    if duration_slots == 0 || duration_slots > MAX_TRANSFER_SESSION_SLOTS {
+        return Err(MetadataError::InvalidTransferSessionDuration.into());
+    }
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if !matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    // the session covers the assets of the verified collection of the asset
+    let collection = match &metadata.collection {
+        Some(collection) if collection.verified => collection.key,
+        Some(_) => return Err(MetadataError::NotVerifiedMemberOfCollection.into()),
+        None => return Err(MetadataError::CollectionNotFound.into()),
+    };
+
+    // only assets with a rule set have a validation to cache
+    let rule_set = match metadata.programmable_config {
+        Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) => rule_set,
+        _ => return Err(MetadataError::MissingAuthorizationRules.into()),
+    };
+
+    let authorization_rules_info = ctx
+        .accounts
+        .authorization_rules_info
+        .ok_or(MetadataError::MissingAuthorizationRules)?;
+    assert_keys_equal(authorization_rules_info.key, &rule_set)?;
+    if !is_auth_rules_program(authorization_rules_info.owner) {
+        return Err(MetadataError::IncorrectOwner.into());
+    }
+
+    let authorization_rules_program_info = ctx
+        .accounts
+        .authorization_rules_program_info
+        .ok_or(MetadataError::MissingAuthorizationRulesProgram)?;
+    if !is_auth_rules_program(authorization_rules_program_info.key) {
+        return Err(MetadataError::KeyMismatch.into());
+    }
+
+    // validates the rule set for a holder transfer to the destination owner
+
+    auth_rules_validate(AuthRulesValidateParams {
+        mint_info: ctx.accounts.mint_info,
+        owner_info: None,
+        authority_info: Some(ctx.accounts.authority_info),
+        source_info: Some(ctx.accounts.authority_info),
+        destination_info: Some(ctx.accounts.destination_owner_info),
+        programmable_config: metadata.programmable_config.clone(),
+        amount,
+        auth_data: authorization_data,
+        auth_rules_info: Some(authorization_rules_info),
+        operation: Operation::Transfer {
+            scenario: TransferScenario::Holder,
+        },
+        is_wallet_to_wallet: false,
+        rule_set_revision: None,
+    })?;
+
+    // caches the result on the session account
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.authority_info.key.as_ref(),
+        TRANSFER_SESSION.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.transfer_session_info,
+        &signer_seeds,
+    )?];
+
+    if ctx.accounts.transfer_session_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.transfer_session_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            TRANSFER_SESSION_SIZE,
+            &signer_seeds,
+        )?;
+    } else {
+        // replaces the open session of the authority
+        assert_owned_by(ctx.accounts.transfer_session_info, program_id)?;
+        TransferSession::from_account_info(ctx.accounts.transfer_session_info)?;
+    }
+
+    let expiry_slot = Clock::get()?
+        .slot
+        .checked_add(duration_slots)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+    let session = TransferSession {
+        bump: bump[0],
+        authority: *ctx.accounts.authority_info.key,
+        rule_set,
+        rule_set_revision: get_latest_revision(authorization_rules_info)?
+            .map(|revision| revision as u64),
+        collection,
+        expiry_slot,
+        ..Default::default()
+    };
+    session.serialize(&mut *ctx.accounts.transfer_session_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Transfer session of {} opened until slot {}",
+        ctx.accounts.authority_info.key,
+        expiry_slot
+    );
+
+    Ok(())
+}
+
+fn end_transfer_session_v1(program_id: &Pubkey, ctx: Context<EndTransferSession>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.transfer_session_info, program_id)?;
+
+    // account relationships
+
+    let session = TransferSession::from_account_info(ctx.accounts.transfer_session_info)?;
+
+    if !cmp_pubkeys(&session.authority, ctx.accounts.authority_info.key) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    close_account_checked(
+        ctx.accounts.transfer_session_info,
+        ctx.accounts.authority_info,
+        Key::TransferSession,
+    )
+}
+
+/// Checks whether a transfer is covered by the transfer session included in the transfer, in
+/// which case the rule set validation is skipped.
+///
+/// The session must belong to the authority and must not be expired. A holder transfer is
+/// covered when the asset is a verified item of the collection of the session and has the rule
+/// set (and revision) validated by the session; otherwise the rule set must be validated in
+/// full.
+pub(crate) fn is_covered_by_transfer_session(
+    program_id: &Pubkey,
+    ctx: &Context<Transfer>,
+    metadata: &Metadata,
+    token_record: &TokenRecord,
+    authority_type: &AuthorityType,
+) -> Result<bool, ProgramError> {
+    let transfer_session_info = ctx
+        .accounts
+        .transfer_session_info
+        .ok_or(MetadataError::InvalidTransferSession)?;
+
+    assert_owned_by(transfer_session_info, program_id)?;
+    let session = TransferSession::from_account_info(transfer_session_info)?;
+
+    if !cmp_pubkeys(&session.authority, ctx.accounts.authority_info.key) {
+        return Err(MetadataError::InvalidTransferSession.into());
+    }
+
+    if session.is_expired(Clock::get()?.slot) {
+        return Err(MetadataError::TransferSessionExpired.into());
+    }
+
+    let rule_set_matches = matches!(
+        metadata.programmable_config,
+        Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) if cmp_pubkeys(&rule_set, &session.rule_set)
+    );
+
+    let collection_matches = matches!(
+        &metadata.collection,
+        Some(collection) if collection.verified && cmp_pubkeys(&collection.key, &session.collection)
+    );
+
+    if !rule_set_matches || !collection_matches || !matches!(authority_type, AuthorityType::Holder)
+    {
+        return Ok(false);
+    }
+
+    // a new revision of the rule set invalidates the session
+    let authorization_rules_info = ctx
+        .accounts
+        .authorization_rules_info
+        .ok_or(MetadataError::MissingAuthorizationRules)?;
+    assert_keys_equal(authorization_rules_info.key, &session.rule_set)?;
+
+    let rule_set_revision = match token_record.rule_set_revision {
+        Some(revision) => Some(revision),
+        None => get_latest_revision(authorization_rules_info)?.map(|revision| revision as u64),
+    };
+
+    if rule_set_revision != session.rule_set_revision {
+        return Ok(false);
+    }
+
+    msg!("Transfer covered by the transfer session");
+
+    Ok(true)
+}
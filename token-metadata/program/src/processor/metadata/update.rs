@@ -1,6 +1,7 @@
 use std::fmt::{Display, Formatter};
 
-use mpl_utils::assert_signer;
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
     program_pack::Pack, pubkey::Pubkey, sysvar,
@@ -8,15 +9,24 @@ use solana_program::{
 use spl_token::state::Account;
 
 use crate::{
-    assertions::{assert_owned_by, programmable::assert_valid_authorization},
+    assertions::{
+        assert_owned_by,
+        programmable::{assert_metadata_delegate_not_expired, assert_valid_authorization},
+    },
     error::MetadataError,
-    instruction::{Context, MetadataDelegateRole, Update, UpdateArgs},
-    pda::{EDITION, PREFIX},
+    instruction::{
+        CollectionToggle, Context, MetadataDelegateRole, MetadataInstruction, Update, UpdateArgs,
+    },
+    pda::{CONTENT_RATING, EDITION, PREFIX},
+    processor::{approve_authority_action, verify_collection_item, CollectionVerifyAccounts},
     state::{
-        AuthorityRequest, AuthorityResponse, AuthorityType, Collection, Metadata,
-        ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+        AuthorityRequest, AuthorityResponse, AuthorityType, Collection, ContentRating,
+        ContentRatingRecord, Metadata, ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+    },
+    utils::{
+        assert_derivation, assert_governance_execution, check_token_standard,
+        clean_write_metadata_with_payer, is_auth_rules_program, is_governance_account,
     },
-    utils::{assert_derivation, check_token_standard},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -48,7 +58,11 @@ pub fn update<'a>(
     }
 }
 
-fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> ProgramResult {
+fn update_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Update<'a>>,
+    args: UpdateArgs,
+) -> ProgramResult {
     //** Account Validation **/
     // Assert signers
 
@@ -98,7 +112,7 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
     // to be passed in.
     if ctx.accounts.authorization_rules_info.is_some() {
         if let Some(authorization_rules_program) = ctx.accounts.authorization_rules_program_info {
-            if authorization_rules_program.key != &mpl_token_auth_rules::ID {
+            if !is_auth_rules_program(authorization_rules_program.key) {
                 return Err(ProgramError::IncorrectProgramId);
             }
         } else {
@@ -114,6 +128,43 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
         return Err(MetadataError::MintMismatch.into());
     }
 
+    // The programmable config extension is expected as the first remaining account when
+    // the asset uses the legacy layout.
+    let remaining_accounts = if metadata.programmable_config_in_extension {
+        let (extension_info, remaining_accounts) = ctx
+            .remaining_accounts
+            .split_first()
+            .ok_or(MetadataError::InvalidProgrammableConfigExtension)?;
+
+        metadata.load_programmable_config(Some(extension_info))?;
+        remaining_accounts
+    } else {
+        ctx.remaining_accounts.as_slice()
+    };
+
+    // When the update authority is an authority config, the signer approves the update
+    // and the update is only applied once the threshold of the config is reached; the
+    // authority config then acts as the authority of the update.
+    let authority_info = if let Some(authority_config_info) = ctx.accounts.authority_config_info {
+        if metadata.update_authority != *authority_config_info.key {
+            return Err(MetadataError::UpdateAuthorityIncorrect.into());
+        }
+
+        if !approve_authority_action(
+            program_id,
+            authority_config_info,
+            ctx.accounts.authority_info,
+            ctx.accounts.metadata_info.key,
+            &MetadataInstruction::Update(args.clone()),
+        )? {
+            return Ok(());
+        }
+
+        authority_config_info
+    } else {
+        ctx.accounts.authority_info
+    };
+
     let token_standard = if let Some(token_standard) = metadata.token_standard {
         token_standard
     } else {
@@ -145,14 +196,18 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
         metadata_delegate_role,
         ..
     } = AuthorityType::get_authority_type(AuthorityRequest {
-        authority: ctx.accounts.authority_info.key,
+        authority: authority_info.key,
         update_authority: &metadata.update_authority,
         mint: ctx.accounts.mint_info.key,
         collection_mint,
         token: token_pubkey,
         token_account: token.as_ref(),
         metadata_delegate_record_info: ctx.accounts.delegate_record_info,
-        metadata_delegate_roles: vec![MetadataDelegateRole::ProgrammableConfig],
+        metadata_delegate_roles: vec![
+            MetadataDelegateRole::ProgrammableConfig,
+            MetadataDelegateRole::Data,
+            MetadataDelegateRole::Uses,
+        ],
         precedence: &[
             AuthorityType::Metadata,
             AuthorityType::MetadataDelegate,
@@ -161,6 +216,34 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
         ..Default::default()
     })?;
 
+    // expired delegates can no longer update the asset
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) =
+        (&authority_type, ctx.accounts.delegate_record_info)
+    {
+        assert_metadata_delegate_not_expired(delegate_record_info)?;
+    }
+
+    // When the update authority is a governance account, the update must be part of a
+    // proposal execution; the executing proposal transaction is expected as the next
+    // remaining account (after the programmable config extension).
+    let remaining_accounts = if matches!(authority_type, AuthorityType::Metadata)
+        && is_governance_account(authority_info)
+    {
+        let (proposal_transaction_info, remaining_accounts) = remaining_accounts
+            .split_first()
+            .ok_or(MetadataError::InvalidGovernanceExecution)?;
+
+        assert_governance_execution(
+            authority_info,
+            proposal_transaction_info,
+            ctx.accounts.sysvar_instructions_info,
+        )?;
+
+        remaining_accounts
+    } else {
+        remaining_accounts
+    };
+
     // For pNFTs, we need to validate the authorization rules.
     if matches!(token_standard, TokenStandard::ProgrammableNonFungible) {
         // If the metadata account has a current rule set, we validate that
@@ -176,21 +259,166 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
 
     validate_update(&args, &authority_type, metadata_delegate_role)?;
 
+    let mut args = args;
+    let UpdateArgs::V1 {
+        collection,
+        content_rating,
+        ..
+    } = &mut args;
+
+    // The content rating record is expected as the first remaining account (after the
+    // programmable config extension and proposal transaction), ahead of the collection
+    // accounts.
+    let remaining_accounts = if let Some(rating) = content_rating.take() {
+        let (content_rating_info, remaining_accounts) = remaining_accounts
+            .split_first()
+            .ok_or(MetadataError::MissingContentRatingAccount)?;
+
+        set_content_rating(
+            program_id,
+            ctx.accounts.mint_info,
+            content_rating_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+            rating,
+        )?;
+
+        remaining_accounts
+    } else {
+        remaining_accounts
+    };
+
+    // Setting a verified collection verifies the item in the same instruction when the
+    // authority is also the authority of the collection (or a collection delegate).
+
+    if let (
+        AuthorityType::Metadata,
+        CollectionToggle::Set(Collection {
+            key,
+            verified: true,
+        }),
+    ) = (&authority_type, &collection)
+    {
+        let is_verified = matches!(&metadata.collection, Some(current) if current.verified);
+
+        if !is_verified {
+            verify_collection(
+                program_id,
+                authority_info,
+                remaining_accounts,
+                &mut metadata,
+                key,
+            )?;
+            *collection = CollectionToggle::None;
+        }
+    }
+
     // If we reach here without errors we have validated that the authority is allowed to
     // perform an update.
     metadata.update_v1(
         args,
-        ctx.accounts.authority_info,
-        ctx.accounts.metadata_info,
+        authority_info,
         token,
         Some(token_standard),
         authority_type,
         metadata_delegate_role,
     )?;
 
+    clean_write_metadata_with_payer(
+        &mut metadata,
+        ctx.accounts.metadata_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
+
+/// Sets the content rating of the asset, creating the content rating record if needed.
+///
+/// The rating is stored outside of the metadata account, so it can be set on immutable
+/// assets as well.
+fn set_content_rating<'a>(
+    program_id: &Pubkey,
+    mint_info: &'a AccountInfo<'a>,
+    content_rating_info: &'a AccountInfo<'a>,
+    payer_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+    rating: ContentRating,
+) -> ProgramResult {
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        CONTENT_RATING.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        content_rating_info,
+        &signer_seeds,
+    )?];
+
+    let mut record = if content_rating_info.data_is_empty() {
+        assert_signer(payer_info)?;
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            content_rating_info,
+            system_program_info,
+            payer_info,
+            ContentRatingRecord::size(),
+            &signer_seeds,
+        )?;
+
+        ContentRatingRecord {
+            bump: bump[0],
+            mint: *mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(content_rating_info, program_id)?;
+        ContentRatingRecord::from_account_info(content_rating_info)?
+    };
+
+    record.rating = rating;
+    record.serialize(&mut *content_rating_info.try_borrow_mut_data()?)?;
+
     Ok(())
 }
 
+/// Verifies the asset as a member of the collection, incrementing the size of sized
+/// collections. The collection accounts are expected in the remaining accounts:
+///
+///   0. `[]` Collection mint
+///   1. `[writable]` Collection metadata
+///   2. `[]` Collection master edition
+///   3. `[optional]` Collection delegate record
+fn verify_collection<'a>(
+    program_id: &Pubkey,
+    authority_info: &'a AccountInfo<'a>,
+    remaining_accounts: &[&'a AccountInfo<'a>],
+    metadata: &mut Metadata,
+    collection_mint: &Pubkey,
+) -> ProgramResult {
+    if remaining_accounts.len() < 3 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts = CollectionVerifyAccounts {
+        collection_mint_info: remaining_accounts[0],
+        collection_metadata_info: remaining_accounts[1],
+        collection_master_edition_info: remaining_accounts[2],
+        delegate_record_info: remaining_accounts.get(3).copied(),
+    };
+
+    verify_collection_item(
+        program_id,
+        authority_info,
+        &accounts,
+        metadata,
+        collection_mint,
+    )
+}
+
 /// Validates that the authority is only updating metadata fields
 /// that it has access to.
 fn validate_update(
@@ -215,7 +443,7 @@ fn validate_update(
             return Err(MetadataError::FeatureNotSupported.into());
         }
         _ => {
-            return Err(MetadataError::InvalidAuthorityType.into());
+            return Err(MetadataError::UnauthorizedUpdate.into());
         }
     }
 
@@ -226,7 +454,12 @@ fn validate_update(
         collection,
         uses,
         new_update_authority,
+        rule_set,
         collection_details,
+        inherit_royalties,
+        use_default_rule_set,
+        unverify_creators_on_authority_change,
+        content_rating,
         ..
     } = args;
 
@@ -242,12 +475,49 @@ fn validate_update(
                 || uses.is_some()
                 || new_update_authority.is_some()
                 || collection_details.is_some()
+                || unverify_creators_on_authority_change.is_some()
+                || content_rating.is_some()
+            {
+                return Err(MetadataError::InvalidUpdateArgs.into());
+            }
+        }
+        Some(MetadataDelegateRole::Data) => {
+            // can only update the data; authority, rule set and collection
+            // fields are reserved to the update authority
+            if primary_sale_happened.is_some()
+                || is_mutable.is_some()
+                || collection.is_some()
+                || uses.is_some()
+                || new_update_authority.is_some()
+                || rule_set.is_some()
+                || collection_details.is_some()
+                || inherit_royalties.is_some()
+                || use_default_rule_set.is_some()
+                || unverify_creators_on_authority_change.is_some()
+                || content_rating.is_some()
+            {
+                return Err(MetadataError::InvalidUpdateArgs.into());
+            }
+        }
+        Some(MetadataDelegateRole::Uses) => {
+            // can only update the uses
+            if data.is_some()
+                || primary_sale_happened.is_some()
+                || is_mutable.is_some()
+                || collection.is_some()
+                || new_update_authority.is_some()
+                || rule_set.is_some()
+                || collection_details.is_some()
+                || inherit_royalties.is_some()
+                || use_default_rule_set.is_some()
+                || unverify_creators_on_authority_change.is_some()
+                || content_rating.is_some()
             {
                 return Err(MetadataError::InvalidUpdateArgs.into());
             }
         }
         Some(_) => {
-            return Err(MetadataError::InvalidAuthorityType.into());
+            return Err(MetadataError::InvalidUpdateDelegateRole.into());
         }
         None => { /* no delegate role to check */ }
     }
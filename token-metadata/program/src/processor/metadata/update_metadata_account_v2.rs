@@ -44,7 +44,7 @@ pub fn process_update_metadata_accounts_v2(
                 false,
                 update_authority_info.is_signer,
             )?;
-            metadata.data = compatible_data;
+            metadata.set_data(compatible_data);
             // If the user passes in Collection data, only allow updating if it's unverified
             // or if it exactly matches the existing collection info.
             // If the user passes in None for the Collection data then only set it if it's unverified.
@@ -68,7 +68,7 @@ pub fn process_update_metadata_accounts_v2(
     }
 
     if let Some(val) = update_authority {
-        metadata.update_authority = val;
+        metadata.set_update_authority(val);
     }
 
     if let Some(val) = primary_sale_happened {
@@ -0,0 +1,64 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
+
+use crate::{
+    assertions::{assert_owned_by, assert_writable},
+    error::MetadataError,
+    instruction::{Context, VerifyContent, VerifyContentArgs},
+    state::{Metadata, TokenMetadataAccount},
+    utils::clean_write_metadata,
+};
+
+/// Attests the hash of the off-chain JSON of an asset on behalf of one of its creators.
+pub fn verify_content<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: VerifyContentArgs,
+) -> ProgramResult {
+    let context = VerifyContent::to_context(accounts)?;
+
+    match args {
+        VerifyContentArgs::V1 { uri_hash } => verify_content_v1(program_id, context, uri_hash),
+    }
+}
+
+fn verify_content_v1(
+    program_id: &Pubkey,
+    ctx: Context<VerifyContent>,
+    uri_hash: [u8; 32],
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.creator_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_writable(ctx.accounts.metadata_info)?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    // only the creators stored on the metadata can attest the content
+    let is_creator = metadata.data.creators.as_ref().map_or(false, |creators| {
+        creators
+            .iter()
+            .any(|creator| cmp_pubkeys(&creator.address, ctx.accounts.creator_info.key))
+    });
+
+    if !is_creator {
+        return Err(MetadataError::MustBeOneOfCreators.into());
+    }
+
+    // the content of an immutable asset is attested once, so buyers can rely on the
+    // hash not being replaced together with the off-chain content
+    if !metadata.is_mutable && metadata.uri_hash.is_some() {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    metadata.uri_hash = Some(uri_hash);
+    msg!("Content verified by {}", ctx.accounts.creator_info.key);
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)
+}
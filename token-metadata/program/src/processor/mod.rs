@@ -1,29 +1,38 @@
+mod archive;
+mod attestation;
+mod authorization;
 mod bubblegum;
 mod burn;
+mod capabilities;
 mod collection;
 mod delegate;
 pub(crate) mod deprecated;
 mod edition;
 pub(crate) mod escrow;
+mod fee;
+mod flag;
 mod freeze;
 mod metadata;
 mod state;
 mod uses;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+pub use archive::*;
+pub use attestation::*;
+pub use authorization::*;
+use borsh::BorshDeserialize;
 pub use bubblegum::*;
 pub use burn::*;
+pub use capabilities::*;
 pub use collection::*;
 pub use delegate::*;
-use deprecated::process_create_metadata_accounts_v2;
+use deprecated::{log_deprecated, process_create_metadata_accounts_v2};
 pub use edition::*;
 pub use escrow::*;
+pub use fee::*;
+pub use flag::*;
 pub use freeze::*;
 pub use metadata::*;
-use mpl_token_auth_rules::payload::Payload;
 use mpl_utils::cmp_pubkeys;
-#[cfg(feature = "serde-feature")]
-use serde::{Deserialize, Serialize};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
     pubkey::Pubkey,
@@ -31,6 +40,8 @@ use solana_program::{
 pub use state::*;
 pub use uses::*;
 
+#[cfg(not(feature = "auth-rules"))]
+pub use crate::state::token_auth_payload::{LeafInfo, Payload, PayloadType, SeedsVec};
 use crate::{
     deprecated_processor::{
         process_deprecated_create_metadata_accounts, process_deprecated_update_metadata_accounts,
@@ -50,24 +61,6 @@ use crate::{
     },
 };
 
-#[repr(C)]
-#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
-pub struct AuthorizationData {
-    pub payload: Payload,
-}
-
-impl AuthorizationData {
-    pub fn new(payload: Payload) -> Self {
-        Self { payload }
-    }
-    pub fn new_empty() -> Self {
-        Self {
-            payload: Payload::new(),
-        }
-    }
-}
-
 /// Process Token Metadata instructions.
 ///
 /// The processor is divided into two parts:
@@ -85,8 +78,21 @@ pub fn process_instruction<'a>(
     // checks if there is a locked token; this will block any instruction that
     // requires the token record account when the token is locked – 'Update' is
     // an example of an instruction that does not require the token record, so
-    // it can be executed even when a token is locked
-    if is_locked(program_id, accounts) && !matches!(instruction, MetadataInstruction::Unlock(_)) {
+    // it can be executed even when a token is locked; 'Transfer' validates the
+    // lock itself, since a 'LockedTransfer' delegate can transfer a locked token
+    // to its locked address; the holder lock is independent of the token state,
+    // so a locked token can also be locked by its holder; 'UnlockAndRevoke'
+    // validates the lock in the same way as 'Unlock'
+    if is_locked(program_id, accounts)
+        && !matches!(
+            instruction,
+            MetadataInstruction::Unlock(_)
+                | MetadataInstruction::UnlockAndRevoke(_)
+                | MetadataInstruction::Transfer(_)
+                | MetadataInstruction::SelfLock(_)
+                | MetadataInstruction::SelfUnlock(_)
+        )
+    {
         return Err(MetadataError::LockedToken.into());
     }
 
@@ -132,10 +138,207 @@ pub fn process_instruction<'a>(
             msg!("IX: Update");
             metadata::update(program_id, accounts, args)
         }
-        MetadataInstruction::Verify(args) => {
+        MetadataInstruction::Verify(_) => {
             msg!("IX: Verify");
+            // replaced by 'VerifyV2'
+            Err(MetadataError::FeatureNotSupported.into())
+        }
+        MetadataInstruction::Unverify(args) => {
+            msg!("IX: Unverify");
+            collection::unverify(program_id, accounts, args)
+        }
+        MetadataInstruction::EscrowTransferOut(args) => {
+            msg!("IX: Escrow Transfer Out");
+            escrow::escrow_transfer_out(program_id, accounts, args)
+        }
+        MetadataInstruction::EscrowDeposit(args) => {
+            msg!("IX: Escrow Deposit");
+            escrow::escrow_deposit(program_id, accounts, args)
+        }
+        MetadataInstruction::ProposeRuleSet(args) => {
+            msg!("IX: Propose Rule Set");
+            metadata::propose_rule_set(program_id, accounts, args)
+        }
+        MetadataInstruction::CommitRuleSet(args) => {
+            msg!("IX: Commit Rule Set");
+            metadata::commit_rule_set(program_id, accounts, args)
+        }
+        MetadataInstruction::PauseCollection(args) => {
+            msg!("IX: Pause Collection");
+            collection::pause_collection(program_id, accounts, args)
+        }
+        MetadataInstruction::UnpauseCollection(args) => {
+            msg!("IX: Unpause Collection");
+            collection::unpause_collection(program_id, accounts, args)
+        }
+        MetadataInstruction::CreateAuthorityConfig(args) => {
+            msg!("IX: Create Authority Config");
+            metadata::create_authority_config(program_id, accounts, args)
+        }
+        MetadataInstruction::CollectFees(args) => {
+            msg!("IX: Collect Fees");
+            fee::collect_fees(program_id, accounts, args)
+        }
+        MetadataInstruction::SetCreatorsOverflow(args) => {
+            msg!("IX: Set Creators Overflow");
+            metadata::set_creators_overflow(program_id, accounts, args)
+        }
+        MetadataInstruction::PayRoyalties(args) => {
+            msg!("IX: Pay Royalties");
+            metadata::pay_royalties(program_id, accounts, args)
+        }
+        MetadataInstruction::SettlePrimarySale(args) => {
+            msg!("IX: Settle Primary Sale");
+            metadata::settle_primary_sale(program_id, accounts, args)
+        }
+        MetadataInstruction::ExpireCollectionMembership(args) => {
+            msg!("IX: Expire Collection Membership");
+            collection::expire_collection_membership(program_id, accounts, args)
+        }
+        MetadataInstruction::SetCollectionFreezeOptIn(args) => {
+            msg!("IX: Set Collection Freeze Opt-In");
+            state::set_collection_freeze_opt_in(program_id, accounts, args)
+        }
+        MetadataInstruction::FreezeCollectionItem(args) => {
+            msg!("IX: Freeze Collection Item");
+            state::freeze_collection_item(program_id, accounts, args)
+        }
+        MetadataInstruction::ThawCollectionItem(args) => {
+            msg!("IX: Thaw Collection Item");
+            state::thaw_collection_item(program_id, accounts, args)
+        }
+        MetadataInstruction::ApproveAttestation(args) => {
+            msg!("IX: Approve Attestation");
+            attestation::approve_attestation(program_id, accounts, args)
+        }
+        MetadataInstruction::RevokeAttestation(args) => {
+            msg!("IX: Revoke Attestation");
+            attestation::revoke_attestation(program_id, accounts, args)
+        }
+        MetadataInstruction::CreateExistingMintMetadata(args) => {
+            msg!("IX: Create Existing Mint Metadata");
+            metadata::create_existing_mint_metadata(program_id, accounts, args)
+        }
+        MetadataInstruction::SetLegacyLayout(args) => {
+            msg!("IX: Set Legacy Layout");
+            collection::set_legacy_layout(program_id, accounts, args)
+        }
+        MetadataInstruction::SyncLayout(args) => {
+            msg!("IX: Sync Layout");
+            collection::sync_layout(program_id, accounts, args)
+        }
+        MetadataInstruction::CloseTokenRecord(args) => {
+            msg!("IX: Close Token Record");
+            burn::close_token_record(program_id, accounts, args)
+        }
+        MetadataInstruction::OpenMigrationWindow(args) => {
+            msg!("IX: Open Migration Window");
+            metadata::open_migration_window(program_id, accounts, args)
+        }
+        MetadataInstruction::OptOutMigration(args) => {
+            msg!("IX: Opt Out Migration");
+            metadata::opt_out_migration(program_id, accounts, args)
+        }
+        MetadataInstruction::Flag(args) => {
+            msg!("IX: Flag");
+            flag::flag(program_id, accounts, args)
+        }
+        MetadataInstruction::Unflag(args) => {
+            msg!("IX: Unflag");
+            flag::unflag(program_id, accounts, args)
+        }
+        MetadataInstruction::AddToDenylist(args) => {
+            msg!("IX: Add To Denylist");
+            flag::add_to_denylist(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveFromDenylist(args) => {
+            msg!("IX: Remove From Denylist");
+            flag::remove_from_denylist(program_id, accounts, args)
+        }
+        MetadataInstruction::UseV2(args) => {
+            msg!("IX: Use V2");
+            uses::use_asset(program_id, accounts, args)
+        }
+        MetadataInstruction::VerifyV2(args) => {
+            msg!("IX: Verify V2");
             collection::verify(program_id, accounts, args)
         }
+        MetadataInstruction::DelegateAndLock(args) => {
+            msg!("IX: Delegate and Lock");
+            delegate::delegate_and_lock(program_id, accounts, args)
+        }
+        MetadataInstruction::UnlockAndRevoke(args) => {
+            msg!("IX: Unlock and Revoke");
+            delegate::unlock_and_revoke(program_id, accounts, args)
+        }
+        MetadataInstruction::SetPrintWindow(args) => {
+            msg!("IX: Set Print Window");
+            edition::set_print_window(program_id, accounts, args)
+        }
+        MetadataInstruction::Archive(args) => {
+            msg!("IX: Archive");
+            archive::archive(program_id, accounts, args)
+        }
+        MetadataInstruction::Restore(args) => {
+            msg!("IX: Restore");
+            archive::restore(program_id, accounts, args)
+        }
+        MetadataInstruction::BatchUpdate(args) => {
+            msg!("IX: Batch Update");
+            metadata::batch_update(program_id, accounts, args)
+        }
+        MetadataInstruction::BatchSetTokenStandard(args) => {
+            msg!("IX: Batch Set Token Standard");
+            metadata::batch_set_token_standard(program_id, accounts, args)
+        }
+        MetadataInstruction::VerifyContent(args) => {
+            msg!("IX: Verify Content");
+            metadata::verify_content(program_id, accounts, args)
+        }
+        MetadataInstruction::SetUsesV2(args) => {
+            msg!("IX: Set Uses V2");
+            uses::set_uses_v2(program_id, accounts, args)
+        }
+        MetadataInstruction::RotateAuthorityWithPolicy(args) => {
+            msg!("IX: Rotate Authority With Policy");
+            delegate::rotate_authority_with_policy(program_id, accounts, args)
+        }
+        MetadataInstruction::BeginTransferSession(args) => {
+            msg!("IX: Begin Transfer Session");
+            metadata::begin_transfer_session(program_id, accounts, args)
+        }
+        MetadataInstruction::EndTransferSession(args) => {
+            msg!("IX: End Transfer Session");
+            metadata::end_transfer_session(program_id, accounts, args)
+        }
+        MetadataInstruction::GetProgramCapabilities => {
+            msg!("IX: Get Program Capabilities");
+            capabilities::get_program_capabilities()
+        }
+        MetadataInstruction::SetDefaultRuleSet(args) => {
+            msg!("IX: Set Default Rule Set");
+            metadata::set_default_rule_set(program_id, accounts, args)
+        }
+        MetadataInstruction::Print(args) => {
+            msg!("IX: Print");
+            edition::print(program_id, accounts, args)
+        }
+        MetadataInstruction::CloseAccounts(args) => {
+            msg!("IX: Close Accounts");
+            burn::close_accounts(program_id, accounts, args)
+        }
+        MetadataInstruction::Resize(args) => {
+            msg!("IX: Resize");
+            metadata::resize(program_id, accounts, args)
+        }
+        MetadataInstruction::SelfLock(args) => {
+            msg!("IX: Self Lock");
+            state::self_lock(program_id, accounts, args)
+        }
+        MetadataInstruction::SelfUnlock(args) => {
+            msg!("IX: Self Unlock");
+            state::self_unlock(program_id, accounts, args)
+        }
         _ => {
             // pNFT accounts can only be used by the "new" API; before forwarding
             // the transaction to the "legacy" processor we determine whether we are
@@ -150,6 +353,9 @@ pub fn process_instruction<'a>(
 }
 
 /// Matches "legacy" (pre-pNFT) instructions.
+///
+/// Instructions that have a replacement on the new API log their deprecation (see
+/// `log_deprecated`) before being processed.
 fn process_legacy_instruction<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -158,6 +364,7 @@ fn process_legacy_instruction<'a>(
     match instruction {
         MetadataInstruction::CreateMetadataAccount(args) => {
             msg!("(Deprecated as of 1.1.0) IX: Create Metadata Accounts");
+            log_deprecated("CreateMetadataAccount", "Create")?;
             process_deprecated_create_metadata_accounts(
                 program_id,
                 accounts,
@@ -167,6 +374,7 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::UpdateMetadataAccount(args) => {
             msg!("(Deprecated as of 1.1.0) IX: Update Metadata Accounts");
+            log_deprecated("UpdateMetadataAccount", "Update")?;
             process_deprecated_update_metadata_accounts(
                 program_id,
                 accounts,
@@ -177,10 +385,12 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::CreateMetadataAccountV2(args) => {
             msg!("IX: Create Metadata Accounts v2");
+            log_deprecated("CreateMetadataAccountV2", "Create")?;
             process_create_metadata_accounts_v2(program_id, accounts, args.data, args.is_mutable)
         }
         MetadataInstruction::CreateMetadataAccountV3(args) => {
             msg!("IX: Create Metadata Accounts v3");
+            log_deprecated("CreateMetadataAccountV3", "Create")?;
             process_create_metadata_accounts_v3(
                 program_id,
                 accounts,
@@ -191,6 +401,7 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::UpdateMetadataAccountV2(args) => {
             msg!("IX: Update Metadata Accounts v2");
+            log_deprecated("UpdateMetadataAccountV2", "Update")?;
             process_update_metadata_accounts_v2(
                 program_id,
                 accounts,
@@ -222,6 +433,7 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::SignMetadata => {
             msg!("IX: Sign Metadata");
+            log_deprecated("SignMetadata", "VerifyV2")?;
             process_sign_metadata(program_id, accounts)
         }
         MetadataInstruction::RemoveCreatorVerification => {
@@ -238,10 +450,12 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::CreateMasterEdition(args) => {
             msg!("(Deprecated as of 1.1.0, please use V3 Create Master Edition)\n V2 Create Master Edition");
+            log_deprecated("CreateMasterEdition", "Create")?;
             process_create_master_edition(program_id, accounts, args.max_supply)
         }
         MetadataInstruction::CreateMasterEditionV3(args) => {
             msg!("V3 Create Master Edition");
+            log_deprecated("CreateMasterEditionV3", "Create")?;
             process_create_master_edition(program_id, accounts, args.max_supply)
         }
         MetadataInstruction::MintNewEditionFromMasterEditionViaToken(args) => {
@@ -269,6 +483,7 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::VerifyCollection => {
             msg!("IX: Verify Collection");
+            log_deprecated("VerifyCollection", "VerifyV2")?;
             verify_collection(program_id, accounts)
         }
         MetadataInstruction::SetAndVerifyCollection => {
@@ -281,42 +496,52 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::Utilize(args) => {
             msg!("IX: Use/Utilize Token");
+            log_deprecated("Utilize", "UseV2")?;
             process_utilize(program_id, accounts, args.number_of_uses)
         }
         MetadataInstruction::ApproveUseAuthority(args) => {
             msg!("IX: Approve Use Authority");
+            log_deprecated("ApproveUseAuthority", "Delegate")?;
             process_approve_use_authority(program_id, accounts, args.number_of_uses)
         }
         MetadataInstruction::RevokeUseAuthority => {
             msg!("IX: Revoke Use Authority");
+            log_deprecated("RevokeUseAuthority", "Revoke")?;
             process_revoke_use_authority(program_id, accounts)
         }
         MetadataInstruction::ApproveCollectionAuthority => {
             msg!("IX: Approve Collection Authority");
+            log_deprecated("ApproveCollectionAuthority", "Delegate")?;
             process_approve_collection_authority(program_id, accounts)
         }
         MetadataInstruction::RevokeCollectionAuthority => {
             msg!("IX: Revoke Collection Authority");
+            log_deprecated("RevokeCollectionAuthority", "Revoke")?;
             process_revoke_collection_authority(program_id, accounts)
         }
         MetadataInstruction::FreezeDelegatedAccount => {
             msg!("IX: Freeze Delegated Account");
+            log_deprecated("FreezeDelegatedAccount", "Lock")?;
             process_freeze_delegated_account(program_id, accounts)
         }
         MetadataInstruction::ThawDelegatedAccount => {
             msg!("IX: Thaw Delegated Account");
+            log_deprecated("ThawDelegatedAccount", "Unlock")?;
             process_thaw_delegated_account(program_id, accounts)
         }
         MetadataInstruction::BurnNft => {
             msg!("IX: Burn NFT");
+            log_deprecated("BurnNft", "Burn")?;
             process_burn_nft(program_id, accounts)
         }
         MetadataInstruction::BurnEditionNft => {
             msg!("IX: Burn Edition NFT");
+            log_deprecated("BurnEditionNft", "Burn")?;
             process_burn_edition_nft(program_id, accounts)
         }
         MetadataInstruction::VerifySizedCollectionItem => {
             msg!("IX: Verify Collection V2");
+            log_deprecated("VerifySizedCollectionItem", "VerifyV2")?;
             verify_sized_collection_item(program_id, accounts)
         }
         MetadataInstruction::SetAndVerifySizedCollectionItem => {
@@ -349,6 +574,7 @@ fn process_legacy_instruction<'a>(
         }
         MetadataInstruction::TransferOutOfEscrow(args) => {
             msg!("IX: Transfer Out Of Escrow");
+            log_deprecated("TransferOutOfEscrow", "EscrowTransferOut")?;
             process_transfer_out_of_escrow(program_id, accounts, args)
         }
         _ => Err(ProgramError::InvalidInstructionData),
@@ -361,10 +587,17 @@ fn process_legacy_instruction<'a>(
 /// This relies on the client setting the `crate::id()` as the pubkey for
 /// accounts that are not set, which effectively allows us to use positional
 /// optional accounts.
+///
+/// Optional accounts at the end of the account list can also be omitted, so
+/// optional accounts appended to an instruction do not break clients sending
+/// the previous (shorter) list of accounts.
 pub fn next_optional_account_info<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
     iter: &mut I,
 ) -> Result<Option<I::Item>, ProgramError> {
-    let account_info = iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let account_info = match iter.next() {
+        Some(account_info) => account_info,
+        None => return Ok(None),
+    };
 
     Ok(if cmp_pubkeys(account_info.key, &crate::id()) {
         None
@@ -413,10 +646,11 @@ pub fn try_get_optional_account_info<'a>(
     }
 }
 
-/// Checks if the instruction's accounts contain a pNFT metadata.
+/// Checks if the instruction's accounts contain a pNFT (or non-transferable) metadata.
 ///
 /// We need to determine if we are dealing with a pNFT metadata or not
-/// so we can restrict the available instructions.
+/// so we can restrict the available instructions. Non-transferable assets
+/// are kept frozen like pNFTs, so they are also restricted to the new API.
 fn has_programmable_metadata<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo],
@@ -424,14 +658,14 @@ fn has_programmable_metadata<'a>(
     for account_info in accounts {
         // checks the account is owned by Token Metadata and it has data
         if account_info.owner == program_id && !account_info.data_is_empty() {
-            let discriminator = account_info.data.borrow()[DISCRIMINATOR_INDEX];
             // checks if the account is a Metadata account
-            if discriminator == Key::MetadataV1 as u8 {
+            if Key::of(&account_info.data.borrow()) == Some(Key::MetadataV1) {
                 let metadata = Metadata::from_account_info(account_info)?;
 
                 if matches!(
                     metadata.token_standard,
                     Some(TokenStandard::ProgrammableNonFungible)
+                        | Some(TokenStandard::NonTransferable)
                 ) {
                     return Ok(true);
                 }
@@ -0,0 +1,217 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::state::Account;
+
+use super::{holder_token_record, HolderLockAccounts};
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, metadata::assert_metadata_derivation,
+        programmable::assert_metadata_delegate_not_expired,
+    },
+    error::MetadataError,
+    instruction::{
+        FreezeCollectionItem, FreezeCollectionItemArgs, MetadataDelegateRole,
+        SetCollectionFreezeOptIn, SetCollectionFreezeOptInArgs, ThawCollectionItem,
+        ThawCollectionItemArgs,
+    },
+    pda::find_token_record_account,
+    state::{
+        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, Resizable,
+        TokenMetadataAccount, TokenRecord, TokenStandard,
+    },
+};
+
+/// Accounts shared by the `FreezeCollectionItem` and `ThawCollectionItem` instructions.
+struct CollectionFreezeAccounts<'a> {
+    authority_info: &'a AccountInfo<'a>,
+    delegate_record_info: &'a AccountInfo<'a>,
+    collection_mint_info: &'a AccountInfo<'a>,
+    collection_metadata_info: &'a AccountInfo<'a>,
+    metadata_info: &'a AccountInfo<'a>,
+    mint_info: &'a AccountInfo<'a>,
+    token_info: &'a AccountInfo<'a>,
+    token_record_info: &'a AccountInfo<'a>,
+}
+
+/// Sets whether the holder allows the `CollectionFreeze` delegate of the collection to
+/// freeze the token.
+pub fn set_collection_freeze_opt_in<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetCollectionFreezeOptInArgs,
+) -> ProgramResult {
+    let context = SetCollectionFreezeOptIn::to_context(accounts)?;
+
+    match args {
+        SetCollectionFreezeOptInArgs::V1 { opt_in } => {
+            let accounts = HolderLockAccounts {
+                authority_info: context.accounts.authority_info,
+                token_info: context.accounts.token_info,
+                mint_info: context.accounts.mint_info,
+                token_record_info: context.accounts.token_record_info,
+                payer_info: context.accounts.payer_info,
+                system_program_info: context.accounts.system_program_info,
+            };
+            let mut token_record = holder_token_record(program_id, &accounts)?;
+
+            // the holder cannot opt out to escape a freeze
+            if !opt_in && token_record.collection_frozen {
+                return Err(MetadataError::CollectionFrozen.into());
+            }
+
+            token_record.collection_freeze_opt_in = opt_in;
+
+            token_record.save(
+                accounts.token_record_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )
+        }
+    }
+}
+
+pub fn freeze_collection_item<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: FreezeCollectionItemArgs,
+) -> ProgramResult {
+    let context = FreezeCollectionItem::to_context(accounts)?;
+
+    match args {
+        FreezeCollectionItemArgs::V1 => {
+            let accounts = CollectionFreezeAccounts {
+                authority_info: context.accounts.authority_info,
+                delegate_record_info: context.accounts.delegate_record_info,
+                collection_mint_info: context.accounts.collection_mint_info,
+                collection_metadata_info: context.accounts.collection_metadata_info,
+                metadata_info: context.accounts.metadata_info,
+                mint_info: context.accounts.mint_info,
+                token_info: context.accounts.token_info,
+                token_record_info: context.accounts.token_record_info,
+            };
+
+            toggle_collection_freeze(program_id, &accounts, true)
+        }
+    }
+}
+
+pub fn thaw_collection_item<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ThawCollectionItemArgs,
+) -> ProgramResult {
+    let context = ThawCollectionItem::to_context(accounts)?;
+
+    match args {
+        ThawCollectionItemArgs::V1 => {
+            let accounts = CollectionFreezeAccounts {
+                authority_info: context.accounts.authority_info,
+                delegate_record_info: context.accounts.delegate_record_info,
+                collection_mint_info: context.accounts.collection_mint_info,
+                collection_metadata_info: context.accounts.collection_metadata_info,
+                metadata_info: context.accounts.metadata_info,
+                mint_info: context.accounts.mint_info,
+                token_info: context.accounts.token_info,
+                token_record_info: context.accounts.token_record_info,
+            };
+
+            toggle_collection_freeze(program_id, &accounts, false)
+        }
+    }
+}
+
+/// Freezes or thaws an item of a collection on behalf of the `CollectionFreeze` delegate
+/// of the collection.
+fn toggle_collection_freeze(
+    program_id: &Pubkey,
+    accounts: &CollectionFreezeAccounts,
+    freeze: bool,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(accounts.authority_info)?;
+
+    // ownership
+
+    assert_owned_by(accounts.delegate_record_info, program_id)?;
+    assert_owned_by(accounts.collection_metadata_info, program_id)?;
+    assert_owned_by(accounts.metadata_info, program_id)?;
+    assert_owned_by(accounts.token_info, &spl_token::ID)?;
+    assert_owned_by(accounts.token_record_info, program_id)?;
+
+    // key match
+
+    assert_metadata_derivation(
+        program_id,
+        accounts.collection_metadata_info,
+        accounts.collection_mint_info,
+    )?;
+    assert_metadata_derivation(program_id, accounts.metadata_info, accounts.mint_info)?;
+
+    let (pda_key, _) = find_token_record_account(accounts.mint_info.key, accounts.token_info.key);
+    assert_keys_equal(&pda_key, accounts.token_record_info.key)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(accounts.metadata_info)?;
+
+    // only programmable assets have a token record to store the opt-in
+    if !matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    // the item must be a verified member of the collection
+    match &metadata.collection {
+        Some(collection)
+            if collection.verified
+                && cmp_pubkeys(&collection.key, accounts.collection_mint_info.key) => {}
+        _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+    }
+
+    let token = Account::unpack(&accounts.token_info.try_borrow_data()?)?;
+
+    if !cmp_pubkeys(&token.mint, accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let collection_metadata = Metadata::from_account_info(accounts.collection_metadata_info)?;
+
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            precedence: &[AuthorityType::MetadataDelegate],
+            authority: accounts.authority_info.key,
+            update_authority: &collection_metadata.update_authority,
+            mint: accounts.collection_mint_info.key,
+            metadata_delegate_record_info: Some(accounts.delegate_record_info),
+            metadata_delegate_roles: vec![MetadataDelegateRole::CollectionFreeze],
+            ..Default::default()
+        })?;
+
+    if !matches!(authority_type, AuthorityType::MetadataDelegate) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+    // expired delegates can no longer freeze or thaw items
+    assert_metadata_delegate_not_expired(accounts.delegate_record_info)?;
+
+    let mut token_record = TokenRecord::from_account_info(accounts.token_record_info)?;
+
+    if !token_record.collection_freeze_opt_in {
+        return Err(MetadataError::CollectionFreezeNotOptedIn.into());
+    }
+
+    if token_record.collection_frozen == freeze {
+        return Err(MetadataError::IncorrectTokenState.into());
+    }
+
+    token_record.collection_frozen = freeze;
+
+    token_record
+        .serialize(&mut *accounts.token_record_info.try_borrow_mut_data()?)
+        .map_err(|_| MetadataError::BorshSerializationError.into())
+}
@@ -0,0 +1,144 @@
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_program,
+    sysvar::Sysvar,
+};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{SelfLock, SelfLockArgs, SelfUnlock, SelfUnlockArgs},
+    pda::find_token_record_account,
+    state::{Resizable, TokenMetadataAccount, TokenRecord},
+};
+
+/// Accounts shared by the instructions signed by the holder (`SelfLock`, `SelfUnlock` and
+/// `SetCollectionFreezeOptIn`).
+pub(super) struct HolderLockAccounts<'a> {
+    pub authority_info: &'a AccountInfo<'a>,
+    pub token_info: &'a AccountInfo<'a>,
+    pub mint_info: &'a AccountInfo<'a>,
+    pub token_record_info: &'a AccountInfo<'a>,
+    pub payer_info: &'a AccountInfo<'a>,
+    pub system_program_info: &'a AccountInfo<'a>,
+}
+
+pub fn self_lock<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SelfLockArgs,
+) -> ProgramResult {
+    let context = SelfLock::to_context(accounts)?;
+
+    match args {
+        SelfLockArgs::V1 { cooldown } => {
+            let accounts = HolderLockAccounts {
+                authority_info: context.accounts.authority_info,
+                token_info: context.accounts.token_info,
+                mint_info: context.accounts.mint_info,
+                token_record_info: context.accounts.token_record_info,
+                payer_info: context.accounts.payer_info,
+                system_program_info: context.accounts.system_program_info,
+            };
+            let mut token_record = holder_token_record(program_id, &accounts)?;
+
+            if token_record.holder_locked {
+                return Err(MetadataError::HolderLocked.into());
+            }
+
+            let cooldown =
+                i64::try_from(cooldown).map_err(|_| MetadataError::NumericalOverflowError)?;
+
+            token_record.holder_locked = true;
+            token_record.holder_unlock_timestamp = Clock::get()?
+                .unix_timestamp
+                .checked_add(cooldown)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+
+            token_record.save(
+                accounts.token_record_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )
+        }
+    }
+}
+
+pub fn self_unlock<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SelfUnlockArgs,
+) -> ProgramResult {
+    let context = SelfUnlock::to_context(accounts)?;
+
+    match args {
+        SelfUnlockArgs::V1 => {
+            let accounts = HolderLockAccounts {
+                authority_info: context.accounts.authority_info,
+                token_info: context.accounts.token_info,
+                mint_info: context.accounts.mint_info,
+                token_record_info: context.accounts.token_record_info,
+                payer_info: context.accounts.payer_info,
+                system_program_info: context.accounts.system_program_info,
+            };
+            let mut token_record = holder_token_record(program_id, &accounts)?;
+
+            if !token_record.holder_locked {
+                return Err(MetadataError::IncorrectTokenState.into());
+            }
+
+            if !token_record.is_holder_unlockable(Clock::get()?.unix_timestamp) {
+                return Err(MetadataError::HolderLockCooldown.into());
+            }
+
+            token_record.holder_locked = false;
+            token_record.holder_unlock_timestamp = 0;
+
+            token_record.save(
+                accounts.token_record_info,
+                accounts.payer_info,
+                accounts.system_program_info,
+            )
+        }
+    }
+}
+
+/// Validates the accounts and returns the token record of the holder.
+pub(super) fn holder_token_record(
+    program_id: &Pubkey,
+    accounts: &HolderLockAccounts,
+) -> Result<TokenRecord, ProgramError> {
+    // signers
+
+    assert_signer(accounts.authority_info)?;
+    assert_signer(accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(accounts.token_info, &spl_token::ID)?;
+    assert_owned_by(accounts.token_record_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(accounts.system_program_info.key, &system_program::ID)?;
+
+    // account relationships
+
+    let token = Account::unpack(&accounts.token_info.try_borrow_data()?)?;
+
+    if !cmp_pubkeys(&token.mint, accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // only the holder can lock or unlock
+    if !cmp_pubkeys(&token.owner, accounts.authority_info.key) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    let (pda_key, _) = find_token_record_account(accounts.mint_info.key, accounts.token_info.key);
+    assert_keys_equal(&pda_key, accounts.token_record_info.key)?;
+
+    TokenRecord::from_account_info(accounts.token_record_info)
+}
@@ -27,9 +27,16 @@ pub fn lock<'a>(
                 system_program_info: context.accounts.system_program_info,
                 sysvar_instructions_info: context.accounts.sysvar_instructions_info,
                 spl_token_program_info: context.accounts.spl_token_program_info,
+                collection_delegate_record_info: context.accounts.collection_delegate_record_info,
+                authorization_rules_program_info: context.accounts.authorization_rules_program_info,
+                authorization_rules_info: context.accounts.authorization_rules_info,
+                // assets using the legacy layout pass the programmable config extension
+                // as the first remaining account
+                programmable_config_extension_info: context.remaining_accounts.first().copied(),
             },
             TokenState::Unlocked,
             TokenState::Locked,
+            None,
         ),
     }
 }
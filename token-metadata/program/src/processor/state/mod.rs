@@ -1,7 +1,11 @@
+mod collection_freeze;
+mod holder_lock;
 mod lock;
 mod unlock;
 
 use borsh::BorshSerialize;
+pub use collection_freeze::*;
+pub use holder_lock::*;
 pub use lock::*;
 use mpl_utils::assert_signer;
 use solana_program::{
@@ -15,16 +19,21 @@ use spl_token::{
 pub use unlock::*;
 
 use crate::{
-    assertions::{assert_keys_equal, metadata::assert_state},
+    assertions::{
+        assert_keys_equal, metadata::assert_state, programmable::assert_staking_program_unlock,
+    },
     error::MetadataError,
-    pda::find_token_record_account,
+    pda::{find_collection_items_delegate_record_account, find_token_record_account},
+    processor::AuthorizationData,
     state::{
-        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, TokenDelegateRole,
-        TokenMetadataAccount, TokenRecord, TokenStandard, TokenState,
+        AuthorityRequest, AuthorityResponse, AuthorityType, CollectionItemsDelegateRecord,
+        Metadata, Operation, ProgrammableConfig, TokenDelegateRole, TokenMetadataAccount,
+        TokenRecord, TokenStandard, TokenState,
     },
     utils::{
         assert_delegated_tokens, assert_freeze_authority_matches_mint, assert_initialized,
-        assert_owned_by, freeze, thaw,
+        assert_owned_by, auth_rules_validate, freeze, is_auth_rules_program, thaw,
+        AuthRulesValidateParams,
     },
 };
 
@@ -39,6 +48,10 @@ pub(crate) struct ToggleAccounts<'a> {
     system_program_info: &'a AccountInfo<'a>,
     sysvar_instructions_info: &'a AccountInfo<'a>,
     spl_token_program_info: Option<&'a AccountInfo<'a>>,
+    collection_delegate_record_info: Option<&'a AccountInfo<'a>>,
+    authorization_rules_program_info: Option<&'a AccountInfo<'a>>,
+    authorization_rules_info: Option<&'a AccountInfo<'a>>,
+    programmable_config_extension_info: Option<&'a AccountInfo<'a>>,
 }
 
 pub(crate) fn toggle_asset_state(
@@ -46,6 +59,7 @@ pub(crate) fn toggle_asset_state(
     accounts: ToggleAccounts,
     from: TokenState,
     to: TokenState,
+    authorization_data: Option<AuthorizationData>,
 ) -> ProgramResult {
     // signers
 
@@ -67,11 +81,12 @@ pub(crate) fn toggle_asset_state(
 
     // account relationships
 
-    let metadata = Metadata::from_account_info(accounts.metadata_info)?;
+    let mut metadata = Metadata::from_account_info(accounts.metadata_info)?;
     // mint must match mint account key
     if metadata.mint != *accounts.mint_info.key {
         return Err(MetadataError::MintMismatch.into());
     }
+    metadata.load_programmable_config(accounts.programmable_config_extension_info)?;
 
     let token = Account::unpack(&accounts.token_info.try_borrow_data()?)?;
     // mint must match mint account key
@@ -79,6 +94,19 @@ pub(crate) fn toggle_asset_state(
         return Err(MetadataError::MintMismatch.into());
     }
 
+    // collection items delegates are validated against their record
+    if let Some(collection_delegate_record_info) = accounts.collection_delegate_record_info {
+        return toggle_collection_item_state(
+            program_id,
+            &accounts,
+            collection_delegate_record_info,
+            &metadata,
+            &token,
+            from,
+            to,
+        );
+    }
+
     // authority – this can be either:
     //  1. token delegate (programmable non-fungible): valid token_record.delegate
     //  2. spl-delegate (non-fungibles): authority == token.delegate
@@ -131,6 +159,25 @@ pub(crate) fn toggle_asset_state(
 
         // make sure we are on the expected state
         assert_state(&token_record, from)?;
+        // staked assets can only be unlocked by their staking program
+        if matches!(to, TokenState::Unlocked) {
+            assert_staking_program_unlock(&token_record, accounts.sysvar_instructions_info)?;
+        }
+        // unlocks from utility delegates are validated against the rule set, which
+        // can require additional accounts or payload fields
+        if matches!(to, TokenState::Unlocked)
+            && matches!(token_record.delegate_role, Some(TokenDelegateRole::Utility))
+        {
+            validate_unlock(
+                accounts.mint_info,
+                accounts.authority_info,
+                accounts.authorization_rules_info,
+                accounts.authorization_rules_program_info,
+                &metadata,
+                &token_record,
+                authorization_data,
+            )?;
+        }
         // for pNFTs, we only need to flip the programmable state
         token_record.state = to;
 
@@ -235,3 +282,182 @@ pub(crate) fn toggle_asset_state(
         }
     }
 }
+
+/// Validates the unlock of a programmable asset by a utility delegate against its
+/// rule set (if any).
+pub(crate) fn validate_unlock<'a>(
+    mint_info: &'a AccountInfo<'a>,
+    authority_info: &'a AccountInfo<'a>,
+    authorization_rules_info: Option<&'a AccountInfo<'a>>,
+    authorization_rules_program_info: Option<&'a AccountInfo<'a>>,
+    metadata: &Metadata,
+    token_record: &TokenRecord,
+    authorization_data: Option<AuthorizationData>,
+) -> ProgramResult {
+    if let Some(ProgrammableConfig::V1 {
+        rule_set: Some(rule_set),
+    }) = metadata.programmable_config
+    {
+        // validates that we got the correct rule set
+        let authorization_rules_info =
+            authorization_rules_info.ok_or(MetadataError::MissingAuthorizationRules)?;
+        assert_keys_equal(authorization_rules_info.key, &rule_set)?;
+        if !is_auth_rules_program(authorization_rules_info.owner) {
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        // validates auth rules program
+        let authorization_rules_program_info = authorization_rules_program_info
+            .ok_or(MetadataError::MissingAuthorizationRulesProgram)?;
+        if !is_auth_rules_program(authorization_rules_program_info.key) {
+            return Err(MetadataError::KeyMismatch.into());
+        }
+
+        auth_rules_validate(AuthRulesValidateParams {
+            mint_info,
+            owner_info: None,
+            authority_info: Some(authority_info),
+            source_info: None,
+            destination_info: None,
+            programmable_config: metadata.programmable_config.clone(),
+            amount: 1,
+            auth_data: authorization_data,
+            auth_rules_info: Some(authorization_rules_info),
+            operation: Operation::Unlock {
+                scenario: UnlockScenario::UtilityDelegate,
+            },
+            is_wallet_to_wallet: false,
+            rule_set_revision: token_record
+                .rule_set_revision
+                .map(|revision| revision as usize),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Locks or unlocks an item of a collection on behalf of a collection items delegate.
+///
+/// The item must not have a token delegate, since the token delegate would otherwise be
+/// able to unlock (or revoke) an item locked by the collection items delegate.
+fn toggle_collection_item_state<'a>(
+    program_id: &Pubkey,
+    accounts: &ToggleAccounts<'a>,
+    collection_delegate_record_info: &'a AccountInfo<'a>,
+    metadata: &Metadata,
+    token: &Account,
+    from: TokenState,
+    to: TokenState,
+) -> ProgramResult {
+    assert_owned_by(collection_delegate_record_info, program_id)?;
+    let mut delegate_record =
+        CollectionItemsDelegateRecord::from_account_info(collection_delegate_record_info)?;
+
+    // the item must be a verified member of the collection
+    match &metadata.collection {
+        Some(collection)
+            if collection.verified && collection.key == delegate_record.collection_mint => {}
+        _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+    }
+
+    // the delegate must have been approved by the token owner
+    if token.owner != delegate_record.holder
+        || *accounts.authority_info.key != delegate_record.delegate
+    {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    let (pda_key, _) = find_collection_items_delegate_record_account(
+        &delegate_record.collection_mint,
+        &delegate_record.holder,
+        &delegate_record.delegate,
+    );
+    assert_keys_equal(&pda_key, collection_delegate_record_info.key)?;
+
+    let locking = matches!(to, TokenState::Locked);
+
+    if matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        let token_record_info = match accounts.token_record_info {
+            Some(token_record_info) => token_record_info,
+            None => return Err(MetadataError::MissingTokenRecord.into()),
+        };
+
+        let (pda_key, _) =
+            find_token_record_account(accounts.mint_info.key, accounts.token_info.key);
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+        assert_owned_by(token_record_info, &crate::ID)?;
+
+        let mut token_record = TokenRecord::from_account_info(token_record_info)?;
+
+        if token_record.delegate.is_some() {
+            return Err(MetadataError::InvalidAuthorityType.into());
+        }
+
+        assert_state(&token_record, from)?;
+        token_record.state = to;
+        token_record
+            .serialize(&mut *token_record_info.try_borrow_mut_data()?)
+            .map_err(|_| MetadataError::BorshSerializationError)?;
+    } else {
+        let spl_token_program_info = match accounts.spl_token_program_info {
+            Some(spl_token_program_info) => {
+                assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+                spl_token_program_info
+            }
+            None => {
+                return Err(MetadataError::MissingSplTokenProgram.into());
+            }
+        };
+        // the (master) edition is the freeze authority of the item
+        let edition_info = match accounts.edition_info {
+            Some(edition_info) => edition_info,
+            None => return Err(MetadataError::MissingEditionAccount.into()),
+        };
+
+        if token.delegate.is_some() {
+            return Err(MetadataError::InvalidAuthorityType.into());
+        }
+
+        match to {
+            TokenState::Locked => {
+                if token.is_frozen() {
+                    return Err(MetadataError::IncorrectTokenState.into());
+                }
+                freeze(
+                    accounts.mint_info.clone(),
+                    accounts.token_info.clone(),
+                    edition_info.clone(),
+                    spl_token_program_info.clone(),
+                )?;
+            }
+            TokenState::Unlocked => {
+                if !token.is_frozen() {
+                    return Err(MetadataError::IncorrectTokenState.into());
+                }
+                thaw(
+                    accounts.mint_info.clone(),
+                    accounts.token_info.clone(),
+                    edition_info.clone(),
+                    spl_token_program_info.clone(),
+                )?;
+            }
+            TokenState::Listed => return Err(MetadataError::IncorrectTokenState.into()),
+        }
+    }
+
+    // keeps track of the locked items so the delegate cannot be revoked while
+    // items are locked
+    delegate_record.locked_items = if locking {
+        delegate_record.locked_items.checked_add(1)
+    } else {
+        delegate_record.locked_items.checked_sub(1)
+    }
+    .ok_or(MetadataError::NumericalOverflowError)?;
+
+    delegate_record
+        .serialize(&mut *collection_delegate_record_info.try_borrow_mut_data()?)
+        .map_err(|_| MetadataError::BorshSerializationError.into())
+}
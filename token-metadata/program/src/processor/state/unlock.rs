@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 use super::toggle_asset_state;
@@ -6,6 +8,19 @@ use crate::{
     state::TokenState,
 };
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnlockScenario {
+    UtilityDelegate,
+}
+
+impl Display for UnlockScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UtilityDelegate => write!(f, "UtilityDelegate"),
+        }
+    }
+}
+
 pub fn unlock<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -14,7 +29,7 @@ pub fn unlock<'a>(
     let context = Unlock::to_context(accounts)?;
 
     match args {
-        UnlockArgs::V1 { .. } => toggle_asset_state(
+        UnlockArgs::V1 { authorization_data } => toggle_asset_state(
             program_id,
             super::ToggleAccounts {
                 payer_info: context.accounts.payer_info,
@@ -27,9 +42,16 @@ pub fn unlock<'a>(
                 system_program_info: context.accounts.system_program_info,
                 sysvar_instructions_info: context.accounts.sysvar_instructions_info,
                 spl_token_program_info: context.accounts.spl_token_program_info,
+                collection_delegate_record_info: context.accounts.collection_delegate_record_info,
+                authorization_rules_program_info: context.accounts.authorization_rules_program_info,
+                authorization_rules_info: context.accounts.authorization_rules_info,
+                // assets using the legacy layout pass the programmable config extension
+                // as the first remaining account
+                programmable_config_extension_info: context.remaining_accounts.first().copied(),
             },
             TokenState::Locked,
             TokenState::Unlocked,
+            authorization_data,
         ),
     }
 }
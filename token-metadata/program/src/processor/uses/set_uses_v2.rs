@@ -0,0 +1,135 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::{assert_metadata_derivation, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{Context, SetUsesV2, SetUsesV2Args},
+    pda::{PREFIX, USES_V2},
+    state::{Metadata, TokenMetadataAccount, UseMethod, UsesV2},
+    utils::clean_write_metadata,
+};
+
+/// Sets the rate limit of the uses of an asset.
+pub fn set_uses_v2<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetUsesV2Args,
+) -> ProgramResult {
+    let context = SetUsesV2::to_context(accounts)?;
+
+    match args {
+        SetUsesV2Args::V1 {
+            cooldown_slots,
+            refill_period_slots,
+            refill_amount,
+        } => set_uses_v2_v1(
+            program_id,
+            context,
+            cooldown_slots,
+            refill_period_slots,
+            refill_amount,
+        ),
+    }
+}
+
+fn set_uses_v2_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetUsesV2>,
+    cooldown_slots: u64,
+    refill_period_slots: u64,
+    refill_amount: u64,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_metadata_derivation(
+        program_id,
+        ctx.accounts.metadata_info,
+        ctx.accounts.mint_info,
+    )?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    let uses = metadata.uses.as_ref().ok_or(MetadataError::Unusable)?;
+
+    // an asset with the burn use method is burned once it runs out of uses, so there
+    // is nothing left to refill
+    if refill_period_slots > 0 && refill_amount > 0 && uses.use_method == UseMethod::Burn {
+        return Err(MetadataError::InvalidUseMethod.into());
+    }
+
+    let mut signer_seeds = vec![
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        USES_V2.as_bytes(),
+    ];
+    let bump = &[assert_derivation(
+        program_id,
+        ctx.accounts.uses_v2_info,
+        &signer_seeds,
+    )?];
+
+    let mut uses_v2 = if ctx.accounts.uses_v2_info.data_is_empty() {
+        signer_seeds.push(bump);
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.uses_v2_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            UsesV2::size(),
+            &signer_seeds,
+        )?;
+
+        UsesV2 {
+            bump: bump[0],
+            mint: *ctx.accounts.mint_info.key,
+            ..Default::default()
+        }
+    } else {
+        assert_owned_by(ctx.accounts.uses_v2_info, program_id)?;
+        UsesV2::from_account_info(ctx.accounts.uses_v2_info)?
+    };
+
+    uses_v2.cooldown_slots = cooldown_slots;
+    uses_v2.refill_period_slots = refill_period_slots;
+    uses_v2.refill_amount = refill_amount;
+    uses_v2.last_refill_slot = Clock::get()?.slot;
+    uses_v2.serialize(&mut *ctx.accounts.uses_v2_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Uses of {} rate limited (cooldown: {}, refill: {} every {} slots)",
+        ctx.accounts.mint_info.key,
+        cooldown_slots,
+        refill_amount,
+        refill_period_slots
+    );
+
+    if !metadata.rate_limited_uses {
+        metadata.rate_limited_uses = true;
+        clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+    }
+
+    Ok(())
+}
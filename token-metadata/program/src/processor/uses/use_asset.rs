@@ -0,0 +1,267 @@
+use borsh::BorshSerialize;
+use mpl_utils::{
+    assert_signer, cmp_pubkeys,
+    token::{spl_token_burn, TokenBurnParams},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, program_pack::Pack,
+    pubkey::Pubkey, system_program, sysvar, sysvar::Sysvar,
+};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by,
+        programmable::{
+            assert_metadata_delegate_not_expired, assert_not_rented,
+            assert_token_delegate_not_expired,
+        },
+    },
+    error::MetadataError,
+    instruction::{Context, MetadataDelegateRole, UseArgs, UseV2},
+    pda::{find_token_record_account, find_uses_v2_account},
+    state::{
+        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, MetadataDelegateRecord,
+        TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard, UseMethod, UsesV2,
+    },
+    utils::{clean_write_metadata, freeze, thaw},
+};
+
+/// Uses an asset, decrementing its remaining uses.
+///
+/// Assets with the `Burn` use method are burned when they run out of uses. When the
+/// authority is a `Use` delegate, the remaining uses of the delegate are also decremented.
+/// The rate limit of assets with rate limited uses is applied from their `UsesV2` account.
+pub fn use_asset<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UseArgs,
+) -> ProgramResult {
+    let context = UseV2::to_context(accounts)?;
+
+    match args {
+        UseArgs::V1 { .. } => use_v1(program_id, context),
+    }
+}
+
+fn use_v1(program_id: &Pubkey, ctx: Context<UseV2>) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+
+    // account relationships
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if !cmp_pubkeys(&metadata.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let token = Account::unpack(&ctx.accounts.token_info.try_borrow_data()?)?;
+
+    if !cmp_pubkeys(&token.mint, ctx.accounts.mint_info.key) {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    let is_programmable = matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    );
+
+    // the token record is required for programmable assets, since it holds the
+    // token delegate
+    let token_record_info = if is_programmable {
+        let token_record_info = ctx
+            .accounts
+            .token_record_info
+            .ok_or(MetadataError::MissingTokenRecord)?;
+
+        let (pda_key, _) =
+            find_token_record_account(ctx.accounts.mint_info.key, ctx.accounts.token_info.key);
+        assert_keys_equal(&pda_key, token_record_info.key)?;
+        assert_owned_by(token_record_info, program_id)?;
+
+        Some(token_record_info)
+    } else {
+        None
+    };
+
+    // authority – this can be either:
+    //  1. token owner
+    //  2. utility or rental delegate (programmable non-fungibles)
+    //  3. use delegate (with its own allowance of uses)
+
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            precedence: &[
+                AuthorityType::TokenDelegate,
+                AuthorityType::Holder,
+                AuthorityType::MetadataDelegate,
+            ],
+            authority: ctx.accounts.authority_info.key,
+            update_authority: &metadata.update_authority,
+            mint: ctx.accounts.mint_info.key,
+            token: Some(ctx.accounts.token_info.key),
+            token_account: Some(&token),
+            token_record_info,
+            token_delegate_roles: vec![TokenDelegateRole::Utility, TokenDelegateRole::Rental],
+            metadata_delegate_record_info: ctx.accounts.delegate_record_info,
+            metadata_delegate_roles: vec![MetadataDelegateRole::Use],
+            ..Default::default()
+        })?;
+
+    match authority_type {
+        AuthorityType::Holder => (),
+        AuthorityType::TokenDelegate => {
+            // the token record is present, otherwise the authority would not be
+            // a token delegate
+            if let Some(token_record_info) = token_record_info {
+                assert_token_delegate_not_expired(&TokenRecord::from_account_info(
+                    token_record_info,
+                )?)?;
+            }
+        }
+        AuthorityType::MetadataDelegate => {
+            // the delegate record is present, otherwise the authority would not be
+            // a metadata delegate
+            if let Some(delegate_record_info) = ctx.accounts.delegate_record_info {
+                assert_metadata_delegate_not_expired(delegate_record_info)?;
+
+                let mut delegate_record =
+                    MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+                delegate_record.consume_use()?;
+                delegate_record.serialize(&mut *delegate_record_info.try_borrow_mut_data()?)?;
+            }
+        }
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    }
+
+    // decrements the remaining uses
+
+    let mut uses = metadata.uses.clone().ok_or(MetadataError::Unusable)?;
+
+    // the refill is applied before the use, so an asset can be used as soon as a
+    // refill period elapses
+    if metadata.rate_limited_uses {
+        let uses_v2_info = ctx
+            .accounts
+            .uses_v2_info
+            .ok_or(MetadataError::MissingUsesV2Account)?;
+
+        let (pda_key, _) = find_uses_v2_account(ctx.accounts.mint_info.key);
+        assert_keys_equal(&pda_key, uses_v2_info.key)?;
+        assert_owned_by(uses_v2_info, program_id)?;
+
+        let slot = Clock::get()?.slot;
+        let mut uses_v2 = UsesV2::from_account_info(uses_v2_info)?;
+        uses_v2.refill(&mut uses, slot);
+        uses_v2.record_use(slot)?;
+        uses_v2.serialize(&mut *uses_v2_info.try_borrow_mut_data()?)?;
+    }
+
+    uses.remaining = uses
+        .remaining
+        .checked_sub(1)
+        .ok_or(MetadataError::NotEnoughUses)?;
+
+    let must_burn = uses.remaining == 0 && matches!(uses.use_method, UseMethod::Burn);
+
+    // use delegates cannot burn the token, so the last use of a burnable asset is
+    // reserved to the token owner and the utility delegate
+    if must_burn && matches!(authority_type, AuthorityType::MetadataDelegate) {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    metadata.uses = Some(uses);
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    if must_burn {
+        burn_used_asset(&ctx, token_record_info)?;
+    }
+
+    Ok(())
+}
+
+/// Burns the token of an asset that ran out of uses.
+///
+/// Programmable assets are thawed for the burn and the (empty) token account is frozen
+/// again, so it can only be operated through the token metadata program.
+fn burn_used_asset<'a>(
+    ctx: &Context<UseV2<'a>>,
+    token_record_info: Option<&'a AccountInfo<'a>>,
+) -> ProgramResult {
+    let spl_token_program_info = ctx
+        .accounts
+        .spl_token_program_info
+        .ok_or(MetadataError::MissingSplTokenProgram)?;
+    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+
+    let edition_info = if let Some(token_record_info) = token_record_info {
+        // rented assets cannot be burned before the rental expires
+        assert_not_rented(&TokenRecord::from_account_info(token_record_info)?)?;
+
+        let edition_info = ctx
+            .accounts
+            .edition_info
+            .ok_or(MetadataError::MissingEditionAccount)?;
+
+        // this will validate the (master) edition derivation, which is the
+        // freeze authority
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            edition_info.clone(),
+            spl_token_program_info.clone(),
+        )?;
+
+        Some(edition_info)
+    } else {
+        None
+    };
+
+    spl_token_burn(TokenBurnParams {
+        mint: ctx.accounts.mint_info.clone(),
+        source: ctx.accounts.token_info.clone(),
+        authority: ctx.accounts.authority_info.clone(),
+        token_program: spl_token_program_info.clone(),
+        amount: 1,
+        authority_signer_seeds: None,
+    })?;
+
+    if let (Some(token_record_info), Some(edition_info)) = (token_record_info, edition_info) {
+        freeze(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            edition_info.clone(),
+            spl_token_program_info.clone(),
+        )?;
+
+        // the delegate has nothing left to act on
+        let mut token_record = TokenRecord::from_account_info(token_record_info)?;
+        token_record.reset();
+        token_record
+            .serialize(&mut *token_record_info.try_borrow_mut_data()?)
+            .map_err(|_| MetadataError::BorshSerializationError)?;
+    }
+
+    Ok(())
+}
@@ -54,6 +54,10 @@ pub fn process_utilize(
     if metadata.uses.is_none() {
         return Err(MetadataError::Unusable.into());
     }
+    // the rate limit is only enforced by the UseV2 instruction
+    if metadata.rate_limited_uses {
+        return Err(MetadataError::RateLimitedUses.into());
+    }
     if *token_program_account_info.key != spl_token::id() {
         return Err(MetadataError::InvalidTokenProgram.into());
     }
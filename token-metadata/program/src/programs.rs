@@ -0,0 +1,124 @@
+//! Program ids of the companion programs and sysvars used by Token Metadata.
+//!
+//! The builders and processors use these constants instead of the ids exported by each
+//! dependency, so integrations have a single place to look up the accounts required by an
+//! instruction. The ids of optional dependencies are declared here regardless of the enabled
+//! features.
+
+use solana_program::{ed25519_program, pubkey, pubkey::Pubkey, system_program, sysvar};
+
+/// System program.
+pub const SYSTEM_PROGRAM: Pubkey = system_program::ID;
+
+/// SPL Token program.
+pub const SPL_TOKEN_PROGRAM: Pubkey = spl_token::ID;
+
+/// SPL Token-2022 program.
+pub const SPL_TOKEN_2022_PROGRAM: Pubkey = spl_token_2022::ID;
+
+/// SPL Associated Token Account program.
+pub const SPL_ATA_PROGRAM: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Token Auth Rules program.
+pub const AUTH_RULES_PROGRAM: Pubkey = pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+/// Bubblegum (compressed NFTs) program.
+pub const BUBBLEGUM_PROGRAM: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// SPL Account Compression program.
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// Compute Budget program.
+pub const COMPUTE_BUDGET_PROGRAM: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Ed25519 signature verification program.
+pub const ED25519_PROGRAM: Pubkey = ed25519_program::ID;
+
+/// Instructions sysvar.
+pub const SYSVAR_INSTRUCTIONS: Pubkey = sysvar::instructions::ID;
+
+/// Rent sysvar.
+pub const SYSVAR_RENT: Pubkey = sysvar::rent::ID;
+
+// Programs and sysvars required by each instruction, in the order of their accounts.
+// Optional accounts (e.g., the Token Auth Rules program) are not included.
+
+/// Programs and sysvars required by `Create`.
+pub const CREATE: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS, SPL_TOKEN_PROGRAM];
+
+/// Programs and sysvars required by `Mint`.
+pub const MINT: &[Pubkey] = &[
+    SYSTEM_PROGRAM,
+    SYSVAR_INSTRUCTIONS,
+    SPL_TOKEN_PROGRAM,
+    SPL_ATA_PROGRAM,
+];
+
+/// Programs and sysvars required by `Transfer`.
+pub const TRANSFER: &[Pubkey] = &[
+    SYSTEM_PROGRAM,
+    SYSVAR_INSTRUCTIONS,
+    SPL_TOKEN_PROGRAM,
+    SPL_ATA_PROGRAM,
+];
+
+/// Programs and sysvars required by `Update`.
+pub const UPDATE: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS];
+
+/// Programs and sysvars required by `Delegate`, `Revoke`, `DelegateAndLock` and
+/// `UnlockAndRevoke`.
+pub const DELEGATE: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS];
+
+/// Programs and sysvars required by `Lock` and `Unlock`.
+pub const LOCK: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS];
+
+/// Programs and sysvars required by `Burn`.
+pub const BURN: &[Pubkey] = &[SPL_TOKEN_PROGRAM];
+
+/// Programs and sysvars required by `Migrate`.
+pub const MIGRATE: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS, SPL_TOKEN_PROGRAM];
+
+/// Programs and sysvars required by `Use`.
+pub const USE: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS];
+
+/// Programs and sysvars required by `VerifyV2` and `Unverify`.
+pub const VERIFY: &[Pubkey] = &[SYSTEM_PROGRAM, SYSVAR_INSTRUCTIONS];
+
+/// Programs and sysvars required by `Print`.
+pub const PRINT: &[Pubkey] = &[
+    SPL_TOKEN_PROGRAM,
+    SPL_ATA_PROGRAM,
+    SYSVAR_INSTRUCTIONS,
+    SYSTEM_PROGRAM,
+];
+
+/// Programs and sysvars required by `EscrowTransferOut` and `EscrowDeposit`.
+pub const ESCROW: &[Pubkey] = &[
+    SYSTEM_PROGRAM,
+    SYSVAR_INSTRUCTIONS,
+    SPL_TOKEN_PROGRAM,
+    SPL_ATA_PROGRAM,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "ata")]
+    fn spl_ata_program_matches_dependency() {
+        assert_eq!(SPL_ATA_PROGRAM, spl_associated_token_account::ID);
+    }
+
+    #[test]
+    fn compute_budget_program_matches_sdk() {
+        assert_eq!(COMPUTE_BUDGET_PROGRAM, solana_sdk::compute_budget::ID);
+    }
+
+    #[test]
+    #[cfg(feature = "auth-rules")]
+    fn auth_rules_program_matches_dependency() {
+        assert_eq!(AUTH_RULES_PROGRAM, mpl_token_auth_rules::ID);
+    }
+}
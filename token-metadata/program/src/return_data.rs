@@ -0,0 +1,110 @@
+//! Return data conventions of the program.
+//!
+//! Instructions that set return data (e.g., `GetProgramCapabilities`) use the same
+//! envelope: a Borsh-serialized [`ReturnDataHeader`] identifying the version of the
+//! envelope and the kind of value, followed by the Borsh-serialized value. Clients and
+//! calling programs decode it with [`decode_return_data`].
+
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::{entrypoint::ProgramResult, program, program_error::ProgramError};
+
+use crate::error::MetadataError;
+
+/// Current version of the return data envelope.
+pub const RETURN_DATA_VERSION: u8 = 1;
+
+/// Kind of value carried by the return data.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ReturnDataKind {
+    ProgramCapabilities,
+}
+
+/// Header of the return data envelope.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ReturnDataHeader {
+    /// Version of the envelope.
+    pub version: u8,
+    /// Kind of the value that follows the header.
+    pub kind: ReturnDataKind,
+}
+
+/// Value that can be set as the return data of an instruction.
+pub trait ReturnData: BorshSerialize + BorshDeserialize {
+    /// Kind of the value, stored on the header of the envelope.
+    const KIND: ReturnDataKind;
+}
+
+/// Serializes `value` in the return data envelope.
+pub fn encode_return_data<T: ReturnData>(value: &T) -> Result<Vec<u8>, ProgramError> {
+    let mut data = ReturnDataHeader {
+        version: RETURN_DATA_VERSION,
+        kind: T::KIND,
+    }
+    .try_to_vec()?;
+    value.serialize(&mut data)?;
+
+    Ok(data)
+}
+
+/// Sets `value` as the return data of the instruction.
+pub fn set_return_data<T: ReturnData>(value: &T) -> ProgramResult {
+    program::set_return_data(&encode_return_data(value)?);
+
+    Ok(())
+}
+
+/// Decodes a value from the return data envelope, checking the version of the envelope
+/// and the kind of the value.
+pub fn decode_return_data<T: ReturnData>(data: &[u8]) -> Result<T, ProgramError> {
+    let mut data = data;
+    let header =
+        ReturnDataHeader::deserialize(&mut data).map_err(|_| MetadataError::InvalidReturnData)?;
+
+    if header.version != RETURN_DATA_VERSION || header.kind != T::KIND {
+        return Err(MetadataError::InvalidReturnData.into());
+    }
+
+    T::deserialize(&mut data).map_err(|_| MetadataError::InvalidReturnData.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::ProgramCapabilities;
+
+    #[test]
+    fn round_trip() {
+        let capabilities = ProgramCapabilities::supported();
+        let data = encode_return_data(&capabilities).unwrap();
+
+        assert_eq!(data[0], RETURN_DATA_VERSION);
+        assert_eq!(
+            decode_return_data::<ProgramCapabilities>(&data).unwrap(),
+            capabilities
+        );
+    }
+
+    #[test]
+    fn invalid_envelope() {
+        let capabilities = ProgramCapabilities::supported();
+
+        // data without the envelope
+        let data = capabilities.try_to_vec().unwrap();
+        assert!(decode_return_data::<ProgramCapabilities>(&data).is_err());
+
+        // unknown version
+        let mut data = encode_return_data(&capabilities).unwrap();
+        data[0] = RETURN_DATA_VERSION + 1;
+        assert!(decode_return_data::<ProgramCapabilities>(&data).is_err());
+
+        // truncated value
+        let data = encode_return_data(&capabilities).unwrap();
+        assert!(decode_return_data::<ProgramCapabilities>(&data[..data.len() - 1]).is_err());
+    }
+}
@@ -15,7 +15,7 @@ pub struct AssetData {
     /// URI pointing to JSON representing the asset.
     pub uri: String,
     /// Royalty basis points that goes to creators in secondary sales (0-10000).
-    pub seller_fee_basis_points: u16,
+    pub seller_fee_basis_points: BasisPoints,
     /// Array of creators.
     pub creators: Option<Vec<Creator>>,
     // Immutable, once flipped, all sales of this metadata are considered secondary.
@@ -41,23 +41,101 @@ pub struct AssetData {
     pub rule_set: Option<Pubkey>,
 }
 
-impl AssetData {
-    pub fn new(token_standard: TokenStandard, name: String, symbol: String, uri: String) -> Self {
+/// Configuration used to create an `AssetData`.
+///
+/// Fields added to the configuration always have a default value, so creating it with
+/// `..Default::default()` for the fields not set keeps compiling across versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetDataConfig {
+    /// The name of the asset.
+    pub name: String,
+    /// The symbol for the asset.
+    pub symbol: String,
+    /// URI pointing to JSON representing the asset.
+    pub uri: String,
+    /// Royalty basis points that goes to creators in secondary sales (0-10000).
+    pub seller_fee_basis_points: BasisPoints,
+    /// Array of creators.
+    pub creators: Option<Vec<Creator>>,
+    /// Whether the primary sale of the asset has happened (default is `false`).
+    pub primary_sale_happened: bool,
+    /// Whether the data struct is mutable (default is `true`).
+    pub is_mutable: bool,
+    /// Type of the token (default is `NonFungible`).
+    pub token_standard: TokenStandard,
+    /// Collection information.
+    pub collection: Option<Collection>,
+    /// Uses information.
+    pub uses: Option<Uses>,
+    /// Collection item details.
+    pub collection_details: Option<CollectionDetails>,
+    /// Programmable rule set for the asset.
+    pub rule_set: Option<Pubkey>,
+}
+
+impl Default for AssetDataConfig {
+    fn default() -> Self {
         Self {
-            name,
-            symbol,
-            uri,
-            seller_fee_basis_points: 0,
+            name: String::default(),
+            symbol: String::default(),
+            uri: String::default(),
+            seller_fee_basis_points: BasisPoints::default(),
             creators: None,
             primary_sale_happened: false,
             is_mutable: true,
-            token_standard,
+            token_standard: TokenStandard::NonFungible,
             collection: None,
             uses: None,
             collection_details: None,
             rule_set: None,
         }
     }
+}
+
+impl AssetData {
+    /// Creates the data of an asset from its configuration.
+    pub fn from_config(config: AssetDataConfig) -> Self {
+        let AssetDataConfig {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened,
+            is_mutable,
+            token_standard,
+            collection,
+            uses,
+            collection_details,
+            rule_set,
+        } = config;
+
+        Self {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened,
+            is_mutable,
+            token_standard,
+            collection,
+            uses,
+            collection_details,
+            rule_set,
+        }
+    }
+
+    #[deprecated(since = "1.9.0", note = "Use `AssetData::from_config` instead")]
+    pub fn new(token_standard: TokenStandard, name: String, symbol: String, uri: String) -> Self {
+        Self::from_config(AssetDataConfig {
+            name,
+            symbol,
+            uri,
+            token_standard,
+            ..Default::default()
+        })
+    }
 
     pub fn as_data_v2(&self) -> DataV2 {
         DataV2 {
@@ -81,3 +159,31 @@ impl AssetData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_new_matches_config() {
+        let asset = AssetData::new(
+            TokenStandard::Fungible,
+            String::from("name"),
+            String::from("symbol"),
+            String::from("uri"),
+        );
+
+        let config = AssetData::from_config(AssetDataConfig {
+            name: String::from("name"),
+            symbol: String::from("symbol"),
+            uri: String::from("uri"),
+            token_standard: TokenStandard::Fungible,
+            ..Default::default()
+        });
+
+        assert_eq!(asset, config);
+        assert!(asset.is_mutable);
+        assert!(!asset.primary_sale_happened);
+    }
+}
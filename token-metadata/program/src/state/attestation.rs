@@ -0,0 +1,95 @@
+use super::*;
+
+pub const ATTESTATION_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 32  // holder
++ 32  // topic hash
++ 32  // message hash
++ 8; // slot
+
+/// The `Attestation` account records a statement co-signed by the holder of an asset (e.g.,
+/// the acceptance of a license), identified by the hash of its topic.
+///
+/// Only the hash of the message is stored on-chain; the message itself is kept off-chain by
+/// the party requesting the attestation. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"attest"`
+/// 5. holder id
+/// 6. topic hash
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct Attestation {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Address of the holder that signed the attestation.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub holder: Pubkey,
+    /// Hash of the topic of the attestation.
+    pub topic_hash: [u8; 32],
+    /// Hash of the attested message.
+    pub message_hash: [u8; 32],
+    /// Slot at which the attestation was (last) approved.
+    pub slot: u64,
+}
+
+impl Default for Attestation {
+    fn default() -> Self {
+        Self {
+            key: Key::Attestation,
+            bump: 255,
+            mint: Pubkey::default(),
+            holder: Pubkey::default(),
+            topic_hash: [0; 32],
+            message_hash: [0; 32],
+            slot: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for Attestation {
+    fn key() -> Key {
+        Key::Attestation
+    }
+
+    fn size() -> usize {
+        ATTESTATION_SIZE
+    }
+}
+
+impl Attestation {
+    pub fn from_bytes(data: &[u8]) -> Result<Attestation, ProgramError> {
+        let attestation: Attestation =
+            try_from_slice_checked(data, Key::Attestation, ATTESTATION_SIZE)?;
+        Ok(attestation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let attestation = Attestation {
+            mint: Pubkey::new_unique(),
+            holder: Pubkey::new_unique(),
+            topic_hash: [1; 32],
+            message_hash: [2; 32],
+            slot: 42,
+            ..Default::default()
+        };
+        let data = attestation.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), ATTESTATION_SIZE);
+        assert_eq!(Attestation::from_bytes(&data).unwrap(), attestation);
+    }
+}
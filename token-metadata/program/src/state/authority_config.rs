@@ -0,0 +1,224 @@
+use solana_program::hash::hash;
+
+use super::*;
+use crate::instruction::MetadataInstruction;
+
+/// Maximum number of signers of an authority config.
+pub const MAX_AUTHORITY_SIGNERS: usize = 10;
+
+pub const AUTHORITY_CONFIG_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // base
++ 1   // threshold
++ 4   // signers length
++ 32 * MAX_AUTHORITY_SIGNERS // signers
++ 1   // pending option
++ 32  // pending target
++ 32  // pending digest
++ 2; // pending approvals
+
+/// Action waiting for the approval of the signers of an authority config.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PendingApproval {
+    /// Account the action applies to (e.g., the metadata account being updated).
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub target: Pubkey,
+    /// Digest of the instruction being approved.
+    pub digest: [u8; 32],
+    /// Bitmask of the signers (by index) that approved the action.
+    pub approvals: u16,
+}
+
+/// The `AuthorityConfig` account is a threshold (M-of-N) multisig that can be used as
+/// the update authority of an asset.
+///
+/// Since the account cannot sign, each signer submits the same `Update` or `VerifyV2`
+/// instruction including the authority config: the approval of the signer is recorded
+/// and the instruction is only executed once `threshold` signers approved the same
+/// instruction on the same account. Approving a different instruction replaces the
+/// pending one. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. base (signer of the creation)
+/// 4. `"authority_config"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct AuthorityConfig {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address used to derive the account.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub base: Pubkey,
+    /// Number of approvals required to execute an action.
+    pub threshold: u8,
+    /// Signers of the authority config.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<Vec<DisplayFromStr>>"))]
+    pub signers: Vec<Pubkey>,
+    /// Action waiting for approvals.
+    pub pending: Option<PendingApproval>,
+}
+
+impl Default for AuthorityConfig {
+    fn default() -> Self {
+        Self {
+            key: Key::AuthorityConfig,
+            bump: 255,
+            base: Pubkey::default(),
+            threshold: 1,
+            signers: vec![],
+            pending: None,
+        }
+    }
+}
+
+impl TokenMetadataAccount for AuthorityConfig {
+    fn key() -> Key {
+        Key::AuthorityConfig
+    }
+
+    fn size() -> usize {
+        AUTHORITY_CONFIG_SIZE
+    }
+}
+
+impl AuthorityConfig {
+    pub fn from_bytes(data: &[u8]) -> Result<AuthorityConfig, ProgramError> {
+        let config: AuthorityConfig =
+            try_from_slice_checked(data, Key::AuthorityConfig, AUTHORITY_CONFIG_SIZE)?;
+        Ok(config)
+    }
+
+    /// Sets the signers and threshold, checking that the signers are unique and that
+    /// the threshold can be reached.
+    pub fn set_signers(&mut self, threshold: u8, signers: Vec<Pubkey>) -> ProgramResult {
+        let has_duplicates = signers
+            .iter()
+            .enumerate()
+            .any(|(index, signer)| signers[..index].contains(signer));
+
+        if signers.is_empty()
+            || signers.len() > MAX_AUTHORITY_SIGNERS
+            || has_duplicates
+            || threshold == 0
+            || threshold as usize > signers.len()
+        {
+            return Err(MetadataError::InvalidAuthorityConfig.into());
+        }
+
+        self.threshold = threshold;
+        self.signers = signers;
+        self.pending = None;
+
+        Ok(())
+    }
+
+    /// Records the approval of `signer` for the action on `target`, returning `true` once
+    /// the threshold is reached. The pending action is cleared when it is approved.
+    pub fn approve(
+        &mut self,
+        signer: &Pubkey,
+        target: &Pubkey,
+        digest: [u8; 32],
+    ) -> Result<bool, ProgramError> {
+        let index = self
+            .signers
+            .iter()
+            .position(|s| s == signer)
+            .ok_or(MetadataError::NotAuthorityConfigSigner)?;
+
+        let pending = match &mut self.pending {
+            Some(pending) if pending.target == *target && pending.digest == digest => pending,
+            pending => pending.insert(PendingApproval {
+                target: *target,
+                digest,
+                approvals: 0,
+            }),
+        };
+
+        pending.approvals |= 1 << index;
+
+        if pending.approvals.count_ones() >= self.threshold as u32 {
+            self.pending = None;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+}
+
+/// Returns the digest of an instruction approved through an authority config.
+pub fn authority_action_digest(
+    instruction: &MetadataInstruction,
+) -> Result<[u8; 32], ProgramError> {
+    Ok(hash(&instruction.try_to_vec()?).to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::UpdateArgs;
+
+    fn config(threshold: u8, signers: usize) -> (AuthorityConfig, Vec<Pubkey>) {
+        let signers: Vec<Pubkey> = (0..signers).map(|_| Pubkey::new_unique()).collect();
+        let mut config = AuthorityConfig::default();
+        config.set_signers(threshold, signers.clone()).unwrap();
+
+        (config, signers)
+    }
+
+    #[test]
+    fn serialized_size_matches() {
+        let (mut config, signers) = config(2, MAX_AUTHORITY_SIGNERS);
+        config.pending = Some(PendingApproval {
+            target: Pubkey::new_unique(),
+            digest: [1; 32],
+            approvals: 1,
+        });
+        let data = config.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), AUTHORITY_CONFIG_SIZE);
+        assert_eq!(AuthorityConfig::from_bytes(&data).unwrap().signers, signers);
+    }
+
+    #[test]
+    fn invalid_signers() {
+        let mut config = AuthorityConfig::default();
+        let signer = Pubkey::new_unique();
+
+        assert!(config.set_signers(1, vec![]).is_err());
+        assert!(config.set_signers(0, vec![signer]).is_err());
+        assert!(config.set_signers(2, vec![signer]).is_err());
+        assert!(config.set_signers(1, vec![signer, signer]).is_err());
+        assert!(config
+            .set_signers(1, vec![signer; MAX_AUTHORITY_SIGNERS + 1])
+            .is_err());
+    }
+
+    #[test]
+    fn approve_until_threshold() {
+        let (mut config, signers) = config(2, 3);
+        let target = Pubkey::new_unique();
+        let digest =
+            authority_action_digest(&MetadataInstruction::Update(UpdateArgs::default())).unwrap();
+
+        assert!(config
+            .approve(&Pubkey::new_unique(), &target, digest)
+            .is_err());
+
+        // approving twice does not count as two approvals
+        assert!(!config.approve(&signers[0], &target, digest).unwrap());
+        assert!(!config.approve(&signers[0], &target, digest).unwrap());
+
+        // a different action replaces the pending one
+        assert!(!config.approve(&signers[1], &target, [0; 32]).unwrap());
+        assert!(!config.approve(&signers[0], &target, digest).unwrap());
+
+        assert!(config.approve(&signers[2], &target, digest).unwrap());
+        assert!(config.pending.is_none());
+    }
+}
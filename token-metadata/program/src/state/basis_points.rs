@@ -0,0 +1,109 @@
+use std::fmt;
+
+use super::*;
+
+/// Maximum value of basis points (100%).
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Basis points (1/100th of a percent), between 0 and 10000.
+///
+/// The value is serialized as a `u16`, so it can replace existing basis points fields
+/// without changing the account or instruction layouts. Since deserialization does not
+/// validate the value, `is_valid` should be checked before using values read from
+/// instruction arguments.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-feature", serde(transparent))]
+#[derive(
+    BorshSerialize, BorshDeserialize, Default, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy,
+)]
+pub struct BasisPoints(u16);
+
+impl BasisPoints {
+    /// Creates a new `BasisPoints`, failing if the value is greater than 10000.
+    pub fn new(value: u16) -> Result<Self, MetadataError> {
+        if value > MAX_BASIS_POINTS {
+            return Err(MetadataError::InvalidBasisPoints);
+        }
+
+        Ok(Self(value))
+    }
+
+    /// Creates a new `BasisPoints` without validating the value. This is used by the
+    /// instruction builders that take raw values, which are validated by the program.
+    pub(crate) const fn new_unchecked(value: u16) -> Self {
+        Self(value)
+    }
+
+    pub const fn value(&self) -> u16 {
+        self.0
+    }
+
+    pub const fn is_valid(&self) -> bool {
+        self.0 <= MAX_BASIS_POINTS
+    }
+
+    /// Returns the portion of `amount` (e.g., lamports) represented by the basis points,
+    /// rounded down. Returns `None` if the value is not valid.
+    pub fn apply_to(&self, amount: u64) -> Option<u64> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        // the result is at most `amount`, so it always fits in a u64
+        Some((amount as u128 * self.0 as u128 / MAX_BASIS_POINTS as u128) as u64)
+    }
+}
+
+impl TryFrom<u16> for BasisPoints {
+    type Error = MetadataError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<BasisPoints> for u16 {
+    fn from(basis_points: BasisPoints) -> Self {
+        basis_points.0
+    }
+}
+
+impl fmt::Display for BasisPoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basis_points_validation() {
+        assert_eq!(BasisPoints::new(10_000).unwrap().value(), 10_000);
+        assert_eq!(
+            BasisPoints::new(10_001).unwrap_err(),
+            MetadataError::InvalidBasisPoints
+        );
+        assert!(!BasisPoints::new_unchecked(u16::MAX).is_valid());
+
+        // same layout as a u16
+        let basis_points = BasisPoints::new(500).unwrap();
+        let data = basis_points.try_to_vec().unwrap();
+        assert_eq!(data, 500u16.try_to_vec().unwrap());
+        assert_eq!(BasisPoints::try_from_slice(&data).unwrap(), basis_points);
+    }
+
+    #[test]
+    fn basis_points_apply_to() {
+        assert_eq!(BasisPoints::new(500).unwrap().apply_to(1_000), Some(50));
+        assert_eq!(BasisPoints::new(333).unwrap().apply_to(1_000), Some(33));
+        assert_eq!(
+            BasisPoints::new(10_000).unwrap().apply_to(u64::MAX),
+            Some(u64::MAX)
+        );
+        assert_eq!(BasisPoints::default().apply_to(u64::MAX), Some(0));
+        assert_eq!(BasisPoints::new_unchecked(10_001).apply_to(1_000), None);
+    }
+}
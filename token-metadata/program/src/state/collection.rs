@@ -55,7 +55,54 @@ impl CollectionAuthorityRecord {
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub enum CollectionDetails {
-    V1 { size: u64 },
+    V1 {
+        size: u64,
+    },
+    /// Collection parent that does not keep track of its size, so verifying or unverifying
+    /// items does not write to the parent account.
+    Uncapped,
+    /// Sized collection parent that can opt in to the stolen asset denylist: when
+    /// `enforce_denylist` is set, transfers of verified items fail while their mint is
+    /// on the denylist (`Denylisted` account).
+    V2 {
+        size: u64,
+        enforce_denylist: bool,
+    },
+}
+
+impl CollectionDetails {
+    /// Returns the size of the collection, or `None` if it does not keep track of it.
+    pub fn size(&self) -> Option<u64> {
+        match self {
+            CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => Some(*size),
+            CollectionDetails::Uncapped => None,
+        }
+    }
+
+    /// Returns the details with a new size, keeping the remaining settings.
+    pub fn with_size(&self, size: u64) -> Self {
+        match self {
+            CollectionDetails::V1 { .. } => CollectionDetails::V1 { size },
+            CollectionDetails::V2 {
+                enforce_denylist, ..
+            } => CollectionDetails::V2 {
+                size,
+                enforce_denylist: *enforce_denylist,
+            },
+            CollectionDetails::Uncapped => CollectionDetails::Uncapped,
+        }
+    }
+
+    /// Indicates whether transfers of the collection items consult the stolen asset denylist.
+    pub fn enforces_denylist(&self) -> bool {
+        matches!(
+            self,
+            CollectionDetails::V2 {
+                enforce_denylist: true,
+                ..
+            }
+        )
+    }
 }
 
 #[cfg(test)]
@@ -66,7 +113,10 @@ mod tests {
 
     use crate::{
         error::MetadataError,
-        state::{CollectionAuthorityRecord, Key, TokenMetadataAccount, UseAuthorityRecord},
+        state::{
+            CollectionAuthorityRecord, CollectionDetails, Key, TokenMetadataAccount,
+            UseAuthorityRecord,
+        },
         ID,
     };
 
@@ -125,4 +175,19 @@ mod tests {
         let error = CollectionAuthorityRecord::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn with_size_keeps_denylist() {
+        let details = CollectionDetails::V2 {
+            size: 1,
+            enforce_denylist: true,
+        };
+
+        let details = details.with_size(2);
+        assert_eq!(details.size(), Some(2));
+        assert!(details.enforces_denylist());
+
+        assert!(!CollectionDetails::V1 { size: 2 }.enforces_denylist());
+        assert_eq!(CollectionDetails::Uncapped.with_size(2).size(), None);
+    }
 }
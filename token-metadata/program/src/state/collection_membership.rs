@@ -0,0 +1,91 @@
+use super::*;
+
+pub const COLLECTION_MEMBERSHIP_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 32  // collection mint
++ 8; // expires at
+
+/// The `CollectionMembership` account stores the expiry of the membership of an asset in its
+/// collection (e.g., seasonal collections or event badges valid for a limited time).
+///
+/// Once expired, the asset no longer counts as a verified member of the collection: the
+/// membership can then be closed by anyone, unverifying the collection of the asset and
+/// decrementing the size of sized collections. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"collection_membership"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionMembership {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Address of the mint of the collection.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Unix timestamp after which the membership expires.
+    pub expires_at: i64,
+}
+
+impl Default for CollectionMembership {
+    fn default() -> Self {
+        Self {
+            key: Key::CollectionMembership,
+            bump: 255,
+            mint: Pubkey::default(),
+            collection_mint: Pubkey::default(),
+            expires_at: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for CollectionMembership {
+    fn key() -> Key {
+        Key::CollectionMembership
+    }
+
+    fn size() -> usize {
+        COLLECTION_MEMBERSHIP_SIZE
+    }
+}
+
+impl CollectionMembership {
+    pub fn from_bytes(data: &[u8]) -> Result<CollectionMembership, ProgramError> {
+        let membership: CollectionMembership =
+            try_from_slice_checked(data, Key::CollectionMembership, COLLECTION_MEMBERSHIP_SIZE)?;
+        Ok(membership)
+    }
+
+    /// Checks whether the membership has expired at the given unix timestamp.
+    pub fn is_expired(&self, timestamp: i64) -> bool {
+        timestamp >= self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let membership = CollectionMembership {
+            mint: Pubkey::new_unique(),
+            collection_mint: Pubkey::new_unique(),
+            expires_at: 1_700_000_000,
+            ..Default::default()
+        };
+        let data = membership.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), COLLECTION_MEMBERSHIP_SIZE);
+        assert_eq!(CollectionMembership::from_bytes(&data).unwrap(), membership);
+        assert!(!membership.is_expired(1_699_999_999));
+        assert!(membership.is_expired(1_700_000_000));
+    }
+}
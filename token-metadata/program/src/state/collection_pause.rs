@@ -0,0 +1,178 @@
+use super::*;
+
+pub const COLLECTION_PAUSE_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // collection mint
++ 9; // paused until
+
+/// Maximum duration (in seconds) of a collection pause.
+pub const MAX_COLLECTION_PAUSE_DURATION: i64 = 604_800;
+
+/// Minimum time (in seconds) between the end of a collection pause and the next pause, so
+/// renewals cannot keep the items of a collection paused indefinitely.
+pub const COLLECTION_PAUSE_COOLDOWN: i64 = 86_400;
+
+/// The `CollectionPause` account stores the emergency pause of a collection set by the
+/// update authority of the collection parent.
+///
+/// While the collection is paused, verified items of the collection cannot be transferred,
+/// burned or have token delegates approved. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. collection mint id
+/// 4. `"collection_pause"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionPause {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the collection mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Unix timestamp at which the last pause of the collection ends (`None` when the
+    /// collection was never paused).
+    pub paused_until: Option<i64>,
+}
+
+impl Default for CollectionPause {
+    fn default() -> Self {
+        Self {
+            key: Key::CollectionPause,
+            bump: 255,
+            collection_mint: Pubkey::default(),
+            paused_until: None,
+        }
+    }
+}
+
+impl TokenMetadataAccount for CollectionPause {
+    fn key() -> Key {
+        Key::CollectionPause
+    }
+
+    fn size() -> usize {
+        COLLECTION_PAUSE_SIZE
+    }
+}
+
+impl CollectionPause {
+    pub fn from_bytes(data: &[u8]) -> Result<CollectionPause, ProgramError> {
+        let pause: CollectionPause =
+            try_from_slice_checked(data, Key::CollectionPause, COLLECTION_PAUSE_SIZE)?;
+        Ok(pause)
+    }
+
+    /// Pauses the collection for the specified duration (in seconds).
+    ///
+    /// A pause cannot be extended: the collection can only be paused again once the
+    /// cooldown after the end of the previous pause has elapsed.
+    pub fn pause(&mut self, duration: i64, now: i64) -> ProgramResult {
+        if duration <= 0 || duration > MAX_COLLECTION_PAUSE_DURATION {
+            return Err(MetadataError::InvalidPauseDuration.into());
+        }
+
+        if let Some(paused_until) = self.paused_until {
+            if now < paused_until.saturating_add(COLLECTION_PAUSE_COOLDOWN) {
+                return Err(MetadataError::CollectionPauseCooldown.into());
+            }
+        }
+
+        self.paused_until = Some(
+            now.checked_add(duration)
+                .ok_or(MetadataError::NumericalOverflowError)?,
+        );
+
+        Ok(())
+    }
+
+    /// Lifts the pause of the collection, which ends at the specified time (the cooldown
+    /// before the next pause starts from it).
+    pub fn unpause(&mut self, now: i64) {
+        if self.is_paused(now) {
+            self.paused_until = Some(now);
+        }
+    }
+
+    /// Indicates whether the collection is paused at the specified time.
+    pub fn is_paused(&self, now: i64) -> bool {
+        matches!(self.paused_until, Some(paused_until) if now < paused_until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let pause = CollectionPause {
+            paused_until: Some(1_000),
+            ..Default::default()
+        };
+        let data = pause.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), COLLECTION_PAUSE_SIZE);
+        assert_eq!(CollectionPause::from_bytes(&data).unwrap(), pause);
+    }
+
+    #[test]
+    fn paused_for_duration() {
+        let mut pause = CollectionPause::default();
+        assert!(!pause.is_paused(0));
+
+        pause.pause(100, 1_000).unwrap();
+
+        assert!(pause.is_paused(1_000));
+        assert!(pause.is_paused(1_099));
+        assert!(!pause.is_paused(1_100));
+    }
+
+    #[test]
+    fn pause_duration_is_capped() {
+        let mut pause = CollectionPause::default();
+
+        assert!(pause.pause(0, 1_000).is_err());
+        assert!(pause
+            .pause(MAX_COLLECTION_PAUSE_DURATION + 1, 1_000)
+            .is_err());
+        assert!(pause.pause(MAX_COLLECTION_PAUSE_DURATION, 1_000).is_ok());
+    }
+
+    #[test]
+    fn pause_renewal_requires_cooldown() {
+        let mut pause = CollectionPause::default();
+        pause.pause(MAX_COLLECTION_PAUSE_DURATION, 0).unwrap();
+
+        // cannot be extended while paused
+        assert!(pause.pause(MAX_COLLECTION_PAUSE_DURATION, 1).is_err());
+
+        // nor renewed right after the pause ends
+        let paused_until = MAX_COLLECTION_PAUSE_DURATION;
+        assert!(pause.pause(100, paused_until).is_err());
+        assert!(pause
+            .pause(100, paused_until + COLLECTION_PAUSE_COOLDOWN - 1)
+            .is_err());
+
+        pause
+            .pause(100, paused_until + COLLECTION_PAUSE_COOLDOWN)
+            .unwrap();
+        assert!(pause.is_paused(paused_until + COLLECTION_PAUSE_COOLDOWN));
+    }
+
+    #[test]
+    fn unpause_starts_cooldown() {
+        let mut pause = CollectionPause::default();
+        pause.pause(MAX_COLLECTION_PAUSE_DURATION, 0).unwrap();
+
+        pause.unpause(10);
+        assert!(!pause.is_paused(10));
+        assert_eq!(pause.paused_until, Some(10));
+
+        // unpausing does not reset the cooldown
+        assert!(pause.pause(100, 11).is_err());
+        pause.pause(100, 10 + COLLECTION_PAUSE_COOLDOWN).unwrap();
+    }
+}
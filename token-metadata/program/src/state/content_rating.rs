@@ -0,0 +1,161 @@
+use super::*;
+use crate::pda::ProgramContext;
+
+pub const CONTENT_RATING_RECORD_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 1; // rating
+
+/// Standardized rating of the content of an asset.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ContentRating {
+    /// Content suitable for all audiences.
+    General,
+    /// Content not suitable for all audiences (e.g., violence, strong language).
+    Mature,
+    /// Explicit content.
+    Explicit,
+}
+
+impl ContentRating {
+    /// Indicates whether the content is safe for work, i.e., it can be displayed without
+    /// any warning.
+    pub fn is_safe_for_work(&self) -> bool {
+        matches!(self, ContentRating::General)
+    }
+}
+
+/// The `ContentRatingRecord` account stores the content rating of an asset set by its
+/// update authority.
+///
+/// Wallets and marketplaces can read the rating to gate the content of an asset; assets
+/// without a record are unrated. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"content_rating"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct ContentRatingRecord {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Content rating of the asset.
+    pub rating: ContentRating,
+}
+
+impl Default for ContentRatingRecord {
+    fn default() -> Self {
+        Self {
+            key: Key::ContentRatingRecord,
+            bump: 255,
+            mint: Pubkey::default(),
+            rating: ContentRating::General,
+        }
+    }
+}
+
+impl TokenMetadataAccount for ContentRatingRecord {
+    fn key() -> Key {
+        Key::ContentRatingRecord
+    }
+
+    fn size() -> usize {
+        CONTENT_RATING_RECORD_SIZE
+    }
+}
+
+impl ContentRatingRecord {
+    pub fn from_bytes(data: &[u8]) -> Result<ContentRatingRecord, ProgramError> {
+        let record: ContentRatingRecord =
+            try_from_slice_checked(data, Key::ContentRatingRecord, CONTENT_RATING_RECORD_SIZE)?;
+        Ok(record)
+    }
+
+    /// Returns the content rating of the asset from its (derived) record account, or
+    /// `None` if the asset is unrated.
+    pub fn content_rating(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        record_info: &AccountInfo,
+    ) -> Result<Option<ContentRating>, ProgramError> {
+        let (expected, _) = ProgramContext::new(*program_id).find_content_rating_account(mint);
+
+        if expected != *record_info.key {
+            return Err(MetadataError::DerivedKeyInvalid.into());
+        }
+
+        if record_info.data_is_empty() {
+            return Ok(None);
+        }
+
+        if record_info.owner != program_id {
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        Ok(Some(Self::from_bytes(&record_info.data.borrow())?.rating))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda::find_content_rating_account;
+
+    #[test]
+    fn safe_for_work() {
+        assert!(ContentRating::General.is_safe_for_work());
+        assert!(!ContentRating::Mature.is_safe_for_work());
+        assert!(!ContentRating::Explicit.is_safe_for_work());
+    }
+
+    #[test]
+    fn serialized_size_matches() {
+        let record = ContentRatingRecord {
+            rating: ContentRating::Explicit,
+            ..Default::default()
+        };
+        let data = record.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), CONTENT_RATING_RECORD_SIZE);
+        assert_eq!(ContentRatingRecord::from_bytes(&data).unwrap(), record);
+    }
+
+    #[test]
+    fn unrated_asset() {
+        let mint = Pubkey::new_unique();
+        let (key, _) = find_content_rating_account(&mint);
+        let mut lamports = 0;
+        let mut data = vec![];
+        let owner = solana_program::system_program::ID;
+
+        let record_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            ContentRatingRecord::content_rating(&crate::ID, &mint, &record_info).unwrap(),
+            None
+        );
+        assert!(ContentRatingRecord::content_rating(
+            &crate::ID,
+            &Pubkey::new_unique(),
+            &record_info
+        )
+        .is_err());
+    }
+}
@@ -14,3 +14,69 @@ pub struct Creator {
     // In percentages, NOT basis points ;) Watch out!
     pub share: u8,
 }
+
+/// Splits a royalty payment between the verified creators, proportionally to their shares.
+///
+/// The remainder of the division is paid to the last verified creator, so the payments
+/// always add up to `amount`.
+pub fn split_royalties(
+    creators: &[Creator],
+    amount: u64,
+) -> Result<Vec<(Pubkey, u64)>, ProgramError> {
+    let verified: Vec<&Creator> = creators.iter().filter(|c| c.verified).collect();
+    let verified_share: u128 = verified.iter().map(|c| c.share as u128).sum();
+
+    if verified_share == 0 {
+        return Err(MetadataError::NoVerifiedCreators.into());
+    }
+
+    let mut payments: Vec<(Pubkey, u64)> = verified
+        .iter()
+        .map(|c| {
+            let payment = (amount as u128 * c.share as u128) / verified_share;
+            (c.address, payment as u64)
+        })
+        .collect();
+
+    let paid: u64 = payments.iter().map(|(_, payment)| payment).sum();
+
+    if let Some((_, last)) = payments.last_mut() {
+        *last = last
+            .checked_add(amount - paid)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+    }
+
+    Ok(payments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creator(verified: bool, share: u8) -> Creator {
+        Creator {
+            address: Pubkey::new_unique(),
+            verified,
+            share,
+        }
+    }
+
+    #[test]
+    fn split_royalties_between_verified_creators() {
+        let creators = vec![creator(true, 50), creator(false, 20), creator(true, 30)];
+
+        let payments = split_royalties(&creators, 1_000).unwrap();
+        assert_eq!(
+            payments,
+            vec![(creators[0].address, 625), (creators[2].address, 375)]
+        );
+
+        // the remainder is paid to the last verified creator
+        let payments = split_royalties(&creators, 7).unwrap();
+        assert_eq!(payments.iter().map(|(_, p)| p).sum::<u64>(), 7);
+        assert_eq!(payments[1].1, 3);
+
+        let err = split_royalties(&[creator(false, 100)], 1_000).unwrap_err();
+        assert_eq!(err, MetadataError::NoVerifiedCreators.into());
+    }
+}
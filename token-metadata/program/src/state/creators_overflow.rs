@@ -0,0 +1,98 @@
+use super::*;
+
+/// Maximum number of creators stored on a creators overflow account.
+pub const MAX_OVERFLOW_CREATOR_LIMIT: usize = 10;
+
+pub const CREATORS_OVERFLOW_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 4   // creators length
++ MAX_OVERFLOW_CREATOR_LIMIT * MAX_CREATOR_LEN; // creators
+
+/// The `CreatorsOverflow` account stores the creators of an asset that do not fit in the
+/// creators array of the metadata (limited to `MAX_CREATOR_LIMIT` entries).
+///
+/// The shares of the creators of both lists must add up to 100; the total share of the
+/// overflow creators is stored on the metadata (`overflow_creators_share`). The seeds for
+/// the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"creators_overflow"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CreatorsOverflow {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Creators in addition to the creators of the metadata.
+    pub creators: Vec<Creator>,
+}
+
+impl Default for CreatorsOverflow {
+    fn default() -> Self {
+        Self {
+            key: Key::CreatorsOverflow,
+            bump: 255,
+            mint: Pubkey::default(),
+            creators: vec![],
+        }
+    }
+}
+
+impl TokenMetadataAccount for CreatorsOverflow {
+    fn key() -> Key {
+        Key::CreatorsOverflow
+    }
+
+    fn size() -> usize {
+        CREATORS_OVERFLOW_SIZE
+    }
+}
+
+impl CreatorsOverflow {
+    pub fn from_bytes(data: &[u8]) -> Result<CreatorsOverflow, ProgramError> {
+        let overflow: CreatorsOverflow =
+            try_from_slice_checked(data, Key::CreatorsOverflow, CREATORS_OVERFLOW_SIZE)?;
+        Ok(overflow)
+    }
+
+    /// Returns the total share of the overflow creators.
+    pub fn share(&self) -> Result<u8, ProgramError> {
+        self.creators.iter().try_fold(0u8, |total, creator| {
+            total
+                .checked_add(creator.share)
+                .ok_or_else(|| MetadataError::NumericalOverflowError.into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let overflow = CreatorsOverflow {
+            mint: Pubkey::new_unique(),
+            creators: (0..MAX_OVERFLOW_CREATOR_LIMIT)
+                .map(|_| Creator {
+                    address: Pubkey::new_unique(),
+                    verified: false,
+                    share: 5,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let data = overflow.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), CREATORS_OVERFLOW_SIZE);
+        assert_eq!(CreatorsOverflow::from_bytes(&data).unwrap(), overflow);
+        assert_eq!(overflow.share().unwrap(), 50);
+    }
+}
@@ -11,7 +11,7 @@ pub struct Data {
     /// URI pointing to JSON representing the asset
     pub uri: String,
     /// Royalty basis points that goes to creators in secondary sales (0-10000)
-    pub seller_fee_basis_points: u16,
+    pub seller_fee_basis_points: BasisPoints,
     /// Array of creators, optional
     pub creators: Option<Vec<Creator>>,
 }
@@ -27,7 +27,7 @@ pub struct DataV2 {
     /// URI pointing to JSON representing the asset
     pub uri: String,
     /// Royalty basis points that goes to creators in secondary sales (0-10000)
-    pub seller_fee_basis_points: u16,
+    pub seller_fee_basis_points: BasisPoints,
     /// Array of creators, optional
     pub creators: Option<Vec<Creator>>,
     /// Collection
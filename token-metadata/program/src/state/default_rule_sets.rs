@@ -0,0 +1,175 @@
+use solana_program::borsh::try_from_slice_unchecked;
+
+use super::*;
+
+/// Size of an empty `DefaultRuleSets` account.
+pub const DEFAULT_RULE_SETS_SIZE: usize = 1 // key
++ 1   // bump
++ 4; // rule sets (vec length)
+
+pub const DEFAULT_RULE_SET_ENTRY_SIZE: usize = 1 // token standard
++ 32; // rule set
+
+/// Entry of the `DefaultRuleSets`, mapping a token standard to its fallback rule set.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct DefaultRuleSetEntry {
+    /// Token standard of the assets.
+    pub token_standard: TokenStandard,
+    /// Address of the rule set.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub rule_set: Pubkey,
+}
+
+/// The `DefaultRuleSets` account stores the rule sets used by assets that opted in to the
+/// protocol defaults (`Metadata.use_default_rule_set`) and do not have a rule set of their own.
+///
+/// Default rule sets are set by the protocol authority. There is a single account for the
+/// program, so the seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. `"default_rule_sets"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct DefaultRuleSets {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Default rule set of each token standard.
+    pub rule_sets: Vec<DefaultRuleSetEntry>,
+}
+
+impl Default for DefaultRuleSets {
+    fn default() -> Self {
+        Self {
+            key: Key::DefaultRuleSets,
+            bump: 255,
+            rule_sets: vec![],
+        }
+    }
+}
+
+impl TokenMetadataAccount for DefaultRuleSets {
+    fn key() -> Key {
+        Key::DefaultRuleSets
+    }
+
+    fn size() -> usize {
+        DEFAULT_RULE_SETS_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+}
+
+impl DefaultRuleSets {
+    pub fn from_bytes(data: &[u8]) -> Result<DefaultRuleSets, ProgramError> {
+        // the size of the account depends on the number of rule sets, so only
+        // the key and the minimum size are checked
+        if data.len() < DEFAULT_RULE_SETS_SIZE || Key::of(data) != Some(Key::DefaultRuleSets) {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let default_rule_sets: DefaultRuleSets = try_from_slice_unchecked(data)?;
+        Ok(default_rule_sets)
+    }
+
+    /// Returns the size of the serialized account.
+    pub fn serialized_size(&self) -> usize {
+        DEFAULT_RULE_SETS_SIZE + self.rule_sets.len() * DEFAULT_RULE_SET_ENTRY_SIZE
+    }
+
+    /// Returns the default rule set of the token standard (if any).
+    pub fn get(&self, token_standard: TokenStandard) -> Option<Pubkey> {
+        self.rule_sets
+            .iter()
+            .find(|entry| entry.token_standard == token_standard)
+            .map(|entry| entry.rule_set)
+    }
+
+    /// Sets the default rule set of the token standard, replacing the current value. A
+    /// `None` rule set removes the default of the token standard.
+    pub fn set(&mut self, token_standard: TokenStandard, rule_set: Option<Pubkey>) {
+        self.rule_sets
+            .retain(|entry| entry.token_standard != token_standard);
+
+        if let Some(rule_set) = rule_set {
+            self.rule_sets.push(DefaultRuleSetEntry {
+                token_standard,
+                rule_set,
+            });
+        }
+    }
+
+    /// Saves the account data, resizing the account to fit the current list of rule sets.
+    pub fn save<'a>(
+        &self,
+        account_info: &'a AccountInfo<'a>,
+        payer_info: &'a AccountInfo<'a>,
+        system_program_info: &'a AccountInfo<'a>,
+    ) -> ProgramResult {
+        let required_size = self.serialized_size();
+
+        if account_info.data_len() != required_size {
+            resize_or_reallocate_account_raw(
+                account_info,
+                payer_info,
+                system_program_info,
+                required_size,
+            )?;
+        }
+
+        let mut account_data = account_info.data.borrow_mut();
+        let mut storage = &mut account_data[..required_size];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_default_rule_sets() {
+        let rule_set = Pubkey::new_unique();
+        let mut default_rule_sets = DefaultRuleSets::default();
+
+        assert_eq!(
+            default_rule_sets.get(TokenStandard::ProgrammableNonFungible),
+            None
+        );
+
+        default_rule_sets.set(TokenStandard::ProgrammableNonFungible, Some(rule_set));
+        assert_eq!(
+            default_rule_sets.get(TokenStandard::ProgrammableNonFungible),
+            Some(rule_set)
+        );
+        assert_eq!(default_rule_sets.get(TokenStandard::NonFungible), None);
+
+        // replaces the current value
+        let other = Pubkey::new_unique();
+        default_rule_sets.set(TokenStandard::ProgrammableNonFungible, Some(other));
+        assert_eq!(default_rule_sets.rule_sets.len(), 1);
+        assert_eq!(
+            default_rule_sets.get(TokenStandard::ProgrammableNonFungible),
+            Some(other)
+        );
+
+        let data = default_rule_sets.try_to_vec().unwrap();
+        assert_eq!(data.len(), default_rule_sets.serialized_size());
+        assert_eq!(
+            DefaultRuleSets::from_bytes(&data).unwrap(),
+            default_rule_sets
+        );
+
+        default_rule_sets.set(TokenStandard::ProgrammableNonFungible, None);
+        assert!(default_rule_sets.rule_sets.is_empty());
+        assert_eq!(default_rule_sets.serialized_size(), DEFAULT_RULE_SETS_SIZE);
+    }
+}
@@ -1,6 +1,12 @@
 use super::*;
 
-const SIZE: usize = 98;
+const SIZE: usize = 116;
+
+/// Size of the records created before the delegate expiry was introduced.
+const LEGACY_SIZE: usize = 98;
+
+/// Size of the records created before the delegate uses were introduced.
+const EXPIRY_SIZE: usize = 107;
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -22,6 +28,10 @@ pub struct MetadataDelegateRecord {
     pub delegate: Pubkey, // 32
     #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
     pub update_authority: Pubkey, // 32
+    /// Unix timestamp at which the delegate expires (if any).
+    pub expiry: Option<i64>, // 9
+    /// Number of uses left to a `Use` delegate (if limited).
+    pub remaining_uses: Option<u64>, // 9
 }
 
 impl Default for MetadataDelegateRecord {
@@ -32,6 +42,8 @@ impl Default for MetadataDelegateRecord {
             mint: Pubkey::default(),
             delegate: Pubkey::default(),
             update_authority: Pubkey::default(),
+            expiry: None,
+            remaining_uses: None,
         }
     }
 }
@@ -44,12 +56,185 @@ impl TokenMetadataAccount for MetadataDelegateRecord {
     fn size() -> usize {
         SIZE
     }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = &account_info.try_borrow_data()?;
+        let delegate = Self::from_bytes(data)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(delegate)
+    }
 }
 
 impl MetadataDelegateRecord {
     pub fn from_bytes(data: &[u8]) -> Result<MetadataDelegateRecord, ProgramError> {
+        // records created before the expiry (or uses) was introduced do not have space
+        // for it, so they are padded with zeros (no expiry and unlimited uses)
         let delegate: MetadataDelegateRecord =
-            try_from_slice_checked(data, Key::MetadataDelegate, MetadataDelegateRecord::size())?;
+            if data.len() == LEGACY_SIZE || data.len() == EXPIRY_SIZE {
+                let mut padded = data.to_vec();
+                padded.resize(SIZE, 0);
+                try_from_slice_checked(&padded, Key::MetadataDelegate, SIZE)?
+            } else {
+                try_from_slice_checked(data, Key::MetadataDelegate, MetadataDelegateRecord::size())?
+            };
         Ok(delegate)
     }
+
+    /// Checks whether the delegate has expired at the given unix timestamp.
+    pub fn is_expired(&self, timestamp: i64) -> bool {
+        matches!(self.expiry, Some(expiry) if timestamp >= expiry)
+    }
+
+    /// Consumes one of the remaining uses of the delegate, if its uses are limited.
+    pub fn consume_use(&mut self) -> ProgramResult {
+        if let Some(remaining_uses) = self.remaining_uses {
+            self.remaining_uses = Some(
+                remaining_uses
+                    .checked_sub(1)
+                    .ok_or(MetadataError::NotEnoughDelegateUses)?,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub const COLLECTION_ITEMS_DELEGATE_RECORD_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // collection mint
++ 32  // holder
++ 32  // delegate
++ 8; // locked items
+
+/// The `CollectionItemsDelegateRecord` account authorizes a delegate to lock and unlock
+/// any item of a collection held by the `holder`, without a delegate per item.
+///
+/// The record is approved by the holder (not the update authority) and can only be
+/// revoked once all the items locked by the delegate are unlocked. The seeds for the
+/// PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. collection mint id
+/// 4. `"collection_items_delegate"`
+/// 5. holder id
+/// 6. delegate id
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct CollectionItemsDelegateRecord {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the collection mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Address of the holder that approved the delegate.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub holder: Pubkey,
+    /// Address of the delegate.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub delegate: Pubkey,
+    /// Number of items currently locked by the delegate.
+    pub locked_items: u64,
+}
+
+impl Default for CollectionItemsDelegateRecord {
+    fn default() -> Self {
+        Self {
+            key: Key::CollectionItemsDelegate,
+            bump: 255,
+            collection_mint: Pubkey::default(),
+            holder: Pubkey::default(),
+            delegate: Pubkey::default(),
+            locked_items: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for CollectionItemsDelegateRecord {
+    fn key() -> Key {
+        Key::CollectionItemsDelegate
+    }
+
+    fn size() -> usize {
+        COLLECTION_ITEMS_DELEGATE_RECORD_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delegate_record_expiry() {
+        let record = MetadataDelegateRecord {
+            mint: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            update_authority: Pubkey::new_unique(),
+            expiry: Some(100),
+            remaining_uses: Some(10),
+            ..Default::default()
+        };
+        let data = record.try_to_vec().unwrap();
+        assert_eq!(data.len(), MetadataDelegateRecord::size());
+        assert_eq!(MetadataDelegateRecord::from_bytes(&data).unwrap(), record);
+
+        assert!(!record.is_expired(99));
+        assert!(record.is_expired(100));
+
+        // records created before the expiry was introduced
+        let legacy_record = MetadataDelegateRecord::from_bytes(&data[..LEGACY_SIZE]).unwrap();
+        assert_eq!(legacy_record.expiry, None);
+        assert_eq!(legacy_record.delegate, record.delegate);
+        assert!(!legacy_record.is_expired(i64::MAX));
+
+        // records created before the uses were introduced
+        let expiry_record = MetadataDelegateRecord::from_bytes(&data[..EXPIRY_SIZE]).unwrap();
+        assert_eq!(expiry_record.expiry, Some(100));
+        assert_eq!(expiry_record.remaining_uses, None);
+    }
+
+    #[test]
+    fn delegate_record_uses() {
+        let mut record = MetadataDelegateRecord {
+            remaining_uses: Some(1),
+            ..Default::default()
+        };
+        let data = record.try_to_vec().unwrap();
+        assert_eq!(data.len(), MetadataDelegateRecord::size() - 8);
+
+        record.consume_use().unwrap();
+        assert_eq!(record.remaining_uses, Some(0));
+        assert!(record.consume_use().is_err());
+
+        // delegates without a limit are not tracked
+        let mut record = MetadataDelegateRecord::default();
+        record.consume_use().unwrap();
+        assert_eq!(record.remaining_uses, None);
+    }
+
+    #[test]
+    fn collection_items_delegate_record_size() {
+        let record = CollectionItemsDelegateRecord {
+            collection_mint: Pubkey::new_unique(),
+            holder: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            locked_items: 1,
+            ..Default::default()
+        };
+        let data = record.try_to_vec().unwrap();
+        assert_eq!(data.len(), CollectionItemsDelegateRecord::size());
+        assert_eq!(
+            CollectionItemsDelegateRecord::safe_deserialize(&data).unwrap(),
+            record
+        );
+    }
 }
@@ -0,0 +1,183 @@
+use solana_program::borsh::try_from_slice_unchecked;
+
+use super::*;
+use crate::instruction::MetadataDelegateRole;
+
+/// Size of an empty registry.
+pub const DELEGATE_REGISTRY_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 4; // delegates (vec length)
+
+pub const DELEGATE_REGISTRY_ENTRY_SIZE: usize = 1 // role
++ 32  // delegate
++ 32; // approver
+
+/// Entry of the `DelegateRegistry`, identifying a `MetadataDelegateRecord`.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct DelegateRegistryEntry {
+    /// Role of the delegate.
+    pub role: MetadataDelegateRole,
+    /// Address of the delegate.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub delegate: Pubkey,
+    /// Address of the update authority that approved the delegate.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub approver: Pubkey,
+}
+
+/// The `DelegateRegistry` account lists the active metadata delegates of a mint.
+///
+/// The registry is created on the first `Delegate` instruction that includes it and is
+/// kept up to date by the `Delegate` and `Revoke` instructions, which allows wallets and
+/// indexers to discover the delegates of an asset without scanning the program accounts.
+/// The size of the account changes as delegates are added and removed. The seeds for the
+/// PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"delegate_registry"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct DelegateRegistry {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Active metadata delegates.
+    pub delegates: Vec<DelegateRegistryEntry>,
+}
+
+impl Default for DelegateRegistry {
+    fn default() -> Self {
+        Self {
+            key: Key::DelegateRegistry,
+            bump: 255,
+            mint: Pubkey::default(),
+            delegates: vec![],
+        }
+    }
+}
+
+impl TokenMetadataAccount for DelegateRegistry {
+    fn key() -> Key {
+        Key::DelegateRegistry
+    }
+
+    fn size() -> usize {
+        DELEGATE_REGISTRY_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+}
+
+impl DelegateRegistry {
+    pub fn from_bytes(data: &[u8]) -> Result<DelegateRegistry, ProgramError> {
+        // the size of the account depends on the number of delegates, so only
+        // the key and the minimum size are checked
+        if data.len() < DELEGATE_REGISTRY_SIZE || Key::of(data) != Some(Key::DelegateRegistry) {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let registry: DelegateRegistry = try_from_slice_unchecked(data)?;
+        Ok(registry)
+    }
+
+    /// Returns the size of the serialized registry.
+    pub fn serialized_size(&self) -> usize {
+        DELEGATE_REGISTRY_SIZE + self.delegates.len() * DELEGATE_REGISTRY_ENTRY_SIZE
+    }
+
+    /// Adds a delegate to the registry. Returns `false` if the delegate was already
+    /// registered.
+    pub fn add(&mut self, entry: DelegateRegistryEntry) -> bool {
+        if self.delegates.contains(&entry) {
+            return false;
+        }
+
+        self.delegates.push(entry);
+        true
+    }
+
+    /// Removes a delegate from the registry. Returns `false` if the delegate was not
+    /// registered.
+    pub fn remove(&mut self, entry: &DelegateRegistryEntry) -> bool {
+        let length = self.delegates.len();
+        self.delegates.retain(|e| e != entry);
+
+        self.delegates.len() != length
+    }
+
+    /// Saves the registry to the specified account, resizing the account to fit the
+    /// current list of delegates.
+    pub fn save<'a>(
+        &self,
+        account_info: &'a AccountInfo<'a>,
+        payer_info: &'a AccountInfo<'a>,
+        system_program_info: &'a AccountInfo<'a>,
+    ) -> ProgramResult {
+        let required_size = self.serialized_size();
+
+        if account_info.data_len() != required_size {
+            resize_or_reallocate_account_raw(
+                account_info,
+                payer_info,
+                system_program_info,
+                required_size,
+            )?;
+        }
+
+        let mut account_data = account_info.data.borrow_mut();
+        let mut storage = &mut account_data[..required_size];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_add_and_remove() {
+        let entry = DelegateRegistryEntry {
+            role: MetadataDelegateRole::Data,
+            delegate: Pubkey::new_unique(),
+            approver: Pubkey::new_unique(),
+        };
+        let other = DelegateRegistryEntry {
+            role: MetadataDelegateRole::Collection,
+            ..entry
+        };
+
+        let mut registry = DelegateRegistry {
+            mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert!(registry.add(entry));
+        assert!(!registry.add(entry));
+        assert!(registry.add(other));
+        assert_eq!(registry.delegates, vec![entry, other]);
+
+        let data = registry.try_to_vec().unwrap();
+        assert_eq!(data.len(), registry.serialized_size());
+        assert_eq!(DelegateRegistry::from_bytes(&data).unwrap(), registry);
+
+        assert!(registry.remove(&entry));
+        assert!(!registry.remove(&entry));
+        assert_eq!(registry.delegates, vec![other]);
+        assert_eq!(
+            registry.serialized_size(),
+            DELEGATE_REGISTRY_SIZE + DELEGATE_REGISTRY_ENTRY_SIZE
+        );
+    }
+}
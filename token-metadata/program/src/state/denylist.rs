@@ -0,0 +1,76 @@
+use super::*;
+
+pub const DENYLISTED_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 32; // payer
+
+/// The `Denylisted` account marks an asset as stolen on the denylist.
+///
+/// Unlike the informational flags of the `Flagged` account, the denylist restricts the asset:
+/// transfers of verified items of collections that opted in to the denylist
+/// (`CollectionDetails::V2`) fail while the account exists. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"denylisted"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct Denylisted {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Payer of the account, which receives the rent when the asset is removed from the
+    /// denylist.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub payer: Pubkey,
+}
+
+impl Default for Denylisted {
+    fn default() -> Self {
+        Self {
+            key: Key::Denylisted,
+            bump: 255,
+            mint: Pubkey::default(),
+            payer: Pubkey::default(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for Denylisted {
+    fn key() -> Key {
+        Key::Denylisted
+    }
+
+    fn size() -> usize {
+        DENYLISTED_SIZE
+    }
+}
+
+impl Denylisted {
+    pub fn from_bytes(data: &[u8]) -> Result<Denylisted, ProgramError> {
+        let denylisted: Denylisted =
+            try_from_slice_checked(data, Key::Denylisted, DENYLISTED_SIZE)?;
+        Ok(denylisted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let denylisted = Denylisted::default();
+        let mut buf = Vec::new();
+        denylisted.serialize(&mut buf).unwrap();
+
+        assert_eq!(buf.len(), DENYLISTED_SIZE);
+        assert_eq!(Denylisted::from_bytes(&buf).unwrap(), denylisted);
+    }
+}
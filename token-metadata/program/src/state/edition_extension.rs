@@ -0,0 +1,115 @@
+use super::*;
+
+pub const MASTER_EDITION_EXTENSION_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 17; // print window (optional start and end timestamps)
+
+/// The `MasterEditionExtension` account stores additional print settings of a master edition.
+///
+/// The account is created by the update authority of the asset and read by the `Print`
+/// instruction. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"edition"`
+/// 5. `"extension"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MasterEditionExtension {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint of the master edition.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Unix timestamps (start and end, both inclusive) during which editions can be
+    /// printed. When not set, printing is not time restricted.
+    pub print_window: Option<(i64, i64)>,
+}
+
+impl Default for MasterEditionExtension {
+    fn default() -> Self {
+        Self {
+            key: Key::MasterEditionExtension,
+            bump: 255,
+            mint: Pubkey::default(),
+            print_window: None,
+        }
+    }
+}
+
+impl TokenMetadataAccount for MasterEditionExtension {
+    fn key() -> Key {
+        Key::MasterEditionExtension
+    }
+
+    fn size() -> usize {
+        MASTER_EDITION_EXTENSION_SIZE
+    }
+}
+
+impl MasterEditionExtension {
+    pub fn from_bytes(data: &[u8]) -> Result<MasterEditionExtension, ProgramError> {
+        let extension: MasterEditionExtension = try_from_slice_checked(
+            data,
+            Key::MasterEditionExtension,
+            MASTER_EDITION_EXTENSION_SIZE,
+        )?;
+        Ok(extension)
+    }
+
+    /// Checks that the start of the print window is not after its end.
+    pub fn validate_print_window(print_window: Option<(i64, i64)>) -> ProgramResult {
+        match print_window {
+            Some((start, end)) if start > end => Err(MetadataError::InvalidPrintWindow.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether editions can be printed at the specified `timestamp`.
+    pub fn is_print_window_open(&self, timestamp: i64) -> bool {
+        match self.print_window {
+            Some((start, end)) => start <= timestamp && timestamp <= end,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn print_window_bounds() {
+        let mut extension = MasterEditionExtension::default();
+        assert!(extension.is_print_window_open(i64::MIN));
+
+        extension.print_window = Some((100, 200));
+        assert!(!extension.is_print_window_open(99));
+        assert!(extension.is_print_window_open(100));
+        assert!(extension.is_print_window_open(200));
+        assert!(!extension.is_print_window_open(201));
+
+        assert!(MasterEditionExtension::validate_print_window(Some((100, 100))).is_ok());
+        assert_eq!(
+            MasterEditionExtension::validate_print_window(Some((200, 100))).unwrap_err(),
+            MetadataError::InvalidPrintWindow.into()
+        );
+    }
+
+    #[test]
+    fn serialized_size_matches() {
+        let extension = MasterEditionExtension {
+            print_window: Some((0, 1)),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        extension.serialize(&mut buf).unwrap();
+
+        assert_eq!(buf.len(), MASTER_EDITION_EXTENSION_SIZE);
+        assert_eq!(MasterEditionExtension::from_bytes(&buf).unwrap(), extension);
+    }
+}
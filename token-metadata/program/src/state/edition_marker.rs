@@ -91,6 +91,18 @@ impl EditionMarker {
         self.ledger[index] |= mask;
         Ok(())
     }
+
+    pub fn remove_edition(&mut self, edition: u64) -> ProgramResult {
+        let (index, mask) = EditionMarker::get_index_and_mask(edition)?;
+        // bitwise and with the inverted mask to clear our position
+        self.ledger[index] &= !mask;
+        Ok(())
+    }
+
+    /// Returns `true` when none of the editions of the marker are taken.
+    pub fn is_empty(&self) -> bool {
+        self.ledger.iter().all(|i| *i == 0)
+    }
 }
 
 #[cfg(test)]
@@ -134,6 +146,28 @@ mod tests {
         assert_eq!(data, expected_data);
     }
 
+    #[test]
+    fn insert_and_remove_edition() {
+        let mut marker = EditionMarker::default();
+        assert!(marker.is_empty());
+
+        marker.insert_edition(1).unwrap();
+        marker.insert_edition(9).unwrap();
+        assert!(marker.edition_taken(1).unwrap());
+        assert!(marker.edition_taken(9).unwrap());
+
+        marker.remove_edition(1).unwrap();
+        assert!(!marker.edition_taken(1).unwrap());
+        assert!(marker.edition_taken(9).unwrap());
+
+        // removing an edition that is not taken is a no-op
+        marker.remove_edition(1).unwrap();
+        assert!(!marker.edition_taken(1).unwrap());
+
+        marker.remove_edition(9).unwrap();
+        assert!(marker.is_empty());
+    }
+
     #[test]
     fn deserializing_wrong_account_type_fails() {
         let wrong_type = Metadata::default();
@@ -1,7 +1,20 @@
+use solana_program::borsh::try_from_slice_unchecked;
+
 use super::*;
 
 pub const ESCROW_POSTFIX: &str = "escrow";
 
+pub const ESCROW_LEDGER_POSTFIX: &str = "ledger";
+
+/// Size of an empty ledger.
+pub const ESCROW_LEDGER_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // escrow
++ 4; // entries (vec length)
+
+pub const ESCROW_LEDGER_ENTRY_SIZE: usize = 32 // mint
++ 8; // amount
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
@@ -39,10 +52,178 @@ impl TokenMetadataAccount for TokenOwnedEscrow {
     }
 
     fn is_correct_account_type(data: &[u8], data_type: Key, _data_size: usize) -> bool {
-        let key: Option<Key> = Key::from_u8(data[0]);
-        match key {
+        match Key::of(data) {
             Some(key) => key == data_type || key == Key::Uninitialized,
             None => false,
         }
     }
 }
+
+/// Entry of the `EscrowLedger`, recording the amount of tokens of a mint held by the escrow.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct EscrowLedgerEntry {
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Amount of tokens held by the escrow.
+    pub amount: u64,
+}
+
+/// The `EscrowLedger` account records the tokens held by a token-owned escrow.
+///
+/// The ledger is created on the first `EscrowDeposit` into the escrow and is kept up to date
+/// by the `EscrowDeposit` and `EscrowTransferOut` instructions, which allows programs to read
+/// the contents of an asset on-chain. Each entry records the balance of the escrow token
+/// account of a mint after the last deposit or withdrawal; mints with no balance are removed.
+/// The size of the account changes as mints are added and removed. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. escrow id
+/// 4. `"ledger"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct EscrowLedger {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the token-owned escrow.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub escrow: Pubkey,
+    /// Tokens held by the escrow.
+    pub entries: Vec<EscrowLedgerEntry>,
+}
+
+impl Default for EscrowLedger {
+    fn default() -> Self {
+        Self {
+            key: Key::EscrowLedger,
+            bump: 255,
+            escrow: Pubkey::default(),
+            entries: vec![],
+        }
+    }
+}
+
+impl TokenMetadataAccount for EscrowLedger {
+    fn key() -> Key {
+        Key::EscrowLedger
+    }
+
+    fn size() -> usize {
+        ESCROW_LEDGER_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+}
+
+impl EscrowLedger {
+    pub fn from_bytes(data: &[u8]) -> Result<EscrowLedger, ProgramError> {
+        // the size of the account depends on the number of entries, so only
+        // the key and the minimum size are checked
+        if data.len() < ESCROW_LEDGER_SIZE || Key::of(data) != Some(Key::EscrowLedger) {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let ledger: EscrowLedger = try_from_slice_unchecked(data)?;
+        Ok(ledger)
+    }
+
+    /// Returns the size of the serialized ledger.
+    pub fn serialized_size(&self) -> usize {
+        ESCROW_LEDGER_SIZE + self.entries.len() * ESCROW_LEDGER_ENTRY_SIZE
+    }
+
+    /// Returns the amount of tokens of the mint held by the escrow.
+    pub fn amount(&self, mint: &Pubkey) -> u64 {
+        self.entries
+            .iter()
+            .find(|entry| entry.mint == *mint)
+            .map(|entry| entry.amount)
+            .unwrap_or(0)
+    }
+
+    /// Records the amount of tokens of the mint held by the escrow, removing the mint from
+    /// the ledger when the amount is zero.
+    pub fn set_amount(&mut self, mint: &Pubkey, amount: u64) {
+        match self.entries.iter_mut().find(|entry| entry.mint == *mint) {
+            Some(_) if amount == 0 => self.entries.retain(|entry| entry.mint != *mint),
+            Some(entry) => entry.amount = amount,
+            None if amount > 0 => self.entries.push(EscrowLedgerEntry {
+                mint: *mint,
+                amount,
+            }),
+            None => (),
+        }
+    }
+
+    /// Saves the ledger to the specified account, resizing the account to fit the
+    /// current list of entries.
+    pub fn save<'a>(
+        &self,
+        account_info: &'a AccountInfo<'a>,
+        payer_info: &'a AccountInfo<'a>,
+        system_program_info: &'a AccountInfo<'a>,
+    ) -> ProgramResult {
+        let required_size = self.serialized_size();
+
+        if account_info.data_len() != required_size {
+            resize_or_reallocate_account_raw(
+                account_info,
+                payer_info,
+                system_program_info,
+                required_size,
+            )?;
+        }
+
+        let mut account_data = account_info.data.borrow_mut();
+        let mut storage = &mut account_data[..required_size];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_set_amount() {
+        let mint = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let mut ledger = EscrowLedger {
+            escrow: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        ledger.set_amount(&mint, 0);
+        assert!(ledger.entries.is_empty());
+
+        ledger.set_amount(&mint, 10);
+        ledger.set_amount(&other, 1);
+        assert_eq!(ledger.amount(&mint), 10);
+        assert_eq!(ledger.amount(&other), 1);
+
+        // partial withdrawal
+        ledger.set_amount(&mint, 4);
+        assert_eq!(ledger.amount(&mint), 4);
+
+        let data = ledger.try_to_vec().unwrap();
+        assert_eq!(data.len(), ledger.serialized_size());
+        assert_eq!(EscrowLedger::from_bytes(&data).unwrap(), ledger);
+
+        ledger.set_amount(&other, 0);
+        assert_eq!(ledger.amount(&other), 0);
+        assert_eq!(ledger.entries, vec![EscrowLedgerEntry { mint, amount: 4 }]);
+        assert_eq!(
+            ledger.serialized_size(),
+            ESCROW_LEDGER_SIZE + ESCROW_LEDGER_ENTRY_SIZE
+        );
+    }
+}
@@ -0,0 +1,7 @@
+/// Protocol fee (in lamports) charged when creating the metadata account of an asset.
+///
+/// The fee is paid into the fee vault (pda of `["metadata", program id, "fee_vault"]`)
+/// when the vault is included on the create instruction, in which case the `fee_flag`
+/// of the metadata is set. Collected fees are withdrawn by the protocol authority with
+/// `CollectFees`.
+pub const CREATE_FEE: u64 = 10_000_000;
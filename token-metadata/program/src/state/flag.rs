@@ -0,0 +1,150 @@
+use super::*;
+
+pub const FLAGGED_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 2   // flags
++ 32; // payer
+
+/// Reasons why an asset can be flagged.
+///
+/// Each reason maps to a bit on the `Flagged.flags` bitmask, which allows the same
+/// asset to be flagged for multiple reasons at the same time.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum FlagReason {
+    /// Asset is part of a known scam or phishing campaign.
+    Scam,
+    /// Asset infringes on copyright.
+    Copyright,
+    /// Asset is considered spam.
+    Spam,
+    /// Asset impersonates another project.
+    Impersonation,
+}
+
+impl FlagReason {
+    /// All available reasons, in bit order.
+    pub const ALL: [FlagReason; 4] = [
+        FlagReason::Scam,
+        FlagReason::Copyright,
+        FlagReason::Spam,
+        FlagReason::Impersonation,
+    ];
+
+    /// Returns the bitmask value of the reason.
+    pub fn mask(&self) -> u16 {
+        1 << (*self as u16)
+    }
+}
+
+/// The `Flagged` account stores the flags set on an asset by the protocol authority.
+///
+/// Flags are informational only: they do not block any instruction on the asset, but
+/// wallets and marketplaces can read them to warn users. Stolen assets are tracked on the
+/// denylist (`Denylisted` account) instead. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"flagged"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct Flagged {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Bitmask of the active `FlagReason`s.
+    pub flags: u16,
+    /// Payer of the account, which receives the rent when the last flag is cleared.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub payer: Pubkey,
+}
+
+impl Default for Flagged {
+    fn default() -> Self {
+        Self {
+            key: Key::Flagged,
+            bump: 255,
+            mint: Pubkey::default(),
+            flags: 0,
+            payer: Pubkey::default(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for Flagged {
+    fn key() -> Key {
+        Key::Flagged
+    }
+
+    fn size() -> usize {
+        FLAGGED_SIZE
+    }
+}
+
+impl Flagged {
+    pub fn from_bytes(data: &[u8]) -> Result<Flagged, ProgramError> {
+        let flagged: Flagged = try_from_slice_checked(data, Key::Flagged, FLAGGED_SIZE)?;
+        Ok(flagged)
+    }
+
+    /// Sets the flag for the specified reason.
+    pub fn set(&mut self, reason: FlagReason) {
+        self.flags |= reason.mask();
+    }
+
+    /// Clears the flag for the specified reason.
+    pub fn clear(&mut self, reason: FlagReason) {
+        self.flags &= !reason.mask();
+    }
+
+    /// Checks whether the flag for the specified reason is set.
+    pub fn is_set(&self, reason: FlagReason) -> bool {
+        self.flags & reason.mask() != 0
+    }
+
+    /// Returns the list of active reasons.
+    pub fn reasons(&self) -> Vec<FlagReason> {
+        FlagReason::ALL
+            .iter()
+            .filter(|reason| self.is_set(**reason))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_clear_reasons() {
+        let mut flagged = Flagged::default();
+        assert!(flagged.reasons().is_empty());
+
+        flagged.set(FlagReason::Scam);
+        flagged.set(FlagReason::Spam);
+        assert!(flagged.is_set(FlagReason::Scam));
+        assert!(!flagged.is_set(FlagReason::Copyright));
+        assert_eq!(flagged.reasons(), vec![FlagReason::Scam, FlagReason::Spam]);
+
+        flagged.clear(FlagReason::Scam);
+        assert_eq!(flagged.reasons(), vec![FlagReason::Spam]);
+    }
+
+    #[test]
+    fn serialized_size_matches() {
+        let flagged = Flagged::default();
+        let mut buf = Vec::new();
+        flagged.serialize(&mut buf).unwrap();
+
+        assert_eq!(buf.len(), FLAGGED_SIZE);
+        assert_eq!(Flagged::from_bytes(&buf).unwrap(), flagged);
+    }
+}
@@ -0,0 +1,155 @@
+use super::*;
+
+pub const LEGACY_LAYOUT_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // collection mint
++ 1; // enabled
+
+pub const PROGRAMMABLE_CONFIG_EXTENSION_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 35; // programmable config
+
+/// The `LegacyLayout` account indicates whether the verified items of a collection store
+/// their programmable config on a `ProgrammableConfigExtension` account instead of the
+/// metadata, so parsers unaware of the programmable config can read their metadata.
+///
+/// The layout of each item is switched by the `SyncLayout` instruction. The seeds for the
+/// PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. collection mint id
+/// 4. `"legacy_layout"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct LegacyLayout {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the collection mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Indicates whether the items of the collection use the legacy layout.
+    pub enabled: bool,
+}
+
+impl Default for LegacyLayout {
+    fn default() -> Self {
+        Self {
+            key: Key::LegacyLayout,
+            bump: 255,
+            collection_mint: Pubkey::default(),
+            enabled: false,
+        }
+    }
+}
+
+impl TokenMetadataAccount for LegacyLayout {
+    fn key() -> Key {
+        Key::LegacyLayout
+    }
+
+    fn size() -> usize {
+        LEGACY_LAYOUT_SIZE
+    }
+}
+
+impl LegacyLayout {
+    pub fn from_bytes(data: &[u8]) -> Result<LegacyLayout, ProgramError> {
+        let layout: LegacyLayout =
+            try_from_slice_checked(data, Key::LegacyLayout, LEGACY_LAYOUT_SIZE)?;
+        Ok(layout)
+    }
+}
+
+/// The `ProgrammableConfigExtension` account stores the programmable config of an asset
+/// that uses the legacy layout (see `LegacyLayout`).
+///
+/// While the extension is in use, the programmable config on the metadata is `None` and
+/// the metadata has the `programmable_config_in_extension` flag set; readers must merge
+/// both accounts (see `Metadata::merge_programmable_config`). The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"programmable_config"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct ProgrammableConfigExtension {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Programmable config of the asset.
+    pub programmable_config: Option<ProgrammableConfig>,
+}
+
+impl Default for ProgrammableConfigExtension {
+    fn default() -> Self {
+        Self {
+            key: Key::ProgrammableConfigExtension,
+            bump: 255,
+            mint: Pubkey::default(),
+            programmable_config: None,
+        }
+    }
+}
+
+impl TokenMetadataAccount for ProgrammableConfigExtension {
+    fn key() -> Key {
+        Key::ProgrammableConfigExtension
+    }
+
+    fn size() -> usize {
+        PROGRAMMABLE_CONFIG_EXTENSION_SIZE
+    }
+}
+
+impl ProgrammableConfigExtension {
+    pub fn from_bytes(data: &[u8]) -> Result<ProgrammableConfigExtension, ProgramError> {
+        let extension: ProgrammableConfigExtension = try_from_slice_checked(
+            data,
+            Key::ProgrammableConfigExtension,
+            PROGRAMMABLE_CONFIG_EXTENSION_SIZE,
+        )?;
+        Ok(extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let layout = LegacyLayout {
+            collection_mint: Pubkey::new_unique(),
+            enabled: true,
+            ..Default::default()
+        };
+        let data = layout.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), LEGACY_LAYOUT_SIZE);
+        assert_eq!(LegacyLayout::from_bytes(&data).unwrap(), layout);
+
+        let extension = ProgrammableConfigExtension {
+            mint: Pubkey::new_unique(),
+            programmable_config: Some(ProgrammableConfig::V1 {
+                rule_set: Some(Pubkey::new_unique()),
+            }),
+            ..Default::default()
+        };
+        let data = extension.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), PROGRAMMABLE_CONFIG_EXTENSION_SIZE);
+        assert_eq!(
+            ProgrammableConfigExtension::from_bytes(&data).unwrap(),
+            extension
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use borsh::maybestd::io::{Result as IoResult, Write};
+use solana_program::msg;
+
 use super::*;
 use crate::{
     assertions::{
@@ -7,7 +10,7 @@ use crate::{
     instruction::{
         CollectionDetailsToggle, CollectionToggle, MetadataDelegateRole, RuleSetToggle, UpdateArgs,
     },
-    utils::{clean_write_metadata, puff_out_data_fields},
+    utils::puff_out_data_fields,
 };
 
 pub const MAX_NAME_LENGTH: usize = 32;
@@ -26,9 +29,40 @@ pub const MAX_METADATA_LEN: usize = 1 // key
 + 2              // token standard
 + 34             // collection
 + 18             // uses
-+ 10             // collection details
++ 11             // collection details (V2 uses 1 byte of the original padding)
 + 33             // programmable config
-+ 75; // Padding
++ METADATA_EXTENSION_LEN
++ 1; // Padding
+
+/// Maximum size of the metadata extension, stored in the 74 bytes that remain of the
+/// original 75 bytes of padding; the metadata account keeps its original size, so new
+/// fields must fit in the remaining padding or be stored on a separate account.
+pub const METADATA_EXTENSION_LEN: usize = 4 // marker
++ 1              // flags
++ 33             // permanent delegate
++ 2              // overflow creators share
++ 33; // uri hash
+
+/// Marker of the version 1 of the metadata extension.
+///
+/// The padding of accounts written before the extension can hold arbitrary data, so the
+/// extension fields are only read when the padding starts with a known marker. A single
+/// version byte is too likely to be matched by junk, hence the multi-byte marker.
+pub const METADATA_EXTENSION_V1: [u8; 4] = *b"MDX1";
+
+// Flags of the metadata extension.
+const INHERIT_ROYALTIES_FLAG: u8 = 1 << 0;
+const USE_DEFAULT_RULE_SET_FLAG: u8 = 1 << 1;
+const UNVERIFY_CREATORS_ON_AUTHORITY_CHANGE_FLAG: u8 = 1 << 2;
+const FEE_FLAG: u8 = 1 << 3;
+const PROGRAMMABLE_CONFIG_IN_EXTENSION_FLAG: u8 = 1 << 4;
+const RATE_LIMITED_USES_FLAG: u8 = 1 << 5;
+const EXTENSION_FLAGS: u8 = INHERIT_ROYALTIES_FLAG
+    | USE_DEFAULT_RULE_SET_FLAG
+    | UNVERIFY_CREATORS_ON_AUTHORITY_CHANGE_FLAG
+    | FEE_FLAG
+    | PROGRAMMABLE_CONFIG_IN_EXTENSION_FLAG
+    | RATE_LIMITED_USES_FLAG;
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -43,7 +77,7 @@ pub const MAX_DATA_SIZE: usize = 4
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
-#[derive(Clone, BorshSerialize, Debug, PartialEq, Eq, ShankAccount)]
+#[derive(Clone, Debug, PartialEq, Eq, ShankAccount)]
 pub struct Metadata {
     /// Account discriminator.
     pub key: Key,
@@ -71,9 +105,79 @@ pub struct Metadata {
     pub collection_details: Option<CollectionDetails>,
     /// Programmable Config
     pub programmable_config: Option<ProgrammableConfig>,
+    /// Indicates whether the royalties (seller fee basis points and creators) are
+    /// read from the collection parent.
+    pub inherit_royalties: bool,
+    /// Indicates whether the protocol default rule set of the token standard applies
+    /// when the asset does not have a rule set.
+    pub use_default_rule_set: bool,
+    /// Permanent delegate set at creation, which retains the right to transfer the
+    /// asset regardless of the holder. Cannot be changed once set.
+    #[cfg_attr(
+        feature = "serde-feature",
+        serde(
+            deserialize_with = "deser_option_pubkey",
+            serialize_with = "ser_option_pubkey"
+        )
+    )]
+    pub permanent_delegate: Option<Pubkey>,
+    /// Indicates whether the verified creators are unverified when the update authority
+    /// changes, so a new authority cannot keep the verification of the original creators.
+    pub unverify_creators_on_authority_change: bool,
+    /// Indicates whether the protocol create fee was paid for the asset.
+    pub fee_flag: bool,
+    /// Total share of the creators stored on the `CreatorsOverflow` account of the asset
+    /// (`None` when the asset does not have overflow creators).
+    pub overflow_creators_share: Option<u8>,
+    /// Indicates whether the programmable config is stored on the `ProgrammableConfigExtension`
+    /// account of the asset (legacy layout), in which case `programmable_config` is `None`.
+    pub programmable_config_in_extension: bool,
+    /// Hash of the off-chain JSON at the URI, attested by a creator through `VerifyContent`.
+    /// The hash is cleared when the URI changes.
+    pub uri_hash: Option<[u8; 32]>,
+    /// Indicates whether the uses of the asset are rate limited by its `UsesV2` account.
+    pub rate_limited_uses: bool,
 }
 
 impl Metadata {
+    /// Packs the boolean fields of the metadata extension.
+    fn extension_flags(&self) -> u8 {
+        [
+            (self.inherit_royalties, INHERIT_ROYALTIES_FLAG),
+            (self.use_default_rule_set, USE_DEFAULT_RULE_SET_FLAG),
+            (
+                self.unverify_creators_on_authority_change,
+                UNVERIFY_CREATORS_ON_AUTHORITY_CHANGE_FLAG,
+            ),
+            (self.fee_flag, FEE_FLAG),
+            (
+                self.programmable_config_in_extension,
+                PROGRAMMABLE_CONFIG_IN_EXTENSION_FLAG,
+            ),
+            (self.rate_limited_uses, RATE_LIMITED_USES_FLAG),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(0, |flags, (_, flag)| flags | flag)
+    }
+
+    /// Unpacks the boolean fields of the metadata extension.
+    pub(crate) fn set_extension_flags(&mut self, flags: u8) {
+        self.inherit_royalties = flags & INHERIT_ROYALTIES_FLAG != 0;
+        self.use_default_rule_set = flags & USE_DEFAULT_RULE_SET_FLAG != 0;
+        self.unverify_creators_on_authority_change =
+            flags & UNVERIFY_CREATORS_ON_AUTHORITY_CHANGE_FLAG != 0;
+        self.fee_flag = flags & FEE_FLAG != 0;
+        self.programmable_config_in_extension = flags & PROGRAMMABLE_CONFIG_IN_EXTENSION_FLAG != 0;
+        self.rate_limited_uses = flags & RATE_LIMITED_USES_FLAG != 0;
+    }
+
+    /// Indicates whether the flags of a metadata extension are known to this version of
+    /// the program.
+    pub(crate) fn is_valid_extension_flags(flags: u8) -> bool {
+        flags & !EXTENSION_FLAGS == 0
+    }
+
     pub fn save(&self, data: &mut [u8]) -> Result<(), BorshError> {
         let mut bytes = Vec::with_capacity(MAX_METADATA_LEN);
         BorshSerialize::serialize(&self, &mut bytes)?;
@@ -81,11 +185,40 @@ impl Metadata {
         Ok(())
     }
 
+    /// Sets the update authority, unverifying the creators when the metadata has the
+    /// `unverify_creators_on_authority_change` policy enabled.
+    ///
+    /// A creator matching the new update authority keeps its verification.
+    pub(crate) fn set_update_authority(&mut self, authority: Pubkey) {
+        if self.unverify_creators_on_authority_change && self.update_authority != authority {
+            if let Some(creators) = self.data.creators.as_mut() {
+                for creator in creators
+                    .iter_mut()
+                    .filter(|creator| creator.verified && creator.address != authority)
+                {
+                    creator.verified = false;
+                    msg!("Creator unverified: {}", creator.address);
+                }
+            }
+        }
+
+        self.update_authority = authority;
+    }
+
+    /// Sets the data, clearing the attested hash of the off-chain JSON when the URI changes.
+    pub(crate) fn set_data(&mut self, data: Data) {
+        if self.data.uri.trim_matches(char::from(0)) != data.uri.trim_matches(char::from(0)) {
+            self.uri_hash = None;
+        }
+
+        self.data = data;
+    }
+
+    /// Applies an update to the metadata, which the caller writes to the account.
     pub(crate) fn update_v1<'a>(
         &mut self,
         args: UpdateArgs,
         update_authority: &AccountInfo<'a>,
-        metadata: &AccountInfo<'a>,
         token: Option<TokenAccount>,
         token_standard: Option<TokenStandard>,
         authority_type: AuthorityType,
@@ -100,6 +233,9 @@ impl Metadata {
             new_update_authority,
             rule_set,
             collection_details,
+            inherit_royalties,
+            use_default_rule_set,
+            unverify_creators_on_authority_change,
             ..
         } = args;
 
@@ -116,7 +252,9 @@ impl Metadata {
             }
         };
 
-        if matches!(authority_type, AuthorityType::Metadata) {
+        if matches!(authority_type, AuthorityType::Metadata)
+            || matches!(delegate_role, Some(MetadataDelegateRole::Data))
+        {
             if let Some(data) = data {
                 if !self.is_mutable {
                     return Err(MetadataError::DataIsImmutable.into());
@@ -129,9 +267,21 @@ impl Metadata {
                     false,
                     update_authority.is_signer,
                 )?;
-                self.data = data;
+                self.set_data(data);
             }
+        }
+
+        if (matches!(authority_type, AuthorityType::Metadata)
+            || matches!(delegate_role, Some(MetadataDelegateRole::Uses)))
+            && uses.is_some()
+        {
+            let uses_option = uses.to_option();
+            // If already None leave it as None.
+            assert_valid_use(&uses_option, &self.uses)?;
+            self.uses = uses_option;
+        }
 
+        if matches!(authority_type, AuthorityType::Metadata) {
             // if the Collection data is 'Set', only allow updating if it is unverified
             // or if it exactly matches the existing collection info; if the Collection data
             // is 'Clear', then only set to 'None' if it is unverified.
@@ -154,15 +304,12 @@ impl Metadata {
                 CollectionToggle::None => { /* nothing to do */ }
             }
 
-            if uses.is_some() {
-                let uses_option = uses.to_option();
-                // If already None leave it as None.
-                assert_valid_use(&uses_option, &self.uses)?;
-                self.uses = uses_option;
+            if let Some(unverify_creators) = unverify_creators_on_authority_change {
+                self.unverify_creators_on_authority_change = unverify_creators;
             }
 
             if let Some(authority) = new_update_authority {
-                self.update_authority = authority;
+                self.set_update_authority(authority);
             }
 
             if let Some(primary_sale) = primary_sale_happened {
@@ -184,13 +331,34 @@ impl Metadata {
             }
 
             if let CollectionDetailsToggle::Set(collection_details) = collection_details {
-                // only unsized collections can have the size set, and only once.
-                if self.collection_details.is_some() {
-                    return Err(MetadataError::SizedCollection.into());
-                }
+                let collection_details = match (&self.collection_details, collection_details) {
+                    // only unsized collections can have the size set, and only once.
+                    (None, collection_details) => collection_details,
+                    // sized collections can opt in or out of the stolen asset denylist, which
+                    // keeps their current size
+                    (
+                        Some(current),
+                        CollectionDetails::V2 {
+                            enforce_denylist, ..
+                        },
+                    ) => CollectionDetails::V2 {
+                        size: current.size().ok_or(MetadataError::UnsizedCollection)?,
+                        enforce_denylist,
+                    },
+                    _ => return Err(MetadataError::SizedCollection.into()),
+                };
 
                 self.collection_details = Some(collection_details);
             }
+
+            if let Some(inherit_royalties) = inherit_royalties {
+                // royalties can only be inherited from a collection parent
+                if inherit_royalties && self.collection.is_none() {
+                    return Err(MetadataError::CollectionNotFound.into());
+                }
+
+                self.inherit_royalties = inherit_royalties;
+            }
         }
 
         if matches!(authority_type, AuthorityType::Metadata)
@@ -214,41 +382,205 @@ impl Metadata {
                     return Err(MetadataError::CannotUpdateAssetWithDelegate.into());
                 }
 
+                // A new rule set is subject to the proposal delay (see `ProposeRuleSet`),
+                // so only clearing the rule set takes effect immediately.
+                if rule_set.is_set() {
+                    return Err(MetadataError::RuleSetChangeRequiresProposal.into());
+                }
+
+                self.assert_current_layout()?;
+
                 self.programmable_config =
                     rule_set.to_option().map(|rule_set| ProgrammableConfig::V1 {
                         rule_set: Some(rule_set),
                     });
             }
+
+            // opting in (or out) of the default rule set follows the same requirements
+            // as updating the rule set
+            if let Some(use_default_rule_set) = use_default_rule_set {
+                if token_standard != TokenStandard::ProgrammableNonFungible {
+                    return Err(MetadataError::InvalidTokenStandard.into());
+                }
+
+                let token = token.ok_or(MetadataError::MissingTokenAccount)?;
+
+                if token.delegate.is_some() {
+                    return Err(MetadataError::CannotUpdateAssetWithDelegate.into());
+                }
+
+                self.use_default_rule_set = use_default_rule_set;
+            }
+        }
+
+        // the programmable config of assets using the legacy layout stays on the extension
+        if self.programmable_config_in_extension {
+            self.programmable_config = None;
         }
 
         puff_out_data_fields(self);
-        clean_write_metadata(self, metadata)?;
+
+        Ok(())
+    }
+
+    /// Returns the creators of the asset, including the creators stored on its
+    /// `CreatorsOverflow` account.
+    ///
+    /// The `overflow` account is required when the asset has overflow creators and is
+    /// ignored otherwise.
+    pub fn all_creators(
+        &self,
+        overflow: Option<&CreatorsOverflow>,
+    ) -> Result<Option<Vec<Creator>>, ProgramError> {
+        if self.overflow_creators_share.is_none() {
+            return Ok(self.data.creators.clone());
+        }
+
+        let overflow = overflow.ok_or(MetadataError::MissingCreatorsOverflow)?;
+
+        if overflow.mint != self.mint {
+            return Err(MetadataError::InvalidCreatorsOverflow.into());
+        }
+
+        let mut creators = self.data.creators.clone().unwrap_or_default();
+        creators.extend(overflow.creators.iter().cloned());
+
+        Ok(Some(creators))
+    }
+
+    /// Returns the royalties (seller fee basis points and creators) that apply to the asset.
+    ///
+    /// When the asset inherits its royalties, the values are read from the `parent` metadata,
+    /// which must be the verified collection of the asset; otherwise the `parent` is ignored.
+    /// The `overflow` account is the creators overflow of the metadata the royalties are
+    /// read from.
+    pub fn effective_royalties(
+        &self,
+        parent: &Metadata,
+        overflow: Option<&CreatorsOverflow>,
+    ) -> Result<(BasisPoints, Option<Vec<Creator>>), ProgramError> {
+        if !self.inherit_royalties {
+            return Ok((
+                self.data.seller_fee_basis_points,
+                self.all_creators(overflow)?,
+            ));
+        }
+
+        let collection = self
+            .collection
+            .as_ref()
+            .ok_or(MetadataError::CollectionNotFound)?;
+
+        if collection.key != parent.mint {
+            return Err(MetadataError::NotAMemberOfCollection.into());
+        }
+
+        if !collection.verified {
+            return Err(MetadataError::NotVerifiedMemberOfCollection.into());
+        }
+
+        Ok((
+            parent.data.seller_fee_basis_points,
+            parent.all_creators(overflow)?,
+        ))
+    }
+
+    /// Returns the programmable config that applies to the asset.
+    ///
+    /// When the asset does not have a rule set and opted in to the default rule set, the
+    /// rule set of its token standard on `default_rule_sets` (if any) is used instead.
+    pub fn effective_programmable_config(
+        &self,
+        default_rule_sets: Option<&DefaultRuleSets>,
+    ) -> Option<ProgrammableConfig> {
+        if let Some(ProgrammableConfig::V1 { rule_set: Some(_) }) = self.programmable_config {
+            return self.programmable_config.clone();
+        }
+
+        if !self.use_default_rule_set {
+            return self.programmable_config.clone();
+        }
+
+        default_rule_sets
+            .zip(self.token_standard)
+            .and_then(|(default_rule_sets, token_standard)| default_rule_sets.get(token_standard))
+            .map(|rule_set| ProgrammableConfig::V1 {
+                rule_set: Some(rule_set),
+            })
+            .or_else(|| self.programmable_config.clone())
+    }
+
+    /// Merges the programmable config stored on the `extension` of an asset that uses the
+    /// legacy layout.
+    ///
+    /// The `extension` is required when the programmable config is stored on it and is
+    /// ignored otherwise.
+    pub fn merge_programmable_config(
+        &mut self,
+        extension: Option<&ProgrammableConfigExtension>,
+    ) -> ProgramResult {
+        if !self.programmable_config_in_extension {
+            return Ok(());
+        }
+
+        let extension = extension.ok_or(MetadataError::InvalidProgrammableConfigExtension)?;
+
+        if extension.mint != self.mint {
+            return Err(MetadataError::InvalidProgrammableConfigExtension.into());
+        }
+
+        self.programmable_config = extension.programmable_config.clone();
+
+        Ok(())
+    }
+
+    /// Loads the `ProgrammableConfigExtension` account and merges its programmable config
+    /// (see `merge_programmable_config`).
+    pub(crate) fn load_programmable_config(
+        &mut self,
+        extension_info: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        if !self.programmable_config_in_extension {
+            return Ok(());
+        }
+
+        let extension = extension_info
+            .map(ProgrammableConfigExtension::from_account_info)
+            .transpose()
+            .map_err(|_| MetadataError::InvalidProgrammableConfigExtension)?;
+
+        self.merge_programmable_config(extension.as_ref())
+    }
+
+    /// Asserts that the programmable config is stored on the metadata, since it cannot
+    /// be changed while the asset uses the legacy layout.
+    pub(crate) fn assert_current_layout(&self) -> ProgramResult {
+        if self.programmable_config_in_extension {
+            return Err(MetadataError::LegacyLayoutAsset.into());
+        }
 
         Ok(())
     }
 
     pub fn into_asset_data(self) -> AssetData {
-        let mut asset_data = AssetData::new(
-            self.token_standard.unwrap_or(TokenStandard::NonFungible),
-            self.data.name,
-            self.data.symbol,
-            self.data.uri,
-        );
-        asset_data.seller_fee_basis_points = self.data.seller_fee_basis_points;
-        asset_data.creators = self.data.creators;
-        asset_data.primary_sale_happened = self.primary_sale_happened;
-        asset_data.is_mutable = self.is_mutable;
-        asset_data.collection = self.collection;
-        asset_data.uses = self.uses;
-        asset_data.collection_details = self.collection_details;
-        asset_data.rule_set =
-            if let Some(ProgrammableConfig::V1 { rule_set }) = self.programmable_config {
+        AssetData::from_config(AssetDataConfig {
+            name: self.data.name,
+            symbol: self.data.symbol,
+            uri: self.data.uri,
+            seller_fee_basis_points: self.data.seller_fee_basis_points,
+            creators: self.data.creators,
+            primary_sale_happened: self.primary_sale_happened,
+            is_mutable: self.is_mutable,
+            token_standard: self.token_standard.unwrap_or(TokenStandard::NonFungible),
+            collection: self.collection,
+            uses: self.uses,
+            collection_details: self.collection_details,
+            rule_set: if let Some(ProgrammableConfig::V1 { rule_set }) = self.programmable_config {
                 rule_set
             } else {
                 None
-            };
-
-        asset_data
+            },
+        })
     }
 }
 
@@ -267,6 +599,15 @@ impl Default for Metadata {
             uses: None,
             collection_details: None,
             programmable_config: None,
+            inherit_royalties: false,
+            use_default_rule_set: false,
+            permanent_delegate: None,
+            unverify_creators_on_authority_change: false,
+            fee_flag: false,
+            overflow_creators_share: None,
+            programmable_config_in_extension: false,
+            uri_hash: None,
+            rate_limited_uses: false,
         }
     }
 }
@@ -279,6 +620,43 @@ impl TokenMetadataAccount for Metadata {
     fn size() -> usize {
         MAX_METADATA_LEN
     }
+
+    fn is_correct_account_type(data: &[u8], data_type: Key, data_size: usize) -> bool {
+        // metadata accounts can be resized to the size of their contents, so
+        // they are only required to not exceed the maximum size
+        match Key::of(data) {
+            Some(key) => {
+                (key == data_type || key == Key::Uninitialized) && (data.len() <= data_size)
+            }
+            None => false,
+        }
+    }
+}
+
+// The fields added after the programmable config are serialized as a versioned extension
+// (see `METADATA_EXTENSION_V1`), with the boolean fields packed in a single byte, so they
+// fit in the original padding of the account.
+impl BorshSerialize for Metadata {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.key.serialize(writer)?;
+        self.update_authority.serialize(writer)?;
+        self.mint.serialize(writer)?;
+        self.data.serialize(writer)?;
+        self.primary_sale_happened.serialize(writer)?;
+        self.is_mutable.serialize(writer)?;
+        self.edition_nonce.serialize(writer)?;
+        self.token_standard.serialize(writer)?;
+        self.collection.serialize(writer)?;
+        self.uses.serialize(writer)?;
+        self.collection_details.serialize(writer)?;
+        self.programmable_config.serialize(writer)?;
+        // extension
+        METADATA_EXTENSION_V1.serialize(writer)?;
+        self.extension_flags().serialize(writer)?;
+        self.permanent_delegate.serialize(writer)?;
+        self.overflow_creators_share.serialize(writer)?;
+        self.uri_hash.serialize(writer)
+    }
 }
 
 // We have a custom implementation of BorshDeserialize for Metadata because of corrupted metadata issues
@@ -342,8 +720,10 @@ mod tests {
     use crate::{
         error::MetadataError,
         state::{
-            CollectionAuthorityRecord, Edition, EditionMarker, Key, MasterEditionV2, Metadata,
-            TokenMetadataAccount, UseAuthorityRecord, MAX_METADATA_LEN,
+            BasisPoints, Collection, CollectionAuthorityRecord, CollectionDetails, Creator,
+            CreatorsOverflow, Data, DefaultRuleSets, Edition, EditionMarker, Key, MasterEditionV2,
+            Metadata, ProgrammableConfig, ProgrammableConfigExtension, TokenMetadataAccount,
+            TokenStandard, UseAuthorityRecord, MAX_METADATA_LEN, METADATA_EXTENSION_V1,
         },
         utils::metadata::tests::{expected_pesky_metadata, pesky_data},
         ID,
@@ -395,6 +775,83 @@ mod tests {
         assert_eq!(md, expected_metadata);
     }
 
+    #[test]
+    fn successfully_deserialize_resized_metadata() {
+        let expected_metadata = expected_pesky_metadata();
+
+        // resized accounts do not have the padding
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+
+        let md = Metadata::safe_deserialize(&buf).unwrap();
+        assert_eq!(md, expected_metadata);
+    }
+
+    #[test]
+    fn successfully_deserialize_metadata_extension() {
+        let mut expected_metadata = expected_pesky_metadata();
+        expected_metadata.collection_details = Some(CollectionDetails::V2 {
+            size: 1,
+            enforce_denylist: true,
+        });
+        expected_metadata.programmable_config = Some(ProgrammableConfig::V1 {
+            rule_set: Some(Keypair::new().pubkey()),
+        });
+        expected_metadata.inherit_royalties = true;
+        expected_metadata.fee_flag = true;
+        expected_metadata.rate_limited_uses = true;
+        expected_metadata.permanent_delegate = Some(Keypair::new().pubkey());
+        expected_metadata.overflow_creators_share = Some(10);
+        expected_metadata.uri_hash = Some([7; 32]);
+
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+        // the extension fits in the original padding of the account
+        assert_eq!(MAX_METADATA_LEN, 679);
+        assert!(buf.len() <= MAX_METADATA_LEN);
+        pad_metadata_length(&mut buf);
+
+        let md = Metadata::safe_deserialize(&buf).unwrap();
+        assert_eq!(md, expected_metadata);
+    }
+
+    #[test]
+    fn deserialize_metadata_ignores_junk_padding() {
+        let expected_metadata = expected_pesky_metadata();
+
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+        // removes the extension: marker, flags and the three `None` fields
+        buf.truncate(buf.len() - 8);
+        let padding_start = buf.len();
+
+        // junk that could be read as a permanent delegate without the extension marker
+        buf.extend([0, 0, 0, 0, 0, 1]);
+        buf.extend([7; 32]);
+        pad_metadata_length(&mut buf);
+
+        let md = Metadata::safe_deserialize(&buf).unwrap();
+        assert_eq!(md.permanent_delegate, None);
+        assert_eq!(md, expected_metadata);
+
+        // legacy junk starting with a non-zero byte and known flags, which a single
+        // version byte could not tell apart from an extension
+        buf[padding_start] = 1;
+        buf[padding_start + 4] = 1;
+
+        let md = Metadata::safe_deserialize(&buf).unwrap();
+        assert_eq!(md.permanent_delegate, None);
+        assert_eq!(md, expected_metadata);
+
+        // junk starting with the extension marker, but with unknown flags
+        buf[padding_start..padding_start + 4].copy_from_slice(&METADATA_EXTENSION_V1);
+        buf[padding_start + 4] = u8::MAX;
+
+        let md = Metadata::safe_deserialize(&buf).unwrap();
+        assert_eq!(md.permanent_delegate, None);
+        assert_eq!(md, expected_metadata);
+    }
+
     #[test]
     fn fail_to_deserialize_metadata_with_wrong_owner() {
         let expected_metadata = expected_pesky_metadata();
@@ -431,7 +888,9 @@ mod tests {
 
         let mut buf = Vec::new();
         expected_metadata.serialize(&mut buf).unwrap();
-        // No padding is added to the metadata so it's too short.
+        // Padding is added beyond the maximum size so it's too long.
+        pad_metadata_length(&mut buf);
+        buf.push(0);
 
         let pubkey = Keypair::new().pubkey();
         let owner = ID;
@@ -610,4 +1069,252 @@ mod tests {
         let err = Metadata::from_account_info(&account_info).unwrap_err();
         assert_eq!(err, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn effective_royalties_from_collection_parent() {
+        let mut parent = expected_pesky_metadata();
+        parent.mint = Keypair::new().pubkey();
+        parent.data.seller_fee_basis_points = BasisPoints::new(1000).unwrap();
+        parent.data.creators = Some(vec![Creator {
+            address: Keypair::new().pubkey(),
+            verified: true,
+            share: 100,
+        }]);
+
+        let mut metadata = expected_pesky_metadata();
+        metadata.collection = Some(Collection {
+            verified: true,
+            key: parent.mint,
+        });
+
+        // the asset does not inherit its royalties
+        let (seller_fee_basis_points, creators) =
+            metadata.effective_royalties(&parent, None).unwrap();
+        assert_eq!(
+            seller_fee_basis_points,
+            metadata.data.seller_fee_basis_points
+        );
+        assert_eq!(creators, metadata.data.creators);
+
+        metadata.inherit_royalties = true;
+
+        let (seller_fee_basis_points, creators) =
+            metadata.effective_royalties(&parent, None).unwrap();
+        assert_eq!(seller_fee_basis_points.value(), 1000);
+        assert_eq!(creators, parent.data.creators);
+    }
+
+    #[test]
+    fn fail_effective_royalties_with_invalid_parent() {
+        let mut parent = expected_pesky_metadata();
+        parent.mint = Keypair::new().pubkey();
+
+        let mut metadata = expected_pesky_metadata();
+        metadata.inherit_royalties = true;
+
+        let err = metadata.effective_royalties(&parent, None).unwrap_err();
+        assert_eq!(err, MetadataError::CollectionNotFound.into());
+
+        metadata.collection = Some(Collection {
+            verified: true,
+            key: Keypair::new().pubkey(),
+        });
+
+        let err = metadata.effective_royalties(&parent, None).unwrap_err();
+        assert_eq!(err, MetadataError::NotAMemberOfCollection.into());
+
+        metadata.collection = Some(Collection {
+            verified: false,
+            key: parent.mint,
+        });
+
+        let err = metadata.effective_royalties(&parent, None).unwrap_err();
+        assert_eq!(err, MetadataError::NotVerifiedMemberOfCollection.into());
+    }
+
+    #[test]
+    fn effective_programmable_config_with_default_rule_set() {
+        let default_rule_set = Keypair::new().pubkey();
+        let mut default_rule_sets = DefaultRuleSets::default();
+        default_rule_sets.set(
+            TokenStandard::ProgrammableNonFungible,
+            Some(default_rule_set),
+        );
+
+        let mut metadata = expected_pesky_metadata();
+        metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+        metadata.programmable_config = Some(ProgrammableConfig::V1 { rule_set: None });
+
+        // the asset did not opt in to the default rule set
+        assert_eq!(
+            metadata.effective_programmable_config(Some(&default_rule_sets)),
+            metadata.programmable_config
+        );
+
+        metadata.use_default_rule_set = true;
+
+        assert_eq!(
+            metadata.effective_programmable_config(Some(&default_rule_sets)),
+            Some(ProgrammableConfig::V1 {
+                rule_set: Some(default_rule_set)
+            })
+        );
+        // no default rule set available
+        assert_eq!(
+            metadata.effective_programmable_config(None),
+            metadata.programmable_config
+        );
+
+        // the rule set of the asset takes precedence
+        let rule_set = Keypair::new().pubkey();
+        metadata.programmable_config = Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        });
+
+        assert_eq!(
+            metadata.effective_programmable_config(Some(&default_rule_sets)),
+            metadata.programmable_config
+        );
+    }
+
+    #[test]
+    fn merge_programmable_config_from_extension() {
+        let rule_set = Keypair::new().pubkey();
+
+        let mut metadata = expected_pesky_metadata();
+        metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+
+        let extension = ProgrammableConfigExtension {
+            mint: metadata.mint,
+            programmable_config: Some(ProgrammableConfig::V1 {
+                rule_set: Some(rule_set),
+            }),
+            ..Default::default()
+        };
+
+        // the programmable config is stored on the metadata
+        metadata.merge_programmable_config(None).unwrap();
+        assert_eq!(metadata.programmable_config, None);
+
+        metadata.programmable_config_in_extension = true;
+
+        let err = metadata.merge_programmable_config(None).unwrap_err();
+        assert_eq!(
+            err,
+            MetadataError::InvalidProgrammableConfigExtension.into()
+        );
+
+        let other = ProgrammableConfigExtension {
+            mint: Keypair::new().pubkey(),
+            ..extension.clone()
+        };
+        let err = metadata
+            .merge_programmable_config(Some(&other))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MetadataError::InvalidProgrammableConfigExtension.into()
+        );
+
+        metadata
+            .merge_programmable_config(Some(&extension))
+            .unwrap();
+        assert_eq!(metadata.programmable_config, extension.programmable_config);
+    }
+
+    #[test]
+    fn set_data_clears_uri_hash_on_uri_change() {
+        let mut metadata = expected_pesky_metadata();
+        metadata.uri_hash = Some([1; 32]);
+
+        // the stored uri is padded, so the same uri keeps the hash
+        let mut data = metadata.data.clone();
+        data.uri = data.uri.trim_matches(char::from(0)).to_string();
+        data.name = String::from("Renamed");
+        metadata.set_data(data.clone());
+        assert_eq!(metadata.uri_hash, Some([1; 32]));
+
+        data.uri = String::from("https://swapped.content");
+        metadata.set_data(data);
+        assert_eq!(metadata.uri_hash, None);
+    }
+
+    #[test]
+    fn unverify_creators_on_authority_change() {
+        let creator = Keypair::new().pubkey();
+        let new_authority = Keypair::new().pubkey();
+
+        let mut metadata = Metadata {
+            update_authority: Keypair::new().pubkey(),
+            data: Data {
+                creators: Some(vec![
+                    Creator {
+                        address: creator,
+                        verified: true,
+                        share: 50,
+                    },
+                    Creator {
+                        address: new_authority,
+                        verified: true,
+                        share: 50,
+                    },
+                ]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // without the policy the creators are kept verified
+        let mut unchanged = metadata.clone();
+        unchanged.set_update_authority(new_authority);
+        assert_eq!(unchanged.update_authority, new_authority);
+        assert!(unchanged
+            .data
+            .creators
+            .unwrap()
+            .iter()
+            .all(|creator| creator.verified));
+
+        // the new update authority keeps its own verification
+        metadata.unverify_creators_on_authority_change = true;
+        metadata.set_update_authority(new_authority);
+        assert_eq!(metadata.update_authority, new_authority);
+
+        let creators = metadata.data.creators.unwrap();
+        assert!(!creators[0].verified);
+        assert!(creators[1].verified);
+    }
+
+    #[test]
+    fn all_creators_with_overflow() {
+        let mut metadata = expected_pesky_metadata();
+        let creators = metadata.data.creators.clone();
+
+        let mut overflow = CreatorsOverflow {
+            mint: metadata.mint,
+            creators: vec![Creator {
+                address: Keypair::new().pubkey(),
+                verified: false,
+                share: 10,
+            }],
+            ..Default::default()
+        };
+
+        // the overflow is ignored when the asset does not have overflow creators
+        assert_eq!(metadata.all_creators(Some(&overflow)).unwrap(), creators);
+
+        metadata.overflow_creators_share = Some(10);
+
+        let err = metadata.all_creators(None).unwrap_err();
+        assert_eq!(err, MetadataError::MissingCreatorsOverflow.into());
+
+        let all_creators = metadata.all_creators(Some(&overflow)).unwrap().unwrap();
+        assert_eq!(all_creators.len(), creators.unwrap().len() + 1);
+        assert_eq!(all_creators.last(), overflow.creators.last());
+
+        overflow.mint = Keypair::new().pubkey();
+
+        let err = metadata.all_creators(Some(&overflow)).unwrap_err();
+        assert_eq!(err, MetadataError::InvalidCreatorsOverflow.into());
+    }
 }
@@ -7,3 +7,171 @@ pub enum MigrationType {
     CollectionV1,
     ProgrammableV1,
 }
+
+pub const MIGRATION_WINDOW_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // collection mint
++ 8; // migrate after
+
+pub const MIGRATION_OPT_OUT_SIZE: usize = 1 // key
++ 1   // bump
++ 32; // mint
+
+/// Minimum grace period (in seconds) before the items of a collection can be migrated
+/// by the collection authority.
+pub const MIN_MIGRATION_GRACE_PERIOD: i64 = 604_800;
+
+/// The `MigrationWindow` account stores the grace period of a collection migration set by
+/// the update authority of the collection parent.
+///
+/// The `CollectionV1` migration is only allowed after the grace period; until then, holders
+/// can opt out their assets (see `MigrationOptOut`). The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. collection mint id
+/// 4. `"migration_window"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MigrationWindow {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the collection mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Unix timestamp after which the items of the collection can be migrated.
+    pub migrate_after: i64,
+}
+
+impl Default for MigrationWindow {
+    fn default() -> Self {
+        Self {
+            key: Key::MigrationWindow,
+            bump: 255,
+            collection_mint: Pubkey::default(),
+            migrate_after: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for MigrationWindow {
+    fn key() -> Key {
+        Key::MigrationWindow
+    }
+
+    fn size() -> usize {
+        MIGRATION_WINDOW_SIZE
+    }
+}
+
+impl MigrationWindow {
+    pub fn from_bytes(data: &[u8]) -> Result<MigrationWindow, ProgramError> {
+        let window: MigrationWindow =
+            try_from_slice_checked(data, Key::MigrationWindow, MIGRATION_WINDOW_SIZE)?;
+        Ok(window)
+    }
+
+    /// Opens the window with the specified grace period (in seconds).
+    ///
+    /// An open window cannot be shortened, so holders keep the grace period they were given.
+    pub fn open(&mut self, grace_period: i64, now: i64) -> ProgramResult {
+        if grace_period < MIN_MIGRATION_GRACE_PERIOD {
+            return Err(MetadataError::InvalidMigrationGracePeriod.into());
+        }
+
+        let migrate_after = now
+            .checked_add(grace_period)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        self.migrate_after = self.migrate_after.max(migrate_after);
+
+        Ok(())
+    }
+
+    /// Indicates whether the grace period has elapsed at the specified time.
+    pub fn is_elapsed(&self, now: i64) -> bool {
+        now >= self.migrate_after
+    }
+}
+
+/// The `MigrationOptOut` account indicates that the holder of an asset opted out of the
+/// migration of its collection by the collection authority. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"migration_opt_out"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MigrationOptOut {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+}
+
+impl Default for MigrationOptOut {
+    fn default() -> Self {
+        Self {
+            key: Key::MigrationOptOut,
+            bump: 255,
+            mint: Pubkey::default(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for MigrationOptOut {
+    fn key() -> Key {
+        Key::MigrationOptOut
+    }
+
+    fn size() -> usize {
+        MIGRATION_OPT_OUT_SIZE
+    }
+}
+
+impl MigrationOptOut {
+    pub fn from_bytes(data: &[u8]) -> Result<MigrationOptOut, ProgramError> {
+        let opt_out: MigrationOptOut =
+            try_from_slice_checked(data, Key::MigrationOptOut, MIGRATION_OPT_OUT_SIZE)?;
+        Ok(opt_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migration_window_grace_period() {
+        let mut window = MigrationWindow {
+            collection_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let data = window.try_to_vec().unwrap();
+        assert_eq!(data.len(), MIGRATION_WINDOW_SIZE);
+        assert_eq!(MigrationWindow::from_bytes(&data).unwrap(), window);
+
+        assert!(window.open(MIN_MIGRATION_GRACE_PERIOD - 1, 100).is_err());
+
+        window.open(MIN_MIGRATION_GRACE_PERIOD, 100).unwrap();
+        assert!(!window.is_elapsed(100 + MIN_MIGRATION_GRACE_PERIOD - 1));
+        assert!(window.is_elapsed(100 + MIN_MIGRATION_GRACE_PERIOD));
+
+        // the window cannot be shortened
+        window.open(MIN_MIGRATION_GRACE_PERIOD, 50).unwrap();
+        assert_eq!(window.migrate_after, 100 + MIN_MIGRATION_GRACE_PERIOD);
+
+        let opt_out = MigrationOptOut {
+            mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let data = opt_out.try_to_vec().unwrap();
+        assert_eq!(data.len(), MIGRATION_OPT_OUT_SIZE);
+        assert_eq!(MigrationOptOut::from_bytes(&data).unwrap(), opt_out);
+    }
+}
@@ -1,30 +1,61 @@
 pub(crate) mod asset_data;
+pub(crate) mod attestation;
+pub(crate) mod authority_config;
+pub(crate) mod basis_points;
 pub(crate) mod collection;
+pub(crate) mod collection_membership;
+pub(crate) mod collection_pause;
+pub(crate) mod content_rating;
 pub(crate) mod creator;
+pub(crate) mod creators_overflow;
 pub(crate) mod data;
+pub(crate) mod default_rule_sets;
 pub(crate) mod delegate;
+pub(crate) mod delegate_registry;
+pub(crate) mod denylist;
 pub(crate) mod edition;
+pub(crate) mod edition_extension;
 pub(crate) mod edition_marker;
 pub(crate) mod escrow;
+pub(crate) mod fee;
+pub(crate) mod flag;
+pub(crate) mod legacy_layout;
 pub(crate) mod master_edition;
 pub(crate) mod metadata;
 pub(crate) mod migrate;
 pub(crate) mod programmable;
 pub(crate) mod reservation;
+pub(crate) mod rule_set_proposal;
 pub(crate) mod token_auth_payload;
+pub(crate) mod tombstone;
+pub(crate) mod transfer_session;
 pub(crate) mod uses;
 
-use std::io::ErrorKind;
+use std::{io::ErrorKind, ops::RangeInclusive};
 
 pub use asset_data::*;
+pub use attestation::*;
+pub use authority_config::*;
+pub use basis_points::*;
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
 pub use collection::*;
+pub use collection_membership::*;
+pub use collection_pause::*;
+pub use content_rating::*;
 pub use creator::*;
+pub use creators_overflow::*;
 pub use data::*;
+pub use default_rule_sets::*;
 pub use delegate::*;
+pub use delegate_registry::*;
+pub use denylist::*;
 pub use edition::*;
+pub use edition_extension::*;
 pub use edition_marker::*;
 pub use escrow::*;
+pub use fee::*;
+pub use flag::*;
+pub use legacy_layout::*;
 pub use master_edition::*;
 pub use metadata::*;
 pub use migrate::*;
@@ -33,12 +64,15 @@ use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 pub use programmable::*;
 pub use reservation::*;
+pub use rule_set_proposal::*;
 use shank::ShankAccount;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
 use spl_token::state::Account as TokenAccount;
+pub use tombstone::*;
+pub use transfer_session::*;
 pub use uses::*;
 #[cfg(feature = "serde-feature")]
 use {
@@ -68,6 +102,7 @@ pub enum TokenStandard {
     Fungible,                // A token with simple metadata
     NonFungibleEdition,      // This is a limited edition
     ProgrammableNonFungible, // NonFungible with programmable configuration
+    NonTransferable,         // NonFungible that is kept frozen and can only be burned by the holder
 }
 
 pub trait TokenMetadataAccount: BorshDeserialize {
@@ -76,12 +111,7 @@ pub trait TokenMetadataAccount: BorshDeserialize {
     fn size() -> usize;
 
     fn is_correct_account_type(data: &[u8], data_type: Key, data_size: usize) -> bool {
-        if data.is_empty() {
-            return false;
-        }
-
-        let key: Option<Key> = Key::from_u8(data[0]);
-        match key {
+        match Key::of(data) {
             Some(key) => {
                 (key == data_type || key == Key::Uninitialized) && (data.len() == data_size)
             }
@@ -120,23 +150,78 @@ where {
     }
 }
 
+/// Discriminator of the accounts of the program, stored as the first byte of the account data.
+///
+/// The values are part of the public interface of the program: once assigned, a value is never
+/// changed or reused, and new account types take the next free value. Since Borsh serializes
+/// the variant index, variants must be declared in the order of their values.
+///
+/// Values in `RESERVED_KEY_RANGE` are never assigned by the program and are reserved for
+/// extensions, so forked and companion programs can discriminate their own accounts without
+/// clashing with the accounts of the program.
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive)]
 pub enum Key {
-    Uninitialized,
-    EditionV1,
-    MasterEditionV1,
-    ReservationListV1,
-    MetadataV1,
-    ReservationListV2,
-    MasterEditionV2,
-    EditionMarker,
-    UseAuthorityRecord,
-    CollectionAuthorityRecord,
-    TokenOwnedEscrow,
-    TokenRecord,
-    MetadataDelegate,
+    Uninitialized = 0,
+    EditionV1 = 1,
+    MasterEditionV1 = 2,
+    ReservationListV1 = 3,
+    MetadataV1 = 4,
+    ReservationListV2 = 5,
+    MasterEditionV2 = 6,
+    EditionMarker = 7,
+    UseAuthorityRecord = 8,
+    CollectionAuthorityRecord = 9,
+    TokenOwnedEscrow = 10,
+    TokenRecord = 11,
+    MetadataDelegate = 12,
+    Flagged = 13,
+    MasterEditionExtension = 14,
+    Tombstone = 15,
+    DelegateRegistry = 16,
+    DefaultRuleSets = 17,
+    CollectionItemsDelegate = 18,
+    EscrowLedger = 19,
+    ContentRatingRecord = 20,
+    RuleSetProposal = 21,
+    CollectionPause = 22,
+    AuthorityConfig = 23,
+    CreatorsOverflow = 24,
+    CollectionMembership = 25,
+    Attestation = 26,
+    LegacyLayout = 27,
+    ProgrammableConfigExtension = 28,
+    MigrationWindow = 29,
+    MigrationOptOut = 30,
+    UsesV2 = 31,
+    EditionTombstone = 32,
+    TransferSession = 33,
+    Denylisted = 34,
+}
+
+/// Range of `Key` values reserved for extensions.
+pub const RESERVED_KEY_RANGE: RangeInclusive<u8> = 128..=255;
+
+impl Key {
+    /// Returns the `Key` of the account data, or `None` when the data is empty or does not
+    /// start with a known discriminator.
+    pub fn of(data: &[u8]) -> Option<Key> {
+        data.first().and_then(|key| Key::try_from(*key).ok())
+    }
+
+    /// Indicates whether the value is reserved for extensions.
+    pub fn is_reserved(value: u8) -> bool {
+        RESERVED_KEY_RANGE.contains(&value)
+    }
+}
+
+impl TryFrom<u8> for Key {
+    type Error = MetadataError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Key::from_u8(value).ok_or(MetadataError::DataTypeMismatch)
+    }
 }
 
 #[cfg(feature = "serde-feature")]
@@ -204,3 +289,82 @@ pub trait Resizable: TokenMetadataAccount + BorshSerialize {
     /// stored byte array (account) can change.
     fn from_bytes(data: &[u8]) -> Result<Self, ProgramError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    const KEYS: [(Key, u8); 35] = [
+        (Key::Uninitialized, 0),
+        (Key::EditionV1, 1),
+        (Key::MasterEditionV1, 2),
+        (Key::ReservationListV1, 3),
+        (Key::MetadataV1, 4),
+        (Key::ReservationListV2, 5),
+        (Key::MasterEditionV2, 6),
+        (Key::EditionMarker, 7),
+        (Key::UseAuthorityRecord, 8),
+        (Key::CollectionAuthorityRecord, 9),
+        (Key::TokenOwnedEscrow, 10),
+        (Key::TokenRecord, 11),
+        (Key::MetadataDelegate, 12),
+        (Key::Flagged, 13),
+        (Key::MasterEditionExtension, 14),
+        (Key::Tombstone, 15),
+        (Key::DelegateRegistry, 16),
+        (Key::DefaultRuleSets, 17),
+        (Key::CollectionItemsDelegate, 18),
+        (Key::EscrowLedger, 19),
+        (Key::ContentRatingRecord, 20),
+        (Key::RuleSetProposal, 21),
+        (Key::CollectionPause, 22),
+        (Key::AuthorityConfig, 23),
+        (Key::CreatorsOverflow, 24),
+        (Key::CollectionMembership, 25),
+        (Key::Attestation, 26),
+        (Key::LegacyLayout, 27),
+        (Key::ProgrammableConfigExtension, 28),
+        (Key::MigrationWindow, 29),
+        (Key::MigrationOptOut, 30),
+        (Key::UsesV2, 31),
+        (Key::EditionTombstone, 32),
+        (Key::TransferSession, 33),
+        (Key::Denylisted, 34),
+    ];
+
+    #[test]
+    fn key_values_are_stable() {
+        for (key, value) in KEYS {
+            assert_eq!(key as u8, value);
+            assert_eq!(key.try_to_vec().unwrap(), vec![value]);
+            assert_eq!(Key::try_from(value), Ok(key));
+        }
+
+        // new keys must be added to the list above
+        let next = KEYS.len() as u8;
+        assert_eq!(Key::try_from(next), Err(MetadataError::DataTypeMismatch));
+    }
+
+    #[test]
+    fn key_values_are_not_reserved() {
+        for (key, _) in KEYS {
+            assert!(!Key::is_reserved(key as u8));
+        }
+
+        for value in RESERVED_KEY_RANGE {
+            assert!(Key::try_from(value).is_err());
+        }
+    }
+
+    #[test]
+    fn key_of_account_data() {
+        assert_eq!(Key::of(&[]), None);
+        assert_eq!(
+            Key::of(&[Key::MetadataV1 as u8, 1, 2]),
+            Some(Key::MetadataV1)
+        );
+        assert_eq!(Key::of(&[*RESERVED_KEY_RANGE.start()]), None);
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::Error;
+use std::{fmt, io::Error, str::FromStr};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_utils::cmp_pubkeys;
@@ -16,7 +16,7 @@ use crate::{
     error::MetadataError,
     instruction::MetadataDelegateRole,
     pda::{find_metadata_delegate_record_account, find_token_record_account},
-    processor::{DelegateScenario, TransferScenario, UpdateScenario},
+    processor::{DelegateScenario, TransferScenario, UnlockScenario, UpdateScenario},
     utils::assert_owned_by,
 };
 
@@ -29,13 +29,28 @@ pub const TOKEN_STATE_INDEX: usize = 2;
 
 pub const LOCKED_TRANSFER_SIZE: usize = 33; // Optional Pubkey
 
+pub const DELEGATE_EXPIRY_SIZE: usize = 9; // Optional i64
+
+pub const STAKING_PROGRAM_SIZE: usize = 33; // Optional Pubkey
+
+pub const HOLDER_LOCK_SIZE: usize = 9; // bool + i64
+
+pub const COLLECTION_FREEZE_SIZE: usize = 2; // bool + bool
+
+pub const PAYER_SIZE: usize = 33; // Optional Pubkey
+
 pub const TOKEN_RECORD_SIZE: usize = 1 // Key
 + 1   // bump
 + 1   // state
 + 9   // rule set revision
 + 33  // delegate
 + 2   // delegate role
-+ 33; // locked transfer
++ 33  // locked transfer
++ 9   // delegate expiry
++ 33  // staking program
++ 9   // holder lock
++ 2   // collection freeze
++ 33; // payer
 
 /// The `TokenRecord` struct represents the state of the token account holding a `pNFT`. Given
 /// that the token account is always frozen, it includes a `state` that provides an abstraction
@@ -79,6 +94,32 @@ pub struct TokenRecord {
     /// Stores the destination pubkey when a transfer is lock to an allowed address. This
     /// pubkey gets set when a 'LockTransfer' delegate is approved.
     pub locked_transfer: Option<Pubkey>,
+    /// Unix timestamp at which the current token delegate expires (if any).
+    pub delegate_expiry: Option<i64>,
+    /// Program that is allowed to unlock the asset. This pubkey gets set when a
+    /// 'Staking' delegate is approved.
+    pub staking_program: Option<Pubkey>,
+    /// Indicates whether the holder locked the token. This flag gets set by the
+    /// 'SelfLock' instruction and blocks transfers, delegates and burns.
+    pub holder_locked: bool,
+    /// Unix timestamp after which the holder can remove the lock.
+    pub holder_unlock_timestamp: i64,
+    /// Indicates whether the holder allows the `CollectionFreeze` delegate of the
+    /// collection to freeze the token.
+    pub collection_freeze_opt_in: bool,
+    /// Indicates whether the token is frozen by the `CollectionFreeze` delegate of the
+    /// collection. This flag blocks transfers, delegates and burns.
+    pub collection_frozen: bool,
+    /// Account that paid the rent of the token record, which gets the rent back when
+    /// the record is closed by the 'CloseTokenRecord' instruction.
+    #[cfg_attr(
+        feature = "serde-feature",
+        serde(
+            deserialize_with = "deser_option_pubkey",
+            serialize_with = "ser_option_pubkey"
+        )
+    )]
+    pub payer: Option<Pubkey>,
 }
 
 impl Default for TokenRecord {
@@ -91,6 +132,13 @@ impl Default for TokenRecord {
             delegate: None,
             delegate_role: None,
             locked_transfer: None,
+            delegate_expiry: None,
+            staking_program: None,
+            holder_locked: false,
+            holder_unlock_timestamp: 0,
+            collection_freeze_opt_in: false,
+            collection_frozen: false,
+            payer: None,
         }
     }
 }
@@ -119,13 +167,38 @@ impl TokenRecord {
         matches!(self.state, TokenState::Locked)
     }
 
+    /// Checks whether the token is listed for sale (a `Sale` delegate is set).
+    pub fn is_listed(&self) -> bool {
+        matches!(self.state, TokenState::Listed)
+    }
+
     /// Resets the token state by clearing any state stored.
+    ///
+    /// The holder lock is kept, since it can only be removed by the holder.
     pub fn reset(&mut self) {
         self.state = TokenState::Unlocked;
         self.rule_set_revision = None;
         self.delegate = None;
         self.delegate_role = None;
         self.locked_transfer = None;
+        self.delegate_expiry = None;
+        self.staking_program = None;
+    }
+
+    /// Checks whether the current token delegate has expired at the given unix timestamp.
+    pub fn is_delegate_expired(&self, timestamp: i64) -> bool {
+        matches!(self.delegate_expiry, Some(expiry) if timestamp >= expiry)
+    }
+
+    /// Checks whether the token has an active `Rental` delegate at the given unix timestamp.
+    pub fn is_rented(&self, timestamp: i64) -> bool {
+        matches!(self.delegate_role, Some(TokenDelegateRole::Rental))
+            && !self.is_delegate_expired(timestamp)
+    }
+
+    /// Checks whether the holder can remove the holder lock at the given unix timestamp.
+    pub fn is_holder_unlockable(&self, timestamp: i64) -> bool {
+        timestamp >= self.holder_unlock_timestamp
     }
 }
 
@@ -137,7 +210,26 @@ impl Resizable for TokenRecord {
 
         // we use the account length in the 'is_correct_account_type' since we are
         // manually checking that the account length is valid
-        if !(length == 0 || length == LOCKED_TRANSFER_SIZE as i64)
+        if !(length == 0
+            || length == PAYER_SIZE as i64
+            || length == (PAYER_SIZE + COLLECTION_FREEZE_SIZE) as i64
+            || length == (PAYER_SIZE + COLLECTION_FREEZE_SIZE + HOLDER_LOCK_SIZE) as i64
+            || length
+                == (PAYER_SIZE + COLLECTION_FREEZE_SIZE + HOLDER_LOCK_SIZE + STAKING_PROGRAM_SIZE)
+                    as i64
+            || length
+                == (PAYER_SIZE
+                    + COLLECTION_FREEZE_SIZE
+                    + HOLDER_LOCK_SIZE
+                    + STAKING_PROGRAM_SIZE
+                    + DELEGATE_EXPIRY_SIZE) as i64
+            || length
+                == (PAYER_SIZE
+                    + COLLECTION_FREEZE_SIZE
+                    + HOLDER_LOCK_SIZE
+                    + STAKING_PROGRAM_SIZE
+                    + DELEGATE_EXPIRY_SIZE
+                    + LOCKED_TRANSFER_SIZE) as i64)
             || !TokenRecord::is_correct_account_type(
                 account_data,
                 Key::TokenRecord,
@@ -156,7 +248,55 @@ impl Resizable for TokenRecord {
         let delegate: Option<Pubkey> = BorshDeserialize::deserialize(&mut data)?;
         let delegate_role: Option<TokenDelegateRole> = BorshDeserialize::deserialize(&mut data)?;
 
-        let locked_transfer: Option<Pubkey> = if length == 0 {
+        let locked_transfer: Option<Pubkey> = if length
+            <= (PAYER_SIZE
+                + COLLECTION_FREEZE_SIZE
+                + HOLDER_LOCK_SIZE
+                + STAKING_PROGRAM_SIZE
+                + DELEGATE_EXPIRY_SIZE) as i64
+        {
+            BorshDeserialize::deserialize(&mut data)?
+        } else {
+            None
+        };
+
+        let delegate_expiry: Option<i64> = if length
+            <= (PAYER_SIZE + COLLECTION_FREEZE_SIZE + HOLDER_LOCK_SIZE + STAKING_PROGRAM_SIZE)
+                as i64
+        {
+            BorshDeserialize::deserialize(&mut data)?
+        } else {
+            None
+        };
+
+        let staking_program: Option<Pubkey> =
+            if length <= (PAYER_SIZE + COLLECTION_FREEZE_SIZE + HOLDER_LOCK_SIZE) as i64 {
+                BorshDeserialize::deserialize(&mut data)?
+            } else {
+                None
+            };
+
+        let (holder_locked, holder_unlock_timestamp): (bool, i64) =
+            if length <= (PAYER_SIZE + COLLECTION_FREEZE_SIZE) as i64 {
+                (
+                    BorshDeserialize::deserialize(&mut data)?,
+                    BorshDeserialize::deserialize(&mut data)?,
+                )
+            } else {
+                (false, 0)
+            };
+
+        let (collection_freeze_opt_in, collection_frozen): (bool, bool) =
+            if length <= PAYER_SIZE as i64 {
+                (
+                    BorshDeserialize::deserialize(&mut data)?,
+                    BorshDeserialize::deserialize(&mut data)?,
+                )
+            } else {
+                (false, false)
+            };
+
+        let payer: Option<Pubkey> = if length == 0 {
             BorshDeserialize::deserialize(&mut data)?
         } else {
             None
@@ -170,6 +310,13 @@ impl Resizable for TokenRecord {
             delegate,
             delegate_role,
             locked_transfer,
+            delegate_expiry,
+            staking_program,
+            holder_locked,
+            holder_unlock_timestamp,
+            collection_freeze_opt_in,
+            collection_frozen,
+            payer,
         })
     }
 }
@@ -197,7 +344,13 @@ pub enum TokenDelegateRole {
     Staking,
     Standard,
     LockedTransfer,
+    /// Can transfer tokens out of the token-owned escrow of the asset, but
+    /// not the asset itself.
+    Escrow,
     Migration = 255,
+    /// Rents the asset to the delegate (the renter) until the delegate expiry; transfers
+    /// and burns are blocked while the rental is active.
+    Rental,
 }
 
 pub struct AuthorityRequest<'a, 'b> {
@@ -223,6 +376,8 @@ pub struct AuthorityRequest<'a, 'b> {
     pub token_record_info: Option<&'a AccountInfo<'a>>,
     /// Expected `TokenDelegateRole` for the request.
     pub token_delegate_roles: Vec<TokenDelegateRole>,
+    /// Permanent delegate of the asset (if any).
+    pub permanent_delegate: Option<&'b Pubkey>,
 }
 
 impl<'a, 'b> Default for AuthorityRequest<'a, 'b> {
@@ -244,6 +399,7 @@ impl<'a, 'b> Default for AuthorityRequest<'a, 'b> {
             metadata_delegate_roles: Vec::with_capacity(0),
             token_record_info: None,
             token_delegate_roles: Vec::with_capacity(0),
+            permanent_delegate: None,
         }
     }
 }
@@ -267,6 +423,7 @@ pub enum AuthorityType {
     Holder,
     MetadataDelegate,
     TokenDelegate,
+    PermanentDelegate,
 }
 
 impl AuthorityType {
@@ -388,6 +545,18 @@ impl AuthorityType {
                         }
                     }
                 }
+                AuthorityType::PermanentDelegate => {
+                    // checks if the authority is the permanent delegate of the asset
+
+                    if let Some(permanent_delegate) = request.permanent_delegate {
+                        if cmp_pubkeys(permanent_delegate, request.authority) {
+                            return Ok(AuthorityResponse {
+                                authority_type: AuthorityType::PermanentDelegate,
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
                 AuthorityType::Metadata => {
                     // checks if the authority is the update authority
 
@@ -407,23 +576,154 @@ impl AuthorityType {
     }
 }
 
+/// Operations validated against the rule set of a programmable asset.
+///
+/// The name of the operation (e.g., `"Transfer:Owner"`) is the key used by rule sets to
+/// select the rules that apply; rule set authors should use `Operation::as_str` instead of
+/// copying the names.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Operation {
     Transfer { scenario: TransferScenario },
     Update { scenario: UpdateScenario },
     Delegate { scenario: DelegateScenario },
+    Unlock { scenario: UnlockScenario },
 }
 
-impl ToString for Operation {
-    fn to_string(&self) -> String {
+impl Operation {
+    /// Returns the canonical name of the operation.
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Transfer { scenario } => format!("Transfer:{}", scenario),
-            Self::Update { scenario } => format!("Update:{}", scenario),
-            Self::Delegate { scenario } => format!("Delegate:{}", scenario),
+            Self::Transfer { scenario } => match scenario {
+                TransferScenario::Holder => "Transfer:Owner",
+                TransferScenario::TransferDelegate => "Transfer:TransferDelegate",
+                TransferScenario::SaleDelegate => "Transfer:SaleDelegate",
+                TransferScenario::MigrationDelegate => "Transfer:MigrationDelegate",
+                TransferScenario::PermanentDelegate => "Transfer:PermanentDelegate",
+            },
+            Self::Update { scenario } => match scenario {
+                UpdateScenario::MetadataAuth => "Update:MetadataAuth",
+                UpdateScenario::Delegate => "Update:Delegate",
+                UpdateScenario::Proxy => "Update:Proxy",
+            },
+            Self::Delegate { scenario } => match scenario {
+                DelegateScenario::Metadata(role) => match role {
+                    MetadataDelegateRole::Authority => "Delegate:Authority",
+                    MetadataDelegateRole::Collection => "Delegate:Collection",
+                    MetadataDelegateRole::Use => "Delegate:Use",
+                    MetadataDelegateRole::Update => "Delegate:Update",
+                    MetadataDelegateRole::ProgrammableConfig => "Delegate:ProgrammableConfig",
+                    MetadataDelegateRole::Data => "Delegate:Data",
+                    MetadataDelegateRole::Uses => "Delegate:Uses",
+                    MetadataDelegateRole::CollectionFreeze => "Delegate:CollectionFreeze",
+                },
+                DelegateScenario::Token(role) => match role {
+                    TokenDelegateRole::Sale => "Delegate:Sale",
+                    TokenDelegateRole::Transfer => "Delegate:Transfer",
+                    TokenDelegateRole::LockedTransfer => "Delegate:LockedTransfer",
+                    TokenDelegateRole::Utility => "Delegate:Utility",
+                    TokenDelegateRole::Staking => "Delegate:Staking",
+                    TokenDelegateRole::Escrow => "Delegate:Escrow",
+                    TokenDelegateRole::Rental => "Delegate:Rental",
+                    _ => panic!("Invalid delegate role"),
+                },
+            },
+            Self::Unlock { scenario } => match scenario {
+                UnlockScenario::UtilityDelegate => "Unlock:UtilityDelegate",
+            },
         }
     }
 }
 
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Operation {
+    type Err = MetadataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let operation = match s {
+            "Transfer:Owner" => Self::Transfer {
+                scenario: TransferScenario::Holder,
+            },
+            "Transfer:TransferDelegate" => Self::Transfer {
+                scenario: TransferScenario::TransferDelegate,
+            },
+            "Transfer:SaleDelegate" => Self::Transfer {
+                scenario: TransferScenario::SaleDelegate,
+            },
+            "Transfer:MigrationDelegate" => Self::Transfer {
+                scenario: TransferScenario::MigrationDelegate,
+            },
+            "Transfer:PermanentDelegate" => Self::Transfer {
+                scenario: TransferScenario::PermanentDelegate,
+            },
+            "Update:MetadataAuth" => Self::Update {
+                scenario: UpdateScenario::MetadataAuth,
+            },
+            "Update:Delegate" => Self::Update {
+                scenario: UpdateScenario::Delegate,
+            },
+            "Update:Proxy" => Self::Update {
+                scenario: UpdateScenario::Proxy,
+            },
+            "Delegate:Authority" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Authority),
+            },
+            "Delegate:Collection" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Collection),
+            },
+            "Delegate:Use" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Use),
+            },
+            "Delegate:Update" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Update),
+            },
+            "Delegate:ProgrammableConfig" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::ProgrammableConfig),
+            },
+            "Delegate:Data" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Data),
+            },
+            "Delegate:Uses" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::Uses),
+            },
+            "Delegate:CollectionFreeze" => Self::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::CollectionFreeze),
+            },
+            "Delegate:Sale" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Sale),
+            },
+            "Delegate:Transfer" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Transfer),
+            },
+            "Delegate:LockedTransfer" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::LockedTransfer),
+            },
+            "Delegate:Utility" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Utility),
+            },
+            "Delegate:Staking" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Staking),
+            },
+            "Delegate:Escrow" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Escrow),
+            },
+            "Delegate:Rental" => Self::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Rental),
+            },
+            "Unlock:UtilityDelegate" => Self::Unlock {
+                scenario: UnlockScenario::UtilityDelegate,
+            },
+            _ => return Err(MetadataError::InvalidOperationName),
+        };
+
+        Ok(operation)
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -471,3 +771,163 @@ impl<'info> ToAccountMeta for AccountInfo<'info> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operation_names_round_trip() {
+        let operations = [
+            Operation::Transfer {
+                scenario: TransferScenario::Holder,
+            },
+            Operation::Transfer {
+                scenario: TransferScenario::PermanentDelegate,
+            },
+            Operation::Update {
+                scenario: UpdateScenario::Proxy,
+            },
+            Operation::Delegate {
+                scenario: DelegateScenario::Metadata(MetadataDelegateRole::ProgrammableConfig),
+            },
+            Operation::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::LockedTransfer),
+            },
+            Operation::Unlock {
+                scenario: UnlockScenario::UtilityDelegate,
+            },
+        ];
+
+        for operation in operations {
+            assert_eq!(operation.to_string(), operation.as_str());
+            assert_eq!(Operation::from_str(operation.as_str()).unwrap(), operation);
+        }
+
+        assert_eq!(
+            Operation::Transfer {
+                scenario: TransferScenario::Holder
+            }
+            .as_str(),
+            "Transfer:Owner"
+        );
+        assert_eq!(
+            Operation::from_str("Transfer:Holder").unwrap_err(),
+            MetadataError::InvalidOperationName
+        );
+    }
+
+    #[test]
+    fn permanent_delegate_authority_type() {
+        let owner = Pubkey::new_unique();
+        let permanent_delegate = Pubkey::new_unique();
+        let token_account = Account {
+            owner,
+            amount: 1,
+            ..Default::default()
+        };
+
+        let authority_type = |authority: &Pubkey, permanent: Option<&Pubkey>| {
+            AuthorityType::get_authority_type(AuthorityRequest {
+                precedence: &[AuthorityType::Holder, AuthorityType::PermanentDelegate],
+                authority,
+                token_account: Some(&token_account),
+                permanent_delegate: permanent,
+                ..Default::default()
+            })
+            .unwrap()
+            .authority_type
+        };
+
+        assert_eq!(
+            authority_type(&permanent_delegate, Some(&permanent_delegate)),
+            AuthorityType::PermanentDelegate
+        );
+        assert_eq!(
+            authority_type(&permanent_delegate, None),
+            AuthorityType::None
+        );
+        // the holder takes precedence when it is also the permanent delegate
+        assert_eq!(authority_type(&owner, Some(&owner)), AuthorityType::Holder);
+    }
+
+    #[test]
+    fn token_record_delegate_expiry() {
+        let token_record = TokenRecord {
+            rule_set_revision: Some(1),
+            delegate: Some(Pubkey::new_unique()),
+            delegate_role: Some(TokenDelegateRole::Utility),
+            locked_transfer: Some(Pubkey::new_unique()),
+            delegate_expiry: Some(100),
+            staking_program: Some(Pubkey::new_unique()),
+            holder_locked: true,
+            holder_unlock_timestamp: 200,
+            collection_freeze_opt_in: true,
+            collection_frozen: true,
+            payer: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        let data = token_record.try_to_vec().unwrap();
+        assert_eq!(data.len(), TOKEN_RECORD_SIZE);
+        assert_eq!(TokenRecord::from_bytes(&data).unwrap(), token_record);
+
+        assert!(!token_record.is_delegate_expired(99));
+        assert!(token_record.is_delegate_expired(100));
+
+        assert!(!token_record.is_holder_unlockable(199));
+        assert!(token_record.is_holder_unlockable(200));
+
+        // records created before the payer was introduced
+        let legacy = &data[..TOKEN_RECORD_SIZE - PAYER_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert_eq!(legacy_record.payer, None);
+        assert!(legacy_record.collection_frozen);
+
+        // records created before the collection freeze was introduced
+        let legacy = &data[..TOKEN_RECORD_SIZE - PAYER_SIZE - COLLECTION_FREEZE_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert!(!legacy_record.collection_freeze_opt_in);
+        assert!(!legacy_record.collection_frozen);
+        assert!(legacy_record.holder_locked);
+
+        // records created before the holder lock was introduced
+        let legacy =
+            &data[..TOKEN_RECORD_SIZE - PAYER_SIZE - COLLECTION_FREEZE_SIZE - HOLDER_LOCK_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert!(!legacy_record.holder_locked);
+        assert_eq!(legacy_record.staking_program, token_record.staking_program);
+
+        // records created before the staking program was introduced
+        let legacy = &data[..TOKEN_RECORD_SIZE
+            - PAYER_SIZE
+            - COLLECTION_FREEZE_SIZE
+            - HOLDER_LOCK_SIZE
+            - STAKING_PROGRAM_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert_eq!(legacy_record.staking_program, None);
+        assert_eq!(legacy_record.delegate_expiry, token_record.delegate_expiry);
+
+        // records created before the expiry was introduced
+        let legacy = &data[..TOKEN_RECORD_SIZE
+            - PAYER_SIZE
+            - COLLECTION_FREEZE_SIZE
+            - HOLDER_LOCK_SIZE
+            - STAKING_PROGRAM_SIZE
+            - DELEGATE_EXPIRY_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert_eq!(legacy_record.delegate_expiry, None);
+        assert_eq!(legacy_record.locked_transfer, token_record.locked_transfer);
+        assert!(!legacy_record.is_delegate_expired(i64::MAX));
+
+        let legacy = &data[..TOKEN_RECORD_SIZE
+            - PAYER_SIZE
+            - COLLECTION_FREEZE_SIZE
+            - HOLDER_LOCK_SIZE
+            - STAKING_PROGRAM_SIZE
+            - DELEGATE_EXPIRY_SIZE
+            - LOCKED_TRANSFER_SIZE];
+        let legacy_record = TokenRecord::from_bytes(legacy).unwrap();
+        assert_eq!(legacy_record.locked_transfer, None);
+        assert_eq!(legacy_record.delegate, token_record.delegate);
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+pub const RULE_SET_PROPOSAL_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 8   // delay
++ 33  // rule set
++ 8; // committable at
+
+/// Minimum delay (in seconds) between proposing and committing a rule set.
+pub const MIN_RULE_SET_DELAY: i64 = 86_400;
+
+/// The `RuleSetProposal` account stores the rule set proposed for a programmable asset
+/// and the delay before it can be committed.
+///
+/// Changing the rule set of an existing asset requires a proposal: the new rule set can
+/// only be committed once the delay has elapsed, giving holders time to react to it. The
+/// delay is configured by the update authority and can only be increased. The seeds for
+/// the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"rule_set_proposal"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct RuleSetProposal {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Delay (in seconds) between proposing and committing a rule set.
+    pub delay: i64,
+    /// Proposed rule set (`None` when there is no pending proposal).
+    #[cfg_attr(
+        feature = "serde-feature",
+        serde(
+            deserialize_with = "deser_option_pubkey",
+            serialize_with = "ser_option_pubkey"
+        )
+    )]
+    pub rule_set: Option<Pubkey>,
+    /// Unix timestamp from which the proposed rule set can be committed.
+    pub committable_at: i64,
+}
+
+impl Default for RuleSetProposal {
+    fn default() -> Self {
+        Self {
+            key: Key::RuleSetProposal,
+            bump: 255,
+            mint: Pubkey::default(),
+            delay: MIN_RULE_SET_DELAY,
+            rule_set: None,
+            committable_at: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for RuleSetProposal {
+    fn key() -> Key {
+        Key::RuleSetProposal
+    }
+
+    fn size() -> usize {
+        RULE_SET_PROPOSAL_SIZE
+    }
+}
+
+impl RuleSetProposal {
+    pub fn from_bytes(data: &[u8]) -> Result<RuleSetProposal, ProgramError> {
+        let proposal: RuleSetProposal =
+            try_from_slice_checked(data, Key::RuleSetProposal, RULE_SET_PROPOSAL_SIZE)?;
+        Ok(proposal)
+    }
+
+    /// Proposes a rule set, restarting the delay of any pending proposal.
+    pub fn propose(&mut self, rule_set: Pubkey, now: i64) -> ProgramResult {
+        self.committable_at = now
+            .checked_add(self.delay)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        self.rule_set = Some(rule_set);
+
+        Ok(())
+    }
+
+    /// Indicates whether the pending rule set can be committed at the specified time.
+    pub fn is_committable(&self, now: i64) -> bool {
+        self.rule_set.is_some() && now >= self.committable_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_size_matches() {
+        let proposal = RuleSetProposal {
+            rule_set: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        let data = proposal.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), RULE_SET_PROPOSAL_SIZE);
+        assert_eq!(RuleSetProposal::from_bytes(&data).unwrap(), proposal);
+    }
+
+    #[test]
+    fn committable_after_delay() {
+        let mut proposal = RuleSetProposal::default();
+        assert!(!proposal.is_committable(i64::MAX));
+
+        proposal.propose(Pubkey::new_unique(), 1_000).unwrap();
+
+        assert!(!proposal.is_committable(1_000));
+        assert!(!proposal.is_committable(1_000 + MIN_RULE_SET_DELAY - 1));
+        assert!(proposal.is_committable(1_000 + MIN_RULE_SET_DELAY));
+    }
+}
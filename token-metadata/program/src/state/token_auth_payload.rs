@@ -2,6 +2,9 @@
 /// cannnot create it from the remote type from mpl-token-auth-rules.
 /// Care will need to be taken to ensure they stay synced with any changes in
 /// mpl-token-auth-rules.
+///
+/// When the `auth-rules` feature is disabled, they are also used as the (wire
+/// compatible) payload of the `AuthorizationData`.
 use std::collections::HashMap;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -12,22 +15,22 @@ use solana_program::pubkey::Pubkey;
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
-struct SeedsVec {
-    seeds: Vec<Vec<u8>>,
+pub struct SeedsVec {
+    pub seeds: Vec<Vec<u8>>,
 }
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
-struct LeafInfo {
-    leaf: [u8; 32],
-    proof: Vec<[u8; 32]>,
+pub struct LeafInfo {
+    pub leaf: [u8; 32],
+    pub proof: Vec<[u8; 32]>,
 }
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
-enum PayloadType {
+pub enum PayloadType {
     Pubkey(Pubkey),
     Seeds(SeedsVec),
     MerkleProof(LeafInfo),
@@ -37,6 +40,21 @@ enum PayloadType {
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Default)]
-struct Payload {
-    map: HashMap<String, PayloadType>,
+pub struct Payload {
+    pub map: HashMap<String, PayloadType>,
+}
+
+#[cfg(not(feature = "auth-rules"))]
+impl Payload {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, label: String, payload: PayloadType) -> Option<PayloadType> {
+        self.map.insert(label, payload)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PayloadType> {
+        self.map.get(key)
+    }
 }
@@ -0,0 +1,188 @@
+use solana_program::hash::hash;
+
+use super::*;
+
+pub const TOMBSTONE_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 32; // hash
+
+/// The edition tombstone is written over the master edition account, so it has the same
+/// size as a master edition.
+pub const EDITION_TOMBSTONE_SIZE: usize = MAX_MASTER_EDITION_LEN;
+
+/// The `Tombstone` account keeps the provenance of an archived metadata account.
+///
+/// When a metadata account is archived, its serialized data is replaced by a hash that
+/// allows the account to be restored from the original bytes. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"tombstone"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct Tombstone {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// SHA-256 hash of the serialized metadata.
+    pub hash: [u8; 32],
+}
+
+impl Default for Tombstone {
+    fn default() -> Self {
+        Self {
+            key: Key::Tombstone,
+            bump: 255,
+            mint: Pubkey::default(),
+            hash: [0u8; 32],
+        }
+    }
+}
+
+impl TokenMetadataAccount for Tombstone {
+    fn key() -> Key {
+        Key::Tombstone
+    }
+
+    fn size() -> usize {
+        TOMBSTONE_SIZE
+    }
+}
+
+impl Tombstone {
+    pub fn from_bytes(data: &[u8]) -> Result<Tombstone, ProgramError> {
+        let tombstone: Tombstone = try_from_slice_checked(data, Key::Tombstone, TOMBSTONE_SIZE)?;
+        Ok(tombstone)
+    }
+
+    /// Returns the hash stored on a tombstone for the serialized metadata.
+    pub fn hash_metadata(data: &[u8]) -> [u8; 32] {
+        hash(data).to_bytes()
+    }
+
+    /// Checks whether `data` is the serialized metadata that was archived.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        Self::hash_metadata(data) == self.hash
+    }
+}
+
+/// The `EditionTombstone` account marks a master edition that was burned while it still
+/// had prints (see `OrphanPrintsPolicy::AllowAndMark`).
+///
+/// The tombstone is written over the master edition account, so the prints keep a valid
+/// parent. Burning a print decrements the number of orphaned prints instead of the supply
+/// of the master edition; the tombstone is closed when its last print is burned and its
+/// rent is returned to the rent destination of the master edition burn. The seeds for the
+/// PDA are the seeds of the master edition:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. master edition mint id
+/// 4. `"edition"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct EditionTombstone {
+    /// Account key.
+    pub key: Key,
+    /// Address of the mint of the burned master edition.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub master_mint: Pubkey,
+    /// Destination of the rent of the tombstone once its last print is burned.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub rent_destination: Pubkey,
+    /// Number of prints of the master edition not yet burned.
+    pub orphaned_prints: u64,
+}
+
+impl Default for EditionTombstone {
+    fn default() -> Self {
+        Self {
+            key: Key::EditionTombstone,
+            master_mint: Pubkey::default(),
+            rent_destination: Pubkey::default(),
+            orphaned_prints: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for EditionTombstone {
+    fn key() -> Key {
+        Key::EditionTombstone
+    }
+
+    fn size() -> usize {
+        EDITION_TOMBSTONE_SIZE
+    }
+}
+
+impl EditionTombstone {
+    pub fn from_bytes(data: &[u8]) -> Result<EditionTombstone, ProgramError> {
+        let tombstone: EditionTombstone =
+            try_from_slice_checked(data, Key::EditionTombstone, EDITION_TOMBSTONE_SIZE)?;
+        Ok(tombstone)
+    }
+
+    /// Releases a burned print, returning whether it was the last orphaned print.
+    pub fn release_print(&mut self) -> Result<bool, ProgramError> {
+        self.orphaned_prints = self
+            .orphaned_prints
+            .checked_sub(1)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+
+        Ok(self.orphaned_prints == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_archived_metadata() {
+        let metadata = Metadata {
+            mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let data = metadata.try_to_vec().unwrap();
+
+        let tombstone = Tombstone {
+            mint: metadata.mint,
+            hash: Tombstone::hash_metadata(&data),
+            ..Default::default()
+        };
+        assert!(tombstone.matches(&data));
+
+        let mut tampered = data;
+        tampered[1] ^= 1;
+        assert!(!tombstone.matches(&tampered));
+
+        let mut buf = Vec::new();
+        tombstone.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), TOMBSTONE_SIZE);
+        assert_eq!(Tombstone::from_bytes(&buf).unwrap(), tombstone);
+    }
+
+    #[test]
+    fn edition_tombstone_releases_prints() {
+        let mut tombstone = EditionTombstone {
+            master_mint: Pubkey::new_unique(),
+            rent_destination: Pubkey::new_unique(),
+            orphaned_prints: 2,
+            ..Default::default()
+        };
+
+        let mut buf = tombstone.try_to_vec().unwrap();
+        EditionTombstone::pad_length(&mut buf).unwrap();
+        assert_eq!(EditionTombstone::from_bytes(&buf).unwrap(), tombstone);
+
+        assert!(!tombstone.release_print().unwrap());
+        assert!(tombstone.release_print().unwrap());
+        assert!(tombstone.release_print().is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+pub const TRANSFER_SESSION_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // authority
++ 32  // rule set
++ 9   // rule set revision
++ 32  // collection
++ 8; // expiry slot
+
+/// Maximum number of slots a transfer session can be open for (roughly one minute).
+pub const MAX_TRANSFER_SESSION_SLOTS: u64 = 150;
+
+/// The `TransferSession` account caches the result of the rule set validation of a holder
+/// transfer, so a burst of holder transfers of assets of the same collection and rule set
+/// (e.g. an editions drop) skips the Token Auth Rules CPI.
+///
+/// The session is opened by `BeginTransferSession`, which validates the rule set once, and is
+/// short-lived: it expires after at most `MAX_TRANSFER_SESSION_SLOTS` slots and is closed by
+/// `EndTransferSession`. Transfers of assets outside the verified collection of the session,
+/// or with another rule set or revision of the rule set, are validated in full. The seeds for
+/// the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. authority
+/// 4. `"transfer_session"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct TransferSession {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Holder that opened the session.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub authority: Pubkey,
+    /// Rule set validated by the session.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub rule_set: Pubkey,
+    /// Revision of the rule set validated by the session.
+    pub rule_set_revision: Option<u64>,
+    /// Verified collection of the assets covered by the session.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection: Pubkey,
+    /// Slot at which the session expires.
+    pub expiry_slot: u64,
+}
+
+impl Default for TransferSession {
+    fn default() -> Self {
+        Self {
+            key: Key::TransferSession,
+            bump: 255,
+            authority: Pubkey::default(),
+            rule_set: Pubkey::default(),
+            rule_set_revision: None,
+            collection: Pubkey::default(),
+            expiry_slot: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for TransferSession {
+    fn key() -> Key {
+        Key::TransferSession
+    }
+
+    fn size() -> usize {
+        TRANSFER_SESSION_SIZE
+    }
+}
+
+impl TransferSession {
+    pub fn from_bytes(data: &[u8]) -> Result<TransferSession, ProgramError> {
+        let session: TransferSession =
+            try_from_slice_checked(data, Key::TransferSession, TRANSFER_SESSION_SIZE)?;
+        Ok(session)
+    }
+
+    /// Checks whether the session has expired at the specified slot.
+    pub fn is_expired(&self, slot: u64) -> bool {
+        slot >= self.expiry_slot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    #[test]
+    fn transfer_session_serialized_size_matches() {
+        let session = TransferSession {
+            rule_set_revision: Some(1),
+            collection: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let data = session.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), TRANSFER_SESSION_SIZE);
+        assert_eq!(TransferSession::from_bytes(&data).unwrap(), session);
+    }
+
+    #[test]
+    fn transfer_session_expiry() {
+        let session = TransferSession {
+            expiry_slot: 100,
+            ..Default::default()
+        };
+
+        assert!(!session.is_expired(99));
+        assert!(session.is_expired(100));
+    }
+}
@@ -2,6 +2,15 @@ use super::*;
 
 pub const USE_AUTHORITY_RECORD_SIZE: usize = 18; //8 byte padding
 
+pub const USES_V2_SIZE: usize = 1 // key
++ 1   // bump
++ 32  // mint
++ 8   // cooldown slots
++ 8   // refill period slots
++ 8   // refill amount
++ 9   // last use slot
++ 8; // last refill slot
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, FromPrimitive)]
@@ -62,6 +71,101 @@ impl UseAuthorityRecord {
     }
 }
 
+/// The `UsesV2` account stores the rate limit of the uses of an asset: a cooldown between
+/// uses and a periodic refill of the remaining uses, both measured in slots.
+///
+/// The rate limit is enforced by `UseV2` on assets with the `rate_limited_uses` flag set on
+/// their metadata. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"uses_v2"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct UsesV2 {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Address of the mint.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Minimum number of slots between two uses (`0` for no cooldown).
+    pub cooldown_slots: u64,
+    /// Number of slots between two refills (`0` for no refill).
+    pub refill_period_slots: u64,
+    /// Number of uses added back at each refill, up to the total uses of the asset.
+    pub refill_amount: u64,
+    /// Slot of the last use (`None` when the asset was not used since the rate limit was set).
+    pub last_use_slot: Option<u64>,
+    /// Slot from which the next refill period is measured.
+    pub last_refill_slot: u64,
+}
+
+impl Default for UsesV2 {
+    fn default() -> Self {
+        Self {
+            key: Key::UsesV2,
+            bump: 255,
+            mint: Pubkey::default(),
+            cooldown_slots: 0,
+            refill_period_slots: 0,
+            refill_amount: 0,
+            last_use_slot: None,
+            last_refill_slot: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for UsesV2 {
+    fn key() -> Key {
+        Key::UsesV2
+    }
+
+    fn size() -> usize {
+        USES_V2_SIZE
+    }
+}
+
+impl UsesV2 {
+    pub fn from_bytes(data: &[u8]) -> Result<UsesV2, ProgramError> {
+        let uses: UsesV2 = try_from_slice_checked(data, Key::UsesV2, USES_V2_SIZE)?;
+        Ok(uses)
+    }
+
+    /// Adds back the uses of the refill periods elapsed at the specified slot.
+    pub fn refill(&mut self, uses: &mut Uses, slot: u64) {
+        if self.refill_period_slots == 0 || self.refill_amount == 0 {
+            return;
+        }
+
+        let periods = slot.saturating_sub(self.last_refill_slot) / self.refill_period_slots;
+
+        if periods > 0 {
+            uses.remaining = uses
+                .remaining
+                .saturating_add(periods.saturating_mul(self.refill_amount))
+                .min(uses.total);
+            // only whole periods are consumed, so a partial period carries over
+            self.last_refill_slot += periods * self.refill_period_slots;
+        }
+    }
+
+    /// Records a use at the specified slot, failing while the cooldown has not elapsed.
+    pub fn record_use(&mut self, slot: u64) -> ProgramResult {
+        if let Some(last_use_slot) = self.last_use_slot {
+            if slot < last_use_slot.saturating_add(self.cooldown_slots) {
+                return Err(MetadataError::UsesCooldown.into());
+            }
+        }
+
+        self.last_use_slot = Some(slot);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use borsh::BorshSerialize;
@@ -70,7 +174,10 @@ mod tests {
 
     use crate::{
         error::MetadataError,
-        state::{CollectionAuthorityRecord, Key, TokenMetadataAccount, UseAuthorityRecord},
+        state::{
+            CollectionAuthorityRecord, Key, TokenMetadataAccount, UseAuthorityRecord, UseMethod,
+            Uses, UsesV2, USES_V2_SIZE,
+        },
         ID,
     };
 
@@ -129,4 +236,60 @@ mod tests {
         let error = UseAuthorityRecord::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn uses_v2_serialized_size_matches() {
+        let uses_v2 = UsesV2 {
+            last_use_slot: Some(1_000),
+            ..Default::default()
+        };
+        let data = uses_v2.try_to_vec().unwrap();
+
+        assert_eq!(data.len(), USES_V2_SIZE);
+        assert_eq!(UsesV2::from_bytes(&data).unwrap(), uses_v2);
+    }
+
+    #[test]
+    fn uses_v2_cooldown() {
+        let mut uses_v2 = UsesV2 {
+            cooldown_slots: 10,
+            ..Default::default()
+        };
+
+        uses_v2.record_use(100).unwrap();
+
+        let error = uses_v2.record_use(109).unwrap_err();
+        assert_eq!(error, MetadataError::UsesCooldown.into());
+
+        uses_v2.record_use(110).unwrap();
+        assert_eq!(uses_v2.last_use_slot, Some(110));
+    }
+
+    #[test]
+    fn uses_v2_refill() {
+        let mut uses_v2 = UsesV2 {
+            refill_period_slots: 100,
+            refill_amount: 2,
+            last_refill_slot: 1_000,
+            ..Default::default()
+        };
+        let mut uses = Uses {
+            use_method: UseMethod::Multiple,
+            remaining: 0,
+            total: 5,
+        };
+
+        uses_v2.refill(&mut uses, 1_099);
+        assert_eq!(uses.remaining, 0);
+
+        // the partial period carries over to the next refill
+        uses_v2.refill(&mut uses, 1_150);
+        assert_eq!(uses.remaining, 2);
+        assert_eq!(uses_v2.last_refill_slot, 1_100);
+
+        // the remaining uses are capped by the total uses
+        uses_v2.refill(&mut uses, 1_500);
+        assert_eq!(uses.remaining, 5);
+        assert_eq!(uses_v2.last_refill_slot, 1_500);
+    }
 }
@@ -0,0 +1,85 @@
+//! Integration with the SPL Associated Token Account program.
+//!
+//! The integration is only available when the `ata` feature is enabled; otherwise,
+//! the program id is not recognized and associated token accounts cannot be derived
+//! nor created.
+
+#[cfg(feature = "ata")]
+use solana_program::program::invoke;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+#[cfg(feature = "ata")]
+use crate::assertions::assert_derivation;
+#[cfg(not(feature = "ata"))]
+use crate::error::MetadataError;
+
+/// Checks whether the `key` is the SPL Associated Token Account program id.
+#[cfg(feature = "ata")]
+pub fn is_spl_ata_program(key: &Pubkey) -> bool {
+    key == &crate::programs::SPL_ATA_PROGRAM
+}
+
+#[cfg(not(feature = "ata"))]
+pub fn is_spl_ata_program(_key: &Pubkey) -> bool {
+    false
+}
+
+/// Asserts that the `token_info` is the associated token account of the `wallet` and `mint`
+/// for the token program of the mint.
+#[cfg(feature = "ata")]
+pub fn assert_associated_token_account(
+    token_info: &AccountInfo,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> ProgramResult {
+    assert_derivation(
+        &spl_associated_token_account::id(),
+        token_info,
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+    )?;
+    Ok(())
+}
+
+#[cfg(not(feature = "ata"))]
+pub fn assert_associated_token_account(
+    _token_info: &AccountInfo,
+    _wallet: &Pubkey,
+    _mint: &Pubkey,
+    _token_program: &Pubkey,
+) -> ProgramResult {
+    Err(MetadataError::FeatureNotEnabled.into())
+}
+
+/// Creates the associated token account of the `wallet` and `mint` for the token program
+/// of the mint. The `account_infos` must include all accounts required by the SPL
+/// Associated Token Account program.
+#[cfg(feature = "ata")]
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    account_infos: &[AccountInfo],
+) -> ProgramResult {
+    #[allow(deprecated)]
+    let create_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        payer,
+        wallet,
+        mint,
+        token_program,
+    );
+
+    invoke(&create_ix, account_infos)
+}
+
+#[cfg(not(feature = "ata"))]
+pub fn create_associated_token_account(
+    _payer: &Pubkey,
+    _wallet: &Pubkey,
+    _mint: &Pubkey,
+    _token_program: &Pubkey,
+    _account_infos: &[AccountInfo],
+) -> ProgramResult {
+    Err(MetadataError::FeatureNotEnabled.into())
+}
@@ -9,16 +9,19 @@ pub fn increment_collection_size(
 ) -> ProgramResult {
     if let Some(ref details) = metadata.collection_details {
         match details {
-            CollectionDetails::V1 { size } => {
-                metadata.collection_details = Some(CollectionDetails::V1 {
-                    size: size
-                        .checked_add(1)
-                        .ok_or(MetadataError::NumericalOverflowError)?,
-                });
+            CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => {
+                metadata.collection_details = Some(
+                    details.with_size(
+                        size.checked_add(1)
+                            .ok_or(MetadataError::NumericalOverflowError)?,
+                    ),
+                );
                 msg!("Clean write collection metadata");
                 clean_write_metadata(metadata, metadata_info)?;
                 Ok(())
             }
+            // uncapped collections do not keep track of their size
+            CollectionDetails::Uncapped => Ok(()),
         }
     } else {
         msg!("No collection details. Can't increment.");
@@ -32,15 +35,17 @@ pub fn decrement_collection_size(
 ) -> ProgramResult {
     if let Some(ref details) = metadata.collection_details {
         match details {
-            CollectionDetails::V1 { size } => {
-                metadata.collection_details = Some(CollectionDetails::V1 {
-                    size: size
-                        .checked_sub(1)
-                        .ok_or(MetadataError::NumericalOverflowError)?,
-                });
+            CollectionDetails::V1 { size } | CollectionDetails::V2 { size, .. } => {
+                metadata.collection_details = Some(
+                    details.with_size(
+                        size.checked_sub(1)
+                            .ok_or(MetadataError::NumericalOverflowError)?,
+                    ),
+                );
                 clean_write_metadata(metadata, metadata_info)?;
                 Ok(())
             }
+            CollectionDetails::Uncapped => Ok(()),
         }
     } else {
         msg!("No collection details. Can't decrement.");
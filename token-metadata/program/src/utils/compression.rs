@@ -1,14 +1,40 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_utils::cmp_pubkeys;
-use solana_program::{account_info::AccountInfo, pubkey, pubkey::Pubkey};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    keccak,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey,
+    pubkey::Pubkey,
+};
 
-pub const BUBBLEGUM_PROGRAM_ADDRESS: Pubkey =
-    pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+use crate::{
+    error::MetadataError,
+    programs,
+    state::{Collection, Creator, TokenStandard, Uses},
+};
+
+pub const BUBBLEGUM_PROGRAM_ADDRESS: Pubkey = programs::BUBBLEGUM_PROGRAM;
 
 pub const BUBBLEGUM_SIGNER: Pubkey = pubkey!("4ewWZC5gT6TGpm5LZNDs9wVonfUT2q5PP5sc9kVbwMAK");
 
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ADDRESS: Pubkey =
+    programs::SPL_ACCOUNT_COMPRESSION_PROGRAM;
+
 // This flag activates certain program authority features of the Bubblegum program.
 pub const BUBBLEGUM_ACTIVATED: bool = true;
 
+/// Version of the leaf schema used by Bubblegum.
+const LEAF_SCHEMA_V1: u8 = 1;
+
+/// Anchor discriminator of the `verify_leaf` instruction of SPL Account Compression.
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
 pub fn find_compression_mint_authority(mint: &Pubkey) -> (Pubkey, u8) {
     let seeds = &[mint.as_ref()];
     Pubkey::find_program_address(seeds, &BUBBLEGUM_PROGRAM_ADDRESS)
@@ -24,3 +50,253 @@ pub fn is_decompression(mint: &AccountInfo, mint_authority_info: &AccountInfo) -
     }
     false
 }
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+/// Metadata of a compressed asset, as hashed into its leaf by Bubblegum.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CompressedMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+/// Returns the asset id of the compressed asset with the specified `nonce` on a tree.
+pub fn find_compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            "asset".as_bytes(),
+            merkle_tree.as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        &BUBBLEGUM_PROGRAM_ADDRESS,
+    )
+    .0
+}
+
+/// Returns the data hash of a leaf given the serialized metadata of the asset.
+pub fn hash_compressed_metadata_data(data: &[u8], seller_fee_basis_points: u16) -> [u8; 32] {
+    let metadata_hash = keccak::hashv(&[data]);
+
+    keccak::hashv(&[
+        &metadata_hash.to_bytes(),
+        &seller_fee_basis_points.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Returns the data hash of a leaf.
+pub fn hash_compressed_metadata(metadata: &CompressedMetadata) -> Result<[u8; 32], ProgramError> {
+    Ok(hash_compressed_metadata_data(
+        &metadata.try_to_vec()?,
+        metadata.seller_fee_basis_points,
+    ))
+}
+
+/// Returns the creator hash of a leaf.
+pub fn hash_compressed_creators(creators: &[Creator]) -> [u8; 32] {
+    let creator_data = creators
+        .iter()
+        .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
+        .collect::<Vec<_>>();
+
+    keccak::hashv(
+        creator_data
+            .iter()
+            .map(|c| c.as_slice())
+            .collect::<Vec<&[u8]>>()
+            .as_ref(),
+    )
+    .to_bytes()
+}
+
+/// Returns the hash of a (V1) leaf of a compressed asset.
+pub fn hash_compressed_leaf(
+    asset_id: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    nonce: u64,
+    data_hash: &[u8; 32],
+    creator_hash: &[u8; 32],
+) -> [u8; 32] {
+    keccak::hashv(&[
+        &[LEAF_SCHEMA_V1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        delegate.as_ref(),
+        nonce.to_le_bytes().as_ref(),
+        data_hash.as_ref(),
+        creator_hash.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Accounts and leaf information required to prove the collection of a compressed asset.
+pub struct CompressedCollectionProof<'a, 'b> {
+    /// Merkle tree account holding the asset.
+    pub merkle_tree_info: &'a AccountInfo<'b>,
+    /// SPL Account Compression program.
+    pub compression_program_info: &'a AccountInfo<'b>,
+    /// Proof nodes of the leaf, as accounts.
+    pub proof: &'a [AccountInfo<'b>],
+    /// Root of the tree the proof was generated for.
+    pub root: [u8; 32],
+    /// Owner of the asset.
+    pub owner: Pubkey,
+    /// Delegate of the asset.
+    pub delegate: Pubkey,
+    /// Nonce of the asset.
+    pub nonce: u64,
+    /// Index of the leaf on the tree.
+    pub index: u32,
+    /// Metadata of the asset.
+    pub metadata: &'a CompressedMetadata,
+}
+
+/// Verifies the leaf of a compressed asset against its merkle tree and returns the
+/// collection of the asset.
+///
+/// The leaf is rebuilt from the supplied metadata and verified by SPL Account Compression,
+/// so the returned collection is the one stored on-chain for the asset.
+pub fn verify_compressed_collection(
+    proof: &CompressedCollectionProof,
+) -> Result<Option<Collection>, ProgramError> {
+    if !cmp_pubkeys(
+        proof.compression_program_info.key,
+        &SPL_ACCOUNT_COMPRESSION_PROGRAM_ADDRESS,
+    ) {
+        return Err(MetadataError::InvalidCompressionProof.into());
+    }
+
+    if !cmp_pubkeys(
+        proof.merkle_tree_info.owner,
+        &SPL_ACCOUNT_COMPRESSION_PROGRAM_ADDRESS,
+    ) {
+        return Err(MetadataError::InvalidCompressionProof.into());
+    }
+
+    let asset_id = find_compressed_asset_id(proof.merkle_tree_info.key, proof.nonce);
+    let leaf = hash_compressed_leaf(
+        &asset_id,
+        &proof.owner,
+        &proof.delegate,
+        proof.nonce,
+        &hash_compressed_metadata(proof.metadata)?,
+        &hash_compressed_creators(&proof.metadata.creators),
+    );
+
+    let mut accounts = vec![AccountMeta::new_readonly(
+        *proof.merkle_tree_info.key,
+        false,
+    )];
+    accounts.extend(
+        proof
+            .proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node.key, false)),
+    );
+
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&proof.root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&proof.index.to_le_bytes());
+
+    let mut account_infos = vec![proof.merkle_tree_info.clone()];
+    account_infos.extend(proof.proof.iter().cloned());
+    account_infos.push(proof.compression_program_info.clone());
+
+    invoke(
+        &Instruction {
+            program_id: SPL_ACCOUNT_COMPRESSION_PROGRAM_ADDRESS,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )
+    .map_err(|_| MetadataError::InvalidCompressionProof)?;
+
+    Ok(proof.metadata.collection.clone())
+}
+
+/// Checks that a compressed asset is a verified member of the collection `collection_mint`.
+pub fn assert_compressed_collection_member(
+    proof: &CompressedCollectionProof,
+    collection_mint: &Pubkey,
+) -> ProgramResult {
+    match verify_compressed_collection(proof)? {
+        Some(collection) if collection.verified && collection.key == *collection_mint => Ok(()),
+        _ => Err(MetadataError::NotAMemberOfCollection.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaf_hash_commits_to_collection() {
+        let mut metadata = CompressedMetadata {
+            name: String::from("Compressed"),
+            symbol: String::from("CMP"),
+            uri: String::from("https://compressed.asset.org"),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: Some(Collection {
+                verified: true,
+                key: Pubkey::new_unique(),
+            }),
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![],
+        };
+
+        let asset_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let creator_hash = hash_compressed_creators(&metadata.creators);
+
+        let leaf = hash_compressed_leaf(
+            &asset_id,
+            &owner,
+            &owner,
+            0,
+            &hash_compressed_metadata(&metadata).unwrap(),
+            &creator_hash,
+        );
+
+        metadata.collection = Some(Collection {
+            verified: true,
+            key: Pubkey::new_unique(),
+        });
+
+        let other = hash_compressed_leaf(
+            &asset_id,
+            &owner,
+            &owner,
+            0,
+            &hash_compressed_metadata(&metadata).unwrap(),
+            &creator_hash,
+        );
+
+        assert_ne!(leaf, other);
+    }
+}
@@ -0,0 +1,77 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+    sysvar::instructions::get_instruction_relative,
+};
+
+use crate::{error::MetadataError, programs::ED25519_PROGRAM};
+
+/// Size of the header of the Ed25519 program instruction (number of signatures and padding).
+const ED25519_HEADER_SIZE: usize = 2;
+
+/// Size of the offsets of each signature of the Ed25519 program instruction.
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Instruction index used by the Ed25519 program offsets to reference its own data.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Returns the public keys that signed the `message` in the Ed25519 program instruction
+/// immediately preceding the current instruction.
+///
+/// The Ed25519 program fails the transaction when any of its signatures is invalid, so the
+/// signatures are already verified; only signatures whose public key and message are part of
+/// the Ed25519 instruction data are considered.
+pub fn ed25519_signers(
+    sysvar_instructions_info: &AccountInfo,
+    message: &[u8],
+) -> Result<Vec<Pubkey>, ProgramError> {
+    let ed25519_ix = get_instruction_relative(-1, sysvar_instructions_info)
+        .map_err(|_| MetadataError::InvalidMintAuthoritySignature)?;
+
+    if ed25519_ix.program_id != ED25519_PROGRAM {
+        return Err(MetadataError::InvalidMintAuthoritySignature.into());
+    }
+
+    let data = ed25519_ix.data.as_slice();
+    let count = *data
+        .first()
+        .ok_or(MetadataError::InvalidMintAuthoritySignature)? as usize;
+
+    let mut signers = Vec::with_capacity(count);
+
+    for index in 0..count {
+        let start = ED25519_HEADER_SIZE + index * ED25519_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..start + ED25519_OFFSETS_SIZE)
+            .ok_or(MetadataError::InvalidMintAuthoritySignature)?;
+        let read = |position: usize| u16::from_le_bytes([offsets[position], offsets[position + 1]]);
+
+        // signature, public key and message must all be in the Ed25519 instruction
+        if read(2) != CURRENT_INSTRUCTION
+            || read(6) != CURRENT_INSTRUCTION
+            || read(12) != CURRENT_INSTRUCTION
+        {
+            continue;
+        }
+
+        let public_key_offset = read(4) as usize;
+        let message_offset = read(8) as usize;
+        let message_size = read(10) as usize;
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(MetadataError::InvalidMintAuthoritySignature)?;
+        let signed_message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(MetadataError::InvalidMintAuthoritySignature)?;
+
+        if signed_message == message {
+            signers.push(Pubkey::new_from_array(
+                public_key
+                    .try_into()
+                    .map_err(|_| MetadataError::InvalidMintAuthoritySignature)?,
+            ));
+        }
+    }
+
+    Ok(signers)
+}
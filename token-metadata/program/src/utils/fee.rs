@@ -0,0 +1,26 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, pubkey::Pubkey,
+    system_instruction,
+};
+
+use crate::{assertions::assert_keys_equal, pda::ProgramContext, state::CREATE_FEE};
+
+/// Transfers the create fee from the payer to the fee vault.
+pub fn charge_create_fee<'a>(
+    program_id: &Pubkey,
+    fee_vault_info: &'a AccountInfo<'a>,
+    payer_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+) -> ProgramResult {
+    let (fee_vault, _) = ProgramContext::new(*program_id).find_fee_vault_account();
+    assert_keys_equal(&fee_vault, fee_vault_info.key)?;
+
+    invoke(
+        &system_instruction::transfer(payer_info.key, fee_vault_info.key, CREATE_FEE),
+        &[
+            payer_info.clone(),
+            fee_vault_info.clone(),
+            system_program_info.clone(),
+        ],
+    )
+}
@@ -0,0 +1,72 @@
+use mpl_utils::cmp_pubkeys;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey, pubkey::Pubkey,
+    sysvar::instructions::get_instruction_relative,
+};
+
+use crate::error::MetadataError;
+
+/// SPL Governance programs allowed to control the update authority of an asset.
+pub const GOVERNANCE_PROGRAM_ADDRESSES: [Pubkey; 1] =
+    [pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw")];
+
+/// Checks whether the account is a governance account, i.e., it is owned by one of the
+/// allowed governance programs.
+pub fn is_governance_account(account_info: &AccountInfo) -> bool {
+    GOVERNANCE_PROGRAM_ADDRESSES
+        .iter()
+        .any(|program_id| cmp_pubkeys(account_info.owner, program_id))
+}
+
+/// Discriminator of the `ExecuteTransaction` instruction of the governance program.
+const EXECUTE_TRANSACTION_DISCRIMINATOR: u8 = 16;
+
+/// Account type of a `ProposalTransactionV2` account of the governance program.
+const PROPOSAL_TRANSACTION_ACCOUNT_TYPE: u8 = 13;
+
+/// Asserts that the current instruction is the execution of a proposal transaction by the
+/// governance program that owns the `governance_info` account.
+///
+/// The top-level instruction must be an `ExecuteTransaction` of the governance program, with
+/// the governance, proposal and proposal transaction as its first accounts. The proposal
+/// transaction must be owned by the governance program and belong to the proposal being
+/// executed, so the governance account only acts as update authority through an approved
+/// proposal.
+pub fn assert_governance_execution(
+    governance_info: &AccountInfo,
+    proposal_transaction_info: &AccountInfo,
+    sysvar_instructions_info: &AccountInfo,
+) -> ProgramResult {
+    let current_ix = get_instruction_relative(0, sysvar_instructions_info)?;
+
+    if !cmp_pubkeys(&current_ix.program_id, governance_info.owner)
+        || current_ix.data.first() != Some(&EXECUTE_TRANSACTION_DISCRIMINATOR)
+    {
+        return Err(MetadataError::InvalidGovernanceExecution.into());
+    }
+
+    let proposal = match current_ix.accounts.as_slice() {
+        [governance, proposal, proposal_transaction, ..]
+            if cmp_pubkeys(&governance.pubkey, governance_info.key)
+                && cmp_pubkeys(&proposal_transaction.pubkey, proposal_transaction_info.key) =>
+        {
+            proposal.pubkey
+        }
+        _ => return Err(MetadataError::InvalidGovernanceExecution.into()),
+    };
+
+    if !cmp_pubkeys(proposal_transaction_info.owner, governance_info.owner) {
+        return Err(MetadataError::InvalidGovernanceExecution.into());
+    }
+
+    // the proposal transaction starts with its account type and the proposal it belongs to
+    let data = proposal_transaction_info.try_borrow_data()?;
+
+    if data.first() != Some(&PROPOSAL_TRANSACTION_ACCOUNT_TYPE)
+        || data.get(1..33) != Some(proposal.as_ref())
+    {
+        return Err(MetadataError::InvalidGovernanceExecution.into());
+    }
+
+    Ok(())
+}
@@ -8,14 +8,14 @@ use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_token::state::{Account, Mint};
+use spl_token::state::Account;
 
 use super::*;
 use crate::{
     assertions::{
-        assert_derivation, assert_initialized, assert_mint_authority_matches_mint, assert_owned_by,
-        assert_token_program_matches_package, edition::assert_edition_valid,
-        metadata::assert_update_authority_is_correct,
+        assert_derivation, assert_initialized, assert_initialized_mint,
+        assert_mint_authority_matches_mint, assert_owned_by, assert_token_program_matches_package,
+        edition::assert_edition_valid, metadata::assert_update_authority_is_correct,
     },
     error::MetadataError,
     state::{
@@ -23,6 +23,7 @@ use crate::{
         TokenMetadataAccount, Uses, EDITION, EDITION_MARKER_BIT_SIZE, MAX_EDITION_LEN,
         MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, PREFIX,
     },
+    utils::clean_write_metadata,
 };
 
 pub struct MintNewEditionFromMasterEditionViaTokenLogicArgs<'a> {
@@ -380,6 +381,11 @@ pub fn mint_limited_edition<'a>(
     if mint_supply != 1 {
         return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
     }
+    // the creators overflow of the master edition cannot be copied to the print
+    if master_metadata.overflow_creators_share.is_some() {
+        return Err(MetadataError::FeatureNotSupported.into());
+    }
+
     let master_data = master_metadata.data;
     // bundle data into v2
     let data_v2 = DataV2 {
@@ -407,6 +413,8 @@ pub fn mint_limited_edition<'a>(
             payer_account_info,
             update_authority_info,
             system_account_info,
+            fee_vault_info: None,
+            mint_authority_approved: false,
         },
         data_v2,
         true,
@@ -480,7 +488,7 @@ pub fn create_master_edition<'a>(
     max_supply: Option<u64>,
 ) -> ProgramResult {
     let metadata = Metadata::from_account_info(metadata_account_info)?;
-    let mint: Mint = assert_initialized(mint_info)?;
+    let mint = assert_initialized_mint(mint_info)?;
 
     let bump_seed = assert_derivation(
         program_id,
@@ -496,7 +504,7 @@ pub fn create_master_edition<'a>(
     assert_token_program_matches_package(token_program_info)?;
     assert_mint_authority_matches_mint(&mint.mint_authority, mint_authority_info)?;
     assert_owned_by(metadata_account_info, program_id)?;
-    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(mint_info, token_program_info.key)?;
 
     if metadata.mint != *mint_info.key {
         return Err(MetadataError::MintMismatch.into());
@@ -539,7 +547,7 @@ pub fn create_master_edition<'a>(
     if metadata_account_info.is_writable {
         let mut metadata_mut = Metadata::from_account_info(metadata_account_info)?;
         metadata_mut.token_standard = Some(TokenStandard::NonFungible);
-        metadata_mut.save(&mut metadata_account_info.try_borrow_mut_data()?)?;
+        clean_write_metadata(&mut metadata_mut, metadata_account_info)?;
     }
 
     // while you can't mint only mint 1 token from your master record, you can
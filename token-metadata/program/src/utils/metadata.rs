@@ -2,18 +2,19 @@ use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize}
 use mpl_utils::{create_or_allocate_account_raw, token::get_mint_authority};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey,
+    rent::Rent, sysvar::Sysvar,
 };
 
 use super::{compression::is_decompression, *};
 use crate::{
     assertions::{
-        assert_mint_authority_matches_mint, assert_owned_by,
+        assert_mint_authority_matches_mint, assert_owned_by_token_program,
         collection::assert_collection_update_is_valid, metadata::assert_data_valid,
         uses::assert_valid_use,
     },
     state::{
         Collection, CollectionDetails, Data, DataV2, Key, Metadata, ProgrammableConfig,
-        TokenStandard, Uses, EDITION, MAX_METADATA_LEN, PREFIX,
+        TokenStandard, Uses, EDITION, MAX_METADATA_LEN, METADATA_EXTENSION_V1, PREFIX,
     },
 };
 
@@ -40,6 +41,12 @@ pub struct CreateMetadataAccountsLogicArgs<'a> {
     pub payer_account_info: &'a AccountInfo<'a>,
     pub update_authority_info: &'a AccountInfo<'a>,
     pub system_account_info: &'a AccountInfo<'a>,
+    /// Fee vault, when the create fee is paid.
+    pub fee_vault_info: Option<&'a AccountInfo<'a>>,
+    /// Whether the creation was already approved on behalf of the mint authority (e.g.,
+    /// through a signed message or by the freeze authority), in which case the mint
+    /// authority account is not required to match the mint or sign the transaction.
+    pub mint_authority_approved: bool,
 }
 
 /// Create a new account instruction
@@ -60,6 +67,8 @@ pub fn process_create_metadata_accounts_logic(
         payer_account_info,
         update_authority_info,
         system_account_info,
+        fee_vault_info,
+        mint_authority_approved,
     } = accounts;
 
     let mut update_authority_key = *update_authority_info.key;
@@ -68,22 +77,24 @@ pub fn process_create_metadata_accounts_logic(
     // IMPORTANT NOTE:
     // This allows the Metaplex Foundation to Create but not update metadata for SPL tokens that
     // have not populated their metadata.
-    assert_mint_authority_matches_mint(&existing_mint_authority, mint_authority_info).or_else(
-        |e| {
-            // Allow seeding by the authority seed populator
-            if mint_authority_info.key == &SEED_AUTHORITY && mint_authority_info.is_signer {
-                // When metadata is seeded, the mint authority should be able to change it
-                if let COption::Some(auth) = existing_mint_authority {
-                    update_authority_key = auth;
-                    is_mutable = true;
+    if !mint_authority_approved {
+        assert_mint_authority_matches_mint(&existing_mint_authority, mint_authority_info).or_else(
+            |e| {
+                // Allow seeding by the authority seed populator
+                if mint_authority_info.key == &SEED_AUTHORITY && mint_authority_info.is_signer {
+                    // When metadata is seeded, the mint authority should be able to change it
+                    if let COption::Some(auth) = existing_mint_authority {
+                        update_authority_key = auth;
+                        is_mutable = true;
+                    }
+                    Ok(())
+                } else {
+                    Err(e)
                 }
-                Ok(())
-            } else {
-                Err(e)
-            }
-        },
-    )?;
-    assert_owned_by(mint_info, &spl_token::id())?;
+            },
+        )?;
+    }
+    assert_owned_by_token_program(mint_info)?;
 
     let metadata_seeds = &[
         PREFIX.as_bytes(),
@@ -149,6 +160,17 @@ pub fn process_create_metadata_accounts_logic(
             CollectionDetails::V1 { size: _size } => {
                 metadata.collection_details = Some(CollectionDetails::V1 { size: 0 });
             }
+            CollectionDetails::Uncapped => {
+                metadata.collection_details = Some(CollectionDetails::Uncapped);
+            }
+            CollectionDetails::V2 {
+                enforce_denylist, ..
+            } => {
+                metadata.collection_details = Some(CollectionDetails::V2 {
+                    size: 0,
+                    enforce_denylist,
+                });
+            }
         }
     } else {
         metadata.collection_details = None;
@@ -176,6 +198,17 @@ pub fn process_create_metadata_accounts_logic(
     ];
     let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
     metadata.edition_nonce = Some(edition_bump_seed);
+
+    if let Some(fee_vault_info) = fee_vault_info {
+        charge_create_fee(
+            program_id,
+            fee_vault_info,
+            payer_account_info,
+            system_account_info,
+        )?;
+        metadata.fee_flag = true;
+    }
+
     // saves the changes to the account data
     metadata.save(&mut metadata_account_info.data.borrow_mut())?;
 
@@ -213,6 +246,14 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     let programmable_config_res: Result<Option<ProgrammableConfig>, BorshError> =
         BorshDeserialize::deserialize(buf);
 
+    // Metadata extension, which can only be located after a valid programmable config
+    let extension: Option<([u8; 4], u8, Option<Pubkey>, Option<u8>, Option<[u8; 32]>)> =
+        if programmable_config_res.is_ok() {
+            BorshDeserialize::deserialize(buf).ok()
+        } else {
+            None
+        };
+
     // We can have accidentally valid, but corrupted data, particularly on the Collection struct,
     // so to increase probability of catching errors. If any of these deserializations fail, set
     // all values to None.
@@ -232,7 +273,19 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     // Programmable Config
     let programmable_config = programmable_config_res.unwrap_or(None);
 
-    let metadata = Metadata {
+    // Metadata Extension: the padding of accounts written before the extension can hold
+    // arbitrary data, so the extension is only read when it starts with a known marker and
+    // does not set unknown flags.
+    let (extension_flags, permanent_delegate, overflow_creators_share, uri_hash) = match extension {
+        Some((marker, flags, permanent_delegate, overflow_creators_share, uri_hash))
+            if marker == METADATA_EXTENSION_V1 && Metadata::is_valid_extension_flags(flags) =>
+        {
+            (flags, permanent_delegate, overflow_creators_share, uri_hash)
+        }
+        _ => (0, None, None, None),
+    };
+
+    let mut metadata = Metadata {
         key,
         update_authority,
         mint,
@@ -245,20 +298,70 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         uses,
         collection_details,
         programmable_config,
+        inherit_royalties: false,
+        use_default_rule_set: false,
+        permanent_delegate,
+        unverify_creators_on_authority_change: false,
+        fee_flag: false,
+        overflow_creators_share,
+        programmable_config_in_extension: false,
+        uri_hash,
+        rate_limited_uses: false,
     };
+    metadata.set_extension_flags(extension_flags);
 
     Ok(metadata)
 }
 
+/// Writes the metadata to the account, growing the account if needed.
+///
+/// Resized accounts only have space for their contents, so they can only grow when they
+/// already hold enough lamports to remain rent exempt at the new size; instructions that
+/// have a payer should use `clean_write_metadata_with_payer` instead.
 pub fn clean_write_metadata(
     metadata: &mut Metadata,
     metadata_account_info: &AccountInfo,
 ) -> ProgramResult {
+    let data = metadata.try_to_vec()?;
+
+    if data.len() > metadata_account_info.data_len() {
+        if metadata_account_info.lamports() < Rent::get()?.minimum_balance(data.len()) {
+            return Err(MetadataError::NotRentExempt.into());
+        }
+
+        metadata_account_info.realloc(data.len(), false)?;
+    }
+
+    write_metadata_data(&data, metadata_account_info)
+}
+
+/// Writes the metadata to the account, growing the account if needed and transferring
+/// the rent of the additional space from the payer.
+pub fn clean_write_metadata_with_payer<'a>(
+    metadata: &mut Metadata,
+    metadata_account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let data = metadata.try_to_vec()?;
+
+    if data.len() > metadata_account_info.data_len() {
+        resize_or_reallocate_account_raw(
+            metadata_account_info,
+            payer_info,
+            system_program_info,
+            data.len(),
+        )?;
+    }
+
+    write_metadata_data(&data, metadata_account_info)
+}
+
+fn write_metadata_data(data: &[u8], metadata_account_info: &AccountInfo) -> ProgramResult {
     // Clear all data to ensure it is serialized cleanly with no trailing data due to creators array resizing.
     let mut metadata_account_info_data = metadata_account_info.try_borrow_mut_data()?;
     metadata_account_info_data[0..].fill(0);
-
-    metadata.serialize(&mut *metadata_account_info_data)?;
+    metadata_account_info_data[..data.len()].copy_from_slice(data);
 
     Ok(())
 }
@@ -268,7 +371,10 @@ pub mod tests {
     use solana_program::pubkey;
 
     use super::*;
-    pub use crate::{state::Creator, utils::puff_out_data_fields};
+    pub use crate::{
+        state::{BasisPoints, Creator},
+        utils::puff_out_data_fields,
+    };
 
     // Pesky Penguins #8060 (NOOT!)
     // Corrupted data that can't be deserialized with the standard BoshDeserialization implementation.
@@ -330,7 +436,7 @@ pub mod tests {
             name: "Pesky Penguins #8060".to_string(),
             symbol: "NOOT".to_string(),
             uri: "https://arweave.net/HzOnfNMWQBHT9v0DW8Er9YFwdiGJX4-uK9RSYAR8fxE".to_string(),
-            seller_fee_basis_points: 500,
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
             creators: Some(creators),
         };
 
@@ -347,6 +453,15 @@ pub mod tests {
             uses: None,
             collection_details: None,
             programmable_config: None,
+            inherit_royalties: false,
+            use_default_rule_set: false,
+            permanent_delegate: None,
+            unverify_creators_on_authority_change: false,
+            fee_flag: false,
+            overflow_creators_share: None,
+            programmable_config_in_extension: false,
+            uri_hash: None,
+            rate_limited_uses: false,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -1,15 +1,24 @@
+pub(crate) mod associated_token;
 pub(crate) mod collection;
 pub(crate) mod compression;
+pub(crate) mod ed25519;
+pub(crate) mod fee;
+pub(crate) mod governance;
 pub(crate) mod master_edition;
 pub(crate) mod metadata;
 pub(crate) mod programmable_asset;
+pub(crate) mod royalties;
 
+pub use associated_token::*;
 pub use collection::*;
 pub use compression::*;
+pub use ed25519::*;
+pub use fee::*;
+pub use governance::*;
 pub use master_edition::*;
 pub use metadata::*;
 pub use mpl_utils::{
-    assert_signer, close_account_raw, create_or_allocate_account_raw,
+    assert_signer, close_account_raw, cmp_pubkeys, create_or_allocate_account_raw,
     resize_or_reallocate_account_raw,
     token::{
         get_mint_authority, get_mint_decimals, get_mint_freeze_authority, get_mint_supply,
@@ -17,11 +26,12 @@ pub use mpl_utils::{
     },
 };
 pub use programmable_asset::*;
+pub(crate) use royalties::*;
 use solana_program::{
     account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult,
     program::invoke_signed, program_error::ProgramError, pubkey::Pubkey, system_program,
 };
-use spl_token::instruction::{set_authority, AuthorityType};
+use spl_token_2022::instruction::{set_authority, AuthorityType};
 
 pub use crate::assertions::{
     assert_delegated_tokens, assert_derivation, assert_freeze_authority_matches_mint,
@@ -182,18 +192,40 @@ pub fn zero_account(s: &str, size: usize) -> String {
     s.to_owned() + std::str::from_utf8(&array_of_zeroes).unwrap()
 }
 
-pub fn close_program_account<'a>(
+/// Closes a program account, sending its lamports to the destination account.
+///
+/// The account must be owned by Token Metadata and its discriminant must match the
+/// `expected_key_type`; the data is zeroed before the lamports are moved so the account
+/// cannot be revived with stale data in the same transaction.
+pub fn close_account_checked<'a>(
     account_info: &AccountInfo<'a>,
     funds_dest_account_info: &AccountInfo<'a>,
+    expected_key_type: Key,
 ) -> ProgramResult {
+    assert_owned_by(account_info, &crate::ID)?;
+
+    if cmp_pubkeys(account_info.key, funds_dest_account_info.key) {
+        return Err(MetadataError::InvalidCloseDestination.into());
+    }
+
+    {
+        let mut data = account_info.try_borrow_mut_data()?;
+
+        if data.first() != Some(&(expected_key_type as u8)) {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        data.fill(0);
+    }
+
     // Transfer lamports from the account to the destination account.
     let dest_starting_lamports = funds_dest_account_info.lamports();
     **funds_dest_account_info.lamports.borrow_mut() = dest_starting_lamports
         .checked_add(account_info.lamports())
-        .unwrap();
+        .ok_or(MetadataError::NumericalOverflowError)?;
     **account_info.lamports.borrow_mut() = 0;
 
-    // Realloc the account data size to 0 bytes and teassign ownership of
+    // Realloc the account data size to 0 bytes and reassign ownership of
     // the account to the system program
     account_info.realloc(0, false)?;
     account_info.assign(&system_program::ID);
@@ -204,16 +236,20 @@ pub fn close_program_account<'a>(
 #[cfg(test)]
 mod tests {
     pub use solana_program::pubkey::Pubkey;
+    use solana_program::{account_info::AccountInfo, program_error::ProgramError};
 
     use crate::{
+        error::MetadataError,
         state::MAX_METADATA_LEN,
         utils::{
+            close_account_checked,
             metadata::tests::{expected_pesky_metadata, pesky_data},
             try_from_slice_checked,
         },
+        ID,
     };
     pub use crate::{
-        state::{Data, Key, Metadata},
+        state::{BasisPoints, Data, Key, Metadata},
         utils::{puff_out_data_fields, puffed_out_string},
     };
 
@@ -245,7 +281,7 @@ mod tests {
                 name: "Garfield".to_string(),
                 symbol: "GARF".to_string(),
                 uri: "https://garfiel.de".to_string(),
-                seller_fee_basis_points: 0,
+                seller_fee_basis_points: BasisPoints::default(),
                 creators: None,
             },
             primary_sale_happened: false,
@@ -256,6 +292,15 @@ mod tests {
             token_standard: None,
             collection_details: None,
             programmable_config: None,
+            inherit_royalties: false,
+            use_default_rule_set: false,
+            permanent_delegate: None,
+            unverify_creators_on_authority_change: false,
+            fee_flag: false,
+            overflow_creators_share: None,
+            programmable_config_in_extension: false,
+            uri_hash: None,
+            rate_limited_uses: false,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -271,7 +316,7 @@ mod tests {
         assert_eq!(name.as_str(), "Garfield\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}");
         assert_eq!(symbol.as_str(), "GARF\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}");
         assert_eq!(uri.as_str(), "https://garfiel.de\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}\u{0}");
-        assert_eq!(seller_fee_basis_points, 0);
+        assert_eq!(seller_fee_basis_points.value(), 0);
         assert_eq!(creators, None);
     }
 
@@ -287,4 +332,44 @@ mod tests {
 
         assert_eq!(metadata, expected_metadata);
     }
+
+    #[test]
+    fn close_account_checked_rejects_wrong_key_type() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![Key::TokenRecord as u8, 1, 2, 3];
+        let account_info =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data, &ID, false, 0);
+
+        let destination = Pubkey::new_unique();
+        let mut destination_lamports = 0;
+        let mut destination_data = vec![];
+        let system_program = solana_program::system_program::ID;
+        let destination_info = AccountInfo::new(
+            &destination,
+            false,
+            true,
+            &mut destination_lamports,
+            &mut destination_data,
+            &system_program,
+            false,
+            0,
+        );
+
+        let error = close_account_checked(&account_info, &destination_info, Key::MetadataDelegate)
+            .unwrap_err();
+        assert_eq!(error, ProgramError::from(MetadataError::DataTypeMismatch));
+
+        // nothing is moved or cleared when the check fails
+        assert_eq!(account_info.lamports(), 1_000_000);
+        assert_eq!(destination_info.lamports(), 0);
+        assert_eq!(account_info.data.borrow()[0], Key::TokenRecord as u8);
+
+        let error =
+            close_account_checked(&account_info, &account_info, Key::TokenRecord).unwrap_err();
+        assert_eq!(
+            error,
+            ProgramError::from(MetadataError::InvalidCloseDestination)
+        );
+    }
 }
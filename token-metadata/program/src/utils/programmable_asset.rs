@@ -1,3 +1,4 @@
+#[cfg(feature = "auth-rules")]
 use mpl_token_auth_rules::{
     instruction::{builders::ValidateBuilder, InstructionBuilder, ValidateArgs},
     payload::PayloadType,
@@ -7,16 +8,20 @@ use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
     program_error::ProgramError, pubkey::Pubkey,
 };
-use spl_token::instruction::{freeze_account, thaw_account};
+use spl_token_2022::instruction::{freeze_account, thaw_account};
 
+#[cfg(not(feature = "auth-rules"))]
+use crate::processor::PayloadType;
+#[cfg(feature = "auth-rules")]
+use crate::state::ToAccountMeta;
 use crate::{
     assertions::{assert_derivation, programmable::assert_valid_authorization},
     error::MetadataError,
     pda::{EDITION, PREFIX},
     processor::{AuthorizationData, TransferScenario},
     state::{
-        Operation, PayloadKey, ProgrammableConfig, Resizable, ToAccountMeta, TokenMetadataAccount,
-        TokenRecord, TOKEN_RECORD_SEED,
+        Operation, PayloadKey, ProgrammableConfig, Resizable, TokenMetadataAccount, TokenRecord,
+        TOKEN_RECORD_SEED,
     },
 };
 
@@ -60,6 +65,7 @@ pub fn create_token_record_account<'a>(
 
     let token_record = TokenRecord {
         bump: bump[0],
+        payer: Some(*payer_info.key),
         ..Default::default()
     };
 
@@ -129,6 +135,30 @@ pub fn thaw<'a>(
     Ok(())
 }
 
+/// Checks whether the `key` is the Token Auth Rules program id. The program id is only
+/// recognized when the `auth-rules` feature is enabled.
+#[cfg(feature = "auth-rules")]
+pub fn is_auth_rules_program(key: &Pubkey) -> bool {
+    key == &crate::programs::AUTH_RULES_PROGRAM
+}
+
+#[cfg(not(feature = "auth-rules"))]
+pub fn is_auth_rules_program(_key: &Pubkey) -> bool {
+    false
+}
+
+/// Returns the latest revision of the rule set.
+#[cfg(feature = "auth-rules")]
+pub fn get_latest_revision(rule_set_info: &AccountInfo) -> Result<Option<usize>, ProgramError> {
+    mpl_token_auth_rules::utils::get_latest_revision(rule_set_info)
+}
+
+#[cfg(not(feature = "auth-rules"))]
+pub fn get_latest_revision(_rule_set_info: &AccountInfo) -> Result<Option<usize>, ProgramError> {
+    Err(MetadataError::FeatureNotEnabled.into())
+}
+
+#[cfg(feature = "auth-rules")]
 pub fn validate<'a>(
     ruleset: &'a AccountInfo<'a>,
     operation: Operation,
@@ -160,6 +190,18 @@ pub fn validate<'a>(
     invoke_signed(&validate_ix, account_infos.as_slice(), &[])
 }
 
+#[cfg(not(feature = "auth-rules"))]
+pub fn validate<'a>(
+    _ruleset: &'a AccountInfo<'a>,
+    _operation: Operation,
+    _mint_info: &'a AccountInfo<'a>,
+    _additional_rule_accounts: Vec<&'a AccountInfo<'a>>,
+    _auth_data: &AuthorizationData,
+    _rule_set_revision: Option<usize>,
+) -> Result<(), ProgramError> {
+    Err(MetadataError::FeatureNotEnabled.into())
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthRulesValidateParams<'a> {
     pub mint_info: &'a AccountInfo<'a>,
@@ -283,6 +325,16 @@ pub fn auth_rules_validate(params: AuthRulesValidateParams) -> ProgramResult {
                         PayloadType::Pubkey(*destination_info.key),
                     );
                 }
+                Operation::Unlock { scenario: _ } => {
+                    // get account infos
+                    let authority_info = authority_info.ok_or(MetadataError::InvalidOperation)?;
+
+                    // unlock authority
+                    auth_data.payload.insert(
+                        PayloadKey::Authority.to_string(),
+                        PayloadType::Pubkey(*authority_info.key),
+                    );
+                }
                 _ => {
                     return Err(MetadataError::InvalidOperation.into());
                 }
@@ -332,3 +384,40 @@ pub fn frozen_transfer<'a, 'b>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "auth-rules"))]
+    fn auth_rules_program_is_not_recognized_without_auth_rules_feature() {
+        assert!(!is_auth_rules_program(&crate::programs::AUTH_RULES_PROGRAM));
+
+        let key = Pubkey::new_unique();
+        let owner = crate::programs::AUTH_RULES_PROGRAM;
+        let mut lamports = 0;
+        let mut data = vec![];
+        let rule_set_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            get_latest_revision(&rule_set_info).unwrap_err(),
+            MetadataError::FeatureNotEnabled.into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "auth-rules")]
+    fn auth_rules_program_is_recognized_with_auth_rules_feature() {
+        assert!(is_auth_rules_program(&crate::programs::AUTH_RULES_PROGRAM));
+    }
+}
@@ -0,0 +1,126 @@
+use mpl_utils::{cmp_pubkeys, token::SPL_TOKEN_PROGRAM_IDS};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, system_instruction,
+};
+
+use crate::{
+    assertions::{assert_initialized_mint, assert_initialized_token_account, assert_owned_by},
+    error::MetadataError,
+};
+
+/// Accounts required to pay creators in SPL tokens.
+pub(crate) struct TokenPaymentAccounts<'a> {
+    pub payer_token_info: &'a AccountInfo<'a>,
+    pub payment_mint_info: &'a AccountInfo<'a>,
+    pub spl_token_program_info: &'a AccountInfo<'a>,
+}
+
+impl<'a> TokenPaymentAccounts<'a> {
+    /// Returns the token payment accounts when a payment mint is provided; the payer token
+    /// account and token program are then required.
+    pub fn from_optional(
+        payer_token_info: Option<&'a AccountInfo<'a>>,
+        payment_mint_info: Option<&'a AccountInfo<'a>>,
+        spl_token_program_info: Option<&'a AccountInfo<'a>>,
+    ) -> Result<Option<Self>, ProgramError> {
+        let payment_mint_info = match payment_mint_info {
+            Some(payment_mint_info) => payment_mint_info,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            payer_token_info: payer_token_info.ok_or(MetadataError::MissingTokenAccount)?,
+            payment_mint_info,
+            spl_token_program_info: spl_token_program_info
+                .ok_or(MetadataError::MissingSplTokenProgram)?,
+        }))
+    }
+}
+
+/// Transfers the `payments` (computed by `split_royalties`) from the payer to the creators.
+///
+/// The `recipients` are in the same order as the payments: the creator wallets for lamport
+/// payments or their token accounts of the payment mint for SPL token payments.
+pub(crate) fn pay_creators<'a>(
+    payer_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+    token_accounts: Option<&TokenPaymentAccounts<'a>>,
+    payments: &[(Pubkey, u64)],
+    recipients: &[&'a AccountInfo<'a>],
+) -> ProgramResult {
+    if recipients.len() != payments.len() {
+        return Err(MetadataError::InvalidRoyaltyRecipient.into());
+    }
+
+    let recipients = payments.iter().zip(recipients.iter().copied());
+
+    if let Some(TokenPaymentAccounts {
+        payer_token_info,
+        payment_mint_info,
+        spl_token_program_info,
+    }) = token_accounts
+    {
+        if !SPL_TOKEN_PROGRAM_IDS.contains(spl_token_program_info.key) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        assert_owned_by(payment_mint_info, spl_token_program_info.key)?;
+        let payment_mint = assert_initialized_mint(payment_mint_info)?;
+
+        for ((creator, payment), recipient_info) in recipients {
+            assert_owned_by(recipient_info, spl_token_program_info.key)?;
+            let recipient = assert_initialized_token_account(recipient_info)?;
+
+            if !cmp_pubkeys(&recipient.owner, creator)
+                || !cmp_pubkeys(&recipient.mint, payment_mint_info.key)
+            {
+                return Err(MetadataError::InvalidRoyaltyRecipient.into());
+            }
+
+            if *payment == 0 {
+                continue;
+            }
+
+            invoke(
+                &spl_token_2022::instruction::transfer_checked(
+                    spl_token_program_info.key,
+                    payer_token_info.key,
+                    payment_mint_info.key,
+                    recipient_info.key,
+                    payer_info.key,
+                    &[],
+                    *payment,
+                    payment_mint.decimals,
+                )?,
+                &[
+                    (*payer_token_info).clone(),
+                    (*payment_mint_info).clone(),
+                    recipient_info.clone(),
+                    payer_info.clone(),
+                ],
+            )?;
+        }
+    } else {
+        for ((creator, payment), recipient_info) in recipients {
+            if !cmp_pubkeys(recipient_info.key, creator) {
+                return Err(MetadataError::InvalidRoyaltyRecipient.into());
+            }
+
+            if *payment == 0 {
+                continue;
+            }
+
+            invoke(
+                &system_instruction::transfer(payer_info.key, creator, *payment),
+                &[
+                    payer_info.clone(),
+                    recipient_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+    }
+
+    Ok(())
+}
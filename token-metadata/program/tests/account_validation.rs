@@ -0,0 +1,409 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{
+            CreateBuilder, DelegateAndLockBuilder, DelegateBuilder, FlagBuilder, LockBuilder,
+            MigrateBuilder, MintBuilder, RevokeBuilder, TransferBuilder, UnflagBuilder,
+            UnlockAndRevokeBuilder, UnlockBuilder, UpdateBuilder,
+        },
+        CreateArgs, DelegateArgs, FlagArgs, InstructionBuilder, LockArgs, MigrateArgs, MintArgs,
+        RevokeArgs, TransferArgs, UnflagArgs, UnlockArgs, UpdateArgs,
+    },
+    state::{AssetData, AssetDataConfig, FlagReason, MigrationType, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod account_validation {
+
+    use super::*;
+
+    /// Executes the instruction once for every signer and writable account, each time
+    /// without the corresponding flag on the account, and asserts that it fails.
+    async fn assert_account_flags(instruction: Instruction, signers: &[&Keypair]) {
+        let context = &mut program_test().start_with_context().await;
+
+        for (index, account) in instruction.accounts.iter().enumerate() {
+            if account.is_writable {
+                let mut ix = instruction.clone();
+                ix.accounts[index].is_writable = false;
+
+                let err = process(context, ix, signers).await;
+                assert_custom_error!(err, MetadataError::ExpectedWritableAccount);
+            }
+
+            if account.is_signer {
+                let mut ix = instruction.clone();
+                ix.accounts[index].is_signer = false;
+
+                let signers = signers
+                    .iter()
+                    .filter(|signer| signer.pubkey() != account.pubkey)
+                    .copied()
+                    .collect::<Vec<&Keypair>>();
+
+                let err = process(context, ix, &signers).await;
+                assert!(
+                    matches!(
+                        err,
+                        BanksClientError::TransactionError(TransactionError::InstructionError(
+                            0,
+                            InstructionError::MissingRequiredSignature
+                        ))
+                    ),
+                    "Expected missing signature for account {} but got '{:#?}'",
+                    index,
+                    err
+                );
+            }
+        }
+    }
+
+    async fn process(
+        context: &mut ProgramTestContext,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> BanksClientError {
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn create() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = CreateBuilder::new()
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .update_authority(Pubkey::new_unique())
+            .initialize_mint(false)
+            .update_authority_as_signer(false)
+            .build(CreateArgs::V1 {
+                asset_data: AssetData::from_config(AssetDataConfig {
+                    name: String::from(DEFAULT_NAME),
+                    symbol: String::from(DEFAULT_SYMBOL),
+                    uri: String::from(DEFAULT_URI),
+                    token_standard: TokenStandard::NonFungible,
+                    ..Default::default()
+                }),
+                decimals: None,
+                print_supply: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn mint() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = MintBuilder::new()
+            .token(Pubkey::new_unique())
+            .token_owner(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .build(MintArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn transfer() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = TransferBuilder::new()
+            .token(Pubkey::new_unique())
+            .token_owner(Pubkey::new_unique())
+            .destination(Pubkey::new_unique())
+            .destination_owner(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .edition(Pubkey::new_unique())
+            .owner_token_record(Pubkey::new_unique())
+            .destination_token_record(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn update() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = UpdateBuilder::new()
+            .authority(authority.pubkey())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .edition(Pubkey::new_unique())
+            .payer(payer.pubkey())
+            .build(UpdateArgs::default())
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn migrate() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = MigrateBuilder::new()
+            .metadata(Pubkey::new_unique())
+            .edition(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .token_owner(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .payer(payer.pubkey())
+            .authority(authority.pubkey())
+            .collection_metadata(Pubkey::new_unique())
+            .delegate_record(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .build(MigrateArgs::V1 {
+                migration_type: MigrationType::ProgrammableV1,
+                rule_set: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn delegate() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = DelegateBuilder::new()
+            .delegate_record(Pubkey::new_unique())
+            .delegate(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn revoke() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = RevokeBuilder::new()
+            .delegate_record(Pubkey::new_unique())
+            .delegate(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(RevokeArgs::UtilityV1)
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn lock() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = LockBuilder::new()
+            .authority(authority.pubkey())
+            .token_owner(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(LockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn unlock() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = UnlockBuilder::new()
+            .authority(authority.pubkey())
+            .token_owner(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(UnlockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn flag() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = FlagBuilder::new()
+            .flagged(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .build(FlagArgs::V1 {
+                reason: FlagReason::Spam,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn unflag() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = UnflagBuilder::new()
+            .flagged(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .rent_destination(Pubkey::new_unique())
+            .build(UnflagArgs::V1 {
+                reason: FlagReason::Spam,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn delegate_and_lock() {
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = DelegateAndLockBuilder::new()
+            .delegate_record(Pubkey::new_unique())
+            .delegate(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            })
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&authority, &payer]).await;
+    }
+
+    #[tokio::test]
+    async fn unlock_and_revoke() {
+        let delegate = Keypair::new();
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let ix = UnlockAndRevokeBuilder::new()
+            .delegate_record(Pubkey::new_unique())
+            .delegate(delegate.pubkey())
+            .metadata(Pubkey::new_unique())
+            .master_edition(Pubkey::new_unique())
+            .token_record(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .token(Pubkey::new_unique())
+            .authority(authority.pubkey())
+            .payer(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(RevokeArgs::UtilityV1)
+            .unwrap()
+            .instruction();
+
+        assert_account_flags(ix, &[&delegate, &authority, &payer]).await;
+    }
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use utils::*;
+
+mod archive {
+
+    use borsh::BorshSerialize;
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{
+            builders::{ArchiveBuilder, RestoreBuilder},
+            ArchiveArgs, InstructionBuilder, RestoreArgs,
+        },
+        pda::find_tombstone_account,
+        state::Tombstone,
+    };
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    use super::*;
+
+    async fn archive(
+        context: &mut ProgramTestContext,
+        nft: &Metadata,
+        update_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (tombstone, _) = find_tombstone_account(&nft.mint.pubkey());
+
+        let ix = ArchiveBuilder::new()
+            .tombstone(tombstone)
+            .metadata(nft.pubkey)
+            .mint(nft.mint.pubkey())
+            .token(nft.token.pubkey())
+            .token_owner(context.payer.pubkey())
+            .update_authority(update_authority.pubkey())
+            .payer(context.payer.pubkey())
+            .build(ArchiveArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn restore(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        metadata: &Pubkey,
+        data: Vec<u8>,
+    ) -> Result<(), BanksClientError> {
+        let (tombstone, _) = find_tombstone_account(mint);
+
+        let ix = RestoreBuilder::new()
+            .tombstone(tombstone)
+            .metadata(*metadata)
+            .mint(*mint)
+            .payer(context.payer.pubkey())
+            .build(RestoreArgs::V1 { data })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn archive_and_restore() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = Metadata::new();
+        nft.create_v3_default(&mut context).await.unwrap();
+
+        let metadata = nft.get_metadata(&mut context).await;
+        let data = metadata.try_to_vec().unwrap();
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        archive(&mut context, &nft, &update_authority)
+            .await
+            .unwrap();
+
+        // metadata is closed and the tombstone holds its hash
+
+        let account = context.banks_client.get_account(nft.pubkey).await.unwrap();
+        assert!(account.is_none());
+
+        let (tombstone_key, _) = find_tombstone_account(&nft.mint.pubkey());
+        let tombstone_account = get_account(&mut context, &tombstone_key).await;
+        let tombstone = Tombstone::from_bytes(&tombstone_account.data).unwrap();
+
+        assert_eq!(tombstone.mint, nft.mint.pubkey());
+        assert!(tombstone.matches(&data));
+
+        // data that does not match the hash is rejected
+
+        let mut tampered = data.clone();
+        tampered[1] ^= 1;
+
+        let error = restore(&mut context, &nft.mint.pubkey(), &nft.pubkey, tampered)
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::ArchivedDataMismatch);
+
+        // restores the original data
+
+        restore(&mut context, &nft.mint.pubkey(), &nft.pubkey, data)
+            .await
+            .unwrap();
+
+        assert_eq!(nft.get_metadata(&mut context).await, metadata);
+
+        let account = context
+            .banks_client
+            .get_account(tombstone_key)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_archive_wrong_update_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = Metadata::new();
+        nft.create_v3_default(&mut context).await.unwrap();
+
+        let error = archive(&mut context, &nft, &Keypair::new())
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{ApproveAttestationBuilder, RevokeAttestationBuilder},
+        ApproveAttestationArgs, InstructionBuilder, RevokeAttestationArgs,
+    },
+    pda::find_attestation_account,
+    state::{Attestation, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod attestation {
+
+    use super::*;
+
+    const TOPIC_HASH: [u8; 32] = [7; 32];
+
+    async fn approve(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        holder: &Keypair,
+        message_hash: [u8; 32],
+    ) -> Result<Pubkey, BanksClientError> {
+        let (attestation, _) =
+            find_attestation_account(&metadata.mint.pubkey(), &holder.pubkey(), &TOPIC_HASH);
+
+        let approve_ix = ApproveAttestationBuilder::new()
+            .attestation(attestation)
+            .holder(holder.pubkey())
+            .token(metadata.token.pubkey())
+            .mint(metadata.mint.pubkey())
+            .metadata(metadata.pubkey)
+            .payer(context.payer.pubkey())
+            .build(ApproveAttestationArgs::V1 {
+                topic_hash: TOPIC_HASH,
+                message_hash,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, holder],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| attestation)
+    }
+
+    #[tokio::test]
+    async fn holder_approves_and_revokes_attestation() {
+        let mut context = program_test().start_with_context().await;
+
+        let metadata = Metadata::new();
+        metadata.create_v2_default(&mut context).await.unwrap();
+
+        let holder = context.payer.dirty_clone();
+        let attestation = approve(&mut context, &metadata, &holder, [1; 32])
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &attestation).await;
+        let record = Attestation::safe_deserialize(&account.data).unwrap();
+
+        assert_eq!(record.mint, metadata.mint.pubkey());
+        assert_eq!(record.holder, holder.pubkey());
+        assert_eq!(record.topic_hash, TOPIC_HASH);
+        assert_eq!(record.message_hash, [1; 32]);
+
+        let destination = Keypair::new().pubkey();
+
+        let revoke_ix = RevokeAttestationBuilder::new()
+            .attestation(attestation)
+            .holder(holder.pubkey())
+            .destination(destination)
+            .build(RevokeAttestationArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[revoke_ix],
+            Some(&holder.pubkey()),
+            &[&holder],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(context
+            .banks_client
+            .get_account(attestation)
+            .await
+            .unwrap()
+            .is_none());
+
+        let destination_account = get_account(&mut context, &destination).await;
+        assert_eq!(destination_account.lamports, account.lamports);
+    }
+
+    #[tokio::test]
+    async fn fail_approve_attestation_without_holding_asset() {
+        let mut context = program_test().start_with_context().await;
+
+        let metadata = Metadata::new();
+        metadata.create_v2_default(&mut context).await.unwrap();
+
+        let stranger = Keypair::new();
+        airdrop(&mut context, &stranger.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let error = approve(&mut context, &metadata, &stranger, [1; 32])
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+    }
+}
@@ -0,0 +1,221 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::instruction::{
+    builders::{CreateAuthorityConfigBuilder, UpdateBuilder},
+    CreateAuthorityConfigArgs, InstructionBuilder, UpdateArgs,
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod authority_config {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        pda::find_authority_config_account,
+        state::{AuthorityConfig, Data, TokenStandard},
+    };
+    use solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use super::*;
+
+    async fn create_authority_config(
+        context: &mut ProgramTestContext,
+        base: &Keypair,
+        threshold: u8,
+        signers: Vec<Pubkey>,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (authority_config, _) = find_authority_config_account(&base.pubkey());
+
+        let create_ix = CreateAuthorityConfigBuilder::new()
+            .authority_config(authority_config)
+            .base(base.pubkey())
+            .payer(context.payer.pubkey())
+            .build(CreateAuthorityConfigArgs::V1 { threshold, signers })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, base],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| authority_config)
+    }
+
+    async fn update_as_signer(
+        context: &mut ProgramTestContext,
+        da: &DigitalAsset,
+        signer: &Keypair,
+        authority_config: Pubkey,
+        args: UpdateArgs,
+    ) -> Result<(), BanksClientError> {
+        let update_ix = UpdateBuilder::new()
+            .authority(signer.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(signer.pubkey())
+            .authority_config(authority_config)
+            .build(args)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&signer.pubkey()),
+            &[signer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    fn rename(data: &Data, name: &str) -> UpdateArgs {
+        let mut args = UpdateArgs::default();
+        let UpdateArgs::V1 { data: new_data, .. } = &mut args;
+        *new_data = Some(Data {
+            name: name.to_string(),
+            ..data.clone()
+        });
+
+        args
+    }
+
+    #[tokio::test]
+    async fn update_after_threshold_approvals() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+        for signer in &signers {
+            airdrop(context, &signer.pubkey(), LAMPORTS_PER_SOL)
+                .await
+                .unwrap();
+        }
+
+        let authority_config = create_authority_config(
+            context,
+            &Keypair::new(),
+            2,
+            signers.iter().map(|s| s.pubkey()).collect(),
+        )
+        .await
+        .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // hands the update authority over to the authority config
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            new_update_authority,
+            ..
+        } = &mut update_args;
+        *new_update_authority = Some(authority_config);
+
+        da.update(context, update_authority, update_args)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        let args = rename(&metadata.data, "Multisig Asset");
+
+        // the first approval is recorded without updating the asset
+
+        update_as_signer(context, &da, &signers[0], authority_config, args.clone())
+            .await
+            .unwrap();
+
+        let pending = da.get_metadata(context).await;
+        assert_eq!(pending.data.name, metadata.data.name);
+
+        let account = get_account(context, &authority_config).await;
+        let config = AuthorityConfig::from_bytes(&account.data).unwrap();
+        assert_eq!(config.pending.unwrap().approvals, 1);
+
+        // the second approval reaches the threshold
+
+        update_as_signer(context, &da, &signers[2], authority_config, args)
+            .await
+            .unwrap();
+
+        let updated = da.get_metadata(context).await;
+        assert!(updated.data.name.starts_with("Multisig Asset"));
+    }
+
+    #[tokio::test]
+    async fn fail_approval_from_non_signer() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let authority_config =
+            create_authority_config(context, &Keypair::new(), 1, vec![Pubkey::new_unique()])
+                .await
+                .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            new_update_authority,
+            ..
+        } = &mut update_args;
+        *new_update_authority = Some(authority_config);
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        // the former update authority is not a signer of the config
+
+        let metadata = da.get_metadata(context).await;
+        let err = update_as_signer(
+            context,
+            &da,
+            &update_authority,
+            authority_config,
+            rename(&metadata.data, "Multisig Asset"),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::NotAuthorityConfigSigner);
+    }
+
+    #[tokio::test]
+    async fn fail_create_with_unreachable_threshold() {
+        let context = &mut program_test().start_with_context().await;
+
+        let err = create_authority_config(
+            context,
+            &Keypair::new(),
+            3,
+            vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidAuthorityConfig);
+    }
+}
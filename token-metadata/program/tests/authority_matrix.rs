@@ -0,0 +1,34 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use solana_program_test::*;
+use utils::*;
+
+mod authority_matrix {
+
+    use super::*;
+
+    /// Runs every case of the authority matrix on a fresh validator, reporting all the
+    /// cases that do not match the expected outcome.
+    #[tokio::test]
+    async fn authority_resolution_matrix() {
+        let mut mismatches = vec![];
+
+        for case in AUTHORITY_MATRIX {
+            let mut context = program_test().start_with_context().await;
+            let result = case.run(&mut context).await;
+
+            if let Some(mismatch) = case.check(result) {
+                mismatches.push(mismatch);
+            }
+        }
+
+        assert!(
+            mismatches.is_empty(),
+            "{} of {} cases do not match the authority matrix:\n{}",
+            mismatches.len(),
+            AUTHORITY_MATRIX.len(),
+            mismatches.join("\n")
+        );
+    }
+}
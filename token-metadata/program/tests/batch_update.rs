@@ -0,0 +1,97 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use utils::*;
+
+mod batch_update {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{RuleSetToggle, UpdateArgs},
+        state::TokenStandard,
+    };
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk::{
+        instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fail_item_not_in_collection() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::default();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut item = DigitalAsset::default();
+        item.create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            primary_sale_happened,
+            ..
+        } = &mut args;
+        *primary_sale_happened = Some(true);
+
+        let error = collection
+            .batch_update(&mut context, &authority, None, &[item.metadata], args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotAMemberOfCollection);
+    }
+
+    #[tokio::test]
+    async fn fail_invalid_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::default();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::new();
+
+        let error = collection
+            .batch_update(&mut context, &authority, None, &[], UpdateArgs::default())
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+    }
+
+    #[tokio::test]
+    async fn fail_update_rule_set() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::default();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut args = UpdateArgs::default();
+        let UpdateArgs::V1 { rule_set, .. } = &mut args;
+        *rule_set = RuleSetToggle::Set(Pubkey::new_unique());
+
+        let error = collection
+            .batch_update(&mut context, &authority, None, &[], args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidUpdateArgs);
+    }
+}
@@ -0,0 +1,328 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::error::MetadataError;
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod burn {
+
+    use mpl_token_metadata::{
+        instruction::{
+            builders::{BurnBuilder, CloseAccountsBuilder},
+            BurnArgs, CloseAccountsArgs, InstructionBuilder, OrphanPrintsPolicy,
+        },
+        state::{EditionTombstone, MasterEditionV2 as ProgramMasterEdition, TokenMetadataAccount},
+    };
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn burn_print_edition_in_separate_wallet() {
+        let mut context = program_test().start_with_context().await;
+
+        let original_nft = Metadata::new();
+        original_nft.create_v2_default(&mut context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&original_nft);
+        master_edition
+            .create_v3(&mut context, Some(10))
+            .await
+            .unwrap();
+
+        let mut print_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        // transfers the print edition to a new owner
+
+        let new_owner = Keypair::new();
+        airdrop(&mut context, &new_owner.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        context.warp_to_slot(10).unwrap();
+
+        print_edition
+            .transfer(&mut context, &new_owner.pubkey())
+            .await
+            .unwrap();
+
+        let master_edition_account = get_account(&mut context, &master_edition.pubkey).await;
+        let master_edition_data =
+            ProgramMasterEdition::safe_deserialize(&master_edition_account.data).unwrap();
+        assert_eq!(master_edition_data.supply, 1);
+
+        // burns the print edition
+
+        let ix = BurnBuilder::new()
+            .metadata(print_edition.new_metadata_pubkey)
+            .authority(new_owner.pubkey())
+            .mint(print_edition.mint.pubkey())
+            .token_account(print_edition.token.pubkey())
+            .edition(print_edition.new_edition_pubkey)
+            .master_edition_mint(original_nft.mint.pubkey())
+            .master_edition(master_edition.pubkey)
+            .edition_marker(print_edition.pubkey)
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&new_owner.pubkey()),
+            &[&new_owner],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // metadata, edition and token accounts are closed
+
+        for pubkey in [
+            print_edition.new_metadata_pubkey,
+            print_edition.new_edition_pubkey,
+            print_edition.token.pubkey(),
+        ] {
+            assert!(context
+                .banks_client
+                .get_account(pubkey)
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        // the edition was the only one on the marker, so the marker is closed even
+        // though the print edition was not held by the master edition owner
+
+        let edition_marker_account = context
+            .banks_client
+            .get_account(print_edition.pubkey)
+            .await
+            .unwrap();
+        assert!(edition_marker_account.is_none());
+
+        let master_edition_account = get_account(&mut context, &master_edition.pubkey).await;
+        let master_edition_data =
+            ProgramMasterEdition::safe_deserialize(&master_edition_account.data).unwrap();
+        assert_eq!(master_edition_data.supply, 0);
+    }
+
+    #[tokio::test]
+    async fn fail_burn_with_invalid_rent_destination() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = Metadata::new();
+        nft.create_v2_default(&mut context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&nft);
+        master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        // the rent destination must be the owner of the token account
+
+        let payer = context.payer.pubkey();
+
+        let ix = BurnBuilder::new()
+            .metadata(nft.pubkey)
+            .authority(payer)
+            .mint(nft.mint.pubkey())
+            .token_account(nft.token.pubkey())
+            .edition(master_edition.pubkey)
+            .rent_destination(Keypair::new().pubkey())
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidRentDestination);
+    }
+
+    #[tokio::test]
+    async fn burn_master_edition_with_orphan_prints() {
+        let mut context = program_test().start_with_context().await;
+
+        let original_nft = Metadata::new();
+        original_nft.create_v2_default(&mut context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&original_nft);
+        master_edition
+            .create_v3(&mut context, Some(10))
+            .await
+            .unwrap();
+
+        let print_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        let payer = context.payer.pubkey();
+
+        let burn_master_ix = |orphan_prints| {
+            BurnBuilder::new()
+                .metadata(original_nft.pubkey)
+                .authority(payer)
+                .mint(original_nft.mint.pubkey())
+                .token_account(original_nft.token.pubkey())
+                .edition(master_edition.pubkey)
+                .build(BurnArgs::V2 {
+                    authorization_data: None,
+                    orphan_prints,
+                })
+                .unwrap()
+                .instruction()
+        };
+
+        // the master edition has a print, so it cannot be burned by default
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_master_ix(OrphanPrintsPolicy::Deny)],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MasterEditionHasPrints);
+
+        // allowing orphan prints replaces the master edition by a tombstone
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_master_ix(OrphanPrintsPolicy::AllowAndMark)],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(context
+            .banks_client
+            .get_account(original_nft.pubkey)
+            .await
+            .unwrap()
+            .is_none());
+
+        let tombstone_account = get_account(&mut context, &master_edition.pubkey).await;
+        let tombstone = EditionTombstone::safe_deserialize(&tombstone_account.data).unwrap();
+        assert_eq!(tombstone.master_mint, original_nft.mint.pubkey());
+        assert_eq!(tombstone.rent_destination, payer);
+        assert_eq!(tombstone.orphaned_prints, 1);
+
+        // burning the last print closes the tombstone
+
+        let ix = BurnBuilder::new()
+            .metadata(print_edition.new_metadata_pubkey)
+            .authority(payer)
+            .mint(print_edition.mint.pubkey())
+            .token_account(print_edition.token.pubkey())
+            .edition(print_edition.new_edition_pubkey)
+            .master_edition_mint(original_nft.mint.pubkey())
+            .master_edition(master_edition.pubkey)
+            .edition_marker(print_edition.pubkey)
+            .tombstone_rent_destination(payer)
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(context
+            .banks_client
+            .get_account(master_edition.pubkey)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn close_accounts_of_burned_nft() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = Metadata::new();
+        nft.create_v2_default(&mut context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&nft);
+        master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        // burns the token directly on the token program, leaving the metadata accounts
+
+        let payer = context.payer.pubkey();
+        let burn_ix = spl_token::instruction::burn(
+            &spl_token::ID,
+            &nft.token.pubkey(),
+            &nft.mint.pubkey(),
+            &payer,
+            &[],
+            1,
+        )
+        .unwrap();
+
+        let destination = Keypair::new().pubkey();
+
+        let close_ix = CloseAccountsBuilder::new()
+            .metadata(nft.pubkey)
+            .edition(master_edition.pubkey)
+            .mint(nft.mint.pubkey())
+            .authority(payer)
+            .destination(destination)
+            .build(CloseAccountsArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix, close_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the rent of the metadata accounts is sent to the destination
+
+        for pubkey in [nft.pubkey, master_edition.pubkey] {
+            assert!(context
+                .banks_client
+                .get_account(pubkey)
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        let destination_account = get_account(&mut context, &destination).await;
+        assert!(destination_account.lamports > 0);
+    }
+}
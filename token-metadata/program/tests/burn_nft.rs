@@ -264,6 +264,9 @@ mod burn_nft {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 0);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not set!");
@@ -291,6 +294,9 @@ mod burn_nft {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 1);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not set");
@@ -380,6 +386,9 @@ mod burn_nft {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 0);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not set!");
@@ -408,6 +417,9 @@ mod burn_nft {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 1);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not set!");
@@ -435,6 +447,9 @@ mod burn_nft {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 0);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not set!");
@@ -0,0 +1,233 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use borsh::BorshSerialize;
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{builders::CloseTokenRecordBuilder, CloseTokenRecordArgs, InstructionBuilder},
+    pda::find_token_record_account,
+    state::{TokenRecord, TokenStandard, TOKEN_RECORD_SIZE},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account as SdkAccount, AccountSharedData},
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod close_token_record {
+
+    use super::*;
+
+    /// Writes a token record for the mint and token account directly to the bank, since
+    /// records are only created alongside minted programmable assets.
+    async fn inject_token_record(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        token: &Pubkey,
+        payer: Option<Pubkey>,
+    ) -> Pubkey {
+        let (token_record, bump) = find_token_record_account(mint, token);
+
+        let mut data = vec![0u8; TOKEN_RECORD_SIZE];
+        let mut buffer = &mut data[..];
+        TokenRecord {
+            bump,
+            payer,
+            ..Default::default()
+        }
+        .serialize(&mut buffer)
+        .unwrap();
+
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let account = SdkAccount {
+            lamports: rent.minimum_balance(TOKEN_RECORD_SIZE),
+            data,
+            owner: mpl_token_metadata::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        context.set_account(&token_record, &AccountSharedData::from(account));
+
+        token_record
+    }
+
+    async fn close_token_record(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        token: &Pubkey,
+        destination: &Pubkey,
+        update_authority: Option<(&Pubkey, &Keypair)>,
+    ) -> Result<(), BanksClientError> {
+        let (token_record, _) = find_token_record_account(mint, token);
+
+        let mut builder = CloseTokenRecordBuilder::new();
+        builder
+            .token_record(token_record)
+            .mint(*mint)
+            .token(*token)
+            .destination(*destination);
+
+        let mut signers = vec![&context.payer];
+
+        if let Some((metadata, authority)) = update_authority {
+            builder.metadata(*metadata).authority(authority.pubkey());
+            signers.push(authority);
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[builder
+                .build(CloseTokenRecordArgs::V1)
+                .unwrap()
+                .instruction()],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn close_record_of_closed_token_account() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let manager = context.payer.pubkey();
+        create_mint(&mut context, &mint, &manager, None, 0)
+            .await
+            .unwrap();
+
+        // the token account does not exist anymore
+        let token = Keypair::new().pubkey();
+        let payer = Keypair::new().pubkey();
+        let token_record =
+            inject_token_record(&mut context, &mint.pubkey(), &token, Some(payer)).await;
+        let rent = get_account(&mut context, &token_record).await.lamports;
+
+        close_token_record(&mut context, &mint.pubkey(), &token, &payer, None)
+            .await
+            .unwrap();
+
+        assert!(context
+            .banks_client
+            .get_account(token_record)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(get_account(&mut context, &payer).await.lamports, rent);
+    }
+
+    #[tokio::test]
+    async fn close_legacy_record_with_update_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(&mut context, TokenStandard::ProgrammableNonFungible, None)
+            .await
+            .unwrap();
+
+        // records created before the payer was recorded
+        let token = Keypair::new().pubkey();
+        inject_token_record(&mut context, &asset.mint.pubkey(), &token, None).await;
+
+        let destination = Keypair::new().pubkey();
+        let update_authority = context.payer.dirty_clone();
+
+        // the update authority is required to set the destination
+        let error = close_token_record(
+            &mut context,
+            &asset.mint.pubkey(),
+            &token,
+            &destination,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIsNotSigner);
+
+        close_token_record(
+            &mut context,
+            &asset.mint.pubkey(),
+            &token,
+            &destination,
+            Some((&asset.metadata, &update_authority)),
+        )
+        .await
+        .unwrap();
+
+        assert!(get_account(&mut context, &destination).await.lamports > 0);
+    }
+
+    #[tokio::test]
+    async fn fail_close_to_other_destination() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let manager = context.payer.pubkey();
+        create_mint(&mut context, &mint, &manager, None, 0)
+            .await
+            .unwrap();
+
+        let token = Keypair::new().pubkey();
+        inject_token_record(
+            &mut context,
+            &mint.pubkey(),
+            &token,
+            Some(Keypair::new().pubkey()),
+        )
+        .await;
+
+        let error = close_token_record(
+            &mut context,
+            &mint.pubkey(),
+            &token,
+            &Keypair::new().pubkey(),
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidRentDestination);
+    }
+
+    #[tokio::test]
+    async fn fail_close_record_in_use() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let manager = context.payer.pubkey();
+        create_mint(&mut context, &mint, &manager, None, 0)
+            .await
+            .unwrap();
+
+        let token = Keypair::new();
+        create_token_account(&mut context, &token, &mint.pubkey(), &manager)
+            .await
+            .unwrap();
+        mint_tokens(
+            &mut context,
+            &mint.pubkey(),
+            &token.pubkey(),
+            1,
+            &manager,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let payer = Keypair::new().pubkey();
+        inject_token_record(&mut context, &mint.pubkey(), &token.pubkey(), Some(payer)).await;
+
+        let error = close_token_record(&mut context, &mint.pubkey(), &token.pubkey(), &payer, None)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::TokenRecordInUse);
+    }
+}
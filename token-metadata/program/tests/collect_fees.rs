@@ -0,0 +1,140 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{CollectFeesBuilder, CreateBuilder},
+        CollectFeesArgs, CreateArgs, InstructionBuilder,
+    },
+    pda::{find_fee_vault_account, find_metadata_account},
+    state::{
+        AssetData, AssetDataConfig, Metadata as ProgramMetadata, TokenMetadataAccount,
+        TokenStandard, CREATE_FEE,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collect_fees {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn create_with_fee() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let (metadata, _) = find_metadata_account(&mint.pubkey());
+        let (fee_vault, _) = find_fee_vault_account();
+        let payer = context.payer.pubkey();
+
+        let create_ix = CreateBuilder::new()
+            .metadata(metadata)
+            .mint(mint.pubkey())
+            .authority(payer)
+            .payer(payer)
+            .update_authority(payer)
+            .initialize_mint(true)
+            .update_authority_as_signer(true)
+            .fee_vault(fee_vault)
+            .build(CreateArgs::V1 {
+                asset_data: AssetData::from_config(AssetDataConfig {
+                    name: String::from("Fungible"),
+                    symbol: String::from("FUN"),
+                    uri: String::from("https://fungible.com"),
+                    token_standard: TokenStandard::Fungible,
+                    ..Default::default()
+                }),
+                decimals: Some(0),
+                print_supply: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix],
+            Some(&payer),
+            &[&context.payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let vault_account = get_account(&mut context, &fee_vault).await;
+        assert_eq!(vault_account.lamports, CREATE_FEE);
+
+        let metadata_account = get_account(&mut context, &metadata).await;
+        let metadata = ProgramMetadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert!(metadata.fee_flag);
+
+        // collecting requires the protocol authority, so the fees stay in the vault
+
+        let recipient = Keypair::new();
+        airdrop(&mut context, &recipient.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let collect_ix = CollectFeesBuilder::new()
+            .authority(recipient.pubkey())
+            .fee_vault(fee_vault)
+            .recipient(recipient.pubkey())
+            .build(CollectFeesArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[collect_ix],
+            Some(&recipient.pubkey()),
+            &[&recipient],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidFeeAuthority);
+
+        let vault_account = get_account(&mut context, &fee_vault).await;
+        assert_eq!(vault_account.lamports, CREATE_FEE);
+    }
+
+    #[tokio::test]
+    async fn create_without_fee() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(&mut context, TokenStandard::Fungible, None)
+            .await
+            .unwrap();
+
+        let metadata = asset.get_metadata(&mut context).await;
+        assert!(!metadata.fee_flag);
+
+        // the metadata account only holds its rent
+
+        let metadata_account = get_account(&mut context, &asset.metadata).await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+        assert_eq!(
+            metadata_account.lamports,
+            rent.minimum_balance(metadata_account.data.len())
+        );
+
+        let (fee_vault, _) = find_fee_vault_account();
+        assert!(context
+            .banks_client
+            .get_account(fee_vault)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
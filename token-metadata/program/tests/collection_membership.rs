@@ -0,0 +1,250 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{ExpireCollectionMembershipBuilder, VerifyV2Builder},
+        CollectionDetailsToggle, CollectionToggle, ExpireCollectionMembershipArgs,
+        InstructionBuilder, UpdateArgs, VerificationArgs,
+    },
+    pda::find_collection_membership_account,
+    state::{
+        Collection, CollectionDetails, CollectionMembership, TokenMetadataAccount, TokenStandard,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::clock::Clock;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collection_membership {
+
+    use super::*;
+
+    /// Creates a sized collection parent and an (unverified) item of it.
+    async fn create_collection_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V1 { size: 0 });
+
+        collection_parent
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let mut item = DigitalAsset::new();
+        item.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: false,
+        });
+
+        item.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        (collection_parent, item)
+    }
+
+    async fn verify_with_expiry(
+        context: &mut ProgramTestContext,
+        collection_parent: &DigitalAsset,
+        item: &DigitalAsset,
+        membership_expires_at: Option<i64>,
+    ) -> Result<(), BanksClientError> {
+        let payer = context.payer.pubkey();
+        let (collection_membership, _) = find_collection_membership_account(&item.mint.pubkey());
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(payer)
+            .metadata(item.metadata)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .collection_master_edition(collection_parent.master_edition.unwrap())
+            .collection_membership(collection_membership)
+            .payer(payer)
+            .build(VerificationArgs::CollectionV2 {
+                membership_expires_at,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn expire(
+        context: &mut ProgramTestContext,
+        collection_parent: &DigitalAsset,
+        item: &DigitalAsset,
+    ) -> Result<(), BanksClientError> {
+        let payer = context.payer.pubkey();
+        let (collection_membership, _) = find_collection_membership_account(&item.mint.pubkey());
+
+        let expire_ix = ExpireCollectionMembershipBuilder::new()
+            .payer(payer)
+            .metadata(item.metadata)
+            .collection_membership(collection_membership)
+            .collection_metadata(collection_parent.metadata)
+            .build(ExpireCollectionMembershipArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[expire_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn collection_size(
+        context: &mut ProgramTestContext,
+        collection_parent: &DigitalAsset,
+    ) -> u64 {
+        match collection_parent
+            .get_metadata(context)
+            .await
+            .collection_details
+        {
+            Some(CollectionDetails::V1 { size }) => size,
+            Some(CollectionDetails::Uncapped | CollectionDetails::V2 { .. }) | None => {
+                panic!("collection is not sized")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn expire_seasonal_membership() {
+        let mut context = program_test().start_with_context().await;
+
+        let update_authority = context.payer.dirty_clone();
+        let (collection_parent, item) =
+            create_collection_item(&mut context, &update_authority).await;
+
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        verify_with_expiry(&mut context, &collection_parent, &item, Some(now + 3_600))
+            .await
+            .unwrap();
+
+        assert!(
+            item.get_metadata(&mut context)
+                .await
+                .collection
+                .unwrap()
+                .verified
+        );
+        assert_eq!(collection_size(&mut context, &collection_parent).await, 1);
+
+        let (collection_membership, _) = find_collection_membership_account(&item.mint.pubkey());
+        let account = get_account(&mut context, &collection_membership).await;
+        let membership = CollectionMembership::safe_deserialize(&account.data).unwrap();
+        assert_eq!(membership.collection_mint, collection_parent.mint.pubkey());
+        assert_eq!(membership.expires_at, now + 3_600);
+
+        // the membership cannot be closed before its expiry
+        let error = expire(&mut context, &collection_parent, &item)
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::CollectionMembershipNotExpired);
+
+        warp_clock(&mut context, 3_600).await;
+
+        expire(&mut context, &collection_parent, &item)
+            .await
+            .unwrap();
+
+        assert!(
+            !item
+                .get_metadata(&mut context)
+                .await
+                .collection
+                .unwrap()
+                .verified
+        );
+        assert_eq!(collection_size(&mut context, &collection_parent).await, 0);
+        assert!(context
+            .banks_client
+            .get_account(collection_membership)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_verify_with_past_expiry() {
+        let mut context = program_test().start_with_context().await;
+
+        let update_authority = context.payer.dirty_clone();
+        let (collection_parent, item) =
+            create_collection_item(&mut context, &update_authority).await;
+
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        let error = verify_with_expiry(&mut context, &collection_parent, &item, Some(now - 1))
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::InvalidCollectionMembershipExpiry);
+
+        // no membership is set when the asset is verified without an expiry
+        verify_with_expiry(&mut context, &collection_parent, &item, None)
+            .await
+            .unwrap();
+
+        assert!(
+            item.get_metadata(&mut context)
+                .await
+                .collection
+                .unwrap()
+                .verified
+        );
+        let (collection_membership, _) = find_collection_membership_account(&item.mint.pubkey());
+        assert!(context
+            .banks_client
+            .get_account(collection_membership)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
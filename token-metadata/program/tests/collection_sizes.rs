@@ -516,6 +516,9 @@ mod size_tracking {
                 CollectionDetails::V1 { size } => {
                     assert_eq!(size, 0);
                 }
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not populated!");
@@ -541,6 +544,9 @@ mod size_tracking {
         if let Some(details) = parent_metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => assert_eq!(size, 1),
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not populated!");
@@ -566,6 +572,9 @@ mod size_tracking {
         if let Some(details) = parent_metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => assert_eq!(size, 0),
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not populated!");
@@ -592,6 +601,9 @@ mod size_tracking {
         if let Some(details) = parent_metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => assert_eq!(size, 1),
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not populated!");
@@ -617,9 +629,114 @@ mod size_tracking {
         if let Some(details) = parent_metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => assert_eq!(size, 0),
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("CollectionDetails is not populated!");
         }
     }
+    #[tokio::test]
+    async fn uncapped_collection_skips_size_tracking() {
+        let mut context = program_test().start_with_context().await;
+
+        // Create a Collection Parent NFT that does not keep track of its size
+        let collection_parent_nft = Metadata::new();
+        collection_parent_nft
+            .create_v3(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                Some(CollectionDetails::Uncapped), // Collection Parent
+            )
+            .await
+            .unwrap();
+        let parent_master_edition_account = MasterEditionV2::new(&collection_parent_nft);
+        parent_master_edition_account
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        let collection = Collection {
+            key: collection_parent_nft.mint.pubkey(),
+            verified: false,
+        };
+
+        let collection_item_nft = Metadata::new();
+        collection_item_nft
+            .create_v3(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                Some(collection),
+                None,
+                None, // Collection Item
+            )
+            .await
+            .unwrap();
+        let item_master_edition_account = MasterEditionV2::new(&collection_item_nft);
+        item_master_edition_account
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        let kpbytes = &context.payer;
+        let payer = Keypair::from_bytes(&kpbytes.to_bytes()).unwrap();
+
+        // Verifying does not change the parent.
+        collection_item_nft
+            .verify_sized_collection_item(
+                &mut context,
+                collection_parent_nft.pubkey,
+                &payer,
+                collection_parent_nft.mint.pubkey(),
+                parent_master_edition_account.pubkey,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let item_metadata = collection_item_nft.get_data(&mut context).await;
+        assert!(item_metadata.collection.unwrap().verified);
+
+        let parent_nft_account = get_account(&mut context, &collection_parent_nft.pubkey).await;
+        let parent_metadata =
+            ProgramMetadata::deserialize(&mut parent_nft_account.data.as_slice()).unwrap();
+        assert_eq!(
+            parent_metadata.collection_details,
+            Some(CollectionDetails::Uncapped)
+        );
+
+        // Burning does not change the parent either.
+        burn(
+            &mut context,
+            collection_item_nft.pubkey,
+            &payer,
+            collection_item_nft.mint.pubkey(),
+            collection_item_nft.token.pubkey(),
+            item_master_edition_account.pubkey,
+            Some(collection_parent_nft.pubkey),
+        )
+        .await
+        .unwrap();
+
+        let parent_nft_account = get_account(&mut context, &collection_parent_nft.pubkey).await;
+        let parent_metadata =
+            ProgramMetadata::deserialize(&mut parent_nft_account.data.as_slice()).unwrap();
+        assert_eq!(
+            parent_metadata.collection_details,
+            Some(CollectionDetails::Uncapped)
+        );
+    }
 }
@@ -0,0 +1,101 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use solana_program_test::*;
+use utils::*;
+
+mod compute_budget {
+
+    use mpl_token_metadata::{
+        instruction::{
+            builders::TransferBuilder, InstructionBuilder, MetadataInstruction, TransferArgs,
+        },
+        pda::{find_default_rule_sets_account, find_token_record_account},
+        state::TokenStandard,
+    };
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk::{signature::Signer, transaction::Transaction};
+    use spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn programmable_transfer_within_hint() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let owner = context.payer.pubkey();
+        let token = asset.token.unwrap();
+        let destination_owner = Pubkey::new_unique();
+        let destination = get_associated_token_address(&destination_owner, &asset.mint.pubkey());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_associated_token_account(
+                &owner,
+                &destination_owner,
+                &asset.mint.pubkey(),
+                &spl_token::id(),
+            )],
+            Some(&owner),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let transfer = TransferBuilder::new()
+            .authority(owner)
+            .token_owner(owner)
+            .token(token)
+            .destination_owner(destination_owner)
+            .destination(destination)
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .mint(asset.mint.pubkey())
+            .owner_token_record(find_token_record_account(&asset.mint.pubkey(), &token).0)
+            .destination_token_record(
+                find_token_record_account(&asset.mint.pubkey(), &destination).0,
+            )
+            .default_rule_sets(find_default_rule_sets_account().0)
+            .payer(owner)
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap();
+
+        let instructions = transfer
+            .instructions_with_compute_budget(TokenStandard::ProgrammableNonFungible, false);
+        assert_eq!(instructions.len(), 2);
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&owner),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+        assert!(simulation.result.unwrap().is_ok());
+
+        let units_consumed = simulation.simulation_details.unwrap().units_consumed;
+        let hint = MetadataInstruction::Transfer(TransferArgs::V1 {
+            amount: 1,
+            authorization_data: None,
+        })
+        .compute_budget_hint(TokenStandard::ProgrammableNonFungible, false);
+        assert!(units_consumed <= hint as u64);
+    }
+}
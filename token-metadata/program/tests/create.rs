@@ -22,12 +22,20 @@ mod create {
 
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{builders::CreateBuilder, CreateArgs, InstructionBuilder},
+        instruction::{
+            builders::{CreateBuilder, MintBuilder},
+            CreateArgs, InstructionBuilder, MintArgs,
+        },
         state::{
-            AssetData, Metadata, PrintSupply, ProgrammableConfig, TokenStandard, EDITION, PREFIX,
+            AssetData, AssetDataConfig, BasisPoints, Metadata, PrintSupply, ProgrammableConfig,
+            TokenStandard, EDITION, PREFIX,
         },
     };
     use solana_program::borsh::try_from_slice_unchecked;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id, instruction::create_associated_token_account,
+    };
+    use spl_token_2022::{extension::StateWithExtensions, state::Account};
 
     use super::*;
 
@@ -41,15 +49,17 @@ mod create {
         let symbol = puffed_out_string("PRG", MAX_SYMBOL_LENGTH);
         let uri = puffed_out_string("uri", MAX_URI_LENGTH);
 
-        let mut asset = AssetData::new(
-            TokenStandard::ProgrammableNonFungible,
-            name.clone(),
-            symbol.clone(),
-            uri.clone(),
-        );
-        asset.seller_fee_basis_points = 500;
-        asset.rule_set =
-            Some(Pubkey::from_str("Cex6GAMtCwD9E17VsEK4rQTbmcVtSdHxWcxhwdwXkuAN").unwrap());
+        let asset = AssetData::from_config(AssetDataConfig {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
+            token_standard: TokenStandard::ProgrammableNonFungible,
+            rule_set: Some(
+                Pubkey::from_str("Cex6GAMtCwD9E17VsEK4rQTbmcVtSdHxWcxhwdwXkuAN").unwrap(),
+            ),
+            ..Default::default()
+        });
 
         // build the mint transaction
 
@@ -104,7 +114,7 @@ mod create {
         assert_eq!(metadata.data.name, name);
         assert_eq!(metadata.data.symbol, symbol);
         assert_eq!(metadata.data.uri, uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 500);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 500);
         assert_eq!(metadata.data.creators, None);
 
         assert!(!metadata.primary_sale_happened);
@@ -142,13 +152,14 @@ mod create {
         let symbol = puffed_out_string("PRG", MAX_SYMBOL_LENGTH);
         let uri = puffed_out_string("uri", MAX_URI_LENGTH);
 
-        let mut asset = AssetData::new(
-            TokenStandard::ProgrammableNonFungible,
-            name.clone(),
-            symbol.clone(),
-            uri.clone(),
-        );
-        asset.seller_fee_basis_points = 500;
+        let asset = AssetData::from_config(AssetDataConfig {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
+            token_standard: TokenStandard::ProgrammableNonFungible,
+            ..Default::default()
+        });
         /*
         asset.programmable_config = Some(ProgrammableConfig {
             rule_set: Pubkey::from_str("Cex6GAMtCwD9E17VsEK4rQTbmcVtSdHxWcxhwdwXkuAN")?,
@@ -214,7 +225,7 @@ mod create {
         assert_eq!(metadata.data.name, name);
         assert_eq!(metadata.data.symbol, symbol);
         assert_eq!(metadata.data.uri, uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 500);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 500);
         assert_eq!(metadata.data.creators, None);
 
         assert!(!metadata.primary_sale_happened);
@@ -245,13 +256,14 @@ mod create {
         let symbol = puffed_out_string("PRG", MAX_SYMBOL_LENGTH);
         let uri = puffed_out_string("uri", MAX_URI_LENGTH);
 
-        let mut asset = AssetData::new(
-            TokenStandard::ProgrammableNonFungible,
-            name.clone(),
-            symbol.clone(),
-            uri.clone(),
-        );
-        asset.seller_fee_basis_points = 500;
+        let asset = AssetData::from_config(AssetDataConfig {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
+            token_standard: TokenStandard::ProgrammableNonFungible,
+            ..Default::default()
+        });
         /*
         asset.programmable_config = Some(ProgrammableConfig {
             rule_set: Pubkey::from_str("Cex6GAMtCwD9E17VsEK4rQTbmcVtSdHxWcxhwdwXkuAN")?,
@@ -447,4 +459,98 @@ mod create {
         assert_eq!(metadata.programmable_config, None);
         assert!(asset.master_edition.is_none());
     }
+
+    #[tokio::test]
+    async fn create_and_mint_fungible_with_token_2022() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = AssetData::from_config(AssetDataConfig {
+            name: String::from(DEFAULT_NAME),
+            symbol: String::from(DEFAULT_SYMBOL),
+            uri: String::from(DEFAULT_URI),
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
+            token_standard: TokenStandard::Fungible,
+            ..Default::default()
+        });
+
+        // the mint is created by the Token-2022 program
+
+        let payer_pubkey = context.payer.pubkey();
+        let mint = Keypair::new();
+        let mint_pubkey = mint.pubkey();
+
+        let program_id = id();
+        let metadata_seeds = &[PREFIX.as_bytes(), program_id.as_ref(), mint_pubkey.as_ref()];
+        let (metadata, _) = Pubkey::find_program_address(metadata_seeds, &id());
+
+        let create_ix = CreateBuilder::new()
+            .metadata(metadata)
+            .mint(mint_pubkey)
+            .authority(payer_pubkey)
+            .payer(payer_pubkey)
+            .update_authority(payer_pubkey)
+            .spl_token_program(spl_token_2022::ID)
+            .initialize_mint(true)
+            .update_authority_as_signer(true)
+            .build(CreateArgs::V1 {
+                asset_data: asset,
+                decimals: Some(0),
+                print_supply: None,
+            })
+            .unwrap()
+            .instruction();
+
+        // mints to the associated token account of the payer
+
+        let token = get_associated_token_address_with_program_id(
+            &payer_pubkey,
+            &mint_pubkey,
+            &spl_token_2022::ID,
+        );
+        let create_token_ix = create_associated_token_account(
+            &payer_pubkey,
+            &payer_pubkey,
+            &mint_pubkey,
+            &spl_token_2022::ID,
+        );
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .token_owner(payer_pubkey)
+            .metadata(metadata)
+            .mint(mint_pubkey)
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .spl_token_program(spl_token_2022::ID)
+            .build(MintArgs::V1 {
+                amount: 10,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix, create_token_ix, mint_ix],
+            Some(&payer_pubkey),
+            &[&context.payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let mint_account = get_account(&mut context, &mint_pubkey).await;
+        assert_eq!(mint_account.owner, spl_token_2022::ID);
+
+        let token_account = get_account(&mut context, &token).await;
+        assert_eq!(token_account.owner, spl_token_2022::ID);
+
+        let token_account = StateWithExtensions::<Account>::unpack(&token_account.data).unwrap();
+        assert_eq!(token_account.base.amount, 10);
+        assert_eq!(token_account.base.mint, mint_pubkey);
+
+        let metadata_account = get_account(&mut context, &metadata).await;
+        let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+        assert_eq!(metadata.mint, mint_pubkey);
+        assert_eq!(metadata.token_standard, Some(TokenStandard::Fungible));
+    }
 }
@@ -0,0 +1,88 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use solana_program_test::*;
+use utils::*;
+
+mod create_collection {
+
+    use mpl_token_metadata::{
+        instruction::{
+            create_collection_instructions, set_compute_unit_limit, CreateCollectionParams,
+            CREATE_COLLECTION_SETUP_LEN,
+        },
+        state::{
+            AssetData, CollectionDetails, Metadata, MetadataDelegateRecord, TokenMetadataAccount,
+            TokenRecord, TokenStandard,
+        },
+    };
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn launch_programmable_collection() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let launchpad = Keypair::new().pubkey();
+        let authority = context.payer.pubkey();
+
+        let (addresses, mut instructions) =
+            create_collection_instructions(CreateCollectionParams {
+                mint: mint.pubkey(),
+                authority,
+                payer: authority,
+                asset_data: AssetData::new(
+                    TokenStandard::ProgrammableNonFungible,
+                    "Collection".to_string(),
+                    "COL".to_string(),
+                    "uri".to_string(),
+                ),
+                collection_delegate: Some(launchpad),
+                use_default_rule_set: true,
+            })
+            .unwrap();
+
+        let configure = instructions.split_off(CREATE_COLLECTION_SETUP_LEN);
+        assert_eq!(configure.len(), 2);
+
+        instructions.insert(0, set_compute_unit_limit(400_000));
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&authority),
+            &[&context.payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &configure,
+            Some(&authority),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata_account = get_account(&mut context, &addresses.metadata).await;
+        let metadata = Metadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(
+            metadata.collection_details,
+            Some(CollectionDetails::V1 { size: 0 })
+        );
+        assert!(metadata.use_default_rule_set);
+
+        let token_record_account =
+            get_account(&mut context, &addresses.token_record.unwrap()).await;
+        assert!(TokenRecord::safe_deserialize(&token_record_account.data).is_ok());
+
+        let delegate_record_account =
+            get_account(&mut context, &addresses.delegate_record.unwrap()).await;
+        let delegate_record =
+            MetadataDelegateRecord::from_bytes(&delegate_record_account.data).unwrap();
+        assert_eq!(delegate_record.delegate, launchpad);
+    }
+}
@@ -0,0 +1,281 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::CreateExistingMintMetadataBuilder, create_existing_mint_metadata_message,
+        CreateExistingMintMetadataArgs, InstructionBuilder,
+    },
+    pda::find_metadata_account,
+    state::{
+        BasisPoints, DataV2, Metadata as ProgramMetadata, TokenMetadataAccount, TokenStandard,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    ed25519_program,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod create_existing_mint_metadata {
+
+    use super::*;
+
+    /// Last slot in which the signed messages of the tests are accepted.
+    const EXPIRY_SLOT: u64 = 100;
+
+    fn args() -> CreateExistingMintMetadataArgs {
+        CreateExistingMintMetadataArgs::V1 {
+            data: DataV2 {
+                name: String::from("Legacy Token"),
+                symbol: String::from("LGC"),
+                uri: String::from("uri"),
+                seller_fee_basis_points: BasisPoints::new(0).unwrap(),
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            is_mutable: true,
+            expiry_slot: Some(EXPIRY_SLOT),
+        }
+    }
+
+    /// Returns the message approving the creation of the metadata of the `mint`.
+    fn approval_message(mint: &Pubkey, update_authority: &Pubkey) -> Vec<u8> {
+        let (metadata, _) = find_metadata_account(mint);
+        create_existing_mint_metadata_message(&metadata, mint, update_authority, &args())
+    }
+
+    /// Builds an Ed25519 program instruction with the signature of `signer` over `message`,
+    /// keeping the public key, signature and message in the instruction data.
+    fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+        const DATA_START: u16 = 16;
+        const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+        let public_key_offset = DATA_START;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            CURRENT_INSTRUCTION,
+            public_key_offset,
+            CURRENT_INSTRUCTION,
+            message_offset,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.pubkey().as_ref());
+        data.extend_from_slice(signer.sign_message(message).as_ref());
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::id(),
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn create_ix(
+        mint: &Pubkey,
+        authority: &Pubkey,
+        payer: &Pubkey,
+        authority_as_signer: bool,
+    ) -> Instruction {
+        let (metadata, _) = find_metadata_account(mint);
+
+        CreateExistingMintMetadataBuilder::new()
+            .metadata(metadata)
+            .mint(*mint)
+            .authority(*authority)
+            .payer(*payer)
+            .update_authority(*payer)
+            .authority_as_signer(authority_as_signer)
+            .build(args())
+            .unwrap()
+            .instruction()
+    }
+
+    #[tokio::test]
+    async fn create_with_signed_mint_authority_message() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        create_mint(&mut context, &mint, &mint_authority.pubkey(), None, 6)
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+        let message = approval_message(&mint.pubkey(), &payer);
+
+        // the mint authority does not sign the transaction
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ed25519_instruction(&mint_authority, &message),
+                create_ix(&mint.pubkey(), &mint_authority.pubkey(), &payer, false),
+            ],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let (metadata, _) = find_metadata_account(&mint.pubkey());
+        let account = get_account(&mut context, &metadata).await;
+        let metadata = ProgramMetadata::safe_deserialize(&account.data).unwrap();
+
+        assert_eq!(metadata.mint, mint.pubkey());
+        assert_eq!(metadata.update_authority, payer);
+        assert_eq!(metadata.token_standard, Some(TokenStandard::Fungible));
+        assert!(metadata.is_mutable);
+    }
+
+    #[tokio::test]
+    async fn create_with_freeze_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        let freeze_authority = Keypair::new();
+        create_mint(
+            &mut context,
+            &mint,
+            &mint_authority.pubkey(),
+            Some(&freeze_authority.pubkey()),
+            0,
+        )
+        .await
+        .unwrap();
+
+        let payer = context.payer.pubkey();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix(
+                &mint.pubkey(),
+                &freeze_authority.pubkey(),
+                &payer,
+                true,
+            )],
+            Some(&payer),
+            &[&context.payer, &freeze_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let (metadata, _) = find_metadata_account(&mint.pubkey());
+        let account = get_account(&mut context, &metadata).await;
+        let metadata = ProgramMetadata::safe_deserialize(&account.data).unwrap();
+
+        assert_eq!(metadata.mint, mint.pubkey());
+        assert_eq!(metadata.token_standard, Some(TokenStandard::FungibleAsset));
+    }
+
+    #[tokio::test]
+    async fn fail_message_signed_by_other_key() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        create_mint(&mut context, &mint, &mint_authority.pubkey(), None, 6)
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+        let message = approval_message(&mint.pubkey(), &payer);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ed25519_instruction(&Keypair::new(), &message),
+                create_ix(&mint.pubkey(), &mint_authority.pubkey(), &payer, false),
+            ],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidMintAuthoritySignature);
+    }
+
+    #[tokio::test]
+    async fn fail_message_for_other_metadata() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let other_mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        create_mint(&mut context, &mint, &mint_authority.pubkey(), None, 6)
+            .await
+            .unwrap();
+
+        // the message approves the metadata of another mint of the same authority
+        let payer = context.payer.pubkey();
+        let (other_metadata, _) = find_metadata_account(&other_mint.pubkey());
+        let message =
+            create_existing_mint_metadata_message(&other_metadata, &mint.pubkey(), &payer, &args());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ed25519_instruction(&mint_authority, &message),
+                create_ix(&mint.pubkey(), &mint_authority.pubkey(), &payer, false),
+            ],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidMintAuthoritySignature);
+    }
+
+    #[tokio::test]
+    async fn fail_expired_message() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        create_mint(&mut context, &mint, &mint_authority.pubkey(), None, 6)
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+        let message = approval_message(&mint.pubkey(), &payer);
+
+        context.warp_to_slot(EXPIRY_SLOT + 1).unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ed25519_instruction(&mint_authority, &message),
+                create_ix(&mint.pubkey(), &mint_authority.pubkey(), &payer, false),
+            ],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::MintAuthoritySignatureExpired);
+    }
+}
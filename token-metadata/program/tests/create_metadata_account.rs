@@ -42,7 +42,7 @@ mod create_meta_accounts {
         assert_eq!(metadata.data.name, puffed_name,);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
 
         assert!(!metadata.primary_sale_happened);
@@ -93,7 +93,7 @@ mod create_meta_accounts {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -0,0 +1,189 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{SetCreatorsOverflowBuilder, VerifyV2Builder},
+        InstructionBuilder, SetCreatorsOverflowArgs, VerificationArgs,
+    },
+    pda::find_creators_overflow_account,
+    state::{Creator, CreatorsOverflow, TokenMetadataAccount},
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod creators_overflow {
+
+    use super::*;
+
+    async fn create_metadata(context: &mut ProgramTestContext) -> Metadata {
+        let metadata = Metadata::new();
+        metadata
+            .create_v2(
+                context,
+                String::from("Overflow"),
+                String::from("OVF"),
+                String::from("https://overflow.com"),
+                Some(vec![Creator {
+                    address: context.payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
+                500,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        metadata
+    }
+
+    fn overflow_creators(count: usize, share: u8) -> Vec<Creator> {
+        (0..count)
+            .map(|_| Creator {
+                address: Pubkey::new_unique(),
+                verified: false,
+                share,
+            })
+            .collect()
+    }
+
+    async fn set_creators_overflow(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        creators: Vec<Creator>,
+        overflow_creators: Vec<Creator>,
+    ) -> Result<(), BanksClientError> {
+        let (creators_overflow, _) = find_creators_overflow_account(&metadata.mint.pubkey());
+        let payer = context.payer.pubkey();
+
+        let set_ix = SetCreatorsOverflowBuilder::new()
+            .metadata(metadata.pubkey)
+            .mint(metadata.mint.pubkey())
+            .creators_overflow(creators_overflow)
+            .authority(payer)
+            .payer(payer)
+            .build(SetCreatorsOverflowArgs::V1 {
+                creators,
+                overflow_creators,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn set_and_verify_overflow_creator() {
+        let mut context = program_test().start_with_context().await;
+        let metadata = create_metadata(&mut context).await;
+
+        let overflow_creator = Keypair::new();
+        airdrop(&mut context, &overflow_creator.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let mut creators = overflow_creators(5, 10);
+        creators[0] = Creator {
+            address: context.payer.pubkey(),
+            verified: true,
+            share: 10,
+        };
+        let mut overflow = overflow_creators(9, 5);
+        overflow.push(Creator {
+            address: overflow_creator.pubkey(),
+            verified: false,
+            share: 5,
+        });
+
+        set_creators_overflow(&mut context, &metadata, creators, overflow)
+            .await
+            .unwrap();
+
+        let metadata_account = metadata.get_data(&mut context).await;
+        assert_eq!(metadata_account.overflow_creators_share, Some(50));
+
+        // the overflow creator verifies itself on the creators overflow account
+
+        let (creators_overflow, _) = find_creators_overflow_account(&metadata.mint.pubkey());
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(overflow_creator.pubkey())
+            .metadata(metadata.pubkey)
+            .creators_overflow(creators_overflow)
+            .build(VerificationArgs::CreatorV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&overflow_creator.pubkey()),
+            &[&overflow_creator],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = get_account(&mut context, &creators_overflow).await;
+        let overflow = CreatorsOverflow::safe_deserialize(&account.data).unwrap();
+
+        let all_creators = metadata_account
+            .all_creators(Some(&overflow))
+            .unwrap()
+            .unwrap();
+        assert_eq!(all_creators.len(), 15);
+        assert!(
+            all_creators
+                .iter()
+                .find(|c| c.address == overflow_creator.pubkey())
+                .unwrap()
+                .verified
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_set_creators_overflow_with_invalid_shares() {
+        let mut context = program_test().start_with_context().await;
+        let metadata = create_metadata(&mut context).await;
+
+        let creators = vec![Creator {
+            address: context.payer.pubkey(),
+            verified: true,
+            share: 50,
+        }];
+
+        // shares add up to 110
+        let error = set_creators_overflow(
+            &mut context,
+            &metadata,
+            creators.clone(),
+            overflow_creators(6, 10),
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(error, MetadataError::ShareTotalMustBe100);
+
+        // too many overflow creators
+        let error =
+            set_creators_overflow(&mut context, &metadata, creators, overflow_creators(11, 0))
+                .await
+                .unwrap_err();
+        assert_custom_error!(error, MetadataError::CreatorsTooLong);
+    }
+}
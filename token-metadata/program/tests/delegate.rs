@@ -5,7 +5,7 @@ use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError,
     signature::{Keypair, Signer},
-    transaction::TransactionError,
+    transaction::{Transaction, TransactionError},
 };
 use utils::*;
 
@@ -14,10 +14,14 @@ mod delegate {
     use mpl_token_auth_rules::error::RuleSetError;
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{DelegateArgs, MetadataDelegateRole},
+        instruction::{
+            builders::{DelegateBuilder, RevokeBuilder},
+            DelegateArgs, InstructionBuilder, MetadataDelegateRole, RevokeArgs,
+        },
         pda::{find_metadata_delegate_record_account, find_token_record_account},
         state::{
             Key, Metadata, MetadataDelegateRecord, TokenDelegateRole, TokenRecord, TokenStandard,
+            TokenState,
         },
     };
     use num_traits::FromPrimitive;
@@ -62,6 +66,7 @@ mod delegate {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -131,6 +136,7 @@ mod delegate {
                 user_pubkey,
                 DelegateArgs::CollectionV1 {
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -184,6 +190,7 @@ mod delegate {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -240,6 +247,7 @@ mod delegate {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -309,6 +317,7 @@ mod delegate {
                 DelegateArgs::UtilityV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -367,6 +376,7 @@ mod delegate {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -431,6 +441,7 @@ mod delegate {
                     amount: 1,
                     locked_address: asset.metadata,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -515,6 +526,7 @@ mod delegate {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -524,4 +536,191 @@ mod delegate {
 
         assert_custom_error_ix!(1, error, RuleSetError::ProgramOwnedListCheckFailed);
     }
+
+    #[tokio::test]
+    async fn set_escrow_delegate_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        assert!(asset.token.is_some());
+
+        // delegates the management of the asset escrow
+
+        let user = Keypair::new();
+        let user_pubkey = user.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                user_pubkey,
+                DelegateArgs::EscrowV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // asserts
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.delegate, Some(user_pubkey));
+        assert_eq!(token_record.delegate_role, Some(TokenDelegateRole::Escrow));
+        assert_eq!(token_record.state, TokenState::Unlocked);
+    }
+
+    #[tokio::test]
+    async fn cannot_set_expired_transfer_delegate() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        assert!(asset.token.is_some());
+
+        // delegates the asset for transfer with an expiry in the past
+
+        let user = Keypair::new();
+        let user_pubkey = user.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .delegate(
+                &mut context,
+                payer,
+                user_pubkey,
+                DelegateArgs::TransferV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: Some(0),
+                },
+            )
+            .await
+            .unwrap_err();
+
+        // asserts
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateExpiry);
+    }
+
+    #[tokio::test]
+    async fn delegate_and_revoke_with_baseline_accounts() {
+        let mut context = program_test().start_with_context().await;
+
+        // clients unaware of the optional accounts appended to 'Delegate' and 'Revoke'
+        // send only the original 14 accounts
+        const BASELINE_ACCOUNTS: usize = 14;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        let delegate = Keypair::new().pubkey();
+        let (token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        // delegates the asset
+
+        let mut delegate_ix = DelegateBuilder::new()
+            .delegate(delegate)
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .master_edition(asset.master_edition.unwrap())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            })
+            .unwrap()
+            .instruction();
+        delegate_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[delegate_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(record.delegate, Some(delegate));
+
+        // revokes the delegate
+
+        let mut revoke_ix = RevokeBuilder::new()
+            .delegate(delegate)
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .master_edition(asset.master_edition.unwrap())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(RevokeArgs::UtilityV1)
+            .unwrap()
+            .instruction();
+        revoke_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[revoke_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(record.delegate, None);
+    }
 }
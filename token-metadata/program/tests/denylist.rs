@@ -0,0 +1,316 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use borsh::BorshSerialize;
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{AddToDenylistBuilder, TransferBuilder},
+        CollectionDetailsToggle, DenylistArgs, InstructionBuilder, TransferArgs, UpdateArgs,
+    },
+    pda::{find_denylisted_account, find_token_record_account},
+    state::{CollectionDetails, Denylisted, TokenStandard, DENYLISTED_SIZE},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account as SdkAccount, AccountSharedData},
+    compute_budget::ComputeBudgetInstruction,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+use utils::*;
+
+mod denylist {
+
+    use super::*;
+
+    /// Writes a `Denylisted` account for the mint directly to the bank, since assets can
+    /// only be added to the denylist by the protocol authority.
+    async fn inject_denylisted(context: &mut ProgramTestContext, mint: &Pubkey) {
+        let (denylisted, bump) = find_denylisted_account(mint);
+
+        let state = Denylisted {
+            bump,
+            mint: *mint,
+            payer: context.payer.pubkey(),
+            ..Default::default()
+        };
+
+        let mut data = vec![0u8; DENYLISTED_SIZE];
+        state.serialize(&mut &mut data[..]).unwrap();
+
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let account = SdkAccount {
+            lamports: rent.minimum_balance(DENYLISTED_SIZE),
+            data,
+            owner: mpl_token_metadata::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        context.set_account(&denylisted, &AccountSharedData::from(account));
+    }
+
+    /// Creates a sized collection parent and a minted asset that is a verified item of it.
+    async fn create_verified_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+        collection_parent
+            .into_collection_parent(context, update_authority)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+        da.add_to_collection(context, &collection_parent, update_authority)
+            .await
+            .unwrap();
+
+        (collection_parent, da)
+    }
+
+    async fn enforce_denylist(
+        context: &mut ProgramTestContext,
+        collection_parent: &DigitalAsset,
+        update_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V2 {
+            size: 0,
+            enforce_denylist: true,
+        });
+
+        collection_parent
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+    }
+
+    async fn transfer(
+        context: &mut ProgramTestContext,
+        da: &DigitalAsset,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let params = TransferFromParams {
+            context,
+            authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        da.transfer_from(params).await
+    }
+
+    /// Transfers the asset with the accounts of a client unaware of the collection pause and
+    /// denylist, optionally including the collection metadata.
+    async fn transfer_without_denylisted(
+        context: &mut ProgramTestContext,
+        da: &DigitalAsset,
+        authority: &Keypair,
+        collection_metadata: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let destination_owner = Keypair::new().pubkey();
+        let destination_token = get_associated_token_address(&destination_owner, &da.mint.pubkey());
+
+        let mut builder = TransferBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .token_owner(authority.pubkey())
+            .token(da.token.unwrap())
+            .destination_owner(destination_owner)
+            .destination(destination_token)
+            .destination_token_record(
+                find_token_record_account(&da.mint.pubkey(), &destination_token).0,
+            )
+            .metadata(da.metadata)
+            .edition(da.master_edition.unwrap())
+            .payer(authority.pubkey())
+            .mint(da.mint.pubkey());
+
+        if let Some(collection_metadata) = collection_metadata {
+            builder.collection_metadata(collection_metadata);
+        }
+
+        let transfer_ix = builder
+            .build(TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                create_associated_token_account(
+                    &authority.pubkey(),
+                    &destination_owner,
+                    &da.mint.pubkey(),
+                    &spl_token::id(),
+                ),
+                transfer_ix,
+            ],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn stolen_asset_cannot_be_transferred() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        enforce_denylist(context, &collection_parent, &update_authority)
+            .await
+            .unwrap();
+
+        // opting in keeps the size of the collection
+        let parent_metadata = collection_parent.get_metadata(context).await;
+        assert_eq!(
+            parent_metadata.collection_details,
+            Some(CollectionDetails::V2 {
+                size: 1,
+                enforce_denylist: true,
+            })
+        );
+
+        inject_denylisted(context, &da.mint.pubkey()).await;
+
+        let err = transfer(context, &da, &update_authority).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::StolenAsset);
+    }
+
+    #[tokio::test]
+    async fn stolen_asset_transfers_without_opt_in() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (_collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        inject_denylisted(context, &da.mint.pubkey()).await;
+
+        transfer(context, &da, &update_authority).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn asset_not_on_denylist_transfers_with_opt_in() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        enforce_denylist(context, &collection_parent, &update_authority)
+            .await
+            .unwrap();
+
+        transfer(context, &da, &update_authority).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn verified_item_transfers_without_collection_accounts() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        enforce_denylist(context, &collection_parent, &update_authority)
+            .await
+            .unwrap();
+
+        // clients that do not include the collection accounts keep working
+
+        transfer_without_denylisted(context, &da, &update_authority, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_transfer_with_opt_in_without_denylisted_account() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        enforce_denylist(context, &collection_parent, &update_authority)
+            .await
+            .unwrap();
+
+        // the collection metadata shows that the collection enforces the denylist, so the
+        // denylisted account of the asset is required
+
+        let err = transfer_without_denylisted(
+            context,
+            &da,
+            &update_authority,
+            Some(collection_parent.metadata),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MissingDenylistAccount);
+    }
+
+    #[tokio::test]
+    async fn fail_add_to_denylist_without_protocol_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (_collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        let (denylisted, _) = find_denylisted_account(&da.mint.pubkey());
+
+        let add_ix = AddToDenylistBuilder::new()
+            .denylisted(denylisted)
+            .mint(da.mint.pubkey())
+            .metadata(da.metadata)
+            .authority(update_authority.pubkey())
+            .payer(update_authority.pubkey())
+            .build(DenylistArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[add_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidFlagAuthority);
+
+        let denylisted_account = context.banks_client.get_account(denylisted).await.unwrap();
+        assert!(denylisted_account.is_none());
+    }
+}
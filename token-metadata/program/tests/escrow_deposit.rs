@@ -0,0 +1,228 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    escrow::{create_escrow_account, find_escrow_account, find_escrow_ledger_account},
+    instruction::{builders::EscrowDepositBuilder, EscrowDepositArgs, InstructionBuilder},
+    state::{EscrowAuthority, EscrowLedger, TokenMetadataAccount, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use utils::*;
+
+mod escrow_deposit {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn success_deposit() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        let escrow = create_escrow(&mut context, &asset).await;
+        let (attribute_mint, attribute_src) = create_attribute_tokens(&mut context, 10).await;
+
+        let depositor = context.payer.dirty_clone();
+        deposit(
+            &mut context,
+            &asset,
+            &escrow,
+            &attribute_mint.pubkey(),
+            &attribute_src.pubkey(),
+            &depositor,
+            4,
+        )
+        .await
+        .unwrap();
+
+        let escrow_token = get_associated_token_address(&escrow, &attribute_mint.pubkey());
+        let account = get_account(&mut context, &escrow_token).await;
+        let escrow_token = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(escrow_token.amount, 4);
+        assert_eq!(escrow_token.owner, escrow);
+
+        let account = get_account(&mut context, &attribute_src.pubkey()).await;
+        let attribute_src = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(attribute_src.amount, 6);
+
+        // the ledger is created and records the balance of the escrow
+        let (escrow_ledger, _) = find_escrow_ledger_account(&escrow);
+        let account = get_account(&mut context, &escrow_ledger).await;
+        let ledger = EscrowLedger::safe_deserialize(&account.data).unwrap();
+        assert_eq!(ledger.escrow, escrow);
+        assert_eq!(ledger.amount(&attribute_mint.pubkey()), 4);
+    }
+
+    #[tokio::test]
+    async fn fail_deposit_with_wrong_depositor() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        let escrow = create_escrow(&mut context, &asset).await;
+        let (attribute_mint, attribute_src) = create_attribute_tokens(&mut context, 10).await;
+
+        // not the owner of the source token account
+        let depositor = Keypair::new();
+        let error = deposit(
+            &mut context,
+            &asset,
+            &escrow,
+            &attribute_mint.pubkey(),
+            &attribute_src.pubkey(),
+            &depositor,
+            4,
+        )
+        .await
+        .unwrap_err();
+
+        // spl-token owner mismatch
+        assert!(
+            matches!(
+                error,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(4)
+                ))
+            ),
+            "Expected owner mismatch but got '{:#?}'",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_deposit_into_wrong_escrow() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        create_escrow(&mut context, &asset).await;
+
+        // the escrow of another asset
+        let other_asset = create_asset(&mut context).await;
+        let other_escrow = create_escrow(&mut context, &other_asset).await;
+        let (attribute_mint, attribute_src) = create_attribute_tokens(&mut context, 10).await;
+
+        let depositor = context.payer.dirty_clone();
+        let error = deposit(
+            &mut context,
+            &asset,
+            &other_escrow,
+            &attribute_mint.pubkey(),
+            &attribute_src.pubkey(),
+            &depositor,
+            4,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::DerivedKeyInvalid);
+    }
+
+    async fn create_asset(context: &mut ProgramTestContext) -> DigitalAsset {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        asset
+    }
+
+    /// Creates the token-owned escrow of the asset, held by the payer.
+    async fn create_escrow(context: &mut ProgramTestContext, asset: &DigitalAsset) -> Pubkey {
+        let (escrow, _) = find_escrow_account(&asset.mint.pubkey(), &EscrowAuthority::TokenOwner);
+
+        let ix = create_escrow_account(
+            mpl_token_metadata::ID,
+            escrow,
+            asset.metadata,
+            asset.mint.pubkey(),
+            asset.token.unwrap(),
+            asset.master_edition.unwrap(),
+            context.payer.pubkey(),
+            None,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        escrow
+    }
+
+    /// Mints `amount` tokens of a new fungible mint to a token account of the payer.
+    async fn create_attribute_tokens(
+        context: &mut ProgramTestContext,
+        amount: u64,
+    ) -> (Keypair, Keypair) {
+        let attribute_mint = Keypair::new();
+        let attribute_src = Keypair::new();
+        let payer = context.payer.pubkey();
+
+        create_mint(context, &attribute_mint, &payer, None, 0)
+            .await
+            .unwrap();
+        create_token_account(context, &attribute_src, &attribute_mint.pubkey(), &payer)
+            .await
+            .unwrap();
+        mint_tokens(
+            context,
+            &attribute_mint.pubkey(),
+            &attribute_src.pubkey(),
+            amount,
+            &payer,
+            None,
+        )
+        .await
+        .unwrap();
+
+        (attribute_mint, attribute_src)
+    }
+
+    /// Deposits tokens into the associated token account of the escrow.
+    async fn deposit(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        escrow: &Pubkey,
+        attribute_mint: &Pubkey,
+        attribute_src: &Pubkey,
+        depositor: &Keypair,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let (escrow_ledger, _) = find_escrow_ledger_account(escrow);
+
+        let ix = EscrowDepositBuilder::new()
+            .escrow(*escrow)
+            .metadata(asset.metadata)
+            .escrow_mint(asset.mint.pubkey())
+            .escrow_ledger(escrow_ledger)
+            .attribute_mint(*attribute_mint)
+            .attribute_src(*attribute_src)
+            .attribute_dst(get_associated_token_address(escrow, attribute_mint))
+            .depositor(depositor.pubkey())
+            .payer(context.payer.pubkey())
+            .build(EscrowDepositArgs::V1 { amount })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, depositor],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+}
@@ -0,0 +1,221 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    escrow::{create_escrow_account, find_escrow_account},
+    instruction::{builders::EscrowTransferOutBuilder, EscrowTransferOutArgs, InstructionBuilder},
+    state::{EscrowAuthority, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use utils::*;
+
+mod escrow_transfer_out {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn success_transfer_out_by_holder() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        let escrow = create_escrow(&mut context, &asset).await;
+        let attribute_mint = fund_escrow(&mut context, &escrow, 10).await;
+
+        let holder = context.payer.dirty_clone();
+        transfer_out(
+            &mut context,
+            &asset,
+            &escrow,
+            &attribute_mint.pubkey(),
+            &holder,
+            4,
+        )
+        .await
+        .unwrap();
+
+        let escrow_token = get_associated_token_address(&escrow, &attribute_mint.pubkey());
+        let account = get_account(&mut context, &escrow_token).await;
+        let escrow_token = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(escrow_token.amount, 6);
+
+        let holder_token = get_associated_token_address(&holder.pubkey(), &attribute_mint.pubkey());
+        let account = get_account(&mut context, &holder_token).await;
+        let holder_token = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(holder_token.amount, 4);
+        assert_eq!(holder_token.owner, holder.pubkey());
+    }
+
+    #[tokio::test]
+    async fn fail_transfer_out_with_wrong_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        let escrow = create_escrow(&mut context, &asset).await;
+        let attribute_mint = fund_escrow(&mut context, &escrow, 10).await;
+
+        // neither the holder nor a delegate of the asset
+        let authority = Keypair::new();
+        let error = transfer_out(
+            &mut context,
+            &asset,
+            &escrow,
+            &attribute_mint.pubkey(),
+            &authority,
+            4,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::MustBeEscrowAuthority);
+    }
+
+    #[tokio::test]
+    async fn fail_transfer_out_of_wrong_escrow() {
+        let mut context = program_test().start_with_context().await;
+
+        let asset = create_asset(&mut context).await;
+        create_escrow(&mut context, &asset).await;
+
+        // the escrow of another asset of the same holder
+        let other_asset = create_asset(&mut context).await;
+        let other_escrow = create_escrow(&mut context, &other_asset).await;
+        let attribute_mint = fund_escrow(&mut context, &other_escrow, 10).await;
+
+        let holder = context.payer.dirty_clone();
+        let error = transfer_out(
+            &mut context,
+            &asset,
+            &other_escrow,
+            &attribute_mint.pubkey(),
+            &holder,
+            4,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::DerivedKeyInvalid);
+    }
+
+    async fn create_asset(context: &mut ProgramTestContext) -> DigitalAsset {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        asset
+    }
+
+    /// Creates the token-owned escrow of the asset, held by the payer.
+    async fn create_escrow(context: &mut ProgramTestContext, asset: &DigitalAsset) -> Pubkey {
+        let (escrow, _) = find_escrow_account(&asset.mint.pubkey(), &EscrowAuthority::TokenOwner);
+
+        let ix = create_escrow_account(
+            mpl_token_metadata::ID,
+            escrow,
+            asset.metadata,
+            asset.mint.pubkey(),
+            asset.token.unwrap(),
+            asset.master_edition.unwrap(),
+            context.payer.pubkey(),
+            None,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        escrow
+    }
+
+    /// Mints `amount` tokens of a new fungible mint to the token account of the escrow.
+    async fn fund_escrow(
+        context: &mut ProgramTestContext,
+        escrow: &Pubkey,
+        amount: u64,
+    ) -> Keypair {
+        let attribute_mint = Keypair::new();
+        let payer = context.payer.pubkey();
+        create_mint(context, &attribute_mint, &payer, None, 0)
+            .await
+            .unwrap();
+
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            escrow,
+            &attribute_mint.pubkey(),
+            &spl_token::ID,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let escrow_token = get_associated_token_address(escrow, &attribute_mint.pubkey());
+        mint_tokens(
+            context,
+            &attribute_mint.pubkey(),
+            &escrow_token,
+            amount,
+            &payer,
+            None,
+        )
+        .await
+        .unwrap();
+
+        attribute_mint
+    }
+
+    /// Transfers tokens out of the escrow to the associated token account of the payer.
+    async fn transfer_out(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        escrow: &Pubkey,
+        attribute_mint: &Pubkey,
+        authority: &Keypair,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = EscrowTransferOutBuilder::new()
+            .escrow(*escrow)
+            .metadata(asset.metadata)
+            .escrow_mint(asset.mint.pubkey())
+            .escrow_token(asset.token.unwrap())
+            .attribute_mint(*attribute_mint)
+            .attribute_src(get_associated_token_address(escrow, attribute_mint))
+            .attribute_dst(get_associated_token_address(
+                &context.payer.pubkey(),
+                attribute_mint,
+            ))
+            .authority(authority.pubkey())
+            .payer(context.payer.pubkey())
+            .build(EscrowTransferOutArgs::V1 { amount })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{FlagBuilder, UnflagBuilder},
+        FlagArgs, InstructionBuilder, UnflagArgs,
+    },
+    pda::find_flagged_account,
+    state::{FlagReason, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod flag {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fail_flag_without_protocol_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        let (flagged, _) = find_flagged_account(&da.mint.pubkey());
+
+        let flag_ix = FlagBuilder::new()
+            .flagged(flagged)
+            .mint(da.mint.pubkey())
+            .metadata(da.metadata)
+            .authority(payer.pubkey())
+            .payer(payer.pubkey())
+            .build(FlagArgs::V1 {
+                reason: FlagReason::Spam,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[flag_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidFlagAuthority);
+
+        // the flagged account was not created
+        let flagged_account = context.banks_client.get_account(flagged).await.unwrap();
+        assert!(flagged_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_unflag_without_protocol_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        let (flagged, _) = find_flagged_account(&da.mint.pubkey());
+
+        let unflag_ix = UnflagBuilder::new()
+            .flagged(flagged)
+            .mint(da.mint.pubkey())
+            .metadata(da.metadata)
+            .authority(payer.pubkey())
+            .payer(payer.pubkey())
+            .rent_destination(payer.pubkey())
+            .build(UnflagArgs::V1 {
+                reason: FlagReason::Spam,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[unflag_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidFlagAuthority);
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use solana_program_test::*;
+use utils::*;
+
+mod get_program_capabilities {
+
+    use mpl_token_metadata::{
+        id,
+        instruction::{get_program_capabilities, ProgramCapabilities, ProgramCapability},
+        return_data::decode_return_data,
+    };
+    use solana_sdk::{signature::Signer, transaction::Transaction};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_supported_capabilities() {
+        let mut context = program_test().start_with_context().await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[get_program_capabilities(id())],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+        assert!(simulation.result.unwrap().is_ok());
+
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, id());
+
+        let capabilities = decode_return_data::<ProgramCapabilities>(&return_data.data).unwrap();
+        assert_eq!(capabilities, ProgramCapabilities::supported());
+        assert!(capabilities.contains(ProgramCapability::BatchUpdateV1));
+    }
+}
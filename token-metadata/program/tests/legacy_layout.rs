@@ -0,0 +1,154 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::TransferArgs,
+    pda::find_programmable_config_extension_account,
+    state::{ProgrammableConfig, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+mod legacy_layout {
+
+    use super::*;
+
+    /// Creates a collection parent and a minted programmable asset that is a verified item
+    /// of it.
+    async fn create_verified_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        da.add_to_collection(context, &collection_parent, update_authority)
+            .await
+            .unwrap();
+
+        (collection_parent, da)
+    }
+
+    #[tokio::test]
+    async fn sync_programmable_config_to_extension_and_back() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = context.payer.dirty_clone();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+        let collection = collection_parent.mint.pubkey();
+
+        collection_parent
+            .set_legacy_layout(context, &update_authority, true)
+            .await
+            .unwrap();
+        da.sync_layout(context, &collection).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.programmable_config_in_extension);
+        assert_eq!(metadata.programmable_config, None);
+
+        // the extension is required to transfer the asset
+        let params = TransferFromParams {
+            context,
+            authority: &update_authority,
+            source_owner: &update_authority.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &update_authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+        let err = da.transfer_from(params).await.unwrap_err();
+        assert_custom_error_ix!(1, err, MetadataError::InvalidProgrammableConfigExtension);
+
+        collection_parent
+            .set_legacy_layout(context, &update_authority, false)
+            .await
+            .unwrap();
+        da.sync_layout(context, &collection).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(!metadata.programmable_config_in_extension);
+        assert_eq!(
+            metadata.programmable_config,
+            Some(ProgrammableConfig::V1 { rule_set: None })
+        );
+
+        let (extension, _) = find_programmable_config_extension_account(&da.mint.pubkey());
+        assert!(context
+            .banks_client
+            .get_account(extension)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn sync_without_verified_collection_keeps_layout() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::ProgrammableNonFungible, None)
+            .await
+            .unwrap();
+
+        da.sync_layout(context, &Keypair::new().pubkey())
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(!metadata.programmable_config_in_extension);
+        assert_eq!(
+            metadata.programmable_config,
+            Some(ProgrammableConfig::V1 { rule_set: None })
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_set_legacy_layout_with_invalid_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::new();
+        airdrop(context, &authority.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let err = collection_parent
+            .set_legacy_layout(context, &authority, true)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -9,16 +9,34 @@ mod lock {
 
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::DelegateArgs,
-        pda::find_token_record_account,
-        state::{TokenRecord, TokenStandard, TokenState},
+        instruction::{
+            builders::{
+                FreezeCollectionItemBuilder, LockBuilder, SelfLockBuilder, SelfUnlockBuilder,
+                SetCollectionFreezeOptInBuilder, ThawCollectionItemBuilder, UnlockBuilder,
+                VerifyV2Builder,
+            },
+            CollectionToggle, DelegateArgs, FreezeCollectionItemArgs, InstructionBuilder, LockArgs,
+            MetadataDelegateRole, SelfLockArgs, SelfUnlockArgs, SetCollectionFreezeOptInArgs,
+            ThawCollectionItemArgs, TransferArgs, UnlockArgs, UpdateArgs, VerificationArgs,
+        },
+        pda::{
+            find_collection_items_delegate_record_account, find_metadata_delegate_record_account,
+            find_token_record_account,
+        },
+        state::{
+            Collection, CollectionItemsDelegateRecord, TokenRecord, TokenStandard, TokenState,
+        },
+    };
+    use solana_program::{
+        borsh::try_from_slice_unchecked, native_token::LAMPORTS_PER_SOL, program_option::COption,
+        program_pack::Pack, pubkey::Pubkey,
     };
-    use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack, pubkey::Pubkey};
     use solana_sdk::{
         instruction::InstructionError,
         signature::{Keypair, Signer},
-        transaction::TransactionError,
+        transaction::{Transaction, TransactionError},
     };
+    use spl_associated_token_account::get_associated_token_address;
     use spl_token::state::Account;
 
     use super::*;
@@ -138,6 +156,7 @@ mod lock {
                 DelegateArgs::UtilityV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -253,6 +272,7 @@ mod lock {
                 DelegateArgs::UtilityV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -288,6 +308,7 @@ mod lock {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -338,6 +359,7 @@ mod lock {
                     amount: 1,
                     locked_address: Pubkey::default(),
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -365,4 +387,841 @@ mod lock {
         assert_eq!(token_record.state, TokenState::Locked);
         assert_eq!(token_record.locked_transfer, Some(Pubkey::default()));
     }
+
+    #[tokio::test]
+    async fn delegate_and_lock_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a utility delegate and lock the asset
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // asserts
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Locked);
+        assert_eq!(token_record.delegate, Some(delegate_pubkey));
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        assert!(token.is_frozen());
+    }
+
+    #[tokio::test]
+    async fn delegate_and_lock_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // set a standard delegate and lock the asset
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::StandardV1 { amount: 1 },
+            )
+            .await
+            .unwrap();
+
+        // asserts
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        assert!(token.is_frozen());
+        assert_eq!(token.delegate, COption::Some(delegate_pubkey));
+    }
+
+    #[tokio::test]
+    async fn fail_delegate_and_lock_sale_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // a sale delegate cannot unlock the asset
+
+        let delegate = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::SaleV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateRole);
+    }
+
+    #[tokio::test]
+    async fn fail_delegate_and_lock_invalid_roles_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // only utility, staking and locked transfer delegates can lock a
+        // programmable asset
+
+        let invalid_roles = vec![
+            DelegateArgs::TransferV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            },
+            DelegateArgs::StandardV1 { amount: 1 },
+        ];
+
+        for args in invalid_roles {
+            let delegate = Keypair::new();
+            let payer = context.payer.dirty_clone();
+
+            let error = asset
+                .delegate_and_lock(&mut context, payer, delegate.pubkey(), args)
+                .await
+                .unwrap_err();
+
+            assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateRole);
+        }
+
+        // metadata delegates are not token delegates and cannot lock the asset
+
+        let delegate = Keypair::new();
+        let payer = context.payer.dirty_clone();
+
+        let error = asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::CollectionV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateArgs);
+    }
+
+    #[tokio::test]
+    async fn fail_delegate_and_lock_invalid_role_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // non-programmable assets can only be locked by a standard delegate
+
+        let delegate = Keypair::new();
+        let payer = context.payer.dirty_clone();
+
+        let error = asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateRole);
+    }
+
+    #[tokio::test]
+    async fn fail_delegate_and_lock_standard_without_master_edition() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // the master edition is the freeze authority required to lock the asset
+
+        asset.master_edition = None;
+
+        let delegate = Keypair::new();
+        let payer = context.payer.dirty_clone();
+
+        let error = asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::StandardV1 { amount: 1 },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::MissingEditionAccount);
+
+        // the token is not frozen nor delegated
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        assert!(!token.is_frozen());
+        assert_eq!(token.delegate, COption::None);
+    }
+
+    #[tokio::test]
+    async fn locked_transfer_delegate_transfer_locked_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a locked transfer delegate and lock the asset
+
+        let delegate = Keypair::new();
+        airdrop(&mut context, &delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+        let locked_address = Keypair::new().pubkey();
+        let payer = context.payer.dirty_clone();
+
+        asset
+            .delegate_and_lock(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::LockedTransferV1 {
+                    amount: 1,
+                    locked_address,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // the holder cannot transfer the locked asset
+
+        let authority = context.payer.dirty_clone();
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: locked_address,
+            destination_token: None,
+            authorization_rules: None,
+            payer: &authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        let error = asset.transfer_from(params).await.unwrap_err();
+
+        assert_custom_error_ix!(2, error, MetadataError::LockedToken);
+
+        // the delegate can transfer the locked asset to the locked address
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &delegate,
+            source_owner: &authority.pubkey(),
+            destination_owner: locked_address,
+            destination_token: None,
+            authorization_rules: None,
+            payer: &delegate,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        asset.transfer_from(params).await.unwrap();
+
+        // asserts
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Unlocked);
+        assert_eq!(token_record.delegate, None);
+
+        let destination = get_associated_token_address(&locked_address, &asset.mint.pubkey());
+        let token_account = get_account(&mut context, &destination).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+
+        assert_eq!(token.amount, 1);
+        assert!(token.is_frozen());
+    }
+
+    #[tokio::test]
+    async fn fail_collection_items_delegate_lock_non_member_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // collection and item (not a member of the collection)
+
+        let mut collection = DigitalAsset::default();
+        collection
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // the holder approves a collection items delegate
+
+        let delegate = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        collection
+            .delegate(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::CollectionItemsV1 {
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (delegate_record, _) = find_collection_items_delegate_record_account(
+            &collection.mint.pubkey(),
+            &context.payer.pubkey(),
+            &delegate.pubkey(),
+        );
+
+        let record_account = get_account(&mut context, &delegate_record).await;
+        let record: CollectionItemsDelegateRecord =
+            try_from_slice_unchecked(&record_account.data).unwrap();
+        assert_eq!(record.holder, context.payer.pubkey());
+        assert_eq!(record.locked_items, 0);
+
+        // tries to lock an item outside of the collection
+
+        let ix = LockBuilder::new()
+            .authority(delegate.pubkey())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .payer(context.payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .collection_delegate_record(delegate_record)
+            .build(LockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&delegate, &context.payer],
+            context.last_blockhash,
+        );
+
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotAMemberOfCollection);
+    }
+
+    #[tokio::test]
+    async fn holder_self_lock_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // the holder locks the asset
+
+        let authority = context.payer.dirty_clone();
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        let self_lock_ix = SelfLockBuilder::new()
+            .authority(authority.pubkey())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .token_record(pda_key)
+            .payer(authority.pubkey())
+            .build(SelfLockArgs::V1 { cooldown: 3600 })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[self_lock_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert!(token_record.holder_locked);
+
+        // the holder lock blocks transfers
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: Pubkey::new_unique(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        let error = asset.transfer_from(params).await.unwrap_err();
+
+        assert_custom_error_ix!(2, error, MetadataError::HolderLocked);
+
+        // the lock cannot be removed before the cool-down elapses
+
+        let self_unlock_ix = SelfUnlockBuilder::new()
+            .authority(authority.pubkey())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .token_record(pda_key)
+            .payer(authority.pubkey())
+            .build(SelfUnlockArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[self_unlock_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::HolderLockCooldown);
+    }
+
+    #[tokio::test]
+    async fn collection_freeze_delegate_freezes_opted_in_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let authority = context.payer.dirty_clone();
+
+        // collection and a verified item of it
+
+        let mut collection_parent = DigitalAsset::default();
+        collection_parent
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: false,
+        });
+
+        asset
+            .update(&mut context, authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(authority.pubkey())
+            .metadata(asset.metadata)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .collection_master_edition(collection_parent.master_edition.unwrap())
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the collection update authority approves a collection freeze delegate
+
+        let delegate = Keypair::new();
+        airdrop(&mut context, &delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        collection_parent
+            .delegate(
+                &mut context,
+                authority.dirty_clone(),
+                delegate.pubkey(),
+                DelegateArgs::CollectionFreezeV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &collection_parent.mint.pubkey(),
+            MetadataDelegateRole::CollectionFreeze,
+            &authority.pubkey(),
+            &delegate.pubkey(),
+        );
+        let (token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        let freeze_ix = FreezeCollectionItemBuilder::new()
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .build(FreezeCollectionItemArgs::V1)
+            .unwrap()
+            .instruction();
+
+        // the item cannot be frozen until the holder opts in
+
+        let tx = Transaction::new_signed_with_payer(
+            &[freeze_ix.clone()],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::CollectionFreezeNotOptedIn);
+
+        let opt_in_ix = SetCollectionFreezeOptInBuilder::new()
+            .authority(authority.pubkey())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .token_record(token_record)
+            .payer(authority.pubkey())
+            .build(SetCollectionFreezeOptInArgs::V1 { opt_in: true })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[opt_in_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[freeze_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert!(record.collection_frozen);
+
+        // the frozen item cannot be transferred
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: Pubkey::new_unique(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        let error = asset.transfer_from(params).await.unwrap_err();
+
+        assert_custom_error_ix!(2, error, MetadataError::CollectionFrozen);
+
+        // the delegate thaws the item
+
+        let thaw_ix = ThawCollectionItemBuilder::new()
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .build(ThawCollectionItemArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[thaw_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert!(!record.collection_frozen);
+        assert!(record.collection_freeze_opt_in);
+    }
+
+    #[tokio::test]
+    async fn lock_and_unlock_with_baseline_accounts() {
+        let mut context = program_test().start_with_context().await;
+
+        // clients unaware of the optional account appended to 'Lock' and 'Unlock' send
+        // only the original 13 accounts
+        const BASELINE_ACCOUNTS: usize = 13;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a utility delegate
+
+        let delegate = Keypair::new();
+        airdrop(&mut context, &delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+        let payer = context.payer.dirty_clone();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        // locks the asset
+
+        let mut lock_ix = LockBuilder::new()
+            .authority(delegate.pubkey())
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .payer(delegate.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(LockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        lock_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[lock_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(record.state, TokenState::Locked);
+
+        // unlocks the asset
+
+        let mut unlock_ix = UnlockBuilder::new()
+            .authority(delegate.pubkey())
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .token(asset.token.unwrap())
+            .token_record(token_record)
+            .payer(delegate.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(UnlockArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+        unlock_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[unlock_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let pda = get_account(&mut context, &token_record).await;
+        let record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(record.state, TokenState::Unlocked);
+    }
 }
@@ -0,0 +1,252 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{MigrateBuilder, OpenMigrationWindowBuilder},
+        InstructionBuilder, MigrateArgs, OpenMigrationWindowArgs,
+    },
+    pda::{
+        find_migration_opt_out_account, find_migration_window_account, find_token_record_account,
+    },
+    state::{TokenStandard, MIN_MIGRATION_GRACE_PERIOD},
+};
+use num_traits::FromPrimitive;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod migrate_collection {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn success_migrate_collection_batch() {
+        let mut context = program_test().start_with_context().await;
+
+        let authority = context.payer.dirty_clone();
+        let collection = create_collection(&mut context, &authority).await;
+
+        let first = create_collection_item(&mut context, &collection, &authority).await;
+        let second = create_collection_item(&mut context, &collection, &authority).await;
+
+        let ix =
+            migrate_collection_instruction(&context, &collection, &[&first, &second], &authority);
+        process(&mut context, ix, &authority).await.unwrap();
+
+        for asset in [&first, &second] {
+            let metadata = asset.get_metadata(&mut context).await;
+            assert_eq!(
+                metadata.token_standard,
+                Some(TokenStandard::ProgrammableNonFungible)
+            );
+
+            // the token record of the migrated asset is created
+            let (token_record, _) =
+                find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+            let account = get_account(&mut context, &token_record).await;
+            assert!(!account.data.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_migrate_item_outside_collection() {
+        let mut context = program_test().start_with_context().await;
+
+        let authority = context.payer.dirty_clone();
+        let collection = create_collection(&mut context, &authority).await;
+
+        let item = create_collection_item(&mut context, &collection, &authority).await;
+
+        let mut outsider = DigitalAsset::new();
+        outsider
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let ix =
+            migrate_collection_instruction(&context, &collection, &[&item, &outsider], &authority);
+        let error = process(&mut context, ix, &authority).await.unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotAMemberOfCollection);
+
+        // the batch is atomic: the item of the collection is not migrated either
+        let metadata = item.get_metadata(&mut context).await;
+        assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+    }
+
+    #[tokio::test]
+    async fn fail_migrate_with_wrong_collection_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let authority = context.payer.dirty_clone();
+        let collection = create_collection(&mut context, &authority).await;
+
+        let item = create_collection_item(&mut context, &collection, &authority).await;
+
+        let fake_authority = Keypair::new();
+        let ix = migrate_collection_instruction(&context, &collection, &[&item], &fake_authority);
+        let error = process(&mut context, ix, &fake_authority)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+    }
+
+    #[tokio::test]
+    async fn fail_migrate_with_malformed_account_groups() {
+        let mut context = program_test().start_with_context().await;
+
+        let authority = context.payer.dirty_clone();
+        let collection = create_collection(&mut context, &authority).await;
+
+        let first = create_collection_item(&mut context, &collection, &authority).await;
+        let second = create_collection_item(&mut context, &collection, &authority).await;
+
+        // drops the opt-out account of the second asset, leaving an incomplete group
+        let mut ix =
+            migrate_collection_instruction(&context, &collection, &[&first, &second], &authority);
+        ix.accounts.pop();
+
+        let error = process(&mut context, ix, &authority).await.unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::NotEnoughAccountKeys
+                ))
+            ),
+            "Expected not enough account keys but got '{:#?}'",
+            error
+        );
+    }
+
+    /// Creates a collection parent and opens its migration window, warping the clock
+    /// past the grace period.
+    async fn create_collection(
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> DigitalAsset {
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let (migration_window, _) = find_migration_window_account(&collection.mint.pubkey());
+
+        let ix = OpenMigrationWindowBuilder::new()
+            .migration_window(migration_window)
+            .collection_mint(collection.mint.pubkey())
+            .collection_metadata(collection.metadata)
+            .authority(authority.pubkey())
+            .payer(context.payer.pubkey())
+            .build(OpenMigrationWindowArgs::V1 {
+                grace_period: MIN_MIGRATION_GRACE_PERIOD,
+            })
+            .unwrap()
+            .instruction();
+
+        process(context, ix, authority).await.unwrap();
+
+        warp_clock(context, MIN_MIGRATION_GRACE_PERIOD).await;
+
+        collection
+    }
+
+    /// Creates a non-fungible asset that is a verified item of the collection.
+    async fn create_collection_item(
+        context: &mut ProgramTestContext,
+        collection: &DigitalAsset,
+        authority: &Keypair,
+    ) -> DigitalAsset {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        asset
+            .add_to_collection(context, collection, authority)
+            .await
+            .unwrap();
+
+        asset
+    }
+
+    /// Builds a `CollectionV1` migration of the assets, the first one being specified by
+    /// the accounts of the instruction and the others by groups of remaining accounts.
+    fn migrate_collection_instruction(
+        context: &ProgramTestContext,
+        collection: &DigitalAsset,
+        assets: &[&DigitalAsset],
+        authority: &Keypair,
+    ) -> Instruction {
+        let (first, others) = assets.split_first().unwrap();
+
+        let (token_record, _) =
+            find_token_record_account(&first.mint.pubkey(), &first.token.unwrap());
+
+        let mut ix = MigrateBuilder::new()
+            .metadata(first.metadata)
+            .edition(first.master_edition.unwrap())
+            .token(first.token.unwrap())
+            .token_owner(context.payer.pubkey())
+            .mint(first.mint.pubkey())
+            .payer(context.payer.pubkey())
+            .authority(authority.pubkey())
+            .collection_metadata(collection.metadata)
+            .delegate_record(mpl_token_metadata::ID)
+            .token_record(token_record)
+            .build(MigrateArgs::CollectionV1 { rule_set: None })
+            .unwrap()
+            .instruction();
+
+        let (migration_window, _) = find_migration_window_account(&collection.mint.pubkey());
+        let (migration_opt_out, _) = find_migration_opt_out_account(&first.mint.pubkey());
+        ix.accounts.extend([
+            AccountMeta::new_readonly(migration_window, false),
+            AccountMeta::new_readonly(migration_opt_out, false),
+        ]);
+
+        for asset in others {
+            let (token_record, _) =
+                find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+            let (migration_opt_out, _) = find_migration_opt_out_account(&asset.mint.pubkey());
+
+            ix.accounts.extend([
+                AccountMeta::new(asset.metadata, false),
+                AccountMeta::new(asset.master_edition.unwrap(), false),
+                AccountMeta::new_readonly(asset.mint.pubkey(), false),
+                AccountMeta::new(asset.token.unwrap(), false),
+                AccountMeta::new(token_record, false),
+                AccountMeta::new_readonly(migration_opt_out, false),
+            ]);
+        }
+
+        ix
+    }
+
+    async fn process(
+        context: &mut ProgramTestContext,
+        ix: Instruction,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+}
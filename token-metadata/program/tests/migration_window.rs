@@ -0,0 +1,247 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{OpenMigrationWindowBuilder, OptOutMigrationBuilder},
+        InstructionBuilder, OpenMigrationWindowArgs, OptOutMigrationArgs,
+    },
+    pda::{find_migration_opt_out_account, find_migration_window_account},
+    state::{
+        MigrationOptOut, MigrationWindow, TokenMetadataAccount, TokenStandard,
+        MIN_MIGRATION_GRACE_PERIOD,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod migration_window {
+
+    use super::*;
+
+    async fn open_migration_window(
+        context: &mut ProgramTestContext,
+        collection: &DigitalAsset,
+        authority: &Keypair,
+        grace_period: i64,
+    ) -> Result<(), BanksClientError> {
+        let (migration_window, _) = find_migration_window_account(&collection.mint.pubkey());
+
+        let ix = OpenMigrationWindowBuilder::new()
+            .migration_window(migration_window)
+            .collection_mint(collection.mint.pubkey())
+            .collection_metadata(collection.metadata)
+            .authority(authority.pubkey())
+            .payer(context.payer.pubkey())
+            .build(OpenMigrationWindowArgs::V1 { grace_period })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn opt_out_migration(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        collection_mint: &Pubkey,
+        token_owner: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (migration_opt_out, _) = find_migration_opt_out_account(&asset.mint.pubkey());
+        let (migration_window, _) = find_migration_window_account(collection_mint);
+
+        let ix = OptOutMigrationBuilder::new()
+            .migration_opt_out(migration_opt_out)
+            .migration_window(migration_window)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .token(asset.token.unwrap())
+            .token_owner(token_owner.pubkey())
+            .payer(context.payer.pubkey())
+            .build(OptOutMigrationArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, token_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn open_window_with_grace_period() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = context.payer.dirty_clone();
+        open_migration_window(
+            &mut context,
+            &collection,
+            &authority,
+            MIN_MIGRATION_GRACE_PERIOD,
+        )
+        .await
+        .unwrap();
+
+        let (migration_window, _) = find_migration_window_account(&collection.mint.pubkey());
+        let account = get_account(&mut context, &migration_window).await;
+        let window = MigrationWindow::safe_deserialize(&account.data).unwrap();
+
+        assert_eq!(window.collection_mint, collection.mint.pubkey());
+        assert!(window.migrate_after >= MIN_MIGRATION_GRACE_PERIOD);
+    }
+
+    #[tokio::test]
+    async fn fail_open_migration_window_with_short_grace_period() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = context.payer.dirty_clone();
+        let error = open_migration_window(
+            &mut context,
+            &collection,
+            &authority,
+            MIN_MIGRATION_GRACE_PERIOD - 1,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidMigrationGracePeriod);
+    }
+
+    #[tokio::test]
+    async fn fail_open_migration_window_with_invalid_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::new();
+        airdrop(&mut context, &authority.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let error = open_migration_window(
+            &mut context,
+            &collection,
+            &authority,
+            MIN_MIGRATION_GRACE_PERIOD,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+    }
+
+    #[tokio::test]
+    async fn opt_out_during_grace_period() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let authority = context.payer.dirty_clone();
+        let asset = create_collection_item(&mut context, &collection, &authority).await;
+
+        open_migration_window(
+            &mut context,
+            &collection,
+            &authority,
+            MIN_MIGRATION_GRACE_PERIOD,
+        )
+        .await
+        .unwrap();
+
+        opt_out_migration(&mut context, &asset, &collection.mint.pubkey(), &authority)
+            .await
+            .unwrap();
+
+        let (migration_opt_out, _) = find_migration_opt_out_account(&asset.mint.pubkey());
+        let account = get_account(&mut context, &migration_opt_out).await;
+        let opt_out = MigrationOptOut::safe_deserialize(&account.data).unwrap();
+        assert_eq!(opt_out.mint, asset.mint.pubkey());
+
+        // holders cannot opt out after the grace period
+        let other = create_collection_item(&mut context, &collection, &authority).await;
+
+        warp_clock(&mut context, MIN_MIGRATION_GRACE_PERIOD).await;
+
+        let error = opt_out_migration(&mut context, &other, &collection.mint.pubkey(), &authority)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::MigrationGracePeriodElapsed);
+    }
+
+    #[tokio::test]
+    async fn fail_opt_out_without_verified_collection() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let token_owner = context.payer.dirty_clone();
+        let error = opt_out_migration(&mut context, &asset, &Keypair::new().pubkey(), &token_owner)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotAMemberOfCollection);
+    }
+
+    /// Creates a non-fungible asset that is a verified item of the collection.
+    async fn create_collection_item(
+        context: &mut ProgramTestContext,
+        collection: &DigitalAsset,
+        authority: &Keypair,
+    ) -> DigitalAsset {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        asset
+            .add_to_collection(context, collection, authority)
+            .await
+            .unwrap();
+
+        asset
+    }
+}
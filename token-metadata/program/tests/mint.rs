@@ -2,14 +2,23 @@
 pub mod utils;
 
 use solana_program_test::*;
-use solana_sdk::{instruction::InstructionError, signature::Signer, transaction::TransactionError};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
 use utils::*;
 
 mod mint {
 
-    use mpl_token_metadata::{error::MetadataError, state::TokenStandard};
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{builders::MintBuilder, InstructionBuilder, MintArgs},
+        state::TokenStandard,
+    };
     use num_traits::FromPrimitive;
-    use solana_program::program_pack::Pack;
+    use solana_program::{program_pack::Pack, pubkey::Pubkey};
+    use spl_associated_token_account::get_associated_token_address;
     use spl_token::state::Account;
 
     use super::*;
@@ -161,4 +170,205 @@ mod mint {
 
         assert_custom_error_ix!(1, error, MetadataError::EditionsMustHaveExactlyOneToken);
     }
+
+    #[tokio::test]
+    async fn mint_batch_fungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::Fungible, None)
+            .await
+            .unwrap();
+
+        let owners = [context.payer.pubkey(), Pubkey::new_unique()];
+        asset
+            .mint_batch(&mut context, &owners, vec![10, 20])
+            .await
+            .unwrap();
+
+        for (owner, amount) in owners.iter().zip([10, 20]) {
+            let token = get_associated_token_address(owner, &asset.mint.pubkey());
+            let account = get_account(&mut context, &token).await;
+            let token_account = Account::unpack(&account.data).unwrap();
+
+            assert_eq!(token_account.amount, amount);
+        }
+    }
+
+    #[tokio::test]
+    async fn try_mint_batch_mismatched_recipients() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::Fungible, None)
+            .await
+            .unwrap();
+
+        let owners = [context.payer.pubkey()];
+        let error = asset
+            .mint_batch(&mut context, &owners, vec![10, 20])
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidBatchRecipients);
+    }
+
+    #[tokio::test]
+    async fn try_mint_batch_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let owners = [context.payer.pubkey()];
+        let error = asset
+            .mint_batch(&mut context, &owners, vec![1])
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidTokenStandard);
+    }
+
+    #[tokio::test]
+    async fn try_mint_nonfungible_above_max_supply() {
+        let mut context = program_test().start_with_context().await;
+
+        // legacy NonFungible with its single token already minted
+        let nft = Metadata::new();
+        nft.create_v3_default(&mut context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&nft);
+        master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        let payer_pubkey = context.payer.pubkey();
+
+        let mint_ix = MintBuilder::new()
+            .token(nft.token.pubkey())
+            .token_owner(payer_pubkey)
+            .metadata(nft.pubkey)
+            .master_edition(master_edition.pubkey)
+            .mint(nft.mint.pubkey())
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .build(MintArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mint_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::MintSupplyExceeded);
+    }
+
+    #[tokio::test]
+    async fn mint_fungible_to_recipient() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::Fungible, None)
+            .await
+            .unwrap();
+
+        // the token owner account defaults to the recipient of the args
+
+        let payer_pubkey = context.payer.pubkey();
+        let recipient = Pubkey::new_unique();
+        let token = get_associated_token_address(&recipient, &asset.mint.pubkey());
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .build(MintArgs::V2 {
+                amount: 25,
+                authorization_data: None,
+                token_owner: recipient,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mint_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = get_account(&mut context, &token).await;
+        let token_account = Account::unpack(&account.data).unwrap();
+
+        assert_eq!(token_account.amount, 25);
+        assert_eq!(token_account.owner, recipient);
+    }
+
+    #[tokio::test]
+    async fn try_mint_to_recipient_with_other_token_account() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::Fungible, None)
+            .await
+            .unwrap();
+
+        // the token account of the payer is not the associated token account of
+        // the recipient
+
+        let payer_pubkey = context.payer.pubkey();
+        let token = get_associated_token_address(&payer_pubkey, &asset.mint.pubkey());
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .build(MintArgs::V2 {
+                amount: 25,
+                authorization_data: None,
+                token_owner: Pubkey::new_unique(),
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mint_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::DerivedKeyInvalid);
+    }
 }
@@ -0,0 +1,274 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{BurnBuilder, CreateBuilder, MintBuilder},
+        BurnArgs, CreateArgs, DelegateArgs, InstructionBuilder, MintArgs, TransferArgs,
+    },
+    pda::{find_master_edition_account, find_metadata_account, find_token_record_account},
+    state::{AssetData, PrintSupply, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::program_pack::Pack;
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token::state::AccountState;
+use utils::*;
+
+mod non_transferable {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn non_transferable_can_only_be_burned_by_holder() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonTransferable, None, None, 1)
+            .await
+            .unwrap();
+
+        // the token is kept frozen
+
+        let token_account = get_account(context, &asset.token.unwrap()).await;
+        let token = spl_token::state::Account::unpack(&token_account.data).unwrap();
+        assert_eq!(token.amount, 1);
+        assert_eq!(token.state, AccountState::Frozen);
+
+        // the holder can neither transfer nor delegate the asset
+
+        let owner = context.payer.dirty_clone();
+
+        let params = TransferFromParams {
+            context,
+            authority: &owner,
+            source_owner: &owner.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &owner,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+        let err = asset.transfer_from(params).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::NonTransferableAsset);
+
+        let err = asset
+            .delegate(
+                context,
+                owner.dirty_clone(),
+                Keypair::new().pubkey(),
+                DelegateArgs::StandardV1 { amount: 1 },
+            )
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+
+        // the holder can burn the asset
+
+        let burn_ix = BurnBuilder::new()
+            .authority(owner.pubkey())
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .token_account(asset.token.unwrap())
+            .edition(asset.master_edition.unwrap())
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&owner.pubkey()),
+            &[&owner],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata_account = context
+            .banks_client
+            .get_account(asset.metadata)
+            .await
+            .unwrap();
+        assert!(metadata_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_legacy_freeze_and_thaw_non_transferable() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonTransferable, None, None, 1)
+            .await
+            .unwrap();
+
+        let owner = context.payer.dirty_clone();
+
+        // the holder cannot thaw (or freeze) the token through the legacy instructions
+
+        for ix in [
+            mpl_token_metadata::instruction::thaw_delegated_account(
+                mpl_token_metadata::id(),
+                owner.pubkey(),
+                asset.token.unwrap(),
+                asset.master_edition.unwrap(),
+                asset.mint.pubkey(),
+            ),
+            mpl_token_metadata::instruction::freeze_delegated_account(
+                mpl_token_metadata::id(),
+                owner.pubkey(),
+                asset.token.unwrap(),
+                asset.master_edition.unwrap(),
+                asset.mint.pubkey(),
+            ),
+        ] {
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&owner.pubkey()),
+                &[&owner],
+                context.last_blockhash,
+            );
+            let err = context
+                .banks_client
+                .process_transaction(tx)
+                .await
+                .unwrap_err();
+            assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+        }
+
+        let token_account = get_account(context, &asset.token.unwrap()).await;
+        let token = spl_token::state::Account::unpack(&token_account.data).unwrap();
+        assert_eq!(token.state, AccountState::Frozen);
+    }
+
+    #[tokio::test]
+    async fn fail_permanent_delegate_burn_non_transferable() {
+        let context = &mut program_test().start_with_context().await;
+
+        let payer = context.payer.dirty_clone();
+        let permanent_delegate = Keypair::new();
+        let mint = Keypair::new();
+        let (metadata, _) = find_metadata_account(&mint.pubkey());
+        let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+        let create_ix = CreateBuilder::new()
+            .metadata(metadata)
+            .master_edition(master_edition)
+            .mint(mint.pubkey())
+            .authority(payer.pubkey())
+            .payer(payer.pubkey())
+            .update_authority(payer.pubkey())
+            .spl_token_program(spl_token_2022::ID)
+            .initialize_mint(true)
+            .update_authority_as_signer(true)
+            .build(CreateArgs::V2 {
+                asset_data: AssetData::new(
+                    TokenStandard::NonTransferable,
+                    String::from(DEFAULT_NAME),
+                    String::from(DEFAULT_SYMBOL),
+                    String::from(DEFAULT_URI),
+                ),
+                decimals: Some(0),
+                print_supply: Some(PrintSupply::Zero),
+                permanent_delegate: Some(permanent_delegate.pubkey()),
+            })
+            .unwrap()
+            .instruction();
+
+        let token = get_associated_token_address_with_program_id(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            &spl_token_2022::ID,
+        );
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .token_record(find_token_record_account(&mint.pubkey(), &token).0)
+            .token_owner(payer.pubkey())
+            .metadata(metadata)
+            .master_edition(master_edition)
+            .mint(mint.pubkey())
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token_2022::ID)
+            .build(MintArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                create_ix,
+                mint_ix,
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the permanent delegate cannot burn the asset
+
+        let burn_ix = BurnBuilder::new()
+            .authority(permanent_delegate.pubkey())
+            .metadata(metadata)
+            .mint(mint.pubkey())
+            .token_account(token)
+            .edition(master_edition)
+            .spl_token_program(spl_token_2022::ID)
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &permanent_delegate],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UnauthorizedBurn);
+
+        let account = get_account(context, &metadata).await;
+        assert!(!account.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fail_create_non_transferable_with_prints() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        let err = asset
+            .create_with_print_supply(
+                context,
+                TokenStandard::NonTransferable,
+                None,
+                PrintSupply::Limited(10),
+            )
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+    }
+}
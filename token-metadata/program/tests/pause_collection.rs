@@ -0,0 +1,275 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::instruction::{
+    builders::{BurnBuilder, VerifyV2Builder},
+    InstructionBuilder,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod pause_collection {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{BurnArgs, CollectionToggle, TransferArgs, UpdateArgs, VerificationArgs},
+        pda::find_collection_pause_account,
+        state::{
+            Collection, TokenStandard, COLLECTION_PAUSE_COOLDOWN, MAX_COLLECTION_PAUSE_DURATION,
+        },
+    };
+
+    use super::*;
+
+    /// Creates a collection parent and a minted asset that is a verified item of it.
+    async fn create_verified_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: false,
+        });
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .collection_master_edition(collection_parent.master_edition.unwrap())
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&update_authority.pubkey()),
+            &[update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        (collection_parent, da)
+    }
+
+    async fn transfer(
+        context: &mut ProgramTestContext,
+        da: &DigitalAsset,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let params = TransferFromParams {
+            context,
+            authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: authority,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        da.transfer_from(params).await
+    }
+
+    #[tokio::test]
+    async fn pause_blocks_transfer_until_unpaused() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        collection_parent
+            .pause_collection(context, &update_authority, MAX_COLLECTION_PAUSE_DURATION)
+            .await
+            .unwrap();
+
+        let err = transfer(context, &da, &update_authority).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionPaused);
+
+        collection_parent
+            .unpause_collection(context, &update_authority)
+            .await
+            .unwrap();
+
+        transfer(context, &da, &update_authority).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pause_blocks_burn() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        collection_parent
+            .pause_collection(context, &update_authority, MAX_COLLECTION_PAUSE_DURATION)
+            .await
+            .unwrap();
+
+        let burn_ix = BurnBuilder::new()
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .token_account(da.token.unwrap())
+            .edition(da.master_edition.unwrap())
+            .collection_metadata(collection_parent.metadata)
+            .collection_pause(find_collection_pause_account(&collection_parent.mint.pubkey()).0)
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionPaused);
+    }
+
+    #[tokio::test]
+    async fn pause_expires_after_duration() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        collection_parent
+            .pause_collection(context, &update_authority, 60)
+            .await
+            .unwrap();
+
+        warp_clock(context, 60).await;
+
+        transfer(context, &da, &update_authority).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_renew_pause_before_cooldown() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_verified_item(context, &update_authority).await;
+
+        collection_parent
+            .pause_collection(context, &update_authority, 60)
+            .await
+            .unwrap();
+
+        // the pause cannot be extended while active
+
+        let err = collection_parent
+            .pause_collection(context, &update_authority, 61)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionPauseCooldown);
+
+        // nor renewed right after it ends
+
+        warp_clock(context, 60).await;
+
+        let err = collection_parent
+            .pause_collection(context, &update_authority, 62)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionPauseCooldown);
+
+        transfer(context, &da, &update_authority).await.unwrap();
+
+        // once the cooldown elapses, the collection can be paused again
+
+        warp_clock(context, COLLECTION_PAUSE_COOLDOWN).await;
+
+        collection_parent
+            .pause_collection(context, &update_authority, 63)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_pause_longer_than_max_duration() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let err = collection_parent
+            .pause_collection(
+                context,
+                &update_authority,
+                MAX_COLLECTION_PAUSE_DURATION + 1,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidPauseDuration);
+    }
+
+    #[tokio::test]
+    async fn fail_pause_with_invalid_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let fake_authority = Keypair::new();
+        airdrop(context, &fake_authority.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let err = collection_parent
+            .pause_collection(context, &fake_authority, MAX_COLLECTION_PAUSE_DURATION)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
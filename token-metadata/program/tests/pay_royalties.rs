@@ -0,0 +1,189 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{PayRoyaltiesBuilder, VerifyV2Builder},
+        InstructionBuilder, PayRoyaltiesArgs, VerificationArgs,
+    },
+    state::Creator,
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::AccountMeta, native_token::LAMPORTS_PER_SOL};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod pay_royalties {
+
+    use super::*;
+
+    async fn pay(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        buyer: &Keypair,
+        recipients: &[Pubkey],
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut pay_ix = PayRoyaltiesBuilder::new()
+            .payer(buyer.pubkey())
+            .metadata(metadata.pubkey)
+            .build(PayRoyaltiesArgs::V1 { amount })
+            .unwrap()
+            .instruction();
+
+        pay_ix.accounts.extend(
+            recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(*recipient, false)),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[pay_ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn pay_lamports_to_verified_creators() {
+        let mut context = program_test().start_with_context().await;
+
+        let first_creator = context.payer.pubkey();
+        let second_creator = Keypair::new();
+        let unverified_creator = Pubkey::new_unique();
+        let buyer = Keypair::new();
+        airdrop(&mut context, &second_creator.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+        airdrop(&mut context, &buyer.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let metadata = Metadata::new();
+        metadata
+            .create_v2(
+                &mut context,
+                String::from("Royalties"),
+                String::from("RYL"),
+                String::from("https://royalties.com"),
+                Some(vec![
+                    Creator {
+                        address: first_creator,
+                        verified: true,
+                        share: 50,
+                    },
+                    Creator {
+                        address: second_creator.pubkey(),
+                        verified: false,
+                        share: 30,
+                    },
+                    Creator {
+                        address: unverified_creator,
+                        verified: false,
+                        share: 20,
+                    },
+                ]),
+                500,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(second_creator.pubkey())
+            .metadata(metadata.pubkey)
+            .build(VerificationArgs::CreatorV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&second_creator.pubkey()),
+            &[&second_creator],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let first_balance = get_account(&mut context, &first_creator).await.lamports;
+        let second_balance = get_account(&mut context, &second_creator.pubkey())
+            .await
+            .lamports;
+
+        // unverified creators are not paid, so the amount is split 50/30
+        pay(
+            &mut context,
+            &metadata,
+            &buyer,
+            &[first_creator, second_creator.pubkey()],
+            8_000,
+        )
+        .await
+        .unwrap();
+
+        let first_account = get_account(&mut context, &first_creator).await;
+        assert_eq!(first_account.lamports, first_balance + 5_000);
+
+        let second_account = get_account(&mut context, &second_creator.pubkey()).await;
+        assert_eq!(second_account.lamports, second_balance + 3_000);
+    }
+
+    #[tokio::test]
+    async fn fail_pay_with_invalid_recipient() {
+        let mut context = program_test().start_with_context().await;
+
+        let creator = context.payer.pubkey();
+        let buyer = Keypair::new();
+        airdrop(&mut context, &buyer.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let metadata = Metadata::new();
+        metadata
+            .create_v2(
+                &mut context,
+                String::from("Royalties"),
+                String::from("RYL"),
+                String::from("https://royalties.com"),
+                Some(vec![Creator {
+                    address: creator,
+                    verified: true,
+                    share: 100,
+                }]),
+                500,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let error = pay(
+            &mut context,
+            &metadata,
+            &buyer,
+            &[Pubkey::new_unique()],
+            1_000,
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(error, MetadataError::InvalidRoyaltyRecipient);
+
+        // one recipient per verified creator is required
+        let error = pay(&mut context, &metadata, &buyer, &[], 1_000)
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::InvalidRoyaltyRecipient);
+    }
+}
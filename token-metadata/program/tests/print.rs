@@ -0,0 +1,135 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use utils::*;
+
+mod print {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{builders::PrintBuilder, InstructionBuilder, PrintArgs},
+        pda::{
+            find_edition_account, find_master_edition_account,
+            find_master_edition_extension_account, find_metadata_account,
+        },
+        state::{Key, PrintSupply, TokenStandard, EDITION_MARKER_BIT_SIZE},
+    };
+    use solana_program::program_pack::Pack;
+    use solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+    use spl_associated_token_account::get_associated_token_address;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn print_edition_and_transfer_between_wallets() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_with_print_supply(
+                &mut context,
+                TokenStandard::NonFungible,
+                None,
+                PrintSupply::Limited(10),
+            )
+            .await
+            .unwrap();
+        asset.mint(&mut context, None, None, 1).await.unwrap();
+
+        let mut print = asset.print_edition(&mut context, 1).await.unwrap();
+
+        let edition = get_account(&mut context, &print.master_edition.unwrap()).await;
+        assert_eq!(edition.data[0], Key::EditionV1 as u8);
+
+        let alice = context.payer.dirty_clone();
+        let bob = Keypair::new();
+        print
+            .transfer_between(&mut context, &alice, &bob)
+            .await
+            .unwrap();
+
+        let token = get_account(&mut context, &print.token.unwrap()).await;
+        let token = spl_token::state::Account::unpack(&token.data).unwrap();
+        assert_eq!(token.owner, bob.pubkey());
+        assert_eq!(token.amount, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_print_from_zero_supply_master_edition() {
+        let mut context = program_test().start_with_context().await;
+
+        // master edition (created with a print supply of zero)
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // mint of the new edition
+
+        let edition_mint = Keypair::new();
+        let payer_pubkey = context.payer.pubkey();
+        create_mint(
+            &mut context,
+            &edition_mint,
+            &payer_pubkey,
+            Some(&payer_pubkey),
+            0,
+        )
+        .await
+        .unwrap();
+
+        let edition = 1;
+        let master_mint = asset.mint.pubkey();
+
+        let ix = PrintBuilder::new()
+            .edition_metadata(find_metadata_account(&edition_mint.pubkey()).0)
+            .edition(find_master_edition_account(&edition_mint.pubkey()).0)
+            .edition_mint(edition_mint.pubkey())
+            .edition_token_account_owner(payer_pubkey)
+            .edition_token_account(get_associated_token_address(
+                &payer_pubkey,
+                &edition_mint.pubkey(),
+            ))
+            .edition_mint_authority(payer_pubkey)
+            .master_edition(asset.master_edition.unwrap())
+            .edition_marker_pda(
+                find_edition_account(
+                    &master_mint,
+                    (edition / EDITION_MARKER_BIT_SIZE).to_string(),
+                )
+                .0,
+            )
+            .payer(payer_pubkey)
+            .master_token_account_owner(payer_pubkey)
+            .master_token_account(asset.token.unwrap())
+            .master_metadata(asset.metadata)
+            .update_authority(payer_pubkey)
+            .master_edition_extension(find_master_edition_extension_account(&master_mint).0)
+            .build(PrintArgs::V1 { edition })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::EditionNumberGreaterThanMaxSupply);
+    }
+}
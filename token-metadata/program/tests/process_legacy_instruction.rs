@@ -129,6 +129,7 @@ mod process_legacy_instruction {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -0,0 +1,204 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::BurnBuilder, BurnArgs, DelegateArgs, InstructionBuilder, RevokeArgs, TransferArgs,
+    },
+    state::{TokenDelegateRole, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::clock::Clock;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod rental {
+
+    use super::*;
+
+    /// Duration (in seconds) of the rentals.
+    const RENTAL_DURATION: i64 = 3_600;
+
+    async fn create_rented(
+        context: &mut ProgramTestContext,
+        renter: &Keypair,
+    ) -> Result<DigitalAsset, BanksClientError> {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        let payer = context.payer.dirty_clone();
+
+        asset
+            .delegate(
+                context,
+                payer,
+                renter.pubkey(),
+                DelegateArgs::RentalV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: clock.unix_timestamp + RENTAL_DURATION,
+                },
+            )
+            .await?;
+
+        Ok(asset)
+    }
+
+    async fn transfer(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+    ) -> Result<(), BanksClientError> {
+        let owner = context.payer.dirty_clone();
+
+        let params = TransferFromParams {
+            context,
+            authority: &owner,
+            source_owner: &owner.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &owner,
+            args: TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            },
+        };
+
+        asset.transfer_from(params).await
+    }
+
+    #[tokio::test]
+    async fn rental_blocks_transfer_and_burn_until_expiry() {
+        let context = &mut program_test().start_with_context().await;
+
+        let renter = Keypair::new();
+        let mut asset = create_rented(context, &renter).await.unwrap();
+
+        assert_eq!(
+            asset
+                .get_token_delegate_role(context, &asset.token.unwrap())
+                .await,
+            Some(TokenDelegateRole::Rental)
+        );
+
+        // the owner can neither transfer, burn nor revoke the rented asset
+
+        let err = transfer(context, &asset).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::RentalActive);
+
+        let owner = context.payer.dirty_clone();
+
+        let burn_ix = BurnBuilder::new()
+            .authority(owner.pubkey())
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .token_account(asset.token.unwrap())
+            .edition(asset.master_edition.unwrap())
+            .token_record(asset.token_record.unwrap())
+            .build(BurnArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&owner.pubkey()),
+            &[&owner],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::RentalActive);
+
+        let err = asset
+            .revoke(
+                context,
+                owner.dirty_clone(),
+                owner.dirty_clone(),
+                renter.pubkey(),
+                RevokeArgs::RentalV1,
+            )
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::RentalActive);
+
+        // once the rental expires, anyone can revoke the delegate
+
+        warp_clock(context, RENTAL_DURATION).await;
+
+        let stranger = Keypair::new();
+        airdrop(context, &stranger.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        asset
+            .revoke(
+                context,
+                owner.dirty_clone(),
+                stranger,
+                renter.pubkey(),
+                RevokeArgs::RentalV1,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            asset
+                .get_token_delegate_role(context, &asset.token.unwrap())
+                .await,
+            None
+        );
+
+        transfer(context, &asset).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_rental_of_nonfungible() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        let payer = context.payer.dirty_clone();
+
+        let err = asset
+            .delegate(
+                context,
+                payer,
+                Keypair::new().pubkey(),
+                DelegateArgs::RentalV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: clock.unix_timestamp + RENTAL_DURATION,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidDelegateRole);
+    }
+}
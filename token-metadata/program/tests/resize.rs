@@ -0,0 +1,175 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use solana_program_test::*;
+use utils::*;
+
+mod resize {
+
+    use borsh::BorshSerialize;
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{
+            builders::ResizeBuilder, CollectionToggle, InstructionBuilder, ResizeArgs, UpdateArgs,
+        },
+        state::{Collection, MAX_METADATA_LEN},
+    };
+    use num_traits::FromPrimitive;
+    use solana_program::{pubkey::Pubkey, rent::Rent};
+    use solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    use super::*;
+
+    async fn create_and_resize(context: &mut ProgramTestContext) -> Metadata {
+        let nft = Metadata::new();
+        nft.create_v3(
+            context,
+            "name".to_string(),
+            "symbol".to_string(),
+            "uri".to_string(),
+            None,
+            0,
+            true,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let payer = context.payer.pubkey();
+
+        let ix = ResizeBuilder::new()
+            .metadata(nft.pubkey)
+            .authority(payer)
+            .destination(payer)
+            .build(ResizeArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        nft
+    }
+
+    #[tokio::test]
+    async fn resize_metadata_to_contents() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = Metadata::new();
+        nft.create_v3_default(&mut context).await.unwrap();
+
+        let metadata_account = get_account(&mut context, &nft.pubkey).await;
+        assert_eq!(metadata_account.data.len(), MAX_METADATA_LEN);
+
+        let metadata = nft.get_data(&mut context).await;
+        let expected_len = metadata.try_to_vec().unwrap().len();
+
+        let destination = Keypair::new().pubkey();
+        let payer = context.payer.pubkey();
+
+        let ix = ResizeBuilder::new()
+            .metadata(nft.pubkey)
+            .authority(payer)
+            .destination(destination)
+            .build(ResizeArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the account only has space for its contents and the excess rent is
+        // sent to the destination
+
+        let resized_account = get_account(&mut context, &nft.pubkey).await;
+        assert_eq!(resized_account.data.len(), expected_len);
+
+        let rent = Rent::default();
+        assert_eq!(resized_account.lamports, rent.minimum_balance(expected_len));
+
+        let destination_account = get_account(&mut context, &destination).await;
+        assert_eq!(
+            destination_account.lamports,
+            metadata_account.lamports - resized_account.lamports
+        );
+
+        assert_eq!(nft.get_data(&mut context).await, metadata);
+    }
+
+    #[tokio::test]
+    async fn update_resized_metadata_with_payer() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = create_and_resize(&mut context).await;
+        let resized_len = get_account(&mut context, &nft.pubkey).await.data.len();
+
+        // setting a collection grows the metadata, and the payer funds the rent of
+        // the additional space
+        let mut args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut args;
+        *collection = CollectionToggle::Set(Collection {
+            key: Pubkey::new_unique(),
+            verified: false,
+        });
+
+        let authority = context.payer.dirty_clone();
+        let da = nft.into_digital_asset(&mut context).await;
+        da.update(&mut context, authority, args).await.unwrap();
+
+        let metadata_account = get_account(&mut context, &da.metadata).await;
+        let metadata = da.get_metadata(&mut context).await;
+        let expected_len = metadata.try_to_vec().unwrap().len();
+
+        assert!(metadata.collection.is_some());
+        assert!(expected_len > resized_len);
+        assert_eq!(metadata_account.data.len(), expected_len);
+        assert_eq!(
+            metadata_account.lamports,
+            Rent::default().minimum_balance(expected_len)
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_legacy_update_resized_metadata_without_rent() {
+        let mut context = program_test().start_with_context().await;
+
+        let nft = create_and_resize(&mut context).await;
+
+        // the legacy update does not have a payer to fund the additional space
+        let error = nft
+            .update_v2(
+                &mut context,
+                "name".to_string(),
+                "symbol".to_string(),
+                "uri".to_string(),
+                None,
+                0,
+                true,
+                Some(Collection {
+                    key: Pubkey::new_unique(),
+                    verified: false,
+                }),
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotRentExempt);
+    }
+}
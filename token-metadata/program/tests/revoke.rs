@@ -5,7 +5,7 @@ use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError,
     signature::{Keypair, Signer},
-    transaction::TransactionError,
+    transaction::{Transaction, TransactionError},
 };
 use utils::*;
 
@@ -14,16 +14,23 @@ mod revoke {
     use borsh::BorshSerialize;
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{DelegateArgs, MetadataDelegateRole, RevokeArgs},
-        pda::{find_metadata_delegate_record_account, find_token_record_account},
+        instruction::{
+            builders::RevokeBuilder, DelegateArgs, InstructionBuilder, MetadataDelegateRole,
+            RevokeArgs,
+        },
+        pda::{
+            find_delegate_registry_account, find_metadata_delegate_record_account,
+            find_token_record_account,
+        },
         state::{
-            Key, Metadata, MetadataDelegateRecord, TokenDelegateRole, TokenRecord, TokenStandard,
-            TOKEN_RECORD_SIZE,
+            DelegateRegistry, DelegateRegistryEntry, Key, Metadata, MetadataDelegateRecord,
+            TokenDelegateRole, TokenRecord, TokenStandard, TOKEN_RECORD_SIZE,
         },
     };
     use num_traits::FromPrimitive;
     use solana_program::{
-        borsh::try_from_slice_unchecked, program_option::COption, program_pack::Pack,
+        borsh::try_from_slice_unchecked, instruction::AccountMeta, program_option::COption,
+        program_pack::Pack,
     };
     use solana_sdk::account::{Account as SdkAccount, AccountSharedData};
     use spl_token::state::Account;
@@ -64,6 +71,7 @@ mod revoke {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -149,6 +157,7 @@ mod revoke {
                 user_pubkey,
                 DelegateArgs::CollectionV1 {
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -224,6 +233,7 @@ mod revoke {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -303,6 +313,7 @@ mod revoke {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -386,6 +397,7 @@ mod revoke {
                 DelegateArgs::SaleV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -455,6 +467,7 @@ mod revoke {
                 DelegateArgs::TransferV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -518,4 +531,210 @@ mod revoke {
             panic!("Missing token account");
         }
     }
+
+    #[tokio::test]
+    async fn delegate_registry_lists_metadata_delegates() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // creates a collection and a data delegate
+
+        let collection_delegate = Keypair::new().pubkey();
+        let data_delegate = Keypair::new().pubkey();
+        let payer_pubkey = context.payer.pubkey();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                collection_delegate,
+                DelegateArgs::CollectionV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                data_delegate,
+                DelegateArgs::DataV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // both delegates are on the registry
+
+        let (registry_key, _) = find_delegate_registry_account(&asset.mint.pubkey());
+        let registry_account = get_account(&mut context, &registry_key).await;
+        let registry = DelegateRegistry::from_bytes(&registry_account.data).unwrap();
+
+        let collection_entry = DelegateRegistryEntry {
+            role: MetadataDelegateRole::Collection,
+            delegate: collection_delegate,
+            approver: payer_pubkey,
+        };
+        let data_entry = DelegateRegistryEntry {
+            role: MetadataDelegateRole::Data,
+            delegate: data_delegate,
+            approver: payer_pubkey,
+        };
+
+        assert_eq!(registry.key, Key::DelegateRegistry);
+        assert_eq!(registry.mint, asset.mint.pubkey());
+        assert_eq!(registry.delegates, vec![collection_entry, data_entry]);
+
+        // revokes the collection delegate
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let approver = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        asset
+            .revoke(
+                &mut context,
+                payer,
+                approver,
+                collection_delegate,
+                RevokeArgs::CollectionV1,
+            )
+            .await
+            .unwrap();
+
+        let registry_account = get_account(&mut context, &registry_key).await;
+        let registry = DelegateRegistry::from_bytes(&registry_account.data).unwrap();
+        assert_eq!(registry.delegates, vec![data_entry]);
+        assert_eq!(registry_account.data.len(), registry.serialized_size());
+    }
+
+    #[tokio::test]
+    async fn revoke_all_delegates_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // creates a transfer and a data delegate
+
+        let transfer_delegate = Keypair::new().pubkey();
+        let data_delegate = Keypair::new().pubkey();
+        let payer_pubkey = context.payer.pubkey();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                transfer_delegate,
+                DelegateArgs::TransferV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                data_delegate,
+                DelegateArgs::DataV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (data_record, _) = find_metadata_delegate_record_account(
+            &asset.mint.pubkey(),
+            MetadataDelegateRole::Data,
+            &payer_pubkey,
+            &data_delegate,
+        );
+
+        // revokes all delegates in a single call
+
+        let mut builder = RevokeBuilder::new();
+        builder
+            .delegate(payer_pubkey)
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .master_edition(asset.master_edition.unwrap())
+            .token(asset.token.unwrap())
+            .token_record(asset.token_record.unwrap())
+            .delegate_registry(find_delegate_registry_account(&asset.mint.pubkey()).0)
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .spl_token_program(spl_token::ID);
+
+        let mut revoke_ix = builder.build(RevokeArgs::AllV1).unwrap().instruction();
+        revoke_ix
+            .accounts
+            .push(AccountMeta::new(data_record, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[revoke_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the token delegate was cleared
+
+        let token_record_account = get_account(&mut context, &asset.token_record.unwrap()).await;
+        let token_record: TokenRecord =
+            try_from_slice_unchecked(&token_record_account.data).unwrap();
+        assert_eq!(token_record.delegate, None);
+        assert_eq!(token_record.delegate_role, None);
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        assert!(token.is_frozen());
+        assert_eq!(token.delegate, COption::None);
+
+        // the metadata delegate record was closed
+
+        let data_record_account = context.banks_client.get_account(data_record).await.unwrap();
+        assert!(data_record_account.is_none());
+
+        let (registry_key, _) = find_delegate_registry_account(&asset.mint.pubkey());
+        let registry_account = get_account(&mut context, &registry_key).await;
+        let registry = DelegateRegistry::from_bytes(&registry_account.data).unwrap();
+        assert!(registry.delegates.is_empty());
+    }
 }
@@ -0,0 +1,155 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::RotateAuthorityWithPolicyBuilder, DelegateArgs, InstructionBuilder,
+        MetadataDelegateRole, RotateAuthorityArgs,
+    },
+    pda::{find_delegate_registry_account, find_metadata_delegate_record_account},
+    state::{DelegateRegistry, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod rotate_authority {
+
+    use super::*;
+
+    /// Creates an asset with a `Data` delegate, returning the address of the delegate record.
+    async fn create_delegated(
+        context: &mut ProgramTestContext,
+        asset: &mut DigitalAsset,
+    ) -> Pubkey {
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let delegate = Keypair::new().pubkey();
+        let payer = context.payer.dirty_clone();
+
+        asset
+            .delegate(
+                context,
+                payer,
+                delegate,
+                DelegateArgs::DataV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        find_metadata_delegate_record_account(
+            &asset.mint.pubkey(),
+            MetadataDelegateRole::Data,
+            &context.payer.pubkey(),
+            &delegate,
+        )
+        .0
+    }
+
+    async fn rotate(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        new_update_authority: Pubkey,
+        delegate_records: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let authority = context.payer.dirty_clone();
+
+        let mut rotate_ix = RotateAuthorityWithPolicyBuilder::new()
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(authority.pubkey())
+            .delegate_registry(find_delegate_registry_account(&asset.mint.pubkey()).0)
+            .payer(authority.pubkey())
+            .build(RotateAuthorityArgs::V1 {
+                new_update_authority,
+                unverify_creators: true,
+            })
+            .unwrap()
+            .instruction();
+
+        for delegate_record in delegate_records {
+            rotate_ix
+                .accounts
+                .push(AccountMeta::new(*delegate_record, false));
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[rotate_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn rotate_authority_revokes_delegates_and_unverifies_creators() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        let delegate_record = create_delegated(context, &mut asset).await;
+
+        let new_update_authority = Keypair::new().pubkey();
+        rotate(context, &asset, new_update_authority, &[delegate_record])
+            .await
+            .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, new_update_authority);
+
+        // the previous authority was the only (verified) creator
+        let creators = metadata.data.creators.unwrap();
+        assert!(creators.iter().all(|creator| !creator.verified));
+
+        // the delegate was revoked
+        let delegate_record_account = context
+            .banks_client
+            .get_account(delegate_record)
+            .await
+            .unwrap();
+        assert!(delegate_record_account.is_none());
+
+        let (registry_key, _) = find_delegate_registry_account(&asset.mint.pubkey());
+        let registry_account = get_account(context, &registry_key).await;
+        let registry = DelegateRegistry::from_bytes(&registry_account.data).unwrap();
+        assert!(registry.delegates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fail_rotate_authority_with_missing_delegate_record() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        let delegate_record = create_delegated(context, &mut asset).await;
+
+        let err = rotate(context, &asset, Keypair::new().pubkey(), &[])
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::DelegateNotFound);
+
+        // nothing changed
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+        let delegate_record_account = context
+            .banks_client
+            .get_account(delegate_record)
+            .await
+            .unwrap();
+        assert!(delegate_record_account.is_some());
+    }
+}
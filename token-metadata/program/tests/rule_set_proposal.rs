@@ -0,0 +1,146 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+mod rule_set_proposal {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        pda::find_rule_set_proposal_account,
+        state::{ProgrammableConfig, RuleSetProposal, TokenStandard, MIN_RULE_SET_DELAY},
+    };
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn commit_after_delay() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        let rule_set = Pubkey::new_unique();
+        let delay = 2 * MIN_RULE_SET_DELAY;
+
+        da.propose_rule_set(context, &update_authority, rule_set, Some(delay))
+            .await
+            .unwrap();
+
+        let (rule_set_proposal, _) = find_rule_set_proposal_account(&da.mint.pubkey());
+        let account = get_account(context, &rule_set_proposal).await;
+        let proposal = RuleSetProposal::from_bytes(&account.data).unwrap();
+
+        assert_eq!(proposal.delay, delay);
+        assert_eq!(proposal.rule_set, Some(rule_set));
+
+        // the rule set cannot be committed before the delay elapses
+
+        warp_clock(context, MIN_RULE_SET_DELAY).await;
+
+        let err = da
+            .commit_rule_set(context, &update_authority)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RuleSetProposalNotCommittable);
+
+        warp_clock(context, MIN_RULE_SET_DELAY).await;
+
+        da.commit_rule_set(context, &update_authority)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.programmable_config,
+            Some(ProgrammableConfig::V1 {
+                rule_set: Some(rule_set)
+            })
+        );
+
+        // the proposal is consumed but keeps the delay
+
+        let account = get_account(context, &rule_set_proposal).await;
+        let proposal = RuleSetProposal::from_bytes(&account.data).unwrap();
+
+        assert_eq!(proposal.delay, delay);
+        assert_eq!(proposal.rule_set, None);
+    }
+
+    #[tokio::test]
+    async fn fail_decrease_delay() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        let err = da
+            .propose_rule_set(
+                context,
+                &update_authority,
+                Pubkey::new_unique(),
+                Some(MIN_RULE_SET_DELAY - 1),
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidRuleSetDelay);
+    }
+
+    #[tokio::test]
+    async fn fail_propose_with_invalid_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        let authority = Keypair::new();
+        airdrop(context, &authority.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        let err = da
+            .propose_rule_set(context, &authority, Pubkey::new_unique(), None)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -89,6 +89,9 @@ mod set_collection_size {
         let retrieved_size = if let Some(details) = metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => size,
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("Expected CollectionDetails::V1");
@@ -191,6 +194,9 @@ mod set_collection_size {
         let retrieved_size = if let Some(details) = metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => size,
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("Expected CollectionDetails::V1");
@@ -385,6 +391,9 @@ mod set_collection_size {
         let retrieved_size = if let Some(details) = metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => size,
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("Expected CollectionDetails::V1");
@@ -452,6 +461,9 @@ mod set_collection_size {
         let retrieved_size = if let Some(details) = metadata.collection_details {
             match details {
                 CollectionDetails::V1 { size } => size,
+                CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                    panic!("Expected CollectionDetails::V1")
+                }
             }
         } else {
             panic!("Expected CollectionDetails::V1");
@@ -0,0 +1,100 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use utils::*;
+
+mod set_print_window {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        pda::find_master_edition_extension_account,
+        state::{MasterEditionExtension, TokenStandard},
+    };
+    use solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn set_and_clear_print_window() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .set_print_window(&mut context, &authority, Some((100, 200)))
+            .await
+            .unwrap();
+
+        let (extension_key, bump) = find_master_edition_extension_account(&asset.mint.pubkey());
+        let account = get_account(&mut context, &extension_key).await;
+        let extension = MasterEditionExtension::from_bytes(&account.data).unwrap();
+
+        assert_eq!(extension.bump, bump);
+        assert_eq!(extension.mint, asset.mint.pubkey());
+        assert_eq!(extension.print_window, Some((100, 200)));
+
+        // removes the restriction
+
+        context.warp_to_slot(10).unwrap();
+
+        asset
+            .set_print_window(&mut context, &authority, None)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &extension_key).await;
+        let extension = MasterEditionExtension::from_bytes(&account.data).unwrap();
+
+        assert_eq!(extension.print_window, None);
+    }
+
+    #[tokio::test]
+    async fn fail_set_invalid_print_window() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .set_print_window(&mut context, &authority, Some((200, 100)))
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidPrintWindow);
+    }
+
+    #[tokio::test]
+    async fn fail_set_print_window_wrong_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create(&mut context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let error = asset
+            .set_print_window(&mut context, &Keypair::new(), Some((100, 200)))
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -4,7 +4,8 @@ pub mod utils;
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_metadata::{
     error::MetadataError,
-    instruction::set_token_standard,
+    instruction::{batch_set_token_standard, set_token_standard},
+    pda::find_master_edition_account,
     state::{Creator, Metadata as ProgramMetadata, TokenStandard},
     ID as PROGRAM_ID,
 };
@@ -528,3 +529,120 @@ async fn updating_nonfungible_without_edition_fails() {
 
     assert_custom_error!(err, MetadataError::MissingEditionAccount);
 }
+
+#[tokio::test]
+async fn successfully_batch_update() {
+    let mut context = program_test().start_with_context().await;
+
+    // Create an old version NFT and fungible asset with no token standard set.
+    let test_nft = Metadata::new();
+    test_nft
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let master_edition = MasterEditionV2::new(&test_nft);
+    master_edition.create(&mut context, Some(0)).await.unwrap();
+
+    let test_asset = Metadata::new();
+    test_asset
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            0,
+            false,
+            0,
+        )
+        .await
+        .unwrap();
+
+    // The edition PDA of the fungible asset does not exist.
+    let (asset_edition, _) = find_master_edition_account(&test_asset.mint.pubkey());
+
+    let ix = batch_set_token_standard(
+        PROGRAM_ID,
+        &[
+            (
+                test_nft.pubkey,
+                test_nft.mint.pubkey(),
+                master_edition.pubkey,
+            ),
+            (test_asset.pubkey, test_asset.mint.pubkey(), asset_edition),
+        ],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let md_account = get_account(&mut context, &test_nft.pubkey).await;
+    let metadata = ProgramMetadata::deserialize(&mut md_account.data.as_slice()).unwrap();
+    assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+
+    let md_account = get_account(&mut context, &test_asset.pubkey).await;
+    let metadata = ProgramMetadata::deserialize(&mut md_account.data.as_slice()).unwrap();
+    assert_eq!(metadata.token_standard, Some(TokenStandard::FungibleAsset));
+}
+
+#[tokio::test]
+async fn batch_update_with_incorrect_edition_fails() {
+    let mut context = program_test().start_with_context().await;
+
+    // Create an old version NFT with no token standard set.
+    let test_nft = Metadata::new();
+    test_nft
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let master_edition = MasterEditionV2::new(&test_nft);
+    master_edition.create(&mut context, Some(0)).await.unwrap();
+
+    // The edition account must be the edition PDA of the mint, so an NFT cannot be
+    // backfilled as a fungible asset.
+    let ix = batch_set_token_standard(
+        PROGRAM_ID,
+        &[(
+            test_nft.pubkey,
+            test_nft.mint.pubkey(),
+            Keypair::new().pubkey(),
+        )],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let err = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(err, MetadataError::DerivedKeyInvalid);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{builders::SettlePrimarySaleBuilder, InstructionBuilder, SettlePrimarySaleArgs},
+    state::Creator,
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::AccountMeta, native_token::LAMPORTS_PER_SOL};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod settle_primary_sale {
+
+    use super::*;
+
+    async fn create_metadata(context: &mut ProgramTestContext, creator: Pubkey) -> Metadata {
+        let metadata = Metadata::new();
+        metadata
+            .create_v2(
+                context,
+                String::from("Primary"),
+                String::from("PRM"),
+                String::from("https://primary.com"),
+                Some(vec![Creator {
+                    address: creator,
+                    verified: true,
+                    share: 100,
+                }]),
+                500,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        metadata
+    }
+
+    async fn settle(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        authority: &Keypair,
+        buyer: &Keypair,
+        recipient: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let mut settle_ix = SettlePrimarySaleBuilder::new()
+            .authority(authority.pubkey())
+            .payer(buyer.pubkey())
+            .metadata(metadata.pubkey)
+            .mint(metadata.mint.pubkey())
+            .token(metadata.token.pubkey())
+            .build(SettlePrimarySaleArgs::V1 { amount: 10_000 })
+            .unwrap()
+            .instruction();
+        settle_ix.accounts.push(AccountMeta::new(recipient, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[settle_ix],
+            Some(&buyer.pubkey()),
+            &[buyer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn holder_settles_primary_sale() {
+        let mut context = program_test().start_with_context().await;
+
+        let creator = context.payer.pubkey();
+        let metadata = create_metadata(&mut context, creator).await;
+
+        let buyer = Keypair::new();
+        airdrop(&mut context, &buyer.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let creator_balance = get_account(&mut context, &creator).await.lamports;
+        let holder = context.payer.dirty_clone();
+
+        settle(&mut context, &metadata, &holder, &buyer, creator)
+            .await
+            .unwrap();
+
+        let creator_account = get_account(&mut context, &creator).await;
+        assert_eq!(creator_account.lamports, creator_balance + 10_000);
+
+        let metadata_account = metadata.get_data(&mut context).await;
+        assert!(metadata_account.primary_sale_happened);
+
+        // the primary sale can only be settled once
+        context.warp_to_slot(100).unwrap();
+
+        let error = settle(&mut context, &metadata, &holder, &buyer, creator)
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::PrimarySaleAlreadyHappened);
+    }
+
+    #[tokio::test]
+    async fn fail_settle_primary_sale_with_invalid_authority() {
+        let mut context = program_test().start_with_context().await;
+
+        let creator = context.payer.pubkey();
+        let metadata = create_metadata(&mut context, creator).await;
+
+        let buyer = Keypair::new();
+        airdrop(&mut context, &buyer.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let stranger = Keypair::new();
+
+        let error = settle(&mut context, &metadata, &stranger, &buyer, creator)
+            .await
+            .unwrap_err();
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+    }
+}
@@ -23,13 +23,17 @@ mod standard_transfer {
 
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{DelegateArgs, TransferArgs},
+        instruction::{builders::TransferBuilder, DelegateArgs, InstructionBuilder, TransferArgs},
+        pda::find_token_record_account,
         state::TokenStandard,
     };
     use solana_program::{
         native_token::LAMPORTS_PER_SOL, program_option::COption, program_pack::Pack, pubkey::Pubkey,
     };
-    use spl_associated_token_account::get_associated_token_address;
+    use solana_sdk::{compute_budget::ComputeBudgetInstruction, transaction::Transaction};
+    use spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account,
+    };
 
     use super::*;
 
@@ -363,6 +367,285 @@ mod standard_transfer {
         // Owner does not match.
         assert_custom_error_ix!(1, err, MetadataError::InvalidAuthorityType);
     }
+
+    #[tokio::test]
+    async fn transfer_with_baseline_accounts() {
+        // clients unaware of the optional accounts appended to 'Transfer' send only
+        // the original 17 accounts
+        const BASELINE_ACCOUNTS: usize = 17;
+
+        for token_standard in [
+            TokenStandard::NonFungible,
+            TokenStandard::ProgrammableNonFungible,
+        ] {
+            let mut context = program_test().start_with_context().await;
+
+            let mut da = DigitalAsset::new();
+            da.create_and_mint(&mut context, token_standard, None, None, 1)
+                .await
+                .unwrap();
+
+            let authority = context.payer.dirty_clone();
+            let destination_owner = Keypair::new().pubkey();
+            let destination_token =
+                get_associated_token_address(&destination_owner, &da.mint.pubkey());
+
+            let mut builder = TransferBuilder::new();
+            builder
+                .authority(authority.pubkey())
+                .token_owner(authority.pubkey())
+                .token(da.token.unwrap())
+                .destination_owner(destination_owner)
+                .destination(destination_token)
+                .metadata(da.metadata)
+                .payer(authority.pubkey())
+                .mint(da.mint.pubkey())
+                .edition(da.master_edition.unwrap());
+
+            if let Some(token_record) = da.token_record {
+                builder
+                    .owner_token_record(token_record)
+                    .destination_token_record(
+                        find_token_record_account(&da.mint.pubkey(), &destination_token).0,
+                    );
+            }
+
+            let mut transfer_ix = builder
+                .build(TransferArgs::V1 {
+                    authorization_data: None,
+                    amount: 1,
+                })
+                .unwrap()
+                .instruction();
+            transfer_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+            let tx = Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                    create_associated_token_account(
+                        &authority.pubkey(),
+                        &destination_owner,
+                        &da.mint.pubkey(),
+                        &spl_token::id(),
+                    ),
+                    transfer_ix,
+                ],
+                Some(&authority.pubkey()),
+                &[&authority],
+                context.last_blockhash,
+            );
+            context.banks_client.process_transaction(tx).await.unwrap();
+
+            let token_account = spl_token::state::Account::unpack(
+                &context
+                    .banks_client
+                    .get_account(destination_token)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data,
+            )
+            .unwrap();
+
+            assert_eq!(token_account.amount, 1);
+        }
+    }
+}
+
+mod permanent_delegate_transfer {
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{
+            builders::{CreateBuilder, MintBuilder, TransferBuilder},
+            CreateArgs, InstructionBuilder, MintArgs,
+        },
+        pda::{find_master_edition_account, find_metadata_account, find_token_record_account},
+        state::{AssetData, PrintSupply},
+    };
+    use solana_program::program_option::COption;
+    use solana_sdk::{compute_budget::ComputeBudgetInstruction, transaction::Transaction};
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id, instruction::create_associated_token_account,
+    };
+    use spl_token_2022::{extension::StateWithExtensions, state::Account};
+
+    use super::*;
+
+    /// Creates an asset with a permanent delegate and mints it to the payer, returning the
+    /// mint and the token account of the payer.
+    async fn create_and_mint(
+        context: &mut ProgramTestContext,
+        token_standard: TokenStandard,
+        permanent_delegate: Pubkey,
+        spl_token_program: Pubkey,
+    ) -> Result<(Keypair, Pubkey), BanksClientError> {
+        let payer = context.payer.dirty_clone();
+        let mint = Keypair::new();
+        let (metadata, _) = find_metadata_account(&mint.pubkey());
+        let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+        let create_ix = CreateBuilder::new()
+            .metadata(metadata)
+            .master_edition(master_edition)
+            .mint(mint.pubkey())
+            .authority(payer.pubkey())
+            .payer(payer.pubkey())
+            .update_authority(payer.pubkey())
+            .spl_token_program(spl_token_program)
+            .initialize_mint(true)
+            .update_authority_as_signer(true)
+            .build(CreateArgs::V2 {
+                asset_data: AssetData::new(
+                    token_standard,
+                    String::from(DEFAULT_NAME),
+                    String::from(DEFAULT_SYMBOL),
+                    String::from(DEFAULT_URI),
+                ),
+                decimals: Some(0),
+                print_supply: Some(PrintSupply::Zero),
+                permanent_delegate: Some(permanent_delegate),
+            })
+            .unwrap()
+            .instruction();
+
+        let token = get_associated_token_address_with_program_id(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            &spl_token_program,
+        );
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .token_record(find_token_record_account(&mint.pubkey(), &token).0)
+            .token_owner(payer.pubkey())
+            .metadata(metadata)
+            .master_edition(master_edition)
+            .mint(mint.pubkey())
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token_program)
+            .build(MintArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                create_ix,
+                mint_ix,
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok((mint, token))
+    }
+
+    #[tokio::test]
+    async fn permanent_delegate_transfers_asset() {
+        for token_standard in [
+            TokenStandard::NonFungible,
+            TokenStandard::ProgrammableNonFungible,
+        ] {
+            let mut context = program_test().start_with_context().await;
+
+            let payer = context.payer.dirty_clone();
+            let permanent_delegate = Keypair::new();
+            let (mint, token) = create_and_mint(
+                &mut context,
+                token_standard,
+                permanent_delegate.pubkey(),
+                spl_token_2022::ID,
+            )
+            .await
+            .unwrap();
+
+            // the permanent delegate is not the delegate of the token account
+            let account = get_account(&mut context, &token).await;
+            let token_account = StateWithExtensions::<Account>::unpack(&account.data).unwrap();
+            assert_eq!(token_account.base.delegate, COption::None);
+
+            let destination_owner = Keypair::new().pubkey();
+            let destination_token = get_associated_token_address_with_program_id(
+                &destination_owner,
+                &mint.pubkey(),
+                &spl_token_2022::ID,
+            );
+
+            let transfer_ix = TransferBuilder::new()
+                .authority(permanent_delegate.pubkey())
+                .token_owner(payer.pubkey())
+                .token(token)
+                .destination_owner(destination_owner)
+                .destination(destination_token)
+                .metadata(find_metadata_account(&mint.pubkey()).0)
+                .edition(find_master_edition_account(&mint.pubkey()).0)
+                .owner_token_record(find_token_record_account(&mint.pubkey(), &token).0)
+                .destination_token_record(
+                    find_token_record_account(&mint.pubkey(), &destination_token).0,
+                )
+                .payer(payer.pubkey())
+                .mint(mint.pubkey())
+                .spl_token_program(spl_token_2022::ID)
+                .build(TransferArgs::V1 {
+                    authorization_data: None,
+                    amount: 1,
+                })
+                .unwrap()
+                .instruction();
+
+            let tx = Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                    create_associated_token_account(
+                        &payer.pubkey(),
+                        &destination_owner,
+                        &mint.pubkey(),
+                        &spl_token_2022::ID,
+                    ),
+                    transfer_ix,
+                ],
+                Some(&payer.pubkey()),
+                &[&payer, &permanent_delegate],
+                context.last_blockhash,
+            );
+            context.banks_client.process_transaction(tx).await.unwrap();
+
+            let account = get_account(&mut context, &destination_token).await;
+            let token_account = StateWithExtensions::<Account>::unpack(&account.data).unwrap();
+            assert_eq!(token_account.base.amount, 1);
+            // programmable assets remain frozen after the transfer
+            assert_eq!(
+                token_account.base.is_frozen(),
+                matches!(token_standard, TokenStandard::ProgrammableNonFungible)
+            );
+
+            let account = get_account(&mut context, &token).await;
+            let token_account = StateWithExtensions::<Account>::unpack(&account.data).unwrap();
+            assert_eq!(token_account.base.amount, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_permanent_delegate_with_spl_token_mint() {
+        let mut context = program_test().start_with_context().await;
+
+        let error = create_and_mint(
+            &mut context,
+            TokenStandard::NonFungible,
+            Keypair::new().pubkey(),
+            spl_token::ID,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidPermanentDelegateMint);
+    }
 }
 
 mod auth_rules_transfer {
@@ -371,7 +654,7 @@ mod auth_rules_transfer {
         error::MetadataError,
         instruction::DelegateArgs,
         pda::find_token_record_account,
-        state::{ProgrammableConfig, TokenDelegateRole, TokenRecord},
+        state::{ProgrammableConfig, TokenDelegateRole, TokenRecord, TokenState},
     };
     use solana_program::borsh::try_from_slice_unchecked;
     use solana_sdk::transaction::Transaction;
@@ -649,6 +932,7 @@ mod auth_rules_transfer {
         let delegate_args = DelegateArgs::TransferV1 {
             amount: transfer_amount,
             authorization_data: None,
+            expiry: None,
         };
 
         nft.delegate(&mut context, payer, delegate.pubkey(), delegate_args)
@@ -827,6 +1111,7 @@ mod auth_rules_transfer {
         let delegate_args = DelegateArgs::TransferV1 {
             amount: transfer_amount,
             authorization_data: None,
+            expiry: None,
         };
 
         nft.delegate(&mut context, payer, delegate.pubkey(), delegate_args)
@@ -918,6 +1203,7 @@ mod auth_rules_transfer {
         let delegate_args = DelegateArgs::SaleV1 {
             amount: transfer_amount,
             authorization_data: Some(auth_data.clone()),
+            expiry: None,
         };
         nft.delegate(&mut context, payer, delegate.pubkey(), delegate_args)
             .await
@@ -1095,6 +1381,94 @@ mod auth_rules_transfer {
         nft.transfer_from(params).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn sale_delegate_transfer_closes_listing() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut nft = DigitalAsset::new();
+        nft.create_and_mint(
+            &mut context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+        )
+        .await
+        .unwrap();
+
+        // lists the asset with a sale delegate
+
+        let payer = context.payer.dirty_clone();
+        let delegate = Keypair::new();
+        airdrop(&mut context, &delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        nft.delegate(
+            &mut context,
+            payer,
+            delegate.pubkey(),
+            DelegateArgs::SaleV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let pda = get_account(&mut context, &nft.token_record.unwrap()).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(token_record.state, TokenState::Listed);
+
+        // the holder cannot transfer a listed asset
+
+        let authority = context.payer.dirty_clone();
+
+        let args = TransferArgs::V1 {
+            authorization_data: None,
+            amount: 1,
+        };
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &authority,
+            source_owner: &authority.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &authority,
+            args: args.clone(),
+        };
+
+        let err = nft.transfer_from(params).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::OnlySaleDelegateCanTransfer);
+
+        // the sale delegate transfer closes the listing
+
+        let params = TransferFromParams {
+            context: &mut context,
+            authority: &delegate,
+            source_owner: &authority.pubkey(),
+            destination_owner: Keypair::new().pubkey(),
+            destination_token: None,
+            authorization_rules: None,
+            payer: &authority,
+            args,
+        };
+
+        nft.transfer_from(params).await.unwrap();
+
+        let (source_token_record, _) =
+            find_token_record_account(&nft.mint.pubkey(), &nft.token.unwrap());
+        let pda = get_account(&mut context, &source_token_record).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Unlocked);
+        assert_eq!(token_record.delegate, None);
+        assert_eq!(token_record.delegate_role, None);
+    }
+
     #[tokio::test]
     async fn locked_transfer_delegate() {
         // tests a LockedTransfer delegate transferring from a system wallet to an invalid address and
@@ -1139,6 +1513,7 @@ mod auth_rules_transfer {
             amount: transfer_amount,
             locked_address: rooster_manager.pda(),
             authorization_data: None,
+            expiry: None,
         };
 
         nft.delegate(&mut context, payer, delegate.pubkey(), delegate_args)
@@ -1294,6 +1669,7 @@ mod auth_rules_transfer {
         let delegate_args = DelegateArgs::SaleV1 {
             amount: transfer_amount,
             authorization_data: None,
+            expiry: None,
         };
 
         nft.delegate(&mut context, payer, rooster_manager.pda(), delegate_args)
@@ -0,0 +1,368 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_auth_rules::payload::{PayloadType, SeedsVec};
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{BeginTransferSessionBuilder, EndTransferSessionBuilder, TransferBuilder},
+        BeginTransferSessionArgs, EndTransferSessionArgs, InstructionBuilder, TransferArgs,
+    },
+    pda::{find_token_record_account, find_transfer_session_account},
+    processor::AuthorizationData,
+    state::{PayloadKey, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+use utils::*;
+
+mod transfer_session {
+
+    use super::*;
+
+    struct Setup {
+        rule_set: Pubkey,
+        auth_data: AuthorizationData,
+        destination_owner: Pubkey,
+        assets: Vec<DigitalAsset>,
+        outsider: DigitalAsset,
+    }
+
+    /// Creates two programmable assets of a collection and a programmable asset outside of
+    /// it, all sharing a rule set, and a PDA destination on the allow list of the rule set.
+    async fn setup(context: &mut ProgramTestContext) -> Setup {
+        let payer = context.payer.dirty_clone();
+        let (rule_set, mut auth_data) =
+            create_default_metaplex_rule_set(context, payer, false).await;
+
+        let update_authority = context.payer.dirty_clone();
+
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let mut assets = vec![];
+
+        for _ in 0..3 {
+            let mut asset = DigitalAsset::new();
+            asset
+                .create_and_mint(
+                    context,
+                    TokenStandard::ProgrammableNonFungible,
+                    Some(rule_set),
+                    Some(auth_data.clone()),
+                    1,
+                )
+                .await
+                .unwrap();
+            assets.push(asset);
+        }
+
+        let outsider = assets.pop().unwrap();
+
+        for asset in &assets {
+            asset
+                .add_to_collection(context, &collection_parent, &update_authority)
+                .await
+                .unwrap();
+        }
+
+        let authority = context.payer.dirty_clone();
+        let rooster_manager = RoosterManager::init(context, authority).await.unwrap();
+
+        auth_data.payload.insert(
+            PayloadKey::DestinationSeeds.to_string(),
+            PayloadType::Seeds(SeedsVec {
+                seeds: vec![
+                    String::from("rooster").as_bytes().to_vec(),
+                    context.payer.pubkey().as_ref().to_vec(),
+                ],
+            }),
+        );
+
+        Setup {
+            rule_set,
+            auth_data,
+            destination_owner: rooster_manager.pda(),
+            assets,
+            outsider,
+        }
+    }
+
+    async fn begin_session(
+        context: &mut ProgramTestContext,
+        setup: &Setup,
+        asset: &DigitalAsset,
+        duration_slots: u64,
+    ) -> Result<(), BanksClientError> {
+        let authority = context.payer.dirty_clone();
+
+        let begin_ix = BeginTransferSessionBuilder::new()
+            .transfer_session(find_transfer_session_account(&authority.pubkey()).0)
+            .authority(authority.pubkey())
+            .destination_owner(setup.destination_owner)
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .payer(authority.pubkey())
+            .authorization_rules_program(mpl_token_auth_rules::ID)
+            .authorization_rules(setup.rule_set)
+            .build(BeginTransferSessionArgs::V1 {
+                amount: 1,
+                duration_slots,
+                authorization_data: Some(setup.auth_data.clone()),
+            })
+            .unwrap()
+            .instruction();
+
+        let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[compute_ix, begin_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    fn transfer_with_session_tx(
+        context: &ProgramTestContext,
+        setup: &Setup,
+        asset: &DigitalAsset,
+        destination_owner: Pubkey,
+        authorization_data: Option<AuthorizationData>,
+    ) -> Transaction {
+        let authority = context.payer.dirty_clone();
+        let destination = get_associated_token_address(&destination_owner, &asset.mint.pubkey());
+
+        let transfer_ix = TransferBuilder::new()
+            .authority(authority.pubkey())
+            .token_owner(authority.pubkey())
+            .token(asset.token.unwrap())
+            .destination_owner(destination_owner)
+            .destination(destination)
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .owner_token_record(asset.token_record.unwrap())
+            .destination_token_record(
+                find_token_record_account(&asset.mint.pubkey(), &destination).0,
+            )
+            .payer(authority.pubkey())
+            .mint(asset.mint.pubkey())
+            .authorization_rules(setup.rule_set)
+            .authorization_rules_program(mpl_token_auth_rules::ID)
+            .transfer_session(find_transfer_session_account(&authority.pubkey()).0)
+            .build(TransferArgs::V1 {
+                authorization_data,
+                amount: 1,
+            })
+            .unwrap()
+            .instruction();
+
+        Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+                create_associated_token_account(
+                    &authority.pubkey(),
+                    &destination_owner,
+                    &asset.mint.pubkey(),
+                    &spl_token::id(),
+                ),
+                transfer_ix,
+            ],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        )
+    }
+
+    async fn transfer_with_session(
+        context: &mut ProgramTestContext,
+        setup: &Setup,
+        asset: &DigitalAsset,
+        destination_owner: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let tx = transfer_with_session_tx(
+            context,
+            setup,
+            asset,
+            destination_owner,
+            Some(setup.auth_data.clone()),
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Simulates the transaction, returning whether it validated a rule set.
+    async fn validates_rule_set(context: &mut ProgramTestContext, tx: Transaction) -> bool {
+        let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+        assert!(simulation.result.unwrap().is_ok());
+
+        let invoke = format!("Program {} invoke", mpl_token_auth_rules::ID);
+        simulation
+            .simulation_details
+            .unwrap()
+            .logs
+            .iter()
+            .any(|log| log.starts_with(&invoke))
+    }
+
+    #[tokio::test]
+    async fn transfer_session_skips_rule_set_validation() {
+        let mut program_test = program_test();
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        program_test.add_program("rooster", rooster::ID, None);
+        let context = &mut program_test.start_with_context().await;
+
+        let setup = setup(context).await;
+        begin_session(context, &setup, &setup.assets[0], 100)
+            .await
+            .unwrap();
+
+        let asset = &setup.assets[0];
+
+        let tx = transfer_with_session_tx(
+            context,
+            &setup,
+            asset,
+            setup.destination_owner,
+            Some(setup.auth_data.clone()),
+        );
+        assert!(!validates_rule_set(context, tx).await);
+
+        transfer_with_session(context, &setup, asset, setup.destination_owner)
+            .await
+            .unwrap();
+
+        let destination =
+            get_associated_token_address(&setup.destination_owner, &asset.mint.pubkey());
+        let token = spl_token::state::Account::unpack(
+            get_account(context, &destination).await.data.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(token.amount, 1);
+
+        // the session covers the other assets of the collection
+
+        let other = &setup.assets[1];
+
+        let tx = transfer_with_session_tx(
+            context,
+            &setup,
+            other,
+            setup.destination_owner,
+            Some(setup.auth_data.clone()),
+        );
+        assert!(!validates_rule_set(context, tx).await);
+
+        transfer_with_session(context, &setup, other, setup.destination_owner)
+            .await
+            .unwrap();
+
+        // ends the session
+
+        let authority = context.payer.dirty_clone();
+        let (transfer_session, _) = find_transfer_session_account(&authority.pubkey());
+
+        let end_ix = EndTransferSessionBuilder::new()
+            .transfer_session(transfer_session)
+            .authority(authority.pubkey())
+            .build(EndTransferSessionArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[end_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let session_account = context
+            .banks_client
+            .get_account(transfer_session)
+            .await
+            .unwrap();
+        assert!(session_account.is_none());
+    }
+
+    #[tokio::test]
+    async fn transfer_session_does_not_cover_other_collection() {
+        let mut program_test = program_test();
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        program_test.add_program("rooster", rooster::ID, None);
+        let context = &mut program_test.start_with_context().await;
+
+        let setup = setup(context).await;
+        begin_session(context, &setup, &setup.assets[0], 100)
+            .await
+            .unwrap();
+
+        // the asset shares the rule set, but it is not an item of the collection of the
+        // session, so its transfer validates the rule set
+
+        let tx = transfer_with_session_tx(
+            context,
+            &setup,
+            &setup.outsider,
+            setup.destination_owner,
+            Some(setup.auth_data.clone()),
+        );
+        assert!(validates_rule_set(context, tx).await);
+
+        transfer_with_session(context, &setup, &setup.outsider, setup.destination_owner)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_begin_transfer_session_without_collection() {
+        let mut program_test = program_test();
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        program_test.add_program("rooster", rooster::ID, None);
+        let context = &mut program_test.start_with_context().await;
+
+        let setup = setup(context).await;
+
+        let err = begin_session(context, &setup, &setup.outsider, 100)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::CollectionNotFound);
+    }
+
+    #[tokio::test]
+    async fn fail_transfer_session_expiry() {
+        let mut program_test = program_test();
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        program_test.add_program("rooster", rooster::ID, None);
+        let context = &mut program_test.start_with_context().await;
+
+        let setup = setup(context).await;
+        begin_session(context, &setup, &setup.assets[0], 10)
+            .await
+            .unwrap();
+
+        // the session expired
+
+        let slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(slot + 20).unwrap();
+
+        let err = transfer_with_session(context, &setup, &setup.assets[0], setup.destination_owner)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::TransferSessionExpired);
+    }
+}
@@ -7,12 +7,18 @@ use utils::*;
 mod utility {
 
     use mpl_token_metadata::{
-        instruction::DelegateArgs,
+        error::MetadataError,
+        instruction::{DelegateArgs, RevokeArgs},
         pda::find_token_record_account,
         state::{TokenDelegateRole, TokenRecord, TokenStandard, TokenState},
     };
+    use num_traits::FromPrimitive;
     use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack, pubkey::Pubkey};
-    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
     use spl_token::state::Account;
 
     use super::*;
@@ -58,6 +64,7 @@ mod utility {
                 DelegateArgs::UtilityV1 {
                     amount: 1,
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -206,6 +213,7 @@ mod utility {
                     amount: 1,
                     locked_address: Pubkey::default(),
                     authorization_data: None,
+                    expiry: None,
                 },
             )
             .await
@@ -251,4 +259,259 @@ mod utility {
         );
         assert_eq!(token_record.locked_transfer, Some(Pubkey::default()));
     }
+
+    #[tokio::test]
+    async fn delegate_unlock_and_revoke_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a utility delegate
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // lock
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let token_delegate = Keypair::from_bytes(&delegate.to_bytes()).unwrap();
+
+        asset
+            .lock(&mut context, token_delegate, Some(pda_key), payer)
+            .await
+            .unwrap();
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(token_record.state, TokenState::Locked);
+
+        // unlock and revoke
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .unlock_and_revoke(&mut context, delegate, payer, RevokeArgs::UtilityV1)
+            .await
+            .unwrap();
+
+        // asserts
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Unlocked);
+        assert_eq!(token_record.delegate, None);
+        assert_eq!(token_record.delegate_role, None);
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        // programmable assets remain frozen
+        assert!(token.is_frozen());
+        assert!(token.delegate.is_none());
+    }
+
+    #[tokio::test]
+    async fn delegate_unlock_and_revoke_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // set a standard delegate
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::StandardV1 { amount: 1 },
+            )
+            .await
+            .unwrap();
+
+        // lock
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let token_delegate = Keypair::from_bytes(&delegate.to_bytes()).unwrap();
+
+        asset
+            .lock(&mut context, token_delegate, None, payer)
+            .await
+            .unwrap();
+
+        // unlock and revoke
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .unlock_and_revoke(&mut context, delegate, payer, RevokeArgs::StandardV1)
+            .await
+            .unwrap();
+
+        // asserts
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        // should NOT be frozen
+        assert!(!token.is_frozen());
+        assert!(token.delegate.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_unlock_and_revoke_unlocked_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a utility delegate
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // unlock and revoke without locking first
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .unlock_and_revoke(&mut context, delegate, payer, RevokeArgs::UtilityV1)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UnlockedToken);
+    }
+
+    #[tokio::test]
+    async fn fail_staking_delegate_unlock_outside_staking_program() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // set a staking delegate and lock the asset
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let staking_program = Pubkey::new_unique();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::StakingV1 {
+                    amount: 1,
+                    staking_program,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let token_delegate = Keypair::from_bytes(&delegate.to_bytes()).unwrap();
+
+        asset
+            .lock(&mut context, token_delegate, Some(pda_key), payer)
+            .await
+            .unwrap();
+
+        // asserts
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Locked);
+        assert_eq!(token_record.delegate_role, Some(TokenDelegateRole::Staking));
+        assert_eq!(token_record.staking_program, Some(staking_program));
+
+        // the delegate cannot unlock the asset outside of the staking program
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .unlock(&mut context, delegate, Some(pda_key), payer)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidStakingProgram);
+    }
 }
@@ -0,0 +1,205 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::instruction::{
+    builders::{UnverifyBuilder, VerifyV2Builder},
+    InstructionBuilder,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::{DigitalAsset, *};
+
+mod unverify {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{
+            CollectionDetailsToggle, CollectionToggle, DelegateArgs, MetadataDelegateRole,
+            UpdateArgs, VerificationArgs,
+        },
+        pda::find_metadata_delegate_record_account,
+        state::{Collection, CollectionDetails, TokenStandard},
+    };
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+
+    async fn unverify_collection(
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        delegate_record: Option<Pubkey>,
+        asset: &DigitalAsset,
+        collection_parent: &DigitalAsset,
+    ) -> Result<(), BanksClientError> {
+        let mut builder = UnverifyBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .metadata(asset.metadata)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata);
+
+        if let Some(delegate_record) = delegate_record {
+            builder.delegate_record(delegate_record);
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[builder
+                .build(VerificationArgs::CollectionV1)
+                .unwrap()
+                .instruction()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Creates a sized collection and an asset verified as a member of it.
+    async fn create_verified_collection_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V1 { size: 0 });
+
+        collection_parent
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: false,
+        });
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let verify_ix = VerifyV2Builder::new()
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .collection_mint(collection_parent.mint.pubkey())
+            .collection_metadata(collection_parent.metadata)
+            .collection_master_edition(collection_parent.master_edition.unwrap())
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&update_authority.pubkey()),
+            &[update_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        (collection_parent, da)
+    }
+
+    #[tokio::test]
+    async fn collection_delegate_unverifies_sized_collection_item() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (mut collection_parent, da) =
+            create_verified_collection_item(context, &update_authority).await;
+
+        let parent = collection_parent.get_metadata(context).await;
+        assert_eq!(
+            parent.collection_details,
+            Some(CollectionDetails::V1 { size: 1 })
+        );
+
+        // the collection update authority approves a collection delegate
+
+        let delegate = Keypair::new();
+
+        collection_parent
+            .delegate(
+                context,
+                update_authority.dirty_clone(),
+                delegate.pubkey(),
+                DelegateArgs::CollectionV1 {
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &collection_parent.mint.pubkey(),
+            MetadataDelegateRole::Collection,
+            &update_authority.pubkey(),
+            &delegate.pubkey(),
+        );
+
+        unverify_collection(
+            context,
+            &delegate,
+            Some(delegate_record),
+            &da,
+            &collection_parent,
+        )
+        .await
+        .unwrap();
+
+        // the item is unverified and the collection size is decremented
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.collection,
+            Some(Collection {
+                key: collection_parent.mint.pubkey(),
+                verified: false,
+            })
+        );
+
+        let parent = collection_parent.get_metadata(context).await;
+        assert_eq!(
+            parent.collection_details,
+            Some(CollectionDetails::V1 { size: 0 })
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_unverify_with_invalid_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) =
+            create_verified_collection_item(context, &update_authority).await;
+
+        let err = unverify_collection(context, &Keypair::new(), None, &da, &collection_parent)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidAuthorityType);
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.collection.unwrap().verified);
+    }
+}
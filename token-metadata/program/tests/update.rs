@@ -19,11 +19,23 @@ mod update {
 
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{DelegateArgs, RuleSetToggle, UpdateArgs},
-        state::{Creator, Data, ProgrammableConfig, TokenStandard},
+        instruction::{
+            CollectionDetailsToggle, CollectionToggle, DelegateArgs, MetadataDelegateRole,
+            RuleSetToggle, UpdateArgs, UsesToggle,
+        },
+        pda::{find_content_rating_account, find_metadata_delegate_record_account},
+        state::{
+            BasisPoints, Collection, CollectionDetails, ContentRating, ContentRatingRecord,
+            Creator, Data, ProgrammableConfig, TokenStandard, UseMethod, Uses, MIN_RULE_SET_DELAY,
+        },
+        utils::GOVERNANCE_PROGRAM_ADDRESSES,
     };
     use solana_program::pubkey::Pubkey;
-    use solana_sdk::signature::Keypair;
+    use solana_sdk::{
+        account::{AccountSharedData, WritableAccount},
+        instruction::AccountMeta,
+        signature::Keypair,
+    };
 
     use super::*;
 
@@ -63,7 +75,7 @@ mod update {
             symbol: new_symbol.clone(),
             uri: new_uri.clone(),
             creators: metadata.data.creators, // keep the same creators
-            seller_fee_basis_points: 0,
+            seller_fee_basis_points: BasisPoints::new(0).unwrap(),
         };
 
         let mut update_args = UpdateArgs::default();
@@ -178,7 +190,8 @@ mod update {
         // Currently users can add an invalid rule set to their pNFT which will effectively
         // prevent it from being updated again because it either won't be owned by the mpl-token-auth rules
         // program or it won't be a valid rule set to call validate on.
-        // We relax the check a little to let users fix invalid rule sets.
+        // Committing a proposed rule set does not validate the current one, which lets users fix
+        // invalid rule sets.
         let mut program_test = ProgramTest::new("mpl_token_metadata", mpl_token_metadata::ID, None);
         program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
         let context = &mut program_test.start_with_context().await;
@@ -212,6 +225,7 @@ mod update {
             panic!("Missing rule set programmable config");
         }
 
+        // The rule set cannot be set directly.
         let mut update_args = UpdateArgs::default();
         let UpdateArgs::V1 { rule_set, .. } = &mut update_args;
         *rule_set = RuleSetToggle::Set(invalid_rule_set);
@@ -237,7 +251,21 @@ mod update {
             context.last_blockhash,
         );
 
-        context.banks_client.process_transaction(tx).await.unwrap();
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RuleSetChangeRequiresProposal);
+
+        da.propose_rule_set(context, &update_authority, invalid_rule_set, None)
+            .await
+            .unwrap();
+        warp_clock(context, MIN_RULE_SET_DELAY).await;
+        da.commit_rule_set(context, &update_authority)
+            .await
+            .unwrap();
 
         let metadata = da.get_metadata(context).await;
 
@@ -283,34 +311,14 @@ mod update {
 
         assert_custom_error!(err, MetadataError::InvalidAuthorizationRules);
 
-        // Finally, try to update with the valid rule set, and it should succeed.
-        let mut update_args = UpdateArgs::default();
-        let UpdateArgs::V1 { rule_set, .. } = &mut update_args;
-        *rule_set = RuleSetToggle::Set(authorization_rules);
-
-        let mut builder = UpdateBuilder::new();
-        builder
-            .authority(update_authority.pubkey())
-            .metadata(da.metadata)
-            .mint(da.mint.pubkey())
-            .token(da.token.unwrap())
-            .authorization_rules(invalid_rule_set)
-            .payer(update_authority.pubkey());
-
-        if let Some(edition) = da.master_edition {
-            builder.edition(edition);
-        }
-
-        let update_ix = builder.build(update_args).unwrap().instruction();
-
-        let tx = Transaction::new_signed_with_payer(
-            &[update_ix],
-            Some(&update_authority.pubkey()),
-            &[&update_authority],
-            context.last_blockhash,
-        );
-
-        context.banks_client.process_transaction(tx).await.unwrap();
+        // Finally, propose the valid rule set, and it should be committed.
+        da.propose_rule_set(context, &update_authority, authorization_rules, None)
+            .await
+            .unwrap();
+        warp_clock(context, MIN_RULE_SET_DELAY).await;
+        da.commit_rule_set(context, &update_authority)
+            .await
+            .unwrap();
 
         let metadata = da.get_metadata(context).await;
 
@@ -375,6 +383,7 @@ mod update {
             DelegateArgs::TransferV1 {
                 amount: 1,
                 authorization_data: None,
+                expiry: None,
             },
         )
         .await
@@ -496,7 +505,7 @@ mod update {
             symbol: new_symbol.clone(),
             uri: new_uri.clone(),
             creators: None, // This should not erase the verified creator.
-            seller_fee_basis_points: 0,
+            seller_fee_basis_points: BasisPoints::new(0).unwrap(),
         };
 
         let mut update_args = UpdateArgs::default();
@@ -595,4 +604,592 @@ mod update {
 
         assert_eq!(metadata.data.creators, None);
     }
+
+    #[tokio::test]
+    async fn fail_governance_authority_outside_proposal_execution() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // Transfers the update authority to an account owned by the governance program.
+        let governance = Keypair::new();
+        context.set_account(
+            &governance.pubkey(),
+            &AccountSharedData::new(1_000_000, 0, &GOVERNANCE_PROGRAM_ADDRESSES[0]),
+        );
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            new_update_authority,
+            ..
+        } = &mut update_args;
+        *new_update_authority = Some(governance.pubkey());
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, governance.pubkey());
+
+        // A proposal transaction account of the governance program, which is not being
+        // executed.
+        let proposal_transaction = Pubkey::new_unique();
+        let mut account = AccountSharedData::new(1_000_000, 33, &GOVERNANCE_PROGRAM_ADDRESSES[0]);
+        account.data_as_mut_slice()[0] = 13;
+        context.set_account(&proposal_transaction, &account);
+
+        // The governance account signs the update directly, without a proposal execution.
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { is_mutable, .. } = &mut update_args;
+        *is_mutable = Some(false);
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(governance.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(context.payer.pubkey());
+
+        if let Some(edition) = da.master_edition {
+            builder.edition(edition);
+        }
+
+        let mut update_ix = builder.build(update_args).unwrap().instruction();
+        update_ix
+            .accounts
+            .push(AccountMeta::new_readonly(proposal_transaction, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &governance],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidGovernanceExecution);
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.is_mutable);
+    }
+
+    #[tokio::test]
+    async fn fail_inherit_royalties_without_collection() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.collection.is_none());
+        assert!(!metadata.inherit_royalties);
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            inherit_royalties, ..
+        } = &mut update_args;
+        *inherit_royalties = Some(true);
+
+        let err = da
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::CollectionNotFound);
+    }
+
+    #[tokio::test]
+    async fn fail_use_default_rule_set_nonfungible() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(!metadata.use_default_rule_set);
+
+        // default rule sets only apply to programmable assets
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            use_default_rule_set,
+            ..
+        } = &mut update_args;
+        *use_default_rule_set = Some(true);
+
+        let err = da
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidTokenStandard);
+    }
+
+    #[tokio::test]
+    async fn data_delegate_update() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // creates a data delegate
+
+        let delegate = Keypair::new();
+        da.delegate(
+            context,
+            update_authority.dirty_clone(),
+            delegate.pubkey(),
+            DelegateArgs::DataV1 {
+                authorization_data: None,
+                expiry: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &da.mint.pubkey(),
+            MetadataDelegateRole::Data,
+            &update_authority.pubkey(),
+            &delegate.pubkey(),
+        );
+
+        let metadata = da.get_metadata(context).await;
+        let new_uri = puffed_out_string("https://revealed.digital.asset.org", MAX_URI_LENGTH);
+
+        // the delegate can update the data of the asset
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { data, .. } = &mut update_args;
+        *data = Some(Data {
+            name: metadata.data.name.clone(),
+            symbol: metadata.data.symbol.clone(),
+            uri: new_uri.clone(),
+            creators: metadata.data.creators.clone(),
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        });
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(context.payer.pubkey());
+
+        if let Some(edition) = da.master_edition {
+            builder.edition(edition);
+        }
+
+        let update_ix = builder.build(update_args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.data.uri, new_uri);
+
+        // but it cannot update the update authority
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            new_update_authority,
+            ..
+        } = &mut update_args;
+        *new_update_authority = Some(delegate.pubkey());
+
+        let update_ix = builder.build(update_args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidUpdateArgs);
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, update_authority.pubkey());
+    }
+
+    #[tokio::test]
+    async fn uses_delegate_update() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // creates a uses delegate
+
+        let delegate = Keypair::new();
+        da.delegate(
+            context,
+            update_authority.dirty_clone(),
+            delegate.pubkey(),
+            DelegateArgs::UsesV1 {
+                authorization_data: None,
+                expiry: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &da.mint.pubkey(),
+            MetadataDelegateRole::Uses,
+            &update_authority.pubkey(),
+            &delegate.pubkey(),
+        );
+
+        // the delegate can update the uses of the asset
+
+        let new_uses = Uses {
+            use_method: UseMethod::Multiple,
+            remaining: 10,
+            total: 10,
+        };
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { uses, .. } = &mut update_args;
+        *uses = UsesToggle::Set(new_uses.clone());
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(context.payer.pubkey());
+
+        if let Some(edition) = da.master_edition {
+            builder.edition(edition);
+        }
+
+        let update_ix = builder.build(update_args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.uses, Some(new_uses));
+
+        // but it cannot update the data
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { data, .. } = &mut update_args;
+        *data = Some(Data {
+            uri: puffed_out_string("https://other.digital.asset.org", MAX_URI_LENGTH),
+            ..metadata.data.clone()
+        });
+
+        let update_ix = builder.build(update_args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidUpdateArgs);
+
+        let new_metadata = da.get_metadata(context).await;
+        assert_eq!(new_metadata.data, metadata.data);
+    }
+
+    #[tokio::test]
+    async fn unverify_creators_on_authority_change() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.data.creators.unwrap()[0].verified);
+
+        // enables the policy and transfers the update authority in the same update
+        let new_update_authority = Keypair::new();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            new_update_authority: new_authority,
+            unverify_creators_on_authority_change,
+            ..
+        } = &mut update_args;
+        *new_authority = Some(new_update_authority.pubkey());
+        *unverify_creators_on_authority_change = Some(true);
+
+        da.update(context, update_authority, update_args)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+        assert!(metadata.unverify_creators_on_authority_change);
+        assert!(!metadata.data.creators.unwrap()[0].verified);
+    }
+
+    #[tokio::test]
+    async fn set_and_verify_sized_collection() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        // the collection and the item share the same update authority
+
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V1 { size: 0 });
+
+        collection_parent
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: true,
+        });
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(update_authority.pubkey());
+
+        if let Some(edition) = da.master_edition {
+            builder.edition(edition);
+        }
+
+        let mut update_ix = builder.build(update_args).unwrap().instruction();
+        // collection accounts
+        update_ix.accounts.extend([
+            AccountMeta::new_readonly(collection_parent.mint.pubkey(), false),
+            AccountMeta::new(collection_parent.metadata, false),
+            AccountMeta::new_readonly(collection_parent.master_edition.unwrap(), false),
+        ]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the item is verified and the collection size is incremented
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.collection,
+            Some(Collection {
+                key: collection_parent.mint.pubkey(),
+                verified: true,
+            })
+        );
+
+        let parent = collection_parent.get_metadata(context).await;
+        assert_eq!(
+            parent.collection_details,
+            Some(CollectionDetails::V1 { size: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn update_authority_sets_content_rating() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let (content_rating_record, _) = find_content_rating_account(&da.mint.pubkey());
+
+        // sets the content rating twice: the first update creates the record
+
+        for rating in [ContentRating::Mature, ContentRating::General] {
+            let mut update_args = UpdateArgs::default();
+            let UpdateArgs::V1 { content_rating, .. } = &mut update_args;
+            *content_rating = Some(rating);
+
+            let mut builder = UpdateBuilder::new();
+            builder
+                .authority(update_authority.pubkey())
+                .metadata(da.metadata)
+                .mint(da.mint.pubkey())
+                .payer(update_authority.pubkey());
+
+            if let Some(edition) = da.master_edition {
+                builder.edition(edition);
+            }
+
+            let mut update_ix = builder.build(update_args).unwrap().instruction();
+            update_ix
+                .accounts
+                .push(AccountMeta::new(content_rating_record, false));
+
+            let tx = Transaction::new_signed_with_payer(
+                &[update_ix],
+                Some(&update_authority.pubkey()),
+                &[&update_authority],
+                context.last_blockhash,
+            );
+
+            context.banks_client.process_transaction(tx).await.unwrap();
+
+            let account = get_account(context, &content_rating_record).await;
+            let record = ContentRatingRecord::from_bytes(&account.data).unwrap();
+
+            assert_eq!(record.mint, da.mint.pubkey());
+            assert_eq!(record.rating, rating);
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_set_content_rating_without_record() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { content_rating, .. } = &mut update_args;
+        *content_rating = Some(ContentRating::Explicit);
+
+        let err = da
+            .update(context, update_authority, update_args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MissingContentRatingAccount);
+    }
+
+    #[tokio::test]
+    async fn update_with_baseline_accounts() {
+        let context = &mut program_test().start_with_context().await;
+
+        // clients unaware of the optional account appended to 'Update' send only the
+        // original 11 accounts
+        const BASELINE_ACCOUNTS: usize = 11;
+
+        let update_authority = context.payer.dirty_clone();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let new_name = puffed_out_string("New Name", MAX_NAME_LENGTH);
+        let metadata = da.get_metadata(context).await;
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            data: current_data, ..
+        } = &mut update_args;
+        *current_data = Some(Data {
+            name: new_name.clone(),
+            ..metadata.data
+        });
+
+        let mut update_ix = UpdateBuilder::new()
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .edition(da.master_edition.unwrap())
+            .payer(update_authority.pubkey())
+            .build(update_args)
+            .unwrap()
+            .instruction();
+        update_ix.accounts.truncate(BASELINE_ACCOUNTS);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(metadata.data.name, new_name);
+    }
 }
@@ -5,8 +5,8 @@ use mpl_token_metadata::{
     error::MetadataError,
     id, instruction,
     state::{
-        Collection, Creator, DataV2, Key, UseMethod, Uses, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
-        MAX_URI_LENGTH,
+        BasisPoints, Collection, Creator, DataV2, Key, UseMethod, Uses, MAX_NAME_LENGTH,
+        MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
     },
     utils::puffed_out_string,
 };
@@ -79,7 +79,7 @@ mod update_metadata_account_v2 {
         assert_eq!(metadata.data.name, puffed_updated_name,);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
 
         assert!(!metadata.primary_sale_happened);
@@ -148,7 +148,7 @@ mod update_metadata_account_v2 {
         assert_eq!(metadata.data.name, puffed_updated_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
 
         assert!(!metadata.primary_sale_happened);
@@ -247,7 +247,7 @@ mod update_metadata_account_v2 {
                     symbol: symbol.clone(),
                     uri: uri.clone(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: Some(Collection {
                         key: incoming_collection.pubkey(),
                         verified: true,
@@ -486,7 +486,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: Some(Collection {
                         key: test_collection.pubkey,
                         verified: true,
@@ -592,7 +592,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: Some(Collection {
                         key: fake_collection_pubkey,
                         verified: true,
@@ -676,7 +676,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: None,
                     uses: None,
                 }),
@@ -704,7 +704,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: None,
                     uses: None,
                 }),
@@ -842,7 +842,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: None,
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: None,
                     uses: Some(Uses {
                         use_method: UseMethod::Multiple,
@@ -936,7 +936,7 @@ mod update_metadata_account_v2 {
                     symbol: "TST".to_string(),
                     uri: "uri".to_string(),
                     creators: Some(new_creators),
-                    seller_fee_basis_points: 10,
+                    seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                     collection: None,
                     uses: None,
                 }),
@@ -1020,7 +1020,7 @@ async fn fail_cannot_unverify_another_creator_by_removing_from_array() {
                 symbol: "TST".to_string(),
                 uri: "uri".to_string(),
                 creators: Some(new_creators),
-                seller_fee_basis_points: 10,
+                seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                 collection: None,
                 uses: None,
             }),
@@ -1097,7 +1097,7 @@ async fn fail_cannot_unverify_creators_by_setting_to_none() {
                 symbol: "TST".to_string(),
                 uri: "uri".to_string(),
                 creators: None,
-                seller_fee_basis_points: 10,
+                seller_fee_basis_points: BasisPoints::new(10).unwrap(),
                 collection: None,
                 uses: None,
             }),
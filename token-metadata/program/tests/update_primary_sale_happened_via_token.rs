@@ -46,7 +46,7 @@ mod update_primary_sale_happened_via_token {
         assert_eq!(metadata.data.name, puffed_name,);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
 
         assert!(metadata.primary_sale_happened);
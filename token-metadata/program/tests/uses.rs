@@ -14,9 +14,18 @@ use utils::*;
 mod uses {
     use mpl_token_metadata::{
         error::MetadataError,
-        pda::{find_program_as_burner_account, find_use_authority_account},
-        state::{Key, UseAuthorityRecord},
+        instruction::{
+            builders::{DelegateBuilder, SetUsesV2Builder, UseV2Builder},
+            DelegateArgs, InstructionBuilder, MetadataDelegateRole, SetUsesV2Args, UpdateArgs,
+            UseArgs, UsesToggle,
+        },
+        pda::{
+            find_metadata_delegate_record_account, find_program_as_burner_account,
+            find_use_authority_account, find_uses_v2_account,
+        },
+        state::{Key, TokenStandard, UseAuthorityRecord, UsesV2},
     };
+    use solana_program::instruction::Instruction;
     use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack};
     use solana_sdk::signature::Keypair;
     use spl_token::state::Account;
@@ -559,4 +568,543 @@ mod uses {
             Account::unpack_from_slice(token_account_after_burn.data.as_slice()).unwrap();
         assert_eq!(token_account_after_burn_data.amount, 0);
     }
+
+    #[tokio::test]
+    async fn use_and_burn_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let test_meta = Metadata::new();
+        test_meta
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Burn,
+                    total: 2,
+                    remaining: 2,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+
+        let use_ix = UseV2Builder::new()
+            .authority(payer)
+            .token(test_meta.token.pubkey())
+            .mint(test_meta.mint.pubkey())
+            .metadata(test_meta.pubkey)
+            .payer(payer)
+            .spl_token_program(spl_token::ID)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        // first use only decrements the remaining uses
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix.clone()],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 1);
+
+        // last use burns the token
+
+        context.warp_to_slot(10).unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+        let token_account = get_account(&mut context, &test_meta.token.pubkey()).await;
+        let token: Account = Account::unpack_from_slice(token_account.data.as_slice()).unwrap();
+        assert_eq!(token.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn utility_delegate_use_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { uses, .. } = &mut update_args;
+        *uses = UsesToggle::Set(Uses {
+            use_method: UseMethod::Burn,
+            total: 1,
+            remaining: 1,
+        });
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .update(&mut context, payer, update_args)
+            .await
+            .unwrap();
+
+        // set a utility delegate
+
+        let delegate = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // the delegate uses the asset, which burns it
+
+        let payer = context.payer.pubkey();
+
+        let use_ix = UseV2Builder::new()
+            .authority(delegate.pubkey())
+            .token_record(asset.token_record.unwrap())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .payer(payer)
+            .spl_token_program(spl_token::ID)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix],
+            Some(&payer),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // the token account is empty and frozen again
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token: Account = Account::unpack_from_slice(token_account.data.as_slice()).unwrap();
+        assert_eq!(token.amount, 0);
+        assert!(token.is_frozen());
+
+        let metadata = asset.get_metadata(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn use_delegate_limited_uses() {
+        let mut context = program_test().start_with_context().await;
+
+        let test_meta = Metadata::new();
+        test_meta
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    total: 5,
+                    remaining: 5,
+                }),
+            )
+            .await
+            .unwrap();
+
+        // the update authority approves a use delegate limited to 2 uses
+
+        let delegate = Keypair::new();
+        let payer = context.payer.pubkey();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &test_meta.mint.pubkey(),
+            MetadataDelegateRole::Use,
+            &payer,
+            &delegate.pubkey(),
+        );
+
+        let delegate_ix = DelegateBuilder::new()
+            .delegate_record(delegate_record)
+            .delegate(delegate.pubkey())
+            .metadata(test_meta.pubkey)
+            .mint(test_meta.mint.pubkey())
+            .authority(payer)
+            .payer(payer)
+            .build(DelegateArgs::UseV1 {
+                allowed_uses: 2,
+                authorization_data: None,
+                expiry: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[delegate_ix],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let use_ix = UseV2Builder::new()
+            .authority(delegate.pubkey())
+            .token(test_meta.token.pubkey())
+            .mint(test_meta.mint.pubkey())
+            .metadata(test_meta.pubkey)
+            .payer(payer)
+            .delegate_record(delegate_record)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        for slot in 1..=2 {
+            context.warp_to_slot(slot * 10).unwrap();
+
+            let tx = Transaction::new_signed_with_payer(
+                &[use_ix.clone()],
+                Some(&payer),
+                &[&context.payer, &delegate],
+                context.last_blockhash,
+            );
+            context.banks_client.process_transaction(tx).await.unwrap();
+        }
+
+        // the delegate allowance is exhausted, while the asset still has uses left
+
+        context.warp_to_slot(30).unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix],
+            Some(&payer),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::NotEnoughDelegateUses);
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 3);
+    }
+
+    #[tokio::test]
+    async fn use_delegate_programmable_nonfungible() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { uses, .. } = &mut update_args;
+        *uses = UsesToggle::Set(Uses {
+            use_method: UseMethod::Multiple,
+            total: 3,
+            remaining: 3,
+        });
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .update(&mut context, payer, update_args)
+            .await
+            .unwrap();
+
+        // an allowance above the remaining uses is rejected
+
+        let delegate = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let err = asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::UseV1 {
+                    allowed_uses: 4,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::NotEnoughUses);
+
+        // the update authority approves a use delegate for a single use
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::UseV1 {
+                    allowed_uses: 1,
+                    authorization_data: None,
+                    expiry: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &asset.mint.pubkey(),
+            MetadataDelegateRole::Use,
+            &payer,
+            &delegate.pubkey(),
+        );
+
+        let use_ix = UseV2Builder::new()
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .token_record(asset.token_record.unwrap())
+            .token(asset.token.unwrap())
+            .mint(asset.mint.pubkey())
+            .metadata(asset.metadata)
+            .edition(asset.master_edition.unwrap())
+            .payer(payer)
+            .spl_token_program(spl_token::ID)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix],
+            Some(&payer),
+            &[&context.payer, &delegate],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = asset.get_metadata(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 2);
+    }
+
+    /// Sends the instruction with the blockhash of the current slot, so the same
+    /// instruction can be sent again after warping.
+    async fn process_at_current_slot(
+        context: &mut ProgramTestContext,
+        ix: &Instruction,
+    ) -> Result<(), BanksClientError> {
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix.clone()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn create_rate_limited(context: &mut ProgramTestContext) -> Metadata {
+        let test_meta = Metadata::new();
+        test_meta
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    total: 2,
+                    remaining: 2,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.pubkey();
+        let set_uses_v2_ix = SetUsesV2Builder::new()
+            .uses_v2(find_uses_v2_account(&test_meta.mint.pubkey()).0)
+            .mint(test_meta.mint.pubkey())
+            .metadata(test_meta.pubkey)
+            .authority(payer)
+            .payer(payer)
+            .build(SetUsesV2Args::V1 {
+                cooldown_slots: 20,
+                refill_period_slots: 50,
+                refill_amount: 1,
+            })
+            .unwrap()
+            .instruction();
+
+        process_at_current_slot(context, &set_uses_v2_ix)
+            .await
+            .unwrap();
+
+        test_meta
+    }
+
+    #[tokio::test]
+    async fn rate_limited_uses_cooldown_and_refill() {
+        let mut context = program_test().start_with_context().await;
+
+        // the refill period starts when the rate limit is set
+        context.warp_to_slot(10).unwrap();
+        let test_meta = create_rate_limited(&mut context).await;
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert!(metadata.rate_limited_uses);
+
+        let payer = context.payer.pubkey();
+        let (uses_v2, _) = find_uses_v2_account(&test_meta.mint.pubkey());
+
+        let use_ix = UseV2Builder::new()
+            .authority(payer)
+            .token(test_meta.token.pubkey())
+            .mint(test_meta.mint.pubkey())
+            .metadata(test_meta.pubkey)
+            .payer(payer)
+            .uses_v2(uses_v2)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        context.warp_to_slot(20).unwrap();
+        process_at_current_slot(&mut context, &use_ix)
+            .await
+            .unwrap();
+
+        // the cooldown has not elapsed
+
+        context.warp_to_slot(30).unwrap();
+        let err = process_at_current_slot(&mut context, &use_ix)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::UsesCooldown);
+
+        context.warp_to_slot(40).unwrap();
+        process_at_current_slot(&mut context, &use_ix)
+            .await
+            .unwrap();
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+        // a refill period elapsed, so one use is added back
+
+        context.warp_to_slot(70).unwrap();
+        process_at_current_slot(&mut context, &use_ix)
+            .await
+            .unwrap();
+
+        let metadata = test_meta.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+        let uses_v2_account = get_account(&mut context, &uses_v2).await;
+        let uses_v2 = UsesV2::from_bytes(&uses_v2_account.data).unwrap();
+        assert_eq!(uses_v2.last_use_slot, Some(70));
+        assert_eq!(uses_v2.last_refill_slot, 60);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_uses_require_uses_v2() {
+        let mut context = program_test().start_with_context().await;
+        let test_meta = create_rate_limited(&mut context).await;
+        let payer = context.payer.pubkey();
+
+        let use_ix = UseV2Builder::new()
+            .authority(payer)
+            .token(test_meta.token.pubkey())
+            .mint(test_meta.mint.pubkey())
+            .metadata(test_meta.pubkey)
+            .payer(payer)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let err = process_at_current_slot(&mut context, &use_ix)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::MissingUsesV2Account);
+
+        // the legacy instruction would bypass the rate limit
+
+        let utilize_ix = mpl_token_metadata::instruction::utilize(
+            mpl_token_metadata::id(),
+            test_meta.pubkey,
+            test_meta.token.pubkey(),
+            test_meta.mint.pubkey(),
+            None,
+            payer,
+            payer,
+            None,
+            1,
+        );
+
+        let err = process_at_current_slot(&mut context, &utilize_ix)
+            .await
+            .unwrap_err();
+        assert_custom_error!(err, MetadataError::RateLimitedUses);
+    }
 }
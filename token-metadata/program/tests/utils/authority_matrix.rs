@@ -0,0 +1,290 @@
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{BurnBuilder, UpdateBuilder},
+        BurnArgs, DelegateArgs, InstructionBuilder, MetadataDelegateRole, TransferArgs, UpdateArgs,
+    },
+    pda::find_metadata_delegate_record_account,
+    state::TokenStandard,
+};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use super::{airdrop, DigitalAsset, DirtyClone, TransferFromParams};
+
+/// Authority signing the operation of a matrix case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixAuthority {
+    /// Update authority and holder of the asset.
+    Owner,
+    /// SPL token delegate (`Standard` role); only for non-programmable assets.
+    StandardDelegate,
+    /// Token delegate with the `Transfer` role; only for programmable assets.
+    TransferDelegate,
+    /// Token delegate with the `Utility` role; only for programmable assets.
+    UtilityDelegate,
+    /// Metadata delegate with the `Data` role.
+    DataDelegate,
+    /// Signer without any relationship to the asset.
+    Stranger,
+}
+
+/// Operation performed by a matrix case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixOperation {
+    /// `Update` changing the name of the asset.
+    Update,
+    /// `Transfer` of the token to a new wallet.
+    Transfer,
+    /// `Burn` of the (master edition) asset.
+    Burn,
+}
+
+/// Expected outcome of a matrix case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatrixOutcome {
+    Success,
+    Failure(MetadataError),
+}
+
+#[derive(Clone, Debug)]
+pub struct MatrixCase {
+    pub token_standard: TokenStandard,
+    pub authority: MatrixAuthority,
+    pub operation: MatrixOperation,
+    pub expected: MatrixOutcome,
+}
+
+const fn case(
+    token_standard: TokenStandard,
+    authority: MatrixAuthority,
+    operation: MatrixOperation,
+    expected: MatrixOutcome,
+) -> MatrixCase {
+    MatrixCase {
+        token_standard,
+        authority,
+        operation,
+        expected,
+    }
+}
+
+use MatrixAuthority::*;
+use MatrixOperation::*;
+use MatrixOutcome::*;
+
+const NFT: TokenStandard = TokenStandard::NonFungible;
+const PNFT: TokenStandard = TokenStandard::ProgrammableNonFungible;
+const INVALID_AUTHORITY: MatrixOutcome = Failure(MetadataError::InvalidAuthorityType);
+const UNAUTHORIZED_UPDATE: MatrixOutcome = Failure(MetadataError::UnauthorizedUpdate);
+const UNAUTHORIZED_BURN: MatrixOutcome = Failure(MetadataError::UnauthorizedBurn);
+
+/// Expected outcome of every combination of token standard, authority and operation.
+#[rustfmt::skip]
+pub const AUTHORITY_MATRIX: &[MatrixCase] = &[
+    case(NFT,  Owner,            Update,   Success),
+    case(NFT,  Owner,            Transfer, Success),
+    case(NFT,  Owner,            Burn,     Success),
+    case(NFT,  StandardDelegate, Update,   UNAUTHORIZED_UPDATE),
+    case(NFT,  StandardDelegate, Transfer, Success),
+    case(NFT,  StandardDelegate, Burn,     UNAUTHORIZED_BURN),
+    case(NFT,  DataDelegate,     Update,   Success),
+    case(NFT,  DataDelegate,     Transfer, INVALID_AUTHORITY),
+    case(NFT,  DataDelegate,     Burn,     UNAUTHORIZED_BURN),
+    case(NFT,  Stranger,         Update,   UNAUTHORIZED_UPDATE),
+    case(NFT,  Stranger,         Transfer, INVALID_AUTHORITY),
+    case(NFT,  Stranger,         Burn,     UNAUTHORIZED_BURN),
+    case(PNFT, Owner,            Update,   Success),
+    case(PNFT, Owner,            Transfer, Success),
+    case(PNFT, Owner,            Burn,     Success),
+    case(PNFT, TransferDelegate, Update,   UNAUTHORIZED_UPDATE),
+    case(PNFT, TransferDelegate, Transfer, Success),
+    case(PNFT, TransferDelegate, Burn,     UNAUTHORIZED_BURN),
+    case(PNFT, UtilityDelegate,  Update,   UNAUTHORIZED_UPDATE),
+    case(PNFT, UtilityDelegate,  Transfer, INVALID_AUTHORITY),
+    case(PNFT, UtilityDelegate,  Burn,     UNAUTHORIZED_BURN),
+    case(PNFT, DataDelegate,     Update,   Success),
+    case(PNFT, DataDelegate,     Transfer, INVALID_AUTHORITY),
+    case(PNFT, DataDelegate,     Burn,     UNAUTHORIZED_BURN),
+    case(PNFT, Stranger,         Update,   UNAUTHORIZED_UPDATE),
+    case(PNFT, Stranger,         Transfer, INVALID_AUTHORITY),
+    case(PNFT, Stranger,         Burn,     UNAUTHORIZED_BURN),
+];
+
+impl MatrixCase {
+    /// Creates the asset and the authority of the case, then executes the operation
+    /// returning its result.
+    pub async fn run(&self, context: &mut ProgramTestContext) -> Result<(), BanksClientError> {
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, self.token_standard, None, None, 1)
+            .await
+            .unwrap();
+
+        let owner = context.payer.dirty_clone();
+
+        let authority = match self.authority {
+            Owner => owner.dirty_clone(),
+            _ => {
+                let authority = Keypair::new();
+                airdrop(context, &authority.pubkey(), 1_000_000_000)
+                    .await
+                    .unwrap();
+                authority
+            }
+        };
+
+        let delegate_args = match self.authority {
+            StandardDelegate => Some(DelegateArgs::StandardV1 { amount: 1 }),
+            TransferDelegate => Some(DelegateArgs::TransferV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            }),
+            UtilityDelegate => Some(DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+                expiry: None,
+            }),
+            DataDelegate => Some(DelegateArgs::DataV1 {
+                authorization_data: None,
+                expiry: None,
+            }),
+            Owner | Stranger => None,
+        };
+
+        if let Some(args) = delegate_args {
+            asset
+                .delegate(context, owner.dirty_clone(), authority.pubkey(), args)
+                .await
+                .unwrap();
+        }
+
+        match self.operation {
+            Update => self.update(context, &asset, &owner, &authority).await,
+            Transfer => {
+                asset
+                    .transfer_from(TransferFromParams {
+                        context,
+                        authority: &authority,
+                        source_owner: &owner.pubkey(),
+                        destination_owner: Keypair::new().pubkey(),
+                        destination_token: None,
+                        payer: &authority,
+                        authorization_rules: None,
+                        args: TransferArgs::V1 {
+                            amount: 1,
+                            authorization_data: None,
+                        },
+                    })
+                    .await
+            }
+            Burn => {
+                let mut builder = BurnBuilder::new();
+                builder
+                    .authority(authority.pubkey())
+                    .metadata(asset.metadata)
+                    .mint(asset.mint.pubkey())
+                    .token_account(asset.token.unwrap())
+                    .edition(asset.master_edition.unwrap());
+
+                if let Some(token_record) = asset.token_record {
+                    builder.token_record(token_record);
+                }
+
+                let burn_ix = builder
+                    .build(BurnArgs::V1 {
+                        authorization_data: None,
+                    })
+                    .unwrap()
+                    .instruction();
+
+                let tx = Transaction::new_signed_with_payer(
+                    &[burn_ix],
+                    Some(&authority.pubkey()),
+                    &[&authority],
+                    context.last_blockhash,
+                );
+
+                context.banks_client.process_transaction(tx).await
+            }
+        }
+    }
+
+    async fn update(
+        &self,
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        owner: &Keypair,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mut data = asset.get_metadata(context).await.data;
+        data.name = String::from("Updated");
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { data: new_data, .. } = &mut update_args;
+        *new_data = Some(data);
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .metadata(asset.metadata)
+            .payer(authority.pubkey())
+            .mint(asset.mint.pubkey())
+            .token(asset.token.unwrap());
+
+        if let Some(master_edition) = asset.master_edition {
+            builder.edition(master_edition);
+        }
+
+        if self.authority == DataDelegate {
+            let (delegate_record, _) = find_metadata_delegate_record_account(
+                &asset.mint.pubkey(),
+                MetadataDelegateRole::Data,
+                &owner.pubkey(),
+                &authority.pubkey(),
+            );
+            builder.delegate_record(delegate_record);
+        }
+
+        let update_ix = builder.build(update_args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Compares the result of the operation with the expected outcome, returning
+    /// a description of the mismatch.
+    pub fn check(&self, result: Result<(), BanksClientError>) -> Option<String> {
+        let matches = match (&self.expected, &result) {
+            (Success, Ok(())) => true,
+            (
+                Failure(error),
+                Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+                    _,
+                    InstructionError::Custom(code),
+                ))),
+            ) => *code == error.clone() as u32,
+            _ => false,
+        };
+
+        if matches {
+            None
+        } else {
+            Some(format!(
+                "{:?} / {:?} / {:?}: expected {:?}, got {:?}",
+                self.token_standard, self.authority, self.operation, self.expected, result
+            ))
+        }
+    }
+}
@@ -2,20 +2,36 @@ use mpl_token_metadata::{
     id,
     instruction::{
         builders::{
-            CreateBuilder, DelegateBuilder, LockBuilder, MigrateBuilder, MintBuilder,
-            RevokeBuilder, TransferBuilder, UnlockBuilder, UpdateBuilder,
+            BatchUpdateBuilder, CommitRuleSetBuilder, CreateBuilder, DelegateAndLockBuilder,
+            DelegateBuilder, LockBuilder, MigrateBuilder, MintBuilder, PauseCollectionBuilder,
+            PrintBuilder, ProposeRuleSetBuilder, RevokeBuilder, SetLegacyLayoutBuilder,
+            SetPrintWindowBuilder, SyncLayoutBuilder, TransferBuilder, UnlockAndRevokeBuilder,
+            UnlockBuilder, UnpauseCollectionBuilder, UpdateBuilder, VerifyV2Builder,
         },
-        CreateArgs, DelegateArgs, InstructionBuilder, LockArgs, MetadataDelegateRole, MigrateArgs,
-        MintArgs, RevokeArgs, TransferArgs, UnlockArgs, UpdateArgs,
+        CollectionDetailsToggle, CollectionToggle, CommitRuleSetArgs, CreateArgs, DelegateArgs,
+        InstructionBuilder, LockArgs, MetadataDelegateRole, MigrateArgs, MintArgs,
+        PauseCollectionArgs, PrintArgs, ProposeRuleSetArgs, RevokeArgs, SetLegacyLayoutArgs,
+        SetPrintWindowArgs, SyncLayoutArgs, TransferArgs, UnlockArgs, UnpauseCollectionArgs,
+        UpdateArgs, VerificationArgs,
+    },
+    pda::{
+        find_collection_items_delegate_record_account, find_collection_pause_account,
+        find_default_rule_sets_account, find_delegate_registry_account, find_denylisted_account,
+        find_edition_account, find_legacy_layout_account, find_master_edition_account,
+        find_master_edition_extension_account, find_metadata_account,
+        find_metadata_delegate_record_account, find_programmable_config_extension_account,
+        find_rule_set_proposal_account, find_token_record_account,
     },
-    pda::{find_metadata_delegate_record_account, find_token_record_account},
     processor::AuthorizationData,
     state::{
-        AssetData, Creator, Metadata, PrintSupply, ProgrammableConfig, TokenDelegateRole,
-        TokenMetadataAccount, TokenRecord, TokenStandard, EDITION, PREFIX,
+        AssetData, AssetDataConfig, BasisPoints, Collection, CollectionDetails, Creator, Metadata,
+        PrintSupply, ProgrammableConfig, TokenDelegateRole, TokenMetadataAccount, TokenRecord,
+        TokenStandard, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX,
     },
 };
-use solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey};
+use solana_program::{
+    borsh::try_from_slice_unchecked, instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey,
+};
 use solana_program_test::{BanksClientError, ProgramTestContext};
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
@@ -26,7 +42,7 @@ use spl_associated_token_account::{
     get_associated_token_address, instruction::create_associated_token_account,
 };
 
-use super::get_account;
+use super::{create_mint, get_account, DirtyClone};
 
 pub const DEFAULT_NAME: &str = "Digital Asset";
 pub const DEFAULT_SYMBOL: &str = "DA";
@@ -70,21 +86,36 @@ impl DigitalAsset {
         token_standard: TokenStandard,
         authorization_rules: Option<Pubkey>,
     ) -> Result<(), BanksClientError> {
-        let mut asset = AssetData::new(
+        self.create_with_print_supply(
+            context,
             token_standard,
-            String::from(DEFAULT_NAME),
-            String::from(DEFAULT_SYMBOL),
-            String::from(DEFAULT_URI),
-        );
-        asset.seller_fee_basis_points = 500;
+            authorization_rules,
+            PrintSupply::Zero,
+        )
+        .await
+    }
 
-        let creators = vec![Creator {
-            address: context.payer.pubkey(),
-            share: 100,
-            verified: true,
-        }];
-        asset.creators = Some(creators);
-        asset.rule_set = authorization_rules;
+    pub async fn create_with_print_supply(
+        &mut self,
+        context: &mut ProgramTestContext,
+        token_standard: TokenStandard,
+        authorization_rules: Option<Pubkey>,
+        print_supply: PrintSupply,
+    ) -> Result<(), BanksClientError> {
+        let asset = AssetData::from_config(AssetDataConfig {
+            name: String::from(DEFAULT_NAME),
+            symbol: String::from(DEFAULT_SYMBOL),
+            uri: String::from(DEFAULT_URI),
+            seller_fee_basis_points: BasisPoints::new(500).unwrap(),
+            creators: Some(vec![Creator {
+                address: context.payer.pubkey(),
+                share: 100,
+                verified: true,
+            }]),
+            token_standard,
+            rule_set: authorization_rules,
+            ..Default::default()
+        });
 
         let payer_pubkey = context.payer.pubkey();
         let mint_pubkey = self.mint.pubkey();
@@ -101,7 +132,9 @@ impl DigitalAsset {
             .update_authority_as_signer(true);
 
         let master_edition = match token_standard {
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible => {
+            TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::NonTransferable => {
                 // master edition PDA address
                 let master_edition_seeds = &[
                     PREFIX.as_bytes(),
@@ -121,7 +154,7 @@ impl DigitalAsset {
             .build(CreateArgs::V1 {
                 asset_data: asset,
                 decimals: Some(0),
-                print_supply: Some(PrintSupply::Zero),
+                print_supply: Some(print_supply),
             })
             .unwrap()
             .instruction();
@@ -257,7 +290,9 @@ impl DigitalAsset {
             | DelegateArgs::TransferV1 { .. }
             | DelegateArgs::UtilityV1 { .. }
             | DelegateArgs::StakingV1 { .. }
-            | DelegateArgs::LockedTransferV1 { .. } => {
+            | DelegateArgs::LockedTransferV1 { .. }
+            | DelegateArgs::EscrowV1 { .. }
+            | DelegateArgs::RentalV1 { .. } => {
                 let (token_record, _) =
                     find_token_record_account(&self.mint.pubkey(), &self.token.unwrap());
                 builder.token_record(token_record);
@@ -280,9 +315,67 @@ impl DigitalAsset {
                 );
                 builder.delegate_record(delegate_record);
             }
+            DelegateArgs::DataV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Data,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            DelegateArgs::UsesV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Uses,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            DelegateArgs::CollectionItemsV1 { .. } => {
+                let (delegate_record, _) = find_collection_items_delegate_record_account(
+                    &self.mint.pubkey(),
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            DelegateArgs::UseV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Use,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            DelegateArgs::CollectionFreezeV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::CollectionFreeze,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
             DelegateArgs::StandardV1 { .. } => { /* nothing to add */ }
         }
 
+        // metadata delegates are listed on the delegate registry
+        if matches!(
+            args,
+            DelegateArgs::CollectionV1 { .. }
+                | DelegateArgs::UpdateV1 { .. }
+                | DelegateArgs::ProgrammableConfigV1 { .. }
+                | DelegateArgs::DataV1 { .. }
+                | DelegateArgs::UsesV1 { .. }
+                | DelegateArgs::UseV1 { .. }
+                | DelegateArgs::CollectionFreezeV1 { .. }
+        ) {
+            builder.delegate_registry(find_delegate_registry_account(&self.mint.pubkey()).0);
+        }
+
         if let Some(edition) = self.master_edition {
             builder.master_edition(edition);
         }
@@ -295,6 +388,10 @@ impl DigitalAsset {
         let metadata_account = get_account(context, &self.metadata).await;
         let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
 
+        if let Some(collection_pause) = collection_pause_account(&metadata) {
+            builder.collection_pause(collection_pause);
+        }
+
         if let Some(ProgrammableConfig::V1 {
             rule_set: Some(rule_set),
         }) = metadata.programmable_config
@@ -317,6 +414,64 @@ impl DigitalAsset {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn delegate_and_lock(
+        &mut self,
+        context: &mut ProgramTestContext,
+        payer: Keypair,
+        delegate: Pubkey,
+        args: DelegateArgs,
+    ) -> Result<(), BanksClientError> {
+        let mut builder = DelegateAndLockBuilder::new();
+        builder
+            .delegate(delegate)
+            .mint(self.mint.pubkey())
+            .metadata(self.metadata)
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token::ID);
+
+        if let Some(token_record) = self.token_record {
+            builder.token_record(token_record);
+        }
+
+        if let Some(edition) = self.master_edition {
+            builder.master_edition(edition);
+        }
+
+        if let Some(token) = self.token {
+            builder.token(token);
+        }
+
+        // determines if we need to set the rule set
+        let metadata_account = get_account(context, &self.metadata).await;
+        let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+        if let Some(collection_pause) = collection_pause_account(&metadata) {
+            builder.collection_pause(collection_pause);
+        }
+
+        if let Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) = metadata.programmable_config
+        {
+            builder.authorization_rules(rule_set);
+            builder.authorization_rules_program(mpl_token_auth_rules::ID);
+        }
+
+        let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+        let delegate_ix = builder.build(args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[compute_ix, delegate_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn migrate(
         &mut self,
         context: &mut ProgramTestContext,
@@ -378,7 +533,9 @@ impl DigitalAsset {
             | RevokeArgs::UtilityV1
             | RevokeArgs::StakingV1
             | RevokeArgs::LockedTransferV1
-            | RevokeArgs::MigrationV1 => {
+            | RevokeArgs::MigrationV1
+            | RevokeArgs::EscrowV1
+            | RevokeArgs::RentalV1 => {
                 let (token_record, _) =
                     find_token_record_account(&self.mint.pubkey(), &self.token.unwrap());
                 builder.token_record(token_record);
@@ -401,9 +558,73 @@ impl DigitalAsset {
                 );
                 builder.delegate_record(delegate_record);
             }
+            RevokeArgs::DataV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Data,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            RevokeArgs::UsesV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Uses,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            RevokeArgs::AllV1 => {
+                if let Some(token_record) = self.token_record {
+                    builder.token_record(token_record);
+                }
+            }
+            RevokeArgs::CollectionItemsV1 => {
+                let (delegate_record, _) = find_collection_items_delegate_record_account(
+                    &self.mint.pubkey(),
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            RevokeArgs::UseV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Use,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+            RevokeArgs::CollectionFreezeV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::CollectionFreeze,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
             RevokeArgs::StandardV1 { .. } => { /* nothing to add */ }
         }
 
+        // metadata delegates are listed on the delegate registry
+        if matches!(
+            args,
+            RevokeArgs::CollectionV1
+                | RevokeArgs::UpdateV1
+                | RevokeArgs::ProgrammableConfigV1
+                | RevokeArgs::DataV1
+                | RevokeArgs::UsesV1
+                | RevokeArgs::UseV1
+                | RevokeArgs::CollectionFreezeV1
+                | RevokeArgs::AllV1
+        ) {
+            builder.delegate_registry(find_delegate_registry_account(&self.mint.pubkey()).0);
+        }
+
         if let Some(edition) = self.master_edition {
             builder.master_edition(edition);
         }
@@ -464,7 +685,8 @@ impl DigitalAsset {
             .destination(destination_token)
             .metadata(self.metadata)
             .payer(payer.pubkey())
-            .mint(self.mint.pubkey());
+            .mint(self.mint.pubkey())
+            .default_rule_sets(find_default_rule_sets_account().0);
 
         if let Some(record) = self.token_record {
             builder.owner_token_record(record);
@@ -484,6 +706,18 @@ impl DigitalAsset {
             builder.authorization_rules_program(mpl_token_auth_rules::ID);
         }
 
+        let metadata = self.get_metadata(context).await;
+
+        if let Some(collection_pause) = collection_pause_account(&metadata) {
+            builder.collection_pause(collection_pause);
+        }
+
+        if let Some(collection_metadata) = collection_metadata_account(&metadata) {
+            builder
+                .collection_metadata(collection_metadata)
+                .denylisted(find_denylisted_account(&self.mint.pubkey()).0);
+        }
+
         let transfer_ix = builder.build(args).unwrap().instruction();
 
         instructions.push(transfer_ix);
@@ -586,6 +820,197 @@ impl DigitalAsset {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn unlock_and_revoke(
+        &mut self,
+        context: &mut ProgramTestContext,
+        delegate: Keypair,
+        payer: Keypair,
+        args: RevokeArgs,
+    ) -> Result<(), BanksClientError> {
+        let mut builder = UnlockAndRevokeBuilder::new();
+        builder
+            .delegate(delegate.pubkey())
+            .mint(self.mint.pubkey())
+            .metadata(self.metadata)
+            .payer(payer.pubkey())
+            .authority(payer.pubkey())
+            .spl_token_program(spl_token::ID);
+
+        if let Some(token_record) = self.token_record {
+            builder.token_record(token_record);
+        }
+
+        if let Some(edition) = self.master_edition {
+            builder.master_edition(edition);
+        }
+
+        if let Some(token) = self.token {
+            builder.token(token);
+        }
+
+        let unlock_ix = builder.build(args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[unlock_ix],
+            Some(&payer.pubkey()),
+            &[&delegate, &payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Mints each of the `amounts` to the associated token account of the matching owner.
+    pub async fn mint_batch(
+        &mut self,
+        context: &mut ProgramTestContext,
+        owners: &[Pubkey],
+        amounts: Vec<u64>,
+    ) -> Result<(), BanksClientError> {
+        let payer_pubkey = context.payer.pubkey();
+        let tokens = owners
+            .iter()
+            .map(|owner| get_associated_token_address(owner, &self.mint.pubkey()))
+            .collect::<Vec<Pubkey>>();
+
+        let mut builder = MintBuilder::new();
+        builder
+            .token(tokens[0])
+            .token_owner(owners[0])
+            .metadata(self.metadata)
+            .mint(self.mint.pubkey())
+            .payer(payer_pubkey)
+            .authority(payer_pubkey);
+
+        let mut mint_ix = builder
+            .build(MintArgs::BatchV1 { amounts })
+            .unwrap()
+            .instruction();
+
+        for (token, owner) in tokens.iter().zip(owners.iter()).skip(1) {
+            mint_ix.accounts.push(AccountMeta::new(*token, false));
+            mint_ix
+                .accounts
+                .push(AccountMeta::new_readonly(*owner, false));
+        }
+
+        let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(800_000);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[compute_ix, mint_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+        self.token = Some(tokens[0]);
+
+        Ok(())
+    }
+
+    pub async fn set_print_window(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        print_window: Option<(i64, i64)>,
+    ) -> Result<(), BanksClientError> {
+        let (extension, _) = find_master_edition_extension_account(&self.mint.pubkey());
+
+        let ix = SetPrintWindowBuilder::new()
+            .master_edition_extension(extension)
+            .master_edition(self.master_edition.unwrap())
+            .mint(self.mint.pubkey())
+            .metadata(self.metadata)
+            .authority(authority.pubkey())
+            .payer(context.payer.pubkey())
+            .build(SetPrintWindowArgs::V1 { print_window })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Prints the edition `edition` of the master edition to the payer, which must hold the
+    /// master edition token. Returns the printed asset.
+    pub async fn print_edition(
+        &self,
+        context: &mut ProgramTestContext,
+        edition: u64,
+    ) -> Result<DigitalAsset, BanksClientError> {
+        let payer_pubkey = context.payer.pubkey();
+        let master_mint = self.mint.pubkey();
+
+        let mut print = DigitalAsset::new();
+        let edition_mint = print.mint.pubkey();
+        create_mint(context, &print.mint, &payer_pubkey, Some(&payer_pubkey), 0).await?;
+
+        let edition_token = get_associated_token_address(&payer_pubkey, &edition_mint);
+        let (edition_account, _) = find_master_edition_account(&edition_mint);
+
+        let mut builder = PrintBuilder::new();
+        builder
+            .edition_metadata(print.metadata)
+            .edition(edition_account)
+            .edition_mint(edition_mint)
+            .edition_token_account_owner(payer_pubkey)
+            .edition_token_account(edition_token)
+            .edition_mint_authority(payer_pubkey)
+            .master_edition(self.master_edition.unwrap())
+            .edition_marker_pda(
+                find_edition_account(
+                    &master_mint,
+                    (edition / EDITION_MARKER_BIT_SIZE).to_string(),
+                )
+                .0,
+            )
+            .payer(payer_pubkey)
+            .master_token_account_owner(payer_pubkey)
+            .master_token_account(self.token.unwrap())
+            .master_metadata(self.metadata)
+            .update_authority(payer_pubkey)
+            .master_edition_extension(find_master_edition_extension_account(&master_mint).0);
+
+        let is_pnft = self.is_pnft(context).await;
+
+        if is_pnft {
+            builder
+                .edition_token_record(find_token_record_account(&edition_mint, &edition_token).0);
+        }
+
+        let print_ix = builder
+            .build(PrintArgs::V1 { edition })
+            .unwrap()
+            .instruction();
+
+        let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(800_000);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[compute_ix, print_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+
+        print.token = Some(edition_token);
+        print.master_edition = Some(edition_account);
+
+        if is_pnft {
+            print.token_record = Some(find_token_record_account(&edition_mint, &edition_token).0);
+        }
+
+        Ok(print)
+    }
+
     pub async fn transfer_to(&self, params: TransferToParams<'_>) -> Result<(), BanksClientError> {
         let TransferToParams {
             context,
@@ -625,7 +1050,8 @@ impl DigitalAsset {
             .destination(destination_token)
             .metadata(self.metadata)
             .payer(payer.pubkey())
-            .mint(self.mint.pubkey());
+            .mint(self.mint.pubkey())
+            .default_rule_sets(find_default_rule_sets_account().0);
 
         // This can be optional for non pNFTs but always include it for now.
         let (owner_token_record, _bump) =
@@ -646,6 +1072,18 @@ impl DigitalAsset {
             builder.authorization_rules_program(mpl_token_auth_rules::ID);
         }
 
+        let metadata = self.get_metadata(context).await;
+
+        if let Some(collection_pause) = collection_pause_account(&metadata) {
+            builder.collection_pause(collection_pause);
+        }
+
+        if let Some(collection_metadata) = collection_metadata_account(&metadata) {
+            builder
+                .collection_metadata(collection_metadata)
+                .denylisted(find_denylisted_account(&self.mint.pubkey()).0);
+        }
+
         let transfer_ix = builder.build(args).unwrap().instruction();
 
         instructions.push(transfer_ix);
@@ -660,6 +1098,114 @@ impl DigitalAsset {
         context.banks_client.process_transaction(tx).await
     }
 
+    /// Transfers all tokens of the asset from `source_owner` to the associated token account
+    /// of `destination_owner`, which is created if needed. Fees and rent are paid by the
+    /// context payer so neither wallet needs to be funded.
+    pub async fn transfer_between(
+        &mut self,
+        context: &mut ProgramTestContext,
+        source_owner: &Keypair,
+        destination_owner: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mint = self.mint.pubkey();
+        let source_token = get_associated_token_address(&source_owner.pubkey(), &mint);
+        let destination_token = get_associated_token_address(&destination_owner.pubkey(), &mint);
+
+        let account = get_account(context, &source_token).await;
+        let amount = spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount;
+
+        let payer = context.payer.dirty_clone();
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(400_000)];
+
+        if context
+            .banks_client
+            .get_account(destination_token)
+            .await?
+            .is_none()
+        {
+            instructions.push(create_associated_token_account(
+                &payer.pubkey(),
+                &destination_owner.pubkey(),
+                &mint,
+                &spl_token::id(),
+            ));
+        }
+
+        let is_pnft = self.is_pnft(context).await;
+        let (destination_token_record, _) = find_token_record_account(&mint, &destination_token);
+
+        let mut builder = TransferBuilder::new();
+        builder
+            .authority(source_owner.pubkey())
+            .token_owner(source_owner.pubkey())
+            .token(source_token)
+            .destination_owner(destination_owner.pubkey())
+            .destination(destination_token)
+            .metadata(self.metadata)
+            .payer(payer.pubkey())
+            .mint(mint)
+            .default_rule_sets(find_default_rule_sets_account().0);
+
+        if is_pnft {
+            builder
+                .owner_token_record(find_token_record_account(&mint, &source_token).0)
+                .destination_token_record(destination_token_record);
+        }
+
+        if let Some(master_edition) = self.master_edition {
+            builder.edition(master_edition);
+        }
+
+        let metadata = self.get_metadata(context).await;
+
+        if let Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) = metadata.programmable_config
+        {
+            builder.authorization_rules(rule_set);
+            builder.authorization_rules_program(mpl_token_auth_rules::ID);
+        }
+
+        if let Some(collection_pause) = collection_pause_account(&metadata) {
+            builder.collection_pause(collection_pause);
+        }
+
+        if let Some(collection_metadata) = collection_metadata_account(&metadata) {
+            builder
+                .collection_metadata(collection_metadata)
+                .denylisted(find_denylisted_account(&self.mint.pubkey()).0);
+        }
+
+        instructions.push(
+            builder
+                .build(TransferArgs::V1 {
+                    authorization_data: None,
+                    amount,
+                })
+                .unwrap()
+                .instruction(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, source_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+
+        self.token = Some(destination_token);
+
+        if is_pnft {
+            self.token_record = Some(destination_token_record);
+        }
+
+        Ok(())
+    }
+
     pub async fn update(
         &self,
         context: &mut ProgramTestContext,
@@ -689,6 +1235,272 @@ impl DigitalAsset {
         context.banks_client.process_transaction(tx).await
     }
 
+    /// Turns the asset into a sized collection parent. The asset must be created with a
+    /// master edition.
+    pub async fn into_collection_parent(
+        &self,
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V1 { size: 0 });
+
+        self.update(context, update_authority.dirty_clone(), update_args)
+            .await
+    }
+
+    /// Sets the collection of the asset to `parent` and verifies it. The update authority of
+    /// the parent must also be the update authority of the asset.
+    pub async fn add_to_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        parent: &DigitalAsset,
+        update_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: parent.mint.pubkey(),
+            verified: false,
+        });
+
+        self.update(context, update_authority.dirty_clone(), update_args)
+            .await?;
+
+        let mut builder = VerifyV2Builder::new();
+        builder
+            .authority(update_authority.pubkey())
+            .metadata(self.metadata)
+            .collection_mint(parent.mint.pubkey())
+            .collection_metadata(parent.metadata)
+            .collection_master_edition(parent.master_edition.unwrap());
+
+        let verify_ix = builder
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&update_authority.pubkey()),
+            &[update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn propose_rule_set(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        rule_set: Pubkey,
+        delay: Option<i64>,
+    ) -> Result<(), BanksClientError> {
+        let (rule_set_proposal, _) = find_rule_set_proposal_account(&self.mint.pubkey());
+
+        let propose_ix = ProposeRuleSetBuilder::new()
+            .rule_set_proposal(rule_set_proposal)
+            .mint(self.mint.pubkey())
+            .metadata(self.metadata)
+            .authority(authority.pubkey())
+            .payer(authority.pubkey())
+            .build(ProposeRuleSetArgs::V1 { rule_set, delay })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[propose_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn commit_rule_set(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (rule_set_proposal, _) = find_rule_set_proposal_account(&self.mint.pubkey());
+
+        let commit_ix = CommitRuleSetBuilder::new()
+            .rule_set_proposal(rule_set_proposal)
+            .mint(self.mint.pubkey())
+            .metadata(self.metadata)
+            .token(self.token.unwrap())
+            .authority(authority.pubkey())
+            .build(CommitRuleSetArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[commit_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Pauses the verified items of the collection, using the asset as the collection.
+    pub async fn pause_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        duration: i64,
+    ) -> Result<(), BanksClientError> {
+        let (collection_pause, _) = find_collection_pause_account(&self.mint.pubkey());
+
+        let pause_ix = PauseCollectionBuilder::new()
+            .collection_pause(collection_pause)
+            .collection_mint(self.mint.pubkey())
+            .collection_metadata(self.metadata)
+            .authority(authority.pubkey())
+            .payer(authority.pubkey())
+            .build(PauseCollectionArgs::V1 { duration })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[pause_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Lifts the pause of the collection, using the asset as the collection.
+    pub async fn unpause_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (collection_pause, _) = find_collection_pause_account(&self.mint.pubkey());
+
+        let unpause_ix = UnpauseCollectionBuilder::new()
+            .collection_pause(collection_pause)
+            .collection_mint(self.mint.pubkey())
+            .collection_metadata(self.metadata)
+            .authority(authority.pubkey())
+            .build(UnpauseCollectionArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[unpause_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Sets whether the items of the collection use the legacy layout, using the asset as
+    /// the collection.
+    pub async fn set_legacy_layout(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        enabled: bool,
+    ) -> Result<(), BanksClientError> {
+        let (legacy_layout, _) = find_legacy_layout_account(&self.mint.pubkey());
+
+        let set_ix = SetLegacyLayoutBuilder::new()
+            .legacy_layout(legacy_layout)
+            .collection_mint(self.mint.pubkey())
+            .collection_metadata(self.metadata)
+            .authority(authority.pubkey())
+            .payer(authority.pubkey())
+            .build(SetLegacyLayoutArgs::V1 { enabled })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Syncs the layout of the asset with the layout of its `collection`.
+    pub async fn sync_layout(
+        &self,
+        context: &mut ProgramTestContext,
+        collection: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (programmable_config_extension, _) =
+            find_programmable_config_extension_account(&self.mint.pubkey());
+        let (legacy_layout, _) = find_legacy_layout_account(collection);
+
+        let sync_ix = SyncLayoutBuilder::new()
+            .metadata(self.metadata)
+            .programmable_config_extension(programmable_config_extension)
+            .legacy_layout(legacy_layout)
+            .payer(context.payer.pubkey())
+            .build(SyncLayoutArgs::V1)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[sync_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Applies the update to the metadata of the `items`, using the asset as the collection.
+    pub async fn batch_update(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        delegate_record: Option<Pubkey>,
+        items: &[Pubkey],
+        update_args: UpdateArgs,
+    ) -> Result<(), BanksClientError> {
+        let mut builder = BatchUpdateBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .collection_mint(self.mint.pubkey())
+            .collection_metadata(self.metadata);
+
+        if let Some(delegate_record) = delegate_record {
+            builder.delegate_record(delegate_record);
+        }
+
+        let mut batch_update_ix = builder.build(update_args).unwrap().instruction();
+
+        for item in items {
+            batch_update_ix
+                .accounts
+                .push(AccountMeta::new(*item, false));
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[batch_update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn get_metadata(&self, context: &mut ProgramTestContext) -> Metadata {
         let metadata_account = context
             .banks_client
@@ -748,6 +1560,28 @@ impl DigitalAsset {
     }
 }
 
+/// Returns the collection metadata account required by verified collection items.
+fn collection_metadata_account(metadata: &Metadata) -> Option<Pubkey> {
+    match &metadata.collection {
+        Some(Collection {
+            key,
+            verified: true,
+        }) => Some(find_metadata_account(key).0),
+        _ => None,
+    }
+}
+
+/// Returns the collection pause account required by verified collection items.
+fn collection_pause_account(metadata: &Metadata) -> Option<Pubkey> {
+    match &metadata.collection {
+        Some(Collection {
+            key,
+            verified: true,
+        }) => Some(find_collection_pause_account(key).0),
+        _ => None,
+    }
+}
+
 pub struct TransferFromParams<'a> {
     pub context: &'a mut ProgramTestContext,
     pub authority: &'a Keypair,
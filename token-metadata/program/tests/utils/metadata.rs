@@ -1,7 +1,7 @@
 use mpl_token_metadata::{
     id, instruction,
     state::{
-        Collection, CollectionDetails, Creator, Data, DataV2, Metadata as TmMetadata,
+        BasisPoints, Collection, CollectionDetails, Creator, Data, DataV2, Metadata as TmMetadata,
         TokenMetadataAccount, TokenStandard, Uses, PREFIX,
     },
 };
@@ -558,7 +558,7 @@ impl Metadata {
                     symbol,
                     uri,
                     creators,
-                    seller_fee_basis_points,
+                    seller_fee_basis_points: BasisPoints::new(seller_fee_basis_points).unwrap(),
                 }),
                 None,
             )],
@@ -593,7 +593,7 @@ impl Metadata {
                     symbol,
                     uri,
                     creators,
-                    seller_fee_basis_points,
+                    seller_fee_basis_points: BasisPoints::new(seller_fee_basis_points).unwrap(),
                     collection,
                     uses,
                 }),
@@ -820,6 +820,9 @@ pub async fn assert_collection_size(
     let retrieved_size = if let Some(details) = collection_md.collection_details {
         match details {
             CollectionDetails::V1 { size } => size,
+            CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                panic!("Expected CollectionDetails::V1")
+            }
         }
     } else {
         panic!("Expected CollectionDetails::V1");
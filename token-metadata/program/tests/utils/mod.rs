@@ -1,4 +1,5 @@
 mod assert;
+mod authority_matrix;
 mod digital_asset;
 mod edition_marker;
 mod master_edition_v2;
@@ -7,6 +8,7 @@ mod programmable;
 mod rooster_manager;
 
 pub use assert::*;
+pub use authority_matrix::*;
 pub use digital_asset::*;
 pub use edition_marker::EditionMarker;
 pub use master_edition_v2::MasterEditionV2;
@@ -17,7 +19,7 @@ pub use programmable::create_default_metaplex_rule_set;
 pub use rooster_manager::*;
 use solana_program_test::*;
 use solana_sdk::{
-    account::Account, program_pack::Pack, pubkey::Pubkey, signature::Signer,
+    account::Account, clock::Clock, program_pack::Pack, pubkey::Pubkey, signature::Signer,
     signer::keypair::Keypair, system_instruction, transaction::Transaction,
 };
 use spl_token::state::Mint;
@@ -38,6 +40,13 @@ pub async fn get_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> A
         .expect("account empty")
 }
 
+/// Moves the clock forward by the specified number of seconds.
+pub async fn warp_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
 pub async fn get_mint(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Mint {
     let account = get_account(context, pubkey).await;
     Mint::unpack(&account.data).unwrap()
@@ -0,0 +1,212 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::instruction::{builders::VerifyV2Builder, InstructionBuilder};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::{DigitalAsset, *};
+
+mod verify {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{CollectionDetailsToggle, CollectionToggle, UpdateArgs, VerificationArgs},
+        state::{Collection, CollectionDetails, Creator, Data, TokenStandard},
+    };
+
+    use super::*;
+
+    async fn verify(
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        asset: &DigitalAsset,
+        collection_parent: Option<&DigitalAsset>,
+        args: VerificationArgs,
+    ) -> Result<(), BanksClientError> {
+        let mut builder = VerifyV2Builder::new();
+        builder
+            .authority(authority.pubkey())
+            .metadata(asset.metadata);
+
+        if let Some(parent) = collection_parent {
+            builder
+                .collection_mint(parent.mint.pubkey())
+                .collection_metadata(parent.metadata)
+                .collection_master_edition(parent.master_edition.unwrap());
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[builder.build(args).unwrap().instruction()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Creates a sized collection and an asset with an unverified collection.
+    async fn create_collection_item(
+        context: &mut ProgramTestContext,
+        update_authority: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection_parent = DigitalAsset::new();
+        collection_parent
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            collection_details, ..
+        } = &mut update_args;
+        *collection_details = CollectionDetailsToggle::Set(CollectionDetails::V1 { size: 0 });
+
+        collection_parent
+            .update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 { collection, .. } = &mut update_args;
+        *collection = CollectionToggle::Set(Collection {
+            key: collection_parent.mint.pubkey(),
+            verified: false,
+        });
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        (collection_parent, da)
+    }
+
+    #[tokio::test]
+    async fn verify_creator() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // adds an unverified creator
+
+        let creator = Keypair::new();
+        let metadata = da.get_metadata(context).await;
+
+        let data = Data {
+            name: metadata.data.name,
+            symbol: metadata.data.symbol,
+            uri: metadata.data.uri,
+            creators: Some(vec![
+                Creator {
+                    address: update_authority.pubkey(),
+                    share: 50,
+                    verified: true,
+                },
+                Creator {
+                    address: creator.pubkey(),
+                    share: 50,
+                    verified: false,
+                },
+            ]),
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        };
+
+        let mut update_args = UpdateArgs::default();
+        let UpdateArgs::V1 {
+            data: current_data, ..
+        } = &mut update_args;
+        *current_data = Some(data);
+
+        da.update(context, update_authority.dirty_clone(), update_args)
+            .await
+            .unwrap();
+
+        verify(context, &creator, &da, None, VerificationArgs::CreatorV1)
+            .await
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert!(metadata.data.creators.unwrap()[1].verified);
+
+        // a creator cannot be verified twice
+
+        context.warp_to_slot(100).unwrap();
+
+        let err = verify(context, &creator, &da, None, VerificationArgs::CreatorV1)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AlreadyVerified);
+    }
+
+    #[tokio::test]
+    async fn verify_sized_collection_item() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_collection_item(context, &update_authority).await;
+
+        verify(
+            context,
+            &update_authority,
+            &da,
+            Some(&collection_parent),
+            VerificationArgs::CollectionV1,
+        )
+        .await
+        .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.collection,
+            Some(Collection {
+                key: collection_parent.mint.pubkey(),
+                verified: true,
+            })
+        );
+
+        let parent = collection_parent.get_metadata(context).await;
+        assert_eq!(
+            parent.collection_details,
+            Some(CollectionDetails::V1 { size: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_verify_collection_with_invalid_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (collection_parent, da) = create_collection_item(context, &update_authority).await;
+
+        let err = verify(
+            context,
+            &Keypair::new(),
+            &da,
+            Some(&collection_parent),
+            VerificationArgs::CollectionV1,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidAuthorityType);
+
+        let metadata = da.get_metadata(context).await;
+        assert!(!metadata.collection.unwrap().verified);
+    }
+}
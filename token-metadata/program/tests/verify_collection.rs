@@ -79,7 +79,7 @@ mod verify_collection {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -181,7 +181,7 @@ mod verify_collection {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -520,7 +520,7 @@ mod verify_collection {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
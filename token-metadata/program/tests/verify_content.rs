@@ -0,0 +1,130 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+use utils::*;
+
+mod verify_content {
+
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction::{builders::VerifyContentBuilder, InstructionBuilder, VerifyContentArgs},
+        state::Creator,
+    };
+    use solana_program::hash::hash;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use super::*;
+
+    async fn verify_content(
+        context: &mut ProgramTestContext,
+        metadata: &Metadata,
+        creator: &Keypair,
+        uri_hash: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let ix = VerifyContentBuilder::new()
+            .metadata(metadata.pubkey)
+            .creator(creator.pubkey())
+            .build(VerifyContentArgs::V1 { uri_hash })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, creator],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn creator_attests_content() {
+        let mut context = program_test().start_with_context().await;
+        let creator = Keypair::new();
+
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                Some(vec![Creator {
+                    address: creator.pubkey(),
+                    verified: false,
+                    share: 100,
+                }]),
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let uri_hash = hash(br#"{"name":"Test"}"#).to_bytes();
+        verify_content(&mut context, &test_metadata, &creator, uri_hash)
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uri_hash, Some(uri_hash));
+
+        // changing the uri clears the attested hash
+        test_metadata
+            .update_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "new uri".to_string(),
+                Some(vec![Creator {
+                    address: creator.pubkey(),
+                    verified: false,
+                    share: 100,
+                }]),
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uri_hash, None);
+    }
+
+    #[tokio::test]
+    async fn non_creator_cannot_attest_content() {
+        let mut context = program_test().start_with_context().await;
+
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let err = verify_content(&mut context, &test_metadata, &Keypair::new(), [1; 32])
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MustBeOneOfCreators);
+    }
+}
@@ -91,7 +91,7 @@ mod verify_sized_collection_item {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -193,7 +193,7 @@ mod verify_sized_collection_item {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -542,7 +542,7 @@ mod verify_sized_collection_item {
         assert_eq!(metadata.data.name, puffed_name);
         assert_eq!(metadata.data.symbol, puffed_symbol);
         assert_eq!(metadata.data.uri, puffed_uri);
-        assert_eq!(metadata.data.seller_fee_basis_points, 10);
+        assert_eq!(metadata.data.seller_fee_basis_points.value(), 10);
         assert_eq!(metadata.data.creators, None);
         assert_eq!(metadata.uses, uses.to_owned());
 
@@ -1234,6 +1234,9 @@ async fn fail_verify_already_verified() {
     let size = if let Some(details) = collection_md.collection_details {
         match details {
             CollectionDetails::V1 { size } => size,
+            CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                panic!("Expected CollectionDetails::V1")
+            }
         }
     } else {
         panic!("Expected CollectionDetails::V1");
@@ -1267,6 +1270,9 @@ async fn fail_verify_already_verified() {
     let size = if let Some(details) = collection_md.collection_details {
         match details {
             CollectionDetails::V1 { size } => size,
+            CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                panic!("Expected CollectionDetails::V1")
+            }
         }
     } else {
         panic!("Expected CollectionDetails::V1");
@@ -1479,6 +1485,9 @@ async fn fail_set_and_verify_already_verified() {
     let size = if let Some(details) = collection_md.collection_details {
         match details {
             CollectionDetails::V1 { size } => size,
+            CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                panic!("Expected CollectionDetails::V1")
+            }
         }
     } else {
         panic!("Expected CollectionDetails::V1");
@@ -1507,6 +1516,9 @@ async fn fail_set_and_verify_already_verified() {
     let size = if let Some(details) = collection_md.collection_details {
         match details {
             CollectionDetails::V1 { size } => size,
+            CollectionDetails::Uncapped | CollectionDetails::V2 { .. } => {
+                panic!("Expected CollectionDetails::V1")
+            }
         }
     } else {
         panic!("Expected CollectionDetails::V1");